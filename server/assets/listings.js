@@ -1,6 +1,9 @@
 (function () {
     let stateWasNull = false;
 
+    // 서버의 `EXPIRING_SOON_SECONDS`와 같은 값
+    const EXPIRING_SOON_SECONDS = 5 * 60;
+
     const state = {
         allowed: [],
         centre: 'All',
@@ -570,13 +573,15 @@
         const lang = state.lang || 'en';
         const now = Math.floor(Date.now() / 1000);
 
-        document.querySelectorAll('.item.expires[data-expires-in]').forEach(elem => {
-            const expiresIn = parseInt(elem.dataset.expiresIn);
+        document.querySelectorAll('.item.expires[data-expires-at]').forEach(elem => {
+            // 서버가 계산한 만료 시각을 그대로 사용 (남은 시간을 다시 추정하지 않음)
+            const expiresAt = parseInt(elem.dataset.expiresAt);
             const textSpan = elem.querySelector('.text');
-            if (textSpan && !isNaN(expiresIn)) {
+            if (textSpan && !isNaN(expiresAt)) {
+                const expiresIn = Math.max(0, expiresAt - now);
                 textSpan.textContent = formatRelativeTime(expiresIn, lang);
+                elem.classList.toggle('expiring-soon', expiresIn < EXPIRING_SOON_SECONDS);
                 // 툴팁: 만료 예정 절대 시간
-                const expiresAt = now + expiresIn;
                 const label = TRANSLATIONS.expires_at ? TRANSLATIONS.expires_at[lang] : 'Expires at';
                 elem.title = `${label}: ${formatAbsoluteTime(expiresAt, lang)}`;
             }
@@ -89,8 +89,8 @@
         if (extractor === null) {
             extractor = (cols) => {
                 return {
-                    label: cols[0].innerHTML,
-                    value: Number(cols[1].innerHTML),
+                    label: cols[0].textContent,
+                    value: Number(cols[1].textContent),
                 };
             };
         }
@@ -259,9 +259,11 @@
                   .range([0, width])
                   .domain(data.map(d => d.label))
                   .padding(0.2);
+        // long series (e.g. 90 days) only label every n-th bar
+        let every = Math.ceil(data.length / 15);
         svg.append('g')
            .attr('transform', `translate(0, ${height})`)
-           .call(d3.axisBottom(x))
+           .call(d3.axisBottom(x).tickValues(x.domain().filter((d, i) => i % every === 0)))
            .attr('font-size', '1em')
            .selectAll('text')
            .style('text-anchor', 'middle')
@@ -297,40 +299,121 @@
              });
     }
 
-    makeTreeMap(
-        d3.hierarchy({
-            children: extractData('duties'),
-        }).sum(d => d.value),
-        'dutiesChart',
-    );
-    makeTreeMap(
-        d3.hierarchy(
-            d3.group(
-                extractData(
-                    'hosts',
-                    (cols) => {
-                        return {
-                            label: cols[1].innerHTML,
-                            world: cols[0].innerHTML,
-                            value: Number(cols[2].innerHTML),
-                        };
-                    },
-                ),
-                d => d.world,
-            )
-        ).sum(d => d.value),
-        'hostsChart',
-        {
-            drawLabels: false,
-            grouped: true,
-        },
-    );
-    makeBarPlot(
-        extractData('hours'),
-        'hoursChart',
-    );
-    makeBarPlot(
-        extractData('days'),
-        'daysChart',
-    );
+    const roleColours = {
+        tank: '#455CCB',
+        healer: '#487B39',
+        dps: '#813B3C',
+    };
+    const weekdays = ['Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat', 'Sun'];
+
+    function makeHeatmap(rows, graphId, colour) {
+        let elem = document.getElementById(graphId);
+        elem.innerHTML = '';
+        const [marginLeft, marginRight, marginTop, marginBottom] = [50, 0, 16, 30];
+        const [width, height] = [elem.offsetWidth - marginLeft - marginRight, elem.offsetHeight - marginTop - marginBottom];
+
+        let svg = d3.select(`#${graphId}`)
+                    .append('svg')
+                    .attr('viewBox', `0 0 ${width + marginLeft + marginRight} ${height + marginTop + marginBottom}`)
+                    .append('g')
+                    .attr('transform', `translate(${marginLeft}, ${marginTop})`);
+
+        let hours = d3.range(24);
+        let x = d3.scaleBand().range([0, width]).domain(hours).padding(0.05);
+        let y = d3.scaleBand().range([0, height]).domain(weekdays).padding(0.05);
+        // long series (e.g. 90 days) only label every n-th bar
+        let every = Math.ceil(data.length / 15);
+        svg.append('g')
+           .attr('transform', `translate(0, ${height})`)
+           .call(d3.axisBottom(x).tickValues(x.domain().filter((d, i) => i % every === 0)))
+           .attr('font-size', '1em');
+        svg.append('g')
+           .call(d3.axisLeft(y))
+           .attr('font-size', '1em');
+
+        let cells = rows.flatMap((hourValues, day) => hourValues.map((value, hour) => ({ day, hour, value })));
+        let max = d3.max(cells, d => d.value) || 1;
+        let shade = d3.scaleLinear().domain([0, max]).range(['#1a1a1a', colour]);
+
+        let group = svg.selectAll('cells')
+                       .data(cells)
+                       .enter()
+                       .append('g');
+        group.append('title')
+             .text(d => `${weekdays[d.day]} ${d.hour}:00 - ${d.value === null ? 'no data' : d.value.toFixed(1)}`);
+        group.append('rect')
+             .attr('x', d => x(d.hour))
+             .attr('y', d => y(weekdays[d.day]))
+             .attr('width', x.bandwidth())
+             .attr('height', y.bandwidth())
+             .attr('fill', d => d.value === null ? 'transparent' : shade(d.value));
+    }
+
+    function loadRoleDemand() {
+        let dc = document.getElementById('roleDemandDc').value;
+        let role = document.getElementById('roleDemandRole').value;
+
+        fetch(`/api/stats/role_demand?dc=${encodeURIComponent(dc)}&days=7`)
+            .then(resp => resp.json())
+            .then(data => {
+                document.getElementById('roleDemandTimezone').textContent = `UTC${data.timezone}`;
+                makeHeatmap(data.matrix[role], 'roleDemandChart', roleColours[role]);
+            })
+            .catch(e => console.error('could not load role demand', e));
+    }
+
+    // the per-duty page only has some of the charts
+    function has(id) {
+        return document.getElementById(id) !== null;
+    }
+
+    if (has('roleDemandDc')) {
+        for (let id of ['roleDemandDc', 'roleDemandRole']) {
+            document.getElementById(id).addEventListener('change', loadRoleDemand);
+        }
+        loadRoleDemand();
+    }
+
+    for (let id of ['duties', 'worlds']) {
+        if (has(id)) {
+            makeTreeMap(
+                d3.hierarchy({
+                    children: extractData(id),
+                }).sum(d => d.value),
+                `${id}Chart`,
+            );
+        }
+    }
+    if (has('hosts')) {
+        makeTreeMap(
+            d3.hierarchy(
+                d3.group(
+                    extractData(
+                        'hosts',
+                        (cols) => {
+                            return {
+                                label: cols[1].innerHTML,
+                                world: cols[0].innerHTML,
+                                value: Number(cols[2].innerHTML),
+                            };
+                        },
+                    ),
+                    d => d.world,
+                )
+            ).sum(d => d.value),
+            'hostsChart',
+            {
+                drawLabels: false,
+                grouped: true,
+            },
+        );
+    }
+    for (let id of ['timeline', 'daily', 'hours', 'days']) {
+        if (has(id)) {
+            makeBarPlot(
+                extractData(id),
+                `${id}Chart`,
+            );
+        }
+    }
 })();
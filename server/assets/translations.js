@@ -10,12 +10,27 @@ const TRANSLATIONS = {
     duty_complete: { en: "Duty Complete", ja: "コンプリート済み", de: "Abgeschlossen", fr: "Déjà terminé", },
     duty_incomplete: { en: "Duty Incomplete", ja: "未コンプリート", de: "Nicht abgeschlossen", fr: "Jamais terminé", },
     weekly_reward_unclaimed: { en: "Duty Complete (Weekly Reward Unclaimed)", ja: "コンプリート済み（今週の週制限報酬：未取得）", de: "Inhalt abgeschlossen (Wöchentliche Vergütung offen)", fr: "Déjà terminée (récompense hebdo. obtenable)", },
+    undersized_party: { en: "Undersized Party", ja: "少人数で攻略", de: "Reduzierte Gruppe", fr: "Groupe réduit", },
+    minimum_item_level: { en: "Minimum IL", ja: "最低IL", de: "Mindest-Gegenstandsstufe", fr: "Niveau d'objet minimum", },
+    silence_echo: { en: "Silence Echo", ja: "超える力無効", de: "Ohne Echo", fr: "Sans l'Écho", },
     one_player_per_job: { en: "One Player per Job", ja: "ジョブ重複不可", de: "Doppelte Jobs nicht möglich", fr: "Jobs identiques impossibles", },
     high_end_duty: { en: "High-end Duty", ja: "高難易度コンテンツ", de: "Schwierige Inhalte", fr: "Missions à difficulté élevée", },
     average_item_level: { en: "Average Item Level", ja: "平均アイテムレベル", de: "Ø Gegen­stands­stufe", fr: "Niveau d'objet moyen", },
     min_item_level: { en: "Min Item Level", ja: "平均IL", de: "Min. Gegenstandsstufe", fr: "Niveau d'objet min.", },
     no_listings: { en: "No listings - download the plugin to help contribute!", ja: "募集がありません - プラグインを導入して募集情報を共有しましょう！", de: "Keine Einträge - Lade das Plugin herunter, um zu helfen!", fr: "Aucune annonce - téléchargez le plugin pour contribuer !", },
     no_members: { en: "No information available for other members", ja: "他メンバーの情報がありません", de: "Keine Informationen zu anderen Mitgliedern verfügbar", fr: "Aucune information disponible pour les autres membres", },
+    parse_header: { en: "Best Parse", ja: "ベストパース", de: "Bester Parse", fr: "Meilleur parse", },
+    listing_unavailable: { en: "This listing could not be displayed", ja: "この募集は表示できません", de: "Dieses Gesuch kann nicht angezeigt werden", fr: "Cette annonce ne peut pas être affichée", },
+    req_min_clears: { en: "Required clears", ja: "必要クリア回数", de: "Benötigte Abschlüsse", fr: "Victoires requises", },
+    req_voice_required: { en: "Voice chat required", ja: "VC必須", de: "Voice-Chat erforderlich", fr: "Chat vocal requis", },
+    req_voice_preferred: { en: "Voice chat preferred", ja: "VC推奨", de: "Voice-Chat bevorzugt", fr: "Chat vocal préféré", },
+    req_voice_not_used: { en: "No voice chat", ja: "VCなし", de: "Kein Voice-Chat", fr: "Pas de chat vocal", },
+    req_loot_free_roll: { en: "Free roll", ja: "ロット自由", de: "Freies Würfeln", fr: "Jets libres", },
+    req_loot_reserved: { en: "Loot reserved", ja: "分配予約あり", de: "Beute reserviert", fr: "Butin réservé", },
+    req_loot_one_per_member: { en: "One item per member", ja: "一人一個", de: "Ein Gegenstand pro Mitglied", fr: "Un objet par membre", },
+    req_loot_leader: { en: "Leader distributes loot", ja: "リーダー分配", de: "Leiter verteilt die Beute", fr: "Le chef répartit le butin", },
+    req_duration: { en: "Planned duration", ja: "予定時間", de: "Geplante Dauer", fr: "Durée prévue", },
+    cross_dc_member: { en: "Visiting from another data centre", ja: "他のデータセンターから参加", de: "Aus einem anderen Datenzentrum", fr: "Venu d'un autre centre de données", },
     // 시간 표시 관련 번역 (i18n)
     time_in: { en: "in", ja: "後", de: "in", fr: "dans", },
     time_ago: { en: "ago", ja: "前", de: "vor", fr: "il y a", },
@@ -1,13 +1,16 @@
 use crate::ffxiv;
-use crate::ffxiv::duties::DutyInfo;
+use crate::ffxiv::duties::{ContentKind, DutyInfo};
 use crate::ffxiv::Language;
-use crate::listing::{ConditionFlags, DutyFinderSettingsFlags, LootRuleFlags, ObjectiveFlags, PartyFinderListing, PartyFinderSlot, SearchAreaFlags};
-use crate::listing_container::QueriedListing;
-use crate::mongo::{get_current_listings, get_players_by_content_ids};
+use crate::listing::{ConditionFlags, DutyCategory, DutyFinderSettingsFlags, DutyType, ListingFilter, LootRuleFlags, ObjectiveFlags, PartyFill, PartyFinderListing, PartyFinderSlot, SearchAreaFlags};
+use crate::listing_container::{QueriedListing, SortKey};
+use crate::mongo::ParseCacheDoc;
 use crate::sestring_ext::SeStringExt;
+use crate::stats::role_demand;
+use crate::template::listings::{ParseDisplay, RenderableMember};
+use crate::web::enrichment::{enrich_listings, parse_content_ids};
 use crate::web::State;
 use crate::ws::WsApiClient;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, Utc};
 use serde::Serialize;
 use sestring::SeString;
 use std::collections::HashMap;
@@ -17,119 +20,429 @@ use warp::filters::BoxedFilter;
 use warp::http::StatusCode;
 use warp::{Filter, Reply};
 
+pub(crate) mod admin;
+pub(crate) mod conditional;
+pub(crate) mod cors;
+pub(crate) mod moderation;
+pub(crate) mod parses;
+pub(crate) mod players;
+
 pub fn api(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    let config = state.config.web.cors.clone();
+    let routes = ws(state.clone())
+        .or(health(state.clone()))
+        .or(status(state.clone()))
+        .or(stats(state.clone()))
+        .or(duty_stats(state.clone()))
+        .or(role_demand(state.clone()))
+        .or(summary(state.clone()))
+        .or(crate::version::version())
+        .or(listings(state.clone()))
+        .or(listing(state.clone()))
+        .or(admin::admin(state.clone()))
+        .or(moderation::moderation(state.clone()))
+        .or(players::lookup(state.clone()))
+        .or(players::players(state.clone()))
+        .or(players::listings(state.clone()))
+        .or(parses::parses(state.clone()))
+        .or(crate::export::datasets(state.clone()))
+        .or(crate::bookmarks::bookmarks(state.clone()))
+        .boxed();
     warp::path("api")
-        .and(ws(state.clone()).or(listings(state.clone())))
+        .and(cors::preflight(config.clone()).or(cors::with_headers(config, routes)))
         .boxed()
 }
 
-fn listings(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+/// GET /api/health: 프로세스 상태, 점검 중 일시 정지 여부, 없는 플레이어 캐시 적중 수, 보관 대기열 상태,
+/// 모집글 수가 급감한 데이터 센터, 등급별 웹소켓 연결 수, 데이터베이스 이름 변경 중 복사 진행 상황,
+/// FFLogs 응답 형태 변경 감지 상태, FFLogs 요청 / API 포인트 사용량
+fn health(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    warp::get()
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .map(move || {
+            let body = serde_json::json!({
+                "status": "ok",
+                "maintenance": state.maintenance.status(Utc::now()),
+                "missing_players": state.missing_players.stats(),
+                "archive": state.archive.stats(),
+                "volume_alerts": state.volume.alerts(),
+                "websockets": state.websockets.stats(),
+                "migration": state.config.mongo.legacy_database().map(|_| state.migration.snapshot()),
+                "fflogs_schema": state.fflogs_client.as_ref().map(|_| state.fflogs_schema.snapshot()),
+                "fflogs_budget": state.fflogs_client.as_ref().map(|client| serde_json::json!({
+                    "requests_last_hour": client.quota().used(),
+                    "requests_per_hour": client.quota().limit(),
+                    "points": client.points().snapshot(),
+                    "points_used_percent": client.points().snapshot().map(|usage| usage.used_percent()),
+                })),
+                "indexes": state.indexes.snapshot(),
+            });
+            warp::reply::with_header(warp::reply::json(&body), "cache-control", "no-store")
+        })
+        .boxed()
+}
+
+/// GET /api/status: `/status` 페이지와 같은 요약 (봇용)
+fn status(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
     async fn logic(state: Arc<State>) -> Result<warp::reply::Response, Infallible> {
-        let listings = get_current_listings(state.collection()).await;
+        let summary = state.status_summary().await;
+        Ok(warp::reply::with_header(warp::reply::json(&summary), "cache-control", "no-store").into_response())
+    }
+
+    warp::get()
+        .and(warp::path("status"))
+        .and(warp::path::end())
+        .and_then(move || logic(state.clone()))
+        .boxed()
+}
+
+/// GET /api/stats, /api/stats/7days: `/stats` 페이지와 같은 캐시된 통계
+/// (모집글 수, 듀티별 / 호스트 월드별 / 시간 / 요일별 모집글 수, 설명 언어별 비교)
+///
+/// 오래된 통계면 그대로 응답하고 백그라운드 갱신을 요청합니다.
+fn stats(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    #[derive(Serialize)]
+    struct ApiStats<'a> {
+        #[serde(flatten)]
+        stats: &'a crate::stats::Statistics,
+        seven_days: bool,
+        // Last time both windows were calculated successfully
+        updated_at: DateTime<Utc>,
+        // A background refresh is running (the numbers are from `generated_at`)
+        refreshing: bool,
+    }
+
+    async fn logic(state: Arc<State>, seven_days: bool) -> Result<warp::reply::Response, Infallible> {
+        let Some(stats) = state.cached_stats().await else {
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "error": "stats haven't been calculated yet" })),
+                StatusCode::SERVICE_UNAVAILABLE,
+            )
+            .into_response());
+        };
+
+        let body = ApiStats {
+            stats: if seven_days { &stats.seven_days } else { &stats.all_time },
+            seven_days,
+            updated_at: stats.updated_at,
+            refreshing: state.stats_refresh.in_flight(),
+        };
+        Ok(warp::reply::with_header(warp::reply::json(&body), "cache-control", "no-store").into_response())
+    }
+
+    let all_time = {
+        let state = state.clone();
+        warp::path("stats")
+            .and(warp::path::end())
+            .and_then(move || logic(state.clone(), false))
+    };
+    let seven_days = warp::path!("stats" / "7days").and_then(move || logic(state.clone(), true));
+
+    warp::get().and(all_time.or(seven_days).unify()).boxed()
+}
+
+/// GET /api/stats/duty/{duty_id}: 듀티 하나의 기간별 모집글 수, 시간 / 요일별 모집글 수,
+/// 모집글을 많이 연 월드, 평균 자리 채움 (전체 기간 / 최근 7일, 없는 듀티는 404)
+fn duty_stats(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(state: Arc<State>, duty: u16) -> Result<warp::reply::Response, Infallible> {
+        let Some(info) = ffxiv::duty(u32::from(duty)) else {
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "error": "unknown duty" })),
+                StatusCode::NOT_FOUND,
+            )
+            .into_response());
+        };
+
+        let (all_time, seven_days) = match tokio::try_join!(state.duty_stats(duty, false), state.duty_stats(duty, true)) {
+            Ok(stats) => stats,
+            Err(e) => {
+                tracing::error!("could not calculate stats for duty {}: {:#?}", duty, e);
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+
+        let window = |stats: &crate::stats::Statistics| {
+            serde_json::json!({
+                "generated_at": stats.generated_at,
+                "listings": stats.num_listings(),
+                "timeline": stats.timeline,
+                "hours": stats
+                    .hours
+                    .iter()
+                    .map(|info| serde_json::json!({ "hour": info.hour, "listings": info.count }))
+                    .collect::<Vec<_>>(),
+                "days": stats
+                    .days
+                    .iter()
+                    .map(|info| serde_json::json!({ "day": info.name(), "listings": info.count }))
+                    .collect::<Vec<_>>(),
+                "worlds": stats
+                    .hosts
+                    .iter()
+                    .map(|host| serde_json::json!({ "world": host.world_name(), "listings": host.count }))
+                    .collect::<Vec<_>>(),
+                "fill": stats.fill(),
+            })
+        };
+        let body = serde_json::json!({
+            "duty": duty,
+            "name": info.name.en,
+            "all_time": window(&all_time),
+            "seven_days": window(&seven_days),
+        });
+        Ok(warp::reply::json(&body).into_response())
+    }
+
+    warp::get()
+        .and(warp::path!("stats" / "duty" / u16))
+        .and_then(move |duty| logic(state.clone(), duty))
+        .boxed()
+}
+
+/// 요약 응답 캐시 시간 (서버 캐시 `SUMMARY_TTL`과 같음)
+pub const SUMMARY_CACHE_CONTROL: &str = "public, max-age=5";
+
+/// GET /api/summary: 현재 모집글 수 (분류별, 많은 듀티 순, 데이터 센터별)와 집계 시각
+///
+/// 봇이 자주 조회하는 용도라 모집글 대신 개수만 집계하고 몇 초간 재사용합니다.
+fn summary(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(state: Arc<State>) -> Result<warp::reply::Response, Infallible> {
+        let summary = match state.listing_summary().await {
+            Ok(summary) => summary,
+            Err(e) => {
+                tracing::error!("could not summarise listings: {:#?}", e);
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+        Ok(warp::reply::with_header(warp::reply::json(&*summary), "cache-control", SUMMARY_CACHE_CONTROL).into_response())
+    }
+
+    warp::get()
+        .and(warp::path("summary"))
+        .and(warp::path::end())
+        .and_then(move || logic(state.clone()))
+        .boxed()
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct RoleDemandQuery {
+    /// 데이터 센터 이름 (예: `Mana`)
+    dc: String,
+    #[serde(default = "default_role_demand_days")]
+    days: u32,
+    /// `DutyCategory` 이름 (없으면 모든 분류)
+    category: Option<String>,
+}
+
+fn default_role_demand_days() -> u32 {
+    7
+}
+
+/// GET /api/stats/role_demand?dc=Mana&days=7: 표시 시간대 기준 요일 × 시간별 평균 빈 자리 (역할별)
+fn role_demand(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(state: Arc<State>, query: RoleDemandQuery) -> Result<warp::reply::Response, Infallible> {
+        let bad_request = |error: String| {
+            Ok(warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "error": error })),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response())
+        };
+
+        if !role_demand::data_centres().contains(query.dc.as_str()) {
+            return bad_request(format!("unknown data centre `{}`", query.dc));
+        }
+        let category = match &query.category {
+            Some(name) => match DutyCategory::ALL.into_iter().find(|c| format!("{:?}", c) == *name) {
+                Some(category) => Some(category),
+                None => return bad_request(format!("unknown category `{}`", name)),
+            },
+            None => None,
+        };
+
+        // 보관 기간보다 긴 기간은 의미가 없음
+        let days = query.days.clamp(1, state.config.role_demand.horizon_days.max(1));
+        let since = Utc::now() - chrono::TimeDelta::try_days(i64::from(days)).unwrap();
+        let samples = match crate::mongo::role_demand_since(state.role_demand_collection(), &query.dc, since).await {
+            Ok(samples) => samples,
+            Err(e) => {
+                tracing::error!("could not load role demand: {:#?}", e);
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+
+        let timezone = state.config.web.display_timezone;
+        let body = serde_json::json!({
+            "data_centre": query.dc,
+            "days": days,
+            "category": query.category,
+            "timezone": timezone.to_string(),
+            "matrix": role_demand::matrix(&samples, timezone, category),
+        });
+        Ok(warp::reply::json(&body).into_response())
+    }
+
+    warp::get()
+        .and(warp::path("stats"))
+        .and(warp::path("role_demand"))
+        .and(warp::path::end())
+        .and(warp::query::<RoleDemandQuery>())
+        .and_then(move |query: RoleDemandQuery| logic(state.clone(), query))
+        .boxed()
+}
+
+/// 이름 필드 형식 (`?format=`)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ApiFormat {
+    /// 모든 언어의 이름과 `Debug` 표기 (기존 응답)
+    #[default]
+    Full,
+    /// 한 언어의 이름과 snake_case 식별자
+    Localized,
+}
+
+/// 지역화 응답의 언어 (`?lang=`이나 `?format=localized`가 있을 때만)
+///
+/// 언어는 `lang`, 없거나 지원하지 않으면 HTML 페이지처럼 `Accept-Language`로 정합니다.
+pub(crate) fn localized_language(lang: Option<&str>, format: ApiFormat, accept_language: Option<&str>) -> Option<Language> {
+    if lang.is_none() && format == ApiFormat::Full {
+        return None;
+    }
+    Some(lang.and_then(Language::from_code).unwrap_or_else(|| Language::from_codes(accept_language)))
+}
+
+/// API 응답 형태
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ApiShape {
+    /// 기본 응답
+    #[default]
+    Compact,
+    /// 멤버의 All Stars 점수 / 순위 포함
+    Extended,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+pub(crate) struct ListingsQuery {
+    #[serde(default)]
+    shape: ApiShape,
+    /// 이름 필드 언어 (`ja`, `en`, `de`, `fr`, 지정하면 `format=localized`)
+    lang: Option<String>,
+    #[serde(default)]
+    format: ApiFormat,
+    /// 듀티 찾기 설정으로 거르기 (`true`면 켠 모집글만, `false`면 끈 모집글만)
+    undersized_party: Option<bool>,
+    minimum_item_level: Option<bool>,
+    silence_echo: Option<bool>,
+    /// 모두 켜져 있어야 하는 원시 비트 (이름이 없는 새 설정으로 거를 때)
+    duty_finder_bits: Option<u32>,
+    /// 일반 듀티 ID (쉼표로 여러 개)
+    duty: Option<String>,
+    /// 데이터 센터 이름 (생성 서버 기준)
+    datacentre: Option<String>,
+    /// `DutyCategory` 이름 (쉼표로 여러 개, `canonical_category` 기준)
+    category: Option<String>,
+    /// 금칙어로 표시된 모집글만 (`true`, 모더레이터 토큰 필요)
+    flagged: Option<bool>,
+}
+
+impl ListingsQuery {
+    /// 듀티 / 데이터 센터 / 분류 조건 (알 수 없는 값이 있으면 `None`)
+    pub fn listing_filter(&self) -> Option<ListingFilter> {
+        ListingFilter::parse(self.duty.as_deref(), self.datacentre.as_deref(), self.category.as_deref())
+    }
+
+    pub fn duty_finder_filter(&self) -> DutyFinderFilter {
+        let mut filter = DutyFinderFilter { required: self.duty_finder_bits.unwrap_or_default(), excluded: 0 };
+        let settings = [
+            (DutyFinderSettingsFlags::UNDERSIZED_PARTY, self.undersized_party),
+            (DutyFinderSettingsFlags::MINIMUM_ITEM_LEVEL, self.minimum_item_level),
+            (DutyFinderSettingsFlags::SILENCE_ECHO, self.silence_echo),
+        ];
+        for (flag, wanted) in settings {
+            match wanted {
+                Some(true) => filter.required |= flag.bits(),
+                Some(false) => filter.excluded |= flag.bits(),
+                None => {}
+            }
+        }
+        filter
+    }
+}
+
+/// `/api/listings`의 듀티 찾기 설정 조건
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DutyFinderFilter {
+    pub required: u32,
+    pub excluded: u32,
+}
+
+impl DutyFinderFilter {
+    pub fn matches(&self, settings: DutyFinderSettingsFlags) -> bool {
+        settings.bits() & self.required == self.required && settings.bits() & self.excluded == 0
+    }
+}
+
+fn listings(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(
+        state: Arc<State>,
+        query: ListingsQuery,
+        head: bool,
+        if_none_match: Option<String>,
+        accept_language: Option<String>,
+        authorization: Option<String>,
+    ) -> Result<warp::reply::Response, Infallible> {
+        let lang = localized_language(query.lang.as_deref(), query.format, accept_language.as_deref());
+        // 알 수 없는 듀티 / 데이터 센터 / 분류에 맞는 모집글은 없음
+        let Some(mut filter) = query.listing_filter() else {
+            return Ok(warp::reply::json(&Vec::<ApiReadableListingContainer>::new()).into_response());
+        };
+        if query.flagged == Some(true) {
+            if moderation::authorize(state.config.moderation.as_ref(), authorization.as_deref()).is_none() {
+                let reply = warp::reply::json(&serde_json::json!({ "error": "flagged listings require a moderator token" }));
+                return Ok(warp::reply::with_status(reply, StatusCode::UNAUTHORIZED).into_response());
+            }
+            filter.flagged = true;
+        }
+        // 모집글보다 먼저 읽어 ETag가 응답 내용보다 새것이 되지 않게 함
+        let revision = state.listings_revision();
+        let listings = state.filtered_listings(&filter).await;
 
         match listings {
             Ok(listings) => {
-                // Collect all member IDs for player fetch
-                let all_content_ids: Vec<u64> = listings.iter()
-                    .flat_map(|l| l.listing.member_content_ids.iter().map(|&id| id as u64))
+                let filter = query.duty_finder_filter();
+                let listings: Vec<QueriedListing> = listings
+                    .iter()
+                    .filter(|ql| filter.matches(ql.listing.duty_finder_settings))
+                    .cloned()
                     .collect();
-                
-                // Fetch players (Batch 1)
-                let players = get_players_by_content_ids(state.players_collection(), &all_content_ids).await.unwrap_or_default();
-                let player_map: HashMap<u64, crate::player::Player> = players.into_iter().map(|p| (p.content_id, p)).collect();
-
-                // Prepare for Batch 2: Collect Content IDs per Zone ID
-                let mut zone_requests: HashMap<u16, Vec<u64>> = HashMap::new();
-                // Store calculated zone info to avoid recalculating in the second loop
-                let mut listing_meta: HashMap<u32, (u16, u16)> = HashMap::new();
-
-                for ql in &listings {
-                    let duty_id = ql.listing.duty;
-                    let fflogs_info = crate::fflogs::mapping::get_fflogs_encounter(duty_id);
-                    let (zone_id, encounter_id) = if let Some(info) = fflogs_info {
-                        (info.zone_id as u32, info.encounter_id)
-                    } else {
-                        (0, 0)
-                    };
-                    
-                    listing_meta.insert(ql.listing.id, (zone_id as u16, encounter_id as u16));
-
-                    if zone_id > 0 {
-                        let entry = zone_requests.entry(zone_id as u16).or_insert_with(Vec::new);
-                        for &mid in &ql.listing.member_content_ids {
-                            entry.push(mid as u64);
-                        }
-                    }
-                }
-
-                // Batch Query: Fetch parses for each Zone
-                // (ZoneID, ContentID) -> ZoneCache
-                let mut parse_data_map: HashMap<(u16, u64), crate::mongo::ZoneCache> = HashMap::new();
 
-                for (zone_id, content_ids) in zone_requests {
-                    // Dedup content_ids
-                    let mut unique_ids = content_ids;
-                    unique_ids.sort_unstable();
-                    unique_ids.dedup();
-
-                    if let Ok(caches) = crate::mongo::get_zone_caches(state.parse_collection(), &unique_ids, zone_id as u32).await {
-                        for (cid, cache) in caches {
-                            parse_data_map.insert((zone_id, cid), cache);
-                        }
-                    }
+                // 바뀌지 않았으면 본문 없이 304, HEAD면 헤더만 (플레이어 / Parse 조회 없음)
+                let mut etag = conditional::listings_etag(revision, &listings);
+                if let Some(lang) = lang {
+                    etag = conditional::localized_etag(&etag, lang);
                 }
-                
-                let mut listings_with_members = Vec::new();
-                for ql in listings {
-                    let member_ids = ql.listing.member_content_ids.clone();
-                    let mut container: ApiReadableListingContainer = ql.into();
-                    
-                    // Retrieve pre-calculated info
-                    let (zone_id, encounter_id) = listing_meta.get(&container.listing.id).copied().unwrap_or((0, 0));
-
-                    let mut members = Vec::new();
-                    
-                    for id in member_ids {
-                        let uid = id as u64;
-                        if let Some(p) = player_map.get(&uid) {
-                            // Lookup in pre-fetched map
-                            let (percentile, color_class) = if zone_id > 0 {
-                                if let Some(zone_cache) = parse_data_map.get(&(zone_id, uid)) {
-                                    let enc_key = encounter_id.to_string();
-                                    if let Some(enc_parse) = zone_cache.encounters.get(&enc_key) {
-                                        if enc_parse.percentile < 0.0 {
-                                            (None, "parse-none".to_string())
-                                        } else {
-                                            (
-                                                Some(enc_parse.percentile.round() as u8),
-                                                crate::fflogs::mapping::percentile_color_class(enc_parse.percentile).to_string(),
-                                            )
-                                        }
-                                    } else {
-                                        (None, "parse-none".to_string())
-                                    }
-                                } else {
-                                    (None, "parse-none".to_string())
-                                }
-                            } else {
-                                (None, "parse-none".to_string())
-                            };
-                            
-                            members.push(ApiReadableMember {
-                                content_id: p.content_id,
-                                name: p.name.clone(),
-                                home_world: p.home_world.into(),
-                                parse_percentile: percentile,
-                                parse_color_class: color_class,
-                            });
-                        }
-                    }
-                    
-                    container.listing.members = members;
-                    listings_with_members.push(container);
+                if if_none_match.as_deref().is_some_and(|header| conditional::etag_matches(header, &etag)) {
+                    return Ok(warp::reply::with_header(StatusCode::NOT_MODIFIED, "etag", etag).into_response());
+                }
+                if head {
+                    let reply = warp::reply::with_header(warp::reply(), "content-type", "application/json");
+                    return Ok(warp::reply::with_header(reply, "etag", etag).into_response());
                 }
 
-                Ok(warp::reply::json(&listings_with_members).into_response())
+                let mut listings_with_members = api_listings(&state, listings, query.shape).await;
+                if let Some(lang) = lang {
+                    localize(&mut listings_with_members, lang);
+                }
+                let mut response =
+                    warp::reply::with_header(warp::reply::json(&listings_with_members), "etag", etag).into_response();
+                // `?lang=` 없이 지역화하면 같은 주소라도 `Accept-Language`마다 본문이 다름
+                if lang.is_some() && query.lang.is_none() {
+                    response.headers_mut().insert("vary", warp::http::HeaderValue::from_static("accept-language"));
+                }
+                Ok(response)
             },
             Err(_) => Ok(warp::reply::with_status(
                 warp::reply(),
@@ -139,22 +452,219 @@ fn listings(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
         }
     }
 
-    warp::get()
+    let method = warp::get().map(|| false).or(warp::head().map(|| true)).unify();
+    method
         .and(warp::path("listings"))
         .and(warp::path::end())
-        .and_then(move || logic(state.clone()))
+        .and(warp::query::<ListingsQuery>())
+        .and(warp::header::optional::<String>("if-none-match"))
+        .and(warp::header::optional::<String>("accept-language"))
+        .and(warp::header::optional::<String>("authorization"))
+        .and_then(
+            move |head: bool,
+                  query: ListingsQuery,
+                  if_none_match: Option<String>,
+                  accept_language: Option<String>,
+                  authorization: Option<String>| {
+                logic(state.clone(), query, head, if_none_match, accept_language, authorization)
+            },
+        )
         .boxed()
 }
 
+#[derive(Debug, Default, serde::Deserialize)]
+pub(crate) struct ListingQuery {
+    #[serde(default)]
+    shape: ApiShape,
+    lang: Option<String>,
+    #[serde(default)]
+    format: ApiFormat,
+    /// 생성 서버 ID (다른 서버의 같은 ID 모집글과 구분)
+    created_world: Option<u16>,
+}
+
+/// GET /api/listings/{id}: `/api/listings`의 항목 하나 (같은 ID가 여럿이면 가장 최근 업데이트)
+fn listing(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(
+        state: Arc<State>,
+        id: u32,
+        query: ListingQuery,
+        accept_language: Option<String>,
+    ) -> Result<warp::reply::Response, Infallible> {
+        let listing = match state.listing_by_id(id, query.created_world).await {
+            Ok(Some(listing)) => listing,
+            Ok(None) => {
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&serde_json::json!({ "error": "listing not found" })),
+                    StatusCode::NOT_FOUND,
+                )
+                .into_response())
+            }
+            Err(e) => {
+                tracing::error!("could not get listing {}: {:#}", id, e);
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+
+        let mut listings = api_listings(&state, vec![listing], query.shape).await;
+        if let Some(lang) = localized_language(query.lang.as_deref(), query.format, accept_language.as_deref()) {
+            localize(&mut listings, lang);
+        }
+        match listings.pop() {
+            Some(listing) => Ok(warp::reply::json(&listing).into_response()),
+            None => Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response()),
+        }
+    }
+
+    warp::get()
+        .and(warp::path!("listings" / u32))
+        .and(warp::query::<ListingQuery>())
+        .and(warp::header::optional::<String>("accept-language"))
+        .and_then(move |id, query, accept_language| logic(state.clone(), id, query, accept_language))
+        .boxed()
+}
+
+/// 모집글 멤버의 플레이어 / Parse 정보를 조회해 API 응답 목록 구성
+pub(crate) async fn api_listings(
+    state: &State,
+    listings: Vec<QueriedListing>,
+    shape: ApiShape,
+) -> Vec<ApiReadableListingContainer> {
+    // Collect all member IDs for player fetch
+    let all_content_ids: Vec<u64> = listings.iter()
+        .flat_map(|l| l.listing.member_content_ids.iter().map(|&id| id as u64))
+        .collect();
+
+    // Fetch players (Batch 1)
+    let players = state.players_by_content_ids(&all_content_ids).await.unwrap_or_default();
+    let player_map: HashMap<u64, crate::player::Player> = players.into_iter().map(|p| (p.content_id, p)).collect();
+
+    // Batch Query: Fetch parse docs of members / leaders of FFLogs listings (Batch 2, same as the listings page)
+    let parse_docs = state
+        .parse_read_collection()
+        .read_by_ids(&parse_content_ids(&listings), |collection, ids| async move {
+            crate::mongo::get_parse_docs(collection, &ids).await
+        })
+        .await
+        .unwrap_or_default();
+
+    let mut api_listings = build_api_listings(listings, &player_map, &parse_docs, state.config.web.display_timezone);
+    apply_shape(&mut api_listings, shape);
+    api_listings
+}
+
+/// 요청한 응답 형태에 없는 필드 제거 (기본 응답은 All Stars 제외)
+pub(crate) fn apply_shape(listings: &mut [ApiReadableListingContainer], shape: ApiShape) {
+    if shape == ApiShape::Compact {
+        for member in listings.iter_mut().flat_map(|container| container.listing.members.iter_mut()) {
+            member.all_stars = None;
+        }
+    }
+}
+
+/// 이름 필드를 한 언어로 바꿈 (`?lang=` / `?format=localized`)
+///
+/// 듀티 / 분류 이름은 `lang`의 문자열 하나로, 분류와 듀티 종류는 `{id, display_name}`으로 바꿉니다.
+pub(crate) fn localize(listings: &mut [ApiReadableListingContainer], lang: Language) {
+    for container in listings {
+        let listing = &mut container.listing;
+        let (category, canonical_category, duty_type) = listing.kinds;
+        listing.category = ApiEnumName::localized(category.id(), category.pf_category().name(), lang);
+        listing.canonical_category =
+            ApiEnumName::localized(canonical_category.id(), canonical_category.pf_category().name(), lang);
+        listing.duty_type = ApiEnumName::localized(duty_type.id(), duty_type.name(), lang);
+        if let Some(label) = &mut listing.category_label {
+            label.localize(lang);
+        }
+        if let Some(duty_info) = &mut listing.duty_info {
+            duty_info.name.localize(lang);
+        }
+    }
+}
+
+/// FFLogs Zone별로 Parse 조회가 필요한 멤버 / 파티장 Content ID (정렬, 중복 제거)
+///
+/// Zone당 한 번의 DB 조회로 처리하기 위해 모집글 수와 무관하게 Zone 단위로 묶습니다.
+pub(crate) fn zone_requests(listings: &[QueriedListing]) -> HashMap<u16, Vec<u64>> {
+    let mut zone_requests: HashMap<u16, Vec<u64>> = HashMap::new();
+
+    for ql in listings {
+        if let Some(info) = ql.listing.fflogs_encounter() {
+            let entry = zone_requests.entry(info.zone_id as u16).or_default();
+            entry.extend(ql.listing.member_content_ids.iter().map(|&mid| mid as u64));
+            entry.push(ql.listing.leader_content_id);
+            entry.retain(|&id| id != 0);
+        }
+    }
+
+    for unique_ids in zone_requests.values_mut() {
+        unique_ids.sort_unstable();
+        unique_ids.dedup();
+    }
+
+    zone_requests
+}
+
+/// 미리 조회한 플레이어 / Parse 정보로 API 응답 목록 구성 (DB 조회 없음)
+///
+/// 목록 페이지와 같은 `enrichment::enrich_listings` 결과를 JSON 형태로 옮깁니다.
+pub(crate) fn build_api_listings(
+    listings: Vec<QueriedListing>,
+    player_map: &HashMap<u64, crate::player::Player>,
+    parse_docs: &HashMap<u64, ParseCacheDoc>,
+    display_timezone: FixedOffset,
+) -> Vec<ApiReadableListingContainer> {
+    // 배열 순서를 그대로 쓰는 클라이언트도 웹사이트와 같은 순서가 되도록 정렬됨
+    enrich_listings(listings, player_map, parse_docs)
+        .into_iter()
+        .map(|renderable| {
+            let parsed_schedule = renderable.container.parsed_schedule(display_timezone);
+            let members = renderable
+                .members
+                .iter()
+                .map(|member| ApiReadableMember::new(member, &renderable.container.listing))
+                .collect();
+
+            let mut container: ApiReadableListingContainer = renderable.container.into();
+            container.parsed_schedule = parsed_schedule;
+            container.listing.data_inconsistent = renderable.data_inconsistent;
+            container.listing.leader_parse = (&renderable.leader_parse).into();
+            container.listing.members = members;
+            container
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct WsQuery {
+    /// Priority token (browsers can't set headers on websocket requests)
+    token: Option<String>,
+}
+
+/// GET /api/ws: anonymous connections are limited by `[websocket]`; a `websocket_tokens` entry in
+/// `?token=` or `Authorization: Bearer` is exempt from the limits and never shed. The admin token
+/// is not accepted here, since query strings end up in proxy and access logs.
 fn ws(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
     let route =
         warp::path("ws")
             .and(warp::ws())
             .and(warp::path::end())
-            .map(move |ws: warp::ws::Ws| {
+            .and(warp::query::<WsQuery>())
+            .and(warp::header::optional::<String>("authorization"))
+            .and(crate::web::fingerprint::client_address(&state.config.ratelimit))
+            .map(move |ws: warp::ws::Ws, query: WsQuery, authorization: Option<String>, address| {
                 let state = Arc::clone(&state);
+                let token = query
+                    .token
+                    .as_deref()
+                    .or_else(|| authorization.as_deref().and_then(|h| h.strip_prefix("Bearer ")));
+                let admission = state.websockets.admit(state.websockets.tier(token), address, std::time::Instant::now());
+
                 ws.on_upgrade(move |websocket| async move {
-                    WsApiClient::run(state, websocket).await;
+                    match admission {
+                        Ok(permit) => WsApiClient::run(state, websocket, permit).await,
+                        Err(refusal) => WsApiClient::refuse(websocket, refusal).await,
+                    }
                 })
             });
 
@@ -164,11 +674,33 @@ fn ws(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
 /// A version of `QueriedListingContainer` with more sensible formatting,
 /// implementation details hidden, and resolved names for duties, etc.
 #[derive(Serialize)]
-struct ApiReadableListingContainer {
+pub(crate) struct ApiReadableListingContainer {
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     time_left: f64,
+    // Expiry instant and remaining time, shared with the listings page
+    expiry: crate::listing::ExpiryInfo,
+    // Number of uploads and distinct uploaders seen for this listing
+    upload_count: u32,
+    uploader_count: u32,
+    // Uploaded at least twice, i.e. confirmed by more than one report
+    multi_sourced: bool,
+    // Scheduled start time found in the description, if any
+    parsed_schedule: Option<DateTime<Utc>>,
+    // Canonical ordering tuple used by the website: updated_bucket DESC,
+    // then category_rank DESC, then time_left ASC. The array is already sorted by it.
+    sort_key: SortKey,
     listing: ApiReadableListing,
+    // Earlier descriptions, oldest first (only in the moderator-only `?flagged=true` view)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    description_history: Vec<ApiDescriptionEdit>,
+}
+
+#[derive(Serialize)]
+struct ApiDescriptionEdit {
+    text: String,
+    // When this description was replaced by the next one
+    replaced_at: DateTime<Utc>,
 }
 
 impl From<QueriedListing> for ApiReadableListingContainer {
@@ -177,13 +709,24 @@ impl From<QueriedListing> for ApiReadableListingContainer {
             created_at: value.created_at,
             updated_at: value.updated_at,
             time_left: value.time_left,
+            expiry: value.expiry,
+            upload_count: value.upload_count,
+            uploader_count: value.uploader_count,
+            multi_sourced: value.is_multi_sourced(),
+            parsed_schedule: None,
+            sort_key: value.sort_key(),
             listing: value.listing.into(),
+            description_history: value
+                .description_history
+                .into_iter()
+                .map(|edit| ApiDescriptionEdit { text: edit.text, replaced_at: edit.replaced_at })
+                .collect(),
         }
     }
 }
 
 #[derive(Serialize)]
-struct ApiReadableListing {
+pub(crate) struct ApiReadableListing {
     id: u32,
     // pub content_id: u32,
     recruiter: String,
@@ -191,16 +734,42 @@ struct ApiReadableListing {
     created_world: ApiReadableWorld,
     home_world: ApiReadableWorld,
     current_world: ApiReadableWorld,
-    // `Debug` of `DutyCategory`
-    category: String,
+    // `Debug` of `DutyCategory`, as uploaded (`{id, display_name}` when localized)
+    category: ApiEnumName,
+    // Category used for sorting, filtering and stats: a high-end duty from the duty table
+    // uploaded under another category gets the duty's own category
+    canonical_category: ApiEnumName,
+    // `category` and `canonical_category` disagree
+    miscategorized: bool,
     duty_info: Option<ApiReadableDutyInfo>,
-    // `Debug` of `DutyType`
-    duty_type: String,
+    // High-end duty from the duty table (always false for roulettes and non-duty listings)
+    high_end: bool,
+    // FFLogs parses are fetched and shown for this listing (mapped to an FFLogs encounter)
+    fflogs_supported: bool,
+    // Party Finder category used for filtering, and its rank in the website sort (higher first)
+    pf_category: &'static str,
+    pf_category_rank: u8,
+    // Localized category name for listings without a duty (hunt trains, FATEs, etc.)
+    category_label: Option<ApiText>,
+    // `Debug` of `DutyType` (`{id, display_name}` when localized)
+    duty_type: ApiEnumName,
+    // Uploaded category, canonical category and duty type behind the name fields
+    #[serde(skip)]
+    kinds: (DutyCategory, DutyCategory, DutyType),
     beginners_welcome: bool,
     seconds_remaining: u16,
     min_item_level: u16,
     num_parties: u8,
-    slot_count: u8, // = slots_available
+    slot_count: u8, // = slots_available, size of a single party
+    // slot_count * num_parties
+    total_capacity: usize,
+    // Filled slots across all parties
+    filled_total: usize,
+    // Per-party breakdown, in the same order as slots_filled
+    parties: Vec<PartyFill>,
+    // One player per job is set but jobs_present repeated a job (stale data);
+    // the repeats are reported as empty in slots_filled
+    data_inconsistent: bool,
     last_server_restart: u32,
     objective: ApiReadableObjectiveFlags,
     conditions: ApiReadableConditionFlags,
@@ -209,16 +778,117 @@ struct ApiReadableListing {
     search_area: ApiReadableSearchAreaFlags,
     slots: Vec<ApiReadablePartyFinderSlot>,
     slots_filled: Vec<Option<&'static str>>, // None if not filled, otherwise the job code
+    // Role-coloured job icons, in the same order as slots_filled
+    slots_filled_icon_urls: Vec<Option<String>>,
+    // Structured requirements sent by newer plugin versions (omitted when not sent)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requirements: Option<crate::listing::requirements::ListingRequirements>,
+    // Best parse of the leader, whatever job they are on (shown even without member data)
+    leader_parse: ApiReadableParse,
     members: Vec<ApiReadableMember>,
 }
 
+#[derive(Serialize)]
+struct ApiReadableParse {
+    percentile: Option<u8>,
+    color_class: String,
+    // Second phase of split encounters (None / "parse-none" otherwise)
+    secondary_percentile: Option<u8>,
+    secondary_color_class: String,
+    // Logs are hidden on FFLogs (color classes are "parse-hidden")
+    hidden: bool,
+}
+
+impl From<&ParseDisplay> for ApiReadableParse {
+    fn from(parse: &ParseDisplay) -> Self {
+        Self {
+            percentile: parse.primary_percentile,
+            color_class: parse.primary_color_class.clone(),
+            secondary_percentile: parse.secondary_percentile,
+            secondary_color_class: parse.secondary_color_class.clone(),
+            hidden: parse.hidden,
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct ApiReadableMember {
     content_id: u64,
+    // "Unknown Member" when the player hasn't been uploaded yet (see `known`)
     name: String,
+    // A player record exists for this member
+    known: bool,
     home_world: ApiReadableWorld,
+    // Home world differs from the listing's created world (false if unknown)
+    cross_world: bool,
+    // Home world is on another data centre than the listing's created world
+    cross_dc: bool,
     parse_percentile: Option<u8>,
     parse_color_class: String,
+    // Second phase of split encounters (None / "parse-none" otherwise)
+    secondary_parse_percentile: Option<u8>,
+    secondary_parse_color_class: String,
+    // The best parse was set on a job of another role than the member's current job
+    parse_role_mismatch: bool,
+    // Logs are hidden on FFLogs (color classes are "parse-hidden")
+    parse_hidden: bool,
+    // All Stars points and rank for the listing's encounter, only with `?shape=extended`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    all_stars: Option<crate::fflogs::AllStars>,
+    // Role-coloured icon of the member's job (None if the slot's job is unknown)
+    icon_url: Option<String>,
+}
+
+impl ApiReadableMember {
+    fn new(member: &RenderableMember, listing: &PartyFinderListing) -> Self {
+        let player = &member.player;
+        Self {
+            content_id: player.content_id,
+            name: player.name.clone(),
+            known: member.is_known(),
+            home_world: player.home_world.into(),
+            cross_world: member.is_cross_world(listing),
+            cross_dc: member.is_cross_dc(listing),
+            parse_percentile: member.parse.primary_percentile,
+            parse_color_class: member.parse.primary_color_class.clone(),
+            secondary_parse_percentile: member.parse.secondary_percentile,
+            secondary_parse_color_class: member.parse.secondary_color_class.clone(),
+            parse_role_mismatch: member.parse.role_mismatch,
+            parse_hidden: member.parse.hidden,
+            all_stars: member.parse.primary_all_stars,
+            icon_url: member.icon_url(),
+        }
+    }
+}
+
+/// Localized text: every language by default, a single string when localized
+#[derive(Serialize)]
+#[serde(untagged)]
+enum ApiText {
+    All(ffxiv::LocalisedText),
+    One(&'static str),
+}
+
+impl ApiText {
+    fn localize(&mut self, lang: Language) {
+        if let Self::All(text) = self {
+            *self = Self::One(text.text(&lang));
+        }
+    }
+}
+
+/// Enum value: its `Debug` name by default, a stable id and display name when localized
+#[derive(Serialize)]
+#[serde(untagged)]
+enum ApiEnumName {
+    Debug(String),
+    Localized { id: &'static str, display_name: &'static str },
+}
+
+impl ApiEnumName {
+    fn localized(id: &'static str, name: ffxiv::LocalisedText, lang: Language) -> Self {
+        Self::Localized { id, display_name: name.text(&lang) }
+    }
 }
 
 #[derive(Serialize)]
@@ -241,40 +911,63 @@ impl From<SeString> for ApiLocalizedString {
 }
 
 impl From<PartyFinderListing> for ApiReadableListing {
-    fn from(value: PartyFinderListing) -> Self {
-        let duty_info = ffxiv::duty(value.duty as u32)
-            .map(|di| ApiReadableDutyInfo {
-                id: value.duty as u32,
-                name: di.name,
-                high_end: di.high_end,
-                content_kind_id: di.content_kind.as_u32(),
-                content_kind: format!("{:?}", di.content_kind),
-            });
-        let slots_filled = value.jobs_present
+    fn from(mut value: PartyFinderListing) -> Self {
+        let data_inconsistent = value.clear_duplicate_jobs();
+        let key = value.key();
+        let duty_info = if value.duty_type == DutyType::Normal {
+            ffxiv::duty_or_record(value.duty as u32, || key.clone())
+        } else {
+            ffxiv::duty(value.duty as u32)
+        };
+        let fflogs_encounter = duty_info.and_then(|di| crate::fflogs::mapping::fflogs_encounter_for(value.duty, di));
+        // Same rule as `PartyFinderListing::canonical_category`
+        let canonical_category = match value.duty_type {
+            DutyType::Normal => value.category.canonical(duty_info),
+            _ => value.category,
+        };
+        let duty_info = duty_info.map(|di| ApiReadableDutyInfo::new(value.duty as u32, di));
+        let category_label = ffxiv::category_label(value.category, value.duty).map(ApiText::All);
+        let high_end = value.duty_type == DutyType::Normal && duty_info.as_ref().is_some_and(|di| di.high_end);
+        // Same rule as `PartyFinderListing::fflogs_supported`, reusing the duty lookup above so unknown duties are recorded once
+        let fflogs_supported = value.duty_type == DutyType::Normal && fflogs_encounter.is_some();
+        let pf_category = canonical_category.pf_category();
+        let (total_capacity, filled_total, parties) = (value.total_capacity(), value.filled_total(), value.parties());
+        let slots_filled: Vec<Option<&'static str>> = value.jobs_present
             .into_iter()
-            .map(|job| if job == 0 {
-                None
-            } else {
-                ffxiv::jobs::JOBS.get(&(job as u32))
-                    .map(|j| j.code())
-            })
+            .map(|job| ffxiv::job_or_record(job as u32, || key.clone()).map(|j| j.code()))
+            .collect();
+        let slots_filled_icon_urls = slots_filled
+            .iter()
+            .map(|code| code.map(crate::web::job_icons::icon_url))
             .collect();
 
         Self {
             id: value.id,
             recruiter: value.name.text(),
             description: value.description.into(),
-            created_world: value.created_world.into(),
-            home_world: value.home_world.into(),
-            current_world: value.current_world.into(),
-            category: format!("{:?}", value.category),
+            created_world: ApiReadableWorld::recorded(value.created_world, &key),
+            home_world: ApiReadableWorld::recorded(value.home_world, &key),
+            current_world: ApiReadableWorld::recorded(value.current_world, &key),
+            category: ApiEnumName::Debug(format!("{:?}", value.category)),
+            canonical_category: ApiEnumName::Debug(format!("{:?}", canonical_category)),
+            miscategorized: canonical_category != value.category,
             duty_info,
-            duty_type: format!("{:?}", value.duty_type),
+            high_end,
+            fflogs_supported,
+            pf_category: pf_category.as_str(),
+            pf_category_rank: pf_category.display_rank(),
+            category_label,
+            duty_type: ApiEnumName::Debug(format!("{:?}", value.duty_type)),
+            kinds: (value.category, canonical_category, value.duty_type),
             beginners_welcome: value.beginners_welcome,
             seconds_remaining: value.seconds_remaining,
             min_item_level: value.min_item_level,
             num_parties: value.num_parties,
             slot_count: value.slots_available,
+            total_capacity,
+            filled_total,
+            parties,
+            data_inconsistent,
             last_server_restart: value.last_server_restart,
             objective: value.objective.into(),
             conditions: value.conditions.into(),
@@ -283,6 +976,9 @@ impl From<PartyFinderListing> for ApiReadableListing {
             search_area: value.search_area.into(),
             slots: value.slots.into_iter().map(|s| s.into()).collect(),
             slots_filled,
+            slots_filled_icon_urls,
+            requirements: value.requirements,
+            leader_parse: (&ParseDisplay::none()).into(),
             members: Vec::new(),
         }
     }
@@ -294,6 +990,18 @@ struct ApiReadableWorld {
     name: &'static str,
 }
 
+impl ApiReadableWorld {
+    /// 모집글 변환용 (알 수 없는 월드는 미확인 ID로 기록)
+    fn recorded(value: u16, key: &str) -> Self {
+        Self {
+            id: value,
+            name: ffxiv::world_or_record(value as u32, || key.to_string())
+                .map(|w| w.as_str())
+                .unwrap_or("Unknown")
+        }
+    }
+}
+
 impl From<u16> for ApiReadableWorld {
     fn from(value: u16) -> Self {
         Self {
@@ -308,29 +1016,38 @@ impl From<u16> for ApiReadableWorld {
 #[derive(Serialize)]
 struct ApiReadableDutyInfo {
     pub id: u32,
-    pub name: ffxiv::LocalisedText,
+    pub name: ApiText,
     pub high_end: bool,
-    pub content_kind_id: u32,
-    pub content_kind: String,
+    #[serde(flatten)]
+    pub content_kind: ApiContentKind,
 }
 
-impl From<&DutyInfo> for ApiReadableDutyInfo {
-    fn from(value: &DutyInfo) -> Self {
-        // Need to find the ID from the value, but DutyInfo doesn't store its own ID.
-        // We need to pass the ID when converting or find a way to get it.
-        // Actually, listing.rs:172 `ffxiv::duty(value.duty as u32).map(|di| di.into())` passes &DutyInfo.
-        // We should change `ApiReadableListing::from` to pass the ID or make `DutyInfo` carry it (unlikely).
-        // Let's modify `ApiReadableListing::from` to instantiate `ApiReadableDutyInfo` manually or pass the ID.
+impl ApiReadableDutyInfo {
+    /// `DutyInfo`는 자기 ID를 갖지 않으므로 조회한 듀티 ID를 같이 받음
+    fn new(id: u32, info: &DutyInfo) -> Self {
         Self {
-            id: 0, // Placeholder, will be fixed in ApiReadableListing::from
-            name: value.name,
-            high_end: value.high_end,
-            content_kind_id: value.content_kind.as_u32(),
-            content_kind: format!("{:?}", value.content_kind),
+            id,
+            name: ApiText::All(info.name),
+            high_end: info.high_end,
+            content_kind: ApiContentKind(info.content_kind),
         }
     }
 }
 
+/// 콘텐츠 종류 (`content_kind_id`와 `content_kind` 이름 두 필드로 직렬화)
+struct ApiContentKind(ContentKind);
+
+impl Serialize for ApiContentKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("ApiContentKind", 2)?;
+        state.serialize_field("content_kind_id", &self.0.as_u32())?;
+        state.serialize_field("content_kind", &format!("{:?}", self.0))?;
+        state.end()
+    }
+}
+
 #[derive(Serialize)]
 struct ApiReadableObjectiveFlags {
     duty_completion: bool,
@@ -370,6 +1087,8 @@ struct ApiReadableDutyFinderSettingsFlags {
     undersized_party: bool,
     minimum_item_level: bool,
     silence_echo: bool,
+    // Bits the plugin sent that have no name yet (0 when none)
+    unknown_bits: u32,
 }
 
 impl From<DutyFinderSettingsFlags> for ApiReadableDutyFinderSettingsFlags {
@@ -378,6 +1097,7 @@ impl From<DutyFinderSettingsFlags> for ApiReadableDutyFinderSettingsFlags {
             undersized_party: value.contains(DutyFinderSettingsFlags::UNDERSIZED_PARTY),
             minimum_item_level: value.contains(DutyFinderSettingsFlags::MINIMUM_ITEM_LEVEL),
             silence_echo: value.contains(DutyFinderSettingsFlags::SILENCE_ECHO),
+            unknown_bits: value.unknown_bits(),
         }
     }
 }
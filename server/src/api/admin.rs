@@ -0,0 +1,1015 @@
+//! 관리자 전용 API (`/api/admin/...`)
+//!
+//! 모든 엔드포인트는 `Authorization: Bearer <admin.token>` 헤더를 요구합니다.
+//! 설정에 `[admin]` 섹션이 없으면 모든 요청이 401로 거부됩니다.
+//! 관리자 페이지(`/admin`)의 세션 쿠키도 받으며, 이때 GET이 아닌 요청은 `csrf` 쿼리가 필요합니다.
+//! POST 본문은 JSON과 폼(`application/x-www-form-urlencoded`) 모두 받습니다.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use warp::filters::BoxedFilter;
+use warp::http::{Method, StatusCode};
+use warp::hyper::body::Buf;
+use warp::{Filter, Rejection, Reply};
+
+use crate::fflogs::backfill::{refresh_listing_parses, RefreshTarget, REFRESH_TIMEOUT};
+use crate::fflogs::refetch::refetch_targets;
+use crate::fflogs::{merge_zone_caches, ParseCacheDoc};
+use crate::listing::{ExpiryInfo, SearchAreaFlags};
+use crate::config::ListingSort;
+use crate::listing_container::{updated_bucket, ListingContainer, QueriedListing};
+use crate::mongo::{
+    get_parse_docs, get_raw_listing, invalidate_zone_caches, parse_docs_cursor, set_fetch_accounting,
+    upsert_zone_cache, upsert_zone_caches,
+};
+use crate::web::background::start_player_compaction;
+use crate::web::listings_cache::ListingsCacheStats;
+use crate::web::admin_page::{AdminSessions, SESSION_COOKIE};
+use crate::web::maintenance::MaintenanceOverride;
+use crate::web::State;
+
+/// 가져오기 시 한 번에 병합/저장하는 문서 수
+const IMPORT_BATCH_SIZE: usize = 200;
+
+pub fn admin(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    warp::path("admin")
+        .and(admin_auth(Arc::clone(&state)))
+        .and(
+            parse_cache_export(Arc::clone(&state))
+                .or(parse_cache_import(Arc::clone(&state)))
+                .or(ingestion(Arc::clone(&state)))
+                .or(raw_listing(Arc::clone(&state)))
+                .or(unknown_ids(Arc::clone(&state)))
+                .or(blocklist(Arc::clone(&state)))
+                .or(parses_invalidate(Arc::clone(&state)))
+                .or(refresh_parses(Arc::clone(&state)))
+                .or(player_compaction(Arc::clone(&state)))
+                .or(maintenance(Arc::clone(&state)))
+                .or(logging(Arc::clone(&state))),
+        )
+        .recover(handle_rejection)
+        .boxed()
+}
+
+/// 관리자 토큰 검증 실패
+#[derive(Debug)]
+struct Unauthorized;
+
+impl warp::reject::Reject for Unauthorized {}
+
+/// 세션 쿠키로 보낸 요청의 CSRF 토큰 검증 실패
+#[derive(Debug)]
+struct Forbidden;
+
+impl warp::reject::Reject for Forbidden {}
+
+/// 관리자 요청 인증 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AdminAuth {
+    Allowed,
+    /// 토큰도 유효한 세션도 없음 (401)
+    Unauthorized,
+    /// 세션은 유효하지만 CSRF 토큰이 없거나 틀림 (403)
+    Forbidden,
+}
+
+/// `Authorization` 헤더 또는 관리자 페이지 세션 쿠키 확인
+pub(crate) fn authorize(
+    sessions: Option<&AdminSessions>,
+    method: &Method,
+    authorization: Option<&str>,
+    session: Option<&str>,
+    csrf: Option<&str>,
+    now: DateTime<Utc>,
+) -> AdminAuth {
+    let Some(sessions) = sessions else {
+        return AdminAuth::Unauthorized;
+    };
+
+    if authorization
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .is_some_and(|token| sessions.check_token(token))
+    {
+        return AdminAuth::Allowed;
+    }
+
+    let Some(session) = session.filter(|session| sessions.verify(session, now)) else {
+        return AdminAuth::Unauthorized;
+    };
+    if method == Method::GET || method == Method::HEAD || csrf.is_some_and(|csrf| sessions.verify_csrf(session, csrf)) {
+        AdminAuth::Allowed
+    } else {
+        AdminAuth::Forbidden
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CsrfQuery {
+    csrf: Option<String>,
+}
+
+/// 관리자 토큰 또는 관리자 페이지 세션을 확인하는 필터
+pub fn admin_auth(state: Arc<State>) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::method()
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::cookie::optional::<String>(SESSION_COOKIE))
+        .and(warp::query::<CsrfQuery>().or(warp::any().map(|| CsrfQuery { csrf: None })).unify())
+        .and_then(move |method: Method, header: Option<String>, session: Option<String>, query: CsrfQuery| {
+            let state = Arc::clone(&state);
+            async move {
+                let sessions = AdminSessions::from_state(&state);
+                match authorize(
+                    sessions.as_ref(),
+                    &method,
+                    header.as_deref(),
+                    session.as_deref(),
+                    query.csrf.as_deref(),
+                    Utc::now(),
+                ) {
+                    AdminAuth::Allowed => Ok(()),
+                    AdminAuth::Unauthorized => Err(warp::reject::custom(Unauthorized)),
+                    AdminAuth::Forbidden => Err(warp::reject::custom(Forbidden)),
+                }
+            }
+        })
+        .untuple_one()
+}
+
+/// JSON 또는 폼 본문 (관리자 페이지의 폼은 JSON을 보낼 수 없음)
+fn json_or_form<T: DeserializeOwned + Send + 'static>() -> impl Filter<Extract = (T,), Error = Rejection> + Clone {
+    warp::body::json().or(warp::body::form()).unify()
+}
+
+async fn handle_rejection(rejection: Rejection) -> Result<warp::reply::Response, Rejection> {
+    let (status, error) = if rejection.find::<Unauthorized>().is_some() {
+        (StatusCode::UNAUTHORIZED, "unauthorized")
+    } else if rejection.find::<Forbidden>().is_some() {
+        (StatusCode::FORBIDDEN, "invalid csrf token")
+    } else {
+        return Err(rejection);
+    };
+
+    Ok(warp::reply::with_status(warp::reply::json(&serde_json::json!({ "error": error })), status).into_response())
+}
+
+// =============================================================================
+// Parse 캐시 내보내기 / 가져오기 (NDJSON)
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+struct ExportQuery {
+    /// 이 content_id 이후부터 내보내기 (이전 응답의 `next_after`)
+    after: Option<i64>,
+    /// 최대 문서 수 (없으면 전체)
+    limit: Option<i64>,
+}
+
+/// 내보내기 마지막 줄에 붙는 요약
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ExportSummary {
+    pub exported: usize,
+    pub errors: usize,
+    /// `limit`에 도달했을 때 다음 요청에 넘길 `after` 값
+    pub next_after: Option<i64>,
+}
+
+/// NDJSON 한 줄 (문서 또는 내보내기 요약)
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ImportLine {
+    Summary {
+        #[allow(unused)]
+        summary: ExportSummary,
+    },
+    Doc(ParseCacheDoc),
+}
+
+/// 가져오기 결과
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct ImportReport {
+    /// 새로 쓰거나 갱신한 문서 수
+    pub upserted_docs: usize,
+    /// 저장된 Zone 수
+    pub zones_written: usize,
+    /// 양쪽에 모두 존재한 Zone 수
+    pub conflicts: usize,
+    /// 충돌 중 기존 데이터가 더 최신이라 유지된 Zone 수
+    pub kept_existing: usize,
+    /// 변경 사항이 없어 건너뛴 문서 수
+    pub unchanged_docs: usize,
+    /// 파싱할 수 없는 줄 수
+    pub malformed_lines: usize,
+    /// DB 오류로 저장하지 못한 문서 수
+    pub failed_docs: usize,
+    /// 요청 본문을 끝까지 읽지 못했는지 여부
+    pub aborted: bool,
+}
+
+/// 문서를 NDJSON 한 줄로 직렬화 (줄바꿈 포함)
+pub(crate) fn export_line(doc: &ParseCacheDoc) -> serde_json::Result<String> {
+    let mut line = serde_json::to_string(doc)?;
+    line.push('\n');
+    Ok(line)
+}
+
+/// NDJSON 한 줄 파싱
+///
+/// 빈 줄과 내보내기 요약 줄은 `Ok(None)`을 반환합니다.
+pub(crate) fn parse_import_line(line: &[u8]) -> serde_json::Result<Option<ParseCacheDoc>> {
+    if line.iter().all(|b| b.is_ascii_whitespace()) {
+        return Ok(None);
+    }
+
+    Ok(match serde_json::from_slice::<ImportLine>(line)? {
+        ImportLine::Summary { .. } => None,
+        ImportLine::Doc(doc) => Some(doc),
+    })
+}
+
+/// 버퍼에서 완성된 줄들을 꺼냄 (마지막 미완성 줄은 버퍼에 남김)
+pub(crate) fn drain_lines(pending: &mut Vec<u8>) -> Vec<Vec<u8>> {
+    let mut lines = Vec::new();
+    while let Some(pos) = pending.iter().position(|&b| b == b'\n') {
+        let mut line: Vec<u8> = pending.drain(..=pos).collect();
+        line.pop();
+        lines.push(line);
+    }
+    lines
+}
+
+fn parse_cache_export(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(state: Arc<State>, query: ExportQuery) -> Result<warp::reply::Response, Infallible> {
+        let cursor = match parse_docs_cursor(state.parse_collection().primary(), query.after, query.limit).await {
+            Ok(cursor) => cursor,
+            Err(e) => {
+                tracing::error!("Failed to open parse cache cursor: {:#?}", e);
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+
+        let limit = query.limit;
+        let body = async_stream::stream! {
+            let mut cursor = cursor;
+            let mut exported = 0;
+            let mut errors = 0;
+            let mut last_id = None;
+
+            while let Some(res) = cursor.next().await {
+                let line = res
+                    .map_err(anyhow::Error::from)
+                    .and_then(|doc| {
+                        last_id = Some(doc.content_id);
+                        export_line(&doc).map_err(anyhow::Error::from)
+                    });
+
+                match line {
+                    Ok(line) => {
+                        exported += 1;
+                        yield Ok::<_, std::io::Error>(line);
+                    }
+                    Err(e) => {
+                        errors += 1;
+                        tracing::warn!("Skipping parse cache document during export: {:?}", e);
+                    }
+                }
+            }
+
+            let next_after = match limit {
+                Some(limit) if (exported + errors) as i64 >= limit => last_id,
+                _ => None,
+            };
+            tracing::info!("[Admin] Parse cache export: {} exported, {} errors", exported, errors);
+
+            let summary = serde_json::json!({
+                "summary": ExportSummary { exported, errors, next_after },
+            });
+            yield Ok(format!("{}\n", summary));
+        };
+
+        Ok(warp::http::Response::builder()
+            .header("content-type", "application/x-ndjson")
+            .body(warp::hyper::Body::wrap_stream(body))
+            .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response()))
+    }
+
+    warp::get()
+        .and(warp::path("parse_cache"))
+        .and(warp::path("export"))
+        .and(warp::path::end())
+        .and(warp::query::<ExportQuery>())
+        .and_then(move |query: ExportQuery| logic(Arc::clone(&state), query))
+        .boxed()
+}
+
+fn parse_cache_import(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    warp::post()
+        .and(warp::path("parse_cache"))
+        .and(warp::path("import"))
+        .and(warp::path::end())
+        .and(warp::body::stream())
+        .and_then(move |body| import_logic(Arc::clone(&state), body))
+        .boxed()
+}
+
+async fn import_logic<S, B>(state: Arc<State>, body: S) -> Result<warp::reply::Response, Infallible>
+where
+    S: Stream<Item = Result<B, warp::Error>>,
+    B: Buf,
+{
+    let mut body = Box::pin(body);
+    let mut report = ImportReport::default();
+    let mut pending = Vec::new();
+    let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+
+    while let Some(chunk) = body.next().await {
+        let mut chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                tracing::warn!("[Admin] Parse cache import body error: {:?}", e);
+                report.aborted = true;
+                break;
+            }
+        };
+
+        while chunk.has_remaining() {
+            let bytes = chunk.chunk();
+            let len = bytes.len();
+            pending.extend_from_slice(bytes);
+            chunk.advance(len);
+        }
+
+        for line in drain_lines(&mut pending) {
+            push_import_line(&line, &mut batch, &mut report);
+            if batch.len() >= IMPORT_BATCH_SIZE {
+                flush_import_batch(&state, &mut batch, &mut report).await;
+            }
+        }
+    }
+
+    if !report.aborted && !pending.is_empty() {
+        push_import_line(&pending, &mut batch, &mut report);
+    }
+    flush_import_batch(&state, &mut batch, &mut report).await;
+
+    tracing::info!("[Admin] Parse cache import: {:?}", report);
+    Ok(warp::reply::json(&report).into_response())
+}
+
+fn push_import_line(line: &[u8], batch: &mut Vec<ParseCacheDoc>, report: &mut ImportReport) {
+    match parse_import_line(line) {
+        Ok(Some(doc)) => batch.push(doc),
+        Ok(None) => {}
+        Err(e) => {
+            report.malformed_lines += 1;
+            tracing::debug!("[Admin] Malformed parse cache line: {:?}", e);
+        }
+    }
+}
+
+/// 배치 단위 병합 후 저장
+///
+/// 기존 문서는 배치당 한 번의 `$in` 조회로 가져오며,
+/// 같은 배치에 동일 content_id가 여러 번 있어도 순서대로 병합됩니다.
+async fn flush_import_batch(state: &State, batch: &mut Vec<ParseCacheDoc>, report: &mut ImportReport) {
+    if batch.is_empty() {
+        return;
+    }
+
+    let content_ids: Vec<u64> = batch.iter().map(|doc| doc.content_id as u64).collect();
+    let mut existing: HashMap<u64, ParseCacheDoc> = match state
+        .parse_collection()
+        .read_by_ids(&content_ids, |collection, ids| async move { get_parse_docs(collection, &ids).await })
+        .await
+    {
+        Ok(docs) => docs,
+        Err(e) => {
+            tracing::error!("[Admin] Failed to load existing parse docs: {:?}", e);
+            report.failed_docs += batch.len();
+            batch.clear();
+            return;
+        }
+    };
+
+    for doc in batch.drain(..) {
+        let content_id = doc.content_id as u64;
+        let outcome = merge_zone_caches(existing.get(&content_id), &doc);
+        report.conflicts += outcome.conflicts;
+        report.kept_existing += outcome.kept_existing;
+
+        if outcome.zones_to_write.is_empty() {
+            report.unchanged_docs += 1;
+            continue;
+        }
+
+        match state
+            .parse_collection()
+            .write(|collection| upsert_zone_caches(collection, content_id, &outcome.zones_to_write))
+            .await
+        {
+            Ok(()) => {
+                report.upserted_docs += 1;
+                report.zones_written += outcome.zones_to_write.len();
+                state.bump_listings_revision();
+
+                let entry = existing.entry(content_id).or_insert_with(|| ParseCacheDoc {
+                    content_id: doc.content_id,
+                    zones: HashMap::new(),
+                    fetch: None,
+                });
+                entry.zones.extend(outcome.zones_to_write);
+            }
+            Err(e) => {
+                tracing::warn!("[Admin] Failed to upsert parse doc {}: {:?}", content_id, e);
+                report.failed_docs += 1;
+            }
+        }
+    }
+}
+
+// =============================================================================
+// 업로드 집계
+// =============================================================================
+
+/// 현재 모집글의 업로드 집계
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct IngestionReport {
+    pub listings: usize,
+    /// 모든 모집글의 업로드 횟수 합
+    pub uploads: u64,
+    /// 두 번 이상 업로드된 모집글 수
+    pub multi_sourced: usize,
+    /// 여러 업로더가 올린 모집글 수
+    pub multi_uploader: usize,
+    /// 현재 모집글 캐시 사용 기록
+    pub listings_cache: ListingsCacheStats,
+    pub entries: Vec<IngestionEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct IngestionEntry {
+    pub id: u32,
+    pub created_world: u16,
+    pub upload_count: u32,
+    pub uploader_count: u32,
+}
+
+impl IngestionReport {
+    pub fn from_listings(listings: &[QueriedListing]) -> Self {
+        let mut report = Self::default();
+        for ql in listings {
+            report.listings += 1;
+            report.uploads += u64::from(ql.upload_count);
+            report.multi_sourced += ql.is_multi_sourced() as usize;
+            report.multi_uploader += (ql.uploader_count >= 2) as usize;
+            report.entries.push(IngestionEntry {
+                id: ql.listing.id,
+                created_world: ql.listing.created_world,
+                upload_count: ql.upload_count,
+                uploader_count: ql.uploader_count,
+            });
+        }
+
+        report.entries.sort_by_key(|entry| std::cmp::Reverse(entry.upload_count));
+        report
+    }
+}
+
+fn ingestion(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(state: Arc<State>) -> Result<warp::reply::Response, Infallible> {
+        match state.current_listings().await {
+            Ok(listings) => {
+                let mut report = IngestionReport::from_listings(&listings);
+                report.listings_cache = state.listings_cache.stats();
+                Ok(warp::reply::json(&report).into_response())
+            }
+            Err(e) => {
+                tracing::error!("[Admin] Failed to get listings: {:#?}", e);
+                Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+            }
+        }
+    }
+
+    warp::get()
+        .and(warp::path("ingestion"))
+        .and(warp::path::end())
+        .and_then(move || logic(Arc::clone(&state)))
+        .boxed()
+}
+
+// =============================================================================
+// 저장된 모집글 원본 조회
+// =============================================================================
+
+/// 모집글 원본과 함께 반환하는 파생 값 (`get_current_listings` / 변환 파이프라인과 같은 계산)
+#[derive(Debug, Serialize)]
+pub(crate) struct ListingDiagnostics {
+    /// 남은 시간 (초, 음수면 만료)
+    pub time_left: f64,
+    /// 듀티 분류별 구간 단위로 내림한 `updated_at` (정렬 키)
+    pub updated_minute: DateTime<Utc>,
+    /// 공개 목록에 표시되지 않는 비공개 모집글 여부
+    pub private: bool,
+    pub pf_category: &'static str,
+    pub duty_name: String,
+    pub category_label: Option<&'static str>,
+    pub high_end: bool,
+    /// Parse 조회 / 표시 대상 (고난이도이면서 FFLogs 매핑이 있음)
+    pub fflogs_supported: bool,
+    pub content_kind: u32,
+    pub slots_filled: usize,
+    /// `jobs_present`의 잡 코드 (알 수 없는 ID는 `null`)
+    pub jobs: Vec<Option<&'static str>>,
+    pub fflogs: Option<FFLogsDiagnostics>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct FFLogsDiagnostics {
+    pub zone_id: u32,
+    pub encounter_id: u32,
+    pub secondary_encounter_id: Option<u32>,
+    pub difficulty_id: Option<u32>,
+    pub name: &'static str,
+}
+
+impl ListingDiagnostics {
+    pub fn new(container: &ListingContainer, now: DateTime<Utc>, sort: &ListingSort) -> Self {
+        let listing = &container.listing;
+        let expiry = ExpiryInfo::new(container.updated_at, listing.seconds_remaining, now);
+
+        Self {
+            time_left: expiry.time_left(now),
+            updated_minute: updated_bucket(container.updated_at, listing.canonical_category(), sort),
+            private: listing.search_area.contains(SearchAreaFlags::PRIVATE),
+            pf_category: listing.html_pf_category(),
+            duty_name: listing.duty_name(&crate::ffxiv::Language::English).into_owned(),
+            category_label: listing.category_label(&crate::ffxiv::Language::English),
+            high_end: listing.high_end(),
+            fflogs_supported: listing.fflogs_supported(),
+            content_kind: listing.content_kind(),
+            slots_filled: listing.filled_total(),
+            jobs: listing.jobs_present
+                .iter()
+                .map(|&job| crate::ffxiv::JOBS.get(&u32::from(job)).map(|cj| cj.code()))
+                .collect(),
+            fflogs: crate::fflogs::mapping::get_fflogs_encounter(listing.duty).map(|info| FFLogsDiagnostics {
+                zone_id: info.zone_id,
+                encounter_id: info.encounter_id,
+                secondary_encounter_id: info.secondary_encounter_id,
+                difficulty_id: info.difficulty_id,
+                name: info.name,
+            }),
+        }
+    }
+}
+
+/// GET /api/admin/listings/{id}/{created_world}/{last_server_restart}/raw
+///
+/// 저장된 문서를 canonical extended JSON으로 그대로 반환합니다.
+/// 아직 개인정보 제외(opt-out) 기능이 없으므로 가리는 필드는 없습니다.
+/// 공개 API에서 제외되는 설명 편집 이력(`description_history`)도 여기서 확인할 수 있습니다.
+fn raw_listing(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(
+        state: Arc<State>,
+        id: u32,
+        created_world: u16,
+        last_server_restart: u32,
+    ) -> Result<warp::reply::Response, Infallible> {
+        let document = match state
+            .collection()
+            .read_one(|collection| get_raw_listing(collection, id, created_world, last_server_restart))
+            .await
+        {
+            Ok(Some(document)) => document,
+            Ok(None) => return Ok(no_store(warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "error": "not found" })),
+                StatusCode::NOT_FOUND,
+            ))),
+            Err(e) => {
+                tracing::error!("[Admin] Failed to get raw listing: {:#?}", e);
+                return Ok(no_store(StatusCode::INTERNAL_SERVER_ERROR));
+            }
+        };
+
+        let diagnostics = match mongodb::bson::from_document::<ListingContainer>(document.clone()) {
+            Ok(container) => serde_json::to_value(ListingDiagnostics::new(&container, Utc::now(), &state.config.sort))
+                .unwrap_or_default(),
+            Err(e) => serde_json::json!({ "error": format!("stored document does not deserialize: {}", e) }),
+        };
+
+        let body = serde_json::json!({
+            "document": mongodb::bson::Bson::Document(document).into_canonical_extjson(),
+            "diagnostics": diagnostics,
+        });
+        Ok(no_store(warp::reply::json(&body)))
+    }
+
+    warp::get()
+        .and(warp::path!("listings" / u32 / u16 / u32 / "raw"))
+        .and_then(move |id, created_world, restart| logic(Arc::clone(&state), id, created_world, restart))
+        .boxed()
+}
+
+/// 캐시 금지 헤더 추가
+fn no_store(reply: impl Reply) -> warp::reply::Response {
+    warp::reply::with_header(reply, "cache-control", "no-store").into_response()
+}
+
+// =============================================================================
+// 미확인 게임 데이터 ID
+// =============================================================================
+
+/// GET /api/admin/unknown_ids
+fn unknown_ids(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    warp::get()
+        .and(warp::path("unknown_ids"))
+        .and(warp::path::end())
+        .map(move || warp::reply::json(&state.unknown_ids.snapshot()))
+        .boxed()
+}
+
+// =============================================================================
+// 목록 숨김 설정
+// =============================================================================
+
+/// GET /api/admin/blocklist
+///
+/// 현재 적용 중인 숨김 목록 (설정 파일을 고친 뒤 SIGHUP으로 다시 읽음)
+fn blocklist(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    warp::get()
+        .and(warp::path("blocklist"))
+        .and(warp::path::end())
+        .map(move || {
+            let blocklist = state.blocklist();
+            no_store(warp::reply::json(&serde_json::json!({
+                "duties": blocklist.duties,
+                "categories": blocklist.category_names(),
+            })))
+        })
+        .boxed()
+}
+
+// =============================================================================
+// Zone Parse 캐시 무효화
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct InvalidateRequest {
+    pub zone_id: u32,
+    /// 현재 모집글의 해당 Zone 멤버를 우선 재조회할지
+    #[serde(default)]
+    pub refetch: bool,
+    /// 이후 조회에 사용할 새 파티션 (FFLogs 파티션 변경 시)
+    #[serde(default)]
+    pub partition: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct InvalidateReport {
+    pub zone_id: u32,
+    /// 이후 조회에 사용되는 파티션
+    pub partition: Option<u32>,
+    /// 해당 Zone 캐시가 있던 문서 수
+    pub matched_docs: u64,
+    /// 실제로 만료 처리된 문서 수
+    pub modified_docs: u64,
+    /// 우선 재조회 대기열에 새로 추가된 플레이어 수
+    pub refetch_queued: usize,
+}
+
+/// POST /api/admin/parses/invalidate
+fn parses_invalidate(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(state: Arc<State>, request: InvalidateRequest) -> Result<warp::reply::Response, Infallible> {
+        let zone_id = request.zone_id;
+        if !crate::fflogs::FFLOGS_ZONES.contains_key(&zone_id) {
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "error": "unknown zone" })),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response());
+        }
+
+        if let Some(partition) = request.partition {
+            state.zone_partitions.set(zone_id, partition);
+        }
+
+        let result = match state
+            .parse_collection()
+            .write(|collection| invalidate_zone_caches(collection, zone_id))
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::error!("Failed to invalidate zone {} caches: {:#?}", zone_id, e);
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+
+        if let Some(client) = &state.fflogs_client {
+            client.forget_zone(zone_id);
+        }
+
+        let mut refetch_queued = 0;
+        if request.refetch {
+            match state.current_listings().await {
+                Ok(listings) => {
+                    refetch_queued = state.parse_refetch.enqueue(zone_id, refetch_targets(&listings, zone_id));
+                }
+                Err(e) => tracing::warn!("Could not load listings for zone {} refetch: {:#?}", zone_id, e),
+            }
+        }
+
+        let report = InvalidateReport {
+            zone_id,
+            partition: state.zone_partitions.get(zone_id),
+            matched_docs: result.matched_count,
+            modified_docs: result.modified_count,
+            refetch_queued,
+        };
+        tracing::info!("[Admin] Zone cache invalidation: {:?}", report);
+
+        Ok(warp::reply::json(&report).into_response())
+    }
+
+    warp::post()
+        .and(warp::path("parses"))
+        .and(warp::path("invalidate"))
+        .and(warp::path::end())
+        .and(json_or_form())
+        .and_then(move |request: InvalidateRequest| logic(Arc::clone(&state), request))
+        .boxed()
+}
+
+/// 모집글 멤버 + 파티장 (0 제외, 중복 없음, 파티장 먼저)
+pub(crate) fn listing_parse_members(container: &ListingContainer) -> Vec<(u64, bool)> {
+    let leader = container.listing.leader_content_id;
+    let mut members: Vec<(u64, bool)> = Vec::new();
+    let ids = std::iter::once(leader).chain(container.listing.member_content_ids.iter().map(|&id| id as u64));
+    for id in ids.filter(|&id| id != 0) {
+        if !members.iter().any(|(known, _)| *known == id) {
+            members.push((id, id == leader));
+        }
+    }
+    members
+}
+
+/// POST /api/admin/listings/{id}/{created_world}/{last_server_restart}/refresh_parses
+///
+/// 모집글 멤버와 파티장의 Parse를 캐시 만료와 무관하게 바로 다시 조회해 저장하고,
+/// 멤버별로 새 값 / 이전 캐시 값 / 조회 에러를 반환합니다.
+/// FFLogs 요청 예산이 소진됐으면 조회하지 않고 429를 반환합니다.
+fn refresh_parses(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(
+        state: Arc<State>,
+        id: u32,
+        created_world: u16,
+        last_server_restart: u32,
+    ) -> Result<warp::reply::Response, Infallible> {
+        let error = |status: StatusCode, message: &str| {
+            no_store(warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "error": message })),
+                status,
+            ))
+        };
+
+        let Some(client) = state.fflogs_client.as_ref() else {
+            return Ok(error(StatusCode::SERVICE_UNAVAILABLE, "FFLogs is not configured"));
+        };
+
+        let container = match state
+            .collection()
+            .read_one(|collection| get_raw_listing(collection, id, created_world, last_server_restart))
+            .await
+            .map(|document| document.map(mongodb::bson::from_document::<ListingContainer>))
+        {
+            Ok(Some(Ok(container))) => container,
+            Ok(None) => return Ok(error(StatusCode::NOT_FOUND, "not found")),
+            Ok(Some(Err(e))) => {
+                tracing::error!("[Admin] Stored listing does not deserialize: {:#?}", e);
+                return Ok(no_store(StatusCode::INTERNAL_SERVER_ERROR));
+            }
+            Err(e) => {
+                tracing::error!("[Admin] Failed to get listing for parse refresh: {:#?}", e);
+                return Ok(no_store(StatusCode::INTERNAL_SERVER_ERROR));
+            }
+        };
+
+        let Some(encounter) = container.listing.fflogs_encounter() else {
+            return Ok(error(StatusCode::BAD_REQUEST, "listing has no FFLogs parses"));
+        };
+
+        let members = listing_parse_members(&container);
+        let content_ids: Vec<u64> = members.iter().map(|(id, _)| *id).collect();
+        let (players, previous) = match futures_util::try_join!(
+            state.players_by_content_ids(&content_ids),
+            state.parse_collection().read_by_ids(&content_ids, |collection, ids| async move {
+                get_parse_docs(collection, &ids).await
+            }),
+        ) {
+            Ok(found) => found,
+            Err(e) => {
+                tracing::error!("[Admin] Failed to load members for parse refresh: {:#?}", e);
+                return Ok(no_store(StatusCode::INTERNAL_SERVER_ERROR));
+            }
+        };
+
+        let targets = members
+            .into_iter()
+            .map(|(content_id, leader)| RefreshTarget {
+                content_id,
+                leader,
+                player: players.iter().find(|player| player.content_id == content_id).cloned(),
+            })
+            .collect();
+
+        let partition = state.zone_partitions.get(encounter.zone_id);
+        let refresh = match refresh_listing_parses(client, targets, encounter, partition, &previous, REFRESH_TIMEOUT).await {
+            Ok(refresh) => refresh,
+            Err(exhausted) => {
+                let reply = error(StatusCode::TOO_MANY_REQUESTS, &exhausted.to_string());
+                return Ok(warp::reply::with_header(
+                    reply,
+                    "retry-after",
+                    exhausted.retry_after.as_secs().max(1).to_string(),
+                )
+                .into_response());
+            }
+        };
+
+        let mut previous = previous;
+        for (content_id, zone_cache) in &refresh.zone_caches {
+            if let Err(e) = state
+                .parse_collection()
+                .write(|collection| upsert_zone_cache(collection, *content_id, encounter.zone_id, zone_cache))
+                .await
+            {
+                tracing::warn!("[Admin] Failed to save refreshed parses for {}: {:#?}", content_id, e);
+            }
+
+            let percentiles: Vec<(u32, f32)> = zone_cache
+                .encounters
+                .iter()
+                .filter_map(|(id, parse)| Some((id.parse().ok()?, parse.percentile)))
+                .collect();
+            let fetch = previous
+                .entry(*content_id)
+                .or_insert_with(|| ParseCacheDoc {
+                    content_id: *content_id as i64,
+                    zones: HashMap::new(),
+                    fetch: None,
+                })
+                .fetch
+                .get_or_insert_with(Default::default);
+            fetch.record(encounter.zone_id, crate::fflogs::is_empty_result(&percentiles), zone_cache.fetched_at);
+            if let Err(e) = state
+                .parse_collection()
+                .write(|collection| set_fetch_accounting(collection, *content_id, fetch))
+                .await
+            {
+                tracing::warn!("[Admin] Failed to record fetch for {}: {:#?}", content_id, e);
+            }
+        }
+
+        if !refresh.zone_caches.is_empty() {
+            state.bump_listings_revision();
+        }
+        tracing::info!(
+            "[Admin] Refreshed parses for listing {}/{}/{}: {} of {} members fetched",
+            id,
+            created_world,
+            last_server_restart,
+            refresh.zone_caches.len(),
+            refresh.report.members.len(),
+        );
+        Ok(no_store(warp::reply::json(&refresh.report)))
+    }
+
+    warp::post()
+        .and(warp::path!("listings" / u32 / u16 / u32 / "refresh_parses"))
+        .and_then(move |id, created_world, restart| logic(Arc::clone(&state), id, created_world, restart))
+        .boxed()
+}
+
+#[derive(Debug, Deserialize)]
+struct MaintenanceRequest {
+    mode: MaintenanceOverride,
+}
+
+/// GET/POST /api/admin/maintenance
+///
+/// 수동 설정은 점검 일정과 자동 감지보다 우선합니다 (`auto`로 되돌림).
+fn maintenance(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    let status_state = Arc::clone(&state);
+    let status = warp::get().map(move || no_store(warp::reply::json(&status_state.maintenance.status(Utc::now()))));
+
+    let update = warp::post()
+        .and(json_or_form())
+        .map(move |request: MaintenanceRequest| {
+            state.maintenance.set_mode(request.mode);
+            tracing::info!("[Admin] Maintenance mode set to {:?}", request.mode);
+            no_store(warp::reply::json(&state.maintenance.status(Utc::now())))
+        });
+
+    warp::path("maintenance")
+        .and(warp::path::end())
+        .and(status.or(update).unify())
+        .boxed()
+}
+
+// =============================================================================
+// 중복 플레이어 병합
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+struct CompactionRequest {
+    /// 병합하지 않고 보고서만 생성 (기본값 true)
+    #[serde(default = "default_dry_run")]
+    dry_run: bool,
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+/// GET/POST /api/admin/players/compaction
+///
+/// GET은 실행 중 여부와 마지막 보고서를, POST는 백그라운드 실행을 시작합니다 (이미 실행 중이면 409).
+/// 먼저 `dry_run`으로 보고서를 확인한 뒤 `{"dry_run": false}`로 실행하세요.
+fn player_compaction(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    let status_state = Arc::clone(&state);
+    let status = warp::get().map(move || {
+        no_store(warp::reply::json(&serde_json::json!({
+            "running": status_state.player_compaction.running(),
+            "last": status_state.player_compaction.last_report(),
+        })))
+    });
+
+    let start = warp::post()
+        .and(json_or_form())
+        .map(move |request: CompactionRequest| {
+            if !start_player_compaction(Arc::clone(&state), request.dry_run, "admin") {
+                return warp::reply::with_status(
+                    warp::reply::json(&serde_json::json!({ "error": "player compaction already running" })),
+                    StatusCode::CONFLICT,
+                )
+                .into_response();
+            }
+
+            tracing::info!("[Admin] Player compaction started (dry run: {})", request.dry_run);
+            warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "started": true, "dry_run": request.dry_run })),
+                StatusCode::ACCEPTED,
+            )
+            .into_response()
+        });
+
+    warp::path("players")
+        .and(warp::path("compaction"))
+        .and(warp::path::end())
+        .and(status.or(start).unify())
+        .boxed()
+}
+
+// =============================================================================
+// 로그 레벨
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+struct LoggingRequest {
+    /// `EnvFilter` 지시어 (예: `"info,remote_party_finder::infra::fflogs=debug"`)
+    directives: String,
+}
+
+/// GET/POST /api/admin/logging
+///
+/// 재시작 없이 로그 레벨을 바꿉니다. 재시작하면 설정 파일의 값으로 돌아갑니다.
+fn logging(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    let current_state = Arc::clone(&state);
+    let current = warp::get().map(move || {
+        no_store(warp::reply::json(&serde_json::json!({ "directives": current_state.log_handle.directives() })))
+    });
+
+    let update = warp::post()
+        .and(json_or_form())
+        .map(move |request: LoggingRequest| match state.log_handle.set_directives(&request.directives) {
+            Ok(()) => {
+                tracing::info!("[Admin] Log directives set to `{}`", request.directives);
+                no_store(warp::reply::json(&serde_json::json!({ "directives": state.log_handle.directives() })))
+            }
+            Err(e) => warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "error": format!("{:#}", e) })),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response(),
+        });
+
+    warp::path("logging")
+        .and(warp::path::end())
+        .and(current.or(update).unify())
+        .boxed()
+}
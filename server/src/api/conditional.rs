@@ -0,0 +1,36 @@
+//! `/api/listings` 조건부 요청 (ETag / `If-None-Match`)
+//!
+//! ETag는 응답할 모집글의 키와 업데이트 시각, `State::listings_revision`으로 만듭니다.
+//! 리비전은 업데이트 시각이 바뀌지 않는 변경(멤버 / 플레이어 이름 / Parse 저장 등)마다 증가합니다.
+//! 남은 시간처럼 시간이 지나며 바뀌는 값은 ETag에 넣지 않습니다.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::ffxiv::Language;
+use crate::listing_container::QueriedListing;
+
+/// 모집글 목록 응답의 ETag (따옴표 포함)
+pub fn listings_etag(revision: u64, listings: &[QueriedListing]) -> String {
+    let mut hasher = DefaultHasher::new();
+    revision.hash(&mut hasher);
+    for container in listings {
+        let listing = &container.listing;
+        (listing.id, listing.created_world, listing.last_server_restart).hash(&mut hasher);
+        container.updated_at.timestamp_millis().hash(&mut hasher);
+    }
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+/// 지역화 응답의 ETag (같은 목록이라도 언어마다 본문이 다름)
+pub fn localized_etag(etag: &str, lang: Language) -> String {
+    format!("\"{}-{}\"", etag.trim_matches('"'), lang.code())
+}
+
+/// `If-None-Match` 값에 `etag`가 있는지 (`*`와 약한 비교 포함)
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+}
@@ -0,0 +1,108 @@
+//! `/api` 경로의 CORS (`[web.cors]`)
+//!
+//! 허용한 출처에서 온 요청의 응답에 `Access-Control-Allow-Origin`을 붙이고,
+//! preflight(`OPTIONS`)는 라우트와 데이터베이스를 거치지 않고 바로 응답합니다.
+//! 허용하지 않은 출처의 요청은 CORS 헤더 없이 그대로 처리하므로 브라우저만 응답을 막고,
+//! 같은 출처 페이지(웹소켓 포함)와 서버 간 호출은 영향을 받지 않습니다.
+
+use warp::filters::BoxedFilter;
+use warp::http::header::{self, HeaderMap, HeaderValue};
+use warp::http::StatusCode;
+use warp::reply::Response;
+use warp::{Filter, Reply};
+
+use crate::config::Cors;
+
+/// 브라우저 스크립트가 읽을 수 있게 노출하는 응답 헤더
+pub const EXPOSED_HEADERS: &str = "etag, x-rpf-version";
+
+/// `origin`에 돌려줄 `Access-Control-Allow-Origin` 값 (허용하지 않으면 `None`)
+pub fn allowed_origin<'a>(config: &Cors, origin: &'a str) -> Option<&'a str> {
+    if config.allowed_origins.iter().any(|allowed| allowed == "*") {
+        Some("*")
+    } else {
+        config
+            .allowed_origins
+            .iter()
+            .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+            .then_some(origin)
+    }
+}
+
+/// preflight 요청의 메서드와 헤더가 모두 허용되는지
+pub fn preflight_allowed(config: &Cors, method: &str, request_headers: Option<&str>) -> bool {
+    let method_allowed = config.allowed_methods.iter().any(|allowed| allowed.eq_ignore_ascii_case(method));
+    let headers_allowed = request_headers
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .all(|name| config.allowed_headers.iter().any(|allowed| allowed.eq_ignore_ascii_case(name)));
+    method_allowed && headers_allowed
+}
+
+/// 허용한 출처의 응답 헤더 (출처를 그대로 돌려주면 캐시가 출처별로 나뉘도록 `Vary: Origin`)
+fn insert_origin(headers: &mut HeaderMap, allow_origin: &str) {
+    if let Ok(value) = HeaderValue::from_str(allow_origin) {
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, value);
+    }
+    if allow_origin != "*" {
+        headers.append(header::VARY, HeaderValue::from_static("origin"));
+    }
+}
+
+/// OPTIONS /api/** preflight (설정이 없으면 없는 경로)
+///
+/// 허용하지 않은 출처 / 메서드 / 헤더는 CORS 헤더 없이 `403`으로 응답합니다.
+pub fn preflight(config: Option<Cors>) -> BoxedFilter<(Response,)> {
+    warp::options()
+        .and(warp::header::<String>("origin"))
+        .and(warp::header::<String>("access-control-request-method"))
+        .and(warp::header::optional::<String>("access-control-request-headers"))
+        .and_then(move |origin: String, method: String, request_headers: Option<String>| {
+            let config = config.clone();
+            async move {
+                let Some(config) = config else {
+                    return Err(warp::reject::not_found());
+                };
+                let allow_origin = allowed_origin(&config, &origin)
+                    .filter(|_| preflight_allowed(&config, &method, request_headers.as_deref()));
+                let Some(allow_origin) = allow_origin else {
+                    return Ok(StatusCode::FORBIDDEN.into_response());
+                };
+
+                let mut response = StatusCode::NO_CONTENT.into_response();
+                let headers = response.headers_mut();
+                insert_origin(headers, allow_origin);
+                for (name, values) in [
+                    (header::ACCESS_CONTROL_ALLOW_METHODS, &config.allowed_methods),
+                    (header::ACCESS_CONTROL_ALLOW_HEADERS, &config.allowed_headers),
+                ] {
+                    if let Ok(value) = HeaderValue::from_str(&values.join(", ")) {
+                        headers.insert(name, value);
+                    }
+                }
+                headers.insert(header::ACCESS_CONTROL_MAX_AGE, HeaderValue::from(config.max_age_secs));
+                Ok(response)
+            }
+        })
+        .boxed()
+}
+
+/// `routes` 응답에 CORS 헤더를 붙임 (설정이 없거나 허용하지 않은 출처면 그대로)
+pub fn with_headers<R: Reply + 'static>(config: Option<Cors>, routes: BoxedFilter<(R,)>) -> BoxedFilter<(Response,)> {
+    warp::header::optional::<String>("origin")
+        .and(routes)
+        .map(move |origin: Option<String>, reply: R| {
+            let mut response = reply.into_response();
+            let allow_origin =
+                config.as_ref().zip(origin.as_deref()).and_then(|(config, origin)| allowed_origin(config, origin));
+            if let Some(allow_origin) = allow_origin {
+                let headers = response.headers_mut();
+                insert_origin(headers, allow_origin);
+                headers.insert(header::ACCESS_CONTROL_EXPOSE_HEADERS, HeaderValue::from_static(EXPOSED_HEADERS));
+            }
+            response
+        })
+        .boxed()
+}
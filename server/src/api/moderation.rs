@@ -0,0 +1,136 @@
+//! 모더레이터 API (`/api/moderation/...`)
+//!
+//! 모든 엔드포인트는 `Authorization: Bearer <token>` 헤더에 `[[moderation.tokens]]`의 토큰을 요구합니다.
+//! 관리자 토큰과 별개이며, 설정에 토큰이 없으면 모든 요청이 401로 거부됩니다.
+//! 숨김 / 해제는 `moderation_log`에 토큰 이름과 함께 기록합니다.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::Deserialize;
+use warp::filters::BoxedFilter;
+use warp::http::StatusCode;
+use warp::{Filter, Rejection, Reply};
+
+use crate::config::Moderation;
+use crate::listing::moderation::{ModerationAction, ModerationRecord, ModerationTarget};
+use crate::mongo::{insert_moderation_record, set_listing_hidden};
+use crate::web::token_compare::tokens_match;
+use crate::web::upload_body;
+use crate::web::State;
+
+pub fn moderation(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    warp::path("moderation")
+        .and(moderator(Arc::clone(&state)))
+        .and(
+            warp::path("hide")
+                .map(|| ModerationAction::Hide)
+                .or(warp::path("unhide").map(|| ModerationAction::Unhide))
+                .unify(),
+        )
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(upload_body::json(4))
+        .and_then(move |moderator: String, action: ModerationAction, request: ModerationRequest| {
+            set_hidden(Arc::clone(&state), moderator, action, request)
+        })
+        .recover(handle_rejection)
+        .recover(upload_body::invalid_body)
+        .boxed()
+}
+
+/// 모더레이터 토큰이 없거나 틀림
+#[derive(Debug)]
+struct Unauthorized;
+
+impl warp::reject::Reject for Unauthorized {}
+
+/// `Authorization` 헤더 확인 (맞는 토큰이면 그 토큰의 이름)
+pub fn authorize<'a>(moderation: Option<&'a Moderation>, authorization: Option<&str>) -> Option<&'a str> {
+    let provided = authorization
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .filter(|token| !token.is_empty())?;
+    moderation?
+        .tokens
+        .iter()
+        .find(|token| tokens_match(&token.token, provided))
+        .map(|token| token.name.as_str())
+}
+
+/// 모더레이터 토큰을 확인하고 토큰 이름을 넘기는 필터
+fn moderator(state: Arc<State>) -> impl Filter<Extract = (String,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("authorization").and_then(move |authorization: Option<String>| {
+        let name = authorize(state.config.moderation.as_ref(), authorization.as_deref()).map(str::to_string);
+        async move { name.ok_or_else(|| warp::reject::custom(Unauthorized)) }
+    })
+}
+
+async fn handle_rejection(rejection: Rejection) -> Result<warp::reply::Response, Rejection> {
+    if rejection.find::<Unauthorized>().is_none() {
+        return Err(rejection);
+    }
+
+    let reply = warp::reply::json(&serde_json::json!({ "error": "unauthorized" }));
+    Ok(warp::reply::with_status(reply, StatusCode::UNAUTHORIZED).into_response())
+}
+
+#[derive(Debug, Deserialize)]
+struct ModerationRequest {
+    #[serde(flatten)]
+    target: ModerationTarget,
+    /// 숨긴 이유 (기록에만 남음)
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// POST /api/moderation/hide, /api/moderation/unhide
+///
+/// 본문은 `{"id": 123, "created_world": 73, "last_server_restart": 1700000000, "reason": "..."}`
+/// (`reason` 외에는 필수, 빠지면 400)이며, 조건에 맞는 모집글이 없으면 404입니다.
+/// 숨긴 모집글은 목록 캐시를 비운 뒤 다음 사라짐 확인 주기에 웹소켓으로 `Removed`가 나갑니다.
+async fn set_hidden(
+    state: Arc<State>,
+    moderator: String,
+    action: ModerationAction,
+    request: ModerationRequest,
+) -> Result<warp::reply::Response, Infallible> {
+    let target = request.target;
+    let result = state
+        .collection()
+        .write(|collection| async move { set_listing_hidden(collection, &target, action.hidden()).await })
+        .await;
+    let matched = match result {
+        Ok(matched) => matched,
+        Err(e) => {
+            state.metrics.record_mongo_error("set_listing_hidden");
+            tracing::error!("[Moderation] Failed to {:?} listing {:?}: {:#?}", action, target, e);
+            let reply = warp::reply::json(&serde_json::json!({ "error": "could not update listing" }));
+            return Ok(warp::reply::with_status(reply, StatusCode::INTERNAL_SERVER_ERROR).into_response());
+        }
+    };
+    if matched == 0 {
+        let reply = warp::reply::json(&serde_json::json!({ "error": "listing not found" }));
+        return Ok(warp::reply::with_status(reply, StatusCode::NOT_FOUND).into_response());
+    }
+
+    state.listings_cache.invalidate();
+    state.bump_listings_revision();
+    tracing::info!("[Moderation] {} set {:?} on {} listing(s) matching {:?}", moderator, action, matched, target);
+
+    let record = ModerationRecord {
+        action,
+        moderator,
+        target,
+        matched,
+        reason: request.reason,
+        at: Utc::now(),
+    };
+    if let Err(e) = insert_moderation_record(state.moderation_log_collection(), &record).await {
+        state.metrics.record_mongo_error("insert_moderation_record");
+        tracing::error!("[Moderation] Failed to record {:?}: {:#?}", record, e);
+    }
+
+    let reply = warp::reply::json(&serde_json::json!({ "action": action, "matched": matched }));
+    Ok(warp::reply::with_status(reply, StatusCode::OK).into_response())
+}
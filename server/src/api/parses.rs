@@ -0,0 +1,151 @@
+//! 플레이어 FFLogs Parse 캐시 조회 (`GET /api/parses/{content_id}`)
+//!
+//! 백그라운드 FFLogs 조회가 모은 Zone별 기록을 그대로 보여줍니다.
+//! 모집글에는 해당 듀티의 Parse만 표시되므로, 플러그인이 툴팁에 티어 전체 기록을 보여줄 때 씁니다.
+//! Zone / Encounter 이름은 `FFLOGS_ZONES` / `DUTY_TO_FFLOGS`에서 찾고, 매핑에 없으면 `null`입니다.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use warp::filters::BoxedFilter;
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
+
+use crate::fflogs::mapping::{display_percentile, encounter_by_id, FFLOGS_ZONES};
+use crate::fflogs::{AllStars, EncounterParse, ParseCacheDoc, ZoneCache};
+use crate::web::State;
+
+/// 조회 결과 캐시 시간 (Zone 캐시는 하루 단위로 갱신됨)
+pub const PARSES_CACHE_CONTROL: &str = "public, max-age=300";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ParsesQuery {
+    /// 이 Zone만 반환
+    pub zone_id: Option<u32>,
+}
+
+/// 플레이어 Parse 기록 (Zone ID 순)
+#[derive(Debug, Serialize)]
+pub struct PlayerParses {
+    pub content_id: u64,
+    pub zones: Vec<ZoneParses>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ZoneParses {
+    pub zone_id: u32,
+    pub name: Option<&'static str>,
+    pub fetched_at: DateTime<Utc>,
+    /// Encounter ID 순
+    pub encounters: Vec<EncounterParses>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EncounterParses {
+    pub encounter_id: u32,
+    pub name: Option<&'static str>,
+    /// 나뉜 Encounter의 두 번째 (P2 등)
+    pub secondary: bool,
+    /// 표시 percentile (`display_percentile`, 기록이 없으면 `null`)
+    pub percentile: Option<u8>,
+    /// 기록을 낸 잡 코드 (알 수 없으면 `null`)
+    pub job: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_stars: Option<AllStars>,
+    /// 잡 코드별 표시 percentile (역할별 랭킹을 조회한 기록만)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub jobs: BTreeMap<&'static str, Option<u8>>,
+}
+
+impl PlayerParses {
+    /// 캐시 문서를 응답 형태로 변환 (`zone_id`가 있으면 그 Zone만, ID가 숫자가 아닌 키는 제외)
+    pub fn new(doc: &ParseCacheDoc, zone_id: Option<u32>) -> Self {
+        let mut zones: Vec<ZoneParses> = doc
+            .zones
+            .iter()
+            .filter_map(|(key, cache)| Some((key.parse::<u32>().ok()?, cache)))
+            .filter(|&(id, _)| zone_id.is_none_or(|zone_id| zone_id == id))
+            .map(|(id, cache)| ZoneParses::new(id, cache))
+            .collect();
+        zones.sort_by_key(|zone| zone.zone_id);
+
+        Self {
+            content_id: doc.content_id as u64,
+            zones,
+        }
+    }
+}
+
+impl ZoneParses {
+    fn new(zone_id: u32, cache: &ZoneCache) -> Self {
+        let mut encounters: Vec<EncounterParses> = cache
+            .encounters
+            .iter()
+            .filter_map(|(key, parse)| Some(EncounterParses::new(zone_id, key.parse().ok()?, parse)))
+            .collect();
+        encounters.sort_by_key(|encounter| encounter.encounter_id);
+
+        Self {
+            zone_id,
+            name: FFLOGS_ZONES.get(&zone_id).map(|zone| zone.name),
+            fetched_at: cache.fetched_at,
+            encounters,
+        }
+    }
+}
+
+impl EncounterParses {
+    fn new(zone_id: u32, encounter_id: u32, parse: &EncounterParse) -> Self {
+        let encounter = encounter_by_id(zone_id, encounter_id);
+        Self {
+            encounter_id,
+            name: encounter.map(|(info, _)| info.name),
+            secondary: encounter.is_some_and(|(_, secondary)| secondary),
+            percentile: display_percentile(parse.percentile),
+            job: job_code(parse.job_id),
+            all_stars: parse.all_stars,
+            jobs: parse
+                .jobs
+                .iter()
+                .filter_map(|(job, &percentile)| Some((job_code(job.parse().ok()?)?, display_percentile(percentile))))
+                .collect(),
+        }
+    }
+}
+
+fn job_code(job_id: u8) -> Option<&'static str> {
+    crate::ffxiv::JOBS.get(&u32::from(job_id)).map(|cj| cj.code())
+}
+
+/// GET /api/parses/{content_id}?zone_id=73 → 캐시된 Parse 기록 (캐시가 없는 플레이어는 404)
+pub fn parses(state: Arc<State>) -> BoxedFilter<(warp::reply::Response,)> {
+    async fn logic(state: Arc<State>, content_id: u64, query: ParsesQuery) -> Result<warp::reply::Response, Infallible> {
+        let doc = state
+            .parse_read_collection()
+            .read_one(|collection| crate::mongo::get_parse_doc(collection, content_id))
+            .await;
+        let doc = match doc {
+            Ok(Some(doc)) => doc,
+            Ok(None) => {
+                let reply = warp::reply::json(&serde_json::json!({ "error": "player not found" }));
+                return Ok(warp::reply::with_status(reply, StatusCode::NOT_FOUND).into_response());
+            }
+            Err(e) => {
+                tracing::error!("could not get parses for {}: {:#}", content_id, e);
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+
+        let body = PlayerParses::new(&doc, query.zone_id);
+        Ok(warp::reply::with_header(warp::reply::json(&body), "cache-control", PARSES_CACHE_CONTROL).into_response())
+    }
+
+    warp::get()
+        .and(warp::path!("parses" / u64))
+        .and(warp::query::<ParsesQuery>())
+        .and_then(move |content_id, query| logic(Arc::clone(&state), content_id, query))
+        .boxed()
+}
@@ -0,0 +1,366 @@
+//! 플레이어 이름 일괄 조회 (`POST /api/players/lookup`)와 검색 (`GET /api/players`)
+//!
+//! 플러그인이 게임에서 본 Content ID를 다른 사용자가 업로드한 이름으로 바꿀 때 씁니다 (업로드의 반대 방향).
+//! `GET /api/players`는 외부 도구가 모집글의 Content ID를 확인하거나 이름 / 서버로 찾을 때 씁니다.
+//! `GET /api/players/{content_id}/listings`는 플레이어가 최근에 올린 모집글을 보여 줍니다 (반복 / 장난 모집 확인용).
+//! 저장된 플레이어만 돌려주고, 모르는 ID와 공개하지 않는 기록은 응답에서 뺍니다.
+//! 이름은 거의 바뀌지 않으므로 응답은 잠시 캐시할 수 있습니다.
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use warp::filters::BoxedFilter;
+use warp::http::StatusCode;
+use warp::{Filter, Rejection, Reply};
+
+use super::{ApiReadableDutyInfo, ApiReadableWorld};
+use crate::listing::history::{HostedListing, DEFAULT_HISTORY_LIMIT, MAX_HISTORY_LIMIT};
+use crate::player::Player;
+use crate::web::State;
+
+/// 한 요청에서 조회할 수 있는 최대 Content ID 수 (넘으면 413)
+pub const MAX_LOOKUP_IDS: usize = 500;
+
+/// 요청 본문 최대 크기 (ID를 모두 문자열로 보내도 충분한 크기)
+pub const MAX_LOOKUP_BODY: u64 = 32 * 1024;
+
+/// `GET /api/players`에서 한 번에 조회할 수 있는 최대 Content ID 수 (넘으면 400)
+pub const MAX_QUERY_IDS: usize = 200;
+
+/// 이름 검색 최대 결과 수
+pub const MAX_NAME_MATCHES: i64 = 50;
+
+/// 조회 결과 캐시 시간
+pub const LOOKUP_CACHE_CONTROL: &str = "public, max-age=300";
+
+/// 모집글 기록 캐시 시간 (새 모집글이 자주 추가됨)
+pub const HISTORY_CACHE_CONTROL: &str = "public, max-age=60";
+
+#[derive(Deserialize)]
+struct LookupRequest {
+    content_ids: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LookupError {
+    /// `MAX_LOOKUP_IDS`를 넘음 (요청한 수)
+    TooMany(usize),
+    /// 본문이나 ID 형식이 잘못됨
+    Malformed(String),
+}
+
+impl LookupError {
+    fn into_response(self) -> warp::reply::Response {
+        let (status, error) = match self {
+            Self::TooMany(count) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("at most {} content_ids per request (got {})", MAX_LOOKUP_IDS, count),
+            ),
+            Self::Malformed(error) => (StatusCode::BAD_REQUEST, error),
+        };
+        warp::reply::with_status(warp::reply::json(&serde_json::json!({ "error": error })), status).into_response()
+    }
+}
+
+/// 요청 본문 (`{"content_ids": [...]}`)의 Content ID (중복 제거, 요청 순서 유지)
+///
+/// ID는 0이 아닌 정수이며, 큰 숫자를 다루지 못하는 클라이언트를 위해 숫자 문자열도 받습니다.
+pub fn parse_lookup_request(body: &[u8]) -> Result<Vec<u64>, LookupError> {
+    let request: LookupRequest =
+        serde_json::from_slice(body).map_err(|e| LookupError::Malformed(format!("invalid request: {}", e)))?;
+    if request.content_ids.len() > MAX_LOOKUP_IDS {
+        return Err(LookupError::TooMany(request.content_ids.len()));
+    }
+
+    let mut seen = HashSet::new();
+    let mut content_ids = Vec::with_capacity(request.content_ids.len());
+    for value in &request.content_ids {
+        let content_id = match value {
+            serde_json::Value::Number(number) => number.as_u64(),
+            serde_json::Value::String(text) => text.parse().ok(),
+            _ => None,
+        }
+        .filter(|&id| id != 0)
+        .ok_or_else(|| LookupError::Malformed(format!("invalid content_id `{}`", value)))?;
+        if seen.insert(content_id) {
+            content_ids.push(content_id);
+        }
+    }
+    Ok(content_ids)
+}
+
+/// 조회 결과 한 건
+#[derive(Serialize)]
+pub struct PlayerRecord {
+    content_id: u64,
+    name: String,
+    home_world: ApiReadableWorld,
+    last_seen: DateTime<Utc>,
+}
+
+impl From<Player> for PlayerRecord {
+    fn from(player: Player) -> Self {
+        Self {
+            content_id: player.content_id,
+            home_world: ApiReadableWorld::from(player.home_world),
+            name: player.name,
+            last_seen: player.last_seen,
+        }
+    }
+}
+
+/// `GET /api/players` 결과 한 건 (관측 횟수 포함)
+#[derive(Serialize)]
+pub struct PlayerDetails {
+    #[serde(flatten)]
+    record: PlayerRecord,
+    seen_count: u32,
+}
+
+impl From<Player> for PlayerDetails {
+    fn from(player: Player) -> Self {
+        Self {
+            seen_count: player.seen_count,
+            record: player.into(),
+        }
+    }
+}
+
+/// 공개하는 기록인지 (이름이 없는 기록은 이름 조회에 쓸 수 없음)
+fn listable(player: &Player) -> bool {
+    !player.name.is_empty()
+}
+
+/// 요청한 ID 중 공개하는 기록만 요청 순서대로
+pub fn lookup_records(content_ids: &[u64], players: Vec<Player>) -> Vec<PlayerRecord> {
+    records_in_order(content_ids, players)
+}
+
+fn records_in_order<R: From<Player>>(content_ids: &[u64], players: Vec<Player>) -> Vec<R> {
+    let mut players: std::collections::HashMap<u64, Player> = players
+        .into_iter()
+        .filter(listable)
+        .map(|player| (player.content_id, player))
+        .collect();
+
+    content_ids
+        .iter()
+        .filter_map(|content_id| players.remove(content_id))
+        .map(R::from)
+        .collect()
+}
+
+/// `GET /api/players` 쿼리 (`content_ids` 또는 `name` + 선택적 `world` 중 하나)
+#[derive(Debug, Default, Deserialize)]
+pub struct PlayersQuery {
+    /// 쉼표로 구분한 Content ID
+    pub content_ids: Option<String>,
+    pub name: Option<String>,
+    /// 홈 서버 이름 (대소문자 무시)
+    pub world: Option<String>,
+}
+
+/// `GET /api/players` 조회 방법
+#[derive(Debug, PartialEq, Eq)]
+pub enum PlayerSearch {
+    /// 중복 제거, 요청 순서 유지
+    ContentIds(Vec<u64>),
+    Name { name: String, home_world: Option<u16> },
+}
+
+impl PlayersQuery {
+    pub fn search(&self) -> Result<PlayerSearch, LookupError> {
+        fn given(value: &Option<String>) -> Option<&str> {
+            value.as_deref().map(str::trim).filter(|value| !value.is_empty())
+        }
+
+        match (given(&self.content_ids), given(&self.name)) {
+            (Some(ids), None) if given(&self.world).is_none() => {
+                let ids: Vec<&str> = ids.split(',').map(str::trim).filter(|id| !id.is_empty()).collect();
+                if ids.len() > MAX_QUERY_IDS {
+                    return Err(LookupError::Malformed(format!(
+                        "at most {} content_ids per request (got {})",
+                        MAX_QUERY_IDS,
+                        ids.len()
+                    )));
+                }
+
+                let mut seen = HashSet::new();
+                let mut content_ids = Vec::with_capacity(ids.len());
+                for id in ids {
+                    let content_id = id
+                        .parse()
+                        .ok()
+                        .filter(|&id| id != 0)
+                        .ok_or_else(|| LookupError::Malformed(format!("invalid content_id `{}`", id)))?;
+                    if seen.insert(content_id) {
+                        content_ids.push(content_id);
+                    }
+                }
+                Ok(PlayerSearch::ContentIds(content_ids))
+            }
+            (None, Some(name)) => {
+                let home_world = match given(&self.world) {
+                    Some(world) => Some(
+                        crate::ffxiv::world_id_by_name(world)
+                            .ok_or_else(|| LookupError::Malformed(format!("unknown world `{}`", world)))?,
+                    ),
+                    None => None,
+                };
+                Ok(PlayerSearch::Name { name: name.to_string(), home_world })
+            }
+            _ => Err(LookupError::Malformed("expected either content_ids or name (with optional world)".to_string())),
+        }
+    }
+}
+
+/// GET /api/players?content_ids=1,2,3 또는 ?name=Foo%20Bar&world=Tonberry → `{"players": [...]}`
+pub fn players(state: Arc<State>) -> BoxedFilter<(warp::reply::Response,)> {
+    async fn logic(state: Arc<State>, query: PlayersQuery) -> Result<warp::reply::Response, Infallible> {
+        let search = match query.search() {
+            Ok(search) => search,
+            Err(e) => return Ok(e.into_response()),
+        };
+
+        let players: Result<Vec<PlayerDetails>, _> = match search {
+            PlayerSearch::ContentIds(content_ids) => state
+                .players_by_content_ids(&content_ids)
+                .await
+                .map(|players| records_in_order(&content_ids, players)),
+            PlayerSearch::Name { name, home_world } => state
+                .players_by_name(&name, home_world, MAX_NAME_MATCHES)
+                .await
+                .map(|players| players.into_iter().filter(listable).map(PlayerDetails::from).collect()),
+        };
+        let players = match players {
+            Ok(players) => players,
+            Err(e) => {
+                tracing::error!("could not search players: {:#}", e);
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+
+        let body = serde_json::json!({ "players": players });
+        Ok(warp::reply::with_header(warp::reply::json(&body), "cache-control", LOOKUP_CACHE_CONTROL).into_response())
+    }
+
+    warp::get()
+        .and(warp::path!("players"))
+        .and(warp::query::<PlayersQuery>())
+        .and_then(move |query| logic(Arc::clone(&state), query))
+        .boxed()
+}
+
+/// POST /api/players/lookup: `{"content_ids": [...]}` (최대 `MAX_LOOKUP_IDS`개) → `{"players": [...]}`
+pub fn lookup(state: Arc<State>) -> BoxedFilter<(warp::reply::Response,)> {
+    async fn logic(state: Arc<State>, body: warp::hyper::body::Bytes) -> Result<warp::reply::Response, Infallible> {
+        let content_ids = match parse_lookup_request(&body) {
+            Ok(content_ids) => content_ids,
+            Err(e) => return Ok(e.into_response()),
+        };
+
+        let players = match state.players_by_content_ids(&content_ids).await {
+            Ok(players) => players,
+            Err(e) => {
+                tracing::error!("could not look up players: {:#}", e);
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+
+        let body = serde_json::json!({ "players": lookup_records(&content_ids, players) });
+        Ok(warp::reply::with_header(warp::reply::json(&body), "cache-control", LOOKUP_CACHE_CONTROL).into_response())
+    }
+
+    /// 본문이 너무 크면 ID가 너무 많을 때와 같은 413 응답
+    async fn too_large(rejection: Rejection) -> Result<warp::reply::Response, Rejection> {
+        match rejection.find::<warp::reject::PayloadTooLarge>() {
+            Some(_) => Ok(LookupError::TooMany(MAX_LOOKUP_IDS + 1).into_response()),
+            None => Err(rejection),
+        }
+    }
+
+    warp::post()
+        .and(warp::path!("players" / "lookup"))
+        .and(
+            warp::body::content_length_limit(MAX_LOOKUP_BODY)
+                .and(warp::body::bytes())
+                .and_then(move |body| logic(Arc::clone(&state), body))
+                .recover(too_large)
+                .unify(),
+        )
+        .boxed()
+}
+
+/// `GET /api/players/{content_id}/listings` 쿼리
+#[derive(Debug, Default, Deserialize)]
+pub struct HistoryQuery {
+    pub limit: Option<usize>,
+}
+
+impl HistoryQuery {
+    /// 결과 수 (기본 `DEFAULT_HISTORY_LIMIT`, 1 ~ `MAX_HISTORY_LIMIT`)
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_HISTORY_LIMIT).clamp(1, MAX_HISTORY_LIMIT)
+    }
+}
+
+/// 모집글 기록 한 건
+#[derive(Serialize)]
+pub struct HostedListingRecord {
+    id: u32,
+    created_world: ApiReadableWorld,
+    created_at: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    category: String,
+    canonical_category: String,
+    duty_type: String,
+    duty_info: Option<ApiReadableDutyInfo>,
+    /// 아직 남아 있는 모집글만 (보관 기록에는 설명이 없음)
+    description: Option<String>,
+}
+
+impl From<HostedListing> for HostedListingRecord {
+    fn from(listing: HostedListing) -> Self {
+        Self {
+            id: listing.id,
+            created_world: ApiReadableWorld::from(listing.created_world),
+            created_at: listing.created_at,
+            last_seen: listing.last_seen,
+            category: format!("{:?}", listing.category),
+            canonical_category: format!("{:?}", listing.canonical_category()),
+            duty_type: format!("{:?}", listing.duty_type),
+            duty_info: listing.duty_info().map(|info| ApiReadableDutyInfo::new(u32::from(listing.duty), info)),
+            description: listing.description,
+        }
+    }
+}
+
+/// GET /api/players/{content_id}/listings?limit=N → `{"content_id": ..., "listings": [...]}` (최근에 만든 순)
+pub fn listings(state: Arc<State>) -> BoxedFilter<(warp::reply::Response,)> {
+    async fn logic(state: Arc<State>, content_id: u64, query: HistoryQuery) -> Result<warp::reply::Response, Infallible> {
+        if content_id == 0 {
+            return Ok(LookupError::Malformed("invalid content_id `0`".to_string()).into_response());
+        }
+
+        let listings = match state.hosted_listings(content_id, query.limit()).await {
+            Ok(listings) => listings,
+            Err(e) => {
+                tracing::error!("could not read listings of player {}: {:#}", content_id, e);
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+
+        let listings: Vec<HostedListingRecord> = listings.into_iter().map(HostedListingRecord::from).collect();
+        let body = serde_json::json!({ "content_id": content_id, "listings": listings });
+        Ok(warp::reply::with_header(warp::reply::json(&body), "cache-control", HISTORY_CACHE_CONTROL).into_response())
+    }
+
+    warp::get()
+        .and(warp::path!("players" / u64 / "listings"))
+        .and(warp::query::<HistoryQuery>())
+        .and_then(move |content_id, query| logic(Arc::clone(&state), content_id, query))
+        .boxed()
+}
@@ -0,0 +1,281 @@
+//! 익명 북마크 (관심 모집글)
+//!
+//! 계정 없이 새로고침이나 다른 기기에서도 모집글을 추적할 수 있도록, 관심 모집글 키 목록을
+//! 서명한 토큰으로 주고받습니다. 서버에는 아무것도 저장하지 않으며 토큰 자체가 상태입니다.
+//!
+//! - `POST /api/bookmarks`: 토큰에 모집글을 추가 / 제거한 새 토큰
+//! - `GET /api/bookmarks/{token}`: 토큰에 담긴 모집글의 현재 상태 (만료되거나 사라진 모집글 포함)
+//! - `GET /listings?watch={token}`: 관심 모집글을 강조하고 맨 위에 고정
+//!
+//! 토큰 형식: `base64url(키 목록 JSON).base64url(HMAC-SHA256)`
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{bail, ensure, Context, Result};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use warp::filters::BoxedFilter;
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
+
+use crate::api::{api_listings, ApiReadableListingContainer, ApiShape};
+use crate::config::Config;
+use crate::listing_container::{sort_for_display, QueriedListing};
+use crate::mongo::existing_listing_keys;
+use crate::template::listings::RenderableListing;
+use crate::web::State;
+
+/// 토큰 하나에 담을 수 있는 최대 모집글 수
+pub const MAX_WATCHED: usize = 20;
+
+/// 검증하기 전에 거부하는 토큰 길이 (모집글 20개 기준으로 충분한 값)
+pub const MAX_TOKEN_LEN: usize = 2048;
+
+const BASE64: base64::Config = base64::URL_SAFE_NO_PAD;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 북마크 토큰 서명 / 검증
+pub struct BookmarkSigner {
+    secret: Vec<u8>,
+}
+
+impl BookmarkSigner {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: secret.as_bytes().to_vec(),
+        }
+    }
+
+    fn mac(&self, payload: &[u8]) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        mac
+    }
+
+    /// 모집글 키 목록을 토큰으로 서명
+    pub fn sign(&self, keys: &[String]) -> Result<String> {
+        validate_keys(keys)?;
+        let payload = serde_json::to_vec(keys)?;
+        let signature = self.mac(&payload).finalize().into_bytes();
+        Ok(format!(
+            "{}.{}",
+            base64::encode_config(&payload, BASE64),
+            base64::encode_config(signature, BASE64)
+        ))
+    }
+
+    /// 토큰의 서명을 확인하고 모집글 키 목록 반환
+    pub fn verify(&self, token: &str) -> Result<Vec<String>> {
+        ensure!(token.len() <= MAX_TOKEN_LEN, "token is too long");
+
+        let (payload, signature) = token.split_once('.').context("malformed token")?;
+        let payload = base64::decode_config(payload, BASE64).context("malformed token")?;
+        let signature = base64::decode_config(signature, BASE64).context("malformed token")?;
+        self.mac(&payload)
+            .verify_slice(&signature)
+            .map_err(|_| anyhow::anyhow!("invalid token signature"))?;
+
+        let keys: Vec<String> = serde_json::from_slice(&payload).context("malformed token")?;
+        validate_keys(&keys)?;
+        Ok(keys)
+    }
+}
+
+/// 모집글 키 (`id/created_world/last_server_restart`) 해석
+pub fn parse_listing_key(key: &str) -> Option<(u32, u16, u32)> {
+    let mut parts = key.split('/');
+    let id = parts.next()?.parse().ok()?;
+    let created_world = parts.next()?.parse().ok()?;
+    let last_server_restart = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((id, created_world, last_server_restart))
+}
+
+fn validate_keys(keys: &[String]) -> Result<()> {
+    ensure!(keys.len() <= MAX_WATCHED, "at most {} listings can be watched", MAX_WATCHED);
+    if let Some(key) = keys.iter().find(|key| parse_listing_key(key).is_none()) {
+        bail!("invalid listing key `{}`", key);
+    }
+    Ok(())
+}
+
+/// `POST /api/bookmarks` 요청
+#[derive(Debug, Default, Deserialize)]
+pub struct BookmarkRequest {
+    /// 기존 토큰 (없으면 새 토큰)
+    #[serde(default)]
+    pub token: Option<String>,
+    /// 추가할 모집글 키
+    #[serde(default)]
+    pub key: Option<String>,
+    /// 제거할 모집글 키 (만료된 모집글 정리용)
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+/// `POST /api/bookmarks` 응답
+#[derive(Debug, Serialize)]
+pub struct BookmarkToken {
+    pub token: String,
+    pub keys: Vec<String>,
+}
+
+/// 요청을 적용한 새 토큰 (기존 토큰이 잘못되었거나 최대 개수를 넘으면 오류)
+pub fn apply(signer: &BookmarkSigner, request: BookmarkRequest) -> Result<BookmarkToken> {
+    let mut keys = match &request.token {
+        Some(token) => signer.verify(token)?,
+        None => Vec::new(),
+    };
+
+    keys.retain(|key| !request.remove.contains(key));
+    if let Some(key) = request.key {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    let token = signer.sign(&keys)?;
+    Ok(BookmarkToken { token, keys })
+}
+
+/// 설정된 서명 키로 `?watch=` 토큰 확인 (북마크가 꺼져 있거나 토큰이 잘못되면 `None`)
+pub fn watched_keys(config: &Config, token: &str) -> Option<Vec<String>> {
+    let signer = BookmarkSigner::new(&config.bookmarks.as_ref()?.secret);
+    match signer.verify(token) {
+        Ok(keys) => Some(keys),
+        Err(e) => {
+            tracing::debug!("ignoring watch token: {:#}", e);
+            None
+        }
+    }
+}
+
+/// 관심 모집글을 표시하고 맨 위로 고정 (각 그룹 안의 순서는 유지)
+pub fn pin_watched(listings: &mut [RenderableListing], keys: &[String]) {
+    for renderable in listings.iter_mut() {
+        renderable.watched = keys.contains(&renderable.container.listing.key());
+    }
+    listings.sort_by_key(|renderable| !renderable.watched);
+}
+
+/// 관심 모집글의 현재 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchStatus {
+    /// 목록에 표시 중
+    Active,
+    /// 남은 시간이 끝났거나 더 이상 갱신되지 않음 (취소 포함)
+    Expired,
+    /// DB에서도 삭제됨 (토큰에서 제거해도 됨)
+    Gone,
+}
+
+#[derive(Serialize)]
+pub(crate) struct WatchedListing {
+    key: String,
+    status: WatchStatus,
+    listing: Option<ApiReadableListingContainer>,
+}
+
+pub fn bookmarks(state: Arc<State>) -> BoxedFilter<(warp::reply::Response,)> {
+    let Some(config) = state.config.bookmarks.clone() else {
+        return warp::path("bookmarks")
+            .and_then(|| async { Err::<warp::reply::Response, _>(warp::reject::not_found()) })
+            .boxed();
+    };
+    let signer = Arc::new(BookmarkSigner::new(&config.secret));
+
+    warp::path("bookmarks")
+        .and(create(Arc::clone(&signer)).or(lookup(state, signer)).unify())
+        .boxed()
+}
+
+fn bad_request(e: anyhow::Error) -> warp::reply::Response {
+    warp::reply::with_status(format!("{:#}", e), StatusCode::BAD_REQUEST).into_response()
+}
+
+fn create(signer: Arc<BookmarkSigner>) -> BoxedFilter<(warp::reply::Response,)> {
+    warp::post()
+        .and(warp::path::end())
+        .and(warp::body::content_length_limit(MAX_TOKEN_LEN as u64 * 2))
+        .and(warp::body::json())
+        .map(move |request: BookmarkRequest| match apply(&signer, request) {
+            Ok(token) => warp::reply::json(&token).into_response(),
+            Err(e) => bad_request(e),
+        })
+        .boxed()
+}
+
+fn lookup(state: Arc<State>, signer: Arc<BookmarkSigner>) -> BoxedFilter<(warp::reply::Response,)> {
+    async fn logic(state: Arc<State>, signer: Arc<BookmarkSigner>, token: String) -> Result<warp::reply::Response, warp::Rejection> {
+        let keys = match signer.verify(&token) {
+            Ok(keys) => keys,
+            Err(e) => return Ok(bad_request(e)),
+        };
+
+        Ok(match watched_listings(&state, &keys).await {
+            Ok(listings) => {
+                let reply = warp::reply::json(&listings);
+                warp::reply::with_header(reply, "cache-control", "no-store").into_response()
+            }
+            Err(e) => {
+                tracing::error!("could not look up watched listings: {:#}", e);
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        })
+    }
+
+    warp::get()
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and_then(move |token: String| logic(Arc::clone(&state), Arc::clone(&signer), token))
+        .boxed()
+}
+
+/// 토큰 순서대로 관심 모집글의 상태 조회
+async fn watched_listings(state: &State, keys: &[String]) -> Result<Vec<WatchedListing>> {
+    let mut active: Vec<QueriedListing> = state
+        .current_listings()
+        .await?
+        .iter()
+        .filter(|listing| keys.contains(&listing.listing.key()))
+        .cloned()
+        .collect();
+
+    // API 응답도 같은 기준으로 정렬하므로, 미리 정렬하면 키와 순서가 그대로 맞음
+    sort_for_display(&mut active);
+    let active_keys: Vec<String> = active.iter().map(|listing| listing.listing.key()).collect();
+    let mut active: HashMap<String, ApiReadableListingContainer> =
+        active_keys.into_iter().zip(api_listings(state, active, ApiShape::Compact).await).collect();
+
+    let inactive: Vec<(u32, u16, u32)> = keys
+        .iter()
+        .filter(|key| !active.contains_key(*key))
+        .filter_map(|key| parse_listing_key(key))
+        .collect();
+    let existing = existing_listing_keys(state.collection().primary(), &inactive).await?;
+
+    Ok(keys
+        .iter()
+        .map(|key| {
+            let listing = active.remove(key);
+            let status = if listing.is_some() {
+                WatchStatus::Active
+            } else if existing.contains(key) {
+                WatchStatus::Expired
+            } else {
+                WatchStatus::Gone
+            };
+            WatchedListing {
+                key: key.clone(),
+                status,
+                listing,
+            }
+        })
+        .collect())
+}
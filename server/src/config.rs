@@ -1,5 +1,8 @@
-use serde::Deserialize;
+use chrono::{FixedOffset, NaiveDateTime, NaiveTime, TimeDelta, Weekday};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 
 #[derive(Deserialize)]
 pub struct Config {
@@ -8,6 +11,459 @@ pub struct Config {
     /// FFLogs API 설정 (선택적)
     #[serde(default)]
     pub fflogs: Option<FFLogs>,
+    /// 관리자 API 설정 (선택적, 없으면 관리자 엔드포인트 비활성화)
+    #[serde(default)]
+    pub admin: Option<Admin>,
+    /// 연구용 익명화 데이터셋 설정 (선택적, 없으면 내보내기 비활성화)
+    #[serde(default)]
+    pub export: Option<Export>,
+    /// 게임 점검 일정 (점검 중에는 백그라운드 작업을 쉼)
+    #[serde(default)]
+    pub maintenance: Maintenance,
+    /// 익명 북마크 설정 (선택적, 없으면 북마크 비활성화)
+    #[serde(default)]
+    pub bookmarks: Option<Bookmarks>,
+    /// 로그 출력 설정
+    #[serde(default)]
+    pub logging: Logging,
+    /// 목록 정렬 설정
+    #[serde(default)]
+    pub sort: ListingSort,
+    /// 데이터 센터별 모집글 수 급감 감지
+    #[serde(default)]
+    pub volume_alerts: VolumeAlerts,
+    /// 공개 목록 표시 설정 (SIGHUP으로 다시 읽음)
+    #[serde(default)]
+    pub display: Display,
+    /// 역할별 빈 자리 기록
+    #[serde(default)]
+    pub role_demand: RoleDemand,
+    /// 중복 플레이어 문서 병합
+    #[serde(default)]
+    pub player_compaction: PlayerCompaction,
+    /// 웹소켓 연결 제한
+    #[serde(default)]
+    pub websocket: Websocket,
+    /// 전체 스냅샷 업로드
+    #[serde(default)]
+    pub snapshot: Snapshot,
+    /// 시작할 때 만드는 인덱스
+    #[serde(default)]
+    pub indexes: Indexes,
+    /// 업로드 주소별 요청 제한
+    #[serde(default)]
+    pub ratelimit: RateLimit,
+    /// 업로드 본문 크기 제한
+    #[serde(default)]
+    pub body_limits: BodyLimits,
+    /// 업로드 토큰 (선택적, 없으면 누구나 업로드 가능)
+    #[serde(default)]
+    pub auth: Option<Auth>,
+    /// 모더레이터 API 설정 (선택적, 없으면 모더레이션 엔드포인트 비활성화)
+    #[serde(default)]
+    pub moderation: Option<Moderation>,
+    /// 설명 금칙어 (맞는 모집글은 저장하되 공개 목록에서 제외)
+    #[serde(default)]
+    pub keyword_filter: KeywordFilter,
+}
+
+/// 설명 금칙어 설정
+///
+/// 설명의 네 언어 텍스트(자동 번역 포함) 중 하나라도 맞으면 모집글에 `flagged`를 기록합니다.
+/// 대소문자는 구분하지 않습니다.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct KeywordFilter {
+    /// 그대로 찾을 단어 / 문구
+    pub words: Vec<String>,
+    /// 정규식 (`regex` 문법, 잘못된 정규식이 있으면 시작하지 않음)
+    pub patterns: Vec<String>,
+}
+
+/// 시작할 때 만드는 인덱스 설정
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Indexes {
+    /// 인덱스를 만들지 못하면 만들 때까지 시작하지 않음
+    ///
+    /// 끄면 권한 부족이나 옵션 충돌은 빠진 인덱스를 경고로 남기고 그대로 시작합니다
+    /// (권한이 제한된 관리형 MongoDB용).
+    pub required: bool,
+}
+
+impl Default for Indexes {
+    fn default() -> Self {
+        Self { required: true }
+    }
+}
+
+/// 전체 스냅샷 업로드 설정
+///
+/// 업로더의 스냅샷에서 빠진 모집글은 다른 업로더가 다시 올리지 않으면 이 시간 후 기본 목록에서 빠집니다.
+/// 스냅샷은 업로드 토큰(`[auth]`)으로 인증한 업로드만 받습니다.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Snapshot {
+    /// 스냅샷에서 빠진 모집글을 목록에 남겨 두는 시간 (분)
+    pub unconfirmed_minutes: u32,
+}
+
+impl Default for Snapshot {
+    fn default() -> Self {
+        Self { unconfirmed_minutes: 10 }
+    }
+}
+
+impl Snapshot {
+    pub fn unconfirmed_window(&self) -> TimeDelta {
+        TimeDelta::try_minutes(i64::from(self.unconfirmed_minutes)).unwrap_or(TimeDelta::MAX)
+    }
+}
+
+/// 웹소켓 연결 제한 설정 (우선 연결 토큰은 `[admin]`의 `websocket_tokens`)
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Websocket {
+    /// 익명 연결 최대 개수
+    pub max_connections: usize,
+    /// 주소(IP)별 익명 연결 최대 개수
+    pub max_per_address: usize,
+    /// 서버가 보내는 ping 간격 (초)
+    pub ping_interval_secs: u64,
+    /// 응답 없는 ping이 이만큼 쌓이면 연결을 끊음
+    pub max_missed_pongs: u32,
+    /// 모집글 변경 방송 채널 용량 (이보다 많이 밀린 연결은 놓친 변경 대신 새 스냅샷을 받음)
+    pub broadcast_capacity: usize,
+}
+
+impl Default for Websocket {
+    fn default() -> Self {
+        Self {
+            max_connections: 1_000,
+            max_per_address: 8,
+            ping_interval_secs: 30,
+            max_missed_pongs: 2,
+            broadcast_capacity: 512,
+        }
+    }
+}
+
+/// 업로드(`/contribute` 경로) 주소별 요청 제한 (토큰 버킷)
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct RateLimit {
+    /// 주소(IP)별 분당 업로드 요청 수 (0이면 제한하지 않음)
+    pub contribute_per_minute: u32,
+    /// 한꺼번에 보낼 수 있는 요청 수 (없으면 `contribute_per_minute`)
+    pub contribute_burst: Option<u32>,
+    /// `X-Forwarded-For`에서 클라이언트 주소를 읽음 (리버스 프록시 뒤에서만 켬,
+    /// 요청 제한 / 업로더 지문 / 웹소켓 주소별 연결 제한에 같이 적용)
+    pub trust_forwarded_for: bool,
+    /// 앞에 있는 리버스 프록시 수 (`X-Forwarded-For`의 오른쪽에서 이 수만큼 건너간 주소를 사용,
+    /// 그보다 왼쪽은 클라이언트가 보낸 값이라 믿지 않음)
+    pub trusted_proxies: usize,
+    /// 업로더 지문 솔트의 원본 비밀값 (없으면 처음 시작할 때 만들어 Mongo `secrets` 컬렉션에 저장)
+    pub uploader_secret: Option<String>,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            contribute_per_minute: 120,
+            contribute_burst: None,
+            trust_forwarded_for: false,
+            trusted_proxies: 1,
+            uploader_secret: None,
+        }
+    }
+}
+
+/// 업로드(`/contribute` 경로) 본문 최대 크기 (KB, 넘으면 413)
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct BodyLimits {
+    /// `/contribute` (모집글 하나)
+    pub contribute_kb: u64,
+    /// `/contribute/multiple` (모집글 여러 개 / 전체 스냅샷)
+    pub multiple_kb: u64,
+    /// `/contribute/players`
+    pub players_kb: u64,
+    /// `/contribute/detail`
+    pub detail_kb: u64,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            contribute_kb: 64,
+            multiple_kb: 4 * 1024,
+            players_kb: 1024,
+            detail_kb: 64,
+        }
+    }
+}
+
+/// 중복 플레이어 문서 병합 설정 (관리자 API로는 항상 실행 가능)
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PlayerCompaction {
+    /// 매일 정해진 시각에 실행 (기본값 false)
+    pub scheduled: bool,
+    /// 실행 시각 (UTC 시)
+    pub hour: u32,
+    /// 예약 실행도 보고서만 생성
+    pub dry_run: bool,
+}
+
+impl Default for PlayerCompaction {
+    fn default() -> Self {
+        Self {
+            scheduled: false,
+            hour: 5,
+            dry_run: false,
+        }
+    }
+}
+
+/// 역할별 빈 자리 기록 설정
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct RoleDemand {
+    /// 기록을 보관하는 기간 (일, TTL 인덱스로 삭제)
+    pub horizon_days: u32,
+}
+
+impl Default for RoleDemand {
+    fn default() -> Self {
+        Self { horizon_days: 28 }
+    }
+}
+
+/// 공개 목록 표시 설정
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Display {
+    /// 목록 / 웹소켓에서 숨길 듀티 ID (통계에는 계속 집계)
+    pub blocked_duties: Vec<u16>,
+    /// 목록 / 웹소켓에서 숨길 듀티 카테고리 (`DutyCategory` 이름, 예: `"TreasureHunt"`)
+    pub blocked_categories: Vec<String>,
+    /// 듀티 ID가 없으면 카테고리 이름으로 표시하고 통계에서 카테고리 단위로 묶을 카테고리
+    /// (`DutyCategory` 이름, 통계 쿼리에 들어가므로 시작할 때만 읽음)
+    pub labeled_categories: Vec<String>,
+}
+
+impl Default for Display {
+    fn default() -> Self {
+        Self {
+            blocked_duties: Vec::new(),
+            blocked_categories: Vec::new(),
+            labeled_categories: ["Fate", "TheHunt", "GatheringForay", "FieldOperation"].map(String::from).to_vec(),
+        }
+    }
+}
+
+/// 데이터 센터별 모집글 수 급감 감지 설정
+///
+/// 5분마다 데이터 센터별 활성 모집글 수를 같은 시간대(UTC 시)의 이전 날짜 평균과 비교합니다.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct VolumeAlerts {
+    /// 평균의 이 비율 미만이 두 번 연속이면 경고
+    pub drop_ratio: f64,
+    /// 경고 후 평균의 이 비율 이상으로 돌아오면 해제
+    pub recover_ratio: f64,
+    /// 시간대별 평균(EWMA)에 새 날짜를 반영하는 비율
+    pub alpha: f64,
+    /// 평균이 이보다 작은 시간대는 검사하지 않음 (원래 모집글이 적은 데이터 센터)
+    pub min_baseline: f64,
+    /// 경고 / 해제 시 JSON을 POST할 주소 (선택)
+    pub webhook: Option<String>,
+}
+
+impl Default for VolumeAlerts {
+    fn default() -> Self {
+        Self {
+            drop_ratio: 0.4,
+            recover_ratio: 0.7,
+            alpha: 0.3,
+            min_baseline: 10.0,
+            webhook: None,
+        }
+    }
+}
+
+/// 목록 정렬 설정
+///
+/// 목록은 마지막 업데이트 시각을 구간 단위로 내림한 값으로 먼저 정렬합니다. 계속 다시 올라오는
+/// 분류(필드 탐색, 딥 던전)는 구간을 넓혀 최근 구간을 독차지하지 않도록 합니다.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ListingSort {
+    /// 기본 업데이트 구간 (분)
+    pub bucket_minutes: u32,
+    /// 듀티 분류별 업데이트 구간 (key: `DutyCategory` 이름, 예: `FieldOperation = 15`)
+    ///
+    /// 이 표를 설정하면 기본 재정의를 대체합니다.
+    pub category_bucket_minutes: HashMap<String, u32>,
+}
+
+impl Default for ListingSort {
+    fn default() -> Self {
+        Self {
+            bucket_minutes: 5,
+            category_bucket_minutes: HashMap::from([
+                ("FieldOperation".to_string(), 15),
+                ("DeepDungeon".to_string(), 15),
+            ]),
+        }
+    }
+}
+
+/// 로그 출력 설정
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Logging {
+    /// 출력 형식 (`"pretty"` | `"json"`)
+    pub format: LogFormat,
+    /// 기본 로그 레벨
+    pub level: String,
+    /// 모듈별 레벨 재정의 (예: `"remote_party_finder::infra::fflogs=debug,warp=warn"`)
+    pub directives: String,
+    /// 파일에도 기록 (일별 로테이션)
+    pub file: bool,
+    /// 로그 파일 디렉터리
+    pub directory: PathBuf,
+}
+
+impl Default for Logging {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::Pretty,
+            level: "info".to_string(),
+            directives: String::new(),
+            file: true,
+            directory: PathBuf::from("logs"),
+        }
+    }
+}
+
+/// 로그 출력 형식
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// 사람이 읽는 한 줄 형식
+    Pretty,
+    /// 한 줄에 JSON 객체 하나 (로그 수집용)
+    Json,
+}
+
+/// 익명 북마크 설정
+#[derive(Deserialize, Clone)]
+pub struct Bookmarks {
+    /// 북마크 토큰 서명 키 (바꾸면 기존 토큰이 모두 무효가 됨)
+    pub secret: String,
+}
+
+/// 게임 점검 일정
+#[derive(Deserialize, Clone)]
+pub struct Maintenance {
+    /// 일정의 시간대 (예: `"+09:00"`, 기본값 UTC)
+    #[serde(default = "utc", deserialize_with = "deserialize_offset")]
+    pub timezone: FixedOffset,
+    /// 매주 반복되는 점검
+    #[serde(default)]
+    pub weekly: Vec<WeeklyWindow>,
+    /// 임시 점검 (시작 ~ 종료)
+    #[serde(default)]
+    pub ranges: Vec<MaintenanceRange>,
+}
+
+impl Default for Maintenance {
+    fn default() -> Self {
+        Self {
+            timezone: utc(),
+            weekly: Vec::new(),
+            ranges: Vec::new(),
+        }
+    }
+}
+
+/// 매주 반복되는 점검 (예: `{ day = "Tue", start = "15:00:00", minutes = 240 }`)
+#[derive(Deserialize, Clone)]
+pub struct WeeklyWindow {
+    pub day: Weekday,
+    pub start: NaiveTime,
+    pub minutes: u32,
+}
+
+/// 임시 점검 (예: `{ start = "2026-01-06T15:00:00", end = "2026-01-07T08:00:00" }`)
+#[derive(Deserialize, Clone)]
+pub struct MaintenanceRange {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+/// 연구용 익명화 데이터셋 설정
+#[derive(Deserialize, Clone)]
+pub struct Export {
+    /// 데이터셋 파일을 저장하고 제공할 디렉터리
+    pub directory: PathBuf,
+    /// 식별자 해시 솔트의 원본 비밀값 (이 값과 연월로 매월 새 솔트를 만듦)
+    pub salt_secret: String,
+    /// 같은 (데이터 센터, 듀티) 그룹이 이보다 적으면 해당 그룹을 제외
+    #[serde(default = "default_k")]
+    pub k: usize,
+}
+
+fn default_k() -> usize {
+    5
+}
+
+/// 관리자 API 설정
+#[derive(Deserialize, Clone)]
+pub struct Admin {
+    /// `Authorization: Bearer <token>` 으로 전달해야 하는 관리자 토큰
+    pub token: String,
+    /// 웹소켓 우선 연결 토큰 (`?token=`으로 전달되어 접근 로그에 남으므로 관리자 토큰과 따로 둠)
+    #[serde(default)]
+    pub websocket_tokens: Vec<String>,
+}
+
+impl Admin {
+    /// 웹소켓 연결 제한에서 빠지는 토큰 (관리자 토큰은 포함하지 않음)
+    pub fn priority_tokens(&self) -> Vec<String> {
+        self.websocket_tokens.clone()
+    }
+}
+
+/// 업로드(`/contribute` 경로) 인증 설정
+#[derive(Deserialize, Clone, Default)]
+pub struct Auth {
+    /// `Authorization: Bearer <token>` 으로 전달해야 하는 업로드 토큰 (비어 있으면 인증하지 않음)
+    #[serde(default)]
+    pub tokens: Vec<UploadToken>,
+}
+
+/// 이름 붙은 업로드 토큰 (업로드 로그에 이름이 남음)
+#[derive(Deserialize, Clone)]
+pub struct UploadToken {
+    pub name: String,
+    pub token: String,
+}
+
+/// 모더레이터 API 설정 (`/api/moderation/...`)
+#[derive(Deserialize, Clone, Default)]
+pub struct Moderation {
+    /// `Authorization: Bearer <token>` 으로 전달해야 하는 모더레이터 토큰 (관리자 토큰과 별개)
+    #[serde(default)]
+    pub tokens: Vec<ModeratorToken>,
+}
+
+/// 이름 붙은 모더레이터 토큰 (숨김 기록에 이름이 남음)
+#[derive(Deserialize, Clone)]
+pub struct ModeratorToken {
+    pub name: String,
+    pub token: String,
 }
 
 /// FFLogs API 설정
@@ -17,14 +473,292 @@ pub struct FFLogs {
     pub client_id: String,
     /// OAuth2 Client Secret
     pub client_secret: String,
+    /// Zone별 랭킹 파티션 재정의 (key: FFLogs Zone ID, 예: `"73" = 2`)
+    ///
+    /// FFLogs가 패치로 파티션을 나누면 기본 매핑 대신 이 값으로 조회합니다.
+    #[serde(default)]
+    pub partitions: HashMap<String, u32>,
+    /// 랭킹이 없는 플레이어의 재조회 간격
+    #[serde(default)]
+    pub empty_backoff: EmptyBackoff,
+    /// 시간당 GraphQL 요청 한도 (없으면 제한 없음)
+    #[serde(default)]
+    pub requests_per_hour: Option<u32>,
+    /// 일시적인 오류(429 / 5xx / 연결 실패) 재시도 정책
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// Zone 캐시 갱신 주기 (시간)
+    #[serde(default = "default_cache_hours")]
+    pub cache_hours: u32,
+    /// Zone별 갱신 주기 재정의 (key: FFLogs Zone ID, 예: 현재 티어 `"73" = 6`)
+    ///
+    /// 기록이 자주 바뀌는 새 티어는 짧게, 더 이상 바뀌지 않는 이전 절은 길게 잡아 조회 한도를 아낍니다.
+    #[serde(default)]
+    pub zone_cache_hours: HashMap<String, u32>,
+    /// Duty → FFLogs 매핑 파일 (`data/fflogs_mapping.toml` 형식, 내장 매핑에 추가 / 교체)
+    #[serde(default)]
+    pub mapping_file: Option<PathBuf>,
+}
+
+fn default_cache_hours() -> u32 {
+    24
+}
+
+/// 연속으로 빈 결과가 나온 플레이어의 재조회 정책
+///
+/// `daily_after`번 연속이면 `daily_hours`마다, `weekly_after`번 연속이면 `weekly_hours`마다 다시 조회합니다.
+/// FFLogs에 없거나 기록을 비공개로 한 캐릭터는 처음부터 `missing_hours`마다 조회합니다.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct EmptyBackoff {
+    pub daily_after: u32,
+    pub daily_hours: u32,
+    pub weekly_after: u32,
+    pub weekly_hours: u32,
+    pub missing_hours: u32,
+}
+
+impl Default for EmptyBackoff {
+    fn default() -> Self {
+        Self {
+            daily_after: 3,
+            daily_hours: 24,
+            weekly_after: 10,
+            weekly_hours: 24 * 7,
+            missing_hours: 24 * 7,
+        }
+    }
+}
+
+/// FFLogs 요청 재시도 정책
+///
+/// 요청을 최대 `attempts`번 보내며, 간격은 `base_delay_ms`부터 두 배씩 늘어 `max_delay_secs`를 넘지 않습니다.
+/// 응답의 `Retry-After`가 있으면 그 값을 쓰고, `max_delay_secs`보다 길면 다음 주기로 미룹니다.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            base_delay_ms: 1_000,
+            max_delay_secs: 30,
+        }
+    }
+}
+
+impl FFLogs {
+    /// 숫자 Zone ID로 변환한 파티션 재정의 (잘못된 키는 무시)
+    pub fn partition_overrides(&self) -> HashMap<u32, u32> {
+        zone_overrides(&self.partitions, "partition")
+    }
+
+    /// 숫자 Zone ID로 변환한 캐시 갱신 주기 재정의 (잘못된 키는 무시)
+    pub fn cache_hour_overrides(&self) -> HashMap<u32, u32> {
+        zone_overrides(&self.zone_cache_hours, "cache hours")
+    }
+}
+
+fn zone_overrides(overrides: &HashMap<String, u32>, what: &str) -> HashMap<u32, u32> {
+    overrides
+        .iter()
+        .filter_map(|(zone_id, &value)| match zone_id.parse() {
+            Ok(zone_id) => Some((zone_id, value)),
+            Err(_) => {
+                tracing::warn!("ignoring {} override for invalid zone id `{}`", what, zone_id);
+                None
+            }
+        })
+        .collect()
 }
 
 #[derive(Deserialize)]
 pub struct Web {
     pub host: SocketAddr,
+    /// 설명의 상대 날짜("오늘", "土曜")를 해석할 표시 시간대 (예: `"+09:00"`, 기본값 UTC)
+    #[serde(default = "utc", deserialize_with = "deserialize_offset")]
+    pub display_timezone: FixedOffset,
+    /// 준비(`/readyz`)될 때까지 공개 경로를 열지 않음 (기본값 false)
+    #[serde(default)]
+    pub wait_for_ready: bool,
+    /// 첫 통계 계산이 끝나지 않아도 준비 완료로 보는 시작 후 대기 시간 (초)
+    #[serde(default = "default_stats_grace_secs")]
+    pub stats_grace_secs: u64,
+    /// 요청 시 이보다 오래된 통계면 백그라운드에서 다시 계산 (시간)
+    #[serde(default = "default_stats_max_age_hours")]
+    pub stats_max_age_hours: u64,
+    /// 정기 통계 계산 간격 (시간, 실패 후 재시도는 5분부터 1시간까지 늘어남)
+    #[serde(default = "default_stats_interval_hours")]
+    pub stats_interval_hours: u64,
+    /// `/metrics`를 공개 주소 대신 이 주소에서만 제공 (예: `"127.0.0.1:9100"`, 없으면 공개 라우터에서 제공)
+    #[serde(default)]
+    pub metrics_host: Option<SocketAddr>,
+    /// `/api` 경로의 CORS (없으면 CORS 헤더를 붙이지 않음)
+    #[serde(default)]
+    pub cors: Option<Cors>,
+}
+
+/// `/api` 경로의 CORS 설정 (`[web.cors]`)
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Cors {
+    /// 허용할 출처 (예: `["https://example.com"]`, `"*"`가 있으면 모든 출처)
+    pub allowed_origins: Vec<String>,
+    /// preflight에서 허용하는 메서드
+    pub allowed_methods: Vec<String>,
+    /// preflight에서 허용하는 요청 헤더 (대소문자 무시)
+    pub allowed_headers: Vec<String>,
+    /// 브라우저가 preflight 결과를 재사용하는 시간 (초)
+    pub max_age_secs: u64,
+}
+
+impl Default for Cors {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: ["GET", "HEAD", "POST"].map(String::from).to_vec(),
+            allowed_headers: ["content-type", "if-none-match"].map(String::from).to_vec(),
+            max_age_secs: 600,
+        }
+    }
+}
+
+fn default_stats_grace_secs() -> u64 {
+    120
+}
+
+fn default_stats_max_age_hours() -> u64 {
+    18
+}
+
+fn default_stats_interval_hours() -> u64 {
+    12
+}
+
+fn utc() -> FixedOffset {
+    FixedOffset::east_opt(0).unwrap()
+}
+
+fn deserialize_offset<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FixedOffset, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value
+        .parse()
+        .map_err(|_| serde::de::Error::custom(format!("invalid UTC offset `{}` (expected e.g. \"+09:00\")", value)))
 }
 
 #[derive(Deserialize)]
 pub struct Mongo {
     pub url: String,
+    /// 사용할 데이터베이스 이름
+    #[serde(default = "default_database")]
+    pub database: String,
+    /// 모집글 컬렉션 이름
+    #[serde(default = "default_listings_collection")]
+    pub listings_collection: String,
+    /// 플레이어 컬렉션 이름
+    #[serde(default = "default_players_collection")]
+    pub players_collection: String,
+    /// FFLogs Parse 컬렉션 이름
+    #[serde(default = "default_parses_collection")]
+    pub parses_collection: String,
+    /// 모집글 보관 시간 (시간, 마지막 업데이트 기준 TTL 인덱스)
+    ///
+    /// 목록에는 이보다 1시간 짧게(최소 1시간) 표시합니다. 값을 바꾸면 시작할 때 TTL 인덱스를 다시 만듭니다.
+    #[serde(default = "default_listings_ttl_hours")]
+    pub listings_ttl_hours: u32,
+    /// 이름을 바꾸기 전 데이터베이스 (있으면 양쪽에 기록하고 이전 문서를 옮김, 복사가 끝나면 제거)
+    #[serde(default)]
+    pub migrate_from: Option<String>,
+    /// 읽기 전용 조회(목록, 통계, 내보내기, 플레이어 / Parse 표시)에 쓰는 노드
+    ///
+    /// 쓰기와 읽은 뒤 고치는 조회는 이 설정과 관계없이 primary 노드를 사용합니다.
+    #[serde(default)]
+    pub read_preference: ReadPreference,
+    /// secondary 노드에서 읽을 때 허용하는 최대 복제 지연 (초, MongoDB 최소값 90, 없으면 제한 없음)
+    #[serde(default)]
+    pub max_staleness_secs: Option<u64>,
+}
+
+/// 읽기 전용 조회의 읽기 설정 (`mongo.read_preference`)
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ReadPreference {
+    /// 항상 primary 노드
+    #[default]
+    Primary,
+    /// primary 노드, 없으면 secondary 노드
+    PrimaryPreferred,
+    /// secondary 노드, 없으면 primary 노드
+    SecondaryPreferred,
+}
+
+/// MongoDB가 허용하는 최소 `maxStalenessSeconds`
+pub const MIN_MAX_STALENESS_SECS: u64 = 90;
+
+impl Mongo {
+    /// 읽기 전용 조회에 붙이는 선택 기준 (`primary`면 `None`으로 클라이언트 기본값 사용)
+    pub fn read_criteria(&self) -> anyhow::Result<Option<mongodb::options::SelectionCriteria>> {
+        use mongodb::options::{ReadPreference as Mode, ReadPreferenceOptions, SelectionCriteria};
+
+        if let Some(secs) = self.max_staleness_secs.filter(|&secs| secs < MIN_MAX_STALENESS_SECS) {
+            anyhow::bail!("mongo.max_staleness_secs must be at least {} (got {})", MIN_MAX_STALENESS_SECS, secs);
+        }
+
+        let options = ReadPreferenceOptions::builder()
+            .max_staleness(self.max_staleness_secs.map(std::time::Duration::from_secs))
+            .build();
+        let mode = match self.read_preference {
+            ReadPreference::Primary => return Ok(None),
+            ReadPreference::PrimaryPreferred => Mode::PrimaryPreferred { options },
+            ReadPreference::SecondaryPreferred => Mode::SecondaryPreferred { options },
+        };
+        Ok(Some(SelectionCriteria::ReadPreference(mode)))
+    }
+
+    /// 읽기 전용 조회가 복제가 늦은 secondary 노드로 갈 수 있는지
+    pub fn reads_may_lag(&self) -> bool {
+        self.read_preference != ReadPreference::Primary
+    }
+
+    /// 모집글 TTL 인덱스의 보관 시간 (최소 1시간)
+    pub fn listings_ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(u64::from(self.listings_ttl_hours.max(1)) * 3600)
+    }
+
+    /// 목록에 표시하는 기간 (마지막 업데이트 기준, TTL보다 1시간 짧게, 최소 1시간)
+    pub fn listings_visible_window(&self) -> TimeDelta {
+        TimeDelta::hours(i64::from(self.listings_ttl_hours.saturating_sub(1).max(1)))
+    }
+
+    /// 복사 중인 이전 데이터베이스 이름 (새 이름과 같으면 무시)
+    pub fn legacy_database(&self) -> Option<&str> {
+        self.migrate_from
+            .as_deref()
+            .filter(|legacy| *legacy != self.database)
+    }
+}
+
+fn default_database() -> String {
+    "rpf".to_string()
+}
+
+fn default_listings_ttl_hours() -> u32 {
+    2
+}
+
+fn default_listings_collection() -> String {
+    "listings".to_string()
+}
+
+fn default_players_collection() -> String {
+    "players".to_string()
+}
+
+fn default_parses_collection() -> String {
+    "parses".to_string()
 }
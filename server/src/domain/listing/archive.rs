@@ -0,0 +1,108 @@
+//! 모집글 보관 기록 (`listings_archive`)
+//!
+//! 모집글 컬렉션은 마지막 업데이트 `mongo.listings_ttl_hours`(기본 2시간) 뒤 TTL로 지워지므로, 통계에 쓰는 필드만 모은 작은 기록을
+//! TTL 없는 컬렉션에 남깁니다. 필드 경로를 모집글 문서와 맞춰 통계 파이프라인을 그대로 씁니다.
+//! 저장 키는 `insert_listing`과 같은 (id, last_server_restart, created_world)입니다.
+//! 모든 자리가 찬 상태로 처음 본 시각(`full_at`)을 남겨 듀티별 모집 완료 시간 통계에 씁니다.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use mongodb::bson::{doc, to_bson, Document};
+
+use crate::listing::container::sanitized_description;
+use crate::listing::description::{detect_language, has_autotranslate};
+use crate::listing::{ListingContainer, PartyFinderListing};
+
+/// 보관 대기 중인 모집글 (마지막 업로드 내용과 처음 / 마지막으로 본 시각)
+#[derive(Debug, Clone)]
+pub struct PendingArchive {
+    pub listing: PartyFinderListing,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// 모든 자리가 찬 상태로 처음 본 시각
+    pub full_at: Option<DateTime<Utc>>,
+}
+
+impl PendingArchive {
+    pub fn new(listing: PartyFinderListing, seen_at: DateTime<Utc>) -> Self {
+        let full_at = (listing.total_capacity() > 0 && listing.open_slots() == 0).then_some(seen_at);
+        Self { listing, first_seen: seen_at, last_seen: seen_at, full_at }
+    }
+
+    /// 저장된 모집글 (처음 / 마지막으로 본 시각은 만든 / 업데이트 시각, 다 찬 시각은 `slots_history`)
+    pub fn stored(container: ListingContainer) -> Self {
+        Self {
+            first_seen: container.created_at,
+            last_seen: container.updated_at,
+            full_at: container.full_at(),
+            listing: container.listing,
+        }
+    }
+
+    /// 같은 모집글의 이후 업로드 (내용은 최신, 처음 본 시각과 처음 다 찬 시각은 유지)
+    pub fn merge(&mut self, newer: PendingArchive) {
+        self.first_seen = self.first_seen.min(newer.first_seen);
+        self.full_at = match (self.full_at, newer.full_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if newer.last_seen >= self.last_seen {
+            self.last_seen = newer.last_seen;
+            self.listing = newer.listing;
+        }
+    }
+
+    /// 저장 키
+    pub fn key(&self) -> (u32, u32, u16) {
+        (self.listing.id, self.listing.last_server_restart, self.listing.created_world)
+    }
+
+    pub fn filter(&self) -> Document {
+        doc! {
+            "listing.id": self.listing.id,
+            "listing.last_server_restart": self.listing.last_server_restart,
+            "listing.created_world": self.listing.created_world as u32,
+        }
+    }
+
+    /// upsert 갱신 문서
+    ///
+    /// 만든 시각과 처음 본 시각은 처음 한 번만 쓰고, 나머지는 마지막 상태(자리 채움 통계 기준)로 덮어씁니다.
+    /// 다 찬 시각은 가장 이른 값을 유지합니다.
+    /// 설명은 통계에 필요한 길이 / 언어 / 자동 번역 여부만 남깁니다.
+    pub fn update(&self) -> Result<Document> {
+        let listing = &self.listing;
+        let description = sanitized_description(listing);
+        // 모집글 문서와 같은 BSON 표현
+        let name = crate::base64_sestring::serialize(&listing.name, mongodb::bson::Serializer::new())?;
+        let mut update = doc! {
+            "$setOnInsert": {
+                "created_at": self.first_seen,
+                "first_seen": self.first_seen,
+            },
+            "$max": {
+                "last_seen": self.last_seen,
+            },
+            "$set": {
+                "listing.content_id_lower": to_bson(&listing.content_id_lower)?,
+                "listing.name": name,
+                "listing.home_world": to_bson(&listing.home_world)?,
+                "listing.category": to_bson(&listing.category)?,
+                "listing.duty": to_bson(&listing.duty)?,
+                "listing.duty_type": to_bson(&listing.duty_type)?,
+                "listing.search_area": to_bson(&listing.search_area)?,
+                "listing.num_parties": to_bson(&listing.num_parties)?,
+                "listing.slots_available": to_bson(&listing.slots_available)?,
+                "listing.jobs_present": to_bson(&listing.jobs_present)?,
+                "canonical_category": listing.canonical_category() as u32 as i64,
+                "description_length": description.chars().count() as i64,
+                "description_language": to_bson(&detect_language(&description))?,
+                "has_autotranslate": has_autotranslate(&listing.description),
+            },
+        };
+        if let Some(full_at) = self.full_at {
+            update.insert("$min", doc! { "full_at": full_at });
+        }
+        Ok(update)
+    }
+}
@@ -0,0 +1,56 @@
+//! 공개 목록에서 숨기는 듀티 / 카테고리
+//!
+//! 설정의 `[display]` 목록으로 만들며, 목록 조회와 웹소켓 전송에서 제외합니다.
+//! 통계는 숨긴 모집글도 계속 집계합니다.
+
+use crate::config::Display;
+use crate::listing::{DutyCategory, DutyType, PartyFinderListing};
+
+/// 숨길 듀티 / 카테고리
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Blocklist {
+    pub duties: Vec<u16>,
+    pub categories: Vec<DutyCategory>,
+}
+
+impl Blocklist {
+    /// 설정에서 생성 (알 수 없는 카테고리 이름은 경고 후 무시)
+    pub fn new(config: &Display) -> Self {
+        let categories = config
+            .blocked_categories
+            .iter()
+            .filter_map(|name| {
+                let category = DutyCategory::ALL.into_iter().find(|c| format!("{:?}", c) == *name);
+                if category.is_none() {
+                    tracing::warn!("ignoring unknown blocked category `{}`", name);
+                }
+                category
+            })
+            .collect();
+
+        Self {
+            duties: config.blocked_duties.clone(),
+            categories,
+        }
+    }
+
+    /// 카테고리 이름 (설정과 같은 표기)
+    pub fn category_names(&self) -> Vec<String> {
+        self.categories.iter().map(|category| format!("{:?}", category)).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.duties.is_empty() && self.categories.is_empty()
+    }
+
+    /// 공개 목록에서 숨겨야 하는 모집글 (듀티 ID는 룰렛 ID와 겹치므로 일반 듀티에만 적용, 분류는 `canonical_category`)
+    pub fn blocks(&self, listing: &PartyFinderListing) -> bool {
+        (listing.duty_type == DutyType::Normal && self.duties.contains(&listing.duty))
+            || self.categories.contains(&listing.canonical_category())
+    }
+
+    /// 웹소켓으로 보낼 모집글만 남김
+    pub fn retain_visible(&self, listings: &mut Vec<PartyFinderListing>) {
+        listings.retain(|listing| !self.blocks(listing));
+    }
+}
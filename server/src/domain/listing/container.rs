@@ -1,7 +1,108 @@
-use crate::listing::PartyFinderListing;
-use chrono::{DateTime, Duration, TimeDelta, Utc};
+use crate::config::ListingSort;
+use crate::ffxiv::Language;
+use crate::listing::description::DescriptionLanguage;
+use crate::listing::schedule::extract_schedule;
+use crate::listing::expiry::ExpiryInfo;
+use crate::listing::{DutyCategory, PartyFinderListing, PartyMember};
+use crate::sestring_ext::SeStringExt;
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
 use chrono_humanize::HumanTime;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+
+/// 모집글당 보관하는 업로더 지문 최대 개수
+pub const MAX_UPLOADER_FINGERPRINTS: usize = 16;
+
+/// 모집글당 보관하는 이전 설명 최대 개수
+pub const MAX_DESCRIPTION_HISTORY: usize = 5;
+
+/// 모집글당 보관하는 채워진 자리 수 기록 최대 개수 (넘으면 오래된 것부터 버림)
+pub const MAX_SLOTS_HISTORY: usize = 24;
+
+/// 공개 조회에서 제외하는 컨테이너 필드 (관리자 원본 조회에서만 노출)
+pub const PRIVATE_CONTAINER_FIELDS: [&str; 4] = [
+    "uploader_fingerprints",
+    "description_hash",
+    "description_text",
+    "description_history",
+];
+
+/// 편집으로 교체되기 전의 설명
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct DescriptionEdit {
+    /// 정리된 설명 텍스트
+    pub text: String,
+    /// 새 설명으로 교체된 시각
+    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub replaced_at: DateTime<Utc>,
+}
+
+/// 채워진 자리 수가 바뀐 시점 (`ListingContainer::slots_history`)
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct SlotsSample {
+    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub at: DateTime<Utc>,
+    pub slots_filled: u32,
+}
+
+/// 모더레이션 기록용으로 정리한 설명 (자동 번역은 영어, 제어 문자 제거)
+pub fn sanitized_description(listing: &PartyFinderListing) -> String {
+    listing
+        .description
+        .full_text(&Language::English)
+        .chars()
+        .filter(|c| !c.is_control() || *c == '\n')
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// 설명 텍스트의 16자리 16진수 해시 (편집 감지용)
+pub fn description_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// 모집글 업로드 한 건의 결과 (`insert_listing`, 업로드 파이프라인이 `upload_outcome`에 기록)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadOutcome {
+    /// 처음 저장된 모집글
+    Inserted,
+    /// 저장된 모집글 내용이 바뀜
+    Updated,
+    /// 같은 내용을 다시 업로드함
+    Unchanged,
+    /// 저장된 것보다 오래된 스냅샷이라 `updated_at` 등 업로드 기록만 갱신함
+    Stale,
+}
+
+impl UploadOutcome {
+    /// 웹소켓으로 전송할지 (저장된 모집글이 실제로 바뀐 경우만)
+    pub fn should_publish(self) -> bool {
+        matches!(self, Self::Inserted | Self::Updated)
+    }
+}
+
+/// 모집글 저장 결과 (`insert_listing`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoredUpload {
+    pub outcome: UploadOutcome,
+    /// 저장돼 있던 모집글을 모더레이터가 숨겼는지
+    pub hidden: bool,
+    /// 업로드된 설명에 금칙어가 있는지
+    pub flagged: bool,
+}
+
+impl StoredUpload {
+    /// 웹소켓으로 전송할지 (숨기거나 금칙어로 표시된 모집글은 바뀌어도 보내지 않음)
+    pub fn should_publish(self) -> bool {
+        self.outcome.should_publish() && !self.hidden && !self.flagged
+    }
+}
 
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct ListingContainer {
@@ -10,26 +111,172 @@ pub struct ListingContainer {
     #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
     pub updated_at: DateTime<Utc>,
     pub listing: PartyFinderListing,
+    /// 이 모집글을 업로드한 횟수 (중복 업로드 포함)
+    #[serde(default)]
+    pub upload_count: u32,
+    /// 업로드한 클라이언트의 해시 지문 (중복 없음, 최대 `MAX_UPLOADER_FINGERPRINTS`개)
+    #[serde(default)]
+    pub uploader_fingerprints: Vec<String>,
+    /// 현재 설명의 해시
+    #[serde(default)]
+    pub description_hash: Option<String>,
+    /// 현재 설명 (편집되면 `description_history`로 옮김)
+    #[serde(default)]
+    pub description_text: Option<String>,
+    /// 이전 설명 (오래된 순, 최대 `MAX_DESCRIPTION_HISTORY`개)
+    #[serde(default)]
+    pub description_history: Vec<DescriptionEdit>,
+    /// 현재 설명에서 판별한 언어 (설명이 비었으면 `None`)
+    #[serde(default)]
+    pub description_language: Option<DescriptionLanguage>,
+    /// 현재 설명에 자동 번역 문구가 있는지
+    #[serde(default)]
+    pub has_autotranslate: bool,
+    /// 이 모집글을 올린 업로더의 스냅샷에서 처음 빠진 시각 (다시 업로드되면 `None`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unconfirmed_at: Option<mongodb::bson::DateTime>,
+    /// `PartyFinderListing::canonical_category` (통계 / 숨김 쿼리용, 이 필드 전에 저장된 문서는 `None`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_category: Option<DutyCategory>,
+    /// 모더레이터가 숨긴 모집글 (공개 목록 / API / 웹소켓에서 제외, 다시 업로드해도 유지)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    /// 설명에 금칙어가 있는 모집글 (`BlockedKeywords::flags`, 공개 목록 / API / 웹소켓에서 제외)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flagged: bool,
+    /// 채워진 자리 수가 바뀔 때마다 남긴 기록 (오래된 순, 최대 `MAX_SLOTS_HISTORY`개)
+    #[serde(default)]
+    pub slots_history: Vec<SlotsSample>,
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+/// 저장할 자리별 멤버 (자리 순서, 같은 자리는 먼저 온 멤버만)
+pub fn sorted_members(members: &[PartyMember]) -> Vec<PartyMember> {
+    let mut members = members.to_vec();
+    members.sort_by_key(|member| member.slot_index);
+    members.dedup_by_key(|member| member.slot_index);
+    members
+}
+
+impl ListingContainer {
+    /// 모든 자리가 처음 찬 것으로 기록된 시각 (기록에 없으면 `None`)
+    pub fn full_at(&self) -> Option<DateTime<Utc>> {
+        let capacity = self.listing.total_capacity() as u32;
+        self.slots_history
+            .iter()
+            .find(|sample| capacity > 0 && sample.slots_filled >= capacity)
+            .map(|sample| sample.at)
+    }
+
+    /// 서로 다른 업로더 수
+    pub fn uploader_count(&self) -> usize {
+        self.uploader_fingerprints.len()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct QueriedListing {
     #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
     pub created_at: DateTime<Utc>,
     #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
     pub updated_at: DateTime<Utc>,
-    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    /// 정렬용 업데이트 구간 (DB에 저장하지 않고 조회 후 `refresh_bucket`으로 계산)
+    #[serde(default, with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
     pub updated_minute: DateTime<Utc>,
     pub time_left: f64,
     pub listing: PartyFinderListing,
+    /// 업로드 횟수 (`ListingContainer::upload_count`)
+    #[serde(default)]
+    pub upload_count: u32,
+    /// 서로 다른 업로더 수 (`ListingContainer::uploader_count`)
+    #[serde(default)]
+    pub uploader_count: u32,
+    /// 만료 정보 (DB에 저장하지 않고 조회 후 `refresh_expiry`로 계산)
+    #[serde(skip)]
+    pub expiry: ExpiryInfo,
+    /// 이전 설명 (금칙어 확인 목록에서만 채움, `filtered_listings_pipeline`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub description_history: Vec<DescriptionEdit>,
+}
+
+/// 웹사이트 목록 정렬 기준
+///
+/// `updated_bucket` 내림차순 → `category_rank` 내림차순 → `time_left` 오름차순
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct SortKey {
+    /// 마지막 업데이트 시각 (듀티 분류별 구간 단위로 내림, `updated_bucket`)
+    pub updated_bucket: DateTime<Utc>,
+    /// `PartyFinderCategory::display_rank`
+    pub category_rank: u8,
+    /// 남은 시간 (초)
+    pub time_left: f64,
+}
+
+impl SortKey {
+    /// 목록에서 먼저 표시되는 쪽이 `Less`
+    pub fn display_cmp(&self, other: &Self) -> Ordering {
+        other.updated_bucket.cmp(&self.updated_bucket)
+            .then_with(|| other.category_rank.cmp(&self.category_rank))
+            .then_with(|| self.time_left.partial_cmp(&other.time_left).unwrap_or(Ordering::Equal))
+    }
+}
+
+/// 듀티 분류의 업데이트 구간 (분, 최소 1)
+pub fn bucket_minutes(sort: &ListingSort, category: DutyCategory) -> u32 {
+    sort.category_bucket_minutes
+        .get(&format!("{:?}", category))
+        .copied()
+        .unwrap_or(sort.bucket_minutes)
+        .max(1)
+}
+
+/// `updated_at`을 듀티 분류의 구간 단위로 내림 (Unix epoch 기준, Mongo `$dateTrunc`와 같은 경계)
+pub fn updated_bucket(updated_at: DateTime<Utc>, category: DutyCategory, sort: &ListingSort) -> DateTime<Utc> {
+    let bucket_secs = i64::from(bucket_minutes(sort, category)) * 60;
+    let secs = updated_at.timestamp();
+    Utc.timestamp_opt(secs - secs.rem_euclid(bucket_secs), 0)
+        .single()
+        .unwrap_or(updated_at)
+}
+
+/// 웹사이트와 API가 공유하는 모집글 정렬
+pub fn sort_for_display(listings: &mut [QueriedListing]) {
+    listings.sort_by(|a, b| a.sort_key().display_cmp(&b.sort_key()));
 }
 
 impl QueriedListing {
+    pub fn sort_key(&self) -> SortKey {
+        SortKey {
+            updated_bucket: self.updated_minute,
+            category_rank: self.listing.pf_category().display_rank(),
+            time_left: self.time_left,
+        }
+    }
+
+    /// 두 번 이상 업로드된 모집글 (여러 클라이언트가 확인한 데이터)
+    pub fn is_multi_sourced(&self) -> bool {
+        self.upload_count >= 2
+    }
+
+    /// 설명에 적힌 예정 시각 (마지막 업데이트 시각 기준으로 해석)
+    pub fn parsed_schedule(&self, tz: FixedOffset) -> Option<DateTime<Utc>> {
+        let description = self.listing.description.full_text(&Language::English);
+        extract_schedule(&description, self.updated_at, tz)
+    }
+
+    /// 정렬 설정에 맞춰 `updated_minute` 계산
+    pub fn refresh_bucket(&mut self, sort: &ListingSort) {
+        self.updated_minute = updated_bucket(self.updated_at, self.listing.canonical_category(), sort);
+    }
+
+    /// `now` 기준으로 만료 정보와 `time_left`를 함께 계산
+    pub fn refresh_expiry(&mut self, now: DateTime<Utc>) {
+        self.expiry = ExpiryInfo::new(self.updated_at, self.listing.seconds_remaining, now);
+        self.time_left = self.expiry.time_left(now);
+    }
+
+    #[deprecated(note = "use `expiry.human_seconds_left()`")]
     pub fn human_time_left(&self) -> HumanTime {
-        HumanTime::from(
-            TimeDelta::try_milliseconds((self.time_left * 1000f64) as i64)
-                .unwrap_or(TimeDelta::zero()),
-        )
+        self.expiry.human_seconds_left()
     }
 
     pub fn since_updated(&self) -> Duration {
@@ -46,7 +293,8 @@ impl QueriedListing {
     }
 
     /// JavaScript에서 시간을 처리하기 위한 남은 시간 (초 단위)
+    #[deprecated(note = "use `expiry.seconds_left` / `expiry.expires_at_timestamp()`")]
     pub fn time_left_seconds(&self) -> i64 {
-        self.time_left as i64
+        self.expiry.seconds_left
     }
 }
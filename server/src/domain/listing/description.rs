@@ -0,0 +1,106 @@
+//! 설명 언어 판별과 자동 번역 사용 여부
+//!
+//! 저장할 때 한 번 계산해 컨테이너에 기록하고, 통계의 언어별 비교에 사용합니다.
+//! 정확한 언어 판별이 아니라 문자 종류와 자주 쓰이는 단어로 고르는 대략적인 분류입니다.
+
+use serde::{Deserialize, Serialize};
+use sestring::{Payload, SeString};
+
+/// CJK 문자 하나를 라틴 문자 몇 개로 셀지 (한 글자에 담긴 정보량 차이 보정)
+const CJK_WEIGHT: usize = 3;
+
+/// 설명에서 판별한 언어
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum DescriptionLanguage {
+    #[serde(rename = "ja")]
+    Japanese,
+    #[serde(rename = "ko")]
+    Korean,
+    #[serde(rename = "zh")]
+    Chinese,
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "fr")]
+    French,
+}
+
+impl DescriptionLanguage {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Japanese => "Japanese",
+            Self::Korean => "Korean",
+            Self::Chinese => "Chinese",
+            Self::English => "English",
+            Self::German => "German",
+            Self::French => "French",
+        }
+    }
+}
+
+const GERMAN_WORDS: &[&str] = &[
+    "und", "der", "die", "das", "mit", "für", "wir", "ist", "nicht", "suchen", "noch", "ein", "eine", "auf",
+];
+const FRENCH_WORDS: &[&str] = &[
+    "le", "la", "les", "et", "pour", "avec", "nous", "est", "pas", "des", "une", "cherche", "recherche", "du",
+];
+const ENGLISH_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "we", "is", "not", "looking", "need", "a", "to", "of", "please", "lf",
+];
+
+/// 정리된 설명 텍스트의 언어 (글자가 없으면 `None`)
+///
+/// CJK 문자가 충분하면 한글 / 가나 / 한자 순으로 고르고,
+/// 아니면 독일어 / 프랑스어 단어가 영어 단어보다 많을 때만 해당 언어로 봅니다.
+pub fn detect_language(text: &str) -> Option<DescriptionLanguage> {
+    let (mut hangul, mut kana, mut han, mut latin) = (0, 0, 0, 0);
+    for c in text.chars() {
+        match c {
+            '\u{AC00}'..='\u{D7A3}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' => hangul += 1,
+            '\u{3040}'..='\u{30FF}' | '\u{FF66}'..='\u{FF9F}' => kana += 1,
+            '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' => han += 1,
+            c if c.is_alphabetic() && c <= '\u{024F}' => latin += 1,
+            _ => {}
+        }
+    }
+
+    let cjk = hangul + kana + han;
+    if cjk == 0 && latin == 0 {
+        return None;
+    }
+
+    if cjk > 0 && cjk * CJK_WEIGHT >= latin {
+        return Some(if hangul > 0 && hangul >= kana {
+            DescriptionLanguage::Korean
+        } else if kana > 0 {
+            DescriptionLanguage::Japanese
+        } else {
+            DescriptionLanguage::Chinese
+        });
+    }
+
+    let (mut german, mut french, mut english) = (0, 0, 0);
+    for word in text.split(|c: char| !c.is_alphabetic()).filter(|word| !word.is_empty()) {
+        let word = word.to_lowercase();
+        german += usize::from(GERMAN_WORDS.contains(&word.as_str()));
+        french += usize::from(FRENCH_WORDS.contains(&word.as_str()));
+        english += usize::from(ENGLISH_WORDS.contains(&word.as_str()));
+    }
+
+    Some(if german > english && german > french {
+        DescriptionLanguage::German
+    } else if french > english && french > german {
+        DescriptionLanguage::French
+    } else {
+        DescriptionLanguage::English
+    })
+}
+
+/// 설명에 자동 번역 문구가 들어 있는지
+pub fn has_autotranslate(description: &SeString) -> bool {
+    description
+        .0
+        .iter()
+        .any(|payload| matches!(payload, Payload::AutoTranslate(_)))
+}
@@ -0,0 +1,48 @@
+//! 모집글 만료 정보
+//!
+//! 남은 시간을 집계 쿼리, 템플릿, API, JS가 각자 계산하면 몇 초씩 어긋나므로
+//! 한 시점(`now`) 기준으로 한 번만 계산해 모든 곳에서 공유합니다.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use chrono_humanize::HumanTime;
+use serde::Serialize;
+
+/// 남은 시간이 이 값(초) 미만이면 곧 만료되는 모집글
+pub const EXPIRING_SOON_SECONDS: i64 = 5 * 60;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ExpiryInfo {
+    /// 만료 시각 (마지막 업데이트 시각 + 업데이트 당시 남은 시간)
+    pub expires_at: DateTime<Utc>,
+    /// `now` 기준 남은 시간 (초, 버림, 최소 0)
+    pub seconds_left: i64,
+    /// 남은 시간이 `EXPIRING_SOON_SECONDS` 미만인지
+    pub is_expiring_soon: bool,
+}
+
+impl ExpiryInfo {
+    pub fn new(updated_at: DateTime<Utc>, seconds_remaining: u16, now: DateTime<Utc>) -> Self {
+        let expires_at = updated_at + TimeDelta::try_seconds(i64::from(seconds_remaining)).unwrap();
+        let seconds_left = (expires_at - now).num_seconds().max(0);
+
+        Self {
+            expires_at,
+            seconds_left,
+            is_expiring_soon: seconds_left < EXPIRING_SOON_SECONDS,
+        }
+    }
+
+    /// `now` 기준 남은 시간 (초, 소수점 포함, 만료 후에는 음수)
+    pub fn time_left(&self, now: DateTime<Utc>) -> f64 {
+        (self.expires_at - now).num_milliseconds() as f64 / 1000.0
+    }
+
+    /// JavaScript 카운트다운용 만료 시각 Unix timestamp (초 단위)
+    pub fn expires_at_timestamp(&self) -> i64 {
+        self.expires_at.timestamp()
+    }
+
+    pub fn human_seconds_left(&self) -> HumanTime {
+        HumanTime::from(TimeDelta::try_seconds(self.seconds_left).unwrap_or(TimeDelta::zero()))
+    }
+}
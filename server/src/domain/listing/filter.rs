@@ -0,0 +1,74 @@
+//! `/api/listings`의 듀티 / 데이터 센터 / 분류 조건
+//!
+//! 조건은 aggregation의 `$match` 단계(`mongo::listing_filter_match`)로 적용해,
+//! 조건에 맞지 않는 모집글은 DB에서 읽지 않습니다.
+//! 웹소켓 구독은 같은 조건을 메모리의 모집글에 적용합니다(`matches`).
+
+use crate::listing::{DutyCategory, DutyType, PartyFinderListing};
+
+/// 모집글 조건 (비어 있는 목록은 조건 없음)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListingFilter {
+    /// 일반 듀티 ID (룰렛 ID와 겹치므로 일반 듀티에만 적용)
+    pub duties: Vec<u16>,
+    /// 생성 서버 ID (데이터 센터의 서버)
+    pub worlds: Vec<u16>,
+    /// `canonical_category` 기준 분류
+    pub categories: Vec<DutyCategory>,
+    /// 금칙어로 표시된 모집글만 (모더레이터 전용, 웹소켓에는 표시된 모집글을 보내지 않음)
+    pub flagged: bool,
+}
+
+impl ListingFilter {
+    /// 쿼리 값에서 생성 (쉼표로 여러 값, 이름은 대소문자 무시)
+    ///
+    /// 알 수 없는 값이 있으면 맞는 모집글이 없으므로 `None`입니다.
+    pub fn parse(duty: Option<&str>, datacentre: Option<&str>, category: Option<&str>) -> Option<Self> {
+        let duties = values(duty)
+            .map(|id| id.parse().ok())
+            .collect::<Option<Vec<u16>>>()?;
+        let worlds = match datacentre.map(str::trim).filter(|name| !name.is_empty()) {
+            Some(name) => crate::ffxiv::data_centre_worlds(name)?,
+            None => Vec::new(),
+        };
+        let categories = values(category).map(category_by_name).collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            duties,
+            worlds,
+            categories,
+            flagged: false,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.duties.is_empty() && self.worlds.is_empty() && self.categories.is_empty() && !self.flagged
+    }
+
+    /// 모집글이 조건에 맞는지 (`mongo::listing_filter_match`와 같은 규칙)
+    pub fn matches(&self, listing: &PartyFinderListing) -> bool {
+        (self.duties.is_empty() || (listing.duty_type == DutyType::Normal && self.duties.contains(&listing.duty)))
+            && self.matches_world(listing.created_world)
+            && (self.categories.is_empty() || self.categories.contains(&listing.canonical_category()))
+    }
+
+    /// 생성 서버만 조건에 맞는지 (듀티 / 분류를 모르는 사라진 모집글용)
+    pub fn matches_world(&self, created_world: u16) -> bool {
+        self.worlds.is_empty() || self.worlds.contains(&created_world)
+    }
+}
+
+/// 분류 이름 (`HighEndDuty`, 대소문자 무시)
+pub fn category_by_name(name: &str) -> Option<DutyCategory> {
+    DutyCategory::ALL
+        .into_iter()
+        .find(|c| format!("{:?}", c).eq_ignore_ascii_case(name))
+}
+
+fn values(value: Option<&str>) -> impl Iterator<Item = &str> {
+    value
+        .into_iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
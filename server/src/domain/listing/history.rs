@@ -0,0 +1,167 @@
+//! 플레이어가 올린 모집글 기록 (`GET /api/players/{content_id}/listings`)
+//!
+//! 보관 기록(`listings_archive`)은 TTL 없이 남지만 설명과 파티장 Content ID는 저장하지 않으므로,
+//! 아직 TTL로 지워지지 않은 모집글은 모집글 컬렉션의 설명과 파티장을 함께 씁니다.
+//! 업로드의 `content_id_lower`는 모집자 Content ID의 하위 32비트라 다른 플레이어와 겹칠 수 있으므로,
+//! 파티장을 아는 모집글은 파티장 Content ID가 같을 때만 셉니다.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::ffxiv;
+use crate::listing::container::sanitized_description;
+use crate::listing::{DutyCategory, DutyType, ListingContainer, PartyFinderListing};
+use crate::player::compaction::lower_content_id;
+
+/// `limit`을 주지 않았을 때의 결과 수
+pub const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+/// 최대 결과 수
+pub const MAX_HISTORY_LIMIT: usize = 100;
+
+/// 보관 기록 한 건 (`PendingArchive::update`로 쓴 필드 중 기록에 필요한 것만)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArchivedListing {
+    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub last_seen: DateTime<Utc>,
+    pub listing: ArchivedFields,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArchivedFields {
+    pub id: u32,
+    pub last_server_restart: u32,
+    pub created_world: u16,
+    pub category: DutyCategory,
+    pub duty: u16,
+    pub duty_type: DutyType,
+}
+
+/// 플레이어가 올린 모집글 한 건
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostedListing {
+    pub id: u32,
+    pub created_world: u16,
+    pub last_server_restart: u32,
+    pub created_at: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub category: DutyCategory,
+    pub duty: u16,
+    pub duty_type: DutyType,
+    /// 아직 남아 있는 모집글만 (보관 기록에는 설명이 없음)
+    pub description: Option<String>,
+}
+
+impl HostedListing {
+    /// 저장 키 (`PendingArchive::key`와 같은 순서)
+    pub fn key(&self) -> (u32, u32, u16) {
+        (self.id, self.last_server_restart, self.created_world)
+    }
+
+    /// 일반 듀티면 듀티 정보
+    pub fn duty_info(&self) -> Option<&'static ffxiv::duties::DutyInfo> {
+        match self.duty_type {
+            DutyType::Normal => ffxiv::duty(u32::from(self.duty)),
+            _ => None,
+        }
+    }
+
+    /// `PartyFinderListing::canonical_category`와 같은 규칙
+    pub fn canonical_category(&self) -> DutyCategory {
+        match self.duty_type {
+            DutyType::Normal => self.category.canonical(self.duty_info()),
+            _ => self.category,
+        }
+    }
+}
+
+impl From<ArchivedListing> for HostedListing {
+    fn from(archived: ArchivedListing) -> Self {
+        let listing = archived.listing;
+        Self {
+            id: listing.id,
+            created_world: listing.created_world,
+            last_server_restart: listing.last_server_restart,
+            created_at: archived.created_at,
+            last_seen: archived.last_seen,
+            category: listing.category,
+            duty: listing.duty,
+            duty_type: listing.duty_type,
+            description: None,
+        }
+    }
+}
+
+impl From<&ListingContainer> for HostedListing {
+    fn from(container: &ListingContainer) -> Self {
+        let listing = &container.listing;
+        Self {
+            id: listing.id,
+            created_world: listing.created_world,
+            last_server_restart: listing.last_server_restart,
+            created_at: container.created_at,
+            last_seen: container.updated_at,
+            category: listing.category,
+            duty: listing.duty,
+            duty_type: listing.duty_type,
+            description: Some(sanitized_description(listing)),
+        }
+    }
+}
+
+/// 이 플레이어가 올린 모집글인지 (파티장을 모르면 하위 32비트로 판단)
+pub fn hosted_by(listing: &PartyFinderListing, content_id: u64) -> bool {
+    match listing.leader_content_id {
+        0 => u64::from(listing.content_id_lower) == lower_content_id(content_id),
+        leader => leader == content_id,
+    }
+}
+
+/// 보관 기록과 아직 남아 있는 모집글을 합쳐 최근에 만든 순으로 최대 `limit`개
+///
+/// 같은 모집글은 남아 있는 쪽의 설명을 쓰고, 처음 본 시각은 더 이른 쪽을 씁니다.
+/// 남아 있는 모집글의 파티장이 다른 플레이어면 보관 기록도 뺍니다.
+pub fn hosted_listings(
+    content_id: u64,
+    archived: Vec<ArchivedListing>,
+    live: &[ListingContainer],
+    limit: usize,
+) -> Vec<HostedListing> {
+    let mut live: HashMap<(u32, u32, u16), (&ListingContainer, bool)> = live
+        .iter()
+        .map(|container| {
+            let listing = &container.listing;
+            let key = (listing.id, listing.last_server_restart, listing.created_world);
+            (key, (container, hosted_by(listing, content_id)))
+        })
+        .collect();
+
+    let mut hosted: Vec<HostedListing> = archived
+        .into_iter()
+        .map(HostedListing::from)
+        .filter_map(|archived| match live.remove(&archived.key()) {
+            Some((_, false)) => None,
+            Some((container, true)) => {
+                let mut current = HostedListing::from(container);
+                current.created_at = current.created_at.min(archived.created_at);
+                current.last_seen = current.last_seen.max(archived.last_seen);
+                Some(current)
+            }
+            None => Some(archived),
+        })
+        .collect();
+    // 아직 보관 대기열에 있거나 파티장으로만 찾은 모집글
+    hosted.extend(
+        live.into_values()
+            .filter(|&(_, hosted)| hosted)
+            .map(|(container, _)| HostedListing::from(container)),
+    );
+
+    hosted.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.key().cmp(&a.key())));
+    hosted.truncate(limit);
+    hosted
+}
@@ -0,0 +1,64 @@
+//! 설명 금칙어 자동 표시 (`[keyword_filter]`)
+//!
+//! 시작할 때 한 번 `RegexSet`으로 만들어 `State`에 두고, 업로드마다 설명의 네 언어 텍스트를 검사합니다.
+//! 맞는 모집글도 그대로 저장해 업로더가 동작 차이로 필터를 알아채지 못하게 하고,
+//! `flagged`만 기록해 공개 목록 / API / 웹소켓에서 뺍니다.
+
+use anyhow::Context;
+use regex::{RegexSet, RegexSetBuilder};
+
+use crate::config::KeywordFilter;
+use crate::ffxiv::SUPPORTED_LANGUAGES;
+use crate::listing::PartyFinderListing;
+use crate::sestring_ext::SeStringExt;
+
+/// 금칙어 검사 전에 지우는 보이지 않는 문자 (단어 사이에 끼워 검사를 피하는 용도)
+const INVISIBLE: [char; 5] = ['\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}'];
+
+/// 설명 금칙어 (설정이 비었으면 아무것도 표시하지 않음)
+#[derive(Debug, Clone)]
+pub struct BlockedKeywords {
+    set: RegexSet,
+}
+
+impl BlockedKeywords {
+    /// 설정에서 생성 (단어는 그대로, 정규식은 문법 그대로, 모두 대소문자 무시)
+    pub fn new(config: &KeywordFilter) -> anyhow::Result<Self> {
+        let patterns = config
+            .words
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .map(regex::escape)
+            .chain(config.patterns.iter().cloned());
+        let set = RegexSetBuilder::new(patterns)
+            .case_insensitive(true)
+            .build()
+            .context("invalid keyword_filter pattern")?;
+        Ok(Self { set })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// 텍스트에 금칙어가 있는지 (제어 문자와 보이지 않는 문자는 지우고 검사)
+    pub fn matches(&self, text: &str) -> bool {
+        let text: String = text
+            .chars()
+            .filter(|c| !c.is_control() && !INVISIBLE.contains(c))
+            .collect();
+        self.set.is_match(&text)
+    }
+
+    /// 모집글 설명에 금칙어가 있는지
+    ///
+    /// 텍스트 사이의 아이콘 / 색상 같은 payload는 빼고 이어 붙인 `full_text`를
+    /// 지원하는 모든 언어로 검사하므로 자동 번역 문구도 각 언어로 확인합니다.
+    pub fn flags(&self, listing: &PartyFinderListing) -> bool {
+        !self.is_empty()
+            && SUPPORTED_LANGUAGES
+                .iter()
+                .any(|lang| self.matches(&listing.description.full_text(lang)))
+    }
+}
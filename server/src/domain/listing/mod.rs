@@ -3,8 +3,24 @@
 //! 파티 찾기 리스팅 관련 타입 및 컨테이너
 
 pub mod types;
+pub mod blocklist;
+pub mod archive;
 pub mod container;
+pub mod description;
+pub mod expiry;
+pub mod filter;
+pub mod history;
+pub mod keywords;
+pub mod moderation;
+pub mod requirements;
+pub mod schedule;
+pub mod snapshot;
+pub mod validation;
 
 // Re-exports for convenience
 pub use types::*;
+pub use blocklist::*;
 pub use container::*;
+pub use expiry::*;
+pub use filter::*;
+pub use validation::*;
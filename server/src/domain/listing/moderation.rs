@@ -0,0 +1,72 @@
+//! 모더레이터가 숨긴 모집글 (`/api/moderation/hide`, `/api/moderation/unhide`)
+//!
+//! 숨긴 모집글은 지우지 않고 `hidden: true`만 기록하므로 통계에는 계속 집계됩니다.
+//! `insert_listing`은 `hidden`을 건드리지 않아 같은 모집글을 다시 올려도 숨김이 유지되고,
+//! 숨긴 모집글의 업로드는 웹소켓으로 보내지 않습니다.
+//! 숨김 / 해제는 누가 언제 했는지 `moderation_log` 컬렉션에 남깁니다.
+
+use chrono::{DateTime, Utc};
+use mongodb::bson::{doc, Document};
+use serde::{Deserialize, Serialize};
+
+use crate::listing::ListingContainer;
+
+/// 숨기거나 다시 보일 모집글
+///
+/// 모집글 ID는 월드 / 서버 재시작마다 겹칠 수 있으므로 `PartyFinderListing::key()`의 세 값을 모두 받아
+/// 정확히 그 모집글만 고릅니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModerationTarget {
+    pub id: u32,
+    pub created_world: u16,
+    pub last_server_restart: u32,
+}
+
+impl ModerationTarget {
+    /// 대상 모집글인지 (`filter`와 같은 규칙)
+    pub fn matches(&self, container: &ListingContainer) -> bool {
+        let listing = &container.listing;
+        listing.id == self.id
+            && listing.created_world == self.created_world
+            && listing.last_server_restart == self.last_server_restart
+    }
+
+    /// `set_listing_hidden`의 조회 조건
+    pub fn filter(&self) -> Document {
+        doc! {
+            "listing.id": self.id,
+            "listing.created_world": u32::from(self.created_world),
+            "listing.last_server_restart": self.last_server_restart,
+        }
+    }
+}
+
+/// 숨김 기록의 동작
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    Hide,
+    Unhide,
+}
+
+impl ModerationAction {
+    /// 이 동작 뒤의 `hidden` 값
+    pub fn hidden(self) -> bool {
+        self == Self::Hide
+    }
+}
+
+/// `moderation_log` 문서 한 건
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ModerationRecord {
+    pub action: ModerationAction,
+    /// 모더레이터 토큰 이름
+    pub moderator: String,
+    pub target: ModerationTarget,
+    /// 조건에 맞은 모집글 수
+    pub matched: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub at: DateTime<Utc>,
+}
@@ -0,0 +1,152 @@
+//! 모집글 참가 조건 (새 플러그인이 설명에서 추출해 보내는 구조화된 값)
+//!
+//! 모든 필드는 선택이며 모르는 필드는 무시합니다. 값이 잘못된 필드는 모집글을 거부하지 않고
+//! 그 필드만 버리므로, 플러그인이 새 값을 추가해도 이전 서버가 업로드를 받을 수 있습니다.
+
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// 예정 진행 시간 상한 (24시간)
+pub const MAX_DURATION_MINUTES: u16 = 24 * 60;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ListingRequirements {
+    /// 필요한 최소 클리어 횟수 (클리어 여부 자체는 `ConditionFlags::DUTY_COMPLETE`)
+    #[serde(default, deserialize_with = "loose_number", skip_serializing_if = "Option::is_none")]
+    pub min_clears: Option<u16>,
+    #[serde(default, deserialize_with = "loose_enum", skip_serializing_if = "Option::is_none")]
+    pub voice_chat: Option<VoiceChat>,
+    /// 게임 분배 설정(`LootRuleFlags`)에 없는 분배 약속
+    #[serde(default, deserialize_with = "loose_enum", skip_serializing_if = "Option::is_none")]
+    pub loot: Option<LootRule>,
+    /// 예정 진행 시간 (분, 최대 `MAX_DURATION_MINUTES`)
+    #[serde(default, deserialize_with = "loose_duration", skip_serializing_if = "Option::is_none")]
+    pub duration_minutes: Option<u16>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceChat {
+    Required,
+    Preferred,
+    NotUsed,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LootRule {
+    /// 모두 자유롭게 주사위
+    FreeRoll,
+    /// 미리 정한 사람이 가져감
+    Reserved,
+    /// 한 사람당 하나
+    OnePerMember,
+    /// 파티장이 나눠줌
+    LeaderDistributes,
+}
+
+/// 목록에 표시하는 조건 하나
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequirementBadge {
+    /// `icons.svg`의 심볼
+    pub icon: &'static str,
+    /// 툴팁의 번역 키 (`translations.js`)
+    pub i18n: &'static str,
+    /// 영어 툴팁
+    pub title: &'static str,
+    /// 아이콘 옆에 표시하는 값
+    pub text: Option<String>,
+}
+
+impl ListingRequirements {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn badges(&self) -> Vec<RequirementBadge> {
+        let badge = |icon, i18n, title, text| RequirementBadge { icon, i18n, title, text };
+        let mut badges = Vec::new();
+
+        if let Some(clears) = self.min_clears {
+            badges.push(badge("trophy", "req_min_clears", "Required clears", Some(format!("{}+", clears))));
+        }
+        if let Some(voice_chat) = self.voice_chat {
+            badges.push(match voice_chat {
+                VoiceChat::Required => badge("headset", "req_voice_required", "Voice chat required", None),
+                VoiceChat::Preferred => badge("headset", "req_voice_preferred", "Voice chat preferred", None),
+                VoiceChat::NotUsed => badge("headset", "req_voice_not_used", "No voice chat", None),
+            });
+        }
+        if let Some(loot) = self.loot {
+            badges.push(match loot {
+                LootRule::FreeRoll => badge("bag", "req_loot_free_roll", "Free roll", None),
+                LootRule::Reserved => badge("bag", "req_loot_reserved", "Loot reserved", None),
+                LootRule::OnePerMember => badge("bag", "req_loot_one_per_member", "One item per member", None),
+                LootRule::LeaderDistributes => badge("bag", "req_loot_leader", "Leader distributes loot", None),
+            });
+        }
+        if let Some(minutes) = self.duration_minutes {
+            let text = match (minutes / 60, minutes % 60) {
+                (0, minutes) => format!("{}m", minutes),
+                (hours, 0) => format!("{}h", hours),
+                (hours, minutes) => format!("{}h {}m", hours, minutes),
+            };
+            badges.push(badge("clock", "req_duration", "Planned duration", Some(text)));
+        }
+
+        badges
+    }
+}
+
+/// 알 수 없는 값이나 다른 타입도 받아서 버리기 위한 형태
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Loose<T> {
+    Known(T),
+    Unknown(IgnoredAny),
+}
+
+impl<T> Loose<T> {
+    fn known(self) -> Option<T> {
+        match self {
+            Self::Known(value) => Some(value),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+fn loose_enum<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Option::<Loose<T>>::deserialize(de)?.and_then(Loose::known))
+}
+
+fn loose_number<'de, D>(de: D) -> Result<Option<u16>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<Loose<u64>>::deserialize(de)?
+        .and_then(Loose::known)
+        .and_then(|number| u16::try_from(number).ok()))
+}
+
+/// 0분은 버리고 24시간을 넘으면 24시간으로 자름
+fn loose_duration<'de, D>(de: D) -> Result<Option<u16>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<Loose<u64>>::deserialize(de)?
+        .and_then(Loose::known)
+        .filter(|&minutes| minutes > 0)
+        .map(|minutes| minutes.min(u64::from(MAX_DURATION_MINUTES)) as u16))
+}
+
+/// `PartyFinderListing::requirements`용 (모든 필드가 비었으면 없는 것과 같음)
+pub fn non_empty<'de, D>(de: D) -> Result<Option<ListingRequirements>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<ListingRequirements>::deserialize(de)?.filter(|requirements| !requirements.is_empty()))
+}
@@ -0,0 +1,181 @@
+//! 모집글 설명에서 예정 시각 추출
+//!
+//! "tonight 21:00", "土曜22時〜", "morgen 20 Uhr", "demain 21h" 같은 표현을 찾아
+//! 표시 시간대 기준의 절대 시각으로 변환합니다.
+//! 날짜 표현이 서로 충돌하거나, 이미 지난 시각이거나, 7일 이후인 경우에는 추출하지 않습니다.
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveTime, TimeZone, Utc, Weekday};
+use regex::Regex;
+
+/// 추출 가능한 가장 먼 미래
+const MAX_DAYS_AHEAD: i64 = 7;
+
+/// 시각 표현의 분(minute) 캡처 방식
+#[derive(Clone, Copy)]
+enum Minutes {
+    /// 두 번째 그룹이 분
+    Group,
+    /// 두 번째 그룹이 "半" (30분)
+    Half,
+}
+
+/// 날짜 표현
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Day {
+    /// 오늘로부터 며칠 뒤
+    Relative(i64),
+    Weekday(Weekday),
+}
+
+lazy_static::lazy_static! {
+    /// 언어별 시각 패턴: (정규식, 분 캡처 방식, 오전/오후 그룹 사용 여부)
+    static ref TIME_PATTERNS: Vec<(Regex, Minutes, bool)> = vec![
+        // English: 21:00, 9:30pm, 9 pm
+        (Regex::new(r"(?i)(?:^|\D)(\d{1,2}):(\d{2})(?:\s*(am|pm)(?:[^a-z]|$))?").unwrap(), Minutes::Group, true),
+        (Regex::new(r"(?i)(?:^|\D)(\d{1,2})()\s*(am|pm)(?:[^a-z]|$)").unwrap(), Minutes::Group, true),
+        // 日本語: 21時半, 22時, 22時30分 (같은 위치면 앞의 패턴 우선)
+        (Regex::new(r"(\d{1,2})時(半)").unwrap(), Minutes::Half, false),
+        (Regex::new(r"(\d{1,2})時(?:(\d{1,2})分)?").unwrap(), Minutes::Group, false),
+        // Deutsch: 20 Uhr, 20.30 Uhr
+        (Regex::new(r"(?i)(?:^|\D)(\d{1,2})(?:[.:](\d{2}))?\s*uhr(?:[^a-z]|$)").unwrap(), Minutes::Group, false),
+        // Français: 21h, 21h30
+        (Regex::new(r"(?i)(?:^|\D)(\d{1,2})h(\d{2})?(?:[^a-z]|$)").unwrap(), Minutes::Group, false),
+    ];
+
+    /// 언어별 날짜 패턴 (프랑스어 "mon"과 겹치는 월요일 약어는 제외)
+    static ref DAY_PATTERNS: Vec<(Regex, Day)> = {
+        let mut patterns = vec![
+            // English
+            (r"(?i)\b(today|tonight)\b", Day::Relative(0)),
+            (r"(?i)\b(tomorrow|tmrw|tmr)\b", Day::Relative(1)),
+            // 日本語
+            (r"明後日|あさって", Day::Relative(2)),
+            (r"今日|今夜|今晩|本日", Day::Relative(0)),
+            (r"明日|あした", Day::Relative(1)),
+            // Deutsch
+            (r"(?i)\bheute\b", Day::Relative(0)),
+            (r"(?i)\bübermorgen\b", Day::Relative(2)),
+            (r"(?i)\bmorgen\b", Day::Relative(1)),
+            // Français ("après-demain"의 "demain"은 제외)
+            (r"(?i)\b(aujourd'hui|ce soir)\b", Day::Relative(0)),
+            (r"(?i)\baprès-demain\b", Day::Relative(2)),
+            (r"(?i)(^|[^-])\bdemain\b", Day::Relative(1)),
+        ];
+
+        let weekdays = [
+            (Weekday::Mon, r"(?i)\b(monday|montag|lundi)\b", r"月曜|[(（]月[)）]"),
+            (Weekday::Tue, r"(?i)\b(tue(s|sday)?|dienstag|mardi)\b", r"火曜|[(（]火[)）]"),
+            (Weekday::Wed, r"(?i)\b(wed(nesday)?|mittwoch|mercredi)\b", r"水曜|[(（]水[)）]"),
+            (Weekday::Thu, r"(?i)\b(thu(rs|rsday)?|donnerstag|jeudi)\b", r"木曜|[(（]木[)）]"),
+            (Weekday::Fri, r"(?i)\b(fri(day)?|freitag|vendredi)\b", r"金曜|[(（]金[)）]"),
+            (Weekday::Sat, r"(?i)\b(sat(urday)?|samstag|samedi)\b", r"土曜|[(（]土[)）]"),
+            (Weekday::Sun, r"(?i)\b(sun(day)?|sonntag|dimanche)\b", r"日曜|[(（]日[)）]"),
+        ];
+        // 日本語는 "土曜" 또는 날짜 뒤의 "(土)"
+        for (weekday, latin, japanese) in weekdays {
+            patterns.push((latin, Day::Weekday(weekday)));
+            patterns.push((japanese, Day::Weekday(weekday)));
+        }
+
+        patterns
+            .into_iter()
+            .map(|(pattern, day)| (Regex::new(pattern).unwrap(), day))
+            .collect()
+    };
+}
+
+/// 전각 숫자/콜론을 반각으로 변환
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '０'..='９' => char::from(b'0' + (c as u32 - '０' as u32) as u8),
+            '：' => ':',
+            _ => c,
+        })
+        .collect()
+}
+
+/// 설명에서 가장 먼저 나오는 시각 (시, 분). 24~29시는 다음 날로 처리합니다.
+fn find_time(text: &str) -> Option<(u32, u32)> {
+    TIME_PATTERNS
+        .iter()
+        .filter_map(|(re, minutes, meridiem)| {
+            let caps = re.captures(text)?;
+            let start = caps.get(1)?.start();
+            let mut hour: u32 = caps.get(1)?.as_str().parse().ok()?;
+            let minute = match minutes {
+                Minutes::Half => 30,
+                Minutes::Group => caps
+                    .get(2)
+                    .filter(|m| !m.as_str().is_empty())
+                    .map(|m| m.as_str().parse().ok())
+                    .unwrap_or(Some(0))?,
+            };
+
+            if *meridiem {
+                if let Some(ampm) = caps.get(3) {
+                    if hour == 0 || hour > 12 {
+                        return None;
+                    }
+                    let pm = ampm.as_str().eq_ignore_ascii_case("pm");
+                    hour = match (pm, hour) {
+                        (false, 12) => 0,
+                        (true, 12) => 12,
+                        (true, h) => h + 12,
+                        (false, h) => h,
+                    };
+                }
+            }
+
+            (hour < 30 && minute < 60).then_some((start, hour, minute))
+        })
+        .min_by_key(|(start, _, _)| *start)
+        .map(|(_, hour, minute)| (hour, minute))
+}
+
+/// 설명의 날짜 표현. 서로 다른 날짜가 섞여 있으면 `Err` (모호함)
+fn find_day(text: &str) -> Result<Option<Day>, ()> {
+    let mut found: Option<Day> = None;
+    for (re, day) in DAY_PATTERNS.iter() {
+        if re.is_match(text) {
+            match found {
+                Some(existing) if existing != *day => return Err(()),
+                _ => found = Some(*day),
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// 설명에서 예정 시각 추출
+///
+/// `reference`는 설명을 확인한 시각이며, 상대 날짜("오늘", "내일", 요일)는
+/// `tz` 기준 `reference`의 날짜를 기준으로 해석합니다.
+pub fn extract_schedule(
+    description: &str,
+    reference: DateTime<Utc>,
+    tz: FixedOffset,
+) -> Option<DateTime<Utc>> {
+    let text = normalize(description);
+    let (hour, minute) = find_time(&text)?;
+    let day = find_day(&text).ok()?;
+
+    let today = reference.with_timezone(&tz).date_naive();
+    let days_ahead = match day {
+        None => 0,
+        Some(Day::Relative(days)) => days,
+        Some(Day::Weekday(weekday)) => {
+            i64::from((7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7)
+        }
+    } + i64::from(hour / 24);
+
+    let date = today + Duration::try_days(days_ahead)?;
+    let time = NaiveTime::from_hms_opt(hour % 24, minute, 0)?;
+    let scheduled = tz
+        .from_local_datetime(&date.and_time(time))
+        .single()?
+        .with_timezone(&Utc);
+
+    let latest = reference + Duration::try_days(MAX_DAYS_AHEAD)?;
+    (scheduled > reference && scheduled <= latest).then_some(scheduled)
+}
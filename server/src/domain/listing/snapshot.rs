@@ -0,0 +1,69 @@
+//! 전체 스냅샷 업로드 (`/contribute/multiple`의 `snapshot: true`)
+//!
+//! 플러그인의 일괄 업로드는 업로더가 지금 보고 있는 모집글 전체입니다. 그래서 이 업로더가 전에 올렸던
+//! 모집글 중 스냅샷 범위(생성 월드)에 있는데 이번 스냅샷에 없는 것은 게임에서 내려간 것으로 봅니다.
+//! 바로 지우지 않고 `unconfirmed_at`만 기록하며, 다른 업로더가 다시 올리면 기록이 지워집니다.
+//! 다른 업로더의 모집글을 내리지 못하도록 업로드 토큰으로 인증한 업로드만 스냅샷으로 다룹니다.
+//! 확인되지 않은 모집글은 1시간 대신 `unconfirmed_minutes`가 지나면 기본 목록에서 빠집니다.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use mongodb::bson::{doc, Document};
+
+use crate::listing::PartyFinderListing;
+
+/// 스냅샷 한 번의 범위 (업로더, 생성 월드, 스냅샷에 있던 모집글)
+#[derive(Debug, Clone)]
+pub struct SnapshotScope {
+    fingerprint: String,
+    worlds: Vec<u16>,
+    /// (id, created_world, last_server_restart): `insert_listing`의 저장 키
+    keys: HashSet<(u32, u16, u32)>,
+}
+
+impl SnapshotScope {
+    pub fn new<'a>(
+        fingerprint: &str,
+        worlds: &[u16],
+        listings: impl IntoIterator<Item = &'a PartyFinderListing>,
+    ) -> Self {
+        Self {
+            fingerprint: fingerprint.to_string(),
+            worlds: worlds.to_vec(),
+            keys: listings
+                .into_iter()
+                .map(|listing| (listing.id, listing.created_world, listing.last_server_restart))
+                .collect(),
+        }
+    }
+
+    /// `mark_unconfirmed`의 조회 조건
+    ///
+    /// 이미 기록된 모집글은 처음 빠진 시각을 유지하도록 다시 고르지 않습니다.
+    pub fn absent_filter(&self, updated_since: DateTime<Utc>) -> Document {
+        let worlds: Vec<u32> = self.worlds.iter().map(|&world| u32::from(world)).collect();
+        let mut filter = doc! {
+            "updated_at": { "$gte": updated_since },
+            "unconfirmed_at": null,
+            "uploader_fingerprints": &self.fingerprint,
+            "listing.created_world": { "$in": worlds },
+        };
+        // 빈 `$nor`는 오류이므로 스냅샷이 비었으면 범위 안의 모든 모집글이 대상
+        if !self.keys.is_empty() {
+            let present: Vec<Document> = self
+                .keys
+                .iter()
+                .map(|&(id, created_world, last_server_restart)| {
+                    doc! {
+                        "listing.id": id,
+                        "listing.created_world": u32::from(created_world),
+                        "listing.last_server_restart": last_server_restart,
+                    }
+                })
+                .collect();
+            filter.insert("$nor", present);
+        }
+        filter
+    }
+}
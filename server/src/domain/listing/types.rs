@@ -1,16 +1,19 @@
 use std::borrow::Cow;
 
 use bitflags::bitflags;
+use chrono::{DateTime, Utc};
 use ffxiv_types::jobs::{Class, ClassJob, Job};
 use ffxiv_types::{Role, World};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use sestring::SeString;
 
+use crate::ffxiv::duties::{ContentKind, DutyInfo};
 use crate::ffxiv::jobs::JOBS_TO_FLAGS;
 use crate::ffxiv::{Language, LocalisedText, JOBS};
+use crate::listing::requirements::{self, ListingRequirements};
 
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct PartyFinderListing {
     pub id: u32,
     pub content_id_lower: u32,
@@ -39,15 +42,119 @@ pub struct PartyFinderListing {
     pub jobs_present: Vec<u8>,
     #[serde(default)]
     pub member_content_ids: Vec<i64>,
+    /// 자리별 멤버 (디테일에서 업데이트, 자리 순서로 정렬, 이 필드 전에 저장된 문서와 이전 플러그인은 비어 있음)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<PartyMember>,
     /// 파티장의 전체 Content ID (디테일에서 업데이트)
     #[serde(default)]
     pub leader_content_id: u64,
+    /// 플러그인이 모집글을 읽은 시각 (늦게 도착한 이전 스냅샷이 최신 데이터를 덮어쓰지 않게 함)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_at: Option<DateTime<Utc>>,
+    /// 새 플러그인이 보내는 구조화된 참가 조건 (없으면 표시하지 않음)
+    #[serde(default, deserialize_with = "requirements::non_empty", skip_serializing_if = "Option::is_none")]
+    pub requirements: Option<ListingRequirements>,
+}
+
+/// 모집글 자리를 차지한 멤버 (플러그인이 보내는 자리 번호 그대로)
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PartyMember {
+    pub content_id: u64,
+    pub job_id: u8,
+    /// `jobs_present`의 위치 (파티 순서대로 이어진 번호)
+    pub slot_index: u8,
 }
 
 #[allow(unused)]
 impl PartyFinderListing {
-    pub fn slots_filled(&self) -> usize {
-        self.jobs_present.iter().filter(|&&job| job > 0).count()
+    /// 저장 키 (`id/created_world/last_server_restart`)
+    pub fn key(&self) -> String {
+        format!("{}/{}/{}", self.id, self.created_world, self.last_server_restart)
+    }
+
+    /// 파티 수 (0으로 올라온 경우 1로 취급)
+    pub fn party_count(&self) -> usize {
+        usize::from(self.num_parties.max(1))
+    }
+
+    /// 전체 자리 수 (`slots_available`은 파티 하나의 크기)
+    pub fn total_capacity(&self) -> usize {
+        usize::from(self.slots_available) * self.party_count()
+    }
+
+    /// 모든 파티에서 채워진 자리 수
+    pub fn filled_total(&self) -> usize {
+        self.jobs_present
+            .iter()
+            .take(self.total_capacity())
+            .filter(|&&job| job > 0)
+            .count()
+    }
+
+    /// 멤버로 표시하는 자리의 (Content ID, 잡 ID), 자리 순서
+    ///
+    /// 자리별 멤버(`members`)가 있으면 그 자리의 잡이 멤버의 잡과 같은 경우만 셉니다
+    /// (디테일 이후 자리가 비었거나 다른 사람으로 바뀐 경우 제외).
+    /// 없으면 `member_content_ids`를 `jobs_present`와 같은 위치끼리 짝짓고, 잡 정보가 없는 멤버는
+    /// 리스팅 정보(`jobs_present`)를 신뢰해 뺍니다.
+    /// 어느 쪽이든 `jobs_present`에 채워진 자리만 나오므로 `slots_filled`와 어긋나지 않습니다.
+    pub fn member_slots(&self) -> impl Iterator<Item = (u64, u8)> + '_ {
+        let explicit = !self.members.is_empty();
+        let capacity = self.total_capacity();
+        let by_slot = self.members
+            .iter()
+            .filter(move |member| {
+                let slot = usize::from(member.slot_index);
+                member.content_id != 0
+                    && member.job_id != 0
+                    && slot < capacity
+                    && self.jobs_present.get(slot) == Some(&member.job_id)
+            })
+            .map(|member| (member.content_id, member.job_id));
+        let positional = self.member_content_ids
+            .iter()
+            .enumerate()
+            .filter(move |(_, id)| !explicit && **id != 0)
+            .map(|(i, &id)| (id as u64, self.jobs_present.get(i).copied().unwrap_or(0)))
+            .filter(|&(_, job_id)| job_id != 0);
+        by_slot.chain(positional)
+    }
+
+    /// 모든 파티에서 비어 있는 자리 수
+    pub fn open_slots(&self) -> usize {
+        self.total_capacity() - self.filled_total()
+    }
+
+    /// 역할별 빈 자리 수 (여러 역할을 받는 자리는 각 역할에 하나씩 셈)
+    pub fn open_role_slots(&self) -> RoleSlots {
+        let mut open = RoleSlots::default();
+        for (i, slot) in self.slots.iter().enumerate().take(self.total_capacity()) {
+            if self.jobs_present.get(i).is_some_and(|&job| job > 0) {
+                continue;
+            }
+
+            open.tank += u32::from(slot.accepting.accepts_role(Role::Tank));
+            open.healer += u32::from(slot.accepting.accepts_role(Role::Healer));
+            open.dps += u32::from(slot.accepting.accepts_role(Role::Dps));
+        }
+        open
+    }
+
+    /// 파티별 참가 현황 (`jobs_present`는 파티 순서대로 이어져 있음)
+    pub fn parties(&self) -> Vec<PartyFill> {
+        let size = usize::from(self.slots_available);
+        (0..self.party_count())
+            .map(|party| PartyFill {
+                filled: self
+                    .jobs_present
+                    .iter()
+                    .skip(party * size)
+                    .take(size)
+                    .filter(|&&job| job > 0)
+                    .count(),
+                capacity: size,
+            })
+            .collect()
     }
 
     pub fn is_cross_world(&self) -> bool {
@@ -58,19 +165,26 @@ impl PartyFinderListing {
         crate::ffxiv::duty_name(self.duty_type, self.category, self.duty, *lang)
     }
 
+    /// 듀티 없이 카테고리로 표시되는 모집글의 카테고리 이름
+    pub fn category_label(&self, lang: &Language) -> Option<&'static str> {
+        crate::ffxiv::category_label(self.category, self.duty).map(|label| label.text(lang))
+    }
+
+    /// 모든 파티의 자리 (파티 순서대로)
     pub fn slots(&self) -> Vec<std::result::Result<ClassJob, (String, String)>> {
-        let mut slots = Vec::with_capacity(self.slots_available as usize);
-        for i in 0..self.slots_available as usize {
+        let mut slots = Vec::with_capacity(self.total_capacity());
+        for i in 0..self.total_capacity() {
             if i >= self.jobs_present.len() {
                 break;
             }
 
-            let cj = match crate::ffxiv::JOBS
-                .get(&u32::from(self.jobs_present[i]))
-                .copied()
-            {
+            let cj = match crate::ffxiv::job_or_record(u32::from(self.jobs_present[i]), || self.key()) {
                 Some(cj) => Ok(cj),
-                None => Err((self.slots[i].html_classes(), self.slots[i].codes())),
+                None => Err(self
+                    .slots
+                    .get(i)
+                    .map(|slot| (slot.html_classes(), slot.codes()))
+                    .unwrap_or_default()),
             };
             slots.push(cj);
         }
@@ -78,6 +192,29 @@ impl PartyFinderListing {
         slots
     }
 
+    /// `ONE_PLAYER_PER_JOB` 모집글에서 같은 잡이 두 번 이상 나오면 (오래된 데이터) 첫 번째만 남기고 비움
+    ///
+    /// 빈 자리(0)는 무시합니다. 비운 자리가 있으면 `true`.
+    pub fn clear_duplicate_jobs(&mut self) -> bool {
+        if !self.search_area.contains(SearchAreaFlags::ONE_PLAYER_PER_JOB) {
+            return false;
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        let mut cleared = false;
+        for job in self.jobs_present.iter_mut().filter(|job| **job != 0) {
+            if !seen.insert(*job) {
+                *job = 0;
+                cleared = true;
+            }
+        }
+
+        if cleared {
+            tracing::debug!("listing {} has duplicate jobs despite one player per job", self.key());
+        }
+        cleared
+    }
+
     pub fn joinable_roles(&self) -> u32 {
         let one_player_per_job = self
             .search_area
@@ -85,7 +222,7 @@ impl PartyFinderListing {
         let mut jobs = JobFlags::empty();
         let mut jobs_taken = JobFlags::empty();
         for (i, present_job) in self.jobs_present.iter().copied().enumerate() {
-            if i >= self.slots_available as usize {
+            if i >= self.total_capacity() {
                 break;
             }
 
@@ -111,9 +248,7 @@ impl PartyFinderListing {
     }
 
     pub fn created_world(&self) -> Option<World> {
-        crate::ffxiv::WORLDS
-            .get(&u32::from(self.created_world))
-            .copied()
+        crate::ffxiv::world_or_record(u32::from(self.created_world), || self.key())
     }
 
     pub fn created_world_string(&self) -> Cow<str> {
@@ -123,9 +258,7 @@ impl PartyFinderListing {
     }
 
     pub fn home_world(&self) -> Option<World> {
-        crate::ffxiv::WORLDS
-            .get(&u32::from(self.home_world))
-            .copied()
+        crate::ffxiv::world_or_record(u32::from(self.home_world), || self.key())
     }
 
     pub fn home_world_string(&self) -> Cow<str> {
@@ -175,13 +308,23 @@ impl PartyFinderListing {
             flags.push("[<span data-i18n='one_player_per_job'>One Player per Job</span>]");
         }
 
+        if self.duty_finder_settings.contains(DutyFinderSettingsFlags::UNDERSIZED_PARTY) {
+            flags.push("[<span data-i18n='undersized_party'>Undersized Party</span>]");
+        }
+
+        if self.duty_finder_settings.contains(DutyFinderSettingsFlags::MINIMUM_ITEM_LEVEL) {
+            flags.push("[<span data-i18n='minimum_item_level'>Minimum IL</span>]");
+        }
+
+        if self.duty_finder_settings.contains(DutyFinderSettingsFlags::SILENCE_ECHO) {
+            flags.push("[<span data-i18n='silence_echo'>Silence Echo</span>]");
+        }
+
         (colour_class, flags.join(""))
     }
 
     pub fn data_centre_name(&self) -> Option<&'static str> {
-        crate::ffxiv::WORLDS
-            .get(&u32::from(self.created_world))
-            .map(|w| w.data_center().name())
+        self.created_world().map(|w| w.data_center().name())
     }
 
     pub fn high_end(&self) -> bool {
@@ -189,23 +332,56 @@ impl PartyFinderListing {
             return false;
         }
 
-        crate::ffxiv::duty(u32::from(self.duty))
+        crate::ffxiv::duty_or_record(u32::from(self.duty), || self.key())
             .map(|info| info.high_end)
             .unwrap_or_default()
     }
 
+    /// Parse를 조회하고 표시하는 FFLogs encounter (조회 대상 종류이고 매핑된 듀티만)
+    ///
+    /// 백그라운드 조회와 목록 / API 표시가 같은 기준을 쓰도록 여기서만 판단합니다.
+    pub fn fflogs_encounter(&self) -> Option<&'static crate::fflogs::FFLogsEncounter> {
+        if self.duty_type != DutyType::Normal {
+            return None;
+        }
+
+        crate::ffxiv::duty_or_record(u32::from(self.duty), || self.key())
+            .and_then(|info| crate::fflogs::mapping::fflogs_encounter_for(self.duty, info))
+    }
+
+    /// FFLogs Parse를 조회 / 표시하는 모집글인지
+    pub fn fflogs_supported(&self) -> bool {
+        self.fflogs_encounter().is_some()
+    }
+
     pub fn content_kind(&self) -> u32 {
         if self.duty_type != DutyType::Normal {
             return 0;
         }
 
-        crate::ffxiv::duty(u32::from(self.duty))
+        crate::ffxiv::duty_or_record(u32::from(self.duty), || self.key())
             .map(|info| info.content_kind.as_u32())
             .unwrap_or_default()
     }
 
+    /// 정렬 / 필터 / 통계에 쓰는 분류 (업로드된 `category`는 바꾸지 않음)
+    ///
+    /// 고난이도 듀티가 다른 분류(None, Other 등)로 올라오면 듀티 표의 분류를 씁니다.
+    pub fn canonical_category(&self) -> DutyCategory {
+        if self.duty_type != DutyType::Normal {
+            return self.category;
+        }
+
+        self.category.canonical(crate::ffxiv::duty_or_record(u32::from(self.duty), || self.key()))
+    }
+
+    /// 업로드된 분류가 듀티 표의 분류와 다름
+    pub fn miscategorized(&self) -> bool {
+        self.canonical_category() != self.category
+    }
+
     pub fn pf_category(&self) -> PartyFinderCategory {
-        self.category.pf_category()
+        self.canonical_category().pf_category()
     }
 
     pub fn html_pf_category(&self) -> &'static str {
@@ -213,7 +389,30 @@ impl PartyFinderListing {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+/// 파티 하나의 참가 현황
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct PartyFill {
+    pub filled: usize,
+    pub capacity: usize,
+}
+
+/// 역할별 자리 수
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub struct RoleSlots {
+    pub tank: u32,
+    pub healer: u32,
+    pub dps: u32,
+}
+
+impl std::ops::AddAssign for RoleSlots {
+    fn add_assign(&mut self, other: Self) {
+        self.tank += other.tank;
+        self.healer += other.healer;
+        self.dps += other.dps;
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct PartyFinderSlot {
     pub accepting: JobFlags,
 }
@@ -256,24 +455,54 @@ pub enum DutyCategory {
 
 #[allow(unused)]
 impl DutyCategory {
+    pub const ALL: [Self; 16] = [
+        Self::None,
+        Self::DutyRoulette,
+        Self::Dungeon,
+        Self::Guildhest,
+        Self::Trial,
+        Self::Raid,
+        Self::HighEndDuty,
+        Self::PvP,
+        Self::GoldSaucer,
+        Self::Fate,
+        Self::TreasureHunt,
+        Self::TheHunt,
+        Self::GatheringForay,
+        Self::DeepDungeon,
+        Self::FieldOperation,
+        Self::VariantAndCriterionDungeon,
+    ];
+
+    /// 듀티 표 기준 분류 (`duty`가 고난이도 듀티일 때만 정하고, 그 밖에는 그대로)
+    pub fn canonical(self, duty: Option<&DutyInfo>) -> Self {
+        match duty {
+            Some(info) if info.high_end => match info.content_kind {
+                ContentKind::VCDungeonFinder => Self::VariantAndCriterionDungeon,
+                _ => Self::HighEndDuty,
+            },
+            _ => self,
+        }
+    }
+
     pub fn from_u32(u: u32) -> Option<Self> {
         Some(match u {
             0 => Self::None,
-            1 => Self::DutyRoulette,
-            2 => Self::Dungeon,
-            4 => Self::Guildhest,
-            8 => Self::Trial,
-            16 => Self::Raid,
-            32 => Self::HighEndDuty,
-            64 => Self::PvP,
-            128 => Self::GoldSaucer,
-            256 => Self::Fate,
-            512 => Self::TreasureHunt,
-            1024 => Self::TheHunt,
-            2048 => Self::GatheringForay,
-            4096 => Self::DeepDungeon,
-            8192 => Self::FieldOperation,
-            16384 => Self::VariantAndCriterionDungeon,
+            2 => Self::DutyRoulette,
+            4 => Self::Dungeon,
+            8 => Self::Guildhest,
+            16 => Self::Trial,
+            32 => Self::Raid,
+            64 => Self::HighEndDuty,
+            128 => Self::PvP,
+            256 => Self::GoldSaucer,
+            512 => Self::Fate,
+            1024 => Self::TreasureHunt,
+            2048 => Self::TheHunt,
+            4096 => Self::GatheringForay,
+            8192 => Self::DeepDungeon,
+            16384 => Self::FieldOperation,
+            32768 => Self::VariantAndCriterionDungeon,
             _ => return None,
         })
     }
@@ -300,6 +529,28 @@ impl DutyCategory {
             }
         }
     }
+
+    /// API 식별자 (snake_case, 이름이 바뀌어도 유지)
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::DutyRoulette => "duty_roulette",
+            Self::Dungeon => "dungeon",
+            Self::Guildhest => "guildhest",
+            Self::Trial => "trial",
+            Self::Raid => "raid",
+            Self::HighEndDuty => "high_end_duty",
+            Self::PvP => "pvp",
+            Self::GoldSaucer => "gold_saucer",
+            Self::Fate => "fate",
+            Self::TreasureHunt => "treasure_hunt",
+            Self::TheHunt => "the_hunt",
+            Self::GatheringForay => "gathering_foray",
+            Self::DeepDungeon => "deep_dungeon",
+            Self::FieldOperation => "field_operation",
+            Self::VariantAndCriterionDungeon => "variant_and_criterion_dungeon",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Deserialize_repr, Serialize_repr, PartialEq)]
@@ -324,6 +575,38 @@ impl DutyType {
             _ => return None,
         })
     }
+
+    /// API 식별자 (snake_case)
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Other => "other",
+            Self::Roulette => "roulette",
+            Self::Normal => "normal",
+        }
+    }
+
+    pub fn name(self) -> LocalisedText {
+        match self {
+            Self::Other => LocalisedText {
+                en: "Other",
+                ja: "その他",
+                de: "Sonstiges",
+                fr: "Autre",
+            },
+            Self::Roulette => LocalisedText {
+                en: "Duty Roulette",
+                ja: "コンテンツルーレット",
+                de: "Zufallsinhalte",
+                fr: "Missions aléatoires",
+            },
+            Self::Normal => LocalisedText {
+                en: "Duty",
+                ja: "コンテンツ",
+                de: "Inhalt",
+                fr: "Mission",
+            },
+        }
+    }
 }
 
 bitflags! {
@@ -349,6 +632,10 @@ bitflags! {
 }
 
 bitflags! {
+    /// 플러그인(Dalamud `DutyFinderSettingsFlags`)이 보내는 듀티 찾기 설정
+    ///
+    /// 플러그인 열거형에는 아래 세 비트만 있습니다. 원시 정수를 그대로 (역)직렬화하므로
+    /// 이름이 없는 새 비트도 저장과 재전송에서 버리지 않습니다 (`unknown_bits`).
     #[derive(Deserialize, Serialize)]
     #[serde(transparent)]
     pub struct DutyFinderSettingsFlags : u32 {
@@ -359,6 +646,13 @@ bitflags! {
     }
 }
 
+impl DutyFinderSettingsFlags {
+    /// 이름이 정해지지 않은 비트
+    pub fn unknown_bits(self) -> u32 {
+        self.bits() & !Self::all().bits()
+    }
+}
+
 bitflags! {
     #[derive(Deserialize, Serialize)]
     #[serde(transparent)]
@@ -420,6 +714,11 @@ bitflags! {
 }
 
 impl JobFlags {
+    /// 해당 역할의 잡을 하나라도 받는지
+    pub fn accepts_role(&self, role: Role) -> bool {
+        self.classjobs().iter().any(|cj| cj.role() == Some(role))
+    }
+
     pub fn classjobs(&self) -> Vec<ClassJob> {
         let mut cjs = Vec::new();
 
@@ -687,6 +986,11 @@ impl PartyFinderCategory {
         Self::None,
     ];
 
+    /// 정렬 순위 (`ALL`에서의 위치, 목록에서는 큰 값이 먼저 표시됨)
+    pub fn display_rank(self) -> u8 {
+        Self::ALL.iter().position(|&category| category == self).unwrap_or_default() as u8
+    }
+
     pub fn as_str(self) -> &'static str {
         match self {
             Self::DutyRoulette => "DutyRoulette",
@@ -772,7 +1076,7 @@ impl PartyFinderCategory {
             },
             Self::TheHunt => LocalisedText {
                 en: "The Hunt",
-                ja: "モブハント ",
+                ja: "モブハント",
                 de: "Hohe Jagd",
                 fr: "Contrats de chasse",
             },
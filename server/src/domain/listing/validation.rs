@@ -0,0 +1,118 @@
+//! 업로드된 모집글 검증
+//!
+//! 게임 데이터에 없는 듀티 / 잡, 파티 크기와 맞지 않는 자리, 지나치게 긴 설명처럼
+//! 게임에서 나올 수 없는 모집글은 저장하지 않습니다.
+//! 게임 데이터에 없는 ID는 미확인 ID로도 기록하므로 패치 직후에는 `/api/admin/unknown_ids`에서 보입니다.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::listing::{DutyType, PartyFinderListing};
+
+/// 남은 시간 최대값 (모집글은 최대 1시간)
+pub const MAX_SECONDS_REMAINING: u16 = 60 * 60;
+
+/// 파티 하나의 자리 수
+pub const PARTY_SIZE: usize = 8;
+
+/// 최대 파티 수 (연합 / 48인 모집)
+pub const MAX_PARTIES: u8 = 6;
+
+/// 월드 ID 상한 (테스트 / 클라우드 월드 ID는 이보다 큼)
+pub const MAX_WORLD_ID: u16 = 1_000;
+
+/// 설명 최대 길이 (인코딩한 SeString 바이트, 게임 입력 제한 192자 + 자동 번역 페이로드 여유)
+pub const MAX_DESCRIPTION_BYTES: usize = 2_048;
+
+/// 검증에 실패한 항목
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListingProblem {
+    /// 남은 시간이 1시간보다 김
+    SecondsRemaining,
+    /// 게임 데이터에 없는 듀티 / 룰렛
+    UnknownDuty,
+    /// 파티 수가 `MAX_PARTIES`보다 많음 (0은 1로 취급)
+    PartyCount,
+    /// 자리 수가 파티 크기보다 많거나 `slots`가 전체 자리보다 많음
+    Slots,
+    /// `jobs_present`가 전체 자리보다 많음
+    JobsPresent,
+    /// 게임 데이터에 없는 잡
+    UnknownJob,
+    /// `MAX_WORLD_ID` 이상의 월드
+    World,
+    /// 설명이 `MAX_DESCRIPTION_BYTES`보다 김
+    DescriptionLength,
+}
+
+/// 검증에 실패한 모집글과 실패한 항목
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InvalidListing {
+    pub id: u32,
+    pub problems: Vec<ListingProblem>,
+}
+
+impl fmt::Display for InvalidListing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid listing {}: {:?}", self.id, self.problems)
+    }
+}
+
+impl std::error::Error for InvalidListing {}
+
+impl PartyFinderListing {
+    /// 저장 전 검증 (실패한 항목 모두 반환)
+    pub fn validate(&self) -> Result<(), InvalidListing> {
+        let mut problems = Vec::new();
+
+        if self.seconds_remaining > MAX_SECONDS_REMAINING {
+            problems.push(ListingProblem::SecondsRemaining);
+        }
+        if !self.duty_is_known() {
+            problems.push(ListingProblem::UnknownDuty);
+        }
+        if self.num_parties > MAX_PARTIES {
+            problems.push(ListingProblem::PartyCount);
+        }
+        if usize::from(self.slots_available) > PARTY_SIZE || self.slots.len() > self.total_capacity() {
+            problems.push(ListingProblem::Slots);
+        }
+        if self.jobs_present.len() > PARTY_SIZE * self.party_count() {
+            problems.push(ListingProblem::JobsPresent);
+        }
+        let unknown_job = self
+            .jobs_present
+            .iter()
+            .any(|&job| job != 0 && crate::ffxiv::job_or_record(u32::from(job), || self.key()).is_none());
+        if unknown_job {
+            problems.push(ListingProblem::UnknownJob);
+        }
+        if [self.created_world, self.home_world, self.current_world].iter().any(|&world| world >= MAX_WORLD_ID) {
+            problems.push(ListingProblem::World);
+        }
+        if self.description.encode().len() > MAX_DESCRIPTION_BYTES {
+            problems.push(ListingProblem::DescriptionLength);
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidListing { id: self.id, problems })
+        }
+    }
+
+    /// 일반 듀티 / 룰렛 ID가 게임 데이터에 있는지 (카테고리 이름으로 표시되는 모집글은 확인하지 않음)
+    fn duty_is_known(&self) -> bool {
+        if self.duty == 0 || crate::ffxiv::category_label(self.category, self.duty).is_some() {
+            return true;
+        }
+
+        match self.duty_type {
+            DutyType::Normal => crate::ffxiv::duty_or_record(u32::from(self.duty), || self.key()).is_some(),
+            DutyType::Roulette => crate::ffxiv::roulette(u32::from(self.duty)).is_some(),
+            DutyType::Other => true,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! 같은 캐릭터를 가리키는 중복 플레이어 문서 찾기
+//!
+//! 예전 버그로 이름이 정리되지 않거나 ContentID 하위 32비트만 저장된 문서가 남아 있습니다.
+//! 정리한 이름과 홈 서버가 같고, 한쪽 ContentID가 다른 쪽의 하위 32비트인 경우만 중복으로 봅니다.
+//! 후보가 둘 이상이면 어느 쪽인지 알 수 없으므로 병합하지 않습니다.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::Player;
+
+/// 하위 32비트만 남은 ContentID인지
+pub fn is_truncated_content_id(content_id: u64) -> bool {
+    content_id != 0 && content_id <= u64::from(u32::MAX)
+}
+
+/// ContentID의 하위 32비트
+pub fn lower_content_id(content_id: u64) -> u64 {
+    content_id & u64::from(u32::MAX)
+}
+
+/// 비교용 이름 (앞뒤 공백 제거, 연속 공백은 하나로, 소문자)
+pub fn normalized_name(name: &str) -> String {
+    name.split_whitespace()
+        .intersperse(" ")
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// 중복 문서를 대상 문서로 합치는 병합 한 건
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlayerMerge {
+    /// 남길 문서 (전체 ContentID)
+    pub target: u64,
+    /// 합친 뒤 지울 문서 (하위 32비트 ContentID)
+    pub duplicate: u64,
+    pub name: String,
+    pub home_world: u16,
+}
+
+/// 후보가 여러 개라 병합하지 않은 문서
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedDuplicate {
+    pub content_id: u64,
+    pub name: String,
+    pub home_world: u16,
+    /// 하위 32비트가 같은 전체 ContentID (오름차순)
+    pub candidates: Vec<u64>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MergePlan {
+    /// 대상 ContentID 오름차순
+    pub merges: Vec<PlayerMerge>,
+    /// ContentID 오름차순
+    pub skipped: Vec<SkippedDuplicate>,
+}
+
+/// 플레이어 문서에서 병합할 중복 찾기
+pub fn plan_merges<'a>(players: impl IntoIterator<Item = &'a Player>) -> MergePlan {
+    // (정리한 이름, 홈 서버, 하위 32비트) → 전체 ContentID
+    let mut full_ids: HashMap<(String, u16, u64), Vec<u64>> = HashMap::new();
+    let mut truncated = Vec::new();
+
+    for player in players {
+        if is_truncated_content_id(player.content_id) {
+            truncated.push(player);
+        } else {
+            full_ids
+                .entry((normalized_name(&player.name), player.home_world, lower_content_id(player.content_id)))
+                .or_default()
+                .push(player.content_id);
+        }
+    }
+
+    let mut plan = MergePlan::default();
+    for player in truncated {
+        let key = (normalized_name(&player.name), player.home_world, player.content_id);
+        let mut candidates = full_ids.get(&key).cloned().unwrap_or_default();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        match candidates[..] {
+            [] => {}
+            [target] => plan.merges.push(PlayerMerge {
+                target,
+                duplicate: player.content_id,
+                name: player.name.clone(),
+                home_world: player.home_world,
+            }),
+            _ => {
+                tracing::warn!(
+                    "player {} ({}, world {}) matches several content ids {:?}, not merging",
+                    player.content_id,
+                    player.name,
+                    player.home_world,
+                    candidates,
+                );
+                plan.skipped.push(SkippedDuplicate {
+                    content_id: player.content_id,
+                    name: player.name.clone(),
+                    home_world: player.home_world,
+                    candidates,
+                });
+            }
+        }
+    }
+
+    plan.merges.sort_by_key(|merge| merge.target);
+    plan.skipped.sort_by_key(|skipped| skipped.content_id);
+    plan
+}
+
+/// 다시 읽은 두 문서가 여전히 병합 조건을 만족하는지 (계획 이후 바뀐 경우 건너뜀)
+pub fn still_duplicates(target: &Player, duplicate: &Player) -> bool {
+    is_truncated_content_id(duplicate.content_id)
+        && !is_truncated_content_id(target.content_id)
+        && lower_content_id(target.content_id) == duplicate.content_id
+        && target.home_world == duplicate.home_world
+        && normalized_name(&target.name) == normalized_name(&duplicate.name)
+}
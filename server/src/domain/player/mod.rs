@@ -2,6 +2,7 @@
 //!
 //! 플레이어 관련 타입
 
+pub mod compaction;
 mod player;
 
 pub use player::*;
@@ -1,7 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use ffxiv_types::World;
 use std::borrow::Cow;
 
+use crate::listing::PartyFinderListing;
+
 /// 플레이어 정보 (크라우드소싱으로 수집)
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Player {
@@ -46,4 +49,25 @@ impl Player {
             .map(|w| Cow::Borrowed(w.as_str()))
             .unwrap_or_else(|| Cow::Owned(format!("Unknown ({})", self.home_world)))
     }
+
+    /// 홈 서버 (아직 모르는 경우의 0 또는 알 수 없는 ID면 `None`)
+    pub fn home_world(&self) -> Option<World> {
+        if self.home_world == 0 {
+            return None;
+        }
+        crate::ffxiv::world_or_record(u32::from(self.home_world), || format!("player {}", self.content_id))
+    }
+
+    /// 모집글 생성 서버와 다른 서버에서 참가한 멤버
+    pub fn is_cross_world(&self, listing: &PartyFinderListing) -> bool {
+        self.home_world().is_some() && self.home_world != listing.created_world
+    }
+
+    /// 모집글 생성 서버와 다른 데이터 센터에서 참가한 멤버
+    pub fn is_cross_dc(&self, listing: &PartyFinderListing) -> bool {
+        match (self.home_world(), listing.created_world()) {
+            (Some(home), Some(created)) => home.data_center() != created.data_center(),
+            _ => false,
+        }
+    }
 }
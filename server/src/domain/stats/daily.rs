@@ -0,0 +1,89 @@
+//! 일별 모집글 수 추이
+//!
+//! 모집글은 마지막 업데이트 `mongo.listings_ttl_hours`(기본 2시간) 뒤 TTL로 지워지므로 통계 집계만으로는 며칠 전 모집글 수를 알 수 없습니다.
+//! 롤업 태스크가 주기적으로 마지막 롤업 이후 만들어진 모집글을 날짜(UTC)별로 세어 `daily_listings`에 더하고,
+//! 통계는 이 기록을 읽어 최근 30일 / 90일 추이를 만듭니다.
+
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use mongodb::bson::{doc, Document};
+use serde::{Deserialize, Serialize};
+
+/// 롤업 간격 (모집글 TTL보다 짧아야 지워지기 전에 셈)
+pub const ROLLUP_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// 모집글 TTL 기본값 (`updated_at` 기준, 만들어진 모집글은 적어도 이만큼 남음)
+pub const LISTING_TTL: TimeDelta = TimeDelta::hours(2);
+
+/// 처리 중인 업로드가 빠지지 않도록 최근 몇 분은 다음 롤업으로 미룸
+pub const ROLLUP_LAG: TimeDelta = TimeDelta::minutes(5);
+
+/// 전체 기간 통계의 추이 일수
+pub const ALL_TIME_DAYS: u32 = 90;
+
+/// 최근 7일 통계의 추이 일수
+pub const SEVEN_DAYS_DAYS: u32 = 30;
+
+/// 하루의 모집글 수 (`daily_listings` 문서)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DailyCount {
+    /// UTC 날짜 (`%Y-%m-%d`)
+    #[serde(rename(deserialize = "_id"))]
+    pub day: String,
+    pub count: u64,
+}
+
+/// 이번 롤업이 셀 `created_at` 범위 (`after` 초과, `until` 이하)
+///
+/// 처음이거나 마지막 롤업이 TTL보다 오래됐으면 아직 남은 모집글부터 셉니다.
+pub fn rollup_window(watermark: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let oldest = now - LISTING_TTL;
+    let after = watermark.map_or(oldest, |watermark| watermark.max(oldest));
+    let until = now - ROLLUP_LAG;
+    (after < until).then_some((after, until))
+}
+
+/// 범위 안에서 만들어진 모집글을 날짜별로 세는 파이프라인 (통계와 같이 비공개 모집글 제외)
+pub fn rollup_pipeline(after: DateTime<Utc>, until: DateTime<Utc>) -> Vec<Document> {
+    vec![
+        doc! {
+            "$match": {
+                "created_at": { "$gt": after, "$lte": until },
+                "listing.search_area": { "$bitsAllClear": 2 },
+            }
+        },
+        doc! {
+            "$group": {
+                "_id": {
+                    "$dateToString": {
+                        "format": "%Y-%m-%d",
+                        "date": "$created_at",
+                    },
+                },
+                "count": { "$sum": 1 },
+            }
+        },
+        doc! {
+            "$sort": { "_id": 1 }
+        },
+    ]
+}
+
+/// `today`까지 `days`일의 첫날 (`%Y-%m-%d`)
+pub fn first_day(today: NaiveDate, days: u32) -> String {
+    (today - TimeDelta::days(i64::from(days.max(1)) - 1)).format("%Y-%m-%d").to_string()
+}
+
+/// 기록이 없는 날을 0으로 채운 `today`까지 `days`일의 추이 (오래된 순)
+pub fn fill_days(counts: &[DailyCount], today: NaiveDate, days: u32) -> Vec<DailyCount> {
+    let days = days.max(1);
+    (0..days)
+        .rev()
+        .map(|ago| {
+            let day = (today - TimeDelta::days(i64::from(ago))).format("%Y-%m-%d").to_string();
+            let count = counts.iter().find(|count| count.day == day).map_or(0, |count| count.count);
+            DailyCount { day, count }
+        })
+        .collect()
+}
@@ -2,6 +2,9 @@
 //!
 //! 통계 관련 타입 및 로직
 
+pub mod daily;
+pub mod role_demand;
 mod stats;
+pub mod summary;
 
 pub use stats::*;
@@ -0,0 +1,125 @@
+//! 역할별 빈 자리 추이 ("탱커 자리가 가장 많은 시간")
+//!
+//! 일정 간격으로 데이터 센터 / 듀티 분류별 빈 자리 수를 기록하고,
+//! 조회 시 요일 × 시간 칸마다 평균을 냅니다.
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, FixedOffset, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::listing::{DutyCategory, PartyFinderListing, RoleSlots};
+
+/// 기록 간격
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 한 시점의 데이터 센터별 빈 자리 (빈 자리가 없던 데이터 센터도 기록해 평균이 부풀지 않게 함)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RoleDemandSample {
+    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub sampled_at: DateTime<Utc>,
+    pub data_centre: String,
+    pub categories: Vec<CategoryDemand>,
+}
+
+/// 듀티 분류별 빈 자리
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CategoryDemand {
+    /// `DutyCategory` 이름
+    pub category: String,
+    #[serde(flatten)]
+    pub open: RoleSlots,
+}
+
+/// 게임 데이터의 모든 데이터 센터
+pub fn data_centres() -> BTreeSet<&'static str> {
+    crate::ffxiv::WORLDS.values().map(|world| world.data_center().name()).collect()
+}
+
+/// 현재 모집글에서 데이터 센터별 표본 생성
+pub fn sample<'a>(
+    listings: impl IntoIterator<Item = &'a PartyFinderListing>,
+    data_centres: &BTreeSet<&'static str>,
+    sampled_at: DateTime<Utc>,
+) -> Vec<RoleDemandSample> {
+    let mut open: HashMap<&str, HashMap<String, RoleSlots>> = HashMap::new();
+    for listing in listings {
+        let Some(data_centre) = listing.data_centre_name() else {
+            continue;
+        };
+        *open
+            .entry(data_centre)
+            .or_default()
+            .entry(format!("{:?}", listing.canonical_category()))
+            .or_default() += listing.open_role_slots();
+    }
+
+    data_centres
+        .iter()
+        .map(|&data_centre| {
+            let mut categories: Vec<CategoryDemand> = open
+                .remove(data_centre)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(category, open)| CategoryDemand { category, open })
+                .collect();
+            categories.sort_by(|a, b| a.category.cmp(&b.category));
+
+            RoleDemandSample {
+                sampled_at,
+                data_centre: data_centre.to_string(),
+                categories,
+            }
+        })
+        .collect()
+}
+
+/// 요일(월요일부터) × 시간 칸별 평균 빈 자리 (표본이 없는 칸은 `None`)
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct RoleDemandMatrix {
+    pub tank: Vec<Vec<Option<f64>>>,
+    pub healer: Vec<Vec<Option<f64>>>,
+    pub dps: Vec<Vec<Option<f64>>>,
+    /// 칸별 표본 수
+    pub samples: Vec<Vec<u32>>,
+}
+
+/// 표본을 표시 시간대의 요일 × 시간 칸으로 모아 평균 (`category`가 있으면 그 분류만)
+pub fn matrix(samples: &[RoleDemandSample], timezone: FixedOffset, category: Option<DutyCategory>) -> RoleDemandMatrix {
+    let category = category.map(|category| format!("{:?}", category));
+    let mut totals = [[RoleSlots::default(); 24]; 7];
+    let mut counts = [[0_u32; 24]; 7];
+
+    for sample in samples {
+        let local = sample.sampled_at.with_timezone(&timezone);
+        let (day, hour) = (local.weekday().num_days_from_monday() as usize, local.hour() as usize);
+
+        for demand in &sample.categories {
+            if category.as_ref().is_none_or(|category| *category == demand.category) {
+                totals[day][hour] += demand.open;
+            }
+        }
+        counts[day][hour] += 1;
+    }
+
+    let average = |role: fn(&RoleSlots) -> u32| -> Vec<Vec<Option<f64>>> {
+        (0..7)
+            .map(|day| {
+                (0..24)
+                    .map(|hour| match counts[day][hour] {
+                        0 => None,
+                        count => Some(f64::from(role(&totals[day][hour])) / f64::from(count)),
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+
+    RoleDemandMatrix {
+        tank: average(|slots| slots.tank),
+        healer: average(|slots| slots.healer),
+        dps: average(|slots| slots.dps),
+        samples: counts.iter().map(|hours| hours.to_vec()).collect(),
+    }
+}
@@ -1,20 +1,38 @@
-use crate::ffxiv::Language;
+use crate::ffxiv::{Language, SUPPORTED_LANGUAGES};
+use crate::listing::description::DescriptionLanguage;
+use super::daily::{self, DailyCount};
 use crate::listing::{DutyCategory, DutyType};
+use crate::mongo::{canonical_category_expr, players_by_lower_content_ids};
+use crate::player::Player;
 use crate::web::State;
 use anyhow::Result;
-use chrono::{TimeDelta, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
+use chrono_humanize::HumanTime;
 use futures_util::TryStreamExt;
-use mongodb::bson::{doc, Document};
+use mongodb::bson::{bson, doc, Bson, Document};
 use mongodb::options::AggregateOptions;
-use serde::{Deserialize, Deserializer};
-use sestring::SeString;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sestring::payload::TextPayload;
+use sestring::{Payload, SeString};
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::future::Future;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct CachedStatistics {
     pub all_time: Statistics,
     pub seven_days: Statistics,
+    /// 마지막으로 두 기간 모두 계산에 성공한 시각
+    #[serde(skip, default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CachedStatistics {
+    /// 두 기간 중 더 오래된 계산 시각
+    pub fn generated_at(&self) -> DateTime<Utc> {
+        self.all_time.generated_at.min(self.seven_days.generated_at)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -32,6 +50,24 @@ pub struct Statistics {
     pub hosts: Vec<HostInfo>,
     pub hours: Vec<HourInfo>,
     pub days: Vec<DayInfo>,
+    /// 설명 언어별 비교 (많은 순)
+    #[serde(default)]
+    pub languages: Vec<LanguageInfo>,
+    /// 기간별 모집글 수 (듀티별 통계만, 오래된 순)
+    #[serde(default)]
+    pub timeline: Vec<TimelineInfo>,
+    /// 자리 채움 요약 (듀티별 통계만, 모집글이 없으면 비어 있음)
+    #[serde(default)]
+    pub fill: Vec<FillInfo>,
+    /// 고난도 듀티별 모집 완료까지 걸린 시간 (다 찬 모집글이 많은 순)
+    #[serde(default)]
+    pub fill_times: Vec<FillTimeInfo>,
+    /// 날짜별 모집글 수 (`daily_listings` 롤업, 전체 기간 90일 / 최근 7일 30일, 듀티별 통계는 비어 있음)
+    #[serde(default)]
+    pub daily: Vec<DailyCount>,
+    /// 집계가 끝난 시각
+    #[serde(skip, default = "Utc::now")]
+    pub generated_at: DateTime<Utc>,
 }
 
 fn alias_de<'de, D>(de: D) -> std::result::Result<HashMap<u32, Alias>, D::Error>
@@ -47,6 +83,11 @@ where
 }
 
 impl Statistics {
+    /// 집계 후 지난 시간 (예: "3 hours ago")
+    pub fn human_generated_at(&self) -> HumanTime {
+        HumanTime::from(self.generated_at - Utc::now())
+    }
+
     pub fn num_listings(&self) -> usize {
         if self.count.is_empty() {
             return 0;
@@ -68,9 +109,49 @@ impl Statistics {
 
         format!("{} @ {}", alias.name.text(), world).into()
     }
+
+    pub fn fill(&self) -> Option<&FillInfo> {
+        self.fill.first()
+    }
+
+    /// 통계에 표시되는 상위 호스트 ContentID (하위 32비트)
+    pub fn top_host_ids(&self) -> Vec<u32> {
+        self.hosts
+            .iter()
+            .flat_map(|host| host.content_ids.iter().map(|entry| entry.content_id))
+            .collect()
+    }
+
+    /// 모집글에서 이름을 찾지 못한 상위 호스트
+    pub fn unnamed_hosts(&self) -> Vec<u32> {
+        self.top_host_ids()
+            .into_iter()
+            .filter(|cid| !self.aliases.contains_key(cid))
+            .collect()
+    }
+
+    /// 이름을 모르는 호스트에 플레이어 기록의 이름 사용 (하위 32비트가 같은 기록이 여럿이면 최근 기록)
+    pub fn fill_aliases_from_players(&mut self, players: impl IntoIterator<Item = Player>) {
+        let mut newest: HashMap<u32, Player> = HashMap::new();
+        for player in players.into_iter().filter(|player| !player.name.is_empty()) {
+            let cid = player.content_id as u32;
+            if newest.get(&cid).is_none_or(|known| known.last_seen < player.last_seen) {
+                newest.insert(cid, player);
+            }
+        }
+
+        for cid in self.unnamed_hosts() {
+            if let Some(player) = newest.remove(&cid) {
+                self.aliases.insert(cid, Alias {
+                    name: SeString(vec![Payload::Text(TextPayload(player.name))]),
+                    home_world: u32::from(player.home_world),
+                });
+            }
+        }
+    }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Count {
     pub count: usize,
 }
@@ -82,13 +163,18 @@ pub struct AliasInfo {
     pub alias: Alias,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Alias {
-    #[serde(with = "crate::base64_sestring")]
+    /// DB에는 base64 SeString, API 응답에는 텍스트
+    #[serde(deserialize_with = "crate::base64_sestring::deserialize", serialize_with = "sestring_text")]
     pub name: SeString,
     pub home_world: u32,
 }
 
+fn sestring_text<S: Serializer>(name: &SeString, ser: S) -> std::result::Result<S::Ok, S::Error> {
+    ser.serialize_str(&name.text())
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct DutyInfo {
     #[serde(rename = "_id")]
@@ -97,6 +183,12 @@ pub struct DutyInfo {
 }
 
 impl DutyInfo {
+    /// 듀티별 통계 페이지가 있는 듀티 ID (카테고리 / 룰렛 / 게임 데이터에 없는 듀티는 `None`)
+    pub fn drill_down_id(&self) -> Option<u16> {
+        let (duty_type, _, duty) = self.info;
+        (duty_type == DutyType::Normal.as_u8() && crate::ffxiv::duty(u32::from(duty)).is_some()).then_some(duty)
+    }
+
     pub fn name(&self, lang: &Language) -> Cow<str> {
         let kind = match DutyType::from_u8(self.info.0) {
             Some(k) => k,
@@ -132,15 +224,15 @@ impl HostInfo {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct HostInfoInfo {
     pub content_id: u32,
     pub count: usize,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct HourInfo {
-    #[serde(rename = "_id")]
+    #[serde(rename(deserialize = "_id"))]
     pub hour: u8,
     pub count: usize,
 }
@@ -167,6 +259,231 @@ impl DayInfo {
     }
 }
 
+/// 설명 언어 하나의 모집글 수, 평균 설명 길이, 자동 번역 사용 비율
+///
+/// 설명이 빈 모집글은 `count`에만 들어가고 평균에서는 빠집니다.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct LanguageInfo {
+    /// 판별한 언어 (설명이 비었거나 언어 기록 전에 저장된 모집글은 `None`)
+    #[serde(rename(deserialize = "_id"))]
+    pub language: Option<DescriptionLanguage>,
+    pub count: usize,
+    /// 설명이 있는 모집글 수
+    pub with_description: usize,
+    /// 정리된 설명의 평균 글자 수
+    pub average_length: Option<f64>,
+    /// 자동 번역 문구가 들어간 설명 비율 (0 ~ 1)
+    pub autotranslate_fraction: Option<f64>,
+}
+
+impl LanguageInfo {
+    pub fn name(&self) -> &'static str {
+        match self.language {
+            Some(language) => language.name(),
+            None => "Unknown / empty",
+        }
+    }
+
+    pub fn average_length_text(&self) -> String {
+        match self.average_length {
+            Some(length) => format!("{:.0}", length),
+            None => "-".to_string(),
+        }
+    }
+
+    pub fn autotranslate_percent(&self) -> String {
+        match self.autotranslate_fraction {
+            Some(fraction) => format!("{:.0}%", fraction * 100.0),
+            None => "-".to_string(),
+        }
+    }
+}
+
+/// 듀티별 통계의 기간 하나 (7일이면 날짜, 전체 기간이면 월)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct TimelineInfo {
+    #[serde(rename(deserialize = "_id"))]
+    pub period: String,
+    pub count: usize,
+}
+
+/// 듀티별 통계의 자리 채움 요약
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FillInfo {
+    /// 모집글당 평균 채워진 자리 수 (모집글의 마지막 상태 기준)
+    pub average_filled: f64,
+    /// 모집글당 평균 전체 자리 수
+    pub average_capacity: f64,
+    /// 모든 자리가 찬 모집글 수
+    pub full: usize,
+}
+
+impl FillInfo {
+    /// 평균 채움 비율 (0 ~ 1)
+    pub fn fraction(&self) -> Option<f64> {
+        (self.average_capacity > 0.0).then(|| self.average_filled / self.average_capacity)
+    }
+}
+
+/// 고난도 듀티 하나의 모집 완료 시간
+///
+/// 다 차지 않은 모집글은 `count`에만 들어가고 중앙값에서는 빠집니다.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct FillTimeInfo {
+    #[serde(rename = "_id")]
+    pub duty: u16,
+    pub count: usize,
+    /// 모든 자리가 찬 모집글 수
+    pub filled: usize,
+    /// 만든 시각부터 다 찰 때까지 걸린 시간의 중앙값 (분)
+    pub median_minutes: Option<f64>,
+}
+
+impl FillTimeInfo {
+    pub fn name(&self, lang: &Language) -> Cow<'static, str> {
+        match crate::ffxiv::duty(u32::from(self.duty)) {
+            Some(info) => Cow::from(info.name.text(lang)),
+            None => Cow::from("<unknown>"),
+        }
+    }
+
+    pub fn median_text(&self) -> String {
+        match self.median_minutes {
+            Some(minutes) if minutes >= 60.0 => format!("{:.0}h {:.0}m", (minutes / 60.0).floor(), minutes % 60.0),
+            Some(minutes) => format!("{:.0}m", minutes),
+            None => "-".to_string(),
+        }
+    }
+}
+
+impl Serialize for FillTimeInfo {
+    fn serialize<S: Serializer>(&self, ser: S) -> std::result::Result<S::Ok, S::Error> {
+        let [en, ja, de, fr] = SUPPORTED_LANGUAGES.map(|lang| self.name(&lang));
+        let mut out = ser.serialize_struct("FillTimeInfo", 5)?;
+        out.serialize_field("duty", &self.duty)?;
+        out.serialize_field("name", &DutyNames { en, ja, de, fr })?;
+        out.serialize_field("count", &self.count)?;
+        out.serialize_field("filled", &self.filled)?;
+        out.serialize_field("median_minutes", &self.median_minutes)?;
+        out.end()
+    }
+}
+
+/// `/api/stats` 응답
+///
+/// 호스트 이름은 `aliases`가 있어야 정해지므로 `Statistics`에서 함께 직렬화합니다.
+impl Serialize for Statistics {
+    fn serialize<S: Serializer>(&self, ser: S) -> std::result::Result<S::Ok, S::Error> {
+        let hosts: Vec<_> = self.hosts.iter().map(|host| HostEntry { host, stats: self }).collect();
+
+        let mut out = ser.serialize_struct("Statistics", 11)?;
+        out.serialize_field("generated_at", &self.generated_at)?;
+        out.serialize_field("listings", &self.num_listings())?;
+        out.serialize_field("duties", &self.duties)?;
+        out.serialize_field("hosts", &hosts)?;
+        out.serialize_field("hours", &self.hours)?;
+        out.serialize_field("days", &self.days)?;
+        out.serialize_field("languages", &self.languages)?;
+        out.serialize_field("timeline", &self.timeline)?;
+        out.serialize_field("fill", &self.fill())?;
+        out.serialize_field("fill_times", &self.fill_times)?;
+        out.serialize_field("daily", &self.daily)?;
+        out.end()
+    }
+}
+
+/// 지원하는 언어별 듀티 이름 (`DutyInfo::name`)
+#[derive(Serialize)]
+struct DutyNames<'a> {
+    en: Cow<'a, str>,
+    ja: Cow<'a, str>,
+    de: Cow<'a, str>,
+    fr: Cow<'a, str>,
+}
+
+impl Serialize for DutyInfo {
+    fn serialize<S: Serializer>(&self, ser: S) -> std::result::Result<S::Ok, S::Error> {
+        let [en, ja, de, fr] = SUPPORTED_LANGUAGES.map(|lang| self.name(&lang));
+        let (duty_type, category, duty) = self.info;
+
+        let mut out = ser.serialize_struct("DutyInfo", 6)?;
+        out.serialize_field("duty_type", &duty_type)?;
+        out.serialize_field("category", &category)?;
+        out.serialize_field("duty", &duty)?;
+        out.serialize_field("name", &DutyNames { en, ja, de, fr })?;
+        out.serialize_field("drill_down_id", &self.drill_down_id())?;
+        out.serialize_field("count", &self.count)?;
+        out.end()
+    }
+}
+
+/// 월드 하나의 호스트 통계 (상위 호스트 이름은 `Statistics::player_name`)
+struct HostEntry<'a> {
+    host: &'a HostInfo,
+    stats: &'a Statistics,
+}
+
+#[derive(Serialize)]
+struct HostPlayer<'a> {
+    content_id: u32,
+    name: Cow<'a, str>,
+    alias: Option<&'a Alias>,
+    count: usize,
+}
+
+impl Serialize for HostEntry<'_> {
+    fn serialize<S: Serializer>(&self, ser: S) -> std::result::Result<S::Ok, S::Error> {
+        let players: Vec<_> = self
+            .host
+            .content_ids
+            .iter()
+            .map(|entry| HostPlayer {
+                content_id: entry.content_id,
+                name: self.stats.player_name(&entry.content_id),
+                alias: self.stats.aliases.get(&entry.content_id),
+                count: entry.count,
+            })
+            .collect();
+
+        let mut out = ser.serialize_struct("HostInfo", 5)?;
+        out.serialize_field("world", &self.host.created_world)?;
+        out.serialize_field("world_name", self.host.world_name())?;
+        out.serialize_field("count", &self.host.count)?;
+        out.serialize_field("other", &self.host.num_other())?;
+        out.serialize_field("players", &players)?;
+        out.end()
+    }
+}
+
+impl Serialize for DayInfo {
+    fn serialize<S: Serializer>(&self, ser: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut out = ser.serialize_struct("DayInfo", 3)?;
+        out.serialize_field("day", &self.day)?;
+        out.serialize_field("name", self.name())?;
+        out.serialize_field("count", &self.count)?;
+        out.end()
+    }
+}
+
+/// `labeled_categories`의 DB 저장 값
+fn labeled_category_ids() -> Vec<i64> {
+    crate::ffxiv::labeled_categories()
+        .iter()
+        .map(|category| *category as u32 as i64)
+        .collect()
+}
+
+/// 고난도 듀티 ID (`PartyFinderListing::high_end`와 같은 기준)
+fn high_end_duty_ids() -> Vec<i64> {
+    let mut ids: Vec<i64> = crate::ffxiv::DUTIES
+        .iter()
+        .filter(|(_, info)| info.high_end)
+        .map(|(&duty, _)| i64::from(duty))
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
 lazy_static::lazy_static! {
     static ref QUERY: [Document; 2] = [
         doc! {
@@ -185,11 +502,23 @@ lazy_static::lazy_static! {
                 "duties": [
                     {
                         "$group": {
-                            "_id": [
-                                "$listing.duty_type",
-                                "$listing.category",
-                                "$listing.duty",
-                            ],
+                            // 카테고리로 표시되는 모집글은 (Other, category, 0)으로 묶음 (분류는 `canonical_category`)
+                            "_id": {
+                                "$cond": [
+                                    {
+                                        "$and": [
+                                            { "$eq": ["$listing.duty", 0] },
+                                            { "$in": [canonical_category_expr(), labeled_category_ids()] },
+                                        ]
+                                    },
+                                    [0, canonical_category_expr(), 0],
+                                    [
+                                        "$listing.duty_type",
+                                        canonical_category_expr(),
+                                        "$listing.duty",
+                                    ],
+                                ]
+                            },
                             "count": {
                                 "$sum": 1
                             },
@@ -275,6 +604,49 @@ lazy_static::lazy_static! {
                         }
                     }
                 ],
+                // 설명이 빈 모집글은 길이와 자동 번역 평균에서 빼기 위해 null로 ($avg는 null 무시)
+                // 보관 기록에는 설명 대신 길이(`description_length`)만 있음
+                "languages": [
+                    {
+                        "$project": {
+                            "language": "$description_language",
+                            "length": {
+                                "$ifNull": [
+                                    "$description_length",
+                                    { "$strLenCP": { "$ifNull": ["$description_text", ""] } },
+                                ]
+                            },
+                            "has_autotranslate": { "$ifNull": ["$has_autotranslate", false] },
+                        }
+                    },
+                    {
+                        "$group": {
+                            "_id": "$language",
+                            "count": { "$sum": 1 },
+                            "with_description": {
+                                "$sum": { "$cond": [{ "$gt": ["$length", 0] }, 1, 0] },
+                            },
+                            "average_length": {
+                                "$avg": { "$cond": [{ "$gt": ["$length", 0] }, "$length", null] },
+                            },
+                            "autotranslate_fraction": {
+                                "$avg": {
+                                    "$cond": [
+                                        { "$gt": ["$length", 0] },
+                                        { "$cond": ["$has_autotranslate", 1, 0] },
+                                        null,
+                                    ]
+                                },
+                            },
+                        }
+                    },
+                    {
+                        "$sort": {
+                            "count": -1,
+                        }
+                    }
+                ],
+                "fill_times": fill_times_facet(),
             }
         },
     ];
@@ -305,71 +677,320 @@ lazy_static::lazy_static! {
     ];
 }
 
-pub async fn get_stats(state: &State) -> Result<Statistics> {
-    get_stats_internal(state, QUERY.iter().cloned()).await
+/// 통계 집계 파이프라인
+///
+/// 기간 / 듀티 조건은 `QUERY` 앞에 `$match`로 붙이므로 모든 통계가 같은 facet 정의를 씁니다.
+/// 듀티별 통계에는 기간별 추이(`timeline`)와 자리 채움(`fill`) facet을 더합니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatsQuery {
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    duty: Option<u16>,
 }
 
-pub async fn get_stats_seven_days(state: &State) -> Result<Statistics> {
-    let last_week = Utc::now() - TimeDelta::try_days(7).unwrap();
+impl StatsQuery {
+    /// 이 시각 이후에 만든 모집글만 집계
+    pub fn since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
 
-    let mut docs = QUERY.to_vec();
-    docs.insert(
-        0,
-        doc! {
-            "$match": {
-                "created_at": {
-                    "$gte": last_week,
+    /// 이 시각까지 만든 모집글만 집계 (집계 중 새로 들어온 모집글 제외)
+    pub fn until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// 듀티 하나(`DutyType::Normal`)의 모집글만 집계
+    pub fn duty(mut self, duty: u16) -> Self {
+        self.duty = Some(duty);
+        self
+    }
+
+    pub fn build(&self) -> Vec<Document> {
+        let mut docs = Vec::with_capacity(QUERY.len() + 2);
+        if let Some(created_at) = self.created_at() {
+            docs.push(doc! {
+                "$match": {
+                    "created_at": created_at,
                 },
+            });
+        }
+        if let Some(duty) = self.duty {
+            docs.push(doc! {
+                "$match": {
+                    "listing.duty_type": i32::from(DutyType::Normal.as_u8()),
+                    "listing.duty": i32::from(duty),
+                },
+            });
+        }
+        docs.extend(QUERY.iter().cloned());
+
+        if self.duty.is_some() {
+            let facets = docs
+                .last_mut()
+                .and_then(|facet| facet.get_document_mut("$facet").ok())
+                .expect("QUERY ends with $facet");
+            facets.insert("timeline", timeline_facet(self.since.is_some()));
+            facets.insert("fill", fill_facet());
+        }
+
+        docs
+    }
+
+    /// 상위 호스트 이름 파이프라인 (통계와 같은 `until` 상한)
+    pub fn aliases(&self, content_ids: &[u32]) -> Vec<Document> {
+        let mut filter = doc! {
+            "listing.content_id_lower": {
+                "$in": content_ids,
             },
+        };
+        if let Some(until) = self.until {
+            filter.insert("created_at", doc! { "$lte": until });
+        }
+
+        let mut docs = vec![doc! { "$match": filter }];
+        docs.extend(ALIASES_QUERY.iter().cloned());
+        docs
+    }
+
+    fn created_at(&self) -> Option<Document> {
+        let mut created_at = Document::new();
+        if let Some(since) = self.since {
+            created_at.insert("$gte", since);
+        }
+        if let Some(until) = self.until {
+            created_at.insert("$lte", until);
+        }
+        (!created_at.is_empty()).then_some(created_at)
+    }
+}
+
+/// 기간별 모집글 수 (짧은 기간은 날짜, 전체 기간은 월 단위)
+fn timeline_facet(daily: bool) -> Bson {
+    let format = if daily { "%Y-%m-%d" } else { "%Y-%m" };
+    bson!([
+        {
+            "$group": {
+                "_id": {
+                    "$dateToString": {
+                        "format": format,
+                        "date": "$created_at",
+                    },
+                },
+                "count": { "$sum": 1 },
+            }
         },
-    );
-
-    get_stats_internal(state, docs).await
-}
-
-async fn get_stats_internal(
-    state: &State,
-    docs: impl IntoIterator<Item = Document>,
-) -> Result<Statistics> {
-    let mut cursor = state
-        .collection()
-        .aggregate(
-            docs,
-            AggregateOptions::builder().allow_disk_use(true).build(),
-        )
-        .await?;
-    let doc = cursor.try_next().await?;
-    let doc = doc.ok_or_else(|| anyhow::anyhow!("missing document"))?;
-    let mut stats: Statistics = mongodb::bson::from_document(doc)?;
+        {
+            "$sort": { "_id": 1 }
+        },
+    ])
+}
 
-    let ids: Vec<u32> = stats
-        .hosts
-        .iter()
-        .flat_map(|host| host.content_ids.iter().map(|entry| entry.content_id))
-        .collect();
-    let mut aliases_query: Vec<Document> = ALIASES_QUERY.iter().cloned().collect();
-    aliases_query.insert(
-        0,
-        doc! {
+/// 고난도 듀티별 모집 완료까지 걸린 시간 (보관 기록의 `full_at` 기준)
+///
+/// `full_at`이 없으면 다 차지 않은 모집글이므로 중앙값에서 빠지도록 null로 둡니다.
+/// 걸린 시간 순으로 정렬한 뒤 묶어 가운데 값을 고릅니다 (`$median`이 없는 버전 호환).
+fn fill_times_facet() -> Bson {
+    bson!([
+        {
             "$match": {
-                "listing.content_id_lower": {
-                    "$in": ids,
-                }
+                "listing.duty_type": i32::from(DutyType::Normal.as_u8()),
+                "listing.duty": { "$in": high_end_duty_ids() },
+            }
+        },
+        {
+            "$project": {
+                "duty": "$listing.duty",
+                "minutes": {
+                    "$cond": [
+                        { "$eq": [{ "$ifNull": ["$full_at", null] }, null] },
+                        null,
+                        {
+                            "$max": [
+                                { "$divide": [{ "$subtract": ["$full_at", "$created_at"] }, 60_000] },
+                                0,
+                            ]
+                        },
+                    ]
+                },
+            }
+        },
+        {
+            "$sort": {
+                "minutes": 1,
+            }
+        },
+        {
+            "$group": {
+                "_id": "$duty",
+                "count": { "$sum": 1 },
+                "minutes": { "$push": "$minutes" },
+            }
+        },
+        {
+            "$project": {
+                "count": 1,
+                "minutes": {
+                    "$filter": { "input": "$minutes", "cond": { "$ne": ["$$this", null] } },
+                },
+            }
+        },
+        {
+            "$project": {
+                "count": 1,
+                "filled": { "$size": "$minutes" },
+                "median_minutes": {
+                    "$let": {
+                        "vars": { "size": { "$size": "$minutes" } },
+                        "in": {
+                            "$cond": [
+                                { "$eq": ["$$size", 0] },
+                                null,
+                                {
+                                    "$avg": [
+                                        { "$arrayElemAt": ["$minutes", { "$floor": { "$divide": [{ "$subtract": ["$$size", 1] }, 2] } }] },
+                                        { "$arrayElemAt": ["$minutes", { "$floor": { "$divide": ["$$size", 2] } }] },
+                                    ]
+                                },
+                            ]
+                        },
+                    }
+                },
             }
         },
-    );
-    let mut cursor = state
-        .collection()
-        .aggregate(
-            aliases_query,
-            AggregateOptions::builder().allow_disk_use(true).build(),
-        )
-        .await?;
+        {
+            "$sort": {
+                "filled": -1,
+                "_id": 1,
+            }
+        }
+    ])
+}
+
+/// 평균 채워진 / 전체 자리 수 (`PartyFinderListing::filled_total`, `total_capacity`와 같은 기준)
+fn fill_facet() -> Bson {
+    bson!([
+        {
+            "$project": {
+                "filled": {
+                    "$size": {
+                        "$filter": {
+                            "input": { "$ifNull": ["$listing.jobs_present", []] },
+                            "cond": { "$gt": ["$$this", 0] },
+                        },
+                    },
+                },
+                "capacity": {
+                    "$multiply": ["$listing.slots_available", { "$max": ["$listing.num_parties", 1] }],
+                },
+            }
+        },
+        {
+            "$group": {
+                "_id": null,
+                "average_filled": { "$avg": { "$min": ["$filled", "$capacity"] } },
+                "average_capacity": { "$avg": "$capacity" },
+                "full": {
+                    "$sum": {
+                        "$cond": [
+                            { "$and": [{ "$gt": ["$capacity", 0] }, { "$gte": ["$filled", "$capacity"] }] },
+                            1,
+                            0,
+                        ]
+                    },
+                },
+            }
+        },
+    ])
+}
+
+fn last_week() -> DateTime<Utc> {
+    Utc::now() - TimeDelta::try_days(7).unwrap()
+}
+
+pub async fn get_stats(state: &State) -> Result<Statistics> {
+    let mut stats = get_stats_internal(state, StatsQuery::default()).await?;
+    stats.daily = load_daily(state, daily::ALL_TIME_DAYS).await;
+    Ok(stats)
+}
+
+pub async fn get_stats_seven_days(state: &State) -> Result<Statistics> {
+    let mut stats = get_stats_internal(state, StatsQuery::default().since(last_week())).await?;
+    stats.daily = load_daily(state, daily::SEVEN_DAYS_DAYS).await;
+    Ok(stats)
+}
+
+/// 오늘까지 `days`일의 날짜별 모집글 수 (조회 실패는 추이만 비워 둠)
+async fn load_daily(state: &State, days: u32) -> Vec<DailyCount> {
+    let today = Utc::now().date_naive();
+    match crate::mongo::daily_listings_since(state.daily_listings_collection(), &daily::first_day(today, days)).await {
+        Ok(counts) => daily::fill_days(&counts, today, days),
+        Err(e) => {
+            tracing::warn!("could not load daily listing counts: {:#}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 듀티 하나의 통계 (`seven_days`면 최근 7일)
+pub async fn get_duty_stats(state: &State, duty: u16, seven_days: bool) -> Result<Statistics> {
+    let mut query = StatsQuery::default().duty(duty);
+    if seven_days {
+        query = query.since(last_week());
+    }
+    get_stats_internal(state, query).await
+}
+
+/// 상위 호스트 이름 채우기
+///
+/// 모집글에서 찾은 이름(`load_aliases`)을 먼저 쓰고, 그 사이 모집글이 지워져 이름이 없는 호스트만
+/// 플레이어 기록(`load_players`, 하위 32비트로 조회)에서 찾습니다. 플레이어 조회 실패는 이름만 비워 둡니다.
+pub async fn resolve_host_names<A, AFut, P, PFut>(stats: &mut Statistics, load_aliases: A, load_players: P) -> Result<()>
+where
+    A: FnOnce(Vec<u32>) -> AFut,
+    AFut: Future<Output = Result<HashMap<u32, Alias>>>,
+    P: FnOnce(Vec<u32>) -> PFut,
+    PFut: Future<Output = Result<Vec<Player>>>,
+{
+    stats.aliases = load_aliases(stats.top_host_ids()).await?;
+
+    let unnamed = stats.unnamed_hosts();
+    if unnamed.is_empty() {
+        return Ok(());
+    }
+    match load_players(unnamed).await {
+        Ok(players) => stats.fill_aliases_from_players(players),
+        Err(e) => tracing::warn!("could not look up host names from players: {:#}", e),
+    }
+    Ok(())
+}
+
+/// 통계는 TTL로 지워지는 모집글 대신 보관 기록(`listings_archive`)을 집계
+async fn get_stats_internal(state: &State, query: StatsQuery) -> Result<Statistics> {
+    // 두 집계가 같은 모집글을 보도록 상한을 한 번만 정함
+    let query = query.until(Utc::now());
+    let collection = state.archive_collection();
+    let options = AggregateOptions::builder().allow_disk_use(true).build();
+
+    let mut cursor = collection.aggregate(query.build(), options.clone()).await?;
     let doc = cursor.try_next().await?;
     let doc = doc.ok_or_else(|| anyhow::anyhow!("missing document"))?;
-    let aliases: Aliases = mongodb::bson::from_document(doc)?;
+    let mut stats: Statistics = mongodb::bson::from_document(doc)?;
 
-    stats.aliases = aliases.aliases;
+    resolve_host_names(
+        &mut stats,
+        |ids| async move {
+            let mut cursor = collection.aggregate(query.aliases(&ids), options).await?;
+            let doc = cursor.try_next().await?;
+            let doc = doc.ok_or_else(|| anyhow::anyhow!("missing document"))?;
+            let aliases: Aliases = mongodb::bson::from_document(doc)?;
+            Ok(aliases.aliases)
+        },
+        |ids| async move { players_by_lower_content_ids(state.players_read_collection().primary(), &ids).await },
+    )
+    .await?;
+    stats.generated_at = Utc::now();
 
     Ok(stats)
 }
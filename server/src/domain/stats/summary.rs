@@ -0,0 +1,115 @@
+//! 현재 모집글 요약 (`GET /api/summary`)
+//!
+//! 봇이 자주 조회하므로 모집글 전체 대신 `listing_summary_pipeline`이 센 개수만 받아 만듭니다.
+//! 데이터 센터별 개수는 월드별 개수를 게임 데이터로 묶어 계산합니다.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::Count;
+use crate::ffxiv::LocalisedText;
+use crate::listing::DutyCategory;
+
+/// 요약에 넣는 듀티 수 (모집글이 많은 순)
+pub const SUMMARY_TOP_DUTIES: usize = 10;
+
+/// 요약 파이프라인 결과 (`$facet` 하나)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SummaryCounts {
+    #[serde(default)]
+    pub total: Vec<Count>,
+    /// 분류(`canonical_category`)별
+    #[serde(default)]
+    pub categories: Vec<SummaryBucket>,
+    /// 듀티별 (일반 듀티만, 상위 `SUMMARY_TOP_DUTIES`개)
+    #[serde(default)]
+    pub duties: Vec<SummaryBucket>,
+    /// 모집글을 만든 월드별
+    #[serde(default)]
+    pub worlds: Vec<SummaryBucket>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct SummaryBucket {
+    #[serde(rename = "_id")]
+    pub id: u32,
+    pub count: usize,
+}
+
+/// `/api/summary` 응답
+#[derive(Debug, Clone, Serialize)]
+pub struct ListingSummary {
+    /// 집계한 시각
+    pub generated_at: DateTime<Utc>,
+    pub listings: usize,
+    pub categories: Vec<CategoryCount>,
+    pub duties: Vec<DutyCount>,
+    pub data_centres: Vec<DataCentreCount>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryCount {
+    /// `DutyCategory::id`
+    pub category: &'static str,
+    pub name: LocalisedText,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DutyCount {
+    pub duty: u16,
+    /// 게임 데이터에 없는 듀티는 `None`
+    pub name: Option<LocalisedText>,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DataCentreCount {
+    pub data_centre: &'static str,
+    pub count: usize,
+}
+
+impl ListingSummary {
+    /// 개수 변환 (알 수 없는 분류 / 월드는 전체 수에만 들어감)
+    pub fn new(counts: SummaryCounts, generated_at: DateTime<Utc>) -> Self {
+        let categories = counts
+            .categories
+            .iter()
+            .filter_map(|bucket| {
+                let category = DutyCategory::from_u32(bucket.id)?;
+                Some(CategoryCount { category: category.id(), name: category.pf_category().name(), count: bucket.count })
+            })
+            .collect();
+        let duties = counts
+            .duties
+            .iter()
+            .filter_map(|bucket| {
+                let duty = u16::try_from(bucket.id).ok()?;
+                let name = crate::ffxiv::duty(bucket.id).map(|info| info.name);
+                Some(DutyCount { duty, name, count: bucket.count })
+            })
+            .collect();
+
+        let mut by_data_centre: BTreeMap<&'static str, usize> = BTreeMap::new();
+        for bucket in &counts.worlds {
+            if let Some(world) = crate::ffxiv::WORLDS.get(&bucket.id) {
+                *by_data_centre.entry(world.data_center().name()).or_default() += bucket.count;
+            }
+        }
+        let mut data_centres: Vec<_> = by_data_centre
+            .into_iter()
+            .map(|(data_centre, count)| DataCentreCount { data_centre, count })
+            .collect();
+        // 많은 순, 같으면 이름 순 (`BTreeMap` 순서 유지)
+        data_centres.sort_by_key(|dc| std::cmp::Reverse(dc.count));
+
+        Self {
+            generated_at,
+            listings: counts.total.first().map(|total| total.count).unwrap_or_default(),
+            categories,
+            duties,
+            data_centres,
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! 연구용 익명화 데이터셋
+//!
+//! 매일 전날(UTC) 갱신된 공개 모집글을 익명화해 gzip 압축 NDJSON으로 저장합니다.
+//! 모집글 컬렉션은 TTL로 지워지므로, 공개 모집글을 주기적으로 `listings_export`에 복사해 두고 그 복사본을 내보냅니다.
+//! 익명화 규칙은 [`anonymize`] 모듈을 참고하세요.
+//!
+//! - `GET /api/datasets`: 생성된 데이터셋 목록과 다운로드 링크
+//! - `GET /api/datasets/{file}`: 데이터셋 다운로드
+//!
+//! 전체 데이터를 메모리에 올리지 않도록 DB 커서를 두 번 순회합니다.
+//! 첫 번째 순회는 그룹별 개수만 세고, 두 번째 순회는 k 미만 그룹을 제외하며 바로 압축해 씁니다.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use warp::filters::BoxedFilter;
+use warp::http::StatusCode;
+use warp::path::Peek;
+use warp::{Filter, Reply};
+
+use crate::config::Export;
+use crate::listing_container::ListingContainer;
+use crate::mongo::{delete_exported_listings, listings_updated_between, oldest_staged_listing, stage_listings_for_export};
+use crate::web::State;
+
+use self::anonymize::{anonymize, group_key, GroupCounts, PeriodSalt};
+
+pub(crate) mod anonymize;
+
+/// 압축된 데이터를 파일로 내보내는 단위
+const FLUSH_BYTES: usize = 64 * 1024;
+
+/// 내보낼 모집글을 복사하는 간격 (모집글 TTL보다 짧아야 함)
+pub const STAGING_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// 날짜별 데이터셋 파일 이름
+pub fn dataset_file_name(date: NaiveDate) -> String {
+    format!("rpf-{}.ndjson.gz", date.format("%Y-%m-%d"))
+}
+
+/// 데이터셋 파일 이름의 날짜 (데이터셋 파일이 아니면 `None`)
+pub fn dataset_date(file_name: &str) -> Option<NaiveDate> {
+    let date = file_name.strip_prefix("rpf-")?.strip_suffix(".ndjson.gz")?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// 내보내기 결과
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct ExportReport {
+    /// 기록한 모집글 수
+    pub written: usize,
+    /// k 미만 그룹이라 제외한 모집글 수
+    pub suppressed: usize,
+    /// 데이터 센터를 알 수 없어 제외한 모집글 수
+    pub ungrouped: usize,
+}
+
+/// 익명화한 줄을 gzip으로 압축해 조금씩 내보내는 기록기
+pub struct DatasetWriter<W> {
+    encoder: GzEncoder<Vec<u8>>,
+    out: W,
+}
+
+impl<W: AsyncWrite + Unpin> DatasetWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            encoder: GzEncoder::new(Vec::new(), Compression::default()),
+            out,
+        }
+    }
+
+    pub async fn write<T: Serialize>(&mut self, row: &T) -> anyhow::Result<()> {
+        serde_json::to_writer(&mut self.encoder, row)?;
+        self.encoder.write_all(b"\n")?;
+
+        if self.encoder.get_ref().len() >= FLUSH_BYTES {
+            let chunk = std::mem::take(self.encoder.get_mut());
+            self.out.write_all(&chunk).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn finish(mut self) -> anyhow::Result<W> {
+        let rest = self.encoder.finish()?;
+        self.out.write_all(&rest).await?;
+        self.out.flush().await?;
+        Ok(self.out)
+    }
+}
+
+/// 첫 번째 순회: 그룹별 모집글 수 집계
+pub async fn count_groups(listings: impl Stream<Item = ListingContainer>) -> GroupCounts {
+    let mut counts = GroupCounts::default();
+    futures_util::pin_mut!(listings);
+    while let Some(container) = listings.next().await {
+        if let Some(key) = group_key(&container.listing) {
+            counts.add(key);
+        }
+    }
+    counts
+}
+
+/// 두 번째 순회: k 이상인 그룹의 모집글만 익명화해 기록
+pub async fn write_dataset<W: AsyncWrite + Unpin>(
+    listings: impl Stream<Item = ListingContainer>,
+    counts: &GroupCounts,
+    k: usize,
+    salt: &PeriodSalt,
+    date: NaiveDate,
+    writer: &mut DatasetWriter<W>,
+) -> anyhow::Result<ExportReport> {
+    let mut report = ExportReport::default();
+    futures_util::pin_mut!(listings);
+    while let Some(container) = listings.next().await {
+        let Some(key) = group_key(&container.listing) else {
+            report.ungrouped += 1;
+            continue;
+        };
+        if !counts.allows(&key, k) {
+            report.suppressed += 1;
+            continue;
+        }
+
+        if let Some(row) = anonymize(&container, salt, date) {
+            writer.write(&row).await?;
+            report.written += 1;
+        }
+    }
+    Ok(report)
+}
+
+/// 날짜의 (시작, 끝) 시각 (UTC)
+fn day_bounds(date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let from = date.and_time(NaiveTime::MIN).and_utc();
+    (from, from + TimeDelta::try_days(1).unwrap())
+}
+
+/// 내보낼 날짜 (가장 오래된 복사본의 날짜부터 `last`까지, 복사본이 없거나 더 최근이면 `last`만)
+pub fn export_dates(oldest_staged: Option<NaiveDate>, last: NaiveDate) -> Vec<NaiveDate> {
+    let first = oldest_staged.filter(|oldest| *oldest < last).unwrap_or(last);
+    first.iter_days().take_while(|date| *date <= last).collect()
+}
+
+/// `last`까지 아직 내보내지 않은 날짜의 데이터셋 생성
+///
+/// 서버가 멈춰 있었거나 내보내기에 실패한 날짜도 복사본이 남아 있으면 이어서 내보냅니다.
+/// 이미 파일이 있는 날짜는 내보내지 않고 남은 복사본만 지웁니다.
+pub async fn export_pending_days(state: &State, config: &Export, last: NaiveDate) {
+    let oldest = match oldest_staged_listing(state.export_staging_collection()).await {
+        Ok(oldest) => oldest.map(|at| at.date_naive()),
+        Err(e) => {
+            tracing::warn!("could not find the oldest staged listing: {:#?}", e);
+            None
+        }
+    };
+
+    for date in export_dates(oldest, last) {
+        let path = config.directory.join(dataset_file_name(date));
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            let (from, to) = day_bounds(date);
+            if let Err(e) = delete_exported_listings(state.export_staging_collection(), from, to).await {
+                tracing::warn!("could not delete staged listings for {}: {:#?}", date, e);
+            }
+            continue;
+        }
+
+        match export_day(state, config, date).await {
+            Ok(report) => tracing::info!("Exported dataset for {}: {:?}", date, report),
+            Err(e) => tracing::error!("error exporting dataset for {}: {:#?}", date, e),
+        }
+    }
+}
+
+/// 하루치 데이터셋 생성 (`listings_export`의 복사본 기준)
+///
+/// 임시 파일에 쓴 뒤 이름을 바꾸므로 목록에는 완성된 파일만 나타납니다.
+/// 다 쓴 뒤에는 그 날짜의 복사본만 지웁니다 (아직 내보내지 않은 이전 날짜는 남겨 둠).
+pub async fn export_day(state: &State, config: &Export, date: NaiveDate) -> anyhow::Result<ExportReport> {
+    let (from, to) = day_bounds(date);
+    // 마지막 복사 이후 갱신된 모집글도 포함
+    stage_listings_for_export(state.collection().primary(), from).await?;
+    let listings = || async {
+        let cursor = listings_updated_between(state.export_staging_collection(), from, to).await?;
+        anyhow::Ok(cursor.filter_map(|res| async move {
+            res.map_err(|e| tracing::warn!("Skipping listing during dataset export: {:?}", e)).ok()
+        }))
+    };
+
+    let counts = count_groups(listings().await?).await;
+
+    tokio::fs::create_dir_all(&config.directory)
+        .await
+        .context("could not create dataset directory")?;
+    let path = config.directory.join(dataset_file_name(date));
+    let partial = path.with_extension("gz.part");
+    let file = tokio::fs::File::create(&partial)
+        .await
+        .context("could not create dataset file")?;
+
+    let salt = PeriodSalt::for_date(&config.salt_secret, date);
+    let mut writer = DatasetWriter::new(tokio::io::BufWriter::new(file));
+    let report = write_dataset(listings().await?, &counts, config.k, &salt, date, &mut writer).await?;
+    writer.finish().await?;
+
+    tokio::fs::rename(&partial, &path)
+        .await
+        .context("could not finalise dataset file")?;
+
+    delete_exported_listings(state.export_staging_collection(), from, to).await?;
+    Ok(report)
+}
+
+/// 데이터셋 목록 항목
+#[derive(Debug, Serialize)]
+pub struct DatasetInfo {
+    pub date: NaiveDate,
+    pub file: String,
+    pub size_bytes: u64,
+    pub url: String,
+}
+
+/// 디렉터리의 데이터셋 목록 (최신순)
+pub async fn list_datasets(directory: &Path) -> anyhow::Result<Vec<DatasetInfo>> {
+    let mut datasets = Vec::new();
+    let mut entries = match tokio::fs::read_dir(directory).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(datasets),
+        Err(e) => return Err(e).context("could not read dataset directory"),
+    };
+
+    while let Some(entry) = entries.next_entry().await? {
+        let file = entry.file_name().to_string_lossy().into_owned();
+        let Some(date) = dataset_date(&file) else {
+            continue;
+        };
+        datasets.push(DatasetInfo {
+            date,
+            size_bytes: entry.metadata().await?.len(),
+            url: format!("/api/datasets/{}", file),
+            file,
+        });
+    }
+
+    datasets.sort_by_key(|dataset| std::cmp::Reverse(dataset.date));
+    Ok(datasets)
+}
+
+/// `/api/datasets` 경로 (내보내기가 설정되지 않았으면 404)
+pub fn datasets(state: Arc<State>) -> BoxedFilter<(warp::reply::Response,)> {
+    let Some(directory) = state.config.export.as_ref().map(|export| export.directory.clone()) else {
+        return warp::path("datasets")
+            .and_then(|| async { Err::<warp::reply::Response, _>(warp::reject::not_found()) })
+            .boxed();
+    };
+
+    warp::get()
+        .and(warp::path("datasets"))
+        .and(index(directory.clone()).or(download(directory)).unify())
+        .boxed()
+}
+
+fn index(directory: PathBuf) -> BoxedFilter<(warp::reply::Response,)> {
+    async fn logic(directory: PathBuf) -> Result<warp::reply::Response, warp::Rejection> {
+        Ok(match list_datasets(&directory).await {
+            Ok(datasets) => warp::reply::json(&datasets).into_response(),
+            Err(e) => {
+                tracing::error!("could not list datasets: {:#}", e);
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        })
+    }
+
+    warp::path::end()
+        .and_then(move || logic(directory.clone()))
+        .boxed()
+}
+
+fn download(directory: PathBuf) -> BoxedFilter<(warp::reply::Response,)> {
+    // 완성된 데이터셋 파일만 제공 (작성 중인 임시 파일 제외)
+    warp::path::peek()
+        .and_then(|peek: Peek| async move {
+            match dataset_date(peek.as_str()) {
+                Some(_) => Ok(()),
+                None => Err(warp::reject::not_found()),
+            }
+        })
+        .untuple_one()
+        .and(warp::fs::dir(directory))
+        .map(|file: warp::fs::File| {
+            warp::reply::with_header(file, "content-type", "application/gzip").into_response()
+        })
+        .boxed()
+}
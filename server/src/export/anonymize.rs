@@ -0,0 +1,152 @@
+//! 데이터셋 익명화 규칙
+//!
+//! - 모집자/멤버 Content ID → 월별 솔트로 만든 해시 (같은 달 안에서만 같은 값)
+//! - 모집자 이름과 설명 → 제거
+//! - 서버 → 데이터 센터로 일반화
+//! - (데이터 센터, 카테고리, 듀티) 그룹이 k 미만이면 그룹 전체 제외
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, DurationRound, NaiveDate, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::listing::PartyFinderListing;
+use crate::listing_container::ListingContainer;
+
+/// 한 달 동안 유지되는 식별자 해시 솔트
+pub struct PeriodSalt {
+    /// 솔트 기간 (`YYYY-MM`)
+    pub period: String,
+    key: [u8; 32],
+}
+
+impl PeriodSalt {
+    /// 날짜가 속한 달의 솔트 (설정의 비밀값과 연월로 결정되므로 재시작해도 같음)
+    pub fn for_date(secret: &str, date: NaiveDate) -> Self {
+        let period = format!("{:04}-{:02}", date.year(), date.month());
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"rpf-dataset-salt\0");
+        hasher.update(secret.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(period.as_bytes());
+
+        Self {
+            period,
+            key: hasher.finalize().into(),
+        }
+    }
+
+    /// Content ID를 솔트와 함께 해시한 32자리 16진수 값
+    pub fn hash(&self, content_id: u64) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.key);
+        hasher.update(content_id.to_le_bytes());
+
+        hasher.finalize()[..16].iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// k-익명성을 판단하는 준식별자 조합
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GroupKey {
+    pub data_centre: &'static str,
+    pub category: u32,
+    pub duty: u16,
+}
+
+/// 모집글의 그룹 (서버를 알 수 없어 일반화할 수 없으면 `None`)
+pub fn group_key(listing: &PartyFinderListing) -> Option<GroupKey> {
+    Some(GroupKey {
+        data_centre: listing.data_centre_name()?,
+        category: listing.category as u32,
+        duty: listing.duty,
+    })
+}
+
+/// 그룹별 모집글 수 (내보내기 첫 번째 순회에서 집계)
+#[derive(Debug, Default)]
+pub struct GroupCounts(HashMap<GroupKey, usize>);
+
+impl GroupCounts {
+    pub fn add(&mut self, key: GroupKey) {
+        *self.0.entry(key).or_default() += 1;
+    }
+
+    pub fn count(&self, key: &GroupKey) -> usize {
+        self.0.get(key).copied().unwrap_or_default()
+    }
+
+    /// 그룹이 k개 이상이라 내보낼 수 있는지 여부
+    pub fn allows(&self, key: &GroupKey, k: usize) -> bool {
+        self.count(key) >= k
+    }
+}
+
+/// 데이터셋 한 줄
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct AnonymizedListing {
+    /// 데이터셋 날짜 (UTC)
+    pub date: NaiveDate,
+    /// 솔트 기간 (`YYYY-MM`, 같은 기간 안에서만 해시를 비교할 수 있음)
+    pub salt_period: String,
+    /// 생성 시각 (시 단위로 버림)
+    pub created_hour: DateTime<Utc>,
+    pub data_centre: String,
+    pub category: u32,
+    pub duty_type: u8,
+    pub duty: u16,
+    pub beginners_welcome: bool,
+    pub min_item_level: u16,
+    pub num_parties: u8,
+    pub slots_available: u8,
+    pub objective: u32,
+    pub conditions: u32,
+    pub duty_finder_settings: u32,
+    pub loot_rules: u32,
+    pub search_area: u32,
+    pub jobs_present: Vec<u8>,
+    pub upload_count: u32,
+    /// 모집자 해시 (디테일이 업로드되지 않아 모르면 `None`)
+    pub recruiter: Option<String>,
+    /// 멤버 해시 (빈 슬롯 제외)
+    pub members: Vec<String>,
+}
+
+/// 저장된 모집글을 데이터셋 한 줄로 변환 (그룹을 정할 수 없으면 `None`)
+pub fn anonymize(container: &ListingContainer, salt: &PeriodSalt, date: NaiveDate) -> Option<AnonymizedListing> {
+    let listing = &container.listing;
+    let group = group_key(listing)?;
+
+    Some(AnonymizedListing {
+        date,
+        salt_period: salt.period.clone(),
+        created_hour: container
+            .created_at
+            .duration_trunc(TimeDelta::try_hours(1).unwrap())
+            .unwrap_or(container.created_at),
+        data_centre: group.data_centre.to_string(),
+        category: group.category,
+        duty_type: listing.duty_type as u8,
+        duty: group.duty,
+        beginners_welcome: listing.beginners_welcome,
+        min_item_level: listing.min_item_level,
+        num_parties: listing.num_parties,
+        slots_available: listing.slots_available,
+        objective: listing.objective.bits(),
+        conditions: listing.conditions.bits(),
+        duty_finder_settings: listing.duty_finder_settings.bits(),
+        loot_rules: listing.loot_rules.bits(),
+        search_area: listing.search_area.bits(),
+        jobs_present: listing.jobs_present.clone(),
+        upload_count: container.upload_count,
+        recruiter: (listing.leader_content_id != 0).then(|| salt.hash(listing.leader_content_id)),
+        members: listing
+            .member_content_ids
+            .iter()
+            .filter(|&&id| id != 0)
+            .map(|&id| salt.hash(id as u64))
+            .collect(),
+    })
+}
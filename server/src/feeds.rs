@@ -0,0 +1,243 @@
+//! 구독용 피드
+//!
+//! - `GET /feeds/schedule/{datacentre}.ics[?duty=<id>]`: 설명에서 추출한 예정 모집 캘린더
+//! - `GET /feeds/listings.atom[?duty=<id>&dc=<datacentre>&lang=<code>]`: 최근 한 시간 동안 올라온 고난도 모집글
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, FixedOffset, TimeDelta, Utc};
+use serde::Deserialize;
+use warp::filters::BoxedFilter;
+use warp::http::StatusCode;
+use warp::{Filter, Rejection, Reply};
+
+use crate::ffxiv::Language;
+use crate::listing::DutyType;
+use crate::listing_container::QueriedListing;
+use crate::web::State;
+
+pub(crate) mod atom;
+pub(crate) mod ics;
+
+/// Atom 피드에 넣는 모집글의 마지막 업데이트 범위
+pub const LISTINGS_FEED_WINDOW: TimeDelta = TimeDelta::hours(1);
+
+/// 렌더링한 피드를 재사용하는 시간
+pub const FEED_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// 피드 종류와 무관하게 렌더링 결과를 키별로 잠시 보관하는 캐시
+pub struct FeedCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Arc<str>)>>,
+}
+
+impl FeedCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Default::default(),
+        }
+    }
+
+    /// 유효한 캐시가 있으면 반환하고, 없으면 `render`로 만들어 저장
+    pub async fn get_or_render<F, Fut>(&self, key: &str, render: F) -> anyhow::Result<Arc<str>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<String>>,
+    {
+        if let Some((rendered_at, body)) = self.entries.lock().unwrap().get(key) {
+            if rendered_at.elapsed() < self.ttl {
+                return Ok(Arc::clone(body));
+            }
+        }
+
+        let body: Arc<str> = render().await?.into();
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (rendered_at, _)| rendered_at.elapsed() < self.ttl);
+        entries.insert(key.to_string(), (Instant::now(), Arc::clone(&body)));
+
+        Ok(body)
+    }
+}
+
+pub fn feeds(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    warp::path("feeds").and(schedule(Arc::clone(&state)).or(listings(state))).boxed()
+}
+
+#[derive(Deserialize)]
+struct ScheduleQuery {
+    duty: Option<u16>,
+}
+
+fn schedule(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(state: Arc<State>, file: String, query: ScheduleQuery) -> Result<warp::reply::Response, Rejection> {
+        let data_centre = file
+            .strip_suffix(".ics")
+            .and_then(data_centre_name)
+            .ok_or_else(warp::reject::not_found)?;
+
+        let key = match query.duty {
+            Some(duty) => format!("schedule/{}/{}", data_centre, duty),
+            None => format!("schedule/{}", data_centre),
+        };
+        let body = state
+            .feed_cache
+            .get_or_render(&key, || async {
+                let listings = state.current_listings().await?;
+                let now = Utc::now();
+                let events = schedule_events(
+                    &listings,
+                    data_centre,
+                    query.duty,
+                    state.config.web.display_timezone,
+                    now,
+                );
+                Ok(ics::render_calendar(&format!("Party Finder: {}", data_centre), &events, now))
+            })
+            .await;
+
+        let response = match body {
+            Ok(body) => warp::reply::with_header(
+                body.to_string(),
+                "content-type",
+                "text/calendar; charset=utf-8",
+            )
+            .into_response(),
+            Err(e) => {
+                tracing::error!("could not render schedule feed for {}: {:#}", data_centre, e);
+                warp::reply::with_status(warp::reply(), StatusCode::INTERNAL_SERVER_ERROR).into_response()
+            }
+        };
+
+        Ok(response)
+    }
+
+    warp::get()
+        .and(warp::path("schedule"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::query::<ScheduleQuery>())
+        .and_then(move |file: String, query: ScheduleQuery| logic(Arc::clone(&state), file, query))
+        .boxed()
+}
+
+#[derive(Deserialize)]
+struct ListingsFeedQuery {
+    duty: Option<u16>,
+    dc: Option<String>,
+    /// 듀티 이름과 자동 번역 문구 언어 (없거나 지원하지 않으면 영어)
+    lang: Option<String>,
+}
+
+fn listings(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(state: Arc<State>, query: ListingsFeedQuery) -> Result<warp::reply::Response, Rejection> {
+        let data_centre = match &query.dc {
+            Some(name) => Some(data_centre_name(name).ok_or_else(warp::reject::not_found)?),
+            None => None,
+        };
+        let lang = query.lang.as_deref().and_then(Language::from_code).unwrap_or(Language::English);
+
+        let mut name = "listings".to_string();
+        if let Some(data_centre) = data_centre {
+            name.push_str(&format!("/{}", data_centre));
+        }
+        if let Some(duty) = query.duty {
+            name.push_str(&format!("/{}", duty));
+        }
+        let key = format!("{}/{}", name, lang.code());
+        let body = state
+            .feed_cache
+            .get_or_render(&key, || async {
+                let listings = state.current_listings().await?;
+                let now = Utc::now();
+                let entries = feed_entries(&listings, data_centre, query.duty, lang, now);
+                let title = match data_centre {
+                    Some(data_centre) => format!("Party Finder: high-end listings ({})", data_centre),
+                    None => "Party Finder: high-end listings".to_string(),
+                };
+                Ok(atom::render_feed(&atom::feed_tag(&name), &title, &entries, now))
+            })
+            .await;
+
+        let response = match body {
+            Ok(body) => warp::reply::with_header(
+                body.to_string(),
+                "content-type",
+                "application/atom+xml; charset=utf-8",
+            )
+            .into_response(),
+            Err(e) => {
+                tracing::error!("could not render listings feed {}: {:#}", key, e);
+                warp::reply::with_status(warp::reply(), StatusCode::INTERNAL_SERVER_ERROR).into_response()
+            }
+        };
+
+        Ok(response)
+    }
+
+    warp::get()
+        .and(warp::path("listings.atom"))
+        .and(warp::path::end())
+        .and(warp::query::<ListingsFeedQuery>())
+        .and_then(move |query: ListingsFeedQuery| logic(Arc::clone(&state), query))
+        .boxed()
+}
+
+/// 대소문자 구분 없이 데이터 센터 이름을 정규화 (알 수 없으면 `None`)
+fn data_centre_name(name: &str) -> Option<&'static str> {
+    crate::ffxiv::WORLDS
+        .values()
+        .map(|world| world.data_center().name())
+        .find(|dc| dc.eq_ignore_ascii_case(name))
+}
+
+/// 데이터 센터(와 듀티)에 해당하는 앞으로의 예정 모집 (시작 시각 순)
+///
+/// `duty`는 일반 듀티(`DutyType::Normal`) ID입니다. 시각을 추출하지 못했거나 이미 지난 모집글은 제외합니다.
+pub(crate) fn schedule_events(
+    listings: &[QueriedListing],
+    data_centre: &str,
+    duty: Option<u16>,
+    tz: FixedOffset,
+    now: DateTime<Utc>,
+) -> Vec<ics::ScheduleEvent> {
+    let mut events: Vec<_> = listings
+        .iter()
+        .filter(|ql| ql.listing.data_centre_name() == Some(data_centre))
+        .filter(|ql| duty.is_none_or(|duty| ql.listing.duty_type == DutyType::Normal && ql.listing.duty == duty))
+        .filter_map(|ql| {
+            let start = ql.parsed_schedule(tz).filter(|start| *start > now)?;
+            Some(ics::ScheduleEvent::new(ql, start))
+        })
+        .collect();
+
+    events.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.uid.cmp(&b.uid)));
+    events
+}
+
+/// `LISTINGS_FEED_WINDOW` 안에 만들었거나 업데이트된 고난도 모집글 (최근 업데이트 순)
+///
+/// `duty`는 일반 듀티(`DutyType::Normal`) ID, `data_centre`는 모집글을 만든 월드의 데이터 센터입니다.
+pub(crate) fn feed_entries(
+    listings: &[QueriedListing],
+    data_centre: Option<&str>,
+    duty: Option<u16>,
+    lang: Language,
+    now: DateTime<Utc>,
+) -> Vec<atom::AtomEntry> {
+    let since = now - LISTINGS_FEED_WINDOW;
+    let mut matching: Vec<_> = listings
+        .iter()
+        .filter(|ql| ql.updated_at >= since)
+        .filter(|ql| ql.listing.high_end())
+        .filter(|ql| data_centre.is_none_or(|dc| ql.listing.data_centre_name() == Some(dc)))
+        .filter(|ql| duty.is_none_or(|duty| ql.listing.duty_type == DutyType::Normal && ql.listing.duty == duty))
+        .collect();
+
+    matching.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then_with(|| a.listing.id.cmp(&b.listing.id)));
+    matching.into_iter().map(|ql| atom::AtomEntry::new(ql, lang)).collect()
+}
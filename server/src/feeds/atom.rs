@@ -0,0 +1,122 @@
+//! Atom (RFC 4287) 렌더링
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::ffxiv::Language;
+use crate::listing_container::QueriedListing;
+use crate::sestring_ext::SeStringExt;
+
+/// 태그 URI (RFC 4151)의 발급자와 날짜
+const TAG_AUTHORITY: &str = "remote-party-finder,2026";
+
+/// 피드 항목 하나 (모집글)
+pub struct AtomEntry {
+    /// 모집글 ID와 서버 재시작 시각 기반의 고정 태그 URI
+    pub id: String,
+    /// "듀티 (모집자)"
+    pub title: String,
+    /// "모집자 @ 월드"
+    pub author: String,
+    pub updated: DateTime<Utc>,
+    /// 설명과 자리 요약
+    pub content: String,
+}
+
+impl AtomEntry {
+    pub fn new(ql: &QueriedListing, lang: Language) -> Self {
+        let listing = &ql.listing;
+        let description = listing.description.full_text(&lang);
+        let slots = slot_summary(ql);
+        Self {
+            id: listing_tag(listing.id, listing.last_server_restart),
+            title: format!("{} ({})", listing.duty_name(&lang), listing.name.text()),
+            author: format!("{} @ {}", listing.name.text(), listing.created_world_string()),
+            updated: ql.updated_at,
+            content: if description.trim().is_empty() { slots } else { format!("{}\n\n{}", description.trim(), slots) },
+        }
+    }
+}
+
+/// 모집글 항목의 태그 URI
+pub fn listing_tag(id: u32, last_server_restart: u32) -> String {
+    format!("tag:{}:listing/{}/{}", TAG_AUTHORITY, id, last_server_restart)
+}
+
+/// 피드 자체의 태그 URI (`name`은 필터를 포함한 피드 이름)
+pub fn feed_tag(name: &str) -> String {
+    format!("tag:{}:feeds/{}", TAG_AUTHORITY, name)
+}
+
+/// 자리 요약 (예: "Slots: 5/8 (open: 1 tank, 1 healer, 2 DPS)")
+fn slot_summary(ql: &QueriedListing) -> String {
+    let listing = &ql.listing;
+    let open = listing.open_role_slots();
+    let mut summary = format!("Slots: {}/{}", listing.filled_total(), listing.total_capacity());
+    if listing.open_slots() > 0 {
+        summary.push_str(&format!(" (open: {} tank, {} healer, {} DPS)", open.tank, open.healer, open.dps));
+    }
+    summary
+}
+
+/// 항목 목록으로 Atom 피드 문서 생성 (`updated`는 가장 최근 항목, 없으면 `now`)
+pub fn render_feed(id: &str, title: &str, entries: &[AtomEntry], now: DateTime<Utc>) -> String {
+    let updated = entries.iter().map(|entry| entry.updated).max().unwrap_or(now);
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    out.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    element(&mut out, 1, "id", id);
+    element(&mut out, 1, "title", title);
+    element(&mut out, 1, "updated", &format_time(updated));
+    out.push_str("  <author>\n");
+    element(&mut out, 2, "name", "Remote Party Finder");
+    out.push_str("  </author>\n");
+    out.push_str("  <generator>remote-party-finder</generator>\n");
+
+    for entry in entries {
+        out.push_str("  <entry>\n");
+        element(&mut out, 2, "id", &entry.id);
+        element(&mut out, 2, "title", &entry.title);
+        element(&mut out, 2, "updated", &format_time(entry.updated));
+        out.push_str("    <author>\n");
+        element(&mut out, 3, "name", &entry.author);
+        out.push_str("    </author>\n");
+        out.push_str("    <content type=\"text\">");
+        out.push_str(&escape_text(&entry.content));
+        out.push_str("</content>\n");
+        out.push_str("  </entry>\n");
+    }
+
+    out.push_str("</feed>\n");
+    out
+}
+
+/// 들여쓴 단순 텍스트 요소 한 줄
+fn element(out: &mut String, depth: usize, name: &str, text: &str) {
+    out.push_str(&"  ".repeat(depth));
+    out.push_str(&format!("<{}>{}</{}>\n", name, escape_text(text), name));
+}
+
+/// RFC 3339 날짜-시각 (`YYYY-MM-DDTHH:MM:SSZ`)
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// XML 텍스트 이스케이프 (`&`, `<`, `>`, 따옴표, XML 1.0에서 쓸 수 없는 제어 문자는 제거)
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' => escaped.push(c),
+            '\r' => {}
+            c if c.is_control() || matches!(c, '\u{FFFE}' | '\u{FFFF}') => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
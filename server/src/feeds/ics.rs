@@ -0,0 +1,101 @@
+//! iCalendar (RFC 5545) 렌더링
+
+use chrono::{DateTime, Utc};
+
+use crate::ffxiv::Language;
+use crate::listing_container::QueriedListing;
+use crate::sestring_ext::SeStringExt;
+
+/// 한 줄 최대 길이 (옥텟, CRLF 제외)
+const MAX_LINE_OCTETS: usize = 75;
+
+/// 캘린더 이벤트 하나 (예정된 모집)
+pub struct ScheduleEvent {
+    /// 모집글 키 기반의 고정 UID
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    /// "듀티 (모집자)"
+    pub summary: String,
+    pub description: String,
+}
+
+impl ScheduleEvent {
+    pub fn new(ql: &QueriedListing, start: DateTime<Utc>) -> Self {
+        let listing = &ql.listing;
+        Self {
+            uid: format!(
+                "listing-{}-{}-{}@remote-party-finder",
+                listing.id, listing.created_world, listing.last_server_restart,
+            ),
+            start,
+            summary: format!("{} ({})", listing.duty_name(&Language::English), listing.name.text()),
+            description: listing.description.full_text(&Language::English),
+        }
+    }
+}
+
+/// 이벤트 목록으로 VCALENDAR 문서 생성 (CRLF 줄바꿈, 75옥텟 접기)
+pub fn render_calendar(name: &str, events: &[ScheduleEvent], now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    let mut line = |content: String| {
+        fold_line(&content, &mut out);
+    };
+
+    line("BEGIN:VCALENDAR".into());
+    line("VERSION:2.0".into());
+    line("PRODID:-//remote-party-finder//schedule feed//EN".into());
+    line("CALSCALE:GREGORIAN".into());
+    line("METHOD:PUBLISH".into());
+    line(format!("X-WR-CALNAME:{}", escape_text(name)));
+
+    for event in events {
+        line("BEGIN:VEVENT".into());
+        line(format!("UID:{}", escape_text(&event.uid)));
+        line(format!("DTSTAMP:{}", format_utc(now)));
+        line(format!("DTSTART:{}", format_utc(event.start)));
+        line(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if !event.description.is_empty() {
+            line(format!("DESCRIPTION:{}", escape_text(&event.description)));
+        }
+        line("END:VEVENT".into());
+    }
+
+    line("END:VCALENDAR".into());
+    out
+}
+
+/// UTC 날짜-시각 형식 (`YYYYMMDDTHHMMSSZ`)
+fn format_utc(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// TEXT 값 이스케이프 (`\`, `;`, `,`, 줄바꿈)
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 75옥텟을 넘는 줄을 UTF-8 문자 경계에서 접어 `out`에 추가
+fn fold_line(content: &str, out: &mut String) {
+    let mut octets = 0;
+    for c in content.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // 이어지는 줄의 선행 공백도 길이에 포함
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
@@ -1,6 +1,8 @@
 use std::borrow::Cow;
-use std::{cmp::Ordering, str::FromStr};
+use std::sync::OnceLock;
 use serde::Serialize;
+use ffxiv_types::jobs::ClassJob;
+use ffxiv_types::World;
 use crate::listing::{DutyCategory, DutyType};
 
 pub use self::{
@@ -14,9 +16,10 @@ pub mod jobs;
 pub mod roulettes;
 pub mod territory_names;
 pub mod treasure_maps;
+pub mod unknown_ids;
 pub mod worlds;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Language {
     English,
     Japanese,
@@ -24,6 +27,14 @@ pub enum Language {
     French,
 }
 
+/// 지원하는 언어 목록 (첫 번째 항목이 기본 언어)
+pub const SUPPORTED_LANGUAGES: [Language; 4] = [
+    Language::English,
+    Language::Japanese,
+    Language::German,
+    Language::French,
+];
+
 impl Language {
     pub fn code(&self) -> &'static str {
         match self {
@@ -43,38 +54,68 @@ impl Language {
         }
     }
 
+    /// 단일 언어 태그를 지원 언어로 변환 (`ja`, `ja-JP`, `EN-us` 등)
+    ///
+    /// 지역 서브태그는 무시하고 기본 언어 서브태그로 비교합니다.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let primary = code.trim().split('-').next().unwrap_or_default();
+        SUPPORTED_LANGUAGES
+            .into_iter()
+            .find(|lang| lang.code().eq_ignore_ascii_case(primary))
+    }
+
+    /// `Accept-Language` 헤더 협상 (RFC 9110 §12.5.4)
+    ///
+    /// 품질 값(q) 순으로 정렬하여 가장 먼저 지원되는 언어를 고릅니다.
+    /// `q=0`인 언어는 제외되며, `*`는 제외되지 않은 첫 번째 지원 언어와 일치합니다.
+    /// 잘못된 q 값을 가진 항목은 무시합니다.
     pub fn from_codes(val: Option<&str>) -> Self {
         let val = match val {
             Some(v) => v,
             None => return Self::English,
         };
 
-        let mut parts: Vec<(&str, f32)> = val
+        let mut ranges: Vec<(&str, u16)> = val
             .split(',')
-            .map(|part| {
-                let sub_parts: Vec<&str> = part.split(';').collect();
-                if sub_parts.len() == 1 {
-                    (sub_parts[0], 1.0)
-                } else if let Ok(val) = f32::from_str(sub_parts[0]) {
-                    (sub_parts[0], val)
-                } else {
-                    (sub_parts[0], 0.0)
+            .filter_map(|part| {
+                let mut params = part.split(';');
+                let range = params.next()?.trim();
+                if range.is_empty() {
+                    return None;
                 }
+
+                let mut quality = 1000;
+                for param in params {
+                    let (name, value) = param.split_once('=').unwrap_or((param, ""));
+                    if name.trim().eq_ignore_ascii_case("q") {
+                        quality = parse_quality(value.trim())?;
+                    }
+                }
+
+                Some((range, quality))
             })
             .collect();
-        parts.sort_unstable_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Less));
-        if parts.len() == 0 {
-            return Self::English;
-        }
 
-        for (lang, _) in parts {
-            let first = lang.split('-').next().unwrap();
-            match first {
-                "en" => return Self::English,
-                "ja" => return Self::Japanese,
-                "de" => return Self::German,
-                "fr" => return Self::French,
-                _ => {}
+        let rejected: Vec<Language> = ranges
+            .iter()
+            .filter(|(_, quality)| *quality == 0)
+            .filter_map(|(range, _)| Self::from_code(range))
+            .collect();
+
+        // 안정 정렬: 같은 품질이면 헤더 순서 유지
+        ranges.retain(|(_, quality)| *quality > 0);
+        ranges.sort_by_key(|r| std::cmp::Reverse(r.1));
+
+        for (range, _) in ranges {
+            if range == "*" {
+                if let Some(lang) = SUPPORTED_LANGUAGES.into_iter().find(|lang| !rejected.contains(lang)) {
+                    return lang;
+                }
+                continue;
+            }
+
+            if let Some(lang) = Self::from_code(range) {
+                return lang;
             }
         }
 
@@ -82,6 +123,21 @@ impl Language {
     }
 }
 
+/// RFC 9110 qvalue를 천분율 정수로 파싱 (`0` ~ `1.000`)
+fn parse_quality(value: &str) -> Option<u16> {
+    let (int, frac) = value.split_once('.').unwrap_or((value, ""));
+    if frac.len() > 3 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let frac = format!("{:0<3}", frac).parse::<u16>().ok()?;
+    match int {
+        "0" => Some(frac),
+        "1" if frac == 0 => Some(1000),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Copy, Clone, Serialize)]
 pub struct LocalisedText {
     pub en: &'static str,
@@ -107,18 +163,137 @@ pub fn duty(duty: u32) -> Option<&'static duties::DutyInfo> {
         .or_else(|| old::OLD_DUTIES.get(&duty))
 }
 
+/// 듀티 조회 (없으면 미확인 ID로 기록)
+pub fn duty_or_record(id: u32, sample: impl FnOnce() -> String) -> Option<&'static duties::DutyInfo> {
+    let info = duty(id);
+    if info.is_none() {
+        unknown_ids::UNKNOWN_IDS.record(unknown_ids::IdKind::Duty, id, sample);
+    }
+    info
+}
+
+/// 잡 조회 (없으면 미확인 ID로 기록, 0은 빈 슬롯)
+pub fn job_or_record(id: u32, sample: impl FnOnce() -> String) -> Option<ClassJob> {
+    let job = JOBS.get(&id).copied();
+    if job.is_none() && id != 0 {
+        unknown_ids::UNKNOWN_IDS.record(unknown_ids::IdKind::Job, id, sample);
+    }
+    job
+}
+
+/// 월드 조회 (없으면 미확인 ID로 기록)
+pub fn world_or_record(id: u32, sample: impl FnOnce() -> String) -> Option<World> {
+    let world = WORLDS.get(&id).copied();
+    if world.is_none() {
+        unknown_ids::UNKNOWN_IDS.record(unknown_ids::IdKind::World, id, sample);
+    }
+    world
+}
+
+/// 서버 이름으로 서버 ID 조회 (대소문자 무시)
+pub fn world_id_by_name(name: &str) -> Option<u16> {
+    WORLDS
+        .iter()
+        .find(|(_, world)| world.name().eq_ignore_ascii_case(name))
+        .and_then(|(&id, _)| u16::try_from(id).ok())
+}
+
+/// 데이터 센터의 서버 ID (대소문자 무시, 정렬됨, 알 수 없는 데이터 센터는 `None`)
+pub fn data_centre_worlds(name: &str) -> Option<Vec<u16>> {
+    let mut worlds: Vec<u16> = WORLDS
+        .iter()
+        .filter(|(_, world)| world.data_center().name().eq_ignore_ascii_case(name))
+        .filter_map(|(&id, _)| u16::try_from(id).ok())
+        .collect();
+    worlds.sort_unstable();
+    (!worlds.is_empty()).then_some(worlds)
+}
+
+/// 서버 ID로 데이터 센터 이름 조회
+pub fn world_data_centre(world: u16) -> Option<&'static str> {
+    WORLDS.get(&u32::from(world)).map(|w| w.data_center().name())
+}
+
 pub fn roulette(roulette: u32) -> Option<&'static roulettes::RouletteInfo> {
     crate::ffxiv::ROULETTES
         .get(&roulette)
         .or_else(|| old::OLD_ROULETTES.get(&roulette))
 }
 
+/// 듀티 ID 없이 카테고리 자체가 모집 목적을 나타내는 카테고리 (모브 헌트, FATE, 채집 등)
+///
+/// 이 카테고리의 `duty == 0` 모집글은 카테고리 이름으로 표시되고, 통계에서도 카테고리 단위로 묶입니다.
+/// `[display] labeled_categories`가 없을 때의 기본값입니다.
+pub const DEFAULT_LABELED_CATEGORIES: [DutyCategory; 4] = [
+    DutyCategory::Fate,
+    DutyCategory::TheHunt,
+    DutyCategory::GatheringForay,
+    DutyCategory::FieldOperation,
+];
+
+/// 시작할 때 설정에서 읽은 카테고리 (`set_labeled_categories`)
+static LABELED_CATEGORIES: OnceLock<Vec<DutyCategory>> = OnceLock::new();
+
+/// 카테고리 이름으로 표시하는 카테고리 (설정하지 않았으면 `DEFAULT_LABELED_CATEGORIES`)
+pub fn labeled_categories() -> &'static [DutyCategory] {
+    LABELED_CATEGORIES.get().map_or(&DEFAULT_LABELED_CATEGORIES, Vec::as_slice)
+}
+
+/// 설정의 카테고리 이름 해석 (`DutyCategory` 이름, 알 수 없는 이름은 경고 후 무시)
+pub fn parse_labeled_categories(names: &[String]) -> Vec<DutyCategory> {
+    names
+        .iter()
+        .filter_map(|name| {
+            let category = DutyCategory::ALL.into_iter().find(|c| format!("{:?}", c) == *name);
+            if category.is_none() {
+                tracing::warn!("ignoring unknown labeled category `{}`", name);
+            }
+            category
+        })
+        .collect()
+}
+
+/// `[display] labeled_categories` 적용 (통계 쿼리를 만들기 전, 시작할 때 한 번만)
+pub fn set_labeled_categories(names: &[String]) -> anyhow::Result<()> {
+    LABELED_CATEGORIES
+        .set(parse_labeled_categories(names))
+        .map_err(|_| anyhow::anyhow!("labeled categories were already set"))
+}
+
+/// 컴파일된 데이터 테이블 해시 (`/api/version`)
+pub fn data_tables_hash() -> String {
+    crate::version::TableDigest::default()
+        .table("duties", &DUTIES)
+        .table("old_duties", &old::OLD_DUTIES)
+        .table("roulettes", &ROULETTES)
+        .table("old_roulettes", &old::OLD_ROULETTES)
+        .table("jobs", &JOBS)
+        .table("worlds", &WORLDS)
+        .table("territory_names", &TERRITORY_NAMES)
+        .table("treasure_maps", &TREASURE_MAPS)
+        .table("auto_translate", &AUTO_TRANSLATE)
+        .finish()
+}
+
+/// 듀티 대신 표시할 카테고리 이름 (`labeled_categories`에 속하고 `duty == 0`인 경우)
+pub fn category_label(category: DutyCategory, duty: u16) -> Option<LocalisedText> {
+    if duty != 0 || !labeled_categories().contains(&category) {
+        return None;
+    }
+
+    Some(category.pf_category().name())
+}
+
 pub fn duty_name<'a>(
     duty_type: DutyType,
     category: DutyCategory,
     duty: u16,
     lang: Language,
 ) -> Cow<'a, str> {
+    if let Some(label) = category_label(category, duty) {
+        return Cow::from(label.text(&lang));
+    }
+
     match (duty_type, category) {
         (DutyType::Other, DutyCategory::Fate) => {
             if let Some(name) = crate::ffxiv::TERRITORY_NAMES.get(&u32::from(duty)) {
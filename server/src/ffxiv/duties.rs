@@ -6614,7 +6614,7 @@ lazy_static::lazy_static! {
                 de: "Die kuriose Unterstadt von Sil'dih (episch)",
                 fr: "Les canalisations sildiennes annexes - Donjon alternatif (sadique)",
             },
-            high_end: false,
+            high_end: true,
             content_kind: ContentKind::VCDungeonFinder,
         },
         880 => DutyInfo {
@@ -7184,7 +7184,7 @@ lazy_static::lazy_static! {
                 de: "Der kuriose Rokkon (episch)",
                 fr: "Le mont Rokkon annexe - Donjon alternatif (sadique)",
             },
-            high_end: false,
+            high_end: true,
             content_kind: ContentKind::VCDungeonFinder,
         },
         948 => DutyInfo {
@@ -7494,7 +7494,7 @@ lazy_static::lazy_static! {
                 de: "Kurioses Aloalo (episch)",
                 fr: "L'île d'Aloalo annexe - Donjon alternatif (sadique)",
             },
-            high_end: false,
+            high_end: true,
             content_kind: ContentKind::VCDungeonFinder,
         },
         981 => DutyInfo {
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use crate::listing::JobFlags;
 use ffxiv_types::jobs::{Class, ClassJob, Job, NonCombatJob};
+use ffxiv_types::Role;
 
 lazy_static::lazy_static! {
     pub static ref JOBS: HashMap<u32, ClassJob> = maplit::hashmap! {
@@ -82,3 +83,13 @@ lazy_static::lazy_static! {
         ClassJob::Job(Job::Pictomancer).as_str() => JobFlags::PICTOMANCER,
     };
 }
+
+/// 역할에 따른 CSS 클래스 ("tank", "healer", "dps", 역할이 없으면 "")
+pub fn role_class(cj: ClassJob) -> &'static str {
+    match cj.role() {
+        Some(Role::Tank) => "tank",
+        Some(Role::Healer) => "healer",
+        Some(Role::Dps) => "dps",
+        None => "",
+    }
+}
@@ -0,0 +1,90 @@
+//! 컴파일된 게임 데이터에 없는 ID 기록
+//!
+//! 패치 직후 플러그인이 새 듀티/잡/월드 ID를 업로드하면 이름 조회가 조용히 실패합니다.
+//! 조회 실패를 여기에 모아 데이터 갱신이 필요한지 운영자가 알 수 있게 합니다.
+//! 표시할 때마다 조회하므로 횟수 대신 ID를 가진 서로 다른 모집글(플레이어) 수를 셉니다.
+//! 메모리에만 보관하므로 재시작하면 초기화됩니다.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+/// 기록하는 서로 다른 ID의 최대 개수 (잘못된 업로드로 메모리가 늘어나지 않도록)
+const MAX_ENTRIES: usize = 1024;
+/// ID별로 세는 모집글(플레이어)의 최대 개수
+const MAX_SOURCES: usize = 256;
+
+lazy_static::lazy_static! {
+    pub static ref UNKNOWN_IDS: UnknownIds = UnknownIds::default();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdKind {
+    Duty,
+    Job,
+    World,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UnknownId {
+    pub kind: IdKind,
+    pub id: u32,
+    /// 이 ID를 가진 서로 다른 모집글(플레이어) 수 (`MAX_SOURCES`까지)
+    pub count: u64,
+    /// 처음 발견된 모집글 키 (`id/created_world/last_server_restart`)
+    pub sample: String,
+}
+
+#[derive(Debug, Default)]
+struct Seen {
+    sample: String,
+    sources: HashSet<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct UnknownIds {
+    seen: Mutex<HashMap<(IdKind, u32), Seen>>,
+}
+
+impl UnknownIds {
+    /// 조회 실패 기록 (`source`: ID를 가진 모집글 키 등, 같은 출처는 한 번만 셈)
+    pub fn record(&self, kind: IdKind, id: u32, source: impl FnOnce() -> String) {
+        let mut seen = self.seen.lock().unwrap();
+        if let Some(entry) = seen.get_mut(&(kind, id)) {
+            if entry.sources.len() < MAX_SOURCES {
+                entry.sources.insert(source());
+            }
+        } else if seen.len() < MAX_ENTRIES {
+            let source = source();
+            seen.insert((kind, id), Seen { sample: source.clone(), sources: HashSet::from([source]) });
+        }
+    }
+
+    /// 모집글(플레이어) 수 내림차순 목록
+    pub fn snapshot(&self) -> Vec<UnknownId> {
+        let mut entries: Vec<UnknownId> = self.seen
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(&(kind, id), entry)| UnknownId {
+                kind,
+                id,
+                count: entry.sources.len() as u64,
+                sample: entry.sample.clone(),
+            })
+            .collect();
+        entries.sort_by(|a, b| b.count.cmp(&a.count).then((a.kind, a.id).cmp(&(b.kind, b.id))));
+        entries
+    }
+
+    /// 서로 다른 미확인 ID 수
+    pub fn len(&self) -> usize {
+        self.seen.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,149 @@
+//! 모집글 하나의 멤버 Parse 즉시 재조회 (관리자)
+//!
+//! 캐시 만료 / 빈 결과 백오프 / 메모와 무관하게 모집글의 멤버와 파티장을 한 번에 조회하고,
+//! 멤버별로 새 값과 이전 캐시 값을 보고합니다. 시간당 요청 예산은 그대로 따릅니다.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::{get_region_from_server, FFLogsClient, FFLogsEncounter, ParseCacheDoc, QuotaExhausted, ZoneCache};
+use crate::player::Player;
+
+/// 관리자 재조회의 FFLogs 응답 대기 시간 (백그라운드 조회보다 넉넉하게)
+pub const REFRESH_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 재조회 대상 (플레이어 정보가 없으면 조회하지 않고 에러로 보고)
+#[derive(Debug, Clone)]
+pub struct RefreshTarget {
+    pub content_id: u64,
+    pub leader: bool,
+    pub player: Option<Player>,
+}
+
+/// 멤버별 재조회 결과
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MemberRefresh {
+    pub content_id: u64,
+    pub name: Option<String>,
+    pub leader: bool,
+    /// 새로 조회한 percentile (기록이 없거나 조회에 실패하면 `None`)
+    pub fetched_percentile: Option<f32>,
+    /// 재조회 전 캐시의 percentile (`-1`이면 기록 없음으로 캐시됨)
+    pub previous_percentile: Option<f32>,
+    pub previous_fetched_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParseRefreshReport {
+    pub zone_id: u32,
+    pub encounter_id: u32,
+    pub members: Vec<MemberRefresh>,
+}
+
+/// 재조회 결과와 저장할 Zone 캐시 (Content ID별)
+#[derive(Debug)]
+pub struct ParseRefresh {
+    pub report: ParseRefreshReport,
+    pub zone_caches: Vec<(u64, ZoneCache)>,
+}
+
+/// 대상 전원을 한 번의 배치 쿼리로 재조회 (예산이 소진됐으면 조회하지 않음)
+pub async fn refresh_listing_parses(
+    client: &FFLogsClient,
+    targets: Vec<RefreshTarget>,
+    encounter: &FFLogsEncounter,
+    partition: Option<u32>,
+    previous: &HashMap<u64, ParseCacheDoc>,
+    timeout: Duration,
+) -> Result<ParseRefresh, QuotaExhausted> {
+    client.quota().check()?;
+
+    let zone_key = encounter.zone_id.to_string();
+    let encounter_key = encounter.encounter_id.to_string();
+    let lookups: Vec<(String, String, &str)> = targets
+        .iter()
+        .filter_map(|target| target.player.as_ref())
+        .map(|player| {
+            let server = player.home_world_name();
+            (player.name.clone(), server.to_string(), get_region_from_server(&server))
+        })
+        .collect();
+
+    let fetched = match tokio::time::timeout(
+        timeout,
+        client.refresh_zone_rankings(lookups, encounter.zone_id, encounter.difficulty_id, partition),
+    )
+    .await
+    {
+        Ok(Ok(results)) => Ok(results),
+        Ok(Err(e)) => match e.downcast_ref::<QuotaExhausted>() {
+            Some(exhausted) => return Err(*exhausted),
+            None => Err(format!("{:#}", e)),
+        },
+        Err(_) => Err(format!("FFLogs did not respond within {}s", timeout.as_secs())),
+    };
+
+    let now = Utc::now();
+    let mut results = fetched.as_ref().ok().map(|results| results.iter());
+    let mut members = Vec::with_capacity(targets.len());
+    let mut zone_caches = Vec::new();
+
+    for target in targets {
+        let cached = previous
+            .get(&target.content_id)
+            .and_then(|doc| doc.zones.get(&zone_key));
+        let mut member = MemberRefresh {
+            content_id: target.content_id,
+            name: target.player.as_ref().map(|player| player.name.clone()),
+            leader: target.leader,
+            fetched_percentile: None,
+            previous_percentile: cached
+                .and_then(|cache| cache.encounters.get(&encounter_key))
+                .map(|parse| parse.percentile),
+            previous_fetched_at: cached.map(|cache| cache.fetched_at),
+            error: None,
+        };
+
+        if target.player.is_none() {
+            member.error = Some("player is not known yet".to_string());
+        } else {
+            match (&fetched, results.as_mut().and_then(|results| results.next())) {
+                (Ok(_), Some(Err(unrecognized))) => member.error = Some(unrecognized.to_string()),
+                (Ok(_), Some(Ok(encounters))) => {
+                    member.fetched_percentile = encounters
+                        .iter()
+                        .find(|(id, _)| *id == encounter.encounter_id)
+                        .map(|(_, parse)| parse.percentile)
+                        .filter(|&percentile| percentile >= 0.0);
+                    zone_caches.push((
+                        target.content_id,
+                        ZoneCache {
+                            fetched_at: now,
+                            encounters: encounters
+                                .iter()
+                                .map(|(id, parse)| (id.to_string(), parse.clone()))
+                                .collect(),
+                        },
+                    ));
+                }
+                (Err(e), _) => member.error = Some(e.clone()),
+                (Ok(_), None) => member.error = Some("missing from FFLogs response".to_string()),
+            }
+        }
+
+        members.push(member);
+    }
+
+    Ok(ParseRefresh {
+        report: ParseRefreshReport {
+            zone_id: encounter.zone_id,
+            encounter_id: encounter.encounter_id,
+            members,
+        },
+        zone_caches,
+    })
+}
@@ -2,10 +2,13 @@
 //!
 //! ContentID별 Parse 캐시 데이터 구조를 정의합니다.
 
-use chrono::{TimeDelta, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
+use ffxiv_types::Role;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::config::EmptyBackoff;
+
 /// FFLogs Parse 캐시 문서 (ContentID당 1개)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParseCacheDoc {
@@ -14,10 +17,100 @@ pub struct ParseCacheDoc {
     /// Zone별 캐시 데이터 (key: zone_id as string)
     #[serde(default)]
     pub zones: HashMap<String, ZoneCache>,
+    /// 조회 기록 (백그라운드 태스크가 갱신)
+    #[serde(default)]
+    pub fetch: Option<FetchAccounting>,
+}
+
+/// 플레이어별 FFLogs 조회 기록
+///
+/// 랭킹이 없는 캐릭터(새 부캐 등)를 매 주기 조회하지 않도록 연속으로 빈 결과가 나온 횟수를 셉니다.
+/// 한 주기에 여러 Zone을 조회하는 플레이어도 있으므로 빈 결과는 Zone별로 셉니다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FetchAccounting {
+    /// 전체 조회 횟수
+    pub attempt_count: u32,
+    /// 마지막 조회 시각
+    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub last_attempt_at: DateTime<Utc>,
+    /// Zone별 빈 결과 기록 (key: zone_id as string)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub zones: HashMap<String, ZoneAccounting>,
+    /// 마지막 조회에서 FFLogs 캐릭터를 볼 수 없었던 이유 (찾으면 `None`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<ProfileMiss>,
+}
+
+/// Zone별 조회 기록
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ZoneAccounting {
+    /// 이 Zone의 마지막 조회 시각
+    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub last_attempt_at: DateTime<Utc>,
+    /// 연속으로 빈 결과가 나온 횟수 (결과가 있으면 0으로 초기화)
+    pub consecutive_empty: u32,
+}
+
+/// FFLogs에서 캐릭터 기록을 볼 수 없는 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileMiss {
+    /// FFLogs에 없는 캐릭터 (`character`가 `null`)
+    Missing,
+    /// 기록을 비공개로 설정함 (GraphQL "permission" 에러)
+    Hidden,
+}
+
+impl FetchAccounting {
+    /// `zone_id` 조회 결과 반영 (`empty`: 기록된 Parse가 하나도 없음, 다른 Zone의 기록은 그대로 둠)
+    pub fn record(&mut self, zone_id: u32, empty: bool, now: DateTime<Utc>) {
+        self.attempt_count += 1;
+        self.last_attempt_at = now;
+        let zone = self.zones.entry(zone_id.to_string()).or_default();
+        zone.last_attempt_at = now;
+        zone.consecutive_empty = if empty { zone.consecutive_empty + 1 } else { 0 };
+        self.profile = None;
+    }
+
+    /// 캐릭터를 볼 수 없었던 조회 반영 (모든 Zone에 해당)
+    pub fn record_miss(&mut self, miss: ProfileMiss, now: DateTime<Utc>) {
+        self.attempt_count += 1;
+        self.last_attempt_at = now;
+        self.profile = Some(miss);
+    }
+
+    /// `zone_id`의 빈 결과 백오프가 끝나는 시각 (백오프 대상이 아니면 `None`)
+    ///
+    /// 캐릭터를 볼 수 없었으면 연속 횟수와 관계없이 `missing_hours` 뒤에 다시 조회합니다.
+    pub fn empty_backoff_until(&self, zone_id: u32, policy: &EmptyBackoff) -> Option<DateTime<Utc>> {
+        if self.profile.is_some() {
+            return Some(self.last_attempt_at + TimeDelta::try_hours(i64::from(policy.missing_hours)).unwrap());
+        }
+
+        let zone = self.zones.get(&zone_id.to_string())?;
+        let hours = if zone.consecutive_empty >= policy.weekly_after {
+            policy.weekly_hours
+        } else if zone.consecutive_empty >= policy.daily_after {
+            policy.daily_hours
+        } else {
+            return None;
+        };
+        Some(zone.last_attempt_at + TimeDelta::try_hours(i64::from(hours)).unwrap())
+    }
+
+    /// 빈 결과 백오프 중이라 이번 주기에 `zone_id`를 조회하지 않아야 하는지
+    pub fn in_empty_backoff(&self, zone_id: u32, policy: &EmptyBackoff, now: DateTime<Utc>) -> bool {
+        self.empty_backoff_until(zone_id, policy).is_some_and(|until| now < until)
+    }
+}
+
+/// Zone 조회 결과에 기록된 Parse가 하나도 없는지 (`percentile < 0`은 로그 없음)
+pub fn is_empty_result(encounters: &[(u32, f32)]) -> bool {
+    encounters.iter().all(|&(_, percentile)| percentile < 0.0)
 }
 
 /// Zone별 캐시 데이터
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ZoneCache {
     /// 이 Zone의 조회 시각
     #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
@@ -28,17 +121,172 @@ pub struct ZoneCache {
 }
 
 /// Encounter별 파싱 데이터
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EncounterParse {
     /// Best Percentile (0-100, -1이면 로그 없음)
     pub percentile: f32,
-    /// 직업 ID (0이면 Best Job)
+    /// 기록을 낸 잡 ID (FFLogs spec, 알 수 없으면 0)
     #[serde(default)]
     pub job_id: u8,
+    /// All Stars 점수 / 순위 (해당 encounter에 All Stars 기록이 없으면 `None`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub all_stars: Option<AllStars>,
+    /// 잡별 Best Percentile (key: 잡 ID as string, 역할별 랭킹에서 채움)
+    ///
+    /// 역할별 랭킹이 없던 예전 문서에는 없으며, 그때는 `percentile`만 사용합니다.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub jobs: HashMap<String, f32>,
+}
+
+/// 현재 잡에 맞춰 고른 기록
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JobParse {
+    pub percentile: f32,
+    /// 기록을 낸 잡 ID (알 수 없으면 0)
+    pub job_id: u8,
+}
+
+impl EncounterParse {
+    /// 현재 잡에 맞는 기록 (같은 잡 → 같은 역할 중 가장 높은 기록 → 최고 기록 순)
+    pub fn for_job(&self, current_job: u8) -> JobParse {
+        let best = JobParse { percentile: self.percentile, job_id: self.job_id };
+        if current_job == self.job_id {
+            return best;
+        }
+        if let Some(&percentile) = self.jobs.get(&current_job.to_string()) {
+            return JobParse { percentile, job_id: current_job };
+        }
+
+        let Some(current_role) = job_role(current_job) else {
+            return best;
+        };
+        if job_role(self.job_id) == Some(current_role) {
+            return best;
+        }
+        self.jobs
+            .iter()
+            .filter_map(|(job, &percentile)| Some(JobParse { percentile, job_id: job.parse().ok()? }))
+            .filter(|parse| job_role(parse.job_id) == Some(current_role))
+            .max_by(|a, b| a.percentile.total_cmp(&b.percentile))
+            .unwrap_or(best)
+    }
+
+    /// 현재 잡에 맞춰 고른 기록의 잡과 현재 잡의 역할이 다른지 (어느 쪽이든 역할을 알 수 없으면 `false`)
+    pub fn role_mismatch(&self, current_job: u8) -> bool {
+        match (job_role(self.for_job(current_job).job_id), job_role(current_job)) {
+            (Some(parsed), Some(current)) => parsed != current,
+            _ => false,
+        }
+    }
+}
+
+fn job_role(job: u8) -> Option<Role> {
+    crate::ffxiv::JOBS.get(&u32::from(job)).and_then(|cj| cj.role())
+}
+
+/// Encounter별 All Stars 점수와 순위
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AllStars {
+    pub points: f32,
+    /// 전체 순위 (1부터)
+    pub rank: u32,
+}
+
+/// 기본 Zone 캐시 갱신 주기 (시간)
+pub const DEFAULT_CACHE_HOURS: u32 = 24;
+
+/// Zone별 캐시 갱신 주기 (`[fflogs] cache_hours` + `zone_cache_hours` 재정의)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheExpiry {
+    default: TimeDelta,
+    zones: HashMap<u32, TimeDelta>,
+}
+
+impl Default for CacheExpiry {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_HOURS, HashMap::new())
+    }
+}
+
+impl CacheExpiry {
+    pub fn new(default_hours: u32, zone_hours: HashMap<u32, u32>) -> Self {
+        let hours = |hours: u32| TimeDelta::hours(i64::from(hours));
+        Self {
+            default: hours(default_hours),
+            zones: zone_hours.into_iter().map(|(zone_id, zone_hours)| (zone_id, hours(zone_hours))).collect(),
+        }
+    }
+
+    /// 설정의 갱신 주기 (`[fflogs]`가 없으면 기본값)
+    pub fn from_config(config: Option<&crate::config::FFLogs>) -> Self {
+        match config {
+            Some(config) => Self::new(config.cache_hours, config.cache_hour_overrides()),
+            None => Self::default(),
+        }
+    }
+
+    /// Zone의 갱신 주기 (재정의가 없으면 기본값)
+    pub fn for_zone(&self, zone_id: u32) -> TimeDelta {
+        self.zones.get(&zone_id).copied().unwrap_or(self.default)
+    }
+}
+
+/// Zone 캐시가 만료되었는지 확인 (`max_age`: `CacheExpiry::for_zone`)
+pub fn is_zone_cache_expired(zone_cache: &ZoneCache, max_age: TimeDelta, now: DateTime<Utc>) -> bool {
+    zone_cache.fetched_at < now - max_age
+}
+
+/// Zone 단위 병합 결과
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ZoneMergeOutcome {
+    /// 저장해야 하는 Zone 캐시 (key: zone_id as string)
+    pub zones_to_write: HashMap<String, ZoneCache>,
+    /// 양쪽 모두에 존재한 Zone 수
+    pub conflicts: usize,
+    /// 충돌 중 기존 데이터가 더 최신이라 유지된 Zone 수
+    pub kept_existing: usize,
+}
+
+/// 가져온 문서를 기존 문서에 Zone 단위로 병합
+///
+/// 같은 Zone이 양쪽에 있으면 `fetched_at`이 더 최신인 쪽을 유지합니다.
+/// 기존 문서에 없는 Zone은 그대로 추가됩니다.
+pub fn merge_zone_caches(existing: Option<&ParseCacheDoc>, incoming: &ParseCacheDoc) -> ZoneMergeOutcome {
+    let mut outcome = ZoneMergeOutcome::default();
+
+    for (zone_key, incoming_zone) in &incoming.zones {
+        match existing.and_then(|doc| doc.zones.get(zone_key)) {
+            Some(existing_zone) => {
+                outcome.conflicts += 1;
+                if incoming_zone.fetched_at > existing_zone.fetched_at {
+                    outcome.zones_to_write.insert(zone_key.clone(), incoming_zone.clone());
+                } else {
+                    outcome.kept_existing += 1;
+                }
+            }
+            None => {
+                outcome.zones_to_write.insert(zone_key.clone(), incoming_zone.clone());
+            }
+        }
+    }
+
+    outcome
+}
+
+/// 중복 플레이어의 Parse 문서를 대상 ContentID로 옮길 때 대상 문서에 기록할 내용
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ParseRepoint {
+    /// `merge_zone_caches`와 같은 규칙으로 고른 Zone 캐시
+    pub zones_to_write: HashMap<String, ZoneCache>,
+    /// 대상 문서에 조회 기록이 없을 때만 옮기는 중복 문서의 조회 기록
+    pub fetch: Option<FetchAccounting>,
 }
 
-/// Zone 캐시가 만료되었는지 확인 (갱신 기준: 24시간)
-pub fn is_zone_cache_expired(zone_cache: &ZoneCache) -> bool {
-    let expire_threshold = Utc::now() - TimeDelta::try_hours(24).unwrap();
-    zone_cache.fetched_at < expire_threshold
+/// 중복 문서의 Zone 캐시와 조회 기록 중 대상 문서로 옮길 것
+pub fn repoint_parse_doc(target: Option<&ParseCacheDoc>, duplicate: &ParseCacheDoc) -> ParseRepoint {
+    let target_has_fetch = target.is_some_and(|doc| doc.fetch.is_some());
+    ParseRepoint {
+        zones_to_write: merge_zone_caches(target, duplicate).zones_to_write,
+        fetch: duplicate.fetch.clone().filter(|_| !target_has_fetch),
+    }
 }
@@ -5,10 +5,18 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tokio::sync::RwLock;
-
-use crate::config::FFLogs as FFLogsConfig;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, RwLock};
+
+use crate::config::{FFLogs as FFLogsConfig, RetryPolicy};
+use crate::fflogs::cache::ProfileMiss;
+use crate::fflogs::parse_response::{parse_character, ParsedRankings, PlayerRankings, RankingsUnavailable, UnrecognizedShape, ZoneParses, ROLE_RANKINGS};
+use crate::fflogs::points::{PointsBudget, PointsUsage, RATE_LIMIT_QUERY};
+use crate::fflogs::quota::RequestQuota;
 
 /// FFLogs API 토큰 엔드포인트
 const OAUTH_TOKEN_URL: &str = "https://www.fflogs.com/oauth/token";
@@ -20,6 +28,18 @@ pub struct FFLogsClient {
     config: FFLogsConfig,
     http: reqwest::Client,
     token: Arc<RwLock<Option<AccessToken>>>,
+    oauth_token_url: String,
+    graphql_url: String,
+    /// 동일 캐릭터 조회 합치기 / 최근 결과 메모
+    coalescer: Mutex<Coalescer>,
+    /// 메모 캐시로 HTTP 요청을 생략한 횟수
+    memo_hits: AtomicU64,
+    /// 진행 중인 동일 조회를 기다려 결과를 공유한 횟수
+    coalesced_waits: AtomicU64,
+    /// 시간당 요청 예산
+    quota: RequestQuota,
+    /// FFLogs가 알려준 API 포인트 사용량
+    points: PointsBudget,
 }
 
 /// OAuth2 Access Token
@@ -49,6 +69,45 @@ struct GraphQLError {
     message: String,
 }
 
+/// 재시도할 수 있는 FFLogs 오류 (429 / 5xx / 연결 실패)
+#[derive(Debug)]
+pub struct TransientError {
+    /// 응답 상태 (연결 실패면 `None`)
+    pub status: Option<reqwest::StatusCode>,
+    pub retry_after: Option<Duration>,
+    pub message: String,
+}
+
+impl fmt::Display for TransientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.status {
+            Some(status) => write!(f, "FFLogs API error: {} - {}", status, self.message),
+            None => write!(f, "FFLogs request failed: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for TransientError {}
+
+/// `attempt`번째(1부터) 요청이 일시적으로 실패한 뒤 기다릴 시간 (포기하면 `None`)
+///
+/// `Retry-After`가 있으면 그 값을, 없으면 `base_delay_ms`부터 두 배씩 늘린 값을 씁니다.
+pub fn retry_delay(policy: &RetryPolicy, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+    if attempt >= policy.attempts {
+        return None;
+    }
+
+    let max_delay = Duration::from_secs(policy.max_delay_secs);
+    match retry_after {
+        Some(retry_after) if retry_after > max_delay => None,
+        Some(retry_after) => Some(retry_after),
+        None => {
+            let backoff = policy.base_delay_ms.saturating_mul(1 << (attempt - 1).min(16));
+            Some(Duration::from_millis(backoff).min(max_delay))
+        }
+    }
+}
+
 /// 캐릭터 조회 응답
 #[derive(Debug, Deserialize)]
 struct CharacterData {
@@ -89,10 +148,22 @@ pub struct ParseInfo {
 impl FFLogsClient {
     /// 새 FFLogs 클라이언트 생성
     pub fn new(config: FFLogsConfig) -> Self {
+        Self::with_endpoints(config, OAUTH_TOKEN_URL, GRAPHQL_URL)
+    }
+
+    /// 엔드포인트를 지정하여 클라이언트 생성 (테스트용 모의 서버 등)
+    pub fn with_endpoints(config: FFLogsConfig, oauth_token_url: &str, graphql_url: &str) -> Self {
         Self {
+            quota: RequestQuota::new(config.requests_per_hour),
+            points: PointsBudget::default(),
             config,
             http: reqwest::Client::new(),
             token: Arc::new(RwLock::new(None)),
+            oauth_token_url: oauth_token_url.to_string(),
+            graphql_url: graphql_url.to_string(),
+            coalescer: Mutex::new(Coalescer::default()),
+            memo_hits: AtomicU64::new(0),
+            coalesced_waits: AtomicU64::new(0),
         }
     }
 
@@ -112,7 +183,7 @@ impl FFLogsClient {
         // 새 토큰 요청
         let response = self
             .http
-            .post(OAUTH_TOKEN_URL)
+            .post(&self.oauth_token_url)
             .basic_auth(&self.config.client_id, Some(&self.config.client_secret))
             .form(&[("grant_type", "client_credentials")])
             .send()
@@ -140,31 +211,78 @@ impl FFLogsClient {
         Ok(token_response.access_token)
     }
 
+    /// GraphQL 요청 전송 (일시적인 오류는 `retry` 정책에 따라 재시도)
+    ///
+    /// 재시도도 요청 예산을 씁니다. 429 / 5xx 외의 상태 코드는 다시 보내도 같으므로 바로 실패합니다.
+    async fn post_graphql(&self, body: &serde_json::Value) -> anyhow::Result<reqwest::Response> {
+        let mut attempt = 1;
+        loop {
+            let error = match self.send_graphql(body).await {
+                Ok(response) => return Ok(response),
+                Err(e) => e,
+            };
+            let Some(transient) = error.downcast_ref::<TransientError>() else {
+                return Err(error);
+            };
+            let Some(delay) = retry_delay(&self.config.retry, attempt, transient.retry_after) else {
+                return Err(error);
+            };
+
+            tracing::warn!(
+                "[FFLogs] {} (attempt {}/{}), retrying in {:?}",
+                transient,
+                attempt,
+                self.config.retry.attempts,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// GraphQL 요청 한 번 전송 (실패한 상태 코드는 에러)
+    async fn send_graphql(&self, body: &serde_json::Value) -> anyhow::Result<reqwest::Response> {
+        self.quota.try_acquire()?;
+        let token = self.get_token().await?;
+
+        let response = match self.http.post(&self.graphql_url).bearer_auth(&token).json(body).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return Err(TransientError { status: None, retry_after: None, message: e.to_string() }.into());
+            }
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+        let body = response.text().await.unwrap_or_default();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            return Err(TransientError { status: Some(status), retry_after, message: body }.into());
+        }
+        anyhow::bail!("FFLogs API error: {} - {}", status, body);
+    }
+
     /// GraphQL 쿼리 실행
     async fn query<T: for<'de> Deserialize<'de>>(
         &self,
         query: &str,
         variables: serde_json::Value,
     ) -> anyhow::Result<T> {
-        let token = self.get_token().await?;
-
         let response = self
-            .http
-            .post(GRAPHQL_URL)
-            .bearer_auth(&token)
-            .json(&serde_json::json!({
+            .post_graphql(&serde_json::json!({
                 "query": query,
                 "variables": variables
             }))
-            .send()
             .await?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("FFLogs API error: {} - {}", status, body);
-        }
-
         let result: GraphQLResponse<T> = response.json().await?;
 
         if let Some(errors) = result.errors {
@@ -300,8 +418,9 @@ impl FFLogsClient {
 
     /// 여러 캐릭터의 Encounter Parse를 한 번에 조회 (배치 쿼리)
     /// 
-    /// GraphQL alias를 사용하여 한 번의 API 호출로 여러 캐릭터를 조회합니다.
-    /// 최대 20명까지 한 번에 조회 가능합니다.
+    /// Zone 전체 조회(`get_batch_zone_all_parses`) 결과에서 해당 encounter만 추출합니다.
+    /// 따라서 동일 캐릭터/Zone 조회는 합쳐지고 메모 캐시를 공유합니다.
+    /// 응답 형태를 알 수 없는 플레이어는 결과에서 빠집니다.
     /// 
     /// # Returns
     /// Vec<(player_index, Option<f32>)> - 각 플레이어의 인덱스와 파싱 결과
@@ -313,115 +432,228 @@ impl FFLogsClient {
         difficulty_id: Option<u32>,
         partition: Option<u32>,
     ) -> anyhow::Result<Vec<(usize, Option<f32>)>> {
-        if players.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        // 동적 GraphQL 쿼리 생성
-        let mut query_parts = Vec::new();
-        for (i, (name, server, region)) in players.iter().enumerate() {
-            let alias = format!("char{}", i);
-            let server_lower = server.to_lowercase();
-            
-            // difficulty와 partition을 조건부로 추가
-            let difficulty_arg = difficulty_id.map(|d| format!(", difficulty: {}", d)).unwrap_or_default();
-            let partition_arg = partition.map(|p| format!(", partition: {}", p)).unwrap_or_default();
-            
-            query_parts.push(format!(
-                r#"{}: character(name: "{}", serverSlug: "{}", serverRegion: "{}") {{
-                    zoneRankings(zoneID: {}{}{}, metric: rdps, timeframe: Historical)
-                }}"#,
-                alias, name, server_lower, region, zone_id, difficulty_arg, partition_arg
-            ));
-        }
-
-        let query = format!(
-            r#"query {{ characterData {{ {} }} }}"#,
-            query_parts.join("\n")
-        );
+        let results = self
+            .get_batch_zone_all_parses(players, zone_id, difficulty_id, partition)
+            .await?;
 
-        let token = self.get_token().await?;
+        Ok(results
+            .into_iter()
+            .map(|(i, encounters)| {
+                let percentile = encounters
+                    .iter()
+                    .find(|(id, _)| *id == encounter_id)
+                    .map(|(_, percentile)| *percentile);
+                (i, percentile)
+            })
+            .collect())
+    }
 
-        let response = self
-            .http
-            .post(GRAPHQL_URL)
-            .bearer_auth(&token)
-            .json(&serde_json::json!({
-                "query": query
-            }))
-            .send()
+    /// 여러 캐릭터의 Zone 내 모든 Encounter Parse를 한 번에 조회 (배치 쿼리)
+    /// 
+    /// `get_batch_zone_rankings`에서 percentile만 추출합니다. 응답 형태를 알 수 없는 플레이어는 결과에서 빠집니다.
+    /// 
+    /// # Returns
+    /// Vec<(player_index, Vec<(encounter_id, percentile)>)> - 각 플레이어의 모든 encounter 결과
+    pub async fn get_batch_zone_all_parses(
+        &self,
+        players: Vec<(String, String, &str)>, // (name, server, region)
+        zone_id: u32,
+        difficulty_id: Option<u32>,
+        partition: Option<u32>,
+    ) -> anyhow::Result<Vec<(usize, Vec<(u32, f32)>)>> {
+        let results = self
+            .get_batch_zone_rankings(players, zone_id, difficulty_id, partition)
             .await?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("FFLogs API error: {} - {}", status, body);
+        Ok(results
+            .into_iter()
+            .filter_map(|(i, encounters)| {
+                let percentiles = encounters
+                    .ok()?
+                    .into_iter()
+                    .map(|(id, parse)| (id, parse.percentile))
+                    .collect();
+                Some((i, percentiles))
+            })
+            .collect())
+    }
+
+    /// 여러 캐릭터의 Zone 내 모든 Encounter 결과를 한 번에 조회 (배치 쿼리)
+    /// 
+    /// GraphQL alias를 사용하여 한 번의 API 호출로 여러 캐릭터를 조회합니다.
+    /// Zone 내 모든 encounter의 percentile과 All Stars 점수 / 순위, 역할별 랭킹에서 얻은 잡별 percentile을 반환합니다.
+    /// 
+    /// 같은 (캐릭터, Zone, 난이도, 파티션) 조회가 동시에 진행 중이면 그 결과를 기다려 공유하고,
+    /// 최근 60초 이내에 조회된 캐릭터는 쿼리에서 제외합니다. 랭킹을 얻지 못한 결과는 메모하지 않습니다.
+    /// 
+    /// # Returns
+    /// Vec<(player_index, PlayerRankings)> - 각 플레이어의 모든 encounter 결과 (형태를 알 수 없거나 캐릭터를 볼 수 없으면 에러)
+    pub async fn get_batch_zone_rankings(
+        &self,
+        players: Vec<(String, String, &str)>, // (name, server, region)
+        zone_id: u32,
+        difficulty_id: Option<u32>,
+        partition: Option<u32>,
+    ) -> anyhow::Result<Vec<(usize, PlayerRankings)>> {
+        if players.is_empty() {
+            return Ok(Vec::new());
         }
 
-        let result: serde_json::Value = response.json().await?;
+        let mut results: Vec<Option<PlayerRankings>> = vec![None; players.len()];
+        let mut waiters = Vec::new();
+        let mut leading = Vec::new();
+        let mut senders = HashMap::new();
 
-        // 결과 파싱
-        let mut results = Vec::new();
-        
-        if let Some(errors) = result.get("errors") {
-            if errors.as_array().map(|a| !a.is_empty()).unwrap_or(false) {
-                // 에러가 있어도 부분 결과는 처리 가능
-                // eprintln!("[FFLogs] Batch query had errors: {:?}", errors);
+        // 1. 메모 / 진행 중인 조회 확인 후 직접 조회할 플레이어 결정
+        {
+            let mut coalescer = self.coalescer.lock().unwrap();
+            for (i, (name, server, _)) in players.iter().enumerate() {
+                let key = LookupKey::new(name, server, zone_id, difficulty_id, partition);
+
+                if let Some(parses) = coalescer.memo_get(&key) {
+                    self.memo_hits.fetch_add(1, Ordering::Relaxed);
+                    results[i] = Some(Ok(parses));
+                    continue;
+                }
+
+                if let Some(rx) = coalescer.in_flight_get(&key) {
+                    self.coalesced_waits.fetch_add(1, Ordering::Relaxed);
+                    waiters.push((i, rx));
+                    continue;
+                }
+
+                let (tx, rx) = watch::channel(None);
+                coalescer.in_flight.insert(key.clone(), rx);
+                senders.insert(key.clone(), tx);
+                leading.push((i, key));
             }
         }
 
-        if let Some(data) = result.get("data").and_then(|d| d.get("characterData")) {
-            for (i, _) in players.iter().enumerate() {
-                let alias = format!("char{}", i);
-                
-                let percentile = data
-                    .get(&alias)
-                    .and_then(|char| char.get("zoneRankings"))
-                    .and_then(|zr| zr.get("rankings"))
-                    .and_then(|rankings| rankings.as_array())
-                    .and_then(|arr| {
-                        for item in arr {
-                            if let Some(enc) = item.get("encounter") {
-                                if let Some(id) = enc.get("id").and_then(|v| v.as_u64()) {
-                                    if id as u32 == encounter_id {
-                                        return item.get("rankPercent").and_then(|v| v.as_f64()).map(|p| p as f32);
-                                    }
-                                }
-                            }
+        // 2. 남은 플레이어만 실제로 조회하고 대기 중인 호출자에게 결과 전달
+        if !leading.is_empty() {
+            let batch: Vec<(String, String, &str)> = leading.iter()
+                .map(|(i, _)| players[*i].clone())
+                .collect();
+            let outcome = self
+                .fetch_batch_zone_rankings(&batch, zone_id, difficulty_id, partition)
+                .await;
+
+            {
+                let mut coalescer = self.coalescer.lock().unwrap();
+                for (pos, (i, key)) in leading.iter().enumerate() {
+                    coalescer.in_flight.remove(key);
+                    if let Ok(all) = &outcome {
+                        if let Ok(parses) = &all[pos] {
+                            coalescer.memo_insert(key.clone(), parses.clone());
                         }
-                        None
-                    });
-                
-                results.push((i, percentile));
+                        results[*i] = Some(all[pos].clone());
+                    }
+                }
             }
-        } else {
-            // No data at all
-            for i in 0..players.len() {
-                results.push((i, None));
+
+            for (pos, (_, key)) in leading.iter().enumerate() {
+                let shared = match &outcome {
+                    Ok(all) => Ok(all[pos].clone()),
+                    Err(e) => Err(e.to_string()),
+                };
+                if let Some(tx) = senders.remove(key) {
+                    let _ = tx.send(Some(shared));
+                }
             }
+
+            outcome?;
         }
 
-        Ok(results)
+        // 3. 다른 호출자가 진행 중이던 조회 결과 대기
+        for (i, mut rx) in waiters {
+            let shared = loop {
+                if let Some(shared) = rx.borrow().clone() {
+                    break shared;
+                }
+                if rx.changed().await.is_err() {
+                    break Err("coalesced FFLogs lookup was abandoned".to_string());
+                }
+            };
+            results[i] = Some(shared.map_err(|e| anyhow::anyhow!(e))?);
+        }
+
+        Ok(results
+            .into_iter()
+            .enumerate()
+            .map(|(i, parses)| (i, parses.unwrap_or(Ok(Vec::new()))))
+            .collect())
     }
 
-    /// 여러 캐릭터의 Zone 내 모든 Encounter Parse를 한 번에 조회 (배치 쿼리)
-    /// 
-    /// GraphQL alias를 사용하여 한 번의 API 호출로 여러 캐릭터를 조회합니다.
-    /// Zone 내 모든 encounter의 rankings를 반환합니다.
-    /// 
-    /// # Returns
-    /// Vec<(player_index, Vec<(encounter_id, percentile)>)> - 각 플레이어의 모든 encounter 결과
-    pub async fn get_batch_zone_all_parses(
+    /// 요청 합치기 통계 (메모 히트 수, 진행 중 조회 대기 수)
+    pub fn coalescing_stats(&self) -> (u64, u64) {
+        (
+            self.memo_hits.load(Ordering::Relaxed),
+            self.coalesced_waits.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Zone의 메모된 결과 삭제 (캐시 무효화 직후 메모가 재조회를 가로채지 않도록)
+    pub fn forget_zone(&self, zone_id: u32) {
+        self.coalescer.lock().unwrap().memo.retain(|key, _| key.zone_id != zone_id);
+    }
+
+    /// 시간당 요청 예산
+    pub fn quota(&self) -> &RequestQuota {
+        &self.quota
+    }
+
+    /// 마지막으로 관측한 FFLogs API 포인트 사용량
+    pub fn points(&self) -> &PointsBudget {
+        &self.points
+    }
+
+    /// 남은 API 포인트 (아직 배치 쿼리 응답을 받지 못했으면 `None`)
+    pub fn remaining_budget(&self) -> Option<f64> {
+        self.points.remaining(Utc::now())
+    }
+
+    /// 메모 / 진행 중인 조회와 무관하게 바로 조회 (관리자 재조회)
+    ///
+    /// 결과는 메모에 반영해 직후의 일반 조회도 새 값을 쓰게 합니다. 반환 값은 `players`와 같은 순서입니다.
+    pub async fn refresh_zone_rankings(
         &self,
         players: Vec<(String, String, &str)>, // (name, server, region)
         zone_id: u32,
         difficulty_id: Option<u32>,
         partition: Option<u32>,
-    ) -> anyhow::Result<Vec<(usize, Vec<(u32, f32)>)>> {
+    ) -> anyhow::Result<Vec<PlayerRankings>> {
         if players.is_empty() {
             return Ok(Vec::new());
         }
 
+        let results = self
+            .fetch_batch_zone_rankings(&players, zone_id, difficulty_id, partition)
+            .await?;
+
+        let mut coalescer = self.coalescer.lock().unwrap();
+        for ((name, server, _), parses) in players.iter().zip(&results) {
+            if let Ok(parses) = parses {
+                let key = LookupKey::new(name, server, zone_id, difficulty_id, partition);
+                coalescer.memo_insert(key, parses.clone());
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Zone Rankings 배치 조회 (실제 HTTP 요청)
+    ///
+    /// 반환 값은 `players`와 같은 순서입니다. `characterData`가 없으면 GraphQL 에러로 실패하고,
+    /// 에러도 없으면 모든 플레이어를 형태를 알 수 없는 응답으로 봅니다.
+    /// 429 / 5xx는 `post_graphql`이 재시도하고, 특정 캐릭터만 가리키는 에러는 그 캐릭터를 볼 수 없다는 뜻입니다
+    /// (`character_errors`).
+    async fn fetch_batch_zone_rankings(
+        &self,
+        players: &[(String, String, &str)], // (name, server, region)
+        zone_id: u32,
+        difficulty_id: Option<u32>,
+        partition: Option<u32>,
+    ) -> anyhow::Result<Vec<PlayerRankings>> {
         // 동적 GraphQL 쿼리 생성
         let mut query_parts = Vec::new();
         for (i, (name, server, region)) in players.iter().enumerate() {
@@ -431,86 +663,191 @@ impl FFLogsClient {
             let difficulty_arg = difficulty_id.map(|d| format!(", difficulty: {}", d)).unwrap_or_default();
             let partition_arg = partition.map(|p| format!(", partition: {}", p)).unwrap_or_default();
             
+            let role_rankings: String = ROLE_RANKINGS
+                .iter()
+                .map(|(role_alias, role)| {
+                    format!(
+                        "\n                    {}: zoneRankings(zoneID: {}{}{}, metric: rdps, timeframe: Historical, role: {})",
+                        role_alias, zone_id, difficulty_arg, partition_arg, role
+                    )
+                })
+                .collect();
+
             query_parts.push(format!(
                 r#"{}: character(name: "{}", serverSlug: "{}", serverRegion: "{}") {{
-                    zoneRankings(zoneID: {}{}{}, metric: rdps, timeframe: Historical)
+                    zoneRankings(zoneID: {}{}{}, metric: rdps, timeframe: Historical){}
                 }}"#,
-                alias, name, server_lower, region, zone_id, difficulty_arg, partition_arg
+                alias, name, server_lower, region, zone_id, difficulty_arg, partition_arg, role_rankings
             ));
         }
 
         let query = format!(
-            r#"query {{ characterData {{ {} }} }}"#,
-            query_parts.join("\n")
+            r#"query {{ characterData {{ {} }} {} }}"#,
+            query_parts.join("\n"),
+            RATE_LIMIT_QUERY
         );
 
-        let token = self.get_token().await?;
-
         let response = self
-            .http
-            .post(GRAPHQL_URL)
-            .bearer_auth(&token)
-            .json(&serde_json::json!({
+            .post_graphql(&serde_json::json!({
                 "query": query
             }))
-            .send()
             .await?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("FFLogs API error: {} - {}", status, body);
-        }
-
         let result: serde_json::Value = response.json().await?;
 
-        // 결과 파싱 - Zone 내 모든 encounter 추출
-        let mut results = Vec::new();
-        
-        if let Some(errors) = result.get("errors") {
-            if errors.as_array().map(|a| !a.is_empty()).unwrap_or(false) {
-                // 에러가 있어도 부분 결과는 처리 가능
+        if let Some(usage) = PointsUsage::from_response(&result, Utc::now()) {
+            if self.points.record(usage) {
+                tracing::warn!(
+                    "[FFLogs] {:.0} of {:.0} API points used this hour (resets in {}s)",
+                    usage.spent_this_hour,
+                    usage.limit_per_hour,
+                    usage.reset_in_secs
+                );
             }
         }
 
-        if let Some(data) = result.get("data").and_then(|d| d.get("characterData")) {
-            for (i, _) in players.iter().enumerate() {
-                let alias = format!("char{}", i);
-                
-                let encounters: Vec<(u32, f32)> = data
-                    .get(&alias)
-                    .and_then(|char| char.get("zoneRankings"))
-                    .and_then(|zr| zr.get("rankings"))
-                    .and_then(|rankings| rankings.as_array())
-                    .map(|arr| {
-                        arr.iter()
-                            .filter_map(|item| {
-                                let enc_id = item.get("encounter")
-                                    .and_then(|e| e.get("id"))
-                                    .and_then(|v| v.as_u64())
-                                    .map(|id| id as u32)?;
-                                let percentile = item.get("rankPercent")
-                                    .and_then(|v| v.as_f64())
-                                    .map(|p| p as f32)?;
-                                Some((enc_id, percentile))
-                            })
-                            .collect()
-                    })
-                    .unwrap_or_default();
-                
-                results.push((i, encounters));
-            }
-        } else {
-            // No data at all
-            for i in 0..players.len() {
-                results.push((i, Vec::new()));
+        // 결과 파싱 - Zone 내 모든 encounter 추출 (에러가 있어도 부분 결과는 처리)
+        let Some(data) = result.get("data").and_then(|d| d.get("characterData")) else {
+            if let Some(errors) = result.get("errors").filter(|errors| errors.as_array().is_some_and(|a| !a.is_empty())) {
+                anyhow::bail!("FFLogs GraphQL errors: {}", errors);
             }
+            tracing::warn!("[FFLogs] Response has no characterData: {}", sample(&result));
+            return Ok(vec![Err(UnrecognizedShape.into()); players.len()]);
+        };
+
+        // 캐릭터별 에러 (비공개 기록, 없는 캐릭터, 잘못된 서버 등)는 배치 실패가 아니라 그 캐릭터의 결과
+        let misses = character_errors(&result);
+        if !misses.is_empty() {
+            tracing::debug!("[FFLogs] {} of {} characters could not be looked up", misses.len(), players.len());
+        }
+
+        let mut fallback = 0;
+        let results: Vec<PlayerRankings> = (0..players.len())
+            .map(|i| {
+                if let Some(&miss) = misses.get(&i) {
+                    return Err(RankingsUnavailable::Profile(miss));
+                }
+                let character = data.get(format!("char{}", i));
+                match parse_character(character) {
+                    ParsedRankings::Fallback(parses) => {
+                        fallback += 1;
+                        Ok(parses)
+                    }
+                    ParsedRankings::Unrecognized => {
+                        tracing::debug!("[FFLogs] Unrecognized zoneRankings for char{}: {}", i, character.map(sample).unwrap_or_default());
+                        Err(UnrecognizedShape.into())
+                    }
+                    parsed => parsed.into_result(),
+                }
+            })
+            .collect();
+
+        if fallback > 0 {
+            tracing::warn!("[FFLogs] {} of {} zoneRankings responses needed the fallback parser", fallback, players.len());
         }
 
         Ok(results)
     }
 }
 
+/// GraphQL 에러의 `path`가 가리키는 캐릭터 (`["characterData", "charN", ...]`의 N)와 볼 수 없는 이유
+///
+/// 메시지에 "permission"이 있으면 기록을 비공개로 한 캐릭터, 그 밖에는 없는 캐릭터로 봅니다.
+pub fn character_errors(response: &serde_json::Value) -> HashMap<usize, ProfileMiss> {
+    response
+        .get("errors")
+        .and_then(|errors| errors.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|error| {
+            let path = error.get("path")?.as_array()?;
+            if path.first()?.as_str()? != "characterData" {
+                return None;
+            }
+            let index = path.get(1)?.as_str()?.strip_prefix("char")?.parse().ok()?;
+            let message = error.get("message").and_then(|message| message.as_str()).unwrap_or_default();
+            let miss = if message.to_lowercase().contains("permission") {
+                ProfileMiss::Hidden
+            } else {
+                ProfileMiss::Missing
+            };
+            Some((index, miss))
+        })
+        .collect()
+}
+
+/// 로그에 남길 응답 앞부분
+fn sample(value: &serde_json::Value) -> String {
+    value.to_string().chars().take(300).collect()
+}
+
+/// 진행 중인 조회 결과 (에러는 공유를 위해 문자열로 전달)
+type SharedLookup = Option<Result<PlayerRankings, String>>;
+
+/// 메모 캐시 유지 시간
+const MEMO_TTL: Duration = Duration::from_secs(60);
+
+/// 요청 합치기 키: (캐릭터, 서버, Zone, 난이도, 파티션)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct LookupKey {
+    name: String,
+    server: String,
+    zone_id: u32,
+    difficulty_id: Option<u32>,
+    partition: Option<u32>,
+}
+
+impl LookupKey {
+    fn new(name: &str, server: &str, zone_id: u32, difficulty_id: Option<u32>, partition: Option<u32>) -> Self {
+        Self {
+            name: name.to_lowercase(),
+            server: server.to_lowercase(),
+            zone_id,
+            difficulty_id,
+            partition,
+        }
+    }
+}
+
+/// 진행 중인 조회와 최근 결과 메모
+#[derive(Default)]
+struct Coalescer {
+    in_flight: HashMap<LookupKey, watch::Receiver<SharedLookup>>,
+    memo: HashMap<LookupKey, (Instant, ZoneParses)>,
+    /// 마지막으로 만료된 메모를 정리한 시각 (`MEMO_TTL`마다 한 번 정리)
+    memo_pruned_at: Option<Instant>,
+}
+
+impl Coalescer {
+    fn memo_get(&self, key: &LookupKey) -> Option<ZoneParses> {
+        self.memo
+            .get(key)
+            .filter(|(at, _)| at.elapsed() < MEMO_TTL)
+            .map(|(_, parses)| parses.clone())
+    }
+
+    fn memo_insert(&mut self, key: LookupKey, parses: ZoneParses) {
+        let now = Instant::now();
+        if self.memo_pruned_at.is_none_or(|at| now.saturating_duration_since(at) >= MEMO_TTL) {
+            self.memo.retain(|_, (at, _)| now.saturating_duration_since(*at) < MEMO_TTL);
+            self.memo_pruned_at = Some(now);
+        }
+        self.memo.insert(key, (now, parses));
+    }
+
+    /// 진행 중인 조회가 있으면 수신자 반환 (조회가 중단된 항목은 제거)
+    fn in_flight_get(&mut self, key: &LookupKey) -> Option<watch::Receiver<SharedLookup>> {
+        let abandoned = self.in_flight.get(key)?.has_changed().is_err();
+
+        if abandoned {
+            self.in_flight.remove(key);
+            return None;
+        }
+
+        self.in_flight.get(key).cloned()
+    }
+}
+
 /// 서버 이름에서 리전 추출
 /// 서버 이름에서 리전 추출
 pub fn get_region_from_server(server: &str) -> &'static str {
@@ -1,11 +1,21 @@
 //! FFLogs API Zone/Encounter ID 매핑
 //!
 //! FFXIV Duty ID를 FFLogs의 Zone/Encounter ID로 매핑합니다.
-//! 고난이도 컨텐츠(Savage, Ultimate, Extreme)만 매핑합니다.
+//! 고난이도 컨텐츠(Savage, Ultimate, Extreme, Unreal, 변주 던전 (Savage))만 매핑합니다.
+//! 매핑은 `data/fflogs_mapping.toml`을 내장하고, `[fflogs] mapping_file`로 시작할 때 항목을 추가 / 교체할 수 있습니다.
+//! 어떤 고난이도 컨텐츠의 Parse를 조회할지는 `parse_policy`가 컨텐츠 종류별로 정합니다.
 //!
 //! 참고: FFLogsViewer 플러그인의 Configuration.cs에서 Encounter ID 확인
 
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{OnceLock, RwLock};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::ffxiv::duties::{ContentKind, DutyInfo};
 
 /// FFLogs Encounter 정보
 #[derive(Debug, Clone, Copy)]
@@ -22,81 +32,150 @@ pub struct FFLogsEncounter {
     pub name: &'static str,
 }
 
+/// 내장 매핑 (`[fflogs] mapping_file`이 없거나 읽지 못하면 이것만 사용)
+const BUILTIN_MAPPING: &str = include_str!("../../../data/fflogs_mapping.toml");
+
+/// 시작할 때 읽은 매핑 파일 항목 (`load_mapping_file`)
+static FILE_MAPPING: OnceLock<Vec<DutyMapping>> = OnceLock::new();
+
+/// `DUTY_TO_FFLOGS`를 만든 뒤에는 매핑 파일을 읽어도 반영되지 않음
+static MAPPING_BUILT: AtomicBool = AtomicBool::new(false);
+
+/// 매핑 파일 형식 (`data/fflogs_mapping.toml`)
+#[derive(Debug, Deserialize)]
+struct MappingFile {
+    #[serde(default)]
+    duties: Vec<DutyMapping>,
+}
+
+/// 매핑 파일의 Duty 하나
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DutyMapping {
+    pub duty_id: u16,
+    pub zone_id: u32,
+    pub encounter_id: u32,
+    #[serde(default)]
+    pub difficulty_id: Option<u32>,
+    #[serde(default)]
+    pub secondary_encounter_id: Option<u32>,
+    pub name: String,
+}
+
+impl DutyMapping {
+    /// 매핑 테이블 항목 (이름은 서버가 끝날 때까지 쓰므로 `'static`으로 남김)
+    fn encounter(&self) -> FFLogsEncounter {
+        FFLogsEncounter {
+            zone_id: self.zone_id,
+            encounter_id: self.encounter_id,
+            difficulty_id: self.difficulty_id,
+            secondary_encounter_id: self.secondary_encounter_id,
+            name: Box::leak(self.name.clone().into_boxed_str()),
+        }
+    }
+}
+
+/// 매핑 파일 해석
+pub fn parse_mapping(toml: &str) -> anyhow::Result<Vec<DutyMapping>> {
+    let file: MappingFile = toml::from_str(toml).context("could not parse FFLogs mapping")?;
+    Ok(file.duties)
+}
+
+/// `FFLOGS_ZONES`에 없는 Zone을 가리키는 항목 제외 (제외한 항목은 경고)
+pub fn validate_mappings(mappings: Vec<DutyMapping>) -> Vec<DutyMapping> {
+    mappings
+        .into_iter()
+        .filter(|mapping| {
+            let known = FFLOGS_ZONES.contains_key(&mapping.zone_id);
+            if !known {
+                tracing::warn!(
+                    "ignoring FFLogs mapping for duty {} ({}): unknown zone {}",
+                    mapping.duty_id,
+                    mapping.name,
+                    mapping.zone_id
+                );
+            }
+            known
+        })
+        .collect()
+}
+
+/// 내장 매핑에 매핑 파일 항목을 합침 (같은 Duty ID는 파일 항목 우선)
+pub fn merge_mappings(builtin: &[DutyMapping], file: &[DutyMapping]) -> HashMap<u16, FFLogsEncounter> {
+    builtin
+        .iter()
+        .chain(file)
+        .map(|mapping| (mapping.duty_id, mapping.encounter()))
+        .collect()
+}
+
+/// 매핑 파일을 읽어 `DUTY_TO_FFLOGS`에 합침 (읽은 항목 수 반환)
+///
+/// `DUTY_TO_FFLOGS`를 처음 쓰기 전에 한 번만 호출해야 하며, 실패하면 내장 매핑만 사용합니다.
+pub fn load_mapping_file(path: &Path) -> anyhow::Result<usize> {
+    if MAPPING_BUILT.load(Ordering::SeqCst) {
+        anyhow::bail!("FFLogs mapping was already built");
+    }
+
+    let toml = std::fs::read_to_string(path)
+        .with_context(|| format!("could not read FFLogs mapping file {}", path.display()))?;
+    let mappings = validate_mappings(parse_mapping(&toml)?);
+    for mapping in &mappings {
+        tracing::info!(
+            "FFLogs mapping: duty {} -> zone {} encounter {} ({})",
+            mapping.duty_id,
+            mapping.zone_id,
+            mapping.encounter_id,
+            mapping.name
+        );
+    }
+
+    let count = mappings.len();
+    FILE_MAPPING
+        .set(mappings)
+        .map_err(|_| anyhow::anyhow!("FFLogs mapping file was already loaded"))?;
+    Ok(count)
+}
+
+/// 내장 매핑 항목
+pub fn builtin_mappings() -> Vec<DutyMapping> {
+    parse_mapping(BUILTIN_MAPPING).expect("built-in FFLogs mapping is valid")
+}
+
 lazy_static::lazy_static! {
-    /// FFXIV Duty ID -> FFLogs Encounter 매핑
+    /// FFXIV Duty ID -> FFLogs Encounter 매핑 (내장 매핑 + `[fflogs] mapping_file`)
     ///
     /// NOTE: Duty ID는 duties.rs 파일에서 정의된 값을 사용합니다.
     /// FFLogs Zone/Encounter ID는 FFLogsViewer 플러그인에서 확인
     pub static ref DUTY_TO_FFLOGS: HashMap<u16, FFLogsEncounter> = {
-        let mut m = HashMap::new();
-
-        // Helper closures
-        let ult = |zone, id, name| FFLogsEncounter { zone_id: zone, encounter_id: id, difficulty_id: Some(100), secondary_encounter_id: None, name };
-        let sav = |zone, id, name| FFLogsEncounter { zone_id: zone, encounter_id: id, difficulty_id: Some(101), secondary_encounter_id: None, name };
-        let ext = |zone, id, name| FFLogsEncounter { zone_id: zone, encounter_id: id, difficulty_id: Some(100), secondary_encounter_id: None, name };
-        // Split encounter helper
-        let sav_split = |zone, id1, id2, name| FFLogsEncounter { zone_id: zone, encounter_id: id1, difficulty_id: Some(101), secondary_encounter_id: Some(id2), name };
-
-        // =================================================================
-        // Dawntrail (7.4) - AAC Heavyweight Tier (Savage) - M9~M12
-        // Zone ID: 73
-        // duties.rs: 1069, 1071, 1073, 1075
-        // FFLogsViewer: EncounterId 101, 102, 103, 104 (105 is P2)
-        // =================================================================
-        m.insert(1069, sav(73, 101, "AAC Heavyweight M1 (Savage)")); // M9S - Vamp Fatale
-        m.insert(1071, sav(73, 102, "AAC Heavyweight M2 (Savage)")); // M10S - Red Hot and Deep Blue
-        m.insert(1073, sav(73, 103, "AAC Heavyweight M3 (Savage)")); // M11S - The Tyrant
-        m.insert(1075, sav_split(73, 104, 105, "AAC Heavyweight M4 (Savage)")); // M12S - The Lindwurm (P1 & P2)
-
-        // =================================================================
-        // Dawntrail (7.4) - Extreme Trial
-        // Zone ID: 72
-        // duties.rs: 1077
-        // FFLogsViewer: Doomtrain = 1083
-        // =================================================================
-        m.insert(1077, ext(72, 1083, "Hell on Rails (Extreme)")); // 극 글라샬라볼라스
-
-        // =================================================================
-        // Dawntrail (7.2) - AAC Cruiserweight Tier (Savage) - M5~M8
-        // Zone ID: 68
-        // TODO: duties.rs에서 Duty ID 확인 필요
-        // FFLogsViewer: 97, 98, 99, 100
-        // =================================================================
-        // m.insert(????, sav(68, 97, "AAC Cruiserweight M1 (Savage)")); // M5S
-        // m.insert(????, sav(68, 98, "AAC Cruiserweight M2 (Savage)")); // M6S
-        // m.insert(????, sav(68, 99, "AAC Cruiserweight M3 (Savage)")); // M7S
-        // m.insert(????, sav(68, 100, "AAC Cruiserweight M4 (Savage)")); // M8S
-
-        // =================================================================
-        // Dawntrail (7.0) - AAC Light-heavyweight Tier (Savage) - M1~M4
-        // Zone ID: 62
-        // TODO: duties.rs에서 Duty ID 확인 필요
-        // FFLogsViewer: 93, 94, 95, 96
-        // =================================================================
-        // m.insert(????, sav(62, 93, "AAC Light-heavyweight M1 (Savage)")); // M1S
-        // m.insert(????, sav(62, 94, "AAC Light-heavyweight M2 (Savage)")); // M2S
-        // m.insert(????, sav(62, 95, "AAC Light-heavyweight M3 (Savage)")); // M3S
-        // m.insert(????, sav(62, 96, "AAC Light-heavyweight M4 (Savage)")); // M4S
-
-        // =================================================================
-        // Ultimates (Dawntrail - Zone 59 Legacy)
-        // duties.rs: 280, 539, 694, 788, 908, 1006
-        // FFLogsViewer: Zone 59 with ids 1073-1077, Zone 65 with 1079
-        // =================================================================
-        // 절바하 - Duty 280
-        m.insert(280, ult(59, 1073, "The Unending Coil of Bahamut (Ultimate)"));
-        // 절신 - Duty 539
-        m.insert(539, ult(59, 1074, "The Weapon's Refrain (Ultimate)"));
-        // 절알렉 - Duty 694
-        m.insert(694, ult(59, 1075, "The Epic of Alexander (Ultimate)"));
-        // 절용시 - Duty 788
-        m.insert(788, ult(59, 1076, "Dragonsong's Reprise (Ultimate)"));
-        // 절오메가 - Duty 908
-        m.insert(908, ult(59, 1077, "The Omega Protocol (Ultimate)"));
-        // 절미래 (절에덴) - Duty 1006, Zone 65
-        m.insert(1006, ult(65, 1079, "Futures Rewritten (Ultimate)"));
+        MAPPING_BUILT.store(true, Ordering::SeqCst);
+        let file = FILE_MAPPING.get().map(Vec::as_slice).unwrap_or_default();
+        merge_mappings(&validate_mappings(builtin_mappings()), file)
+    };
 
-        m
+    /// FFLogs 랭킹의 spec 이름 -> 잡 ID (`crate::ffxiv::JOBS`)
+    pub static ref SPEC_TO_JOB: HashMap<&'static str, u8> = maplit::hashmap! {
+        "Paladin" => 19,
+        "Monk" => 20,
+        "Warrior" => 21,
+        "Dragoon" => 22,
+        "Bard" => 23,
+        "WhiteMage" => 24,
+        "BlackMage" => 25,
+        "Summoner" => 27,
+        "Scholar" => 28,
+        "Ninja" => 30,
+        "Machinist" => 31,
+        "DarkKnight" => 32,
+        "Astrologian" => 33,
+        "Samurai" => 34,
+        "RedMage" => 35,
+        "BlueMage" => 36,
+        "Gunbreaker" => 37,
+        "Dancer" => 38,
+        "Reaper" => 39,
+        "Sage" => 40,
+        "Viper" => 41,
+        "Pictomancer" => 42,
     };
 
     /// FFLogs Zone ID -> Zone 정보
@@ -108,10 +187,23 @@ lazy_static::lazy_static! {
         m.insert(65, FFLogsZone { name: "Futures Rewritten (Ultimate)", partition: 1 }); 
         m.insert(62, FFLogsZone { name: "AAC Light-heavyweight (Savage)", partition: 1 });
         m.insert(59, FFLogsZone { name: "Ultimates (Legacy)", partition: 1 });
+        m.insert(71, FFLogsZone { name: "Unreal", partition: 1 });
+        m.insert(56, FFLogsZone { name: "Another Aloalo Island", partition: 1 });
+        m.insert(52, FFLogsZone { name: "Another Mount Rokkon", partition: 1 });
+        m.insert(46, FFLogsZone { name: "Another Sil'dihn Subterrane", partition: 1 });
         m
     };
 }
 
+/// Duty / Zone 매핑 해시 (`/api/version`)
+pub fn mapping_hash() -> String {
+    crate::version::TableDigest::default()
+        .table("duty_to_fflogs", &DUTY_TO_FFLOGS)
+        .table("fflogs_zones", &FFLOGS_ZONES)
+        .table("spec_to_job", &SPEC_TO_JOB)
+        .finish()
+}
+
 /// FFLogs Zone 정보
 #[derive(Debug, Clone, Copy)]
 pub struct FFLogsZone {
@@ -119,32 +211,136 @@ pub struct FFLogsZone {
     pub partition: u32,
 }
 
+/// Zone별 조회 파티션 (`FFLOGS_ZONES` 기본값 + 설정/관리자 재정의)
+pub struct ZonePartitions {
+    overrides: RwLock<HashMap<u32, u32>>,
+}
+
+impl ZonePartitions {
+    pub fn new(overrides: HashMap<u32, u32>) -> Self {
+        Self {
+            overrides: RwLock::new(overrides),
+        }
+    }
+
+    /// 조회에 사용할 파티션 (재정의가 없으면 기본 매핑)
+    pub fn get(&self, zone_id: u32) -> Option<u32> {
+        self.overrides
+            .read()
+            .unwrap()
+            .get(&zone_id)
+            .copied()
+            .or_else(|| FFLOGS_ZONES.get(&zone_id).map(|z| z.partition))
+    }
+
+    /// 파티션 재정의 (이후 조회부터 적용)
+    pub fn set(&self, zone_id: u32, partition: u32) {
+        self.overrides.write().unwrap().insert(zone_id, partition);
+    }
+}
+
+/// 고난이도 듀티를 어떻게 다룰지
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsePolicy {
+    /// FFLogs Parse를 조회하고 표시
+    Fetch,
+    /// 고난이도로 강조만 하고 Parse는 조회하지 않음
+    DisplayOnly,
+}
+
+/// 컨텐츠 종류별 Parse 처리
+///
+/// 레이드 / 절 / 토벌전 / 변주 던전은 FFLogs가 Zone별로 집계합니다.
+/// 혼돈 연합 레이드처럼 FFLogs 랭킹이 없는 컨텐츠는 강조만 합니다.
+pub fn parse_policy(kind: ContentKind) -> ParsePolicy {
+    match kind {
+        ContentKind::Raids | ContentKind::UltimateRaids | ContentKind::Trials | ContentKind::VCDungeonFinder => {
+            ParsePolicy::Fetch
+        }
+        _ => ParsePolicy::DisplayOnly,
+    }
+}
+
+/// 듀티의 Parse를 조회하고 표시하는 FFLogs Encounter (조회 대상 종류, 매핑된 듀티)
+///
+/// 게임 데이터는 현재 티어만 고난이도로 표시하므로, 이전 티어 영웅 레이드도 조회하도록 매핑 여부로만 판단합니다.
+pub fn fflogs_encounter_for(duty_id: u16, info: &DutyInfo) -> Option<&'static FFLogsEncounter> {
+    if parse_policy(info.content_kind) != ParsePolicy::Fetch {
+        return None;
+    }
+
+    get_fflogs_encounter(duty_id)
+}
+
 /// Duty ID로 FFLogs Encounter 조회
 pub fn get_fflogs_encounter(duty_id: u16) -> Option<&'static FFLogsEncounter> {
     DUTY_TO_FFLOGS.get(&duty_id)
 }
 
+/// FFLogs Zone / Encounter ID로 Encounter 조회 (`DUTY_TO_FFLOGS` 역방향)
+///
+/// 두 번째 Encounter(P2 등)로 찾으면 `true`를 함께 반환합니다.
+/// 여러 듀티가 같은 Encounter를 가리키면 Duty ID가 가장 작은 쪽을 씁니다.
+pub fn encounter_by_id(zone_id: u32, encounter_id: u32) -> Option<(&'static FFLogsEncounter, bool)> {
+    DUTY_TO_FFLOGS
+        .iter()
+        .filter(|(_, info)| info.zone_id == zone_id)
+        .filter_map(|(&duty, info)| {
+            if info.encounter_id == encounter_id {
+                Some((duty, info, false))
+            } else if info.secondary_encounter_id == Some(encounter_id) {
+                Some((duty, info, true))
+            } else {
+                None
+            }
+        })
+        .min_by_key(|&(duty, _, _)| duty)
+        .map(|(_, info, secondary)| (info, secondary))
+}
+
+/// FFLogs spec 이름의 잡 ID (알 수 없는 spec은 0)
+pub fn job_id_for_spec(spec: &str) -> u8 {
+    SPEC_TO_JOB.get(spec).copied().unwrap_or(0)
+}
+
 /// 해당 Duty가 FFLogs 조회 대상인지 확인
 pub fn is_fflogs_supported(duty_id: u16) -> bool {
     DUTY_TO_FFLOGS.contains_key(&duty_id)
 }
 
-/// FFLogs percentile 색상 클래스 반환
+/// 화면과 API에 표시하는 percentile
+///
+/// 반올림(.5는 올림)한 뒤 0 ~ 100으로 자릅니다. 기록 없음(`-1`)과 NaN은 `None`입니다.
+/// 색상도 이 값으로 고르므로 94.6은 95로 표시되고 95 이상의 색을 받습니다.
+pub fn display_percentile(percentile: f32) -> Option<u8> {
+    if percentile.is_nan() || percentile < 0.0 {
+        return None;
+    }
+    Some(percentile.round().min(100.0) as u8)
+}
+
+/// 표시 percentile과 색상 클래스 (기록이 없으면 `parse-none`)
+pub fn percentile_display(percentile: f32) -> (Option<u8>, &'static str) {
+    (display_percentile(percentile), percentile_color_class(percentile))
+}
+
+/// FFLogs percentile 색상 클래스 반환 (`display_percentile` 기준, 기록이 없으면 `parse-none`)
 pub fn percentile_color_class(percentile: f32) -> &'static str {
-    match percentile as u32 {
-        100 => "parse-gold",
-        99 => "parse-pink",
-        95..=98 => "parse-orange",
-        75..=94 => "parse-purple",
-        50..=74 => "parse-blue",
-        25..=49 => "parse-green",
-        _ => "parse-gray",
+    match display_percentile(percentile) {
+        None => "parse-none",
+        Some(100) => "parse-gold",
+        Some(99) => "parse-pink",
+        Some(95..=98) => "parse-orange",
+        Some(75..=94) => "parse-purple",
+        Some(50..=74) => "parse-blue",
+        Some(25..=49) => "parse-green",
+        Some(_) => "parse-gray",
     }
 }
 
-/// FFLogs percentile RGB 색상 반환
+/// FFLogs percentile RGB 색상 반환 (`display_percentile` 기준)
 pub fn percentile_color(percentile: f32) -> &'static str {
-    match percentile as u32 {
+    match display_percentile(percentile).unwrap_or(0) {
         100 => "#E5CC80",
         99 => "#E268A8",
         95..=98 => "#FF8000",
@@ -1,14 +1,28 @@
 //! FFLogs 관련 모듈
 //!
 //! - `client`: FFLogs API 클라이언트
+//! - `parse_response`: `zoneRankings` 응답 해석 / 형태 변경 감지
 //! - `mapping`: FFXIV Duty ID ↔ FFLogs Zone/Encounter 매핑
 //! - `cache`: Parse 캐시 타입
+//! - `refetch`: 관리자가 요청한 우선 재조회 대기열
+//! - `quota`: 시간당 FFLogs 요청 예산
+//! - `points`: FFLogs가 알려준 시간당 API 포인트 사용량
+//! - `backfill`: 관리자가 요청한 모집글 단위 즉시 재조회
 
 pub mod client;
+pub mod parse_response;
 pub mod mapping;
 pub mod cache;
+pub mod refetch;
+pub mod quota;
+pub mod points;
+pub mod backfill;
 
 // 편의를 위한 re-export
 pub use client::{FFLogsClient, get_region_from_server};
-pub use mapping::{get_fflogs_encounter, percentile_color_class, FFLogsEncounter, DUTY_TO_FFLOGS, FFLOGS_ZONES};
-pub use cache::{ParseCacheDoc, ZoneCache, EncounterParse, is_zone_cache_expired};
+pub use mapping::{get_fflogs_encounter, percentile_color_class, FFLogsEncounter, ZonePartitions, DUTY_TO_FFLOGS, FFLOGS_ZONES};
+pub use cache::{ParseCacheDoc, ZoneCache, EncounterParse, JobParse, AllStars, FetchAccounting, ZoneAccounting, ProfileMiss, CacheExpiry, is_empty_result, is_zone_cache_expired, merge_zone_caches, repoint_parse_doc, ParseRepoint, ZoneMergeOutcome};
+pub use refetch::RefetchQueue;
+pub use quota::{QuotaExhausted, RequestQuota};
+pub use points::{PointsBudget, PointsUsage};
+pub use parse_response::{PlayerRankings, RankingsUnavailable, SchemaHealth, UnrecognizedShape, ZoneParses};
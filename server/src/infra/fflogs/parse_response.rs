@@ -0,0 +1,310 @@
+//! FFLogs `zoneRankings` 응답 해석
+//!
+//! `zoneRankings`는 GraphQL 스키마상 JSON 스칼라라서 FFLogs가 형태를 바꿔도 쿼리는 성공합니다.
+//! 알려진 형태는 타입으로 읽고, 실패하면 encounter 목록을 찾아 읽는 대체 파서를 시도합니다.
+//! 둘 다 실패하면 "기록 없음"과 구분해 `Unrecognized`로 보고하며, 이 결과는 캐시에 저장하지 않습니다.
+//! 캐릭터 자체가 `null`이면 FFLogs에 없는 캐릭터(`ProfileMiss`)로 보고, 긴 간격으로만 다시 조회합니다.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::cache::{AllStars, EncounterParse, ProfileMiss};
+use super::mapping::job_id_for_spec;
+
+/// 한 캐릭터의 Zone 내 encounter별 결과
+pub type ZoneParses = Vec<(u32, EncounterParse)>;
+
+/// 조회 파이프라인에서 쓰는 캐릭터별 결과 (형태를 알 수 없거나 캐릭터를 볼 수 없으면 에러)
+pub type PlayerRankings = Result<ZoneParses, RankingsUnavailable>;
+
+/// 한 주기에서 이 비율(%)을 넘는 응답을 해석하지 못하면 경고
+pub const UNRECOGNIZED_ALERT_PERCENT: f64 = 20.0;
+
+/// 응답 하나의 일시적인 이상으로 경고하지 않도록 요구하는 최소 건수
+pub const UNRECOGNIZED_ALERT_MIN: u64 = 2;
+
+/// 형태를 알 수 없는 `zoneRankings` 응답
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnrecognizedShape;
+
+impl fmt::Display for UnrecognizedShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unrecognized FFLogs zoneRankings response")
+    }
+}
+
+impl std::error::Error for UnrecognizedShape {}
+
+/// 캐릭터의 랭킹을 얻지 못한 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankingsUnavailable {
+    /// 응답 형태를 알 수 없음 (다음 주기에 다시 조회)
+    Unrecognized(UnrecognizedShape),
+    /// FFLogs에 없거나 기록이 비공개인 캐릭터 (`EmptyBackoff::missing_hours` 뒤에 다시 조회)
+    Profile(ProfileMiss),
+}
+
+impl From<UnrecognizedShape> for RankingsUnavailable {
+    fn from(shape: UnrecognizedShape) -> Self {
+        Self::Unrecognized(shape)
+    }
+}
+
+impl fmt::Display for RankingsUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unrecognized(shape) => shape.fmt(f),
+            Self::Profile(ProfileMiss::Missing) => f.write_str("character not found on FFLogs"),
+            Self::Profile(ProfileMiss::Hidden) => f.write_str("character logs are hidden on FFLogs"),
+        }
+    }
+}
+
+impl std::error::Error for RankingsUnavailable {}
+
+/// `zoneRankings` 해석 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedRankings {
+    /// 알려진 형태 (kill이 없는 encounter는 제외, 모두 없으면 빈 목록)
+    Known(ZoneParses),
+    /// 알려진 형태는 아니지만 encounter 목록을 찾아 읽음
+    Fallback(ZoneParses),
+    /// 랭킹이 없음 (`null`)
+    NoRankings,
+    /// FFLogs에 없는 캐릭터 (`character`가 `null`)
+    Missing,
+    /// 형태를 알 수 없음
+    Unrecognized,
+}
+
+impl ParsedRankings {
+    pub fn into_result(self) -> PlayerRankings {
+        match self {
+            Self::Known(parses) | Self::Fallback(parses) => Ok(parses),
+            Self::NoRankings => Ok(Vec::new()),
+            Self::Missing => Err(RankingsUnavailable::Profile(ProfileMiss::Missing)),
+            Self::Unrecognized => Err(UnrecognizedShape.into()),
+        }
+    }
+}
+
+/// 현재 `zoneRankings` 형태
+#[derive(Debug, Deserialize)]
+struct ZoneRankings {
+    rankings: Vec<Ranking>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Ranking {
+    encounter: Encounter,
+    /// kill이 없으면 `null`
+    rank_percent: Option<f64>,
+    /// Zone 단위 요약과 달리 encounter마다 없을 수 있음
+    #[serde(default)]
+    all_stars: Option<RankingAllStars>,
+    best_spec: Option<String>,
+    spec: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Encounter {
+    id: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct RankingAllStars {
+    points: Option<f64>,
+    rank: Option<u32>,
+}
+
+impl Ranking {
+    fn into_parse(self) -> Option<(u32, EncounterParse)> {
+        let percentile = self.rank_percent? as f32;
+        let all_stars = self.all_stars.and_then(|all_stars| {
+            Some(AllStars { points: all_stars.points? as f32, rank: all_stars.rank? })
+        });
+        // 최고 기록을 낸 잡 (없으면 마지막 기록의 잡)
+        let job_id = self.best_spec.or(self.spec).as_deref().map(job_id_for_spec).unwrap_or(0);
+        Some((self.encounter.id, EncounterParse { percentile, job_id, all_stars, jobs: HashMap::new() }))
+    }
+}
+
+/// 역할별 `zoneRankings` alias와 FFLogs `RoleType` 값
+///
+/// 역할마다 그 역할에서 가장 높은 기록과 잡을 돌려주므로, 최고 기록 외에 역할별 잡 기록을 채울 수 있습니다.
+pub const ROLE_RANKINGS: [(&str, &str); 3] = [("tankRankings", "Tank"), ("healerRankings", "Healer"), ("dpsRankings", "DPS")];
+
+/// 캐릭터 하나의 응답 (`charN` alias 값) 해석
+///
+/// 캐릭터가 `null`이면 FFLogs에 없는 캐릭터이고, `zoneRankings` 필드 자체가 없으면 형태 변경으로 봅니다.
+/// alias가 응답에 아예 없으면 "기록 없음"입니다.
+/// 역할별 랭킹(`ROLE_RANKINGS`)은 encounter별 `jobs`에 합치며, 해석하지 못하면 무시합니다.
+pub fn parse_character(character: Option<&serde_json::Value>) -> ParsedRankings {
+    match character {
+        None => ParsedRankings::NoRankings,
+        Some(serde_json::Value::Null) => ParsedRankings::Missing,
+        Some(character) => match character.get("zoneRankings") {
+            Some(zone_rankings) => match classify_zone_rankings(zone_rankings) {
+                ParsedRankings::Known(mut parses) => {
+                    merge_role_rankings(&mut parses, character);
+                    ParsedRankings::Known(parses)
+                }
+                ParsedRankings::Fallback(mut parses) => {
+                    merge_role_rankings(&mut parses, character);
+                    ParsedRankings::Fallback(parses)
+                }
+                parsed => parsed,
+            },
+            None => ParsedRankings::Unrecognized,
+        },
+    }
+}
+
+/// 역할별 랭킹의 잡 기록을 최고 기록 encounter의 `jobs`에 추가 (잡을 알 수 없는 기록은 제외)
+fn merge_role_rankings(parses: &mut ZoneParses, character: &serde_json::Value) {
+    for (alias, _) in ROLE_RANKINGS {
+        let Some(role_rankings) = character.get(alias) else {
+            continue;
+        };
+        for (encounter_id, role_parse) in parse_zone_rankings(role_rankings) {
+            if role_parse.job_id == 0 {
+                continue;
+            }
+            let Some((_, parse)) = parses.iter_mut().find(|(id, _)| *id == encounter_id) else {
+                continue;
+            };
+            let percentile = parse.jobs.entry(role_parse.job_id.to_string()).or_insert(role_parse.percentile);
+            *percentile = percentile.max(role_parse.percentile);
+        }
+    }
+}
+
+/// `zoneRankings` 값 해석 (알려진 형태 → 대체 파서 순)
+pub fn classify_zone_rankings(zone_rankings: &serde_json::Value) -> ParsedRankings {
+    match zone_rankings {
+        serde_json::Value::Null => return ParsedRankings::NoRankings,
+        // JSON 스칼라가 문자열로 한 번 더 감싸여 오는 경우
+        serde_json::Value::String(raw) => {
+            return match serde_json::from_str::<serde_json::Value>(raw) {
+                Ok(inner) if !inner.is_string() => classify_zone_rankings(&inner),
+                _ => ParsedRankings::Unrecognized,
+            };
+        }
+        _ => {}
+    }
+
+    if let Ok(known) = ZoneRankings::deserialize(zone_rankings) {
+        return ParsedRankings::Known(known.rankings.into_iter().filter_map(Ranking::into_parse).collect());
+    }
+
+    match find_encounter_list(zone_rankings, 0) {
+        Some(items) => ParsedRankings::Fallback(items.iter().filter_map(fallback_parse).collect()),
+        None => ParsedRankings::Unrecognized,
+    }
+}
+
+/// `zoneRankings` 응답에서 encounter별 percentile과 All Stars 추출 (해석하지 못하면 빈 목록)
+pub fn parse_zone_rankings(zone_rankings: &serde_json::Value) -> ZoneParses {
+    classify_zone_rankings(zone_rankings).into_result().unwrap_or_default()
+}
+
+/// 대체 파서가 찾아 내려가는 깊이
+const FALLBACK_MAX_DEPTH: usize = 3;
+
+const ENCOUNTER_ID_KEYS: [&str; 3] = ["encounterID", "encounterId", "encounter_id"];
+const PERCENT_KEYS: [&str; 4] = ["rankPercent", "percentile", "bestPercent", "historicalPercent"];
+const SPEC_KEYS: [&str; 3] = ["bestSpec", "spec", "specName"];
+
+/// 모든 항목이 encounter ID를 가진 비어 있지 않은 배열 (객체라면 필드 이름 순으로 찾아 내려감)
+fn find_encounter_list(value: &serde_json::Value, depth: usize) -> Option<&Vec<serde_json::Value>> {
+    match value {
+        serde_json::Value::Array(items) if !items.is_empty() && items.iter().all(|item| encounter_id(item).is_some()) => {
+            Some(items)
+        }
+        serde_json::Value::Object(fields) if depth < FALLBACK_MAX_DEPTH => {
+            fields.values().find_map(|field| find_encounter_list(field, depth + 1))
+        }
+        _ => None,
+    }
+}
+
+fn encounter_id(item: &serde_json::Value) -> Option<u32> {
+    let id = match item.get("encounter") {
+        Some(encounter) if encounter.is_object() => encounter.get("id").or_else(|| encounter.get("encounterID")),
+        Some(id) => Some(id),
+        None => ENCOUNTER_ID_KEYS.iter().find_map(|key| item.get(key)),
+    }?;
+    number(id).filter(|id| id.fract() == 0.0 && *id > 0.0).map(|id| id as u32)
+}
+
+/// 숫자 또는 숫자 문자열
+fn number(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn fallback_parse(item: &serde_json::Value) -> Option<(u32, EncounterParse)> {
+    let id = encounter_id(item)?;
+    let percentile = PERCENT_KEYS.iter().find_map(|key| item.get(key).and_then(number))? as f32;
+    let all_stars = item.get("allStars").and_then(|all_stars| {
+        Some(AllStars {
+            points: all_stars.get("points").and_then(number)? as f32,
+            rank: all_stars.get("rank").and_then(number)? as u32,
+        })
+    });
+    let job_id = SPEC_KEYS
+        .iter()
+        .find_map(|key| item.get(key).and_then(|spec| spec.as_str()))
+        .map(job_id_for_spec)
+        .unwrap_or(0);
+    Some((id, EncounterParse { percentile, job_id, all_stars, jobs: HashMap::new() }))
+}
+
+/// FFLogs 응답 형태 상태 (`/api/health`)
+#[derive(Debug, Default)]
+pub struct SchemaHealth {
+    inner: Mutex<SchemaHealthSnapshot>,
+}
+
+/// `SchemaHealth`의 현재 상태
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct SchemaHealthSnapshot {
+    /// 마지막 주기에서 해석하지 못한 응답이 `UNRECOGNIZED_ALERT_PERCENT`를 넘음
+    pub degraded: bool,
+    /// 마지막으로 응답이 있던 주기의 캐릭터 수
+    pub last_responses: u64,
+    pub last_unrecognized: u64,
+    /// 서버 시작 후 해석하지 못한 응답 수
+    pub total_unrecognized: u64,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+impl SchemaHealth {
+    /// 한 주기의 결과 기록 (응답이 없던 주기는 상태를 바꾸지 않음), 경고 상태면 `true`
+    pub fn record_cycle(&self, responses: u64, unrecognized: u64, now: DateTime<Utc>) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        inner.total_unrecognized += unrecognized;
+        if responses == 0 {
+            return inner.degraded;
+        }
+
+        inner.degraded = unrecognized >= UNRECOGNIZED_ALERT_MIN
+            && unrecognized as f64 * 100.0 > responses as f64 * UNRECOGNIZED_ALERT_PERCENT;
+        inner.last_responses = responses;
+        inner.last_unrecognized = unrecognized;
+        inner.checked_at = Some(now);
+        inner.degraded
+    }
+
+    pub fn snapshot(&self) -> SchemaHealthSnapshot {
+        *self.inner.lock().unwrap()
+    }
+}
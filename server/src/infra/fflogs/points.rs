@@ -0,0 +1,110 @@
+//! FFLogs API 포인트 예산
+//!
+//! FFLogs는 클라이언트마다 시간당 포인트 한도를 두고 쿼리 복잡도만큼 포인트를 차감합니다.
+//! 한도를 넘으면 그 시간이 끝날 때까지 모든 쿼리가 실패하므로, 배치 쿼리에 `rateLimitData`를 함께 요청해
+//! 마지막 사용량을 기억합니다. 수집 태스크는 사용량이 많으면 배치 간격을 늘리고, 거의 소진되면 주기를 일찍 끝냅니다.
+
+use std::sync::RwLock;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+
+/// 사용량이 한도의 이 비율(%)을 넘으면 경고하고 배치 간격을 늘림
+pub const WARN_PERCENT: f64 = 80.0;
+
+/// 남은 포인트가 한도의 이 비율(%) 이하면 수집 주기를 멈춤 (관리자 재조회 몫)
+pub const RESERVE_PERCENT: f64 = 5.0;
+
+/// 기본 배치 간격
+pub const BATCH_DELAY: Duration = Duration::from_secs(1);
+
+/// `WARN_PERCENT`를 넘은 뒤의 배치 간격
+pub const SLOW_BATCH_DELAY: Duration = Duration::from_secs(5);
+
+/// 배치 쿼리에 덧붙이는 필드
+pub const RATE_LIMIT_QUERY: &str = "rateLimitData { limitPerHour pointsSpentThisHour pointsResetIn }";
+
+/// `rateLimitData` 응답 (관측 시각 포함)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PointsUsage {
+    pub limit_per_hour: f64,
+    pub spent_this_hour: f64,
+    /// 관측 시점부터 한도가 초기화될 때까지 남은 초
+    pub reset_in_secs: u64,
+    pub observed_at: DateTime<Utc>,
+}
+
+impl PointsUsage {
+    /// GraphQL 응답의 `data.rateLimitData` 해석 (없거나 형태가 다르면 `None`)
+    pub fn from_response(response: &serde_json::Value, now: DateTime<Utc>) -> Option<Self> {
+        let data = response.get("data")?.get("rateLimitData")?;
+        Some(Self {
+            limit_per_hour: data.get("limitPerHour")?.as_f64()?,
+            spent_this_hour: data.get("pointsSpentThisHour")?.as_f64()?,
+            reset_in_secs: data.get("pointsResetIn")?.as_u64()?,
+            observed_at: now,
+        })
+    }
+
+    /// 사용량 (%, 한도를 모르면 0)
+    pub fn used_percent(&self) -> f64 {
+        if self.limit_per_hour <= 0.0 {
+            return 0.0;
+        }
+        self.spent_this_hour * 100.0 / self.limit_per_hour
+    }
+
+    /// `now`에 남은 포인트 (초기화 시각이 지났으면 전체 한도)
+    pub fn remaining(&self, now: DateTime<Utc>) -> f64 {
+        if now >= self.resets_at() {
+            return self.limit_per_hour;
+        }
+        (self.limit_per_hour - self.spent_this_hour).max(0.0)
+    }
+
+    pub fn resets_at(&self) -> DateTime<Utc> {
+        self.observed_at + TimeDelta::seconds(self.reset_in_secs as i64)
+    }
+}
+
+/// 마지막으로 관측한 포인트 사용량
+#[derive(Debug, Default)]
+pub struct PointsBudget {
+    usage: RwLock<Option<PointsUsage>>,
+}
+
+impl PointsBudget {
+    /// 새 사용량 기록 (`WARN_PERCENT`를 새로 넘었으면 `true`)
+    pub fn record(&self, usage: PointsUsage) -> bool {
+        let mut current = self.usage.write().unwrap();
+        let was_over = current.is_some_and(|previous| {
+            usage.observed_at < previous.resets_at() && previous.used_percent() >= WARN_PERCENT
+        });
+        *current = Some(usage);
+        !was_over && usage.used_percent() >= WARN_PERCENT
+    }
+
+    pub fn snapshot(&self) -> Option<PointsUsage> {
+        *self.usage.read().unwrap()
+    }
+
+    /// 남은 포인트 (아직 관측하지 못했으면 `None`)
+    pub fn remaining(&self, now: DateTime<Utc>) -> Option<f64> {
+        self.snapshot().map(|usage| usage.remaining(now))
+    }
+
+    /// 수집 주기를 멈춰야 하는지 (남은 포인트가 `RESERVE_PERCENT` 이하)
+    pub fn should_pause(&self, now: DateTime<Utc>) -> bool {
+        self.snapshot()
+            .is_some_and(|usage| usage.remaining(now) <= usage.limit_per_hour * RESERVE_PERCENT / 100.0)
+    }
+
+    /// 다음 배치까지 기다릴 시간 (초기화 전이고 `WARN_PERCENT`를 넘었으면 `SLOW_BATCH_DELAY`)
+    pub fn batch_delay(&self, now: DateTime<Utc>) -> Duration {
+        match self.snapshot() {
+            Some(usage) if now < usage.resets_at() && usage.used_percent() >= WARN_PERCENT => SLOW_BATCH_DELAY,
+            _ => BATCH_DELAY,
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! FFLogs 요청 예산
+//!
+//! 최근 한 시간 동안 보낸 GraphQL 요청 수를 세어 설정한 한도(`fflogs.requests_per_hour`)를
+//! 넘지 않게 합니다. 한도가 없으면 요청 수만 셉니다.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 예산을 세는 기간
+const WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// 예산 소진 (다음 요청이 가능해질 때까지 남은 시간)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaExhausted {
+    pub retry_after: Duration,
+}
+
+impl fmt::Display for QuotaExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FFLogs request budget exhausted (retry in {}s)", self.retry_after.as_secs())
+    }
+}
+
+impl std::error::Error for QuotaExhausted {}
+
+#[derive(Debug, Default)]
+pub struct RequestQuota {
+    per_hour: Option<u32>,
+    /// 최근 한 시간 안에 보낸 요청 시각 (오래된 순)
+    sent: Mutex<VecDeque<Instant>>,
+}
+
+impl RequestQuota {
+    pub fn new(per_hour: Option<u32>) -> Self {
+        Self {
+            per_hour,
+            sent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// 요청 하나를 예산에서 차감 (소진됐으면 차감하지 않고 에러)
+    pub fn try_acquire(&self) -> Result<(), QuotaExhausted> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    pub fn try_acquire_at(&self, now: Instant) -> Result<(), QuotaExhausted> {
+        let mut sent = self.sent.lock().unwrap();
+        Self::expire(&mut sent, now);
+        Self::exhausted(self.per_hour, &sent, now).map_or(Ok(()), Err)?;
+        sent.push_back(now);
+        Ok(())
+    }
+
+    /// 차감 없이 예산이 남았는지 확인
+    pub fn check(&self) -> Result<(), QuotaExhausted> {
+        self.check_at(Instant::now())
+    }
+
+    pub fn check_at(&self, now: Instant) -> Result<(), QuotaExhausted> {
+        let mut sent = self.sent.lock().unwrap();
+        Self::expire(&mut sent, now);
+        Self::exhausted(self.per_hour, &sent, now).map_or(Ok(()), Err)
+    }
+
+    /// 설정한 시간당 한도
+    pub fn limit(&self) -> Option<u32> {
+        self.per_hour
+    }
+
+    /// 최근 한 시간 동안 보낸 요청 수
+    pub fn used(&self) -> usize {
+        let mut sent = self.sent.lock().unwrap();
+        Self::expire(&mut sent, Instant::now());
+        sent.len()
+    }
+
+    fn expire(sent: &mut VecDeque<Instant>, now: Instant) {
+        while sent.front().is_some_and(|&at| now.duration_since(at) >= WINDOW) {
+            sent.pop_front();
+        }
+    }
+
+    fn exhausted(per_hour: Option<u32>, sent: &VecDeque<Instant>, now: Instant) -> Option<QuotaExhausted> {
+        let limit = per_hour? as usize;
+        if sent.len() < limit {
+            return None;
+        }
+        // 가장 오래된 요청이 기간을 벗어나면 다시 가능
+        let oldest = sent.front().copied().unwrap_or(now);
+        Some(QuotaExhausted {
+            retry_after: WINDOW.saturating_sub(now.duration_since(oldest)),
+        })
+    }
+}
@@ -0,0 +1,69 @@
+//! 우선 재조회 대기열
+//!
+//! 관리자가 Zone 캐시를 무효화하면서 재조회를 요청하면, 해당 Zone의 현재 모집글 멤버를
+//! 대기열에 넣고 백그라운드 작업을 즉시 깨워 다른 Zone보다 먼저 조회합니다.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+use crate::listing_container::QueriedListing;
+
+#[derive(Default)]
+pub struct RefetchQueue {
+    /// Zone ID → 캐시와 무관하게 다시 조회할 Content ID
+    pending: Mutex<BTreeMap<u32, BTreeSet<u64>>>,
+    notify: Notify,
+}
+
+impl RefetchQueue {
+    /// 대기열에 추가하고 백그라운드 작업을 깨움. 새로 추가된 플레이어 수 반환
+    pub fn enqueue(&self, zone_id: u32, content_ids: impl IntoIterator<Item = u64>) -> usize {
+        let added = {
+            let mut pending = self.pending.lock().unwrap();
+            let zone = pending.entry(zone_id).or_default();
+            let before = zone.len();
+            zone.extend(content_ids.into_iter().filter(|&id| id != 0));
+            zone.len() - before
+        };
+
+        self.notify.notify_one();
+        added
+    }
+
+    /// 대기 중인 항목을 모두 꺼냄
+    pub fn drain(&self) -> BTreeMap<u32, BTreeSet<u64>> {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+
+    /// 새 항목이 추가될 때까지 대기
+    pub async fn notified(&self) {
+        self.notify.notified().await
+    }
+}
+
+/// 현재 모집글 중 해당 Zone에 해당하는 고난이도 모집글의 멤버 Content ID
+pub fn refetch_targets(listings: &[QueriedListing], zone_id: u32) -> Vec<u64> {
+    let mut targets: Vec<u64> = listings
+        .iter()
+        .filter(|container| {
+            container
+                .listing
+                .fflogs_encounter()
+                .is_some_and(|info| info.zone_id == zone_id)
+        })
+        .flat_map(|container| container.listing.member_content_ids.iter().map(|&id| id as u64))
+        .filter(|&id| id != 0)
+        .collect();
+    targets.sort_unstable();
+    targets.dedup();
+    targets
+}
+
+/// 우선 재조회 Zone을 앞에 두는 처리 순서 (나머지는 Zone ID 순)
+pub fn zone_order(zone_ids: impl IntoIterator<Item = u32>, priority: &HashSet<u32>) -> Vec<u32> {
+    let mut zones: Vec<u32> = zone_ids.into_iter().collect();
+    zones.sort_by_key(|zone_id| (!priority.contains(zone_id), *zone_id));
+    zones
+}
@@ -0,0 +1,372 @@
+//! 시작할 때 만드는 인덱스와 실제 인덱스 비교
+//!
+//! 권한이 제한된 관리형 MongoDB에서는 이전에 만든 인덱스가 있어도 `createIndex`가 실패합니다.
+//! 실패는 종류별로 나눠, 같은 인덱스가 이미 있으면 그대로 계속하고,
+//! `indexes.required = false`면 권한 부족 / 옵션 충돌을 경고만 남기고 인덱스 없이 시작합니다.
+//! 시작할 때 `listIndexes`로 기대한 인덱스와 비교한 결과는 `/api/health`에 표시합니다.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use futures_util::TryStreamExt;
+use mongodb::bson::{doc, Document};
+use mongodb::error::{Error, ErrorKind};
+use mongodb::options::{Collation, CollationStrength, IndexOptions};
+use mongodb::{Collection, Database, IndexModel};
+use serde::Serialize;
+
+use crate::config::Mongo;
+
+/// 시작할 때 만드는 인덱스 하나
+#[derive(Debug, Clone)]
+pub struct ExpectedIndex {
+    pub collection: String,
+    pub model: IndexModel,
+}
+
+impl ExpectedIndex {
+    fn new(collection: &str, keys: Document, options: IndexOptions) -> Self {
+        Self {
+            collection: collection.to_string(),
+            model: IndexModel::builder().keys(keys).options(options).build(),
+        }
+    }
+
+    /// MongoDB 기본 인덱스 이름 (예: `updated_at_1`)
+    pub fn name(&self) -> String {
+        self.model
+            .keys
+            .iter()
+            .map(|(field, direction)| format!("{}_{}", field, direction))
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+
+    fn is_ttl(&self) -> bool {
+        self.expire_after().is_some()
+    }
+
+    fn unique(&self) -> bool {
+        unique(&self.model)
+    }
+
+    fn expire_after(&self) -> Option<Duration> {
+        self.model.options.as_ref().and_then(|options| options.expire_after)
+    }
+
+    /// 키와 옵션(unique, TTL, collation)이 같은 인덱스인지
+    fn matches(&self, existing: &IndexModel) -> bool {
+        self.model.keys == existing.keys
+            && self.unique() == unique(existing)
+            && self.expire_after() == existing.options.as_ref().and_then(|options| options.expire_after)
+            && collation(&self.model) == collation(existing)
+    }
+}
+
+fn unique(model: &IndexModel) -> bool {
+    model.options.as_ref().and_then(|options| options.unique).unwrap_or(false)
+}
+
+/// collation의 언어와 비교 수준 (`listIndexes`는 나머지 기본값도 채워서 돌려줌)
+fn collation(model: &IndexModel) -> Option<(String, Option<i32>)> {
+    let collation = model.options.as_ref()?.collation.as_ref()?;
+    let collation = mongodb::bson::to_document(collation).ok()?;
+    Some((collation.get_str("locale").ok()?.to_string(), collation.get_i32("strength").ok()))
+}
+
+/// 플레이어 이름 검색 collation (대소문자 무시, 인덱스와 조회가 같은 값을 써야 인덱스를 씀)
+pub fn player_name_collation() -> Collation {
+    Collation::builder().locale("en").strength(CollationStrength::Secondary).build()
+}
+
+/// 서버가 쓰는 인덱스 (컬렉션 이름은 `mongo` 설정, `role_demand_horizon`은 역할별 빈 자리 기록 보관 기간)
+pub fn expected_indexes(mongo: &Mongo, role_demand_horizon: Duration) -> Vec<ExpectedIndex> {
+    vec![
+        ExpectedIndex::new(
+            &mongo.listings_collection,
+            doc! {
+                "listing.id": 1,
+                "listing.last_server_restart": 1,
+                "listing.created_world": 1,
+            },
+            IndexOptions::builder().unique(true).build(),
+        ),
+        ExpectedIndex::new(
+            &mongo.listings_collection,
+            doc! { "updated_at": 1 },
+            IndexOptions::builder().expire_after(mongo.listings_ttl()).build(),
+        ),
+        ExpectedIndex::new(
+            "role_demand",
+            doc! { "sampled_at": 1 },
+            IndexOptions::builder().expire_after(role_demand_horizon).build(),
+        ),
+        ExpectedIndex::new(
+            &mongo.parses_collection,
+            doc! { "content_id": 1 },
+            IndexOptions::builder().unique(true).build(),
+        ),
+        ExpectedIndex::new(
+            &mongo.players_collection,
+            doc! { "name": 1, "home_world": 1 },
+            IndexOptions::builder().collation(player_name_collation()).build(),
+        ),
+        // 보관 기록은 TTL 없이 모집글과 같은 키로 하나씩
+        ExpectedIndex::new(
+            "listings_archive",
+            doc! {
+                "listing.id": 1,
+                "listing.last_server_restart": 1,
+                "listing.created_world": 1,
+            },
+            IndexOptions::builder().unique(true).build(),
+        ),
+        ExpectedIndex::new("listings_archive", doc! { "created_at": 1 }, IndexOptions::default()),
+        // 플레이어별 모집글 기록 (`GET /api/players/{content_id}/listings`)
+        ExpectedIndex::new(
+            "listings_archive",
+            doc! { "listing.content_id_lower": 1, "created_at": -1 },
+            IndexOptions::default(),
+        ),
+    ]
+}
+
+/// 인덱스 생성 실패 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexFailure {
+    /// 같은 인덱스가 이미 있음 (IndexAlreadyExists)
+    AlreadyExists,
+    /// 같은 키 / 이름의 인덱스가 다른 옵션으로 있음 (IndexOptionsConflict, IndexKeySpecsConflict)
+    Conflict,
+    /// 인덱스를 만들 권한이 없음
+    Unauthorized,
+    /// 연결 실패 등 그 밖의 오류
+    Other,
+}
+
+/// 권한 부족 오류 코드 (Unauthorized)
+const UNAUTHORIZED: i32 = 13;
+/// 관리형 MongoDB(Atlas)의 권한 오류 코드 (메시지로 구분)
+const ATLAS_ERROR: i32 = 8000;
+/// 컬렉션이 아직 없음 (`listIndexes`)
+const NAMESPACE_NOT_FOUND: i32 = 26;
+
+pub fn classify(error: &Error) -> IndexFailure {
+    let ErrorKind::Command(command) = &*error.kind else {
+        return IndexFailure::Other;
+    };
+    match command.code {
+        68 => IndexFailure::AlreadyExists,
+        85 | 86 => IndexFailure::Conflict,
+        UNAUTHORIZED => IndexFailure::Unauthorized,
+        ATLAS_ERROR if command.message.contains("not allowed") || command.message.contains("not authorized") => {
+            IndexFailure::Unauthorized
+        }
+        _ => IndexFailure::Other,
+    }
+}
+
+/// 실패한 인덱스 생성 처리 방법
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// 같은 인덱스가 이미 있으므로 조용히 계속
+    Present,
+    /// 경고를 남기고 인덱스 없이 계속
+    Skipped,
+    /// 시작을 멈추고 다시 시도
+    Fail,
+}
+
+/// `present`는 `listIndexes`에서 같은 인덱스를 찾았는지, `required`는 `indexes.required`
+pub fn resolve(failure: IndexFailure, present: bool, required: bool) -> Resolution {
+    match failure {
+        IndexFailure::AlreadyExists => Resolution::Present,
+        IndexFailure::Unauthorized if present => Resolution::Present,
+        IndexFailure::Unauthorized | IndexFailure::Conflict if !required => Resolution::Skipped,
+        _ => Resolution::Fail,
+    }
+}
+
+/// 인덱스 생성 (만들지 못하고 건너뛰었으면 `false`)
+///
+/// 보관 기간(`mongo.listings_ttl_hours` 등)이 바뀌어 옵션이 충돌하는 TTL 인덱스는
+/// 같은 키의 기존 인덱스를 이름과 관계없이 지우고 다시 만듭니다.
+pub async fn ensure_index(database: &Database, index: &ExpectedIndex, required: bool) -> Result<bool> {
+    let collection = database.collection::<Document>(&index.collection);
+    let name = index.name();
+
+    let mut result = collection.create_index(index.model.clone(), None).await.map(|_| ());
+    if index.is_ttl() && result.as_ref().err().is_some_and(|e| classify(e) == IndexFailure::Conflict) {
+        let existing = list_indexes(&collection).await.unwrap_or_default();
+        let stale = stale_ttl_index(index, &existing);
+        let stale_name = stale.and_then(|model| model.options.as_ref()?.name.clone()).unwrap_or_else(|| name.clone());
+        tracing::warn!(
+            "Index {}.{} exists with TTL {:?}, recreating it with TTL {:?}",
+            index.collection,
+            stale_name,
+            stale.and_then(|model| model.options.as_ref()?.expire_after),
+            index.expire_after(),
+        );
+        result = recreate(&collection, index, &stale_name).await;
+        if result.is_ok() {
+            tracing::info!("Index {}.{} recreated with the new TTL", index.collection, name);
+        }
+    }
+
+    let Err(e) = result else {
+        return Ok(true);
+    };
+    let failure = classify(&e);
+    let present = failure == IndexFailure::Unauthorized && index_present(&collection, index).await;
+    match resolve(failure, present, required) {
+        Resolution::Present => {
+            tracing::debug!("Index {}.{} already exists: {}", index.collection, name, e);
+            Ok(true)
+        }
+        Resolution::Skipped => {
+            tracing::warn!("Could not create index {}.{} ({:?}): {}", index.collection, name, failure, e);
+            Ok(false)
+        }
+        Resolution::Fail => Err(e).with_context(|| format!("could not create index {}.{}", index.collection, name)),
+    }
+}
+
+/// `index`와 키가 같고 보관 기간이 다른 기존 인덱스 (TTL 변경 시 지울 대상)
+pub fn stale_ttl_index<'a>(index: &ExpectedIndex, existing: &'a [IndexModel]) -> Option<&'a IndexModel> {
+    existing.iter().find(|model| {
+        model.keys == index.model.keys
+            && model.options.as_ref().and_then(|options| options.expire_after) != index.expire_after()
+    })
+}
+
+/// `name`(기존 인덱스 이름)을 지우고 `index`를 만듦
+async fn recreate(collection: &Collection<Document>, index: &ExpectedIndex, name: &str) -> mongodb::error::Result<()> {
+    collection.drop_index(name, None).await?;
+    collection.create_index(index.model.clone(), None).await?;
+    Ok(())
+}
+
+async fn index_present(collection: &Collection<Document>, index: &ExpectedIndex) -> bool {
+    match list_indexes(collection).await {
+        Ok(existing) => existing.iter().any(|model| index.matches(model)),
+        Err(e) => {
+            tracing::warn!("could not list indexes of {}: {:#}", index.collection, e);
+            false
+        }
+    }
+}
+
+/// 컬렉션의 인덱스 (컬렉션이 아직 없으면 빈 목록)
+async fn list_indexes(collection: &Collection<Document>) -> Result<Vec<IndexModel>> {
+    let cursor = match collection.list_indexes(None).await {
+        Ok(cursor) => cursor,
+        Err(e) if matches!(&*e.kind, ErrorKind::Command(command) if command.code == NAMESPACE_NOT_FOUND) => {
+            return Ok(Vec::new());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    cursor.try_collect().await.context("could not read indexes")
+}
+
+/// 기대한 인덱스 하나의 상태
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexStatus {
+    Present,
+    Missing,
+    /// 같은 키의 인덱스가 다른 옵션으로 있음
+    Different,
+    /// 인덱스 목록을 읽지 못함
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IndexState {
+    pub collection: String,
+    pub name: String,
+    pub status: IndexStatus,
+}
+
+/// `/api/health`에 표시할 인덱스 비교 결과
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IndexReport {
+    pub checked_at: DateTime<Utc>,
+    /// 기대한 인덱스 중 없거나 옵션이 다른 인덱스가 있음
+    pub drift: bool,
+    pub indexes: Vec<IndexState>,
+}
+
+impl IndexReport {
+    /// 비교 결과 작성 (`existing`에 없는 컬렉션은 목록을 읽지 못한 것으로 처리)
+    pub fn new(expected: &[ExpectedIndex], existing: &HashMap<&str, Vec<IndexModel>>, now: DateTime<Utc>) -> Self {
+        let indexes: Vec<IndexState> = expected
+            .iter()
+            .map(|index| {
+                let status = match existing.get(index.collection.as_str()) {
+                    None => IndexStatus::Unknown,
+                    Some(models) if models.iter().any(|model| index.matches(model)) => IndexStatus::Present,
+                    Some(models) if models.iter().any(|model| model.keys == index.model.keys) => IndexStatus::Different,
+                    Some(_) => IndexStatus::Missing,
+                };
+                IndexState {
+                    collection: index.collection.clone(),
+                    name: index.name(),
+                    status,
+                }
+            })
+            .collect();
+
+        Self {
+            checked_at: now,
+            drift: indexes
+                .iter()
+                .any(|index| matches!(index.status, IndexStatus::Missing | IndexStatus::Different)),
+            indexes,
+        }
+    }
+
+    /// 없거나 옵션이 다른 인덱스 이름 (`collection.name`)
+    pub fn drifted(&self) -> Vec<String> {
+        self.indexes
+            .iter()
+            .filter(|index| matches!(index.status, IndexStatus::Missing | IndexStatus::Different))
+            .map(|index| format!("{}.{}", index.collection, index.name))
+            .collect()
+    }
+}
+
+/// 실제 인덱스를 읽어 비교
+pub async fn check_indexes(database: &Database, expected: &[ExpectedIndex]) -> IndexReport {
+    let mut existing = HashMap::new();
+    for index in expected {
+        if existing.contains_key(index.collection.as_str()) {
+            continue;
+        }
+        match list_indexes(&database.collection::<Document>(&index.collection)).await {
+            Ok(models) => {
+                existing.insert(index.collection.as_str(), models);
+            }
+            Err(e) => tracing::warn!("could not list indexes of {}: {:#}", index.collection, e),
+        }
+    }
+    IndexReport::new(expected, &existing, Utc::now())
+}
+
+/// 마지막 인덱스 비교 결과
+#[derive(Debug, Default)]
+pub struct IndexHealth {
+    report: Mutex<Option<IndexReport>>,
+}
+
+impl IndexHealth {
+    pub fn record(&self, report: IndexReport) {
+        *self.report.lock().unwrap() = Some(report);
+    }
+
+    pub fn snapshot(&self) -> Option<IndexReport> {
+        self.report.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,281 @@
+//! 이전 데이터베이스 문서를 새 데이터베이스로 옮기는 복사기
+//!
+//! `_id` 순서로 일정 크기씩 옮기고, 진행 상황을 새 데이터베이스의 `migration` 컬렉션에 기록해
+//! 재시작해도 이어서 복사합니다. 새 데이터베이스에 이미 같은 문서(키 필드 기준)가 있으면
+//! 모집글은 더 최근에 기록된 것이므로 덮어쓰지 않고, 플레이어와 Parse 캐시는 이전 문서를 합칩니다 ([`Merge`]).
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use futures_util::TryStreamExt;
+use mongodb::bson::{doc, Bson, Document};
+use mongodb::options::{FindOptions, ReplaceOptions, UpdateModifications, UpdateOptions};
+use mongodb::Database;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Mongo;
+
+/// 진행 상황을 기록하는 컬렉션 (새 데이터베이스)
+pub const META_COLLECTION: &str = "migration";
+
+/// 새 데이터베이스에 같은 문서가 있을 때 이전 문서를 반영하는 방법
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Merge {
+    /// 새 문서가 더 최근이므로 건너뜀 (모집글)
+    Skip,
+    /// 관측 횟수와 마지막 관측 시각은 큰 쪽 (플레이어, [`counts_update`])
+    Counts,
+    /// 새 문서에 없는 Zone 캐시와 필드만 채움 (Parse 캐시, [`zones_update`])
+    Zones,
+}
+
+/// 옮길 컬렉션과 같은 문서를 찾는 키 필드 (목록은 TTL이 짧으므로 먼저 옮김, 이름은 `mongo` 설정)
+pub fn collections(mongo: &Mongo) -> [(&str, &'static [&'static str], Merge); 3] {
+    [
+        (
+            &mongo.listings_collection,
+            &["listing.id", "listing.last_server_restart", "listing.created_world"],
+            Merge::Skip,
+        ),
+        (&mongo.players_collection, &["content_id"], Merge::Counts),
+        (&mongo.parses_collection, &["content_id"], Merge::Zones),
+    ]
+}
+
+/// 이전 플레이어 문서를 합치는 upsert 갱신 (없던 문서면 그대로 옮김)
+///
+/// 복사 중에도 쓰기는 두 데이터베이스에 모두 기록되므로 이전 문서의 관측 횟수에는 새 문서의 관측이 이미 들어 있습니다.
+/// 그래서 관측 횟수와 마지막 관측 시각은 더하지 않고 큰 쪽을 남기며, 다시 실행해도 결과가 같습니다.
+pub fn counts_update(legacy: &Document) -> Document {
+    let mut on_insert = legacy.clone();
+    on_insert.remove("_id");
+    let mut max = Document::new();
+    for field in ["seen_count", "last_seen"] {
+        if let Some(value) = on_insert.remove(field) {
+            max.insert(field, value);
+        }
+    }
+    let mut update = doc! { "$setOnInsert": on_insert };
+    if !max.is_empty() {
+        update.insert("$max", max);
+    }
+    update
+}
+
+/// 이전 Parse 캐시 문서를 합치는 upsert 파이프라인
+///
+/// 새 문서에 이미 있는 Zone 캐시와 필드는 더 최근 것이므로 그대로 두고, 없는 것만 이전 문서에서 채웁니다.
+pub fn zones_update(legacy: &Document) -> Vec<Document> {
+    let mut set = Document::new();
+    for (field, value) in legacy {
+        if field == "_id" || field == "zones" {
+            continue;
+        }
+        set.insert(field, doc! { "$ifNull": [format!("${}", field), { "$literal": value.clone() }] });
+    }
+    let zones = legacy.get_document("zones").cloned().unwrap_or_default();
+    set.insert("zones", doc! { "$mergeObjects": [{ "$literal": zones }, { "$ifNull": ["$zones", {}] }] });
+    vec![doc! { "$set": set }]
+}
+
+const BATCH_SIZE: i64 = 500;
+
+/// 배치 사이 대기 시간 (서비스 쿼리에 여유를 둠)
+const BATCH_PAUSE: Duration = Duration::from_millis(200);
+
+/// 컬렉션별 복사 진행 상황
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyProgress {
+    #[serde(rename = "_id")]
+    pub collection: String,
+    /// 마지막으로 처리한 이전 데이터베이스 문서의 `_id`
+    pub last_id: Option<Bson>,
+    /// 새로 옮긴 문서 수
+    pub copied: u64,
+    /// 새 데이터베이스에 이미 있어 합친 문서 수
+    #[serde(default)]
+    pub merged: u64,
+    /// 새 데이터베이스에 이미 있어 건너뛴 문서 수
+    pub skipped: u64,
+    pub done: bool,
+    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CopyProgress {
+    fn new(collection: &str) -> Self {
+        Self {
+            collection: collection.to_string(),
+            last_id: None,
+            copied: 0,
+            merged: 0,
+            skipped: 0,
+            done: false,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+/// `/api/health`에 표시할 복사 진행 상황
+#[derive(Debug, Default)]
+pub struct MigrationStatus {
+    progress: Mutex<Vec<CopyProgress>>,
+}
+
+impl MigrationStatus {
+    pub fn update(&self, progress: &CopyProgress) {
+        let mut all = self.progress.lock().unwrap();
+        match all.iter_mut().find(|p| p.collection == progress.collection) {
+            Some(existing) => *existing = progress.clone(),
+            None => all.push(progress.clone()),
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<CopyProgress> {
+        self.progress.lock().unwrap().clone()
+    }
+}
+
+/// 문서에서 키 필드만 뽑은 조회 조건 (키가 하나라도 없으면 `None`)
+pub fn key_filter(doc: &Document, keys: &[&str]) -> Option<Document> {
+    let mut filter = Document::new();
+    for key in keys {
+        let mut parts = key.split('.');
+        let mut value = doc.get(parts.next()?)?;
+        for part in parts {
+            value = value.as_document()?.get(part)?;
+        }
+        filter.insert(*key, value.clone());
+    }
+    Some(filter)
+}
+
+/// 모든 컬렉션을 옮김 (이미 끝난 컬렉션은 건너뜀)
+pub async fn copy_all(
+    legacy: &Database,
+    primary: &Database,
+    mongo: &Mongo,
+    status: &MigrationStatus,
+) -> anyhow::Result<()> {
+    for (name, keys, merge) in collections(mongo) {
+        let mut progress = load_progress(primary, name).await?;
+        status.update(&progress);
+        if progress.done {
+            continue;
+        }
+
+        copy_collection(legacy, primary, name, keys, merge, &mut progress, status)
+            .await
+            .with_context(|| format!("could not copy `{}`", name))?;
+        tracing::info!(
+            "Copied `{}` from the legacy database ({} copied, {} merged, {} already present)",
+            name,
+            progress.copied,
+            progress.merged,
+            progress.skipped,
+        );
+    }
+
+    Ok(())
+}
+
+async fn load_progress(primary: &Database, name: &str) -> anyhow::Result<CopyProgress> {
+    let saved = primary
+        .collection::<CopyProgress>(META_COLLECTION)
+        .find_one(doc! { "_id": name }, None)
+        .await?;
+    Ok(saved.unwrap_or_else(|| CopyProgress::new(name)))
+}
+
+async fn save_progress(primary: &Database, progress: &CopyProgress) -> anyhow::Result<()> {
+    primary
+        .collection::<CopyProgress>(META_COLLECTION)
+        .replace_one(
+            doc! { "_id": &progress.collection },
+            progress,
+            ReplaceOptions::builder().upsert(true).build(),
+        )
+        .await?;
+    Ok(())
+}
+
+async fn copy_collection(
+    legacy: &Database,
+    primary: &Database,
+    name: &str,
+    keys: &[&str],
+    merge: Merge,
+    progress: &mut CopyProgress,
+    status: &MigrationStatus,
+) -> anyhow::Result<()> {
+    let source = legacy.collection::<Document>(name);
+    let target = primary.collection::<Document>(name);
+
+    loop {
+        let filter = match &progress.last_id {
+            Some(last_id) => doc! { "_id": { "$gt": last_id.clone() } },
+            None => doc! {},
+        };
+        let options = FindOptions::builder().sort(doc! { "_id": 1 }).limit(BATCH_SIZE).build();
+        let batch: Vec<Document> = source.find(filter, options).await?.try_collect().await?;
+
+        if batch.is_empty() {
+            progress.done = true;
+        }
+
+        for doc in batch {
+            match key_filter(&doc, keys) {
+                Some(filter) if merge != Merge::Skip => {
+                    let update: UpdateModifications = match merge {
+                        Merge::Counts => counts_update(&doc).into(),
+                        _ => zones_update(&doc).into(),
+                    };
+                    let options = UpdateOptions::builder().upsert(true).build();
+                    let result = target.update_one(filter, update, options).await?;
+                    if result.upserted_id.is_some() {
+                        progress.copied += 1;
+                    } else {
+                        progress.merged += 1;
+                    }
+                }
+                filter => {
+                    let existing = match filter {
+                        Some(filter) => target.find_one(filter, None).await?,
+                        None => None,
+                    };
+
+                    if existing.is_some() {
+                        progress.skipped += 1;
+                    } else {
+                        match target.insert_one(&doc, None).await {
+                            Ok(_) => progress.copied += 1,
+                            // 확인 후 서비스 쓰기가 먼저 들어간 경우
+                            Err(e) if is_duplicate_key(&e) => progress.skipped += 1,
+                            Err(e) => return Err(e.into()),
+                        }
+                    }
+                }
+            }
+
+            progress.last_id = doc.get("_id").cloned();
+        }
+
+        progress.updated_at = Utc::now();
+        save_progress(primary, progress).await?;
+        status.update(progress);
+
+        if progress.done {
+            return Ok(());
+        }
+        tokio::time::sleep(BATCH_PAUSE).await;
+    }
+}
+
+fn is_duplicate_key(error: &mongodb::error::Error) -> bool {
+    match &*error.kind {
+        mongodb::error::ErrorKind::Write(mongodb::error::WriteFailure::WriteError(e)) => e.code == 11000,
+        _ => false,
+    }
+}
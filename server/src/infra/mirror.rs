@@ -0,0 +1,112 @@
+//! 데이터베이스 이름 변경 중 읽기 / 쓰기 정책
+//!
+//! 설정에 이전 데이터베이스(`mongo.migrate_from`)가 있으면:
+//! - 쓰기는 새 데이터베이스와 이전 데이터베이스에 모두 기록 (이전 쪽 실패는 경고만 남김)
+//! - 플레이어 / Parse 읽기는 새 데이터베이스를 먼저 보고, 없는 문서만 이전 데이터베이스에서 찾음
+//! - 그 밖의 읽기(목록, 통계, 내보내기)는 새 데이터베이스만 사용
+//!
+//! 이전 데이터베이스가 없으면 새 데이터베이스만 사용합니다.
+//! 읽기 설정(`mongo.read_preference`)은 읽기 전용 조회용으로 따로 만든 `Mirrored`에만 붙입니다.
+//! `C`는 보통 `mongodb::Collection`이며, 테스트에서는 메모리 저장소로 바꿔 씁니다.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use mongodb::options::{CollectionOptions, SelectionCriteria};
+use mongodb::{Collection, Database};
+
+#[derive(Debug, Clone)]
+pub struct Mirrored<C> {
+    primary: C,
+    legacy: Option<C>,
+}
+
+impl<T> Mirrored<Collection<T>> {
+    /// 두 데이터베이스의 같은 이름 컬렉션
+    pub fn collection(primary: &Database, legacy: Option<&Database>, name: &str) -> Self {
+        Self::new(primary.collection(name), legacy.map(|db| db.collection(name)))
+    }
+
+    /// 읽기 전용 조회용 같은 컬렉션 (`criteria`가 `None`이면 클라이언트 기본값인 primary 노드)
+    pub fn collection_for_reads(
+        primary: &Database,
+        legacy: Option<&Database>,
+        name: &str,
+        criteria: Option<SelectionCriteria>,
+    ) -> Self {
+        let options = CollectionOptions::builder().selection_criteria(criteria).build();
+        Self::new(
+            primary.collection_with_options(name, options.clone()),
+            legacy.map(|db| db.collection_with_options(name, options)),
+        )
+    }
+}
+
+impl<C: Clone> Mirrored<C> {
+    pub fn new(primary: C, legacy: Option<C>) -> Self {
+        Self { primary, legacy }
+    }
+
+    /// 새 데이터베이스 (목록, 통계 등 대체 조회가 없는 읽기)
+    pub fn primary(&self) -> C {
+        self.primary.clone()
+    }
+
+    /// 두 데이터베이스에 기록하고 새 데이터베이스의 결과를 돌려줌 (이전 데이터베이스 실패는 무시)
+    pub async fn write<F, Fut, R>(&self, op: F) -> anyhow::Result<R>
+    where
+        F: Fn(C) -> Fut,
+        Fut: Future<Output = anyhow::Result<R>>,
+    {
+        let result = op(self.primary.clone()).await;
+
+        if let Some(legacy) = &self.legacy {
+            if let Err(e) = op(legacy.clone()).await {
+                tracing::warn!("write to the legacy database failed: {:#}", e);
+            }
+        }
+
+        result
+    }
+
+    /// ID별 문서 조회 (새 데이터베이스에 없는 ID만 이전 데이터베이스에서 다시 조회)
+    pub async fn read_by_ids<F, Fut, R>(&self, ids: &[u64], op: F) -> anyhow::Result<HashMap<u64, R>>
+    where
+        F: Fn(C, Vec<u64>) -> Fut,
+        Fut: Future<Output = anyhow::Result<HashMap<u64, R>>>,
+    {
+        let mut found = op(self.primary.clone(), ids.to_vec()).await?;
+
+        if let Some(legacy) = &self.legacy {
+            let missing: Vec<u64> = ids.iter().copied().filter(|id| !found.contains_key(id)).collect();
+            if !missing.is_empty() {
+                match op(legacy.clone(), missing).await {
+                    Ok(fallback) => {
+                        for (id, doc) in fallback {
+                            found.entry(id).or_insert(doc);
+                        }
+                    }
+                    Err(e) => tracing::warn!("fallback read from the legacy database failed: {:#}", e),
+                }
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// 문서 하나 조회 (새 데이터베이스에 없으면 이전 데이터베이스에서 조회)
+    pub async fn read_one<F, Fut, R>(&self, op: F) -> anyhow::Result<Option<R>>
+    where
+        F: Fn(C) -> Fut,
+        Fut: Future<Output = anyhow::Result<Option<R>>>,
+    {
+        if let Some(doc) = op(self.primary.clone()).await? {
+            return Ok(Some(doc));
+        }
+
+        match &self.legacy {
+            Some(legacy) => op(legacy.clone()).await,
+            None => Ok(None),
+        }
+    }
+}
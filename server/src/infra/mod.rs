@@ -1,7 +1,15 @@
 //! Infrastructure 레이어 - 외부 시스템 연동
 //!
 //! - `mongo`: MongoDB 데이터베이스
+//! - `indexes`: 시작할 때 만드는 인덱스와 실제 인덱스 비교
+//! - `mirror`: 데이터베이스 이름 변경 중 읽기 / 쓰기 정책
+//! - `migration`: 이전 데이터베이스 문서 복사
+//! - `player_compaction`: 중복 플레이어 문서 병합
 //! - `fflogs`: FFLogs API 및 캐시
 
 pub mod mongo;
+pub mod indexes;
+pub mod mirror;
+pub mod migration;
+pub mod player_compaction;
 pub mod fflogs;
@@ -1,78 +1,295 @@
 use anyhow::Context;
-use crate::listing::PartyFinderListing;
-use crate::listing_container::{ListingContainer, QueriedListing};
-use chrono::{TimeDelta, Utc};
-use futures_util::StreamExt;
-use mongodb::bson::doc;
+use crate::config::ListingSort;
+use crate::listing::description::{detect_language, has_autotranslate};
+use crate::listing::history::ArchivedListing;
+use crate::listing::moderation::{ModerationRecord, ModerationTarget};
+use crate::listing::{Blocklist, DutyType, ListingFilter, PartyFinderListing, PartyMember};
+use crate::listing_container::{
+    description_hash, sanitized_description, sorted_members, ListingContainer, QueriedListing, StoredUpload,
+    MAX_DESCRIPTION_HISTORY, MAX_SLOTS_HISTORY, MAX_UPLOADER_FINGERPRINTS, PRIVATE_CONTAINER_FIELDS,
+};
+use crate::stats::summary::SummaryCounts;
+use chrono::{DateTime, TimeDelta, Utc};
+use futures_util::{StreamExt, TryStreamExt};
+use mongodb::bson::{doc, Bson, Document};
 use mongodb::results::UpdateResult;
 use mongodb::Collection;
-use mongodb::options::UpdateOptions;
+use mongodb::options::{FindOneAndUpdateOptions, FindOneOptions, FindOptions, ReturnDocument, UpdateOptions};
 
-pub async fn get_current_listings(
-    collection: Collection<ListingContainer>,
-) -> anyhow::Result<Vec<QueriedListing>> {
-    let one_hour_ago = Utc::now() - TimeDelta::try_hours(1).unwrap();
-    let cursor = collection
-        .aggregate(
-            [
-                // don't ask me why, but mongo shits itself unless you provide a hard date
-                // doc! {
-                //     "$match": {
-                //         "created_at": {
-                //             "$gte": {
-                //                 "$dateSubtract": {
-                //                     "startDate": "$$NOW",
-                //                     "unit": "hour",
-                //                     "amount": 2,
-                //                 },
-                //             },
-                //         },
-                //     }
-                // },
-                doc! {
-                    "$match": {
-                        "updated_at": { "$gte": one_hour_ago },
-                    }
-                },
-                doc! {
-                    "$match": {
-                        // filter private pfs
-                        "listing.search_area": { "$bitsAllClear": 2 },
-                    }
-                },
-                doc! {
-                    "$set": {
-                        "time_left": {
-                            "$divide": [
-                                {
-                                    "$subtract": [
-                                        { "$multiply": ["$listing.seconds_remaining", 1000] },
-                                        { "$subtract": ["$$NOW", "$updated_at"] },
-                                    ]
-                                },
-                                1000,
+/// 공개 목록 조회 파이프라인 (`updated_since` 이후 갱신된 공개 모집글, 숨긴 듀티 / 카테고리와
+/// 모더레이터가 숨긴 모집글, 금칙어로 표시된 모집글 제외)
+///
+/// 스냅샷에서 빠진 모집글은 `unconfirmed_since` 이후에 빠진 것만 남깁니다.
+pub fn current_listings_pipeline(
+    updated_since: DateTime<Utc>,
+    unconfirmed_since: DateTime<Utc>,
+    blocklist: &Blocklist,
+) -> Vec<Document> {
+    let mut pipeline = vec![
+        // don't ask me why, but mongo shits itself unless you provide a hard date
+        // doc! {
+        //     "$match": {
+        //         "created_at": {
+        //             "$gte": {
+        //                 "$dateSubtract": {
+        //                     "startDate": "$$NOW",
+        //                     "unit": "hour",
+        //                     "amount": 2,
+        //                 },
+        //             },
+        //         },
+        //     }
+        // },
+        doc! {
+            "$match": {
+                "updated_at": { "$gte": updated_since },
+                "hidden": { "$ne": true },
+                "flagged": { "$ne": true },
+                "$or": [
+                    { "unconfirmed_at": null },
+                    { "unconfirmed_at": { "$gte": unconfirmed_since } },
+                ],
+            }
+        },
+        doc! {
+            "$match": {
+                // filter private pfs
+                "listing.search_area": { "$bitsAllClear": 2 },
+            }
+        },
+        doc! {
+            "$set": {
+                "time_left": {
+                    "$divide": [
+                        {
+                            "$subtract": [
+                                { "$multiply": ["$listing.seconds_remaining", 1000] },
+                                { "$subtract": ["$$NOW", "$updated_at"] },
                             ]
                         },
-                        "updated_minute": {
-                            "$dateTrunc": {
-                                "date": "$updated_at",
-                                "unit": "minute",
-                                "binSize": 5,
-                            },
-                        },
-                    }
+                        1000,
+                    ]
                 },
-                doc! {
-                    "$match": {
-                        "time_left": { "$gte": 0 },
-                    }
+                "uploader_count": {
+                    "$size": { "$ifNull": ["$uploader_fingerprints", []] },
                 },
+            }
+        },
+        doc! {
+            "$match": {
+                "time_left": { "$gte": 0 },
+            }
+        },
+        doc! {
+            "$unset": PRIVATE_CONTAINER_FIELDS.to_vec(),
+        },
+    ];
+
+    if !blocklist.is_empty() {
+        pipeline.insert(2, blocklist_match(blocklist));
+    }
+    pipeline
+}
+
+/// 집계에서 쓰는 `PartyFinderListing::canonical_category` (저장되기 전 문서는 업로드된 분류)
+pub fn canonical_category_expr() -> Document {
+    doc! { "$ifNull": ["$canonical_category", "$listing.category"] }
+}
+
+/// 숨긴 듀티 / 카테고리를 제외하는 `$match` 단계 (`Blocklist::blocks`와 같은 규칙)
+pub fn blocklist_match(blocklist: &Blocklist) -> Document {
+    let categories: Vec<i64> = blocklist.categories.iter().map(|&category| i64::from(category as u32)).collect();
+    let duties: Vec<i32> = blocklist.duties.iter().map(|&duty| i32::from(duty)).collect();
+
+    doc! {
+        "$match": {
+            "$or": [
+                { "canonical_category": { "$exists": true, "$nin": &categories } },
+                { "canonical_category": { "$exists": false }, "listing.category": { "$nin": &categories } },
             ],
-            None,
-        )
-        .await?;
+            "$nor": [{
+                "listing.duty_type": DutyType::Normal as i32,
+                "listing.duty": { "$in": duties },
+            }],
+        }
+    }
+}
+
+/// 조건에 맞는 모집글만 남기는 `$match` 단계 (분류는 `blocklist_match`와 같이 `canonical_category` 기준)
+pub fn listing_filter_match(filter: &ListingFilter) -> Document {
+    let mut stage = Document::new();
+    if !filter.duties.is_empty() {
+        let duties: Vec<i32> = filter.duties.iter().map(|&duty| i32::from(duty)).collect();
+        stage.insert("listing.duty_type", DutyType::Normal as i32);
+        stage.insert("listing.duty", doc! { "$in": duties });
+    }
+    if !filter.worlds.is_empty() {
+        let worlds: Vec<i32> = filter.worlds.iter().map(|&world| i32::from(world)).collect();
+        stage.insert("listing.created_world", doc! { "$in": worlds });
+    }
+    if !filter.categories.is_empty() {
+        let categories: Vec<i64> = filter.categories.iter().map(|&category| i64::from(category as u32)).collect();
+        stage.insert(
+            "$or",
+            vec![
+                doc! { "canonical_category": { "$in": &categories } },
+                doc! { "canonical_category": { "$exists": false }, "listing.category": { "$in": &categories } },
+            ],
+        );
+    }
+    doc! { "$match": stage }
+}
+
+/// `current_listings_pipeline`에 `filter` 조건을 더한 파이프라인 (조건은 시간 조건 바로 뒤)
+///
+/// `filter.flagged`면 금칙어로 표시된 모집글만 남기고, 편집 전 설명을 확인할 수 있게
+/// `description_history`를 지우지 않습니다 (모더레이터 확인용).
+pub fn filtered_listings_pipeline(
+    updated_since: DateTime<Utc>,
+    unconfirmed_since: DateTime<Utc>,
+    blocklist: &Blocklist,
+    filter: &ListingFilter,
+) -> Vec<Document> {
+    let mut pipeline = current_listings_pipeline(updated_since, unconfirmed_since, blocklist);
+    if filter.flagged {
+        if let Ok(stage) = pipeline[0].get_document_mut("$match") {
+            stage.insert("flagged", true);
+        }
+        if let Some(stage) = pipeline.iter_mut().find(|stage| stage.contains_key("$unset")) {
+            let fields: Vec<&str> =
+                PRIVATE_CONTAINER_FIELDS.into_iter().filter(|&field| field != "description_history").collect();
+            stage.insert("$unset", fields);
+        }
+    }
+    if !filter.is_empty() {
+        pipeline.insert(1, listing_filter_match(filter));
+    }
+    pipeline
+}
+
+/// 공개 목록에 표시할 활성 모집글 (정렬 구간은 `sort` 설정으로 계산, `blocklist`의 모집글 제외)
+///
+/// 마지막 업데이트가 `visible_window`보다 오래된 모집글과
+/// 스냅샷에서 빠진 뒤 `unconfirmed_window`가 지난 모집글은 제외합니다.
+pub async fn get_current_listings(
+    collection: Collection<ListingContainer>,
+    sort: &ListingSort,
+    blocklist: &Blocklist,
+    visible_window: TimeDelta,
+    unconfirmed_window: TimeDelta,
+) -> anyhow::Result<Vec<QueriedListing>> {
+    get_filtered_listings(collection, sort, blocklist, visible_window, unconfirmed_window, &ListingFilter::default()).await
+}
+
+/// `filter` 조건에 맞는 활성 모집글 (조건은 aggregation에서 적용)
+pub async fn get_filtered_listings(
+    collection: Collection<ListingContainer>,
+    sort: &ListingSort,
+    blocklist: &Blocklist,
+    visible_window: TimeDelta,
+    unconfirmed_window: TimeDelta,
+    filter: &ListingFilter,
+) -> anyhow::Result<Vec<QueriedListing>> {
+    let updated_since = Utc::now() - visible_window;
+    let unconfirmed_since = Utc::now() - unconfirmed_window;
+    let pipeline = filtered_listings_pipeline(updated_since, unconfirmed_since, blocklist, filter);
+    aggregate_current(collection, pipeline, sort).await
+}
+
+/// ID가 같은 활성 모집글 중 가장 최근에 업데이트된 것 (`created_world`가 있으면 그 서버의 모집글만)
+///
+/// 목록과 같은 파이프라인을 거치므로 `time_left`와 숨김 / 만료 조건이 `/api/listings`와 같습니다.
+pub fn listing_by_id_pipeline(
+    updated_since: DateTime<Utc>,
+    unconfirmed_since: DateTime<Utc>,
+    blocklist: &Blocklist,
+    id: u32,
+    created_world: Option<u16>,
+) -> Vec<Document> {
+    let mut pipeline = current_listings_pipeline(updated_since, unconfirmed_since, blocklist);
+    let mut stage = doc! { "listing.id": id };
+    if let Some(world) = created_world {
+        stage.insert("listing.created_world", i32::from(world));
+    }
+    pipeline.insert(1, doc! { "$match": stage });
+    pipeline.push(doc! { "$sort": { "updated_at": -1 } });
+    pipeline.push(doc! { "$limit": 1 });
+    pipeline
+}
+
+/// 활성 모집글 하나 (`listing_by_id_pipeline`, 없으면 `None`)
+pub async fn get_listing_by_id(
+    collection: Collection<ListingContainer>,
+    sort: &ListingSort,
+    blocklist: &Blocklist,
+    visible_window: TimeDelta,
+    unconfirmed_window: TimeDelta,
+    id: u32,
+    created_world: Option<u16>,
+) -> anyhow::Result<Option<QueriedListing>> {
+    let updated_since = Utc::now() - visible_window;
+    let unconfirmed_since = Utc::now() - unconfirmed_window;
+    let pipeline = listing_by_id_pipeline(updated_since, unconfirmed_since, blocklist, id, created_world);
+    Ok(aggregate_current(collection, pipeline, sort).await?.pop())
+}
+
+/// 현재 모집글 요약 파이프라인 (`current_listings_pipeline`과 같은 조건으로 분류별 / 듀티별 / 월드별 개수)
+///
+/// 듀티는 일반 듀티(`DutyType::Normal`)만 모집글이 많은 순으로 `top_duties`개 남깁니다.
+pub fn listing_summary_pipeline(
+    updated_since: DateTime<Utc>,
+    unconfirmed_since: DateTime<Utc>,
+    blocklist: &Blocklist,
+    top_duties: usize,
+) -> Vec<Document> {
+    let mut pipeline = current_listings_pipeline(updated_since, unconfirmed_since, blocklist);
+    pipeline.push(doc! {
+        "$facet": {
+            "total": [{ "$count": "count" }],
+            "categories": [
+                { "$group": { "_id": canonical_category_expr(), "count": { "$sum": 1 } } },
+                { "$sort": { "count": -1, "_id": 1 } },
+            ],
+            "duties": [
+                { "$match": { "listing.duty_type": DutyType::Normal as i32, "listing.duty": { "$ne": 0 } } },
+                { "$group": { "_id": "$listing.duty", "count": { "$sum": 1 } } },
+                { "$sort": { "count": -1, "_id": 1 } },
+                { "$limit": top_duties as i64 },
+            ],
+            "worlds": [
+                { "$group": { "_id": "$listing.created_world", "count": { "$sum": 1 } } },
+            ],
+        }
+    });
+    pipeline
+}
+
+/// 현재 모집글 요약 개수 (`listing_summary_pipeline`, 모집글은 읽지 않음)
+pub async fn listing_summary(
+    collection: Collection<ListingContainer>,
+    blocklist: &Blocklist,
+    visible_window: TimeDelta,
+    unconfirmed_window: TimeDelta,
+    top_duties: usize,
+) -> anyhow::Result<SummaryCounts> {
+    let updated_since = Utc::now() - visible_window;
+    let unconfirmed_since = Utc::now() - unconfirmed_window;
+    let pipeline = listing_summary_pipeline(updated_since, unconfirmed_since, blocklist, top_duties);
+    let counts = match collection.aggregate(pipeline, None).await?.try_next().await? {
+        Some(doc) => mongodb::bson::from_document(doc).context("could not read listing summary")?,
+        None => SummaryCounts::default(),
+    };
+    Ok(counts)
+}
 
-    let collect = cursor
+async fn aggregate_current(
+    collection: Collection<ListingContainer>,
+    pipeline: Vec<Document>,
+    sort: &ListingSort,
+) -> anyhow::Result<Vec<QueriedListing>> {
+    let cursor = collection.aggregate(pipeline, None).await?;
+
+    let mut collect: Vec<QueriedListing> = cursor
         .filter_map(async |res| {
             res.ok()
                 .and_then(|doc| mongodb::bson::from_document(doc).ok())
@@ -80,65 +297,487 @@ pub async fn get_current_listings(
         .collect::<Vec<_>>()
         .await;
 
+    // 남은 시간은 한 시점 기준으로 다시 계산 (DB 서버 시계와의 차이 제거)
+    // 정렬 구간은 듀티 분류마다 달라서 aggregation 대신 여기서 계산
+    let now = Utc::now();
+    for listing in &mut collect {
+        listing.refresh_expiry(now);
+        listing.refresh_bucket(sort);
+    }
+
     Ok(collect)
 }
 
+/// 업로드한 모집글의 저장 키 (id, created_world, last_server_restart)
+pub fn upload_filter(listing: &PartyFinderListing) -> Document {
+    doc! {
+        "listing.id": listing.id,
+        "listing.last_server_restart": listing.last_server_restart,
+        "listing.created_world": listing.created_world as u32,
+    }
+}
+
+/// 업로드 한 건을 반영하는 업데이트 파이프라인 (`insert_listing`)
+///
+/// `snapshot_at`이 있는 업로드는 저장된 스냅샷보다 새로울 때만 모집글과 설명을 덮어쓰고,
+/// 오래된 스냅샷이어도 `updated_at`과 업로드 집계는 갱신해 모집글이 만료되지 않게 합니다.
+/// 모더레이터가 숨긴 모집글은 `hidden`을 건드리지 않으므로 다시 올려도 숨김이 유지됩니다.
+/// `flagged`(설명 금칙어)는 설명과 같이 업로드마다 다시 기록합니다.
+/// 어느 업로더든 다시 올리면 스냅샷에서 빠졌던 기록(`unconfirmed_at`)은 지웁니다.
+pub fn upload_pipeline(
+    listing: &PartyFinderListing,
+    fingerprint: &str,
+    flagged: bool,
+    now: DateTime<Utc>,
+) -> anyhow::Result<Vec<Document>> {
+    // 미래 시각의 스냅샷은 지금 찍은 것으로 취급 (이후 업로드가 모두 오래된 것으로 밀리지 않게)
+    let mut listing = listing.clone();
+    listing.snapshot_at = listing.snapshot_at.map(|snapshot_at| snapshot_at.min(now));
+    let listing = &listing;
+    let bson_value = mongodb::bson::to_bson(listing)?;
+    let description = sanitized_description(listing);
+    let hash = description_hash(&description);
+    let language = mongodb::bson::to_bson(&detect_language(&description))?;
+    let autotranslate = has_autotranslate(&listing.description);
+    let canonical_category = listing.canonical_category() as u32 as i64;
+    // 저장된 `snapshot_at`은 문자열이므로 날짜로 바꿔 비교 (없으면 null이라 항상 더 새로움)
+    let applies: Bson = match listing.snapshot_at {
+        Some(snapshot_at) => doc! { "$gt": [snapshot_at, { "$toDate": "$listing.snapshot_at" }] }.into(),
+        None => true.into(),
+    };
+    let unless_stale = |value: Bson, field: &str| -> Document {
+        doc! { "$cond": [applies.clone(), value, format!("${}", field)] }
+    };
+    // 업로드 집계와 설명 이력을 한 번의 파이프라인 업데이트로 처리
+    // 설명 해시가 바뀐 경우에만 이전 설명을 이력에 추가하고, 오래된 항목부터 버림
+    let description_history = doc! {
+        "$let": {
+            "vars": { "history": { "$ifNull": ["$description_history", []] } },
+            "in": {
+                "$cond": [
+                    {
+                        "$and": [
+                            { "$eq": [{ "$type": "$description_hash" }, "string"] },
+                            { "$ne": ["$description_hash", &hash] },
+                        ]
+                    },
+                    {
+                        "$slice": [
+                            {
+                                "$concatArrays": [
+                                    "$$history",
+                                    [{
+                                        "text": { "$ifNull": ["$description_text", ""] },
+                                        "replaced_at": "$$NOW",
+                                    }],
+                                ]
+                            },
+                            -(MAX_DESCRIPTION_HISTORY as i32),
+                        ]
+                    },
+                    "$$history",
+                ]
+            },
+        }
+    };
+    // 채워진 자리 수가 마지막 기록과 다를 때만 추가하고, 오래된 항목부터 버림
+    let slots_filled = listing.filled_total() as i64;
+    let slots_history = doc! {
+        "$let": {
+            "vars": { "history": { "$ifNull": ["$slots_history", []] } },
+            "in": {
+                "$cond": [
+                    { "$eq": [{ "$arrayElemAt": ["$$history.slots_filled", -1] }, slots_filled] },
+                    "$$history",
+                    {
+                        "$slice": [
+                            { "$concatArrays": ["$$history", [{ "at": "$$NOW", "slots_filled": slots_filled }]] },
+                            -(MAX_SLOTS_HISTORY as i32),
+                        ]
+                    },
+                ]
+            },
+        }
+    };
+    // 멤버 / 파티장 Content ID는 `/contribute/detail`로만 들어오므로 업로드에 없으면 저장된 값 유지
+    let mut party_detail = Document::new();
+    if listing.member_content_ids.is_empty() {
+        party_detail.insert("member_content_ids", doc! { "$ifNull": ["$listing.member_content_ids", []] });
+    }
+    if listing.members.is_empty() {
+        party_detail.insert("members", doc! { "$ifNull": ["$listing.members", []] });
+    }
+    if listing.leader_content_id == 0 {
+        party_detail.insert("leader_content_id", doc! { "$ifNull": ["$listing.leader_content_id", 0_i64] });
+    }
+    let stored_listing = doc! { "$mergeObjects": [{ "$literal": bson_value }, party_detail] };
+    // 새 문서인지, 오래된 스냅샷인지는 갱신 전 값으로 판단
+    let outcome = doc! {
+        "$cond": [
+            { "$eq": [{ "$type": "$created_at" }, "missing"] },
+            "inserted",
+            { "$cond": [applies.clone(), "updated", "stale"] },
+        ]
+    };
+    Ok(vec![doc! {
+        "$set": {
+            "upload_outcome": outcome,
+            "previous_listing": "$listing",
+            "updated_at": "$$NOW",
+            "created_at": { "$ifNull": ["$created_at", now] },
+            "listing": unless_stale(stored_listing.into(), "listing"),
+            "upload_count": { "$add": [{ "$ifNull": ["$upload_count", 0] }, 1] },
+            "uploader_fingerprints": {
+                "$let": {
+                    "vars": { "fps": { "$ifNull": ["$uploader_fingerprints", []] } },
+                    "in": {
+                        "$cond": [
+                            {
+                                "$or": [
+                                    { "$in": [fingerprint, "$$fps"] },
+                                    { "$gte": [{ "$size": "$$fps" }, MAX_UPLOADER_FINGERPRINTS as i32] },
+                                ]
+                            },
+                            "$$fps",
+                            { "$concatArrays": ["$$fps", [fingerprint]] },
+                        ]
+                    },
+                }
+            },
+            // 같은 `$set` 안의 필드 참조는 갱신 전 값이므로 이전 설명을 그대로 옮길 수 있음
+            "description_history": unless_stale(description_history.into(), "description_history"),
+            "description_hash": unless_stale(hash.clone().into(), "description_hash"),
+            "description_text": unless_stale(description.into(), "description_text"),
+            "description_language": unless_stale(language, "description_language"),
+            "has_autotranslate": unless_stale(autotranslate.into(), "has_autotranslate"),
+            "flagged": unless_stale(flagged.into(), "flagged"),
+            "slots_history": unless_stale(slots_history.into(), "slots_history"),
+            // 업로드된 `listing.category`는 그대로 두고 따로 저장
+            "canonical_category": unless_stale(canonical_category.into(), "canonical_category"),
+        },
+    }, doc! {
+        // 덮어쓴 모집글이 이전과 같으면 바뀌지 않은 것으로 기록
+        // (`snapshot_at`은 다시 올릴 때마다 바뀌므로 비교하지 않음)
+        "$set": {
+            "upload_outcome": {
+                "$cond": [
+                    {
+                        "$and": [
+                            { "$eq": ["$upload_outcome", "updated"] },
+                            {
+                                "$eq": [
+                                    { "$mergeObjects": ["$previous_listing", { "snapshot_at": null }] },
+                                    { "$mergeObjects": ["$listing", { "snapshot_at": null }] },
+                                ]
+                            },
+                        ]
+                    },
+                    "unchanged",
+                    "$upload_outcome",
+                ]
+            },
+        },
+    }, doc! {
+        // 어느 업로더든 다시 올리면 스냅샷에서 빠졌던 기록을 지움
+        "$unset": ["unconfirmed_at", "previous_listing"],
+    }])
+}
+
+/// 업로드 후 문서에 기록된 결과 (`upload_pipeline`의 `upload_outcome`)
+pub fn stored_upload(doc: &Document, flagged: bool) -> anyhow::Result<StoredUpload> {
+    let outcome = mongodb::bson::from_bson(doc.get("upload_outcome").cloned().unwrap_or(Bson::Null))
+        .context("could not read upload outcome")?;
+    let hidden = doc.get_bool("hidden").unwrap_or(false);
+    Ok(StoredUpload { outcome, hidden, flagged })
+}
+
+/// 모집글 업로드 저장 (`upload_pipeline`)
+///
+/// 결과는 파이프라인이 기록한 `upload_outcome`을 갱신 후 문서에서 읽습니다.
 pub async fn insert_listing(
     collection: Collection<ListingContainer>,
     listing: &PartyFinderListing,
-) -> anyhow::Result<UpdateResult> {
-    if listing.created_world >= 1_000
-        || listing.home_world >= 1_000
-        || listing.current_world >= 1_000
-    {
-        anyhow::bail!("invalid listing");
-    }
+    fingerprint: &str,
+    flagged: bool,
+) -> anyhow::Result<StoredUpload> {
+    listing.validate()?;
 
-    let opts = UpdateOptions::builder().upsert(true).build();
-    let bson_value = mongodb::bson::to_bson(&listing)?;
-    let now = Utc::now();
+    let opts = FindOneAndUpdateOptions::builder()
+        .upsert(true)
+        .return_document(ReturnDocument::After)
+        .projection(doc! { "upload_outcome": 1, "hidden": 1 })
+        .build();
+    let update = upload_pipeline(listing, fingerprint, flagged, Utc::now())?;
+
+    let stored = collection
+        .clone_with_type::<Document>()
+        .find_one_and_update(upload_filter(listing), update, opts)
+        .await
+        .context("could not insert record")?
+        .context("upsert returned no document")?;
+
+    stored_upload(&stored, flagged)
+}
+
+/// 모더레이터 숨김 / 해제 (`hidden` 기록, 조건에 맞은 모집글 수 반환)
+pub async fn set_listing_hidden(
+    collection: Collection<ListingContainer>,
+    target: &ModerationTarget,
+    hidden: bool,
+) -> anyhow::Result<u64> {
+    let result = collection
+        .update_many(target.filter(), doc! { "$set": { "hidden": hidden } }, None)
+        .await
+        .context("could not update hidden listings")?;
+    Ok(result.matched_count)
+}
+
+/// `moderation_log`에 숨김 / 해제 기록 추가
+pub async fn insert_moderation_record(
+    collection: Collection<ModerationRecord>,
+    record: &ModerationRecord,
+) -> anyhow::Result<()> {
     collection
-        .update_one(
+        .insert_one(record, None)
+        .await
+        .context("could not insert moderation record")?;
+    Ok(())
+}
+
+/// `/contribute/detail`의 파티 상세 정보를 모집글에 기록하는 업데이트 (조건은 `listing.id`)
+///
+/// 멤버는 자리 순서로 정리해 저장합니다 (`sorted_members`).
+pub fn party_detail_update(
+    leader_content_id: u64,
+    member_content_ids: &[u64],
+    members: &[PartyMember],
+) -> anyhow::Result<Document> {
+    let member_content_ids: Vec<i64> = member_content_ids.iter().map(|&id| id as i64).collect();
+    Ok(doc! {
+        "$set": {
+            "listing.member_content_ids": member_content_ids,
+            "listing.members": mongodb::bson::to_bson(&sorted_members(members))?,
+            "listing.leader_content_id": leader_content_id as i64,
+        }
+    })
+}
+
+/// 스냅샷에서 빠진 모집글에 `now`를 기록하는 업데이트 (`mark_unconfirmed`)
+pub fn unconfirmed_update(now: DateTime<Utc>) -> Document {
+    doc! { "$set": { "unconfirmed_at": now } }
+}
+
+/// 스냅샷에서 빠진 모집글에 `unconfirmed_at` 기록 (조건은 `SnapshotScope::absent_filter`)
+///
+/// 기록한 모집글 수를 반환합니다.
+pub async fn mark_unconfirmed(collection: Collection<ListingContainer>, filter: Document) -> anyhow::Result<u64> {
+    let result = collection
+        .update_many(filter, unconfirmed_update(Utc::now()), None)
+        .await
+        .context("could not mark unconfirmed listings")?;
+    Ok(result.modified_count)
+}
+
+/// 저장된 모집글 문서를 그대로 조회 (디버깅용)
+///
+/// `insert_listing`과 같은 키 (id, created_world, last_server_restart)를 사용합니다.
+pub async fn get_raw_listing(
+    collection: Collection<ListingContainer>,
+    id: u32,
+    created_world: u16,
+    last_server_restart: u32,
+) -> anyhow::Result<Option<Document>> {
+    collection
+        .clone_with_type::<Document>()
+        .find_one(
             doc! {
-                "listing.id": listing.id,
-                "listing.last_server_restart": listing.last_server_restart,
-                "listing.created_world": listing.created_world as u32,
+                "listing.id": id,
+                "listing.last_server_restart": last_server_restart,
+                "listing.created_world": created_world as u32,
             },
+            None,
+        )
+        .await
+        .context("could not get listing")
+}
+
+/// DB에 아직 남아 있는 모집글의 키 (`PartyFinderListing::key`, 만료 여부와 무관)
+pub async fn existing_listing_keys(
+    collection: Collection<ListingContainer>,
+    keys: &[(u32, u16, u32)],
+) -> anyhow::Result<std::collections::HashSet<String>> {
+    if keys.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let filters: Vec<Document> = keys
+        .iter()
+        .map(|&(id, created_world, last_server_restart)| doc! {
+            "listing.id": id,
+            "listing.last_server_restart": last_server_restart,
+            "listing.created_world": created_world as u32,
+        })
+        .collect();
+
+    let containers: Vec<ListingContainer> = collection
+        .find(doc! { "$or": filters }, None)
+        .await
+        .context("could not query listings")?
+        .filter_map(async |res| res.ok())
+        .collect()
+        .await;
+
+    Ok(containers.iter().map(|container| container.listing.key()).collect())
+}
+
+/// 데이터셋으로 내보낼 때까지 모집글을 옮겨 두는 컬렉션 (TTL 없음, 내보낸 날짜는 지움)
+pub const EXPORT_STAGING_COLLECTION: &str = "listings_export";
+
+/// `since` 이후 갱신된 공개 모집글을 `EXPORT_STAGING_COLLECTION`에 복사하는 파이프라인
+///
+/// 모집글 컬렉션은 TTL로 지워지므로 하루치를 내보낼 때까지 따로 남겨 둡니다.
+/// 공개 조회에서 빼는 필드(업로더 지문, 이전 설명 등)는 옮기지 않습니다.
+pub fn export_staging_pipeline(since: DateTime<Utc>) -> Vec<Document> {
+    vec![
+        doc! {
+            "$match": {
+                "updated_at": { "$gte": since },
+                // filter private pfs
+                "listing.search_area": { "$bitsAllClear": 2 },
+            }
+        },
+        doc! { "$unset": PRIVATE_CONTAINER_FIELDS.to_vec() },
+        doc! {
+            "$merge": {
+                "into": EXPORT_STAGING_COLLECTION,
+                "on": "_id",
+                "whenMatched": "replace",
+                "whenNotMatched": "insert",
+            }
+        },
+    ]
+}
+
+/// `export_staging_pipeline`으로 내보낼 모집글 복사
+pub async fn stage_listings_for_export(
+    collection: Collection<ListingContainer>,
+    since: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    collection
+        .aggregate(export_staging_pipeline(since), None)
+        .await
+        .context("could not stage listings for export")?;
+    Ok(())
+}
+
+/// 내보낸 기간의 복사본 삭제 (지운 수)
+pub async fn delete_exported_listings(
+    collection: Collection<ListingContainer>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    let result = collection
+        .delete_many(doc! { "updated_at": { "$gte": from, "$lt": to } }, None)
+        .await
+        .context("could not delete exported listings")?;
+    Ok(result.deleted_count)
+}
+
+/// 가장 오래된 복사본의 갱신 시각 (복사본이 없으면 `None`)
+pub async fn oldest_staged_listing(collection: Collection<ListingContainer>) -> anyhow::Result<Option<DateTime<Utc>>> {
+    let options = FindOneOptions::builder().sort(doc! { "updated_at": 1 }).build();
+    let oldest = collection
+        .find_one(doc! {}, options)
+        .await
+        .context("could not read staged listings")?;
+    Ok(oldest.map(|container| container.updated_at))
+}
+
+/// 기간 안에 마지막으로 갱신된 공개 모집글을 순회하는 커서 (데이터셋 내보내기용)
+pub async fn listings_updated_between(
+    collection: Collection<ListingContainer>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> anyhow::Result<mongodb::Cursor<ListingContainer>> {
+    collection
+        .find(
             doc! {
-                "$currentDate": {
-                    "updated_at": true,
-                },
-                "$set": {
-                    "listing": bson_value,
-                },
-                "$setOnInsert": {
-                    "created_at": now,
-                },
+                "updated_at": { "$gte": from, "$lt": to },
+                // filter private pfs
+                "listing.search_area": { "$bitsAllClear": 2 },
             },
-            opts,
+            None,
         )
         .await
-        .context("could not insert record")
+        .context("could not query listings")
+}
+
+/// 기준 시각 이후 갱신된 모집글 수 (점검 자동 감지용)
+pub async fn count_active_listings(
+    collection: Collection<ListingContainer>,
+    since: DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    collection
+        .count_documents(doc! { "updated_at": { "$gte": since } }, None)
+        .await
+        .context("could not count listings")
 }
 
 /// 플레이어 정보를 upsert (있으면 업데이트, 없으면 삽입)
+///
+/// 한 번의 `update` 명령(`ordered: false`)으로 보내므로 문서 하나가 실패해도 나머지는 저장됩니다.
+/// 반환값은 성공한 upsert 수입니다.
 pub async fn upsert_players(
     collection: Collection<crate::player::Player>,
     players: &[crate::player::UploadablePlayer],
 ) -> anyhow::Result<usize> {
-    let mut successful = 0;
-    let now = Utc::now();
+    let updates = player_upserts(players, Utc::now());
+    if updates.is_empty() {
+        return Ok(0);
+    }
+
+    let namespace = collection.namespace();
+    let mut command = doc! {
+        "update": &namespace.coll,
+        "updates": &updates,
+        "ordered": false,
+    };
+    if let Some(write_concern) = collection.write_concern() {
+        command.insert("writeConcern", mongodb::bson::to_bson(write_concern)?);
+    }
 
+    let response = collection
+        .client()
+        .database(&namespace.db)
+        .run_command(command, None)
+        .await
+        .context("could not upsert players")?;
+    Ok(successful_upserts(updates.len(), &response))
+}
+
+/// 플레이어 upsert 문 (`update` 명령의 `updates`)
+///
+/// 잘못된 플레이어(`content_id == 0`, 빈 이름, 월드 ID 1000 이상)는 빼고,
+/// 같은 Content ID가 여러 번 있으면 마지막 것만 보냅니다 (`seen_count`는 한 번만 증가).
+pub fn player_upserts(players: &[crate::player::UploadablePlayer], now: DateTime<Utc>) -> Vec<Document> {
+    let mut latest: Vec<&crate::player::UploadablePlayer> = Vec::with_capacity(players.len());
     for player in players {
         if player.content_id == 0 || player.name.is_empty() || player.home_world >= 1_000 {
             continue;
         }
 
-        let opts = UpdateOptions::builder().upsert(true).build();
-        let result = collection
-            .update_one(
-                doc! { "content_id": player.content_id as i64 },
-                doc! {
+        match latest.iter_mut().find(|seen| seen.content_id == player.content_id) {
+            Some(seen) => *seen = player,
+            None => latest.push(player),
+        }
+    }
+
+    latest
+        .into_iter()
+        .map(|player| {
+            doc! {
+                "q": { "content_id": player.content_id as i64 },
+                "u": {
                     "$set": {
                         "name": &player.name,
                         "home_world": player.home_world as u32,
@@ -149,16 +788,19 @@ pub async fn upsert_players(
                         "content_id": player.content_id as i64,
                     },
                 },
-                opts,
-            )
-            .await;
+                "upsert": true,
+            }
+        })
+        .collect()
+}
 
-        if result.is_ok() {
-            successful += 1;
-        }
+/// `update` 명령 응답에서 성공한 문 수 (`writeErrors`에 있는 문은 실패)
+pub fn successful_upserts(statements: usize, response: &Document) -> usize {
+    let failed = response.get_array("writeErrors").map(|errors| errors.len()).unwrap_or(0);
+    if failed > 0 {
+        tracing::warn!("{} of {} player upserts failed: {:?}", failed, statements, response.get("writeErrors"));
     }
-
-    Ok(successful)
+    statements.saturating_sub(failed)
 }
 
 /// ContentID 목록으로 플레이어 정보 조회
@@ -188,6 +830,32 @@ pub async fn get_players_by_content_ids(
     Ok(players)
 }
 
+/// 이름(대소문자 무시)과 홈 서버로 플레이어 조회 (최근에 본 순서, 최대 `limit`명)
+pub async fn get_players_by_name(
+    collection: Collection<crate::player::Player>,
+    name: &str,
+    home_world: Option<u16>,
+    limit: i64,
+) -> anyhow::Result<Vec<crate::player::Player>> {
+    let mut filter = doc! { "name": name };
+    if let Some(home_world) = home_world {
+        filter.insert("home_world", u32::from(home_world));
+    }
+    let opts = FindOptions::builder()
+        .collation(crate::infra::indexes::player_name_collation())
+        .sort(doc! { "last_seen": -1 })
+        .limit(limit)
+        .build();
+
+    collection
+        .find(filter, opts)
+        .await
+        .context("could not search players")?
+        .try_collect()
+        .await
+        .context("could not read players")
+}
+
 /// 최근 활성 플레이어 전체 조회 (last_seen 7일 이내)
 pub async fn get_all_active_players(
     collection: Collection<crate::player::Player>,
@@ -206,12 +874,97 @@ pub async fn get_all_active_players(
     Ok(players)
 }
 
+/// 하위 32비트만 저장된 플레이어 조회 (중복 병합용)
+pub async fn truncated_players(
+    collection: Collection<crate::player::Player>,
+) -> anyhow::Result<Vec<crate::player::Player>> {
+    let filter = doc! { "content_id": { "$gt": 0_i64, "$lte": i64::from(u32::MAX) } };
+    let players = collection.find(filter, None).await?.try_collect().await?;
+    Ok(players)
+}
+
+/// 하위 32비트가 주어진 값 중 하나인 전체 ContentID 플레이어 조회 (중복 병합용)
+pub async fn players_with_lower_ids(
+    collection: Collection<crate::player::Player>,
+    lower_ids: &HashSet<u64>,
+) -> anyhow::Result<Vec<crate::player::Player>> {
+    use crate::player::compaction::lower_content_id;
+
+    let filter = doc! { "content_id": { "$gt": i64::from(u32::MAX) } };
+    let players = collection
+        .find(filter, None)
+        .await?
+        .try_filter(|player| std::future::ready(lower_ids.contains(&lower_content_id(player.content_id))))
+        .try_collect()
+        .await?;
+    Ok(players)
+}
+
+/// 하위 32비트로 플레이어 조회 (통계의 호스트 이름용, 하위 32비트만 저장된 기록 포함)
+pub async fn players_by_lower_content_ids(
+    collection: Collection<crate::player::Player>,
+    lower_ids: &[u32],
+) -> anyhow::Result<Vec<crate::player::Player>> {
+    if lower_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let modulus = i64::from(u32::MAX) + 1;
+    let filter: Vec<Document> = lower_ids
+        .iter()
+        .map(|&lower| doc! { "content_id": { "$mod": [modulus, i64::from(lower)] } })
+        .collect();
+    let players = collection.find(doc! { "$or": filter }, None).await?.try_collect().await?;
+    Ok(players)
+}
+
+/// 중복 문서의 관측 기록을 대상 문서에 더하는 갱신 (`merge_player_record`)
+///
+/// 관측 횟수는 더하고 마지막 관측 시각은 더 최근 것을 남깁니다.
+/// 읽은 뒤 계산한 값을 덮어쓰지 않으므로 그 사이 들어온 업로드도 잃지 않습니다.
+pub fn merge_player_update(duplicate: &crate::player::Player) -> Document {
+    doc! {
+        "$inc": { "seen_count": i64::from(duplicate.seen_count) },
+        "$max": { "last_seen": duplicate.last_seen },
+        "$addToSet": { "merged_from": duplicate.content_id as i64 },
+    }
+}
+
+/// 대상 문서에 중복 문서의 관측 기록을 합치고 `merged_from`에 중복 ContentID 기록
+///
+/// 이미 `merged_from`에 있는 중복이면 아무것도 바꾸지 않으므로 다시 실행해도 관측 횟수가 두 번 더해지지 않습니다.
+pub async fn merge_player_record(
+    collection: Collection<crate::player::Player>,
+    target: u64,
+    duplicate: &crate::player::Player,
+) -> anyhow::Result<UpdateResult> {
+    collection
+        .update_one(
+            doc! {
+                "content_id": target as i64,
+                "merged_from": { "$ne": duplicate.content_id as i64 },
+            },
+            merge_player_update(duplicate),
+            None,
+        )
+        .await
+        .context("could not merge player record")
+}
+
+pub async fn delete_player(collection: Collection<crate::player::Player>, content_id: u64) -> anyhow::Result<()> {
+    collection
+        .delete_one(doc! { "content_id": content_id as i64 }, None)
+        .await
+        .context("could not delete player")?;
+    Ok(())
+}
+
 // =============================================================================
 // FFLogs Parse 캐시 (타입은 fflogs::cache에 정의됨)
 // =============================================================================
 
-use std::collections::HashMap;
-pub use crate::fflogs::cache::{ParseCacheDoc, ZoneCache, EncounterParse, is_zone_cache_expired};
+use std::collections::{HashMap, HashSet};
+pub use crate::fflogs::cache::{ParseCacheDoc, ZoneCache, EncounterParse, FetchAccounting, is_zone_cache_expired};
 
 /// 플레이어의 특정 Zone 캐시 조회
 pub async fn get_zone_cache(
@@ -231,35 +984,12 @@ pub async fn get_zone_cache(
     Ok(doc.and_then(|d| d.zones.get(&zone_key).cloned()))
 }
 
-/// 여러 플레이어의 특정 Zone 캐시 일괄 조회
-pub async fn get_zone_caches(
+/// 플레이어의 Parse 캐시 문서 조회
+pub async fn get_parse_doc(
     collection: Collection<ParseCacheDoc>,
-    content_ids: &[u64],
-    zone_id: u32,
-) -> anyhow::Result<HashMap<u64, ZoneCache>> {
-    let ids: Vec<i64> = content_ids.iter().map(|&id| id as i64).collect();
-    let zone_key = zone_id.to_string();
-    
-    let cursor = collection
-        .find(
-            doc! { "content_id": { "$in": ids } },
-            None,
-        )
-        .await?;
-    
-    let docs: Vec<ParseCacheDoc> = cursor
-        .filter_map(async |res| res.ok())
-        .collect::<Vec<_>>()
-        .await;
-    
-    let mut result = HashMap::new();
-    for doc in docs {
-        if let Some(zone_cache) = doc.zones.get(&zone_key) {
-            result.insert(doc.content_id as u64, zone_cache.clone());
-        }
-    }
-    
-    Ok(result)
+    content_id: u64,
+) -> anyhow::Result<Option<ParseCacheDoc>> {
+    Ok(collection.find_one(doc! { "content_id": content_id as i64 }, None).await?)
 }
 
 /// 여러 플레이어의 전체 Parse 데이터 일괄 조회 (배치 최적화용)
@@ -289,6 +1019,30 @@ pub async fn get_parse_docs(
     Ok(result)
 }
 
+/// Zone 캐시 일괄 무효화 쿼리 (filter, update)
+///
+/// 해당 Zone 캐시가 있는 모든 문서의 `fetched_at`을 epoch로 바꿔 만료 처리합니다.
+pub fn zone_invalidation(zone_id: u32) -> (Document, Document) {
+    let zone_key = format!("zones.{}", zone_id);
+    let filter = doc! { &zone_key: { "$exists": true } };
+    let update = doc! {
+        "$set": { format!("{}.fetched_at", zone_key): mongodb::bson::DateTime::from_millis(0) },
+    };
+    (filter, update)
+}
+
+/// Zone 캐시 일괄 무효화 (단일 `update_many`)
+pub async fn invalidate_zone_caches(
+    collection: Collection<ParseCacheDoc>,
+    zone_id: u32,
+) -> anyhow::Result<UpdateResult> {
+    let (filter, update) = zone_invalidation(zone_id);
+    collection
+        .update_many(filter, update, None)
+        .await
+        .context("could not invalidate zone caches")
+}
+
 /// Zone 전체 캐시 저장/업데이트
 /// 
 /// content_id 문서가 없으면 생성, 있으면 해당 zone만 갱신
@@ -318,6 +1072,266 @@ pub async fn upsert_zone_cache(
     Ok(())
 }
 
+/// 플레이어 조회 기록 저장 (문서가 없으면 생성)
+pub async fn set_fetch_accounting(
+    collection: Collection<ParseCacheDoc>,
+    content_id: u64,
+    accounting: &FetchAccounting,
+) -> anyhow::Result<()> {
+    let opts = UpdateOptions::builder().upsert(true).build();
+
+    collection
+        .update_one(
+            doc! { "content_id": content_id as i64 },
+            doc! {
+                "$set": { "fetch": mongodb::bson::to_bson(accounting)? },
+                "$setOnInsert": { "content_id": content_id as i64 },
+            },
+            opts,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// 여러 Zone 캐시를 한 번에 저장
+///
+/// 전달된 Zone만 갱신하며, 문서가 없으면 생성합니다.
+pub async fn upsert_zone_caches(
+    collection: Collection<ParseCacheDoc>,
+    content_id: u64,
+    zones: &HashMap<String, ZoneCache>,
+) -> anyhow::Result<()> {
+    let opts = UpdateOptions::builder().upsert(true).build();
+
+    let mut set = mongodb::bson::Document::new();
+    for (zone_key, zone_cache) in zones {
+        set.insert(format!("zones.{}", zone_key), mongodb::bson::to_bson(zone_cache)?);
+    }
+
+    collection
+        .update_one(
+            doc! { "content_id": content_id as i64 },
+            doc! {
+                "$set": set,
+                "$setOnInsert": { "content_id": content_id as i64 },
+            },
+            opts,
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete_parse_doc(collection: Collection<ParseCacheDoc>, content_id: u64) -> anyhow::Result<()> {
+    collection
+        .delete_one(doc! { "content_id": content_id as i64 }, None)
+        .await
+        .context("could not delete parse cache")?;
+    Ok(())
+}
+
+/// Parse 캐시 전체를 content_id 오름차순으로 순회하는 커서 (내보내기용)
+///
+/// `after`가 주어지면 해당 content_id 이후부터 이어서 조회합니다.
+pub async fn parse_docs_cursor(
+    collection: Collection<ParseCacheDoc>,
+    after: Option<i64>,
+    limit: Option<i64>,
+) -> anyhow::Result<mongodb::Cursor<ParseCacheDoc>> {
+    let filter = match after {
+        Some(after) => doc! { "content_id": { "$gt": after } },
+        None => doc! {},
+    };
+    let opts = FindOptions::builder()
+        .sort(doc! { "content_id": 1 })
+        .limit(limit)
+        .build();
+
+    Ok(collection.find(filter, opts).await?)
+}
+
 // Note: 유저 요청에 따라 Parse 데이터에 대한 자동 삭제(TTL) 로직은 제거함.
 // 데이터는 오직 갱신(overwrite)만 되며, 유실되지 않음.
 
+// =============================================================================
+// 역할별 빈 자리 기록
+// =============================================================================
+
+use crate::stats::role_demand::RoleDemandSample;
+
+pub async fn insert_role_demand(
+    collection: Collection<RoleDemandSample>,
+    samples: &[RoleDemandSample],
+) -> anyhow::Result<()> {
+    if !samples.is_empty() {
+        collection.insert_many(samples, None).await?;
+    }
+    Ok(())
+}
+
+/// 데이터 센터의 `since` 이후 기록
+pub async fn role_demand_since(
+    collection: Collection<RoleDemandSample>,
+    data_centre: &str,
+    since: DateTime<Utc>,
+) -> anyhow::Result<Vec<RoleDemandSample>> {
+    let cursor = collection
+        .find(doc! { "data_centre": data_centre, "sampled_at": { "$gte": since } }, None)
+        .await?;
+    let samples = cursor
+        .filter_map(async |res| res.ok())
+        .collect::<Vec<_>>()
+        .await;
+    Ok(samples)
+}
+
+// =============================================================================
+// 일별 모집글 수
+// =============================================================================
+
+use crate::stats::daily::DailyCount;
+
+/// `created_at`이 `after` 초과 `until` 이하인 모집글의 날짜별 수 (`daily::rollup_pipeline`)
+pub async fn count_listings_by_day(
+    collection: Collection<ListingContainer>,
+    after: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> anyhow::Result<Vec<DailyCount>> {
+    let cursor = collection.aggregate(crate::stats::daily::rollup_pipeline(after, until), None).await?;
+    let docs: Vec<Document> = cursor.try_collect().await?;
+    Ok(docs.into_iter().map(mongodb::bson::from_document).collect::<Result<_, _>>()?)
+}
+
+/// 마지막 롤업이 센 `created_at` 상한
+pub async fn daily_listings_watermark(collection: Collection<DailyCount>) -> anyhow::Result<Option<DateTime<Utc>>> {
+    let collection = collection.clone_with_type::<Document>();
+    let options = FindOneOptions::builder().sort(doc! { "rolled_up_until": -1 }).build();
+    let doc = collection.find_one(doc! { "rolled_up_until": { "$exists": true } }, options).await?;
+    Ok(doc.and_then(|doc| doc.get_datetime("rolled_up_until").ok().map(|at| at.to_chrono())))
+}
+
+/// 롤업 결과를 날짜별 문서에 더하고 `until`을 다음 롤업의 시작으로 기록
+///
+/// 모집글이 없던 범위도 상한이 앞으로 가도록 `until`의 날짜 문서는 항상 씁니다.
+pub async fn add_daily_listings(
+    collection: Collection<DailyCount>,
+    counts: &[DailyCount],
+    until: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let collection = collection.clone_with_type::<Document>();
+    let until_day = until.format("%Y-%m-%d").to_string();
+    let mut days: Vec<(&str, u64)> = counts.iter().map(|count| (count.day.as_str(), count.count)).collect();
+    if !days.iter().any(|(day, _)| *day == until_day) {
+        days.push((&until_day, 0));
+    }
+
+    let options = UpdateOptions::builder().upsert(true).build();
+    for (day, count) in days {
+        collection
+            .update_one(
+                doc! { "_id": day },
+                doc! {
+                    "$inc": { "count": count as i64 },
+                    "$max": { "rolled_up_until": until },
+                },
+                options.clone(),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// `first_day` 이후 날짜별 모집글 수 (오래된 순)
+pub async fn daily_listings_since(collection: Collection<DailyCount>, first_day: &str) -> anyhow::Result<Vec<DailyCount>> {
+    let options = FindOptions::builder().sort(doc! { "_id": 1 }).build();
+    let cursor = collection.find(doc! { "_id": { "$gte": first_day } }, options).await?;
+    Ok(cursor.try_collect().await?)
+}
+
+// =============================================================================
+// 모집글 보관 기록
+// =============================================================================
+
+use crate::listing::archive::PendingArchive;
+
+/// TTL로 지워지기 전의 모든 모집글 (보관 기록 채우기용)
+pub async fn all_listings(collection: Collection<ListingContainer>) -> anyhow::Result<Vec<ListingContainer>> {
+    let cursor = collection.find(None, None).await?;
+    Ok(cursor.try_collect().await?)
+}
+
+/// 플레이어가 올린 모집글 조회 조건 (파티장 Content ID 또는 하위 32비트, `history::hosted_by`로 다시 거름)
+pub fn hosted_listings_filter(content_id: u64) -> Document {
+    doc! {
+        "$or": [
+            { "listing.leader_content_id": content_id as i64 },
+            { "listing.content_id_lower": crate::player::compaction::lower_content_id(content_id) as i64 },
+        ]
+    }
+}
+
+/// 하위 32비트가 같은 보관 기록 (최근에 만든 순, 최대 `limit`개)
+pub async fn archived_listings_by_host(
+    collection: Collection<Document>,
+    content_id: u64,
+    limit: usize,
+) -> anyhow::Result<Vec<ArchivedListing>> {
+    let opts = FindOptions::builder()
+        .sort(doc! { "created_at": -1 })
+        .limit(limit as i64)
+        .build();
+    let listings = collection
+        .clone_with_type::<ArchivedListing>()
+        .find(doc! { "listing.content_id_lower": crate::player::compaction::lower_content_id(content_id) as i64 }, opts)
+        .await?
+        .try_collect()
+        .await
+        .context("could not read archived listings")?;
+    Ok(listings)
+}
+
+/// 공개 목록과 같이 모더레이터가 숨기거나 금칙어로 표시된 모집글을 뺀 `hosted_listings_filter`
+pub fn live_hosted_listings_filter(content_id: u64) -> Document {
+    let mut filter = hosted_listings_filter(content_id);
+    filter.insert("hidden", doc! { "$ne": true });
+    filter.insert("flagged", doc! { "$ne": true });
+    filter
+}
+
+/// 아직 남아 있는 모집글 중 이 플레이어가 올렸을 수 있는 것 (만료 여부와 무관, 숨기거나 표시된 모집글 제외)
+pub async fn live_listings_by_host(
+    collection: Collection<ListingContainer>,
+    content_id: u64,
+) -> anyhow::Result<Vec<ListingContainer>> {
+    let listings = collection
+        .find(live_hosted_listings_filter(content_id), None)
+        .await?
+        .try_collect()
+        .await
+        .context("could not read hosted listings")?;
+    Ok(listings)
+}
+
+/// 대기열의 모집글을 `listings_archive`에 upsert (쓴 수, 실패하면 쓰지 못한 모집글과 함께 오류)
+pub async fn archive_listings(
+    collection: Collection<Document>,
+    entries: Vec<PendingArchive>,
+) -> Result<usize, (anyhow::Error, Vec<PendingArchive>)> {
+    let options = UpdateOptions::builder().upsert(true).build();
+    let mut entries = entries.into_iter();
+    let mut written = 0;
+    while let Some(entry) = entries.next() {
+        let result = match entry.update() {
+            Ok(update) => collection.update_one(entry.filter(), update, options.clone()).await.map_err(Into::into),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            let mut unwritten = vec![entry];
+            unwritten.extend(entries);
+            return Err((e, unwritten));
+        }
+        written += 1;
+    }
+    Ok(written)
+}
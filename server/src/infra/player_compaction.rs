@@ -0,0 +1,239 @@
+//! 중복 플레이어 문서 병합 작업
+//!
+//! `player::compaction`의 규칙으로 찾은 중복을 전체 ContentID 문서로 합칩니다.
+//! 병합 한 건은 다음 순서로 진행하며, 각 단계는 다시 실행해도 결과가 같습니다.
+//!
+//! 1. 두 문서를 다시 읽어 조건 확인 (이미 지워진 중복은 끝난 것으로 봄)
+//! 2. 대상 문서에 관측 기록을 합치고 `merged_from`에 중복 ContentID 기록
+//! 3. 중복의 Parse 캐시를 대상 ContentID로 옮기고 삭제
+//! 4. 중복 플레이어 문서 삭제
+//!
+//! `dry_run`이면 1단계까지만 하고 보고서만 만듭니다.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use mongodb::Collection;
+use serde::Serialize;
+
+use crate::fflogs::{repoint_parse_doc, ParseCacheDoc};
+use crate::infra::mirror::Mirrored;
+use crate::mongo::{
+    delete_parse_doc, delete_player, get_parse_docs, get_players_by_content_ids, merge_player_record,
+    players_with_lower_ids, set_fetch_accounting, truncated_players, upsert_zone_caches,
+};
+use crate::player::compaction::{plan_merges, still_duplicates, PlayerMerge, SkippedDuplicate};
+use crate::player::Player;
+
+const BATCH_SIZE: usize = 100;
+
+/// 배치 사이 대기 시간 (서비스 쿼리에 여유를 둠)
+const BATCH_PAUSE: Duration = Duration::from_millis(200);
+
+/// 보고서에 남기는 병합 / 건너뜀 항목 최대 개수 (개수는 모두 셈)
+pub const REPORT_SAMPLE_LIMIT: usize = 100;
+
+/// 병합 작업 보고서
+#[derive(Debug, Clone, Serialize)]
+pub struct CompactionReport {
+    pub dry_run: bool,
+    /// `"admin"` 또는 `"schedule"`
+    pub trigger: &'static str,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// 확인한 플레이어 문서 수 (하위 32비트 문서 + 후보 문서)
+    pub scanned: usize,
+    /// 병합한 (`dry_run`이면 병합할) 중복 수
+    pub merged: usize,
+    /// 이전 실행에서 이미 병합된 중복 수
+    pub already_merged: usize,
+    /// 대상 ContentID로 옮긴 Parse 캐시 문서 수
+    pub parse_docs_repointed: usize,
+    /// 후보가 여러 개라 건너뛴 중복 수
+    pub skipped_ambiguous: usize,
+    /// 계획 이후 문서가 바뀌어 건너뛴 중복 수
+    pub skipped_changed: usize,
+    /// 오류로 중단된 병합 수 (다음 실행에서 이어서 처리)
+    pub failed: usize,
+    pub merges: Vec<PlayerMerge>,
+    pub skipped: Vec<SkippedDuplicate>,
+    /// 작업 전체가 실패한 경우의 오류
+    pub error: Option<String>,
+}
+
+impl CompactionReport {
+    fn new(dry_run: bool, trigger: &'static str) -> Self {
+        Self {
+            dry_run,
+            trigger,
+            started_at: Utc::now(),
+            finished_at: None,
+            scanned: 0,
+            merged: 0,
+            already_merged: 0,
+            parse_docs_repointed: 0,
+            skipped_ambiguous: 0,
+            skipped_changed: 0,
+            failed: 0,
+            merges: Vec::new(),
+            skipped: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+/// 실행 중 여부와 마지막 보고서 (관리자 API)
+#[derive(Debug, Default)]
+pub struct CompactionStatus {
+    running: AtomicBool,
+    last: Mutex<Option<CompactionReport>>,
+}
+
+impl CompactionStatus {
+    /// 실행 시작 (이미 실행 중이면 `false`)
+    pub fn begin(&self) -> bool {
+        !self.running.swap(true, Ordering::AcqRel)
+    }
+
+    pub fn finish(&self, report: CompactionReport) {
+        *self.last.lock().unwrap() = Some(report);
+        self.running.store(false, Ordering::Release);
+    }
+
+    pub fn running(&self) -> bool {
+        self.running.load(Ordering::Acquire)
+    }
+
+    pub fn last_report(&self) -> Option<CompactionReport> {
+        self.last.lock().unwrap().clone()
+    }
+}
+
+/// 중복 플레이어를 찾아 병합 (`dry_run`이면 보고서만 생성)
+pub async fn compact_players(
+    players: &Mirrored<Collection<Player>>,
+    parses: &Mirrored<Collection<ParseCacheDoc>>,
+    dry_run: bool,
+    trigger: &'static str,
+) -> CompactionReport {
+    let mut report = CompactionReport::new(dry_run, trigger);
+    if let Err(e) = run(players, parses, &mut report).await {
+        tracing::error!("player compaction failed: {:#}", e);
+        report.error = Some(format!("{:#}", e));
+    }
+    report.finished_at = Some(Utc::now());
+
+    tracing::info!(
+        "Player compaction{} finished: {} merged, {} already merged, {} ambiguous, {} changed, {} failed",
+        if dry_run { " (dry run)" } else { "" },
+        report.merged,
+        report.already_merged,
+        report.skipped_ambiguous,
+        report.skipped_changed,
+        report.failed,
+    );
+    report
+}
+
+async fn run(
+    players: &Mirrored<Collection<Player>>,
+    parses: &Mirrored<Collection<ParseCacheDoc>>,
+    report: &mut CompactionReport,
+) -> anyhow::Result<()> {
+    let truncated = truncated_players(players.primary()).await?;
+    let lower_ids: HashSet<u64> = truncated.iter().map(|player| player.content_id).collect();
+    let candidates = players_with_lower_ids(players.primary(), &lower_ids).await?;
+    report.scanned = truncated.len() + candidates.len();
+
+    let plan = plan_merges(truncated.iter().chain(&candidates));
+    report.skipped_ambiguous = plan.skipped.len();
+    report.skipped = plan.skipped.into_iter().take(REPORT_SAMPLE_LIMIT).collect();
+
+    for (i, batch) in plan.merges.chunks(BATCH_SIZE).enumerate() {
+        if i > 0 {
+            tokio::time::sleep(BATCH_PAUSE).await;
+        }
+
+        for merge in batch {
+            match apply_merge(players, parses, merge, report.dry_run).await {
+                Ok(MergeResult::Merged { parse_doc_repointed }) => {
+                    report.merged += 1;
+                    report.parse_docs_repointed += usize::from(parse_doc_repointed);
+                    if report.merges.len() < REPORT_SAMPLE_LIMIT {
+                        report.merges.push(merge.clone());
+                    }
+                }
+                Ok(MergeResult::AlreadyMerged) => report.already_merged += 1,
+                Ok(MergeResult::Changed) => {
+                    tracing::warn!("player {} changed since planning, not merging into {}", merge.duplicate, merge.target);
+                    report.skipped_changed += 1;
+                }
+                Err(e) => {
+                    tracing::error!("could not merge player {} into {}: {:#}", merge.duplicate, merge.target, e);
+                    report.failed += 1;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+enum MergeResult {
+    Merged { parse_doc_repointed: bool },
+    AlreadyMerged,
+    Changed,
+}
+
+async fn apply_merge(
+    players: &Mirrored<Collection<Player>>,
+    parses: &Mirrored<Collection<ParseCacheDoc>>,
+    merge: &PlayerMerge,
+    dry_run: bool,
+) -> anyhow::Result<MergeResult> {
+    let ids = [merge.target, merge.duplicate];
+    let current = get_players_by_content_ids(players.primary(), &ids).await?;
+    let target = current.iter().find(|player| player.content_id == merge.target);
+    let duplicate = current.iter().find(|player| player.content_id == merge.duplicate);
+
+    let (target, duplicate) = match (target, duplicate) {
+        (Some(target), Some(duplicate)) => (target, duplicate),
+        (Some(_), None) => return Ok(MergeResult::AlreadyMerged),
+        _ => return Ok(MergeResult::Changed),
+    };
+    if !still_duplicates(target, duplicate) {
+        return Ok(MergeResult::Changed);
+    }
+
+    let parse_docs = get_parse_docs(parses.primary(), &ids).await?;
+    let duplicate_parses = parse_docs.get(&merge.duplicate);
+    if dry_run {
+        return Ok(MergeResult::Merged { parse_doc_repointed: duplicate_parses.is_some() });
+    }
+
+    players
+        .write(|collection| merge_player_record(collection, merge.target, duplicate))
+        .await?;
+
+    if let Some(duplicate_parses) = duplicate_parses {
+        let repoint = repoint_parse_doc(parse_docs.get(&merge.target), duplicate_parses);
+        if !repoint.zones_to_write.is_empty() {
+            parses
+                .write(|collection| upsert_zone_caches(collection, merge.target, &repoint.zones_to_write))
+                .await?;
+        }
+        if let Some(fetch) = &repoint.fetch {
+            parses
+                .write(|collection| set_fetch_accounting(collection, merge.target, fetch))
+                .await?;
+        }
+        parses.write(|collection| delete_parse_doc(collection, merge.duplicate)).await?;
+    }
+
+    players.write(|collection| delete_player(collection, merge.duplicate)).await?;
+
+    Ok(MergeResult::Merged { parse_doc_repointed: duplicate_parses.is_some() })
+}
@@ -0,0 +1,92 @@
+//! 로그 초기화
+//!
+//! `[logging]` 설정에 따라 사람이 읽는 형식 또는 JSON 한 줄 형식으로 stderr(+ 일별 파일)에 기록합니다.
+//! 레벨 필터는 reload 핸들로 감싸 관리자 API에서 재시작 없이 바꿀 수 있습니다.
+//!
+//! JSON 형식은 현재 span의 필드(`request_id` 등)를 `span` 객체에 함께 기록합니다.
+
+use anyhow::{Context, Result};
+use tracing::Subscriber;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::fmt::writer::{BoxMakeWriter, MakeWriterExt};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+use crate::config::{LogFormat, Logging};
+
+/// 실행 중에 레벨 필터를 바꾸는 핸들
+#[derive(Clone)]
+pub struct LogHandle {
+    filter: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LogHandle {
+    /// 현재 적용 중인 필터
+    pub fn directives(&self) -> String {
+        self.filter.with_current(|filter| filter.to_string()).unwrap_or_default()
+    }
+
+    /// 필터 교체 (잘못된 지시어가 있으면 기존 필터 유지)
+    pub fn set_directives(&self, directives: &str) -> Result<()> {
+        let filter = EnvFilter::try_new(directives).context("invalid log directives")?;
+        self.filter.reload(filter).context("could not reload log filter")?;
+        Ok(())
+    }
+}
+
+/// 설정의 기본 레벨 + 모듈별 재정의 (+ `RUST_LOG`, 같은 대상이면 환경 변수가 우선)
+pub fn directives(config: &Logging, env: Option<&str>) -> String {
+    [config.level.as_str(), config.directives.as_str(), env.unwrap_or_default()]
+        .iter()
+        .flat_map(|directives| directives.split(','))
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// 설정에 맞는 구독자와 레벨 변경 핸들
+pub fn subscriber(config: &Logging, writer: BoxMakeWriter) -> Result<(impl Subscriber + Send + Sync, LogHandle)> {
+    let env = std::env::var("RUST_LOG").ok();
+    let filter = EnvFilter::try_new(directives(config, env.as_deref())).context("invalid log directives")?;
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let output = match config.format {
+        LogFormat::Pretty => tracing_subscriber::fmt::layer()
+            .with_writer(writer)
+            .with_ansi(true)
+            .boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_writer(writer)
+            .boxed(),
+    };
+
+    let subscriber = tracing_subscriber::registry().with(filter).with(output);
+    Ok((subscriber, LogHandle { filter: handle }))
+}
+
+/// 전역 로그 초기화
+///
+/// 파일 기록을 켜면 반환된 guard가 살아 있는 동안 파일에 기록됩니다.
+pub fn init(config: &Logging) -> Result<(LogHandle, Option<WorkerGuard>)> {
+    let (writer, guard) = if config.file {
+        let file_appender = tracing_appender::rolling::Builder::new()
+            .rotation(tracing_appender::rolling::Rotation::DAILY)
+            .filename_prefix("server")
+            .filename_suffix("log")
+            .build(&config.directory)
+            .context("initializing rolling file appender failed")?;
+        let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+        (BoxMakeWriter::new(std::io::stderr.and(non_blocking)), Some(guard))
+    } else {
+        (BoxMakeWriter::new(std::io::stderr), None)
+    };
+
+    let (subscriber, handle) = subscriber(config, writer)?;
+    subscriber.try_init().context("could not install log subscriber")?;
+    Ok((handle, guard))
+}
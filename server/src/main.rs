@@ -4,18 +4,19 @@
 use crate::config::Config;
 use anyhow::Context;
 use std::borrow::Cow;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
-use tracing_subscriber::fmt::writer::MakeWriterExt;
 
 // =============================================================================
 // 유틸리티 모듈
 // =============================================================================
 mod base64_sestring;
 mod config;
+mod logging;
 mod sestring_ext;
+mod version;
 
 // =============================================================================
 // FFXIV 데이터 모듈
@@ -44,6 +45,9 @@ pub use infra::fflogs;
 // 웹 레이어
 // =============================================================================
 mod api;
+mod bookmarks;
+mod export;
+mod feeds;
 mod template;
 mod web;
 mod ws;
@@ -53,25 +57,6 @@ mod test;
 
 #[tokio::main]
 async fn main() {
-    // 로깅 초기화: 콘솔 + 일별 로테이션 파일
-    let file_appender = tracing_appender::rolling::Builder::new()
-        .rotation(tracing_appender::rolling::Rotation::DAILY)
-        .filename_prefix("server")
-        .filename_suffix("log")
-        .build("logs")
-        .expect("initializing rolling file appender failed");
-
-    let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
-    
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into())
-        )
-        .with_writer(std::io::stderr.and(non_blocking))
-        .with_ansi(true)
-        .init();
-
     let mut args: Vec<String> = std::env::args().skip(1).collect();
     let config_path = if args.is_empty() {
         Cow::from("./config.toml")
@@ -79,7 +64,20 @@ async fn main() {
         Cow::from(args.remove(0))
     };
 
-    let config = match get_config(&*config_path).await {
+    // 설정을 읽지 못해도 오류를 기록할 수 있도록 기본 로그 설정으로 초기화
+    let config = get_config(&*config_path).await;
+    let logging = config.as_ref().map(|config| config.logging.clone()).unwrap_or_default();
+
+    // 로깅 초기화: 콘솔 + 일별 로테이션 파일 (guard가 살아 있는 동안 파일에 기록)
+    let (log_handle, _guard) = match logging::init(&logging) {
+        Ok(logging) => logging,
+        Err(e) => {
+            eprintln!("Failed to initialise logging: {:#}", e);
+            return;
+        }
+    };
+
+    let config = match config {
         Ok(config) => config,
         Err(e) => {
             tracing::error!("Failed to load config: {}", e);
@@ -87,7 +85,30 @@ async fn main() {
         }
     };
 
-    if let Err(e) = self::web::start(Arc::new(config)).await {
+    // 버전 정보가 매핑 해시를 계산하기 전에 읽어야 함
+    if let Some(path) = config.fflogs.as_ref().and_then(|fflogs| fflogs.mapping_file.as_ref()) {
+        match fflogs::mapping::load_mapping_file(path) {
+            Ok(count) => tracing::info!("Loaded {} FFLogs mappings from {}", count, path.display()),
+            Err(e) => tracing::warn!("Using the built-in FFLogs mapping: {:#}", e),
+        }
+    }
+
+    // 통계 쿼리와 카테고리 표시가 처음 쓰기 전에 정해야 함
+    if let Err(e) = ffxiv::set_labeled_categories(&config.display.labeled_categories) {
+        tracing::warn!("Using the default labeled categories: {:#}", e);
+    }
+
+    let version = &*version::VERSION;
+    tracing::info!(
+        "remote-party-finder {} (commit {}, built {}, data tables {}, fflogs mapping {})",
+        version.version,
+        version.git_hash,
+        version.built_at,
+        version.data_tables,
+        version.fflogs_mapping,
+    );
+
+    if let Err(e) = self::web::start(Arc::new(config), PathBuf::from(&*config_path), log_handle).await {
         tracing::error!("Server error: {}", e);
         tracing::error!("  {:?}", e);
     }
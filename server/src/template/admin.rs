@@ -0,0 +1,98 @@
+use askama::Template;
+
+use crate::api::admin::ListingDiagnostics;
+use crate::ffxiv::unknown_ids::UnknownId;
+use crate::ffxiv::Language;
+use crate::infra::player_compaction::CompactionReport;
+use crate::listing::Blocklist;
+use crate::web::maintenance::{MaintenanceOverride, MaintenanceStatus, PauseReason};
+
+#[derive(Debug, Template)]
+#[template(path = "admin_login.html")]
+pub struct AdminLoginTemplate {
+    pub lang: Language,
+    pub error: Option<&'static str>,
+}
+
+#[derive(Debug, Template)]
+#[template(path = "admin.html")]
+pub struct AdminTemplate {
+    pub lang: Language,
+    /// 폼 주소에 붙이는 CSRF 토큰
+    pub csrf: String,
+    pub maintenance: MaintenanceStatus,
+    pub compaction_running: bool,
+    pub compaction_last: Option<CompactionReport>,
+    pub unknown_ids: Vec<UnknownId>,
+    pub blocklist: Blocklist,
+    /// FFLogs 설정이 없으면 Parse 관련 폼을 숨김
+    pub fflogs_enabled: bool,
+    pub log_directives: String,
+    pub lookup: Option<ListingLookup>,
+}
+
+/// 모집글 조회 결과
+#[derive(Debug)]
+pub struct ListingLookup {
+    pub key: String,
+    pub diagnostics: Option<ListingDiagnostics>,
+    pub error: Option<&'static str>,
+}
+
+impl ListingLookup {
+    pub fn error(key: &str, error: &'static str) -> Self {
+        Self { key: key.to_string(), diagnostics: None, error: Some(error) }
+    }
+}
+
+impl AdminTemplate {
+    /// CSRF 토큰을 붙인 관리자 API 주소
+    pub fn action(&self, path: &str) -> String {
+        format!("/api/admin/{}?csrf={}", path, self.csrf)
+    }
+
+    pub fn refresh_action(&self, key: &str) -> String {
+        self.action(&format!("listings/{}/refresh_parses", key))
+    }
+
+    pub fn mode_label(&self, mode: &MaintenanceOverride) -> &'static str {
+        match mode {
+            MaintenanceOverride::Auto => "auto",
+            MaintenanceOverride::Paused => "paused",
+            MaintenanceOverride::Running => "running",
+        }
+    }
+
+    pub fn maintenance_state(&self) -> &'static str {
+        match self.maintenance.reason {
+            Some(PauseReason::Manual) => "paused (manual)",
+            Some(PauseReason::Scheduled) => "paused (scheduled maintenance)",
+            Some(PauseReason::AutoDetected) => "paused (maintenance detected)",
+            None => "running",
+        }
+    }
+
+    pub fn compaction_summary(&self, report: &CompactionReport) -> String {
+        let finished = match report.finished_at {
+            Some(at) => format!("finished {} UTC", at.format("%Y-%m-%d %H:%M")),
+            None => "unfinished".to_string(),
+        };
+        format!(
+            "{} run by {}, {}: {} scanned, {} merged, {} failed",
+            if report.dry_run { "Dry" } else { "Real" },
+            report.trigger,
+            finished,
+            report.scanned,
+            report.merged,
+            report.failed,
+        )
+    }
+
+    pub fn blocked_categories(&self) -> String {
+        self.blocklist.category_names().join(", ")
+    }
+
+    pub fn blocked_duties(&self) -> String {
+        self.blocklist.duties.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    }
+}
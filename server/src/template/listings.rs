@@ -1,9 +1,12 @@
 use crate::ffxiv::Language;
+use crate::fflogs::AllStars;
 use crate::listing::JobFlags;
 use crate::listing::PartyFinderCategory;
+use crate::listing::PartyFinderListing;
 use crate::listing_container::QueriedListing;
 use crate::sestring_ext::SeStringExt;
 use askama::Template;
+use futures_util::Stream;
 use std::borrow::Borrow;
 
 #[derive(Debug, Template)]
@@ -11,6 +14,121 @@ use std::borrow::Borrow;
 pub struct ListingsTemplate {
     pub containers: Vec<RenderableListing>,
     pub lang: Language,
+    /// 목록 위에 표시할 경고 (예: 알 수 없는 서버 이름으로 조건을 무시함)
+    pub notice: Option<String>,
+}
+
+/// 스트리밍 렌더링에서 모집글 조각이 들어갈 자리 (페이지 틀에만 나타남)
+pub const LISTINGS_PLACEHOLDER: &str = "<!--listings-->";
+
+/// 모집글 자리에 `LISTINGS_PLACEHOLDER`만 남긴 페이지 틀
+#[derive(Template)]
+#[template(path = "listings.html")]
+struct ListingsShellTemplate<'a> {
+    containers: &'a [RenderableListing],
+    lang: Language,
+    notice: Option<&'a str>,
+}
+
+impl<'a> ListingsShellTemplate<'a> {
+    fn new(containers: &'a [RenderableListing], lang: Language, notice: Option<&'a str>) -> Self {
+        Self { containers, lang, notice }
+    }
+
+    fn streamed(&self) -> bool {
+        true
+    }
+}
+
+/// 모집글 하나 (`listings.html`이 모집글마다 포함하는 조각)
+#[derive(Template)]
+#[template(path = "_listing.html")]
+pub(crate) struct ListingFragmentTemplate<'a> {
+    renderable: &'a RenderableListing,
+    lang: Language,
+}
+
+impl<'a> ListingFragmentTemplate<'a> {
+    pub(crate) fn new(renderable: &'a RenderableListing, lang: Language) -> Self {
+        Self { renderable, lang }
+    }
+}
+
+impl ListingsTemplate {
+    fn streamed(&self) -> bool {
+        false
+    }
+
+    /// 페이지 전체를 한 문자열로 만들지 않고 조각으로 렌더링
+    ///
+    /// 페이지 앞부분, 모집글 `per_chunk`개씩, 페이지 뒷부분 순서로 내보내며
+    /// 모두 이으면 `render()`와 같은 출력입니다. 모집글 하나의 렌더링 실패는 `render_row`가
+    /// 자리표시 행으로 대체하므로, 에러로 끝나는 경우는 페이지 틀 렌더링이 실패했을 때뿐입니다.
+    pub fn render_chunks(self, per_chunk: usize) -> impl Stream<Item = askama::Result<String>> {
+        self.render_chunks_with(per_chunk, |renderable, lang, out| {
+            ListingFragmentTemplate::new(renderable, lang).render_into(out)
+        })
+    }
+
+    /// `render_chunks`와 같지만 모집글 행을 `row`로 렌더링
+    pub(crate) fn render_chunks_with<F>(self, per_chunk: usize, row: F) -> impl Stream<Item = askama::Result<String>>
+    where
+        F: Fn(&RenderableListing, Language, &mut String) -> askama::Result<()>,
+    {
+        async_stream::try_stream! {
+            let shell = ListingsShellTemplate::new(&self.containers, self.lang, self.notice.as_deref()).render()?;
+            let (head, tail) = shell
+                .split_once(LISTINGS_PLACEHOLDER)
+                .ok_or_else(|| askama::Error::Custom("listings placeholder is missing".into()))?;
+            yield head.to_string();
+
+            for group in self.containers.chunks(per_chunk.max(1)) {
+                let mut chunk = String::new();
+                for renderable in group {
+                    render_row(renderable, &mut chunk, |out| row(renderable, self.lang, out));
+                }
+                yield chunk;
+            }
+
+            yield tail.to_string();
+        }
+    }
+}
+
+/// 모집글 한 행 렌더링 (에러나 패닉이 나면 기록하고 자리표시 행으로 대체)
+///
+/// 잘못된 모집글 하나 때문에 목록 전체가 깨지지 않게 합니다. 실패한 행이 남긴 일부 출력은 버립니다.
+pub(crate) fn render_row<F>(renderable: &RenderableListing, out: &mut String, render: F)
+where
+    F: FnOnce(&mut String) -> askama::Result<()>,
+{
+    let mut row = String::new();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| render(&mut row)));
+
+    let reason = match result {
+        Ok(Ok(())) => {
+            out.push_str(&row);
+            return;
+        }
+        Ok(Err(e)) => e.to_string(),
+        Err(panic) => panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic".to_string()),
+    };
+
+    let listing = &renderable.container.listing;
+    tracing::error!("Failed to render listing {}: {}", listing.key(), reason);
+    out.push_str(&unavailable_row(listing.id));
+}
+
+/// 렌더링에 실패한 모집글 자리에 넣는 행
+pub fn unavailable_row(id: u32) -> String {
+    format!(
+        r#"<div class="listing unavailable" data-id="{}"><div class="left"><div class="description"><em data-i18n="listing_unavailable">This listing could not be displayed</em></div></div></div>"#,
+        id,
+    )
 }
 
 #[derive(Debug)]
@@ -19,6 +137,10 @@ pub struct RenderableListing {
     pub members: Vec<RenderableMember>,
     /// 파티장 로그 정보 (멤버 정보가 없어도 표시 가능)
     pub leader_parse: ParseDisplay,
+    /// `?watch=` 토큰에 담긴 관심 모집글
+    pub watched: bool,
+    /// 한 잡당 한 명인데 같은 잡이 여러 번 올라와 중복을 비움
+    pub data_inconsistent: bool,
 }
 
 /// Parse percentile 표시 정보
@@ -29,6 +151,12 @@ pub struct ParseDisplay {
     pub secondary_percentile: Option<u8>,
     pub secondary_color_class: String,
     pub has_secondary: bool,
+    /// 주 encounter의 All Stars 점수 / 순위 (`?shape=extended`일 때만 표시)
+    pub primary_all_stars: Option<AllStars>,
+    /// 주 encounter 기록을 현재 잡과 다른 역할로 냄
+    pub role_mismatch: bool,
+    /// FFLogs에서 기록을 비공개로 함 (percentile 대신 "hidden" 표시)
+    pub hidden: bool,
 }
 
 impl ParseDisplay {
@@ -40,6 +168,9 @@ impl ParseDisplay {
             secondary_percentile: None,
             secondary_color_class: "parse-none".to_string(),
             has_secondary: false,
+            primary_all_stars: None,
+            role_mismatch: false,
+            hidden: false,
         }
     }
     
@@ -55,8 +186,31 @@ impl ParseDisplay {
             secondary_percentile: p2,
             secondary_color_class: p2_class,
             has_secondary,
+            primary_all_stars: None,
+            role_mismatch: false,
+            hidden: false,
         }
     }
+
+    pub fn with_all_stars(mut self, all_stars: Option<AllStars>) -> Self {
+        self.primary_all_stars = all_stars;
+        self
+    }
+
+    pub fn with_role_mismatch(mut self, role_mismatch: bool) -> Self {
+        self.role_mismatch = role_mismatch;
+        self
+    }
+
+    /// 비공개 표시 (색 클래스도 `parse-hidden`으로 바꿈)
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        if hidden {
+            self.primary_color_class = "parse-hidden".to_string();
+            self.secondary_color_class = "parse-hidden".to_string();
+        }
+        self
+    }
 }
 
 /// 멤버 정보 + 해당 슬롯의 잡 ID
@@ -68,6 +222,24 @@ pub struct RenderableMember {
 }
 
 impl RenderableMember {
+    /// 플레이어 정보가 있는 멤버 (`enrichment::unknown_player`는 관측 기록이 없음)
+    pub fn is_known(&self) -> bool {
+        self.player.seen_count > 0
+    }
+
+    /// 표시할 홈 서버 이름 (알 수 없으면 `None`, 태그를 표시하지 않음)
+    pub fn home_world_name(&self) -> Option<&'static str> {
+        self.player.home_world().map(|world| world.name())
+    }
+
+    pub fn is_cross_world(&self, listing: &PartyFinderListing) -> bool {
+        self.player.is_cross_world(listing)
+    }
+
+    pub fn is_cross_dc(&self, listing: &PartyFinderListing) -> bool {
+        self.player.is_cross_dc(listing)
+    }
+
     /// 잡 코드 반환 (예: "WHM", "PLD")
     pub fn job_code(&self) -> Option<&'static str> {
         crate::ffxiv::JOBS.get(&(self.job_id as u32)).map(|cj| cj.code())
@@ -75,16 +247,23 @@ impl RenderableMember {
     
     /// 역할에 따른 CSS 클래스 반환 ("tank", "healer", "dps")
     pub fn role_class(&self) -> &'static str {
-        use ffxiv_types::Role;
-        if let Some(cj) = crate::ffxiv::JOBS.get(&(self.job_id as u32)) {
-            match cj.role() {
-                Some(Role::Tank) => "tank",
-                Some(Role::Healer) => "healer",
-                Some(Role::Dps) => "dps",
-                None => "",
-            }
-        } else {
-            ""
+        crate::ffxiv::JOBS
+            .get(&(self.job_id as u32))
+            .map_or("", |&cj| crate::ffxiv::jobs::role_class(cj))
+    }
+
+    /// 역할 색을 입힌 잡 아이콘 주소
+    pub fn icon_url(&self) -> Option<String> {
+        self.job_code().map(crate::web::job_icons::icon_url)
+    }
+}
+
+impl RenderableListing {
+    /// 기본 표시에서 All Stars 숨김
+    pub fn hide_all_stars(&mut self) {
+        self.leader_parse.primary_all_stars = None;
+        for member in &mut self.members {
+            member.parse.primary_all_stars = None;
         }
     }
 }
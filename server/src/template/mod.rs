@@ -1,2 +1,4 @@
+pub mod admin;
 pub mod listings;
 pub mod stats;
+pub mod status;
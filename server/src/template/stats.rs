@@ -1,10 +1,74 @@
 use crate::ffxiv::Language;
-use crate::stats::Statistics;
+use crate::stats::{DutyInfo, FillTimeInfo, Statistics};
 use askama::Template;
+use std::collections::BTreeSet;
+use std::sync::Arc;
 
 #[derive(Debug, Template)]
 #[template(path = "stats.html")]
 pub struct StatsTemplate {
     pub stats: Statistics,
     pub lang: Language,
+    /// 최근 7일 통계 (듀티 링크도 같은 기간으로 연결)
+    pub seven_days: bool,
+    /// 게임 데이터에 없는 ID 수 (0이 아니면 데이터 갱신 경고 표시)
+    pub unknown_ids: usize,
+    /// 목록에서 숨긴 듀티 / 카테고리가 있음 (통계에는 포함)
+    pub hidden_from_listings: bool,
+}
+
+impl StatsTemplate {
+    /// 역할별 빈 자리 차트에서 고를 수 있는 데이터 센터
+    pub fn data_centres(&self) -> BTreeSet<&'static str> {
+        crate::stats::role_demand::data_centres()
+    }
+
+    /// 듀티별 통계 페이지 주소 (페이지가 없는 행은 `None`)
+    pub fn duty_link(&self, info: &DutyInfo) -> Option<String> {
+        info.drill_down_id().map(|duty| duty_stats_path(duty, self.seven_days))
+    }
+
+    /// 모집 완료 시간 행의 듀티별 통계 페이지 주소
+    pub fn fill_time_link(&self, info: &FillTimeInfo) -> String {
+        duty_stats_path(info.duty, self.seven_days)
+    }
+}
+
+/// 듀티별 통계 페이지 주소
+pub fn duty_stats_path(duty: u16, seven_days: bool) -> String {
+    if seven_days {
+        format!("/stats/7days/duty/{}", duty)
+    } else {
+        format!("/stats/duty/{}", duty)
+    }
+}
+
+#[derive(Debug, Template)]
+#[template(path = "stats_duty.html")]
+pub struct DutyStatsTemplate {
+    pub duty: u16,
+    pub duty_name: String,
+    pub stats: Arc<Statistics>,
+    pub seven_days: bool,
+    pub lang: Language,
+}
+
+impl DutyStatsTemplate {
+    pub fn window_path(&self, seven_days: bool) -> String {
+        duty_stats_path(self.duty, seven_days)
+    }
+
+    /// 평균 자리 채움 (예: "5.2 / 8 (65%), 120 full")
+    pub fn fill_summary(&self) -> String {
+        match self.stats.fill() {
+            Some(fill) => format!(
+                "{:.1} / {:.1} ({:.0}%), {} full",
+                fill.average_filled,
+                fill.average_capacity,
+                fill.fraction().unwrap_or(0.0) * 100.0,
+                fill.full,
+            ),
+            None => "-".to_string(),
+        }
+    }
 }
@@ -0,0 +1,61 @@
+use crate::ffxiv::Language;
+use crate::web::status::{human_age, Banner, ContributionState, PipelineState, StatusSummary};
+use askama::Template;
+
+#[derive(Debug, Template)]
+#[template(path = "status.html")]
+pub struct StatusTemplate {
+    pub summary: StatusSummary,
+    pub lang: Language,
+}
+
+impl StatusTemplate {
+    pub fn age(&self, secs: &Option<i64>) -> String {
+        secs.map(human_age).unwrap_or_else(|| "never".to_string())
+    }
+
+    pub fn contribution_label(&self, state: &ContributionState) -> &'static str {
+        match state {
+            ContributionState::Ok => "ok",
+            ContributionState::Stale => "stale",
+            ContributionState::Unknown => "unknown",
+        }
+    }
+
+    pub fn fflogs_label(&self) -> &'static str {
+        match self.summary.fflogs.state {
+            PipelineState::Disabled => "disabled",
+            PipelineState::Paused => "paused",
+            PipelineState::Starting => "starting",
+            PipelineState::Running => "running",
+            PipelineState::Stalled => "stalled",
+        }
+    }
+
+    pub fn fflogs_last_cycle(&self) -> String {
+        let cycle = &self.summary.fflogs.cycle;
+        match cycle.last_finished_at {
+            Some(at) => {
+                let age = human_age((self.summary.generated_at - at).num_seconds().max(0));
+                let result = if cycle.last_ok { "" } else { ", failed" };
+                match cycle.last_duration_secs {
+                    Some(secs) => format!("{} (took {:.0}s{})", age, secs, result),
+                    None => format!("{}{}", age, result),
+                }
+            }
+            None => "never".to_string(),
+        }
+    }
+
+    pub fn banner_text(&self, banner: &Banner) -> String {
+        match banner {
+            Banner::Maintenance { until: Some(until), .. } => {
+                format!("Game maintenance in progress until {} UTC. Listings will return afterwards.", until.format("%Y-%m-%d %H:%M"))
+            }
+            Banner::Maintenance { .. } => "Game maintenance in progress. Listings will return afterwards.".to_string(),
+            Banner::LowVolume { data_centre, .. } => {
+                format!("Fewer listings than usual on {}. Uploads from this data centre may be delayed.", data_centre)
+            }
+        }
+    }
+}
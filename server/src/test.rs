@@ -2,8 +2,103 @@ use crate::listing::{
     ConditionFlags, DutyCategory, DutyFinderSettingsFlags, DutyType, JobFlags, LootRuleFlags,
     ObjectiveFlags, PartyFinderListing, PartyFinderSlot, SearchAreaFlags,
 };
+use crate::listing_container::QueriedListing;
+use chrono::Utc;
 use sestring::SeString;
 
+mod admin_page;
+mod all_stars;
+mod atom_feed;
+mod api_duty_info;
+mod api_localization;
+mod blocklist;
+mod bookmarks;
+mod canonical_category;
+mod category_label;
+mod collection_names;
+mod cors;
+mod daily_listings;
+mod database_migration;
+mod description_language;
+mod description_history;
+mod duplicate_jobs;
+mod duty_finder_settings;
+mod duty_stats;
+mod empty_backoff;
+mod endpoint_consistency;
+mod expiry;
+mod export;
+mod fflogs_coalescing;
+mod fflogs_gating;
+mod fflogs_mapping;
+mod fflogs_points;
+mod fflogs_response_shapes;
+mod fflogs_retry;
+mod fill_rate;
+mod fixture_world;
+mod index_startup;
+mod job_icons;
+mod keyword_filter;
+mod language;
+mod listing_archive;
+mod listing_detail;
+mod listing_filter;
+mod listing_order;
+mod listing_removals;
+mod listing_requirements;
+mod listing_summary;
+mod listing_validation;
+mod listings_cache;
+mod listings_etag;
+mod listings_stream;
+mod load;
+mod logging;
+mod maintenance;
+mod member_worlds;
+mod metrics;
+mod missing_players;
+mod moderation;
+mod mongo_eval;
+mod parse_backfill;
+mod parse_cache;
+mod parse_cache_expiry;
+mod parse_invalidation;
+mod parse_roles;
+mod page_filter;
+mod party_capacity;
+mod party_detail;
+mod party_members;
+mod percentile_rounding;
+mod player_compaction;
+mod player_history;
+mod player_lookup;
+mod player_parses;
+mod player_upserts;
+mod profile_misses;
+mod rate_limit;
+mod raw_listing;
+mod read_preference;
+mod readiness;
+mod role_demand;
+mod schedule;
+mod snapshot_order;
+mod snapshot_uploads;
+mod stats_api;
+mod stats_host_names;
+mod stats_refresh;
+mod status_page;
+mod unknown_ids;
+mod upload_auth;
+mod upload_body;
+mod upload_hints;
+mod upload_response;
+mod uploaders;
+mod version;
+mod volume_alerts;
+mod ws_keepalive;
+mod ws_limits;
+mod ws_snapshot;
+
 const LISTING: &str = r###"
 {
   "id": 123,
@@ -21,6 +116,7 @@ const LISTING: &str = r###"
   "min_item_level": 0,
   "num_parties": 1,
   "slots_available": 7,
+  "last_server_restart": 0,
   "objective": 3,
   "conditions": 1,
   "duty_finder_settings": 0,
@@ -40,9 +136,28 @@ const LISTING: &str = r###"
     0,
     0,
     0
-  ]
+  ],
+  "member_content_ids": [],
+  "leader_content_id": 0
 }"###;
 
+/// 모집글을 방금 업데이트된 조회 결과로 감쌈 (남은 시간 55분, 업로드 1회)
+///
+/// 다른 값이 필요하면 `QueriedListing { time_left: …, ..queried(listing) }`처럼 덮어씁니다.
+pub fn queried(listing: PartyFinderListing) -> QueriedListing {
+    QueriedListing {
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        updated_minute: Utc::now(),
+        time_left: 3300.0,
+        listing,
+        upload_count: 1,
+        uploader_count: 1,
+        expiry: Default::default(),
+        description_history: Vec::new(),
+    }
+}
+
 lazy_static::lazy_static! {
     static ref EXPECTED: PartyFinderListing = PartyFinderListing {
         id: 123,
@@ -52,7 +167,7 @@ lazy_static::lazy_static! {
         created_world: 73,
         home_world: 73,
         current_world: 73,
-        category: DutyCategory::Duty,
+        category: DutyCategory::None,
         duty: 55,
         duty_type: DutyType::Normal,
         beginners_welcome: false,
@@ -60,6 +175,7 @@ lazy_static::lazy_static! {
         min_item_level: 0,
         num_parties: 1,
         slots_available: 7,
+        last_server_restart: 0,
         objective: ObjectiveFlags::PRACTICE | ObjectiveFlags::DUTY_COMPLETION,
         conditions: ConditionFlags::NONE,
         duty_finder_settings: DutyFinderSettingsFlags::NONE,
@@ -71,6 +187,11 @@ lazy_static::lazy_static! {
             },
         ],
         jobs_present: vec![5, 0, 0, 0, 0, 0, 0, 0],
+        member_content_ids: vec![],
+        members: Vec::new(),
+        leader_content_id: 0,
+        snapshot_at: None,
+        requirements: None,
     };
 }
 
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use chrono::{TimeDelta, Utc};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::{Method, StatusCode};
+
+use crate::api::admin::{authorize, AdminAuth};
+use crate::config::{Config, Logging};
+use crate::web::admin_page::AdminSessions;
+use crate::web::maintenance::MaintenanceOverride;
+use crate::web::routes::router;
+use crate::web::State;
+
+const TOKEN: &str = "admin-token";
+
+#[test]
+fn sessions_are_signed_and_expire() {
+    let sessions = AdminSessions::new(TOKEN);
+    let now = Utc::now();
+    let session = sessions.issue(now);
+
+    assert!(sessions.verify(&session, now));
+    assert!(sessions.verify(&session, now + TimeDelta::try_hours(11).unwrap()));
+    assert!(!sessions.verify(&session, now + TimeDelta::try_hours(12).unwrap()));
+
+    // 만료 시각을 늘리거나 다른 토큰으로 서명한 쿠키
+    let (expires, signature) = session.split_once('.').unwrap();
+    let extended = format!("{}.{}", expires.parse::<i64>().unwrap() + 3600, signature);
+    assert!(!sessions.verify(&extended, now));
+    assert!(!AdminSessions::new("rotated").verify(&session, now));
+    assert!(!sessions.verify("garbage", now));
+}
+
+#[test]
+fn posts_with_a_session_need_the_csrf_token() {
+    let sessions = AdminSessions::new(TOKEN);
+    let now = Utc::now();
+    let session = sessions.issue(now);
+    let csrf = sessions.csrf_token(&session);
+    let other_csrf = sessions.csrf_token(&sessions.issue(now - TimeDelta::try_minutes(1).unwrap()));
+    let auth = |method: Method, header: Option<&str>, session: Option<&str>, csrf: Option<&str>| {
+        authorize(Some(&sessions), &method, header, session, csrf, now)
+    };
+
+    assert_eq!(auth(Method::POST, Some("Bearer admin-token"), None, None), AdminAuth::Allowed);
+    assert_eq!(auth(Method::POST, Some("Bearer wrong"), None, None), AdminAuth::Unauthorized);
+    assert_eq!(auth(Method::GET, None, Some(&session), None), AdminAuth::Allowed);
+    assert_eq!(auth(Method::POST, None, Some(&session), Some(&csrf)), AdminAuth::Allowed);
+    assert_eq!(auth(Method::POST, None, Some(&session), None), AdminAuth::Forbidden);
+    assert_eq!(auth(Method::POST, None, Some(&session), Some(&other_csrf)), AdminAuth::Forbidden);
+    assert_eq!(auth(Method::POST, None, Some("1.forged"), Some(&csrf)), AdminAuth::Unauthorized);
+
+    // `[admin]`이 없으면 모두 거부
+    let no_admin = authorize(None, &Method::GET, Some("Bearer admin-token"), Some(&session), None, now);
+    assert_eq!(no_admin, AdminAuth::Unauthorized);
+}
+
+/// DB에 연결하지 않는 상태 (Mongo 클라이언트는 첫 조회 전까지 연결하지 않음)
+async fn state() -> (Arc<State>, impl tracing::Subscriber) {
+    let config: Config = toml::from_str(&format!(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+
+        [admin]
+        token = "{}"
+        "#,
+        TOKEN,
+    ))
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    (State::new(Arc::new(config), log_handle).await.unwrap(), subscriber)
+}
+
+/// 로그인 후 세션 쿠키
+async fn log_in(state: &Arc<State>) -> String {
+    let response = warp::test::request()
+        .method("POST")
+        .path("/admin/login")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(format!("token={}", TOKEN))
+        .reply(&router(Arc::clone(state)))
+        .await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+    let cookie = response.headers()["set-cookie"].to_str().unwrap();
+    assert!(cookie.contains("HttpOnly") && cookie.contains("SameSite=Strict"));
+    cookie.split(';').next().unwrap().to_string()
+}
+
+/// 페이지의 POST 폼 (주소, 필드마다 첫 값)
+fn post_forms(html: &str) -> Vec<(String, Vec<(String, String)>)> {
+    let form = regex::Regex::new(r#"(?s)<form method="post" action="([^"]+)"[^>]*>(.*?)</form>"#).unwrap();
+    let field = regex::Regex::new(r#"name="([^"]+)"(?: value="([^"]*)")?"#).unwrap();
+
+    form.captures_iter(html)
+        .map(|form| {
+            let mut fields: Vec<(String, String)> = Vec::new();
+            for field in field.captures_iter(&form[2]) {
+                if !fields.iter().any(|(name, _)| *name == field[1]) {
+                    let value = field.get(2).map(|value| value.as_str()).unwrap_or_default();
+                    fields.push((field[1].to_string(), value.to_string()));
+                }
+            }
+            (form[1].to_string(), fields)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn page_requires_login_and_is_never_cached() {
+    let (state, _subscriber) = state().await;
+    let routes = router(Arc::clone(&state));
+
+    let response = warp::test::request().path("/admin").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["cache-control"], "no-store");
+    let body = String::from_utf8_lossy(response.body()).to_string();
+    assert!(body.contains(r#"name="token""#));
+    assert!(!body.contains("/api/admin/"));
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/admin/login")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body("token=wrong")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(response.headers().get("set-cookie").is_none());
+
+    let cookie = log_in(&state).await;
+    let response = warp::test::request().path("/admin").header("cookie", &cookie).reply(&routes).await;
+    assert_eq!(response.headers()["cache-control"], "no-store");
+    let body = String::from_utf8_lossy(response.body()).to_string();
+    assert!(body.contains(r#"id="maintenance""#));
+    // FFLogs가 없어도 페이지는 표시하고 Parse 폼만 숨김
+    assert!(body.contains("FFLogs is not configured."));
+    assert!(!body.contains("parses/invalidate"));
+
+    // 잘못된 모집글 키는 DB를 조회하지 않고 오류로 표시
+    let response = warp::test::request()
+        .path("/admin?listing=not-a-key")
+        .header("cookie", &cookie)
+        .reply(&routes)
+        .await;
+    assert!(String::from_utf8_lossy(response.body()).contains("expected id/created_world/last_server_restart"));
+}
+
+#[tokio::test]
+async fn session_cookie_authorizes_the_admin_api() {
+    let (state, _subscriber) = state().await;
+    let routes = router(Arc::clone(&state));
+
+    let response = warp::test::request().path("/api/admin/unknown_ids").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let cookie = log_in(&state).await;
+    let response = warp::test::request().path("/api/admin/unknown_ids").header("cookie", &cookie).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let post = |path: &str| {
+        warp::test::request()
+            .method("POST")
+            .path(path)
+            .header("cookie", &cookie)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body("mode=paused")
+    };
+    assert_eq!(post("/api/admin/maintenance").reply(&routes).await.status(), StatusCode::FORBIDDEN);
+    assert_eq!(post("/api/admin/maintenance?csrf=forged").reply(&routes).await.status(), StatusCode::FORBIDDEN);
+    assert_eq!(state.maintenance.status(Utc::now()).mode, MaintenanceOverride::Auto);
+
+    // 헤더 토큰과 JSON 본문은 그대로 동작
+    let response = warp::test::request()
+        .method("POST")
+        .path("/api/admin/maintenance")
+        .header("authorization", format!("Bearer {}", TOKEN))
+        .json(&serde_json::json!({ "mode": "running" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(state.maintenance.status(Utc::now()).mode, MaintenanceOverride::Running);
+}
+
+#[tokio::test]
+async fn every_form_posts_to_a_working_endpoint() {
+    let (state, _subscriber) = state().await;
+    let routes = router(Arc::clone(&state));
+    let cookie = log_in(&state).await;
+
+    let response = warp::test::request().path("/admin").header("cookie", &cookie).reply(&routes).await;
+    let forms = post_forms(&String::from_utf8_lossy(response.body()));
+    let actions: Vec<&str> = forms.iter().map(|(action, _)| action.split('?').next().unwrap()).collect();
+    assert_eq!(
+        actions,
+        vec!["/admin/logout", "/api/admin/maintenance", "/api/admin/players/compaction", "/api/admin/logging"],
+    );
+
+    for (action, fields) in &forms {
+        let body: Vec<String> = fields.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+        let response = warp::test::request()
+            .method("POST")
+            .path(action)
+            .header("cookie", &cookie)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(body.join("&"))
+            .reply(&routes)
+            .await;
+        assert!(response.status().is_success() || response.status().is_redirection(), "{}: {}", action, response.status());
+    }
+
+    // 첫 버튼의 값이 적용됨
+    assert_eq!(state.maintenance.status(Utc::now()).mode, MaintenanceOverride::Auto);
+    assert!(state.player_compaction.running() || state.player_compaction.last_report().is_some_and(|r| r.dry_run));
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use chrono::{FixedOffset, Utc};
+use mongodb::bson::{self, doc};
+
+use super::fixture_world::parse_docs;
+use crate::api::{apply_shape, build_api_listings, ApiShape};
+use crate::fflogs::parse_response::parse_zone_rankings;
+use crate::fflogs::{AllStars, EncounterParse, ZoneCache, DUTY_TO_FFLOGS};
+use crate::listing::PartyFinderListing;
+use crate::player::Player;
+
+/// FFLogs `zoneRankings` 응답 (Zone 단위 `allStars`는 있지만 encounter 102에는 없음)
+const ZONE_RANKINGS: &str = r#"{
+    "bestPerformanceAverage": 88.4,
+    "medianPerformanceAverage": 71.2,
+    "difficulty": 101,
+    "metric": "rdps",
+    "partition": 1,
+    "zone": 62,
+    "allStars": [
+        { "partition": 1, "spec": "Paladin", "points": 245.31, "possiblePoints": 480, "rank": 1523, "regionRank": 402, "serverRank": 12, "rankPercent": 97.1, "total": 51234 }
+    ],
+    "rankings": [
+        {
+            "encounter": { "id": 101, "name": "Black Cat" },
+            "rankPercent": 95.5,
+            "medianPercent": 80.1,
+            "lockedIn": true,
+            "totalKills": 12,
+            "spec": "Paladin",
+            "allStars": { "points": 120.5, "possiblePoints": 120, "partition": 1, "rank": 842, "regionRank": 201, "serverRank": 5, "rankPercent": 98.2, "total": 40211 }
+        },
+        {
+            "encounter": { "id": 102, "name": "Honey B. Lovely" },
+            "rankPercent": 61.0,
+            "lockedIn": true,
+            "totalKills": 3,
+            "spec": "Paladin",
+            "allStars": null
+        },
+        {
+            "encounter": { "id": 103, "name": "Brute Bomber" },
+            "rankPercent": null,
+            "lockedIn": false,
+            "totalKills": 0,
+            "allStars": null
+        }
+    ]
+}"#;
+
+#[test]
+fn all_stars_are_parsed_per_encounter() {
+    let zone_rankings: serde_json::Value = serde_json::from_str(ZONE_RANKINGS).unwrap();
+    let parses = parse_zone_rankings(&zone_rankings);
+
+    assert_eq!(
+        parses,
+        vec![
+            (101, EncounterParse { percentile: 95.5, job_id: 19, all_stars: Some(AllStars { points: 120.5, rank: 842 }), jobs: Default::default() }),
+            // Zone 요약에만 있는 All Stars는 encounter에 붙이지 않음
+            (102, EncounterParse { percentile: 61.0, job_id: 19, all_stars: None, jobs: Default::default() }),
+        ]
+    );
+}
+
+#[test]
+fn all_stars_are_stored_only_when_present() {
+    let stored = bson::to_document(&EncounterParse {
+        percentile: 95.5,
+        job_id: 0,
+        all_stars: Some(AllStars { points: 120.5, rank: 842 }),
+        jobs: Default::default(),
+    })
+    .unwrap();
+    let parse: EncounterParse = bson::from_document(stored).unwrap();
+    assert_eq!(parse.all_stars, Some(AllStars { points: 120.5, rank: 842 }));
+
+    let without = bson::to_document(&EncounterParse { percentile: 61.0, job_id: 0, all_stars: None, jobs: Default::default() }).unwrap();
+    assert!(!without.contains_key("all_stars"));
+
+    // 필드가 추가되기 전 문서
+    let old: EncounterParse = bson::from_document(doc! { "percentile": 42.0_f64, "job_id": 0 }).unwrap();
+    assert_eq!(old.all_stars, None);
+}
+
+#[test]
+fn all_stars_are_serialized_only_in_extended_shape() {
+    let (&duty, info) = DUTY_TO_FFLOGS.iter().next().unwrap();
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.duty = duty;
+    listing.member_content_ids = vec![1, 2];
+    listing.jobs_present = vec![19, 24];
+    let queried = || super::queried(serde_json::from_value(serde_json::to_value(&listing).unwrap()).unwrap());
+
+    let players: HashMap<u64, Player> = [1, 2]
+        .into_iter()
+        .map(|id| {
+            let player = Player {
+                content_id: id,
+                name: format!("Member {}", id),
+                home_world: 73,
+                last_seen: Utc::now(),
+                seen_count: 1,
+            };
+            (id, player)
+        })
+        .collect();
+    let parse = |all_stars| ZoneCache {
+        fetched_at: Utc::now(),
+        encounters: maplit::hashmap! {
+            info.encounter_id.to_string() => EncounterParse { percentile: 99.2, job_id: 0, all_stars, jobs: Default::default() },
+        },
+    };
+    let parse_docs = parse_docs(maplit::hashmap! {
+        (info.zone_id as u16, 1) => parse(Some(AllStars { points: 120.5, rank: 842 })),
+        (info.zone_id as u16, 2) => parse(None),
+    });
+
+    let members = |shape| {
+        let mut api = build_api_listings(vec![queried()], &players, &parse_docs, FixedOffset::east_opt(0).unwrap());
+        apply_shape(&mut api, shape);
+        serde_json::to_value(&api).unwrap()[0]["listing"]["members"].clone()
+    };
+
+    let compact = members(ApiShape::Compact);
+    assert!(compact.as_array().unwrap().iter().all(|m| m.get("all_stars").is_none()));
+    // 색상은 계속 percentile 기준
+    assert_eq!(compact[0]["parse_color_class"], "parse-pink");
+
+    let extended = members(ApiShape::Extended);
+    assert_eq!(extended[0]["all_stars"], serde_json::json!({ "points": 120.5, "rank": 842 }));
+    assert!(extended[1].get("all_stars").is_none());
+    assert_eq!(extended[1]["parse_percentile"], 99);
+}
+
+#[test]
+fn shape_defaults_to_compact() {
+    let shape: ApiShape = serde_json::from_str(r#""extended""#).unwrap();
+    assert_eq!(shape, ApiShape::Extended);
+    assert_eq!(ApiShape::default(), ApiShape::Compact);
+}
@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use chrono::FixedOffset;
+use serde_json::{json, Value};
+
+use super::fixture_world::{ListingBuilder, DUNGEON, SAVAGE_SPLIT};
+use crate::api::build_api_listings;
+use crate::listing::DutyCategory;
+
+fn duty_info(duty: u16, category: DutyCategory) -> Value {
+    let listings = vec![ListingBuilder::new(1).duty(duty, category).build()];
+    let api = build_api_listings(listings, &HashMap::new(), &HashMap::new(), FixedOffset::east_opt(0).unwrap());
+    serde_json::to_value(&api).unwrap()[0]["listing"]["duty_info"].clone()
+}
+
+#[test]
+fn duty_info_carries_the_listing_duty_id() {
+    let info = duty_info(SAVAGE_SPLIT, DutyCategory::HighEndDuty);
+    assert_eq!(info["id"], json!(1075));
+    assert_eq!(info["high_end"], json!(true));
+
+    assert_eq!(duty_info(DUNGEON, DutyCategory::Dungeon)["id"], json!(DUNGEON));
+}
+
+#[test]
+fn content_kind_serializes_as_id_and_name() {
+    let info = duty_info(SAVAGE_SPLIT, DutyCategory::HighEndDuty);
+    assert_eq!(info["content_kind_id"], json!(5));
+    assert_eq!(info["content_kind"], json!("Raids"));
+}
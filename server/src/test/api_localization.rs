@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use chrono::FixedOffset;
+use serde_json::{json, Value};
+
+use super::fixture_world::{ListingBuilder, SAVAGE};
+use crate::api::conditional::localized_etag;
+use crate::api::{build_api_listings, localize, localized_language, ApiFormat};
+use crate::ffxiv::Language;
+use crate::listing::DutyCategory;
+
+/// 이름 필드만 모은 응답 (듀티 없는 모집글, 고난이도 듀티를 분류 없이 올린 모집글 순으로 정렬됨)
+fn names(lang: Option<Language>) -> Vec<Value> {
+    let listings = vec![
+        ListingBuilder::new(1).duty(SAVAGE, DutyCategory::None).build(),
+        ListingBuilder::new(2).category(DutyCategory::TheHunt).build(),
+    ];
+
+    let mut api = build_api_listings(listings, &HashMap::new(), &HashMap::new(), FixedOffset::east_opt(0).unwrap());
+    if let Some(lang) = lang {
+        localize(&mut api, lang);
+    }
+    serde_json::to_value(&api)
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|container| {
+            let listing = &container["listing"];
+            json!({
+                "category": listing["category"],
+                "canonical_category": listing["canonical_category"],
+                "duty_type": listing["duty_type"],
+                "category_label": listing["category_label"],
+                "duty_name": listing["duty_info"]["name"],
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn full_format_keeps_every_language_and_debug_names() {
+    assert_eq!(
+        names(None),
+        vec![
+            json!({
+                "category": "TheHunt",
+                "canonical_category": "TheHunt",
+                "duty_type": "Other",
+                "category_label": { "en": "The Hunt", "ja": "モブハント", "de": "Hohe Jagd", "fr": "Contrats de chasse" },
+                "duty_name": null,
+            }),
+            json!({
+                "category": "None",
+                "canonical_category": "HighEndDuty",
+                "duty_type": "Normal",
+                "category_label": null,
+                "duty_name": {
+                    "en": "AAC Heavyweight M1 (Savage)",
+                    "ja": "至天の座アルカディア零式：ヘビー級1",
+                    "de": "Arkadion - Superschwergewicht R1 (episch)",
+                    "fr": "Poids lourds CCA - match 1 (sadique)",
+                },
+            }),
+        ]
+    );
+}
+
+#[test]
+fn localized_format_uses_one_language_and_stable_ids() {
+    assert_eq!(
+        names(Some(Language::Japanese)),
+        vec![
+            json!({
+                "category": { "id": "the_hunt", "display_name": "モブハント" },
+                "canonical_category": { "id": "the_hunt", "display_name": "モブハント" },
+                "duty_type": { "id": "other", "display_name": "その他" },
+                "category_label": "モブハント",
+                "duty_name": null,
+            }),
+            json!({
+                "category": { "id": "none", "display_name": "設定なし" },
+                "canonical_category": { "id": "high_end_duty", "display_name": "高難易度コンテンツ" },
+                "duty_type": { "id": "normal", "display_name": "コンテンツ" },
+                "category_label": null,
+                "duty_name": "至天の座アルカディア零式：ヘビー級1",
+            }),
+        ]
+    );
+}
+
+#[test]
+fn language_comes_from_the_query_then_accept_language() {
+    // 지정하지 않으면 기존 응답 (브라우저가 보내는 Accept-Language만으로는 바꾸지 않음)
+    assert_eq!(localized_language(None, ApiFormat::Full, Some("ja")), None);
+
+    assert_eq!(localized_language(Some("de"), ApiFormat::Full, Some("ja")), Some(Language::German));
+    assert_eq!(localized_language(None, ApiFormat::Localized, Some("fr-FR,fr;q=0.9")), Some(Language::French));
+    assert_eq!(localized_language(Some("xx"), ApiFormat::Localized, Some("ja")), Some(Language::Japanese));
+    assert_eq!(localized_language(None, ApiFormat::Localized, None), Some(Language::English));
+
+    // 언어마다 본문이 다르므로 ETag도 다름
+    assert_eq!(localized_etag("\"00000000000000ff\"", Language::Japanese), "\"00000000000000ff-ja\"");
+    assert_ne!(localized_etag("\"ff\"", Language::English), localized_etag("\"ff\"", Language::German));
+}
+
+#[test]
+fn every_category_has_a_distinct_snake_case_id() {
+    let ids: Vec<&str> = DutyCategory::ALL.iter().map(|category| category.id()).collect();
+    let mut unique = ids.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    assert_eq!(unique.len(), ids.len());
+    assert!(ids.iter().all(|id| id.bytes().all(|b| b.is_ascii_lowercase() || b == b'_')));
+}
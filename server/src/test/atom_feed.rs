@@ -0,0 +1,82 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use sestring::SeString;
+
+use super::fixture_world::{ListingBuilder, DUNGEON, SAVAGE};
+use crate::feeds::atom::{feed_tag, listing_tag, render_feed, AtomEntry};
+use crate::feeds::feed_entries;
+use crate::ffxiv::Language;
+use crate::listing::DutyCategory;
+use crate::listing_container::QueriedListing;
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+}
+
+/// `minutes`분 전에 업데이트된 모집글 (생성 서버 73 = Adamantoise, Aether)
+fn listing(id: u32, duty: u16, minutes: i64) -> ListingBuilder {
+    ListingBuilder::new(id).duty(duty, DutyCategory::HighEndDuty).member(1001, 19).at(now()).updated_minutes_ago(minutes)
+}
+
+fn queried(id: u32, duty: u16, minutes: i64) -> QueriedListing {
+    listing(id, duty, minutes).build()
+}
+
+fn ids(entries: &[AtomEntry]) -> Vec<&str> {
+    entries.iter().map(|entry| entry.id.as_str()).collect()
+}
+
+#[test]
+fn entries_are_recent_high_end_listings_newest_first() {
+    let listings = vec![queried(1, SAVAGE, 30), queried(2, SAVAGE, 5), queried(3, SAVAGE, 61), queried(4, DUNGEON, 1)];
+    let restart = listings[0].listing.last_server_restart;
+
+    let entries = feed_entries(&listings, None, None, Language::English, now());
+    assert_eq!(ids(&entries), vec![listing_tag(2, restart), listing_tag(1, restart)]);
+    assert_eq!(entries[0].updated, now() - TimeDelta::try_minutes(5).unwrap());
+}
+
+#[test]
+fn duty_and_data_centre_filters() {
+    let listings = vec![queried(1, SAVAGE, 1), queried(2, super::fixture_world::SAVAGE_SPLIT, 2)];
+
+    let by_duty = feed_entries(&listings, None, Some(SAVAGE), Language::English, now());
+    assert_eq!(by_duty.len(), 1);
+    assert!(by_duty[0].title.starts_with("AAC Heavyweight M1 (Savage) ("));
+
+    assert_eq!(feed_entries(&listings, Some("Aether"), None, Language::English, now()).len(), 2);
+    assert!(feed_entries(&listings, Some("Primal"), None, Language::English, now()).is_empty());
+}
+
+#[test]
+fn entry_content_has_description_and_slots() {
+    let ql = listing(1, SAVAGE, 1).description(SeString::parse("Clear party <3 & reclears".as_bytes()).unwrap()).build();
+
+    let entry = AtomEntry::new(&ql, Language::English);
+    assert_eq!(entry.id, listing_tag(1, ql.listing.last_server_restart));
+    assert!(entry.content.starts_with("Clear party <3 & reclears\n\nSlots: 1/8 (open: "));
+
+    // 같은 모집글은 업데이트해도 같은 ID
+    let later = queried(1, SAVAGE, 0);
+    assert_eq!(AtomEntry::new(&later, Language::Japanese).id, entry.id);
+    assert!(AtomEntry::new(&later, Language::Japanese).title.starts_with("至天の座アルカディア零式：ヘビー級1 ("));
+}
+
+#[test]
+fn feed_is_escaped_atom() {
+    let ql = listing(1, SAVAGE, 1).description(SeString::parse("<b>\"tank\" & 'heal'\u{7}</b>".as_bytes()).unwrap()).build();
+    let entries = vec![AtomEntry::new(&ql, Language::English)];
+
+    let xml = render_feed(&feed_tag("listings"), "Party Finder: high-end listings", &entries, now());
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+    assert!(xml.contains("<id>tag:remote-party-finder,2026:feeds/listings</id>"));
+    // 피드 `updated`는 가장 최근 항목
+    assert!(xml.contains("<updated>2026-03-01T11:59:00Z</updated>"));
+    assert!(xml.contains("&lt;b&gt;&quot;tank&quot; &amp; &apos;heal&apos;&lt;/b&gt;"));
+    assert!(!xml.contains('\u{7}'));
+    assert_eq!(xml.matches("<entry>").count(), 1);
+    assert!(xml.trim_end().ends_with("</feed>"));
+
+    let empty = render_feed(&feed_tag("listings"), "Party Finder", &[], now());
+    assert!(empty.contains("<updated>2026-03-01T12:00:00Z</updated>"));
+    assert!(!empty.contains("<entry>"));
+}
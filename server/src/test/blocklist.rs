@@ -0,0 +1,74 @@
+use chrono::Utc;
+
+use crate::config::Display;
+use crate::listing::{Blocklist, DutyCategory, DutyType, PartyFinderListing};
+use crate::mongo::current_listings_pipeline;
+use crate::web::handlers::publish_listings;
+use crate::web::listing_events::ListingEvent;
+
+fn listing(duty: u16, duty_type: DutyType, category: DutyCategory) -> PartyFinderListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.duty = duty;
+    listing.duty_type = duty_type;
+    listing.category = category;
+    listing
+}
+
+fn blocklist() -> Blocklist {
+    Blocklist::new(&Display {
+        blocked_duties: vec![1010],
+        blocked_categories: vec!["TreasureHunt".into(), "NotACategory".into()],
+        ..Default::default()
+    })
+}
+
+#[test]
+fn unknown_category_names_are_ignored() {
+    let blocklist = blocklist();
+    assert_eq!(blocklist.categories, vec![DutyCategory::TreasureHunt]);
+    assert_eq!(blocklist.category_names(), vec!["TreasureHunt".to_string()]);
+}
+
+#[test]
+fn pipeline_matches_blocklist_only_when_configured() {
+    let plain = current_listings_pipeline(Utc::now(), Utc::now(), &Blocklist::default());
+    let blocked = current_listings_pipeline(Utc::now(), Utc::now(), &blocklist());
+
+    assert_eq!(blocked.len(), plain.len() + 1);
+    assert!(!format!("{:?}", plain).contains("$nin"));
+    assert!(format!("{:?}", blocked).contains("$nin"));
+}
+
+#[test]
+fn duty_ids_only_block_normal_duties() {
+    let blocklist = blocklist();
+
+    assert!(blocklist.blocks(&listing(1010, DutyType::Normal, DutyCategory::HighEndDuty)));
+    // 룰렛 ID는 일반 듀티 ID와 겹침
+    assert!(!blocklist.blocks(&listing(1010, DutyType::Roulette, DutyCategory::DutyRoulette)));
+    assert!(blocklist.blocks(&listing(1, DutyType::Other, DutyCategory::TreasureHunt)));
+    assert!(!blocklist.blocks(&listing(1, DutyType::Normal, DutyCategory::Dungeon)));
+}
+
+#[test]
+fn published_listings_skip_blocked_duties() {
+    let (tx, mut rx) = tokio::sync::broadcast::channel(4);
+    let blocklist = blocklist();
+
+    publish_listings(&tx, &blocklist, vec![listing(1, DutyType::Other, DutyCategory::TreasureHunt)]);
+    publish_listings(
+        &tx,
+        &blocklist,
+        vec![
+            listing(1010, DutyType::Normal, DutyCategory::HighEndDuty),
+            listing(2, DutyType::Normal, DutyCategory::Dungeon),
+        ],
+    );
+
+    let ListingEvent::Updated(sent) = rx.try_recv().unwrap() else {
+        panic!("expected an update");
+    };
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].duty, 2);
+    assert!(rx.try_recv().is_err());
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use chrono::{DateTime, Utc};
+
+use crate::bookmarks::{apply, parse_listing_key, pin_watched, BookmarkRequest, BookmarkSigner, MAX_WATCHED};
+use crate::ffxiv::Language;
+use crate::listing::PartyFinderListing;
+use crate::listing_container::QueriedListing;
+use crate::template::listings::ListingsTemplate;
+use crate::web::handlers::build_renderable_listings;
+
+fn signer() -> BookmarkSigner {
+    BookmarkSigner::new("test secret")
+}
+
+fn keys(ids: &[u32]) -> Vec<String> {
+    ids.iter().map(|id| format!("{}/73/1700000000", id)).collect()
+}
+
+fn queried(id: u32, time_left: f64, now: DateTime<Utc>) -> QueriedListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.id = id;
+    listing.created_world = 73;
+    listing.last_server_restart = 1700000000;
+    listing.slots_available = listing.slots.len() as u8;
+
+    QueriedListing {
+        created_at: now,
+        updated_at: now,
+        updated_minute: now,
+        time_left,
+        ..super::queried(listing)
+    }
+}
+
+#[test]
+fn token_round_trip() {
+    let signer = signer();
+    let watched = keys(&[1, 2, 3]);
+
+    let token = signer.sign(&watched).unwrap();
+    assert!(!token.contains(['+', '/', '=']), "token must be URL-safe: {}", token);
+    assert_eq!(signer.verify(&token).unwrap(), watched);
+
+    assert_eq!(parse_listing_key("12/73/1700000000"), Some((12, 73, 1700000000)));
+    assert_eq!(parse_listing_key("12/73"), None);
+    assert_eq!(parse_listing_key("12/73/1/4"), None);
+}
+
+#[test]
+fn tampered_tokens_are_rejected() {
+    let signer = signer();
+    let token = signer.sign(&keys(&[1])).unwrap();
+    let (payload, signature) = token.split_once('.').unwrap();
+
+    // 다른 키 목록에 원래 서명
+    let forged = signer.sign(&keys(&[2])).unwrap();
+    let forged_payload = forged.split_once('.').unwrap().0;
+    assert!(signer.verify(&format!("{}.{}", forged_payload, signature)).is_err());
+
+    // 서명 변조 / 누락
+    let mut bad_signature = signature.to_string();
+    bad_signature.replace_range(0..1, if signature.starts_with('A') { "B" } else { "A" });
+    assert!(signer.verify(&format!("{}.{}", payload, bad_signature)).is_err());
+    assert!(signer.verify(payload).is_err());
+
+    // 다른 서명 키
+    assert!(BookmarkSigner::new("other secret").verify(&token).is_err());
+
+    // 검증 전에 길이 제한
+    assert!(signer.verify(&"A".repeat(10_000)).is_err());
+}
+
+#[test]
+fn size_limits_are_enforced() {
+    let signer = signer();
+    let full: Vec<u32> = (1..=MAX_WATCHED as u32).collect();
+    let token = signer.sign(&keys(&full)).unwrap();
+    assert_eq!(signer.verify(&token).unwrap().len(), MAX_WATCHED);
+
+    let overflow = apply(&signer, BookmarkRequest {
+        token: Some(token.clone()),
+        key: Some(keys(&[999]).remove(0)),
+        ..Default::default()
+    });
+    assert!(overflow.is_err());
+
+    // 이미 있는 키는 개수가 늘지 않음
+    let same = apply(&signer, BookmarkRequest {
+        token: Some(token),
+        key: Some(keys(&[1]).remove(0)),
+        ..Default::default()
+    });
+    assert_eq!(same.unwrap().keys.len(), MAX_WATCHED);
+
+    assert!(signer.sign(&["not a key".to_string()]).is_err());
+}
+
+#[test]
+fn apply_adds_and_prunes() {
+    let signer = signer();
+    let first = apply(&signer, BookmarkRequest {
+        key: Some(keys(&[1]).remove(0)),
+        ..Default::default()
+    })
+    .unwrap();
+    let second = apply(&signer, BookmarkRequest {
+        token: Some(first.token),
+        key: Some(keys(&[2]).remove(0)),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(second.keys, keys(&[1, 2]));
+
+    // 만료된 모집글 정리
+    let pruned = apply(&signer, BookmarkRequest {
+        token: Some(second.token),
+        remove: keys(&[1, 7]),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(pruned.keys, keys(&[2]));
+    assert_eq!(signer.verify(&pruned.token).unwrap(), keys(&[2]));
+}
+
+#[test]
+fn watched_listings_are_pinned_in_order() {
+    let now = Utc::now();
+    let containers = vec![queried(1, 100.0, now), queried(2, 200.0, now), queried(3, 300.0, now), queried(4, 400.0, now)];
+    let mut renderable = build_renderable_listings(containers, &HashMap::new(), &HashMap::new());
+
+    // 만료되어 목록에 없는 키(9)는 무시
+    pin_watched(&mut renderable, &keys(&[4, 9, 2]));
+    let order: Vec<u32> = renderable.iter().map(|l| l.container.listing.id).collect();
+    assert_eq!(order, [2, 4, 1, 3]);
+
+    let html = ListingsTemplate {
+        containers: renderable,
+        lang: Language::English,
+        notice: None,
+    }
+    .render()
+    .unwrap();
+
+    assert_eq!(html.matches(r#"class="listing watched""#).count(), 2);
+    let position = |id: u32| html.find(&format!(r#"data-id="{}""#, id)).unwrap();
+    assert!(position(2) < position(4));
+    assert!(position(4) < position(1));
+    assert!(position(1) < position(3));
+}
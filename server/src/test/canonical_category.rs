@@ -0,0 +1,119 @@
+use chrono::{FixedOffset, Utc};
+
+use super::queried;
+use crate::api::build_api_listings;
+use crate::config::Display;
+use crate::listing::{Blocklist, DutyCategory, DutyType, PartyFinderListing};
+use crate::mongo::current_listings_pipeline;
+
+/// AAC Heavyweight M1 (Savage)
+const SAVAGE: u16 = 1069;
+/// Another Aloalo Island (Savage): 변주 던전 분류
+const CRITERION_SAVAGE: u16 = 980;
+/// 고난이도가 아닌 듀티
+const NORMAL: u16 = 55;
+/// 게임 데이터에 없는 듀티
+const UNKNOWN: u16 = u16::MAX;
+
+fn listing(duty: u16, category: DutyCategory) -> PartyFinderListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.duty = duty;
+    listing.duty_type = DutyType::Normal;
+    listing.category = category;
+    listing
+}
+
+fn blocklist(category: &str) -> Blocklist {
+    Blocklist::new(&Display {
+        blocked_duties: Vec::new(),
+        blocked_categories: vec![category.into()],
+        ..Default::default()
+    })
+}
+
+#[test]
+fn agreeing_categories_are_kept() {
+    for (duty, category) in [
+        (SAVAGE, DutyCategory::HighEndDuty),
+        (CRITERION_SAVAGE, DutyCategory::VariantAndCriterionDungeon),
+        (NORMAL, DutyCategory::Dungeon),
+    ] {
+        let listing = listing(duty, category);
+        assert_eq!(listing.canonical_category(), category, "{}", duty);
+        assert!(!listing.miscategorized(), "{}", duty);
+    }
+}
+
+#[test]
+fn high_end_duties_use_the_duty_table_category() {
+    let savage = listing(SAVAGE, DutyCategory::None);
+    assert_eq!(savage.canonical_category(), DutyCategory::HighEndDuty);
+    assert!(savage.miscategorized());
+    assert_eq!(savage.html_pf_category(), DutyCategory::HighEndDuty.pf_category().as_str());
+
+    let criterion = listing(CRITERION_SAVAGE, DutyCategory::None);
+    assert_eq!(criterion.canonical_category(), DutyCategory::VariantAndCriterionDungeon);
+
+    // 업로드된 분류는 그대로 저장
+    assert_eq!(serde_json::to_value(&savage).unwrap()["category"], DutyCategory::None as u32);
+
+    // 숨김 설정도 듀티 표 기준
+    let blocklist = blocklist("HighEndDuty");
+    assert!(blocklist.blocks(&savage));
+    assert!(!blocklist.blocks(&listing(NORMAL, DutyCategory::Dungeon)));
+}
+
+#[test]
+fn unknown_and_non_duty_listings_keep_their_category() {
+    let unknown = listing(UNKNOWN, DutyCategory::None);
+    assert_eq!(unknown.canonical_category(), DutyCategory::None);
+    assert!(!unknown.miscategorized());
+
+    // 고난이도가 아닌 듀티는 분류를 고치지 않음
+    assert!(!listing(NORMAL, DutyCategory::Trial).miscategorized());
+
+    // 룰렛 ID는 듀티 ID와 겹침
+    let mut roulette = listing(SAVAGE, DutyCategory::DutyRoulette);
+    roulette.duty_type = DutyType::Roulette;
+    assert!(!roulette.miscategorized());
+}
+
+#[test]
+fn api_exposes_both_categories() {
+    let listings = vec![
+        queried(listing(SAVAGE, DutyCategory::None)),
+        queried(listing(NORMAL, DutyCategory::Dungeon)),
+    ];
+    let api = build_api_listings(listings, &Default::default(), &Default::default(), FixedOffset::east_opt(0).unwrap());
+    let api = serde_json::to_value(&api).unwrap();
+
+    let by_duty = |duty: u16| {
+        api.as_array()
+            .unwrap()
+            .iter()
+            .find(|container| container["listing"]["duty_info"]["id"] == duty)
+            .unwrap()["listing"]
+            .clone()
+    };
+    let savage = by_duty(SAVAGE);
+    assert_eq!(savage["category"], "None");
+    assert_eq!(savage["canonical_category"], "HighEndDuty");
+    assert_eq!(savage["miscategorized"], true);
+    assert_eq!(savage["pf_category"], DutyCategory::HighEndDuty.pf_category().as_str());
+    assert_eq!(savage["fflogs_supported"], true);
+
+    let normal = by_duty(NORMAL);
+    assert_eq!((&normal["category"], &normal["canonical_category"]), (&"Dungeon".into(), &"Dungeon".into()));
+    assert_eq!(normal["miscategorized"], false);
+}
+
+/// 집계는 저장된 `canonical_category`, 없으면 업로드된 분류로 묶음
+#[test]
+fn queries_group_and_filter_by_canonical_category() {
+    let stats = format!("{:?}", crate::stats::StatsQuery::default().build());
+    assert!(stats.contains("$canonical_category"));
+
+    let pipeline = format!("{:?}", current_listings_pipeline(Utc::now(), Utc::now(), &blocklist("TreasureHunt")));
+    assert!(pipeline.contains("canonical_category"));
+    assert!(pipeline.contains("listing.category"));
+}
@@ -0,0 +1,85 @@
+use askama::Template;
+use chrono::Utc;
+
+use crate::api::ApiReadableListing;
+use crate::ffxiv::Language;
+use crate::listing::{DutyCategory, DutyType, PartyFinderListing};
+use crate::listing_container::QueriedListing;
+use crate::stats::DutyInfo;
+use crate::template::listings::{ListingsTemplate, ParseDisplay, RenderableListing};
+
+/// 듀티 ID가 없는 모브 헌트 모집글
+fn hunt_train() -> PartyFinderListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.duty_type = DutyType::Other;
+    listing.category = DutyCategory::TheHunt;
+    listing.duty = 0;
+    // 공용 픽스처는 슬롯 정보가 하나뿐이므로 렌더링 가능한 크기로 맞춤
+    listing.slots_available = listing.slots.len() as u8;
+    listing
+}
+
+#[test]
+fn hunt_train_renders_category_label() {
+    let now = Utc::now();
+    let template = ListingsTemplate {
+        containers: vec![RenderableListing {
+            container: QueriedListing {
+                created_at: now,
+                updated_at: now,
+                updated_minute: now,
+                ..super::queried(hunt_train())
+            },
+            members: Vec::new(),
+            leader_parse: ParseDisplay::none(),
+            watched: false,
+            data_inconsistent: false,
+        }],
+        lang: Language::Japanese,
+        notice: None,
+    };
+
+    let html = template.render().unwrap();
+    assert!(html.contains(r#"data-category-label="モブハント""#));
+    assert!(!html.contains("<unknown>"));
+}
+
+#[test]
+fn hunt_train_api_has_category_label() {
+    let json = serde_json::to_value(ApiReadableListing::from(hunt_train())).unwrap();
+
+    assert_eq!(json["category"], "TheHunt");
+    assert!(json["duty_info"].is_null());
+    assert_eq!(json["category_label"]["en"], "The Hunt");
+    assert_eq!(json["category_label"]["fr"], "Contrats de chasse");
+}
+
+#[test]
+fn duty_listings_have_no_category_label() {
+    let listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    assert_eq!(listing.category_label(&Language::English), None);
+
+    let json = serde_json::to_value(ApiReadableListing::from(listing)).unwrap();
+    assert!(json["category_label"].is_null());
+}
+
+#[test]
+fn stats_bucket_uses_category_name() {
+    // 통계 집계 키는 DB 저장 값 (Other, TheHunt, 0)
+    let bucket = DutyInfo {
+        info: (0, DutyCategory::TheHunt as u32, 0),
+        count: 3,
+    };
+
+    assert_eq!(bucket.name(&Language::German), "Hohe Jagd");
+}
+
+#[test]
+fn labeled_categories_come_from_config() {
+    // 설정이 없으면 기본 목록
+    let display = crate::config::Display::default();
+    assert_eq!(crate::ffxiv::parse_labeled_categories(&display.labeled_categories), crate::ffxiv::DEFAULT_LABELED_CATEGORIES);
+
+    let display: crate::config::Display = toml::from_str(r#"labeled_categories = ["TheHunt", "NotACategory"]"#).unwrap();
+    assert_eq!(crate::ffxiv::parse_labeled_categories(&display.labeled_categories), [DutyCategory::TheHunt]);
+}
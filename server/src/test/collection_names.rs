@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+
+use crate::config::{Config, Logging, Mongo};
+use crate::infra::indexes::expected_indexes;
+use crate::infra::migration::collections;
+use crate::web::State;
+
+const CUSTOM: &str = r#"
+[web]
+host = "127.0.0.1:8000"
+
+[mongo]
+url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+database = "rpf_test"
+listings_collection = "test_listings"
+players_collection = "test_players"
+parses_collection = "test_parses"
+"#;
+
+#[test]
+fn collection_names_default_to_the_original_ones() {
+    let mongo: Mongo = toml::from_str(r#"url = "mongodb://127.0.0.1""#).unwrap();
+    assert_eq!(mongo.database, "rpf");
+    assert_eq!(mongo.listings_collection, "listings");
+    assert_eq!(mongo.players_collection, "players");
+    assert_eq!(mongo.parses_collection, "parses");
+}
+
+#[tokio::test]
+async fn configured_names_reach_every_collection_accessor() {
+    let config: Config = toml::from_str(CUSTOM).unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let state = State::new(Arc::new(config), log_handle).await.unwrap();
+
+    let namespaces = [
+        state.collection().primary().namespace(),
+        state.read_collection().primary().namespace(),
+        state.players_collection().primary().namespace(),
+        state.players_read_collection().primary().namespace(),
+        state.parse_collection().primary().namespace(),
+        state.parse_read_collection().primary().namespace(),
+    ];
+    let namespaces: Vec<String> = namespaces.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        namespaces,
+        vec![
+            "rpf_test.test_listings",
+            "rpf_test.test_listings",
+            "rpf_test.test_players",
+            "rpf_test.test_players",
+            "rpf_test.test_parses",
+            "rpf_test.test_parses",
+        ]
+    );
+}
+
+#[test]
+fn indexes_and_migration_use_the_configured_names() {
+    let config: Config = toml::from_str(CUSTOM).unwrap();
+
+    let expected = expected_indexes(&config.mongo, Duration::from_secs(28 * 24 * 3600));
+    let mut indexed: Vec<&str> = expected.iter().map(|index| index.collection.as_str()).collect();
+    indexed.dedup();
+    assert_eq!(
+        indexed,
+        vec!["test_listings", "role_demand", "test_parses", "test_players", "listings_archive"]
+    );
+
+    let copied: Vec<&str> = collections(&config.mongo).iter().map(|&(name, _, _)| name).collect();
+    assert_eq!(copied, vec!["test_listings", "test_players", "test_parses"]);
+}
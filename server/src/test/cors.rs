@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::{Response, StatusCode};
+use warp::hyper::body::Bytes;
+
+use crate::api::cors::{allowed_origin, preflight_allowed};
+use crate::config::{Config, Cors, Logging};
+use crate::web::routes::router;
+use crate::web::State;
+
+async fn state(cors: &str) -> Arc<State> {
+    let config: Config = toml::from_str(&format!(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+        {}
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+        cors
+    ))
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    State::new(Arc::new(config), log_handle).await.unwrap()
+}
+
+const PRIVATE: &str = r#"
+        [web.cors]
+        allowed_origins = ["https://tools.example.com"]
+"#;
+
+fn header<'a>(response: &'a Response<Bytes>, name: &str) -> Option<&'a str> {
+    response.headers().get(name).map(|value| value.to_str().unwrap())
+}
+
+async fn simple(state: &Arc<State>, origin: &str) -> Response<Bytes> {
+    warp::test::request()
+        .path("/api/version")
+        .header("origin", origin)
+        .reply(&router(Arc::clone(state)))
+        .await
+}
+
+async fn preflight(state: &Arc<State>, origin: &str, method: &str, headers: &str) -> Response<Bytes> {
+    warp::test::request()
+        .method("OPTIONS")
+        .path("/api/listings")
+        .header("origin", origin)
+        .header("access-control-request-method", method)
+        .header("access-control-request-headers", headers)
+        .reply(&router(Arc::clone(state)))
+        .await
+}
+
+#[test]
+fn origins_match_the_configured_list_or_wildcard() {
+    let private = Cors { allowed_origins: vec!["https://tools.example.com/".to_string()], ..Default::default() };
+    assert_eq!(allowed_origin(&private, "https://tools.example.com"), Some("https://tools.example.com"));
+    assert_eq!(allowed_origin(&private, "https://evil.example.com"), None);
+
+    let public = Cors { allowed_origins: vec!["*".to_string()], ..Default::default() };
+    assert_eq!(allowed_origin(&public, "https://anything.example"), Some("*"));
+
+    // 목록이 비어 있으면 아무 출처도 허용하지 않음
+    assert_eq!(allowed_origin(&Cors::default(), "https://tools.example.com"), None);
+
+    assert!(preflight_allowed(&private, "GET", Some("If-None-Match, content-type")));
+    assert!(preflight_allowed(&private, "post", None));
+    assert!(!preflight_allowed(&private, "DELETE", None));
+    assert!(!preflight_allowed(&private, "GET", Some("x-custom")));
+}
+
+#[tokio::test]
+async fn simple_requests_from_allowed_origins_get_cors_headers() {
+    let state = state(PRIVATE).await;
+
+    let response = simple(&state, "https://tools.example.com").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header(&response, "access-control-allow-origin"), Some("https://tools.example.com"));
+    assert_eq!(header(&response, "vary"), Some("origin"));
+    assert_eq!(header(&response, "access-control-expose-headers"), Some("etag, x-rpf-version"));
+
+    // 허용하지 않은 출처도 응답은 그대로지만 CORS 헤더가 없어 브라우저가 막음
+    let response = simple(&state, "https://evil.example.com").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+    assert_eq!(header(&response, "access-control-expose-headers"), None);
+}
+
+#[tokio::test]
+async fn preflight_is_answered_without_the_database() {
+    let state = state(PRIVATE).await;
+
+    // 데이터베이스에 연결할 수 없어도 라우트를 거치지 않으므로 바로 응답
+    let response = preflight(&state, "https://tools.example.com", "GET", "if-none-match").await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(header(&response, "access-control-allow-origin"), Some("https://tools.example.com"));
+    assert_eq!(header(&response, "access-control-allow-methods"), Some("GET, HEAD, POST"));
+    assert_eq!(header(&response, "access-control-allow-headers"), Some("content-type, if-none-match"));
+    assert_eq!(header(&response, "access-control-max-age"), Some("600"));
+
+    for response in [
+        preflight(&state, "https://evil.example.com", "GET", "if-none-match").await,
+        preflight(&state, "https://tools.example.com", "DELETE", "").await,
+        preflight(&state, "https://tools.example.com", "GET", "x-custom").await,
+    ] {
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(header(&response, "access-control-allow-origin"), None);
+        assert_eq!(header(&response, "access-control-allow-methods"), None);
+    }
+}
+
+#[tokio::test]
+async fn wildcard_mode_allows_every_origin() {
+    let state = state("[web.cors]\nallowed_origins = [\"*\"]").await;
+
+    let response = simple(&state, "https://anything.example").await;
+    assert_eq!(header(&response, "access-control-allow-origin"), Some("*"));
+    assert_eq!(header(&response, "vary"), None);
+
+    let response = preflight(&state, "https://anything.example", "GET", "").await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(header(&response, "access-control-allow-origin"), Some("*"));
+}
+
+#[tokio::test]
+async fn without_config_no_cors_headers_are_sent() {
+    let state = state("").await;
+
+    let response = simple(&state, "https://tools.example.com").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+
+    let response = preflight(&state, "https://tools.example.com", "GET", "").await;
+    assert!(response.status().is_client_error());
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+}
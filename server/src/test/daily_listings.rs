@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use chrono::{DateTime, NaiveDate, TimeDelta, TimeZone, Utc};
+use mongodb::bson::doc;
+
+use crate::ffxiv::Language;
+use crate::stats::daily::{fill_days, first_day, rollup_pipeline, rollup_window, DailyCount, LISTING_TTL, ROLLUP_LAG};
+use crate::stats::Statistics;
+use crate::template::stats::StatsTemplate;
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 2, 0, 30, 0).unwrap()
+}
+
+fn day(day: &str, count: u64) -> DailyCount {
+    DailyCount { day: day.to_string(), count }
+}
+
+#[test]
+fn rollups_continue_from_the_last_one() {
+    let last = now() - TimeDelta::minutes(30);
+    assert_eq!(rollup_window(Some(last), now()), Some((last, now() - ROLLUP_LAG)));
+
+    // 처음이거나 오래 멈췄으면 TTL로 남아 있는 모집글부터
+    assert_eq!(rollup_window(None, now()), Some((now() - LISTING_TTL, now() - ROLLUP_LAG)));
+    assert_eq!(rollup_window(Some(now() - TimeDelta::days(3)), now()).unwrap().0, now() - LISTING_TTL);
+
+    // 지연 구간 안이면 다음 롤업으로
+    assert_eq!(rollup_window(Some(now() - TimeDelta::minutes(2)), now()), None);
+}
+
+#[test]
+fn rollup_counts_public_listings_by_utc_day() {
+    let pipeline = rollup_pipeline(now() - TimeDelta::hours(1), now());
+    assert_eq!(
+        pipeline[0],
+        doc! {
+            "$match": {
+                "created_at": { "$gt": now() - TimeDelta::hours(1), "$lte": now() },
+                "listing.search_area": { "$bitsAllClear": 2 },
+            }
+        }
+    );
+    let group_id = pipeline[1].get_document("$group").unwrap().get_document("_id").unwrap();
+    assert_eq!(group_id.get_document("$dateToString").unwrap().get_str("format"), Ok("%Y-%m-%d"));
+
+    let counts: DailyCount = mongodb::bson::from_document(doc! { "_id": "2026-03-01", "count": 12_i64, "rolled_up_until": now() }).unwrap();
+    assert_eq!(counts, day("2026-03-01", 12));
+}
+
+#[test]
+fn missing_days_are_filled_with_zero() {
+    let today = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+    assert_eq!(first_day(today, 3), "2026-02-28");
+
+    let series = fill_days(&[day("2026-02-28", 4), day("2026-03-02", 1)], today, 3);
+    assert_eq!(series, [day("2026-02-28", 4), day("2026-03-01", 0), day("2026-03-02", 1)]);
+    assert_eq!(fill_days(&[], today, 90).len(), 90);
+}
+
+#[test]
+fn stats_page_and_json_show_the_daily_series() {
+    let stats = Statistics {
+        count: vec![],
+        aliases: HashMap::new(),
+        duties: vec![],
+        hosts: vec![],
+        hours: vec![],
+        days: vec![],
+        languages: vec![],
+        timeline: vec![],
+        fill: vec![],
+        fill_times: vec![],
+        daily: vec![day("2026-03-01", 7), day("2026-03-02", 3)],
+        generated_at: now(),
+    };
+    assert_eq!(
+        serde_json::to_value(&stats).unwrap()["daily"],
+        serde_json::json!([{ "day": "2026-03-01", "count": 7 }, { "day": "2026-03-02", "count": 3 }])
+    );
+
+    let render = |stats| {
+        StatsTemplate { stats, lang: Language::English, seven_days: false, unknown_ids: 0, hidden_from_listings: false }
+            .render()
+            .unwrap()
+    };
+    let html = render(stats.clone());
+    assert!(html.contains(r#"<table id="daily">"#));
+    assert!(html.contains("<td>2026-03-01</td>\n                    <td>7</td>"), "{}", html);
+
+    let html = render(Statistics { daily: vec![], ..stats });
+    assert!(!html.contains("dailyChart"));
+}
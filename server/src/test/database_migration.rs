@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{TimeZone, Utc};
+use mongodb::bson::doc;
+
+use crate::config::Mongo;
+use super::mongo_eval::Collection;
+use crate::infra::migration::{collections, counts_update, key_filter, zones_update, Merge};
+use crate::infra::mirror::Mirrored;
+
+/// 메모리 컬렉션 (`fail`이면 모든 작업 실패)
+#[derive(Default)]
+struct Store {
+    docs: Mutex<HashMap<u64, String>>,
+    fail: bool,
+}
+
+type Handle = Arc<Store>;
+
+fn store(docs: &[(u64, &str)]) -> Handle {
+    Arc::new(Store {
+        docs: Mutex::new(docs.iter().map(|&(id, doc)| (id, doc.to_string())).collect()),
+        fail: false,
+    })
+}
+
+fn failing() -> Handle {
+    Arc::new(Store { fail: true, ..Default::default() })
+}
+
+async fn find(store: Handle, ids: Vec<u64>) -> anyhow::Result<HashMap<u64, String>> {
+    anyhow::ensure!(!store.fail, "unavailable");
+    let docs = store.docs.lock().unwrap();
+    Ok(ids.into_iter().filter_map(|id| Some((id, docs.get(&id)?.clone()))).collect())
+}
+
+async fn find_one(store: Handle, id: u64) -> anyhow::Result<Option<String>> {
+    Ok(find(store, vec![id]).await?.remove(&id))
+}
+
+async fn upsert(store: Handle, id: u64, doc: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(!store.fail, "unavailable");
+    store.docs.lock().unwrap().insert(id, doc.to_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn reads_prefer_new_database_and_fall_back_per_document() {
+    // 1은 옮겨졌고 (새 쪽이 더 최근), 2는 아직 이전 데이터베이스에만 있음
+    let new = store(&[(1, "new-1")]);
+    let old = store(&[(1, "old-1"), (2, "old-2")]);
+    let mirrored = Mirrored::new(new, Some(old));
+
+    let found = mirrored.read_by_ids(&[1, 2, 3], find).await.unwrap();
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[&1], "new-1");
+    assert_eq!(found[&2], "old-2");
+
+    assert_eq!(mirrored.read_one(|s| find_one(s, 2)).await.unwrap().as_deref(), Some("old-2"));
+    assert_eq!(mirrored.read_one(|s| find_one(s, 3)).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn reads_ignore_old_database_after_migration() {
+    let mirrored = Mirrored::new(store(&[(1, "new-1")]), None);
+
+    let found = mirrored.read_by_ids(&[1, 2], find).await.unwrap();
+    assert_eq!(found.into_keys().collect::<Vec<_>>(), vec![1]);
+}
+
+#[tokio::test]
+async fn failed_fallback_still_returns_new_documents() {
+    let mirrored = Mirrored::new(store(&[(1, "new-1")]), Some(failing()));
+
+    let found = mirrored.read_by_ids(&[1, 2], find).await.unwrap();
+    assert_eq!(found.len(), 1);
+}
+
+#[tokio::test]
+async fn writes_go_to_both_databases() {
+    let (new, old) = (store(&[]), store(&[]));
+    let mirrored = Mirrored::new(Arc::clone(&new), Some(Arc::clone(&old)));
+
+    mirrored.write(|s| upsert(s, 7, "player")).await.unwrap();
+    assert_eq!(new.docs.lock().unwrap()[&7], "player");
+    assert_eq!(old.docs.lock().unwrap()[&7], "player");
+}
+
+#[tokio::test]
+async fn old_database_write_failures_are_best_effort() {
+    let new = store(&[]);
+    let mirrored = Mirrored::new(Arc::clone(&new), Some(failing()));
+    mirrored.write(|s| upsert(s, 7, "player")).await.unwrap();
+    assert_eq!(new.docs.lock().unwrap()[&7], "player");
+
+    // 새 데이터베이스 실패는 그대로 알리지만 이전 데이터베이스에는 기록
+    let old = store(&[]);
+    let mirrored = Mirrored::new(failing(), Some(Arc::clone(&old)));
+    assert!(mirrored.write(|s| upsert(s, 7, "player")).await.is_err());
+    assert_eq!(old.docs.lock().unwrap()[&7], "player");
+}
+
+#[test]
+fn copier_matches_documents_by_their_natural_key() {
+    let mongo: Mongo = toml::from_str(r#"url = "mongodb://127.0.0.1""#).unwrap();
+    let [(_, listing_keys, _), (_, player_keys, _), _] = collections(&mongo);
+    let listing = doc! {
+        "_id": 1,
+        "listing": { "id": 10, "last_server_restart": 20, "created_world": 73, "duty": 5 },
+    };
+    assert_eq!(
+        key_filter(&listing, listing_keys),
+        Some(doc! { "listing.id": 10, "listing.last_server_restart": 20, "listing.created_world": 73 }),
+    );
+
+    assert_eq!(key_filter(&doc! { "content_id": 5_i64 }, player_keys), Some(doc! { "content_id": 5_i64 }));
+    assert_eq!(key_filter(&doc! { "name": "x" }, player_keys), None);
+}
+
+#[test]
+fn only_listings_keep_the_new_document() {
+    let mongo: Mongo = toml::from_str(r#"url = "mongodb://127.0.0.1""#).unwrap();
+    let merges: Vec<Merge> = collections(&mongo).iter().map(|&(_, _, merge)| merge).collect();
+    assert_eq!(merges, [Merge::Skip, Merge::Counts, Merge::Zones]);
+}
+
+#[test]
+fn legacy_player_is_merged_into_a_dual_written_one() {
+    let at = |day| Utc.with_ymd_and_hms(2026, 1, day, 12, 0, 0).unwrap();
+    // 복사 전에 새 데이터베이스에 기록된 플레이어 (이전 문서에도 같은 관측이 더해짐)
+    let mut players = Collection {
+        docs: vec![doc! { "content_id": 5_i64, "name": "New Name", "home_world": 73, "last_seen": at(3), "seen_count": 2 }],
+    };
+    let legacy = doc! { "_id": 1, "content_id": 5_i64, "name": "Old Name", "home_world": 73, "last_seen": at(3), "seen_count": 40 };
+
+    let filter = doc! { "content_id": 5_i64 };
+    assert!(players.update_one(&filter, &counts_update(&legacy), true));
+    assert!(players.update_one(&filter, &counts_update(&legacy), true));
+    let merged = &players.docs[0];
+    assert_eq!(merged.get_i32("seen_count"), Ok(40));
+    assert_eq!(merged.get_datetime("last_seen").unwrap().to_chrono(), at(3));
+    assert_eq!(merged.get_str("name"), Ok("New Name"));
+
+    // 새 데이터베이스에 없던 플레이어는 그대로 옮김
+    let other = doc! { "_id": 2, "content_id": 6_i64, "name": "Only Old", "home_world": 79, "last_seen": at(1), "seen_count": 7 };
+    players.update_one(&doc! { "content_id": 6_i64 }, &counts_update(&other), true);
+    let copied = players.find(&doc! { "content_id": 6_i64 })[0].clone();
+    assert_eq!(copied, doc! { "content_id": 6_i64, "name": "Only Old", "home_world": 79, "seen_count": 7, "last_seen": at(1) });
+}
+
+#[test]
+fn legacy_parse_zones_fill_the_new_document() {
+    let now = Utc::now();
+    let mut parses = Collection { docs: vec![doc! { "content_id": 5_i64, "zones": { "73": { "fresh": true } } }] };
+    let legacy = doc! {
+        "_id": 1,
+        "content_id": 5_i64,
+        "zones": { "73": { "fresh": false }, "68": { "fresh": false } },
+        "fetch": { "attempt_count": 3 },
+    };
+
+    let filter = doc! { "content_id": 5_i64 };
+    parses.upsert_pipeline(&filter, &zones_update(&legacy), now);
+    let merged = &parses.docs[0];
+    let zones = merged.get_document("zones").unwrap();
+    assert_eq!(zones.get_document("73").unwrap().get_bool("fresh"), Ok(true));
+    assert_eq!(zones.get_document("68").unwrap().get_bool("fresh"), Ok(false));
+    assert_eq!(merged.get_document("fetch").unwrap().get_i32("attempt_count"), Ok(3));
+
+    // 새 데이터베이스에 없던 문서는 그대로 옮김
+    let mut empty = Collection::default();
+    empty.upsert_pipeline(&filter, &zones_update(&legacy), now);
+    let mut expected = legacy.clone();
+    expected.remove("_id");
+    assert_eq!(empty.docs[0].get_document("zones"), expected.get_document("zones"));
+    assert_eq!(empty.docs[0].get_document("fetch"), expected.get_document("fetch"));
+}
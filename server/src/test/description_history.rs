@@ -0,0 +1,92 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use mongodb::bson::{self, doc, Document};
+use sestring::SeString;
+
+use super::fixture_world::ListingBuilder;
+use super::mongo_eval::Collection;
+use crate::api::ApiReadableListingContainer;
+use crate::listing::{Blocklist, ListingFilter, PartyFinderListing};
+use crate::listing_container::{description_hash, ListingContainer, QueriedListing, MAX_DESCRIPTION_HISTORY};
+use crate::mongo::{current_listings_pipeline, filtered_listings_pipeline};
+
+fn at(minutes: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 2, 1, 20, 0, 0).unwrap() + TimeDelta::try_minutes(minutes).unwrap()
+}
+
+fn listing(description: &str) -> PartyFinderListing {
+    ListingBuilder::new(1).description(SeString::parse(description.as_bytes()).unwrap()).listing()
+}
+
+/// `insert_listing`의 업데이트 파이프라인으로 업로드 한 건 반영
+fn upload(listings: &mut Collection, description: &str, minutes: i64) -> ListingContainer {
+    let listing = listing(description);
+    listings.upload(&listing, "aaaaaaaaaaaaaaaa", false, at(minutes));
+    listings.stored(&listing)
+}
+
+/// 조회 파이프라인의 `$unset` 단계와 같이 필드를 지운 문서
+fn queried(mut document: Document, pipeline: &[Document]) -> QueriedListing {
+    let stage = pipeline.iter().find(|stage| stage.contains_key("$unset")).unwrap();
+    for field in stage.get_array("$unset").unwrap() {
+        document.remove(field.as_str().unwrap());
+    }
+    document.extend(doc! {
+        "updated_minute": bson::DateTime::from_chrono(at(5)),
+        "time_left": 100.0,
+        "uploader_count": 0,
+    });
+    bson::from_document(document).unwrap()
+}
+
+#[test]
+fn first_upload_and_repeats_do_not_create_history() {
+    let mut listings = Collection::default();
+    upload(&mut listings, "  LF healer, chill  ", 0);
+    let container = upload(&mut listings, "LF healer, chill", 1);
+
+    assert_eq!(container.description_text.as_deref(), Some("LF healer, chill"));
+    assert_eq!(container.description_hash, Some(description_hash("LF healer, chill")));
+    assert!(container.description_history.is_empty());
+}
+
+#[test]
+fn successive_edits_keep_capped_history() {
+    let mut listings = Collection::default();
+    let container = (0..8).map(|i| upload(&mut listings, &format!("edit {}", i), i)).last().unwrap();
+
+    assert_eq!(container.description_history.len(), MAX_DESCRIPTION_HISTORY);
+    // 가장 오래된 edit 0, 1은 버려지고 현재 설명은 이력에 없음
+    let texts: Vec<&str> = container.description_history.iter().map(|e| e.text.as_str()).collect();
+    assert_eq!(texts, ["edit 2", "edit 3", "edit 4", "edit 5", "edit 6"]);
+    assert_eq!(container.description_history[0].replaced_at, at(3));
+    assert_eq!(container.description_text.as_deref(), Some("edit 7"));
+}
+
+#[test]
+fn history_is_only_in_raw_document_and_flagged_view() {
+    let mut listings = Collection::default();
+    upload(&mut listings, "offensive text", 0);
+    upload(&mut listings, "LF healer", 5);
+
+    // 관리자 원본 조회는 저장된 문서를 그대로 반환
+    let document = listings.docs[0].clone();
+    assert_eq!(document.get_array("description_history").unwrap().len(), 1);
+
+    // 공개 조회 파이프라인은 비공개 필드를 제외
+    let public = current_listings_pipeline(at(0), at(0), &Blocklist::default());
+    let public = queried(document.clone(), &public);
+    assert!(public.description_history.is_empty());
+    let json = serde_json::to_string(&ApiReadableListingContainer::from(public)).unwrap();
+    assert!(!json.contains("offensive"));
+    assert!(!json.contains("description_history"));
+
+    // 금칙어 확인 목록은 이전 설명을 남김 (지문과 현재 설명 해시는 계속 제외)
+    let filter = ListingFilter { flagged: true, ..Default::default() };
+    let flagged = filtered_listings_pipeline(at(0), at(0), &Blocklist::default(), &filter);
+    let flagged = queried(document, &flagged);
+    assert_eq!(flagged.description_history[0].text, "offensive text");
+    let json = serde_json::to_value(ApiReadableListingContainer::from(flagged)).unwrap();
+    assert_eq!(json["description_history"][0]["text"], "offensive text");
+    assert_eq!(json["description_history"][0]["replaced_at"], "2026-02-01T20:05:00Z");
+    assert!(json.get("uploader_fingerprints").is_none());
+}
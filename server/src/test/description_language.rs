@@ -0,0 +1,145 @@
+use askama::Template;
+use chrono::Utc;
+use mongodb::bson::{self, doc, Bson};
+use sestring::payload::{AutoTranslatePayload, TextPayload};
+use sestring::{Payload, SeString};
+
+use super::fixture_world::ListingBuilder;
+use super::mongo_eval::Collection;
+use crate::ffxiv::Language;
+use crate::listing::description::{detect_language, DescriptionLanguage};
+use crate::listing_container::ListingContainer;
+use crate::stats::{LanguageInfo, Statistics};
+use crate::template::stats::StatsTemplate;
+
+/// `insert_listing`의 업데이트 파이프라인으로 설명 기록
+fn upload(listings: &mut Collection, description: SeString) -> ListingContainer {
+    let listing = ListingBuilder::new(1).description(description).listing();
+    listings.upload(&listing, "aaaaaaaaaaaaaaaa", false, Utc::now());
+    listings.stored(&listing)
+}
+
+fn text(text: &str) -> Payload {
+    Payload::Text(TextPayload(text.to_string()))
+}
+
+#[test]
+fn languages_are_detected_from_script_and_common_words() {
+    assert_eq!(detect_language("초보 환영 클리어 목표"), Some(DescriptionLanguage::Korean));
+    // 한국어 설명에 섞인 영어 약어
+    assert_eq!(detect_language("DPS 구함 ㄱㄱ"), Some(DescriptionLanguage::Korean));
+    assert_eq!(detect_language("クリア目的 初見歓迎"), Some(DescriptionLanguage::Japanese));
+    assert_eq!(detect_language("周回 速刷"), Some(DescriptionLanguage::Chinese));
+    assert_eq!(
+        detect_language("Prog to enrage, looking for a healer with the strats down"),
+        Some(DescriptionLanguage::English),
+    );
+    assert_eq!(detect_language("Wir suchen noch einen Heiler für die Prog"), Some(DescriptionLanguage::German));
+    assert_eq!(detect_language("On cherche un heal pour la prog, merci"), Some(DescriptionLanguage::French));
+    // 영어 문장 속 한 글자는 무시
+    assert_eq!(detect_language("LF healer for the weekly clear 草"), Some(DescriptionLanguage::English));
+
+    assert_eq!(detect_language(""), None);
+    assert_eq!(detect_language(" 123 !! "), None);
+}
+
+#[test]
+fn autotranslate_flag_is_recorded_with_the_description() {
+    let mut listings = Collection::default();
+    let container = upload(&mut listings, SeString(vec![text("LF healer ")]));
+    assert_eq!(container.description_language, Some(DescriptionLanguage::English));
+    assert!(!container.has_autotranslate);
+
+    let container = upload(
+        &mut listings,
+        SeString(vec![text("プログ "), Payload::AutoTranslate(AutoTranslatePayload { group: 1, key: 1 })]),
+    );
+    assert!(container.has_autotranslate);
+    assert_eq!(container.description_language, Some(DescriptionLanguage::Japanese));
+
+    // 자동 번역만 지워도 다시 기록
+    upload(&mut listings, SeString(vec![text("")]));
+    let document = &listings.docs[0];
+    assert_eq!(document.get("description_language"), Some(&Bson::Null));
+    assert_eq!(document.get_bool("has_autotranslate"), Ok(false));
+}
+
+#[test]
+fn documents_without_the_new_fields_still_load() {
+    let mut listings = Collection::default();
+    upload(&mut listings, SeString(vec![text("LF healer ")]));
+    let mut document = listings.docs[0].clone();
+    document.remove("description_language");
+    document.remove("has_autotranslate");
+
+    let container: ListingContainer = bson::from_document(document).unwrap();
+    assert_eq!(container.description_language, None);
+    assert!(!container.has_autotranslate);
+}
+
+/// `$facet` 결과 문서 (aggregation 출력 형태)
+fn facet() -> bson::Document {
+    doc! {
+        "count": [{ "count": 6 }],
+        "duties": [],
+        "hosts": [],
+        "hours": [],
+        "days": [],
+        "languages": [
+            {
+                "_id": "en",
+                "count": 3,
+                "with_description": 3,
+                "average_length": 120.4,
+                "autotranslate_fraction": 0.3333333333333333,
+            },
+            { "_id": "ko", "count": 2, "with_description": 2, "average_length": 18.0, "autotranslate_fraction": 0.0 },
+            // 설명이 빈 모집글만 있는 그룹은 평균이 null
+            { "_id": null, "count": 1, "with_description": 0, "average_length": null, "autotranslate_fraction": null },
+        ],
+    }
+}
+
+#[test]
+fn language_facet_deserialises() {
+    let stats: Statistics = bson::from_document(facet()).unwrap();
+
+    let languages: Vec<_> = stats.languages.iter().map(|info| info.language).collect();
+    assert_eq!(languages, vec![Some(DescriptionLanguage::English), Some(DescriptionLanguage::Korean), None]);
+    assert_eq!(
+        stats.languages[2],
+        LanguageInfo {
+            language: None,
+            count: 1,
+            with_description: 0,
+            average_length: None,
+            autotranslate_fraction: None,
+        },
+    );
+    assert_eq!(stats.languages[0].average_length_text(), "120");
+    assert_eq!(stats.languages[0].autotranslate_percent(), "33%");
+    assert_eq!(stats.languages[2].autotranslate_percent(), "-");
+
+    let json = serde_json::to_value(&stats.languages).unwrap();
+    assert_eq!(json[1]["language"], "ko");
+    assert!(json[2]["language"].is_null());
+
+    // 이 기능 이전에 캐시된 통계
+    let mut old = facet();
+    old.remove("languages");
+    assert!(bson::from_document::<Statistics>(old).unwrap().languages.is_empty());
+}
+
+#[test]
+fn comparison_table_is_rendered() {
+    let stats: Statistics = bson::from_document(facet()).unwrap();
+    let html = StatsTemplate { stats, lang: Language::English, seven_days: false, unknown_ids: 0, hidden_from_listings: false }
+        .render()
+        .unwrap();
+
+    let table = &html[html.find(r#"id="languages""#).unwrap()..];
+    assert!(table.contains("<td>Korean</td>"));
+    assert!(table.contains("<td>120</td>"));
+    assert!(table.contains("<td>33%</td>"));
+    assert!(table.contains("<td>Unknown / empty</td>"));
+}
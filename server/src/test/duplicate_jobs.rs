@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use chrono::FixedOffset;
+
+use super::queried;
+use crate::api::build_api_listings;
+use crate::listing::{JobFlags, PartyFinderListing, PartyFinderSlot, SearchAreaFlags};
+use crate::web::handlers::build_renderable_listings;
+
+/// 19 = PLD, 24 = WHM
+const JOBS: [u8; 8] = [19, 24, 19, 0, 0, 24, 0, 0];
+
+fn listing(one_player_per_job: bool) -> PartyFinderListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.slots_available = 8;
+    listing.slots = (0..8).map(|_| PartyFinderSlot { accepting: JobFlags::all() }).collect();
+    listing.jobs_present = JOBS.to_vec();
+    if one_player_per_job {
+        listing.search_area |= SearchAreaFlags::ONE_PLAYER_PER_JOB;
+    }
+    listing
+}
+
+fn api_json(listing: PartyFinderListing) -> serde_json::Value {
+    let api = build_api_listings(vec![queried(listing)], &HashMap::new(), &HashMap::new(), FixedOffset::east_opt(0).unwrap());
+    serde_json::to_value(&api).unwrap()[0]["listing"].clone()
+}
+
+/// `slots_filled`에서 채워진 자리
+fn filled(listing: &serde_json::Value) -> Vec<bool> {
+    listing["slots_filled"].as_array().unwrap().iter().map(|job| !job.is_null()).collect()
+}
+
+#[test]
+fn duplicates_are_cleared_only_for_one_player_per_job() {
+    let mut flagged = listing(true);
+    assert!(flagged.clear_duplicate_jobs());
+    assert_eq!(flagged.jobs_present, [19, 24, 0, 0, 0, 0, 0, 0]);
+    // 한 번 정리하면 더 이상 중복 없음
+    assert!(!flagged.clear_duplicate_jobs());
+
+    let mut unflagged = listing(false);
+    assert!(!unflagged.clear_duplicate_jobs());
+    assert_eq!(unflagged.jobs_present, JOBS);
+
+    // 빈 자리만 여러 개인 것은 중복이 아님
+    let mut empty = listing(true);
+    empty.jobs_present = vec![19, 0, 0, 0, 0, 0, 0, 0];
+    assert!(!empty.clear_duplicate_jobs());
+}
+
+#[test]
+fn api_nulls_duplicates_and_marks_listing() {
+    let flagged = api_json(listing(true));
+    assert_eq!(flagged["data_inconsistent"], true);
+    assert_eq!(filled(&flagged), [true, true, false, false, false, false, false, false]);
+    assert_eq!(flagged["filled_total"], 2);
+
+    let unflagged = api_json(listing(false));
+    assert_eq!(unflagged["data_inconsistent"], false);
+    assert_eq!(filled(&unflagged), [true, true, true, false, false, true, false, false]);
+}
+
+#[test]
+fn html_path_applies_same_rule() {
+    let renderable = build_renderable_listings(
+        vec![queried(listing(true)), queried(listing(false))],
+        &HashMap::new(),
+        &HashMap::new(),
+    );
+    let mut filled: Vec<usize> = renderable.iter().map(|l| l.container.listing.filled_total()).collect();
+    filled.sort_unstable();
+    assert_eq!(filled, [2, 4]);
+}
@@ -0,0 +1,96 @@
+use chrono::FixedOffset;
+use mongodb::bson;
+
+use super::queried;
+use crate::api::{build_api_listings, ListingsQuery};
+use crate::listing::{DutyFinderSettingsFlags, PartyFinderListing};
+
+/// 플러그인 열거형에 아직 이름이 없는 비트
+const FUTURE_BIT: u32 = 1 << 30;
+
+fn listing(settings: u32) -> PartyFinderListing {
+    let mut listing: serde_json::Value = serde_json::from_str(super::LISTING).unwrap();
+    listing["duty_finder_settings"] = serde_json::json!(settings);
+    serde_json::from_value(listing).unwrap()
+}
+
+#[test]
+fn unknown_bits_survive_round_trips() {
+    let settings = DutyFinderSettingsFlags::SILENCE_ECHO.bits() | FUTURE_BIT | (1 << 31);
+    let listing = listing(settings);
+    assert!(listing.duty_finder_settings.contains(DutyFinderSettingsFlags::SILENCE_ECHO));
+    assert_eq!(listing.duty_finder_settings.unknown_bits(), FUTURE_BIT | (1 << 31));
+
+    let json = serde_json::to_value(&listing).unwrap();
+    assert_eq!(json["duty_finder_settings"], settings);
+
+    let stored: PartyFinderListing = bson::from_document(bson::to_document(&listing).unwrap()).unwrap();
+    assert_eq!(stored.duty_finder_settings.bits(), settings);
+}
+
+#[test]
+fn api_exposes_settings_and_unknown_bits() {
+    let listings = vec![
+        queried(listing(DutyFinderSettingsFlags::UNDERSIZED_PARTY.bits() | FUTURE_BIT)),
+        queried(listing(0)),
+    ];
+
+    let api = serde_json::to_value(build_api_listings(
+        listings,
+        &Default::default(),
+        &Default::default(),
+        FixedOffset::east_opt(0).unwrap(),
+    ))
+    .unwrap();
+    let settings: Vec<&serde_json::Value> = api
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|container| &container["listing"]["duty_finder_settings"])
+        .collect();
+    assert!(settings.contains(&&serde_json::json!({
+        "undersized_party": true,
+        "minimum_item_level": false,
+        "silence_echo": false,
+        "unknown_bits": FUTURE_BIT,
+    })));
+    assert!(settings.iter().any(|settings| settings["unknown_bits"] == 0));
+}
+
+#[test]
+fn settings_are_shown_with_the_other_flags() {
+    let (_, flags) = listing(DutyFinderSettingsFlags::all().bits()).prepend_flags();
+    assert!(flags.contains("data-i18n='undersized_party'"));
+    assert!(flags.contains("data-i18n='minimum_item_level'"));
+    assert!(flags.contains("data-i18n='silence_echo'"));
+
+    // 이름이 없는 비트는 표시하지 않음
+    assert_eq!(listing(FUTURE_BIT).prepend_flags(), listing(0).prepend_flags());
+}
+
+async fn query(query: &str) -> ListingsQuery {
+    warp::test::request()
+        .path(&format!("/api/listings?{}", query))
+        .filter(&warp::query::<ListingsQuery>())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn listings_can_be_filtered_by_settings() {
+    let silenced = DutyFinderSettingsFlags::SILENCE_ECHO;
+    let undersized = DutyFinderSettingsFlags::UNDERSIZED_PARTY;
+
+    let filter = query("").await.duty_finder_filter();
+    assert!(filter.matches(silenced) && filter.matches(DutyFinderSettingsFlags::NONE));
+
+    let filter = query("silence_echo=true&undersized_party=false").await.duty_finder_filter();
+    assert!(filter.matches(silenced));
+    assert!(!filter.matches(silenced | undersized));
+    assert!(!filter.matches(DutyFinderSettingsFlags::NONE));
+
+    // 이름이 없는 새 설정은 원시 비트로 거름
+    let filter = query(&format!("duty_finder_bits={}", FUTURE_BIT)).await.duty_finder_filter();
+    assert!(filter.matches(listing(FUTURE_BIT | silenced.bits()).duty_finder_settings));
+    assert!(!filter.matches(silenced));
+}
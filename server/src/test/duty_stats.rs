@@ -0,0 +1,174 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{TimeDelta, Utc};
+use mongodb::bson::{doc, Document};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use crate::config::{Config, Logging};
+use crate::stats::{DutyInfo, Statistics, StatsQuery};
+use crate::web::duty_stats::{DutyStatsCache, DutyStatsKey};
+use crate::web::routes::router;
+use crate::web::State;
+
+/// 게임 데이터에 있는 듀티 (`DutyType::Normal`)
+fn known_duty() -> u16 {
+    *crate::ffxiv::DUTIES.keys().find(|&&duty| duty <= u32::from(u16::MAX)).unwrap() as u16
+}
+
+const UNKNOWN_DUTY: u16 = u16::MAX;
+
+fn facets(pipeline: &[Document]) -> &Document {
+    pipeline.last().unwrap().get_document("$facet").unwrap()
+}
+
+#[test]
+fn duty_query_reuses_the_facets_after_a_match() {
+    let all_time = StatsQuery::default().build();
+    let duty = StatsQuery::default().duty(1010).build();
+
+    assert_eq!(duty.len(), all_time.len() + 1);
+    assert_eq!(duty[0], doc! { "$match": { "listing.duty_type": 2, "listing.duty": 1010 } });
+    assert_eq!(duty[1..duty.len() - 1], all_time[..all_time.len() - 1]);
+
+    // 전체 통계의 facet은 그대로 두고 추이 / 자리 채움만 더함
+    let (base, extended) = (facets(&all_time), facets(&duty));
+    for (name, facet) in base {
+        assert_eq!(extended.get(name), Some(facet), "{}", name);
+    }
+    let added: Vec<&String> = extended.keys().filter(|name| !base.contains_key(name.as_str())).collect();
+    assert_eq!(added, vec!["timeline", "fill"]);
+    assert!(!base.contains_key("timeline"));
+}
+
+#[test]
+fn seven_day_duty_query_matches_the_window_first() {
+    let since = Utc::now() - TimeDelta::try_days(7).unwrap();
+    let pipeline = StatsQuery::default().duty(1010).since(since).build();
+
+    assert_eq!(pipeline[0], doc! { "$match": { "created_at": { "$gte": since } } });
+    assert_eq!(pipeline[1], doc! { "$match": { "listing.duty_type": 2, "listing.duty": 1010 } });
+
+    // 7일은 날짜별, 전체 기간은 월별 추이
+    let format = |pipeline: &[Document]| {
+        let timeline = facets(pipeline).get_array("timeline").unwrap();
+        let group = timeline[0].as_document().unwrap().get_document("$group").unwrap();
+        group.get_document("_id").unwrap().get_document("$dateToString").unwrap().get_str("format").unwrap().to_string()
+    };
+    assert_eq!(format(&pipeline), "%Y-%m-%d");
+    assert_eq!(format(&StatsQuery::default().duty(1010).build()), "%Y-%m");
+}
+
+fn statistics(listings: usize) -> Statistics {
+    serde_json::from_value(serde_json::json!({
+        "count": [{ "count": listings }],
+        "duties": [],
+        "hosts": [],
+        "hours": [],
+        "days": [],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn cache_is_keyed_by_duty_and_window() {
+    let cache = DutyStatsCache::new(Duration::from_secs(60), 2);
+    let now = Instant::now();
+    let key = |duty, seven_days| DutyStatsKey { duty, seven_days };
+
+    cache.insert(key(1010, false), statistics(10), now);
+    assert_eq!(cache.get(key(1010, false), now).unwrap().num_listings(), 10);
+    assert!(cache.get(key(1010, true), now).is_none());
+    assert!(cache.get(key(1011, false), now).is_none());
+    assert!(cache.get(key(1010, false), now + Duration::from_secs(60)).is_none());
+
+    // 가득 차면 가장 오래된 항목을 지움
+    cache.insert(key(1010, true), statistics(7), now + Duration::from_secs(1));
+    cache.insert(key(1011, false), statistics(3), now + Duration::from_secs(2));
+    assert!(cache.get(key(1010, false), now + Duration::from_secs(2)).is_none());
+    assert_eq!(cache.get(key(1010, true), now + Duration::from_secs(2)).unwrap().num_listings(), 7);
+}
+
+#[tokio::test]
+async fn failed_loads_are_not_cached() {
+    let cache = DutyStatsCache::default();
+    let key = DutyStatsKey { duty: 1010, seven_days: false };
+
+    assert!(cache.get_or_load(key, || async { anyhow::bail!("database unavailable") }).await.is_err());
+    let stats = cache.get_or_load(key, || async { Ok(statistics(4)) }).await.unwrap();
+    assert_eq!(stats.num_listings(), 4);
+    let cached = cache.get_or_load(key, || async { Ok(statistics(5)) }).await.unwrap();
+    assert!(Arc::ptr_eq(&stats, &cached));
+}
+
+#[tokio::test]
+async fn concurrent_loads_of_the_same_key_are_shared() {
+    let cache = DutyStatsCache::default();
+    let key = |duty| DutyStatsKey { duty, seven_days: false };
+    let loads = AtomicU32::new(0);
+    let load = |count| {
+        let loads = &loads;
+        move || async move {
+            loads.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(statistics(count))
+        }
+    };
+
+    let (first, second, other) = tokio::join!(
+        cache.get_or_load(key(1010), load(4)),
+        cache.get_or_load(key(1010), load(5)),
+        cache.get_or_load(key(1011), load(6)),
+    );
+    assert!(Arc::ptr_eq(&first.unwrap(), &second.unwrap()));
+    assert_eq!(other.unwrap().num_listings(), 6);
+    assert_eq!(loads.load(Ordering::Relaxed), 2);
+
+    // 실패도 기다리던 요청에 그대로 전달
+    let failing = || async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        anyhow::bail!("database unavailable")
+    };
+    let (first, second) = tokio::join!(cache.get_or_load(key(1012), failing), cache.get_or_load(key(1012), load(7)));
+    assert!(first.is_err());
+    assert!(second.unwrap_err().to_string().contains("database unavailable"));
+    assert_eq!(loads.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn only_known_duties_link_to_their_page() {
+    let duty = known_duty();
+    assert_eq!(DutyInfo { info: (2, 0, duty), count: 1 }.drill_down_id(), Some(duty));
+    assert_eq!(DutyInfo { info: (2, 0, UNKNOWN_DUTY), count: 1 }.drill_down_id(), None);
+    // 룰렛 / 카테고리로 묶인 행
+    assert_eq!(DutyInfo { info: (1, 0, duty), count: 1 }.drill_down_id(), None);
+    assert_eq!(DutyInfo { info: (0, 0, 0), count: 1 }.drill_down_id(), None);
+}
+
+#[tokio::test]
+async fn unknown_duties_are_not_found() {
+    assert!(crate::ffxiv::duty(u32::from(UNKNOWN_DUTY)).is_none());
+
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let routes = router(State::new(Arc::new(config), log_handle).await.unwrap());
+
+    for path in ["/stats/duty/65535", "/stats/7days/duty/65535", "/api/stats/duty/65535"] {
+        let response = warp::test::request().path(path).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", path);
+    }
+    let response = warp::test::request().path("/api/stats/duty/65535").reply(&routes).await;
+    assert_eq!(response.body().as_ref(), br#"{"error":"unknown duty"}"#);
+}
@@ -0,0 +1,117 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+
+use crate::config::EmptyBackoff;
+use crate::fflogs::{is_empty_result, FetchAccounting};
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 6, 12, 0, 0).unwrap()
+}
+
+fn hours(n: i64) -> TimeDelta {
+    TimeDelta::try_hours(n).unwrap()
+}
+
+const ZONE: u32 = 73;
+
+/// `count`번 연속으로 `ZONE`의 빈 결과를 기록 (1시간 간격, 마지막 기록 시각 반환)
+fn record_empty(fetch: &mut FetchAccounting, count: u32) -> DateTime<Utc> {
+    let mut now = start();
+    for _ in 0..count {
+        now += hours(1);
+        fetch.record(ZONE, true, now);
+    }
+    now
+}
+
+fn streak(fetch: &FetchAccounting, zone_id: u32) -> u32 {
+    fetch.zones[&zone_id.to_string()].consecutive_empty
+}
+
+#[test]
+fn empty_result_means_no_logged_parse() {
+    assert!(is_empty_result(&[]));
+    assert!(is_empty_result(&[(93, -1.0), (94, -1.0)]));
+    assert!(!is_empty_result(&[(93, -1.0), (94, 0.0)]));
+}
+
+#[test]
+fn counters_track_empty_streak_and_reset_on_result() {
+    let mut fetch = FetchAccounting::default();
+    let last = record_empty(&mut fetch, 4);
+    assert_eq!(fetch.attempt_count, 4);
+    assert_eq!(streak(&fetch, ZONE), 4);
+    assert_eq!(fetch.last_attempt_at, last);
+
+    let now = last + hours(24);
+    fetch.record(ZONE, false, now);
+    assert_eq!(fetch.attempt_count, 5);
+    assert_eq!(streak(&fetch, ZONE), 0);
+    assert_eq!(fetch.last_attempt_at, now);
+    assert!(!fetch.in_empty_backoff(ZONE, &EmptyBackoff::default(), now + hours(1)));
+}
+
+#[test]
+fn backoff_thresholds_follow_policy() {
+    let policy = EmptyBackoff::default();
+
+    // 2번까지는 매 주기 조회
+    let mut fetch = FetchAccounting::default();
+    let last = record_empty(&mut fetch, 2);
+    assert_eq!(fetch.empty_backoff_until(ZONE, &policy), None);
+    assert!(!fetch.in_empty_backoff(ZONE, &policy, last));
+
+    // 3번째부터 하루에 한 번
+    let last = record_empty(&mut fetch, 1);
+    assert_eq!(streak(&fetch, ZONE), 3);
+    assert_eq!(fetch.empty_backoff_until(ZONE, &policy), Some(last + hours(24)));
+    assert!(fetch.in_empty_backoff(ZONE, &policy, last + hours(23)));
+    assert!(!fetch.in_empty_backoff(ZONE, &policy, last + hours(24)));
+
+    // 10번째부터 일주일에 한 번
+    let mut fetch = FetchAccounting::default();
+    let last = record_empty(&mut fetch, 10);
+    assert_eq!(fetch.empty_backoff_until(ZONE, &policy), Some(last + hours(24 * 7)));
+    assert!(fetch.in_empty_backoff(ZONE, &policy, last + hours(24 * 6)));
+    assert!(!fetch.in_empty_backoff(ZONE, &policy, last + hours(24 * 7)));
+}
+
+#[test]
+fn policy_parameters_come_from_config() {
+    let policy: EmptyBackoff = toml::from_str("daily_after = 1\ndaily_hours = 6").unwrap();
+    assert_eq!(policy.daily_after, 1);
+    assert_eq!(policy.weekly_after, 10);
+
+    let mut fetch = FetchAccounting::default();
+    let last = record_empty(&mut fetch, 1);
+    assert_eq!(fetch.empty_backoff_until(ZONE, &policy), Some(last + hours(6)));
+}
+
+/// 한 주기에 여러 Zone을 조회해도 Zone마다 한 번씩만 셈
+#[test]
+fn streaks_are_counted_per_zone() {
+    let policy = EmptyBackoff::default();
+    let mut fetch = FetchAccounting::default();
+
+    // 한 주기에 세 Zone 모두 빈 결과: 아직 백오프 전
+    let now = start();
+    for zone_id in [71, 72, ZONE] {
+        fetch.record(zone_id, true, now);
+    }
+    assert_eq!(fetch.attempt_count, 3);
+    assert!([71, 72, ZONE].iter().all(|&zone_id| streak(&fetch, zone_id) == 1));
+    assert!(!fetch.in_empty_backoff(ZONE, &policy, now));
+
+    // 한 Zone에 기록이 있어도 다른 Zone의 연속 횟수는 그대로
+    let mut now = now;
+    for _ in 0..2 {
+        now += hours(1);
+        fetch.record(71, false, now);
+        fetch.record(ZONE, true, now);
+    }
+    assert_eq!(streak(&fetch, 71), 0);
+    assert_eq!(streak(&fetch, ZONE), 3);
+    assert!(!fetch.in_empty_backoff(71, &policy, now));
+    assert!(fetch.in_empty_backoff(ZONE, &policy, now));
+    // 조회한 적 없는 Zone은 백오프 대상이 아님
+    assert_eq!(fetch.empty_backoff_until(74, &policy), None);
+}
@@ -0,0 +1,172 @@
+use chrono::FixedOffset;
+use serde_json::Value;
+
+use super::fixture_world::{ListingBuilder, World, SAVAGE};
+use crate::api::build_api_listings;
+use crate::fflogs::{FetchAccounting, ParseCacheDoc, ProfileMiss};
+use crate::listing::{Blocklist, DutyCategory};
+use crate::template::listings::RenderableListing;
+use crate::web::handlers::{build_renderable_listings, publish_listings};
+use crate::web::listing_events::ListingEvent;
+
+const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/test/fixtures/golden/api_listings.json");
+
+fn utc() -> FixedOffset {
+    FixedOffset::east_opt(0).unwrap()
+}
+
+fn page(world: &World) -> Vec<RenderableListing> {
+    build_renderable_listings(world.listings.clone(), &world.players, &world.parse_docs)
+}
+
+fn api(world: &World) -> Value {
+    let listings = build_api_listings(world.listings.clone(), &world.players, &world.parse_docs, utc());
+    serde_json::to_value(&listings).unwrap()
+}
+
+#[test]
+fn listings_page_and_api_show_the_same_listings() {
+    let world = World::rich();
+    let page = page(&world);
+    let api = api(&world);
+    let api = api.as_array().unwrap();
+
+    let page_ids: Vec<u32> = page.iter().map(|listing| listing.container.listing.id).collect();
+    let api_ids: Vec<u64> = api.iter().map(|listing| listing["listing"]["id"].as_u64().unwrap()).collect();
+    assert_eq!(page_ids, [1, 3, 4, 2, 5]);
+    assert_eq!(api_ids, [1, 3, 4, 2, 5]);
+
+    for (rendered, json) in page.iter().zip(api) {
+        let listing = &rendered.container.listing;
+        let json = &json["listing"];
+        assert_eq!(json["filled_total"], listing.filled_total(), "listing {}", listing.id);
+        assert_eq!(json["total_capacity"], listing.total_capacity(), "listing {}", listing.id);
+        assert_eq!(json["data_inconsistent"], rendered.data_inconsistent, "listing {}", listing.id);
+
+        let members = json["members"].as_array().unwrap();
+        assert_eq!(members.len(), rendered.members.len(), "listing {}", listing.id);
+        for (member, json) in rendered.members.iter().zip(members) {
+            let parse = &member.parse;
+            assert_eq!(json["content_id"], member.player.content_id);
+            assert_eq!(json["name"], member.player.name.as_str());
+            assert_eq!(json["known"], member.is_known());
+            assert_eq!(json["parse_percentile"], serde_json::json!(parse.primary_percentile));
+            assert_eq!(json["parse_color_class"], parse.primary_color_class.as_str());
+            assert_eq!(json["secondary_parse_percentile"], serde_json::json!(parse.secondary_percentile));
+            assert_eq!(json["secondary_parse_color_class"], parse.secondary_color_class.as_str());
+            assert_eq!(json["parse_role_mismatch"], parse.role_mismatch);
+            assert_eq!(json["parse_hidden"], parse.hidden);
+        }
+
+        let leader = &rendered.leader_parse;
+        assert_eq!(json["leader_parse"]["percentile"], serde_json::json!(leader.primary_percentile), "listing {}", listing.id);
+        assert_eq!(json["leader_parse"]["color_class"], leader.primary_color_class.as_str());
+        assert_eq!(json["leader_parse"]["secondary_percentile"], serde_json::json!(leader.secondary_percentile));
+        assert_eq!(json["fflogs_supported"], listing.fflogs_supported(), "listing {}", listing.id);
+    }
+}
+
+/// 비공개 표시와 이전 티어 듀티도 두 경로가 같은 규칙을 사용
+#[test]
+fn hidden_profiles_and_previous_tiers_match_between_page_and_api() {
+    let mut world = World::default()
+        .listing(ListingBuilder::new(1).duty(SAVAGE, DutyCategory::HighEndDuty).leader(1001).member(1001, 19).member(1002, 24))
+        .listing(ListingBuilder::new(2).duty(986, DutyCategory::HighEndDuty).leader(1003).member(1003, 21))
+        .player(1001, "Hidden Leader", 73)
+        .player(1002, "Shown Member", 73)
+        .player(1003, "Previous Tier", 73)
+        .parse(1003, 62, 93, 77.0, 21);
+    world.parse_docs.insert(
+        1001,
+        ParseCacheDoc {
+            content_id: 1001,
+            zones: Default::default(),
+            fetch: Some(FetchAccounting { profile: Some(ProfileMiss::Hidden), ..Default::default() }),
+        },
+    );
+
+    let page = page(&world);
+    let api = api(&world);
+    let hidden = api.as_array().unwrap().iter().find(|listing| listing["listing"]["id"] == 1).unwrap();
+    let previous = api.as_array().unwrap().iter().find(|listing| listing["listing"]["id"] == 2).unwrap();
+
+    assert!(page.iter().find(|rendered| rendered.container.listing.id == 1).unwrap().leader_parse.hidden);
+    assert_eq!(hidden["listing"]["leader_parse"]["hidden"], true);
+    assert_eq!(hidden["listing"]["leader_parse"]["color_class"], "parse-hidden");
+    let members: Vec<bool> = hidden["listing"]["members"].as_array().unwrap().iter().map(|m| m["parse_hidden"] == true).collect();
+    assert_eq!(members, [true, false]);
+
+    let rendered = page.iter().find(|rendered| rendered.container.listing.id == 2).unwrap();
+    assert!(!rendered.container.listing.high_end());
+    assert!(rendered.container.listing.fflogs_supported());
+    assert_eq!(previous["listing"]["fflogs_supported"], true);
+    assert_eq!(rendered.leader_parse.primary_percentile, Some(77));
+    assert_eq!(previous["listing"]["leader_parse"]["percentile"], 77);
+}
+
+#[test]
+fn rich_world_exercises_every_member_rule() {
+    let page = page(&World::rich());
+    let split = &page[0];
+    let members: Vec<(u64, Option<u8>, Option<u8>, bool)> = split
+        .members
+        .iter()
+        .map(|m| (m.player.content_id, m.parse.primary_percentile, m.parse.secondary_percentile, m.is_known()))
+        .collect();
+    // 잡이 없는 자리(1005)는 멤버로 표시하지 않음, 1003은 플레이어 정보 없음
+    assert_eq!(
+        members,
+        [
+            (1001, Some(98), Some(42), true),
+            (1002, Some(55), None, true),
+            (1003, Some(12), None, false),
+            (1004, None, Some(100), true),
+        ]
+    );
+    assert!(split.members[1].parse.role_mismatch);
+    assert_eq!(split.leader_parse.primary_percentile, Some(98));
+
+    let duplicate = page.iter().find(|listing| listing.container.listing.id == 5).unwrap();
+    assert!(duplicate.data_inconsistent);
+    let ids: Vec<u64> = duplicate.members.iter().map(|m| m.player.content_id).collect();
+    assert_eq!(ids, [1008, 1010]);
+}
+
+#[test]
+fn websocket_frames_use_the_same_member_rule() {
+    let world = World::rich();
+    let page = page(&world);
+    let (tx, mut rx) = tokio::sync::broadcast::channel(4);
+
+    publish_listings(
+        &tx,
+        &Blocklist::default(),
+        world.listings.iter().map(|container| container.listing.clone()).collect(),
+    );
+    let ListingEvent::Updated(frame) = rx.try_recv().unwrap() else {
+        panic!("expected an update");
+    };
+
+    assert_eq!(frame.len(), page.len());
+    for listing in frame.iter() {
+        let rendered = page.iter().find(|rendered| rendered.container.listing.id == listing.id).unwrap();
+        assert_eq!(listing.jobs_present, rendered.container.listing.jobs_present, "listing {}", listing.id);
+        assert_eq!(listing.filled_total(), rendered.container.listing.filled_total());
+        let members: Vec<u64> = listing.member_slots().map(|(content_id, _)| content_id).collect();
+        let rendered: Vec<u64> = rendered.members.iter().map(|m| m.player.content_id).collect();
+        assert_eq!(members, rendered, "listing {}", listing.id);
+    }
+}
+
+/// `/api/listings` 응답 고정 (`UPDATE_GOLDEN=1 cargo test`로 갱신)
+#[test]
+fn api_listings_match_golden_snapshot() {
+    let actual = serde_json::to_string_pretty(&api(&World::rich())).unwrap() + "\n";
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(std::path::Path::new(GOLDEN).parent().unwrap()).unwrap();
+        std::fs::write(GOLDEN, &actual).unwrap();
+    }
+
+    let expected = std::fs::read_to_string(GOLDEN).unwrap();
+    assert!(actual == expected, "API output differs from {}; rerun with UPDATE_GOLDEN=1 to update", GOLDEN);
+}
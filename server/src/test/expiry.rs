@@ -0,0 +1,72 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+
+use crate::listing::{ExpiryInfo, EXPIRING_SOON_SECONDS};
+use crate::listing_container::QueriedListing;
+
+/// 밀리초가 있는 업데이트 시각 (초 경계 테스트용)
+fn updated_at() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap() + TimeDelta::try_milliseconds(500).unwrap()
+}
+
+fn ms(n: i64) -> TimeDelta {
+    TimeDelta::try_milliseconds(n).unwrap()
+}
+
+#[test]
+fn seconds_left_truncates_at_second_boundaries() {
+    let expires_at = updated_at() + TimeDelta::try_seconds(600).unwrap();
+
+    let fresh = ExpiryInfo::new(updated_at(), 600, updated_at());
+    assert_eq!(fresh.expires_at, expires_at);
+    assert_eq!(fresh.seconds_left, 600);
+    assert_eq!(fresh.expires_at_timestamp(), expires_at.timestamp());
+
+    // 1ms 지나면 599초
+    assert_eq!(ExpiryInfo::new(updated_at(), 600, updated_at() + ms(1)).seconds_left, 599);
+    // 만료 직전 / 직후
+    assert_eq!(ExpiryInfo::new(updated_at(), 600, expires_at - ms(1)).seconds_left, 0);
+    let expired = ExpiryInfo::new(updated_at(), 600, expires_at + ms(5_000));
+    assert_eq!(expired.seconds_left, 0);
+    assert_eq!(expired.time_left(expires_at + ms(5_000)), -5.0);
+}
+
+#[test]
+fn expiring_soon_threshold() {
+    let expires_at = updated_at() + TimeDelta::try_seconds(3600).unwrap();
+    let at = |seconds_before: i64, extra_ms: i64| {
+        ExpiryInfo::new(updated_at(), 3600, expires_at - ms(seconds_before * 1000 + extra_ms))
+    };
+
+    assert!(!at(EXPIRING_SOON_SECONDS, 0).is_expiring_soon);
+    assert!(!at(EXPIRING_SOON_SECONDS, 999).is_expiring_soon);
+    // 임계값 1ms 전부터 버림으로 299초 → 곧 만료
+    assert!(at(EXPIRING_SOON_SECONDS, -1).is_expiring_soon);
+    assert!(at(0, 0).is_expiring_soon);
+}
+
+#[test]
+#[allow(deprecated)]
+fn queried_listing_derives_everything_from_one_instant() {
+    let mut listing = QueriedListing {
+        created_at: updated_at(),
+        updated_at: updated_at(),
+        updated_minute: updated_at(),
+        // 집계 쿼리가 DB 서버 시계로 계산한 값 (몇 초 어긋남)
+        time_left: 1203.0,
+        ..super::queried(serde_json::from_str(super::LISTING).unwrap())
+    };
+    listing.listing.seconds_remaining = 1800;
+
+    let now = updated_at() + ms(600_250);
+    listing.refresh_expiry(now);
+
+    assert_eq!(listing.expiry.seconds_left, 1199);
+    assert_eq!(listing.time_left, 1199.75);
+    assert_eq!(listing.time_left as i64, listing.expiry.seconds_left);
+    // 이전 헬퍼도 같은 값을 사용
+    assert_eq!(listing.time_left_seconds(), listing.expiry.seconds_left);
+    assert_eq!(
+        listing.human_time_left().to_string(),
+        listing.expiry.human_seconds_left().to_string(),
+    );
+}
@@ -0,0 +1,170 @@
+use std::io::BufRead;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use flate2::read::GzDecoder;
+
+use super::mongo_eval::{matches, run_pipeline};
+use crate::export::anonymize::{group_key, AnonymizedListing, GroupCounts, PeriodSalt};
+use crate::export::{
+    count_groups, dataset_date, dataset_file_name, export_dates, write_dataset, DatasetWriter, ExportReport,
+};
+use crate::listing_container::ListingContainer;
+use crate::mongo::{export_staging_pipeline, EXPORT_STAGING_COLLECTION};
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+}
+
+/// 73 = Adamantoise (Aether), 49 = Kujata (Elemental), 9999 = 알 수 없는 서버
+fn container(world: u16, duty: u16, leader: u64, members: impl IntoIterator<Item = i64>) -> ListingContainer {
+    let mut listing: crate::listing::PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.created_world = world;
+    listing.duty = duty;
+    listing.leader_content_id = leader;
+    listing.member_content_ids = members.into_iter().collect();
+
+    ListingContainer {
+        created_at: Utc.with_ymd_and_hms(2026, 1, 3, 21, 47, 12).unwrap(),
+        updated_at: Utc.with_ymd_and_hms(2026, 1, 3, 22, 5, 0).unwrap(),
+        listing,
+        upload_count: 3,
+        uploader_fingerprints: vec!["0123456789abcdef".to_string()],
+        description_hash: None,
+        description_text: None,
+        description_history: Vec::new(),
+        description_language: None,
+        has_autotranslate: false,
+        unconfirmed_at: None,
+        canonical_category: None,
+        hidden: false,
+        flagged: false,
+        slots_history: Vec::new(),
+    }
+}
+
+#[test]
+fn hashes_are_stable_within_a_salt_period() {
+    let early = PeriodSalt::for_date("secret", date(1, 1));
+    let late = PeriodSalt::for_date("secret", date(1, 31));
+
+    assert_eq!(early.period, "2026-01");
+    assert_eq!(early.hash(42), late.hash(42));
+    assert_eq!(early.hash(42).len(), 32);
+    assert_ne!(early.hash(42), early.hash(43));
+}
+
+#[test]
+fn hashes_change_across_salt_periods_and_secrets() {
+    let january = PeriodSalt::for_date("secret", date(1, 31));
+    let february = PeriodSalt::for_date("secret", date(2, 1));
+    let other_secret = PeriodSalt::for_date("other", date(1, 31));
+
+    assert_eq!(february.period, "2026-02");
+    assert_ne!(january.hash(42), february.hash(42));
+    assert_ne!(january.hash(42), other_secret.hash(42));
+    // 원래 ID가 해시에 그대로 드러나지 않음
+    assert!(!january.hash(42).contains("42"));
+}
+
+#[test]
+fn groups_smaller_than_k_are_suppressed() {
+    let aether = group_key(&container(73, 55, 1, []).listing).unwrap();
+    let elemental = group_key(&container(49, 55, 1, []).listing).unwrap();
+    assert_eq!(aether.data_centre, "Aether");
+    assert_ne!(aether, elemental);
+    assert!(group_key(&container(9999, 55, 1, []).listing).is_none());
+
+    let mut counts = GroupCounts::default();
+    for _ in 0..5 {
+        counts.add(aether.clone());
+    }
+    for _ in 0..4 {
+        counts.add(elemental.clone());
+    }
+
+    assert!(counts.allows(&aether, 5));
+    assert!(!counts.allows(&elemental, 5));
+    assert!(counts.allows(&elemental, 4));
+}
+
+#[tokio::test]
+async fn dataset_is_anonymized_compressed_ndjson() {
+    let listings = || {
+        let mut listings: Vec<_> = (0..5).map(|i| container(73, 55, 100 + i, [100 + i as i64, 0, 7])).collect();
+        // 4개뿐인 Elemental 그룹과 일반화할 수 없는 서버
+        listings.extend((0..4).map(|i| container(49, 55, 200 + i, [])));
+        listings.push(container(9999, 55, 300, []));
+        futures_util::stream::iter(listings)
+    };
+
+    let day = date(1, 3);
+    let salt = PeriodSalt::for_date("secret", day);
+    let counts = count_groups(listings()).await;
+
+    let mut writer = DatasetWriter::new(Vec::new());
+    let report = write_dataset(listings(), &counts, 5, &salt, day, &mut writer).await.unwrap();
+    let compressed = writer.finish().await.unwrap();
+
+    assert_eq!(report, ExportReport { written: 5, suppressed: 4, ungrouped: 1 });
+
+    let lines: Vec<String> = std::io::BufReader::new(GzDecoder::new(&compressed[..]))
+        .lines()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(lines.len(), 5);
+    for line in &lines {
+        assert!(!line.contains("description") && !line.contains("name"));
+        assert!(!line.contains("Adamantoise"));
+    }
+
+    let row: AnonymizedListing = serde_json::from_str(&lines[0]).unwrap();
+    assert_eq!(row.date, day);
+    assert_eq!(row.data_centre, "Aether");
+    assert_eq!(row.created_hour, Utc.with_ymd_and_hms(2026, 1, 3, 21, 0, 0).unwrap());
+    assert_eq!(row.recruiter, Some(salt.hash(100)));
+    // 같은 사람은 모집자/멤버로 같은 해시, 빈 슬롯은 제외
+    assert_eq!(row.members, vec![salt.hash(100), salt.hash(7)]);
+}
+
+#[test]
+fn staging_copies_public_listings_without_uploader_data() {
+    let since = Utc.with_ymd_and_hms(2026, 1, 3, 22, 0, 0).unwrap();
+    let pipeline = export_staging_pipeline(since);
+    let stage = pipeline[0].get_document("$match").unwrap();
+
+    let public = mongodb::bson::to_document(&container(73, 55, 1, [])).unwrap();
+    assert!(matches(stage, &public));
+    let mut private = container(73, 55, 1, []);
+    private.listing.search_area |= crate::listing::SearchAreaFlags::PRIVATE;
+    assert!(!matches(stage, &mongodb::bson::to_document(&private).unwrap()));
+    let mut old = container(73, 55, 1, []);
+    old.updated_at = Utc.with_ymd_and_hms(2026, 1, 3, 21, 0, 0).unwrap();
+    assert!(!matches(stage, &mongodb::bson::to_document(&old).unwrap()));
+
+    // 업로더 지문은 복사본에 남기지 않고, 복사본은 그대로 내보낼 수 있음
+    let staged = run_pipeline(public, &pipeline[1..2], since);
+    assert!(!staged.contains_key("uploader_fingerprints"));
+    let staged: ListingContainer = mongodb::bson::from_document(staged).unwrap();
+    assert_eq!(staged.upload_count, 3);
+
+    let merge = pipeline[2].get_document("$merge").unwrap();
+    assert_eq!(merge.get_str("into"), Ok(EXPORT_STAGING_COLLECTION));
+}
+
+#[test]
+fn dataset_file_names_round_trip() {
+    assert_eq!(dataset_file_name(date(1, 3)), "rpf-2026-01-03.ndjson.gz");
+    assert_eq!(dataset_date("rpf-2026-01-03.ndjson.gz"), Some(date(1, 3)));
+    assert_eq!(dataset_date("rpf-2026-01-03.ndjson.gz.part"), None);
+    assert_eq!(dataset_date("../config.toml"), None);
+}
+
+/// 내보내지 못한 날짜의 복사본이 남아 있으면 그 날짜부터 이어서 내보냄
+#[test]
+fn missed_days_are_exported_from_the_oldest_staged_copy() {
+    assert_eq!(export_dates(None, date(1, 3)), [date(1, 3)]);
+    assert_eq!(export_dates(Some(date(1, 3)), date(1, 3)), [date(1, 3)]);
+    assert_eq!(export_dates(Some(date(1, 4)), date(1, 3)), [date(1, 3)]);
+    assert_eq!(export_dates(Some(date(1, 1)), date(1, 3)), [date(1, 1), date(1, 2), date(1, 3)]);
+    assert_eq!(export_dates(Some(date(2, 27)), date(3, 1)), [date(2, 27), date(2, 28), date(3, 1)]);
+}
@@ -0,0 +1,128 @@
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use warp::Filter;
+
+use crate::config::FFLogs as FFLogsConfig;
+use crate::fflogs::FFLogsClient;
+
+/// GraphQL 요청 수를 세는 모의 FFLogs 서버 실행
+///
+/// 모든 `charN` alias에 encounter 101 / 50.0 percentile을 응답합니다 (API 포인트는 3600 중 900.5 사용).
+pub(super) fn spawn_mock_fflogs() -> (SocketAddr, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let token = warp::path!("oauth" / "token").map(|| {
+        warp::reply::json(&serde_json::json!({
+            "access_token": "test",
+            "expires_in": 3600,
+            "token_type": "Bearer",
+        }))
+    });
+
+    let counter = Arc::clone(&calls);
+    let graphql = warp::path!("api" / "v2" / "client")
+        .and(warp::body::json())
+        .and_then(move |body: serde_json::Value| {
+            let counter = Arc::clone(&counter);
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                // 동시 요청이 진행 중인 조회에 합류할 시간 확보
+                tokio::time::sleep(Duration::from_millis(100)).await;
+
+                let query = body["query"].as_str().unwrap_or_default();
+                let aliases = query.matches(": character(").count();
+                let character_data: serde_json::Map<_, _> = (0..aliases)
+                    .map(|i| (
+                        format!("char{}", i),
+                        serde_json::json!({
+                            "zoneRankings": {
+                                "rankings": [{ "encounter": { "id": 101 }, "rankPercent": 50.0 }],
+                            },
+                        }),
+                    ))
+                    .collect();
+
+                Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
+                    "data": {
+                        "characterData": character_data,
+                        "rateLimitData": { "limitPerHour": 3600, "pointsSpentThisHour": 900.5, "pointsResetIn": 1800 },
+                    },
+                })))
+            }
+        });
+
+    let (addr, server) = warp::serve(warp::post().and(token.or(graphql)))
+        .bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+
+    (addr, calls)
+}
+
+pub(super) fn mock_client(addr: SocketAddr) -> FFLogsClient {
+    FFLogsClient::with_endpoints(
+        FFLogsConfig {
+            client_id: "id".to_string(),
+            client_secret: "secret".to_string(),
+            empty_backoff: Default::default(),
+            partitions: Default::default(),
+            requests_per_hour: None,
+            cache_hours: 24,
+            zone_cache_hours: Default::default(),
+            mapping_file: None,
+            retry: Default::default(),
+        },
+        &format!("http://{}/oauth/token", addr),
+        &format!("http://{}/api/v2/client", addr),
+    )
+}
+
+fn player(name: &str) -> Vec<(String, String, &'static str)> {
+    vec![(name.to_string(), "Tonberry".to_string(), "JP")]
+}
+
+#[tokio::test]
+async fn concurrent_identical_lookups_share_one_request() {
+    let (addr, calls) = spawn_mock_fflogs();
+    let client = mock_client(addr);
+
+    let (a, b, c) = tokio::join!(
+        client.get_batch_zone_all_parses(player("Alpha Tester"), 73, Some(101), None),
+        client.get_batch_zone_all_parses(player("alpha tester"), 73, Some(101), None),
+        client.get_batch_encounter_parses(player("Alpha Tester"), 73, 101, Some(101), None),
+    );
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(a.unwrap(), vec![(0, vec![(101, 50.0)])]);
+    assert_eq!(b.unwrap(), vec![(0, vec![(101, 50.0)])]);
+    assert_eq!(c.unwrap(), vec![(0, Some(50.0))]);
+    assert_eq!(client.coalescing_stats(), (0, 2));
+}
+
+#[tokio::test]
+async fn recent_lookups_are_memoized_per_key() {
+    let (addr, calls) = spawn_mock_fflogs();
+    let client = mock_client(addr);
+
+    client.get_batch_zone_all_parses(player("Alpha Tester"), 73, Some(101), None).await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // 메모 히트: HTTP 요청 없음
+    let cached = client.get_batch_zone_all_parses(player("Alpha Tester"), 73, Some(101), None).await.unwrap();
+    assert_eq!(cached, vec![(0, vec![(101, 50.0)])]);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(client.coalescing_stats(), (1, 0));
+
+    // 다른 파티션은 별도 키
+    client.get_batch_zone_all_parses(player("Alpha Tester"), 73, Some(101), Some(2)).await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // 배치에서 이미 조회된 캐릭터는 제외되고 나머지만 조회
+    let mut players = player("Alpha Tester");
+    players.extend(player("Beta Tester"));
+    let mixed = client.get_batch_zone_all_parses(players, 73, Some(101), None).await.unwrap();
+    assert_eq!(mixed.len(), 2);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use chrono::Utc;
+
+use super::fixture_world::parse_docs;
+use crate::api::{build_api_listings, zone_requests};
+use crate::ffxiv::Language;
+use crate::fflogs::mapping::{get_fflogs_encounter, parse_policy, ParsePolicy};
+use crate::fflogs::refetch::refetch_targets;
+use crate::fflogs::{EncounterParse, ParseCacheDoc, ZoneCache, FFLOGS_ZONES};
+use crate::ffxiv::duties::ContentKind;
+use crate::listing::PartyFinderListing;
+use crate::listing_container::QueriedListing;
+use crate::player::Player;
+use crate::template::listings::ListingsTemplate;
+use crate::web::handlers::build_renderable_listings;
+
+/// AAC Heavyweight M1 (Savage): 고난이도, FFLogs Zone 73 / encounter 101
+const MAPPED: u16 = 1069;
+/// The Cloud of Darkness (Chaotic): 고난이도지만 FFLogs 매핑 없음
+const UNMAPPED: u16 = 1010;
+/// 고난이도가 아닌 듀티
+const NORMAL: u16 = 55;
+/// Another Aloalo Island (Savage): 변주 던전 Savage
+const CRITERION_SAVAGE: u16 = 980;
+/// Another Aloalo Island: 같은 Zone의 일반 난이도
+const CRITERION: u16 = 979;
+
+fn queried(id: u32, duty: u16) -> QueriedListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.id = id;
+    listing.duty = duty;
+    listing.member_content_ids = vec![i64::from(id)];
+    listing.jobs_present = vec![19];
+
+    super::queried(listing)
+}
+
+fn listings() -> Vec<QueriedListing> {
+    vec![queried(1, MAPPED), queried(2, UNMAPPED), queried(3, NORMAL)]
+}
+
+fn players() -> HashMap<u64, Player> {
+    (1..=3)
+        .map(|id| {
+            let player = Player {
+                content_id: id,
+                name: format!("Member {}", id),
+                home_world: 73,
+                last_seen: Utc::now(),
+                seen_count: 1,
+            };
+            (id, player)
+        })
+        .collect()
+}
+
+fn zone_cache() -> ZoneCache {
+    ZoneCache {
+        fetched_at: Utc::now(),
+        encounters: maplit::hashmap! {
+            "101".to_string() => EncounterParse { percentile: 87.0, job_id: 19, all_stars: None, jobs: Default::default() },
+        },
+    }
+}
+
+#[test]
+fn only_mapped_high_end_duties_get_parses() {
+    let supported: Vec<(bool, bool)> = listings()
+        .iter()
+        .map(|ql| (ql.listing.high_end(), ql.listing.fflogs_supported()))
+        .collect();
+    assert_eq!(supported, vec![(true, true), (true, false), (false, false)]);
+    assert_eq!(listings()[0].listing.fflogs_encounter().map(|info| info.zone_id), Some(73));
+}
+
+/// 조회 대상도 같은 기준
+#[test]
+fn fetcher_uses_the_same_gate() {
+    assert_eq!(refetch_targets(&listings(), 73), vec![1]);
+    let zones = zone_requests(&listings());
+    assert_eq!(zones.keys().copied().collect::<Vec<_>>(), vec![73]);
+    assert_eq!(zones[&73], vec![1]);
+}
+
+#[test]
+fn api_exposes_high_end_and_fflogs_support() {
+    // 모든 멤버에게 같은 Zone 캐시가 있어도 지원하는 모집글만 표시
+    let parse_docs = parse_docs((1..=3).map(|id| ((73, id), zone_cache())));
+    let api = build_api_listings(listings(), &players(), &parse_docs, chrono::FixedOffset::east_opt(0).unwrap());
+    let api = serde_json::to_value(&api).unwrap();
+
+    let by_id = |id: u64| {
+        api.as_array()
+            .unwrap()
+            .iter()
+            .find(|container| container["listing"]["id"] == id)
+            .unwrap()["listing"]
+            .clone()
+    };
+    for (id, high_end, supported, percentile) in [
+        (1, true, true, serde_json::json!(87)),
+        (2, true, false, serde_json::Value::Null),
+        (3, false, false, serde_json::Value::Null),
+    ] {
+        let listing = by_id(id);
+        assert_eq!(listing["high_end"], high_end, "listing {}", id);
+        assert_eq!(listing["fflogs_supported"], supported, "listing {}", id);
+        assert_eq!(listing["members"][0]["parse_percentile"], percentile, "listing {}", id);
+        assert!(listing["pf_category"].is_string());
+        assert!(listing["pf_category_rank"].is_u64());
+    }
+}
+
+#[test]
+fn page_shows_parses_only_for_supported_listings() {
+    let parse_docs: HashMap<u64, ParseCacheDoc> = (1..=3)
+        .map(|id| {
+            let doc = ParseCacheDoc {
+                content_id: id as i64,
+                zones: maplit::hashmap! { "73".to_string() => zone_cache() },
+                fetch: None,
+            };
+            (id, doc)
+        })
+        .collect();
+
+    let renderable = build_renderable_listings(listings(), &players(), &parse_docs);
+    let mut percentiles: Vec<(u32, Option<u8>)> = renderable
+        .iter()
+        .map(|listing| (listing.container.listing.id, listing.members[0].parse.primary_percentile))
+        .collect();
+    percentiles.sort();
+    assert_eq!(percentiles, vec![(1, Some(87)), (2, None), (3, None)]);
+
+    let html = ListingsTemplate { containers: renderable, lang: Language::English, notice: None }.render().unwrap();
+    assert_eq!(html.matches(r#"class="parse-header""#).count(), 1);
+    // 파티장 + 멤버 Parse 칸
+    assert_eq!(html.matches(r#"class="parse "#).count(), 2);
+}
+
+#[test]
+fn every_fetch_eligible_duty_has_a_mapping() {
+    for (&duty, info) in crate::ffxiv::DUTIES.iter() {
+        if !info.high_end || parse_policy(info.content_kind) != ParsePolicy::Fetch {
+            continue;
+        }
+        let encounter = get_fflogs_encounter(duty as u16);
+        assert!(encounter.is_some(), "{} ({}) has no FFLogs mapping", duty, info.name.en);
+        assert!(FFLOGS_ZONES.contains_key(&encounter.unwrap().zone_id), "{}", duty);
+    }
+}
+
+#[test]
+fn criterion_savage_listings_get_parses() {
+    let listings = [queried(4, CRITERION_SAVAGE), queried(5, CRITERION)];
+    let savage = &listings[0].listing;
+    assert!(savage.high_end() && savage.fflogs_supported());
+    assert_eq!(savage.fflogs_encounter().and_then(|info| info.difficulty_id), Some(101));
+    assert!(!listings[1].listing.high_end());
+
+    let zone_id = savage.fflogs_encounter().unwrap().zone_id;
+    assert_eq!(refetch_targets(&listings, zone_id), vec![4]);
+}
+
+#[test]
+fn chaotic_alliance_raids_are_display_only() {
+    assert_eq!(parse_policy(ContentKind::ChaoticAllianceRaid), ParsePolicy::DisplayOnly);
+    assert_eq!(parse_policy(ContentKind::VCDungeonFinder), ParsePolicy::Fetch);
+    assert_eq!(parse_policy(ContentKind::Trials), ParsePolicy::Fetch);
+
+    let chaotic = queried(2, UNMAPPED);
+    assert!(chaotic.listing.high_end());
+    assert!(!chaotic.listing.fflogs_supported());
+}
@@ -0,0 +1,55 @@
+use super::fixture_world::ListingBuilder;
+use crate::fflogs::mapping::{builtin_mappings, get_fflogs_encounter, merge_mappings, parse_mapping, validate_mappings};
+use crate::fflogs::FFLOGS_ZONES;
+use crate::listing::DutyCategory;
+
+#[test]
+fn builtin_mapping_covers_previous_savage_tiers() {
+    let builtin = builtin_mappings();
+    assert_eq!(validate_mappings(builtin.clone()).len(), builtin.len());
+    for mapping in &builtin {
+        assert!(crate::ffxiv::duty(u32::from(mapping.duty_id)).is_some(), "duty {}", mapping.duty_id);
+    }
+
+    // (Duty ID, Zone, Encounter): M1S, M4S, M5S, M8S
+    for (duty, zone, encounter) in [(986, 62, 93), (992, 62, 96), (1020, 68, 97), (1026, 68, 100)] {
+        let info = get_fflogs_encounter(duty).unwrap();
+        assert_eq!((info.zone_id, info.encounter_id, info.difficulty_id), (zone, encounter, Some(101)), "duty {}", duty);
+    }
+    assert_eq!(get_fflogs_encounter(1075).unwrap().secondary_encounter_id, Some(105));
+}
+
+/// 게임 데이터는 이전 티어를 고난이도로 표시하지 않지만 매핑이 있으면 Parse를 조회
+#[test]
+fn previous_tier_listings_show_parses() {
+    let listing = ListingBuilder::new(1).duty(986, DutyCategory::HighEndDuty).build().listing;
+    assert!(!listing.high_end());
+    assert_eq!(listing.fflogs_encounter().map(|info| info.zone_id), Some(62));
+}
+
+#[test]
+fn file_mappings_replace_builtin_entries_and_skip_unknown_zones() {
+    let file = parse_mapping(
+        r#"
+        duties = [
+            { duty_id = 1069, zone_id = 73, encounter_id = 201, difficulty_id = 101, name = "Renamed" },
+            { duty_id = 60000, zone_id = 9999, encounter_id = 1, name = "Unknown zone" },
+            { duty_id = 60001, zone_id = 73, encounter_id = 202, secondary_encounter_id = 203, name = "New duty" },
+        ]
+        "#,
+    )
+    .unwrap();
+    assert!(!FFLOGS_ZONES.contains_key(&9999));
+
+    let file = validate_mappings(file);
+    assert_eq!(file.iter().map(|mapping| mapping.duty_id).collect::<Vec<_>>(), [1069, 60001]);
+
+    let merged = merge_mappings(&builtin_mappings(), &file);
+    assert_eq!(merged.len(), builtin_mappings().len() + 1);
+    assert_eq!((merged[&1069].encounter_id, merged[&1069].name), (201, "Renamed"));
+    assert_eq!(merged[&60001].difficulty_id, None);
+    assert_eq!(merged[&60001].secondary_encounter_id, Some(203));
+    assert_eq!(merged[&1071].encounter_id, 102);
+
+    assert!(parse_mapping("duties = [{ duty_id = 1 }]").is_err());
+}
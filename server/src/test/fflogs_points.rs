@@ -0,0 +1,82 @@
+use chrono::{TimeDelta, TimeZone, Utc};
+
+use super::fflogs_coalescing::{mock_client, spawn_mock_fflogs};
+use crate::fflogs::points::{PointsBudget, PointsUsage, BATCH_DELAY, SLOW_BATCH_DELAY};
+
+fn usage(spent: f64) -> PointsUsage {
+    PointsUsage {
+        limit_per_hour: 1_000.0,
+        spent_this_hour: spent,
+        reset_in_secs: 600,
+        observed_at: Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap(),
+    }
+}
+
+#[test]
+fn rate_limit_data_is_read_from_the_response() {
+    let now = Utc::now();
+    let response = serde_json::json!({
+        "data": { "rateLimitData": { "limitPerHour": 3600, "pointsSpentThisHour": 12.5, "pointsResetIn": 90 } },
+    });
+    let usage = PointsUsage::from_response(&response, now).unwrap();
+
+    assert_eq!((usage.limit_per_hour, usage.spent_this_hour, usage.reset_in_secs), (3600.0, 12.5, 90));
+    assert_eq!(usage.remaining(now), 3587.5);
+    // 초기화 시각이 지나면 전체 한도
+    assert_eq!(usage.remaining(now + TimeDelta::seconds(90)), 3600.0);
+
+    assert_eq!(PointsUsage::from_response(&serde_json::json!({ "data": {} }), now), None);
+}
+
+#[test]
+fn warning_is_raised_once_per_hour() {
+    let budget = PointsBudget::default();
+
+    assert!(!budget.record(usage(500.0)));
+    assert!(budget.record(usage(850.0)));
+    assert!(!budget.record(usage(900.0)));
+
+    // 다음 시간에 다시 넘으면 다시 경고
+    let mut next_hour = usage(100.0);
+    next_hour.observed_at += TimeDelta::hours(1);
+    assert!(!budget.record(next_hour));
+    next_hour.spent_this_hour = 810.0;
+    assert!(budget.record(next_hour));
+}
+
+#[test]
+fn cycles_slow_down_then_pause_near_the_limit() {
+    let budget = PointsBudget::default();
+    let now = usage(0.0).observed_at;
+    assert_eq!(budget.remaining(now), None);
+    assert_eq!(budget.batch_delay(now), BATCH_DELAY);
+    assert!(!budget.should_pause(now));
+
+    budget.record(usage(500.0));
+    assert_eq!(budget.batch_delay(now), BATCH_DELAY);
+
+    budget.record(usage(850.0));
+    assert_eq!(budget.batch_delay(now), SLOW_BATCH_DELAY);
+    assert!(!budget.should_pause(now));
+
+    budget.record(usage(960.0));
+    assert!(budget.should_pause(now));
+    // 초기화된 뒤에는 다시 조회
+    let later = now + TimeDelta::seconds(601);
+    assert!(!budget.should_pause(later));
+    assert_eq!(budget.batch_delay(later), BATCH_DELAY);
+}
+
+#[tokio::test]
+async fn batch_queries_report_the_points_budget() {
+    let (addr, _) = spawn_mock_fflogs();
+    let client = mock_client(addr);
+    assert_eq!(client.remaining_budget(), None);
+
+    let players = vec![("Alpha One".to_string(), "Tonberry".to_string(), "JP")];
+    client.get_batch_zone_rankings(players, 73, Some(101), None).await.unwrap();
+
+    let usage = client.points().snapshot().unwrap();
+    assert_eq!((usage.limit_per_hour, usage.spent_this_hour), (3600.0, 900.5));
+    assert_eq!(client.remaining_budget(), Some(2699.5));
+}
@@ -0,0 +1,127 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use chrono::Utc;
+use warp::Filter;
+
+use crate::fflogs::parse_response::{classify_zone_rankings, parse_character, ParsedRankings, SchemaHealth};
+use crate::fflogs::{AllStars, EncounterParse, ProfileMiss, RankingsUnavailable, UnrecognizedShape};
+
+/// 현재 GraphQL 배치 응답 (기록 있음 / FFLogs에 없는 캐릭터 / 기록 없음)
+const BATCH_CURRENT: &str = include_str!("fixtures/fflogs/batch_current.json");
+
+/// 예전 v1 `/parses/character` 응답 (encounter ID와 percentile이 평평한 배열)
+const PARSES_V1: &str = include_str!("fixtures/fflogs/parses_v1.json");
+
+fn character(batch: &serde_json::Value, alias: &str) -> ParsedRankings {
+    parse_character(batch["data"]["characterData"].get(alias))
+}
+
+#[test]
+fn current_shape_distinguishes_no_rankings() {
+    let batch: serde_json::Value = serde_json::from_str(BATCH_CURRENT).unwrap();
+
+    assert_eq!(
+        character(&batch, "char0"),
+        ParsedRankings::Known(vec![
+            (93, EncounterParse { percentile: 95.5, job_id: 19, all_stars: Some(AllStars { points: 120.5, rank: 842 }), jobs: Default::default() }),
+            (94, EncounterParse { percentile: 61.0, job_id: 21, all_stars: None, jobs: Default::default() }),
+        ])
+    );
+    assert_eq!(character(&batch, "char1"), ParsedRankings::Missing);
+    assert_eq!(character(&batch, "char1").into_result(), Err(RankingsUnavailable::Profile(ProfileMiss::Missing)));
+    assert_eq!(character(&batch, "char9"), ParsedRankings::NoRankings);
+    assert_eq!(character(&batch, "char2"), ParsedRankings::Known(Vec::new()));
+    assert_eq!(character(&batch, "char2").into_result(), Ok(Vec::new()));
+}
+
+#[test]
+fn historical_and_drifted_shapes_use_the_fallback_parser() {
+    let expected = vec![
+        (93, EncounterParse { percentile: 95.5, job_id: 19, all_stars: None, jobs: Default::default() }),
+        (94, EncounterParse { percentile: 61.0, job_id: 21, all_stars: None, jobs: Default::default() }),
+    ];
+
+    let v1: serde_json::Value = serde_json::from_str(PARSES_V1).unwrap();
+    assert_eq!(classify_zone_rankings(&v1), ParsedRankings::Fallback(expected.clone()));
+
+    // 목록 이름이 바뀌고 한 단계 더 감싸진 응답, JSON 문자열로 온 응답
+    let batch: serde_json::Value = serde_json::from_str(BATCH_CURRENT).unwrap();
+    let rankings = batch["data"]["characterData"]["char0"]["zoneRankings"]["rankings"].clone();
+    let nested = serde_json::json!({ "data": { "encounterRankings": rankings } });
+    assert!(matches!(classify_zone_rankings(&nested), ParsedRankings::Fallback(parses) if parses.len() == 2));
+    let text = serde_json::Value::String(batch["data"]["characterData"]["char0"]["zoneRankings"].to_string());
+    assert!(matches!(classify_zone_rankings(&text), ParsedRankings::Known(parses) if parses.len() == 2));
+}
+
+#[test]
+fn unknown_shapes_are_never_reported_as_no_rankings() {
+    let unrecognized = [
+        serde_json::json!({ "zoneRankingsV2": { "rankings": [] } }),
+        serde_json::json!({ "zoneRankings": { "ranks": [{ "boss": "Black Cat", "score": 95.5 }] } }),
+        serde_json::json!({ "zoneRankings": { "encounterRankings": [] } }),
+        serde_json::json!({ "zoneRankings": "not json" }),
+        serde_json::json!({ "zoneRankings": 42 }),
+    ];
+    for character in &unrecognized {
+        assert_eq!(parse_character(Some(character)), ParsedRankings::Unrecognized, "{}", character);
+        assert_eq!(parse_character(Some(character)).into_result(), Err(UnrecognizedShape.into()));
+    }
+}
+
+#[test]
+fn health_degrades_above_the_threshold() {
+    let health = SchemaHealth::default();
+    let now = Utc::now();
+
+    // 응답 하나의 이상이나 기준 이하 비율은 경고하지 않음
+    assert!(!health.record_cycle(3, 1, now));
+    assert!(!health.record_cycle(20, 4, now));
+    assert!(health.record_cycle(20, 5, now));
+    // 조회가 없던 주기는 상태 유지
+    assert!(health.record_cycle(0, 0, now));
+    assert!(!health.record_cycle(10, 0, now));
+
+    let snapshot = health.snapshot();
+    assert!(!snapshot.degraded);
+    assert_eq!((snapshot.last_responses, snapshot.last_unrecognized, snapshot.total_unrecognized), (10, 0, 10));
+}
+
+/// 형태를 알 수 없는 응답은 메모하지 않고 다음 조회에서 다시 요청
+#[tokio::test]
+async fn unrecognized_responses_are_not_memoized() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let token = warp::path!("oauth" / "token").map(|| {
+        warp::reply::json(&serde_json::json!({ "access_token": "test", "expires_in": 3600, "token_type": "Bearer" }))
+    });
+    let counter = Arc::clone(&calls);
+    let graphql = warp::path!("api" / "v2" / "client").map(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        warp::reply::json(&serde_json::json!({
+            "data": { "characterData": {
+                "char0": { "zoneRankings": { "ranks": [] } },
+                "char1": { "zoneRankings": { "rankings": [{ "encounter": { "id": 93 }, "rankPercent": 50.0 }] } },
+            } },
+        }))
+    });
+    let (addr, server) = warp::serve(warp::post().and(token.or(graphql))).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+
+    let client = super::fflogs_coalescing::mock_client(addr);
+    let players = || {
+        vec![
+            ("Beta Tester".to_string(), "Tonberry".to_string(), "JP"),
+            ("Alpha Tester".to_string(), "Tonberry".to_string(), "JP"),
+        ]
+    };
+
+    let results = client.get_batch_zone_rankings(players(), 62, Some(101), None).await.unwrap();
+    assert_eq!(results[0].1, Err(UnrecognizedShape.into()));
+    assert_eq!(results[1].1.as_ref().unwrap().len(), 1);
+
+    // 두 번째 캐릭터만 메모에서 가져오고, percentile만 돌려주는 조회에서는 첫 캐릭터가 빠짐
+    let parses = client.get_batch_zone_all_parses(players(), 62, Some(101), None).await.unwrap();
+    assert_eq!(parses, vec![(1, vec![(93, 50.0)])]);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(client.coalescing_stats(), (1, 0));
+}
@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
+
+use crate::config::{FFLogs as FFLogsConfig, RetryPolicy};
+use crate::fflogs::client::{character_errors, retry_delay};
+use crate::fflogs::{FFLogsClient, ProfileMiss, RankingsUnavailable};
+
+/// 앞의 요청에는 `statuses`의 상태 코드로(`Retry-After: 0`), 그 뒤에는 모든 캐릭터에 encounter 101 / 50.0으로 응답
+///
+/// `missing`의 캐릭터는 `null`과 그 캐릭터를 가리키는 GraphQL 에러로 응답합니다.
+fn spawn_flaky_fflogs(statuses: &[u16], missing: Option<usize>) -> (SocketAddr, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let statuses = Arc::new(Mutex::new(statuses.iter().copied().collect::<VecDeque<_>>()));
+
+    let token = warp::path!("oauth" / "token").map(|| {
+        warp::reply::json(&serde_json::json!({ "access_token": "test", "expires_in": 3600, "token_type": "Bearer" }))
+    });
+
+    let counter = Arc::clone(&calls);
+    let graphql = warp::path!("api" / "v2" / "client").and(warp::body::json()).map(move |body: serde_json::Value| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        if let Some(status) = statuses.lock().unwrap().pop_front() {
+            let reply = warp::reply::with_status("try again later", StatusCode::from_u16(status).unwrap());
+            return warp::reply::with_header(reply, "retry-after", "0").into_response();
+        }
+
+        let aliases = body["query"].as_str().unwrap_or_default().matches(": character(").count();
+        let character_data: serde_json::Map<_, _> = (0..aliases)
+            .map(|i| {
+                let character = match missing {
+                    Some(missing) if missing == i => serde_json::Value::Null,
+                    _ => serde_json::json!({
+                        "zoneRankings": { "rankings": [{ "encounter": { "id": 101 }, "rankPercent": 50.0 }] },
+                    }),
+                };
+                (format!("char{}", i), character)
+            })
+            .collect();
+        let errors: Vec<_> = missing
+            .map(|i| serde_json::json!({ "message": "Character not found", "path": ["characterData", format!("char{}", i)] }))
+            .into_iter()
+            .collect();
+        warp::reply::json(&serde_json::json!({ "data": { "characterData": character_data }, "errors": errors }))
+            .into_response()
+    });
+
+    let (addr, server) = warp::serve(warp::post().and(token.or(graphql))).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+
+    (addr, calls)
+}
+
+fn client(addr: SocketAddr, attempts: u32) -> FFLogsClient {
+    FFLogsClient::with_endpoints(
+        FFLogsConfig {
+            client_id: "id".to_string(),
+            client_secret: "secret".to_string(),
+            empty_backoff: Default::default(),
+            partitions: Default::default(),
+            requests_per_hour: None,
+            cache_hours: 24,
+            zone_cache_hours: Default::default(),
+            mapping_file: None,
+            retry: RetryPolicy { attempts, base_delay_ms: 10, max_delay_secs: 1 },
+        },
+        &format!("http://{}/oauth/token", addr),
+        &format!("http://{}/api/v2/client", addr),
+    )
+}
+
+fn players() -> Vec<(String, String, &'static str)> {
+    ["Alpha One", "Beta Two"].iter().map(|name| (name.to_string(), "Tonberry".to_string(), "JP")).collect()
+}
+
+#[test]
+fn retry_delay_backs_off_and_honours_retry_after() {
+    let policy = RetryPolicy { attempts: 4, base_delay_ms: 1_000, max_delay_secs: 3 };
+
+    assert_eq!(retry_delay(&policy, 1, None), Some(Duration::from_secs(1)));
+    assert_eq!(retry_delay(&policy, 2, None), Some(Duration::from_secs(2)));
+    assert_eq!(retry_delay(&policy, 3, None), Some(Duration::from_secs(3)));
+    assert_eq!(retry_delay(&policy, 4, None), None);
+
+    assert_eq!(retry_delay(&policy, 1, Some(Duration::from_secs(2))), Some(Duration::from_secs(2)));
+    // 최대 간격보다 오래 기다리라면 다음 주기로 미룸
+    assert_eq!(retry_delay(&policy, 1, Some(Duration::from_secs(10))), None);
+}
+
+#[tokio::test]
+async fn rate_limited_batches_eventually_succeed() {
+    let (addr, calls) = spawn_flaky_fflogs(&[429, 502], None);
+    let results = client(addr, 3).get_batch_zone_rankings(players(), 73, Some(101), None).await.unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(results.len(), 2);
+    for (_, parses) in results {
+        assert_eq!(parses.unwrap()[0].1.percentile, 50.0);
+    }
+}
+
+#[tokio::test]
+async fn retries_stop_after_the_configured_attempts() {
+    let (addr, calls) = spawn_flaky_fflogs(&[429, 429, 429], None);
+    let error = client(addr, 2).get_batch_zone_rankings(players(), 73, Some(101), None).await.unwrap_err();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(error.to_string().contains("429"), "{}", error);
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+    let (addr, calls) = spawn_flaky_fflogs(&[400], None);
+    assert!(client(addr, 3).get_batch_zone_rankings(players(), 73, Some(101), None).await.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn character_errors_do_not_fail_the_batch() {
+    let response = serde_json::json!({
+        "errors": [
+            { "message": "Character not found", "path": ["characterData", "char3"] },
+            { "message": "You do not have Permission to view this character", "path": ["characterData", "char5", "zoneRankings"] },
+            { "message": "Something else" },
+        ],
+    });
+    assert_eq!(character_errors(&response), [(3, ProfileMiss::Missing), (5, ProfileMiss::Hidden)].into_iter().collect());
+
+    let (addr, _) = spawn_flaky_fflogs(&[], Some(1));
+    let results = client(addr, 3).get_batch_zone_rankings(players(), 73, Some(101), None).await.unwrap();
+
+    assert_eq!(results[0].1.as_ref().unwrap().len(), 1);
+    assert_eq!(results[1].1, Err(RankingsUnavailable::Profile(ProfileMiss::Missing)));
+}
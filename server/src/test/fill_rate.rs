@@ -0,0 +1,134 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use mongodb::bson::{self, doc, Bson};
+
+use super::fixture_world::ListingBuilder;
+use super::mongo_eval::Collection;
+use crate::ffxiv::Language;
+use crate::listing::archive::PendingArchive;
+use crate::listing::{DutyCategory, PartyFinderListing};
+use crate::listing_container::{ListingContainer, MAX_SLOTS_HISTORY};
+use crate::stats::{FillTimeInfo, StatsQuery};
+
+/// 절 바하무트 토벌전 (고난도 듀티)
+const ULTIMATE: u16 = 280;
+
+fn at(minutes: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 2, 1, 20, 0, 0).unwrap() + TimeDelta::try_minutes(minutes).unwrap()
+}
+
+fn listing(filled: usize) -> PartyFinderListing {
+    let mut builder = ListingBuilder::new(1).duty(ULTIMATE, DutyCategory::HighEndDuty);
+    for i in 0..filled {
+        builder = builder.member(1000 + i as u64, 19);
+    }
+    builder.build().listing
+}
+
+/// `insert_listing`의 업데이트 파이프라인으로 업로드 기록
+fn upload(listings: &mut Collection, listing: &PartyFinderListing, minutes: i64) -> ListingContainer {
+    listings.upload(listing, "aaaaaaaaaaaaaaaa", false, at(minutes));
+    listings.stored(listing)
+}
+
+#[test]
+fn slots_history_records_only_changes() {
+    let mut listings = Collection::default();
+    let mut listing = listing(2);
+    upload(&mut listings, &listing, 0);
+    upload(&mut listings, &listing, 1);
+    listing.jobs_present[2] = 24;
+    let container = upload(&mut listings, &listing, 5);
+
+    let filled: Vec<_> = container.slots_history.iter().map(|sample| (sample.at, sample.slots_filled)).collect();
+    assert_eq!(filled, vec![(at(0), 2), (at(5), 3)]);
+    assert_eq!(container.full_at(), None);
+}
+
+#[test]
+fn slots_history_is_capped() {
+    let mut listings = Collection::default();
+    let mut listing = listing(0);
+    for i in 0..40 {
+        listing.jobs_present[0] = if i % 2 == 0 { 19 } else { 0 };
+        upload(&mut listings, &listing, i);
+    }
+
+    let container = listings.stored(&listing);
+    assert_eq!(container.slots_history.len(), MAX_SLOTS_HISTORY);
+    assert_eq!(container.slots_history[0].at, at(40 - MAX_SLOTS_HISTORY as i64));
+}
+
+#[test]
+fn full_at_is_the_first_full_sample() {
+    let mut listings = Collection::default();
+    let mut listing = listing(7);
+    upload(&mut listings, &listing, 0);
+    listing.jobs_present[7] = 24;
+    upload(&mut listings, &listing, 12);
+    // 한 명이 나갔다가 다시 참
+    listing.jobs_present[7] = 0;
+    upload(&mut listings, &listing, 15);
+    listing.jobs_present[7] = 24;
+    let container = upload(&mut listings, &listing, 20);
+
+    assert_eq!(container.full_at(), Some(at(12)));
+    assert_eq!(PendingArchive::stored(container).full_at, Some(at(12)));
+}
+
+#[test]
+fn slots_history_is_optional_in_stored_documents() {
+    let mut listings = Collection::default();
+    upload(&mut listings, &listing(1), 0);
+    let mut document = listings.docs[0].clone();
+    document.remove("slots_history");
+
+    let restored: ListingContainer = bson::from_document(document).unwrap();
+    assert!(restored.slots_history.is_empty());
+}
+
+#[test]
+fn archive_keeps_the_earliest_full_time() {
+    let open = listing(3);
+    let full = listing(8);
+
+    let mut entry = PendingArchive::new(open.clone(), at(0));
+    assert_eq!(entry.full_at, None);
+    assert!(!entry.update().unwrap().contains_key("$min"));
+
+    entry.merge(PendingArchive::new(full.clone(), at(10)));
+    entry.merge(PendingArchive::new(full, at(20)));
+    assert_eq!(entry.full_at, Some(at(10)));
+    assert_eq!(entry.update().unwrap().get_document("$min").unwrap(), &doc! { "full_at": at(10) });
+
+    // 나중에 자리가 비어도 처음 다 찬 시각은 유지
+    entry.merge(PendingArchive::new(open, at(30)));
+    assert_eq!(entry.full_at, Some(at(10)));
+}
+
+#[test]
+fn stats_pipeline_has_fill_time_facet_for_high_end_duties() {
+    let pipeline = StatsQuery::default().build();
+    let facets = pipeline.last().unwrap().get_document("$facet").unwrap();
+    let stages = facets.get_array("fill_times").unwrap();
+    let first = stages[0].as_document().unwrap().get_document("$match").unwrap();
+    let duties = first.get_document("listing.duty").unwrap().get_array("$in").unwrap();
+
+    assert!(duties.contains(&Bson::Int64(i64::from(ULTIMATE))));
+    assert!(!duties.contains(&Bson::Int64(i64::from(super::fixture_world::DUNGEON))));
+}
+
+#[test]
+fn never_filled_duties_have_no_median() {
+    let filled: FillTimeInfo =
+        bson::from_document(doc! { "_id": i32::from(ULTIMATE), "count": 5, "filled": 3, "median_minutes": 95.0 }).unwrap();
+    let never: FillTimeInfo =
+        bson::from_document(doc! { "_id": i32::from(ULTIMATE), "count": 2, "filled": 0, "median_minutes": Bson::Null }).unwrap();
+
+    assert_eq!(filled.median_text(), "1h 35m");
+    assert_eq!(never.median_text(), "-");
+    assert_eq!(never.name(&Language::English), "The Unending Coil of Bahamut (Ultimate)");
+
+    let json = serde_json::to_value(&never).unwrap();
+    assert_eq!(json["median_minutes"], serde_json::Value::Null);
+    assert_eq!(json["name"]["en"], "The Unending Coil of Bahamut (Ultimate)");
+}
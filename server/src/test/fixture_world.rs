@@ -0,0 +1,312 @@
+//! 여러 테스트에서 같이 쓰는 모집글 / 플레이어 / Parse 데이터
+//!
+//! DB 없이 목록 페이지, `/api/listings`, 웹소켓에 같은 데이터를 넣기 위한 빌더입니다.
+//! 시각은 모두 `base_time` 기준이라 JSON 출력이 실행마다 같습니다.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use sestring::SeString;
+
+use crate::config::ListingSort;
+use crate::fflogs::{EncounterParse, ParseCacheDoc, ZoneCache};
+use crate::listing::{DutyCategory, DutyType, JobFlags, PartyFinderListing, PartyFinderSlot, PartyMember, SearchAreaFlags};
+use crate::listing_container::QueriedListing;
+use crate::player::Player;
+
+/// 분할 영식 (M12S, Zone 73, Encounter 104 / 105)
+pub const SAVAGE_SPLIT: u16 = 1075;
+/// 일반 영식 (M9S, Zone 73, Encounter 101)
+pub const SAVAGE: u16 = 1069;
+/// 일반 던전
+pub const DUNGEON: u16 = 55;
+
+/// (Zone, 플레이어)별 캐시를 플레이어별 Parse 문서로 묶음
+pub fn parse_docs(zone_caches: impl IntoIterator<Item = ((u16, u64), ZoneCache)>) -> HashMap<u64, ParseCacheDoc> {
+    let mut docs: HashMap<u64, ParseCacheDoc> = HashMap::new();
+    for ((zone_id, content_id), cache) in zone_caches {
+        docs.entry(content_id)
+            .or_insert_with(|| ParseCacheDoc { content_id: content_id as i64, zones: HashMap::new(), fetch: None })
+            .zones
+            .insert(zone_id.to_string(), cache);
+    }
+    docs
+}
+
+/// 고정 기준 시각 (모든 업데이트 / 만료 시각의 기준)
+pub fn base_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 5, 12, 0, 0).unwrap()
+}
+
+/// 8인 파티 모집글 빌더 (생성 서버 73 = Adamantoise)
+pub struct ListingBuilder {
+    listing: PartyFinderListing,
+    now: DateTime<Utc>,
+    updated_minutes_ago: i64,
+    time_left: Option<f64>,
+    upload_count: u32,
+    next_slot: usize,
+}
+
+impl ListingBuilder {
+    pub fn new(id: u32) -> Self {
+        let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+        listing.id = id;
+        listing.slots = vec![PartyFinderSlot { accepting: JobFlags::all() }; 8];
+        listing.slots_available = 8;
+        listing.jobs_present = vec![0; 8];
+        Self::from_listing(listing)
+    }
+
+    /// 이미 만든 모집글로 시작 (자리 / 멤버는 그대로)
+    pub fn from_listing(listing: PartyFinderListing) -> Self {
+        let next_slot = listing.member_content_ids.len();
+        Self {
+            listing,
+            now: base_time(),
+            updated_minutes_ago: 0,
+            time_left: None,
+            upload_count: 1,
+            next_slot,
+        }
+    }
+
+    /// 일반 듀티 모집글
+    pub fn duty(mut self, duty: u16, category: DutyCategory) -> Self {
+        self.listing.duty = duty;
+        self.listing.duty_type = DutyType::Normal;
+        self.listing.category = category;
+        self
+    }
+
+    /// 듀티 없이 분류만 있는 모집글 (`duty == 0`)
+    pub fn category(mut self, category: DutyCategory) -> Self {
+        self.listing.duty = 0;
+        self.listing.duty_type = DutyType::Other;
+        self.listing.category = category;
+        self
+    }
+
+    /// 생성 서버 (데이터 센터 판정 기준)
+    pub fn world(mut self, created_world: u16) -> Self {
+        self.listing.created_world = created_world;
+        self
+    }
+
+    pub fn description(mut self, description: SeString) -> Self {
+        self.listing.description = description;
+        self
+    }
+
+    pub fn leader(mut self, content_id: u64) -> Self {
+        self.listing.leader_content_id = content_id;
+        self.listing.content_id_lower = content_id as u32;
+        self
+    }
+
+    /// 모집자 Content ID의 하위 32비트만 설정 (파티장 정보 없음)
+    pub fn content_id_lower(mut self, content_id_lower: u32) -> Self {
+        self.listing.content_id_lower = content_id_lower;
+        self
+    }
+
+    /// 다음 자리에 멤버 추가 (`job_id == 0`이면 잡 정보가 없는 자리)
+    pub fn member(mut self, content_id: u64, job_id: u8) -> Self {
+        let slot = self.next_slot;
+        self.listing.member_content_ids.resize(slot, 0);
+        self.listing.member_content_ids.push(content_id as i64);
+        self.listing.jobs_present[slot] = job_id;
+        self.next_slot += 1;
+        self
+    }
+
+    /// 멤버 Content ID만 설정 (자리 / 잡과 맞추지 않음)
+    pub fn member_ids(mut self, content_ids: impl IntoIterator<Item = u64>) -> Self {
+        self.listing.member_content_ids = content_ids.into_iter().map(|id| id as i64).collect();
+        self
+    }
+
+    /// 자리별 멤버 목록 설정 (`/contribute/detail`의 `members`)
+    pub fn members(mut self, members: &[PartyMember]) -> Self {
+        self.listing.members = members.to_vec();
+        self
+    }
+
+    /// 다음 자리에 멤버 정보 없이 잡만 채움
+    pub fn job(self, job_id: u8) -> Self {
+        self.member(0, job_id)
+    }
+
+    pub fn one_player_per_job(mut self) -> Self {
+        self.listing.search_area |= SearchAreaFlags::ONE_PLAYER_PER_JOB;
+        self
+    }
+
+    pub fn min_item_level(mut self, min_item_level: u16) -> Self {
+        self.listing.min_item_level = min_item_level;
+        self
+    }
+
+    pub fn updated_minutes_ago(mut self, minutes: i64) -> Self {
+        self.updated_minutes_ago = minutes;
+        self
+    }
+
+    /// 기준 시각 (업데이트 시각과 만료 계산의 기준, 기본은 `base_time`)
+    pub fn at(mut self, now: DateTime<Utc>) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// 집계 쿼리가 계산한 남은 시간 (초, 지정하면 만료 정보는 계산하지 않음)
+    pub fn time_left(mut self, seconds: f64) -> Self {
+        self.time_left = Some(seconds);
+        self
+    }
+
+    /// 업로드 수 (업로더 수도 같은 수)
+    pub fn uploads(mut self, count: u32) -> Self {
+        self.upload_count = count;
+        self
+    }
+
+    /// 업로드 형태의 모집글 (자리 / 멤버 설정까지만 반영)
+    pub fn listing(self) -> PartyFinderListing {
+        self.listing
+    }
+
+    pub fn build(self) -> QueriedListing {
+        let updated_at = self.now - TimeDelta::try_minutes(self.updated_minutes_ago).unwrap();
+        let mut container = QueriedListing {
+            created_at: updated_at - TimeDelta::try_minutes(10).unwrap(),
+            updated_at,
+            updated_minute: updated_at,
+            time_left: 0.0,
+            listing: self.listing,
+            upload_count: self.upload_count,
+            uploader_count: self.upload_count,
+            expiry: Default::default(),
+            description_history: Vec::new(),
+        };
+        container.refresh_bucket(&ListingSort::default());
+        match self.time_left {
+            Some(time_left) => container.time_left = time_left,
+            None => container.refresh_expiry(self.now),
+        }
+        container
+    }
+}
+
+/// 모집글과 미리 조회한 플레이어 / Parse 캐시
+#[derive(Clone, Default)]
+pub struct World {
+    pub listings: Vec<QueriedListing>,
+    pub players: HashMap<u64, Player>,
+    pub parse_docs: HashMap<u64, ParseCacheDoc>,
+}
+
+impl World {
+    pub fn listing(mut self, builder: ListingBuilder) -> Self {
+        self.listings.push(builder.build());
+        self
+    }
+
+    pub fn player(mut self, content_id: u64, name: &str, home_world: u16) -> Self {
+        self.players.insert(
+            content_id,
+            Player {
+                content_id,
+                name: name.to_string(),
+                home_world,
+                last_seen: base_time(),
+                seen_count: 1,
+            },
+        );
+        self
+    }
+
+    /// `job_id`는 기록을 낸 잡
+    pub fn parse(mut self, content_id: u64, zone_id: u32, encounter_id: u32, percentile: f32, job_id: u8) -> Self {
+        let doc = self.parse_docs.entry(content_id).or_insert_with(|| ParseCacheDoc {
+            content_id: content_id as i64,
+            zones: HashMap::new(),
+            fetch: None,
+        });
+        let zone = doc.zones.entry(zone_id.to_string()).or_insert_with(|| ZoneCache {
+            fetched_at: base_time(),
+            encounters: HashMap::new(),
+        });
+        zone.encounters.insert(
+            encounter_id.to_string(),
+            EncounterParse {
+                percentile,
+                job_id,
+                all_stars: None,
+                jobs: Default::default(),
+            },
+        );
+        self
+    }
+
+    /// 표시 규칙을 한 번씩 거치는 모집글 다섯 개
+    ///
+    /// 1. 분할 영식: 파티장 Parse, 1·2페이즈 Parse, Parse 없는 멤버, 역할이 다른 Parse,
+    ///    플레이어 정보가 없는 멤버, 다른 서버 / 데이터 센터 멤버, 잡이 없는 자리
+    /// 2. 일반 영식: 이전 업데이트 구간
+    /// 3. 던전: 1과 같은 구간 (분류 순위로 정렬)
+    /// 4. 듀티 없는 돌발 모집글: 멤버 정보 없이 잡만 채워진 자리
+    /// 5. 한 잡당 한 명 모집글의 중복 잡
+    pub fn rich() -> Self {
+        Self::default()
+            .listing(
+                ListingBuilder::new(1)
+                    .duty(SAVAGE_SPLIT, DutyCategory::HighEndDuty)
+                    .leader(1001)
+                    .member(1001, 19)
+                    .member(1002, 24)
+                    .member(1003, 22)
+                    .member(1004, 28)
+                    .member(1005, 0)
+                    .uploads(3),
+            )
+            .listing(
+                ListingBuilder::new(2)
+                    .duty(SAVAGE, DutyCategory::HighEndDuty)
+                    .leader(1002)
+                    .member(1002, 24)
+                    .updated_minutes_ago(12),
+            )
+            .listing(
+                ListingBuilder::new(3)
+                    .duty(DUNGEON, DutyCategory::Dungeon)
+                    .leader(1006)
+                    .member(1006, 21)
+                    .member(1007, 37),
+            )
+            .listing(ListingBuilder::new(4).category(DutyCategory::Fate).job(23).job(31).updated_minutes_ago(3))
+            .listing(
+                ListingBuilder::new(5)
+                    .duty(DUNGEON, DutyCategory::Dungeon)
+                    .one_player_per_job()
+                    .leader(1008)
+                    .member(1008, 19)
+                    .member(1009, 19)
+                    .member(1010, 24)
+                    .updated_minutes_ago(30),
+            )
+            .player(1001, "Alpha Tank", 73)
+            .player(1002, "Beta Healer", 79)
+            .player(1004, "Delta Scholar", 49)
+            .player(1005, "Ghost Slot", 73)
+            .player(1006, "Echo Warrior", 73)
+            .player(1008, "Golf Paladin", 73)
+            .player(1009, "Hotel Paladin", 73)
+            .player(1010, "India Healer", 0)
+            .parse(1001, 73, 104, 97.6, 19)
+            .parse(1001, 73, 105, 42.0, 19)
+            .parse(1002, 73, 104, 55.4, 19)
+            .parse(1002, 73, 101, 99.5, 24)
+            .parse(1003, 73, 104, 12.0, 22)
+            .parse(1004, 73, 105, 100.0, 28)
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{TimeDelta, Utc};
+use mongodb::bson::doc;
+use mongodb::error::{CommandError, Error, ErrorKind};
+use mongodb::options::{Collation, CollationStrength, IndexOptions};
+use mongodb::IndexModel;
+
+use crate::config::{Config, Mongo};
+use crate::infra::indexes::{
+    classify, expected_indexes, resolve, stale_ttl_index, IndexFailure, IndexReport, IndexStatus, Resolution,
+};
+
+fn command_error(code: i32, message: &str) -> Error {
+    let command: CommandError = mongodb::bson::from_document(doc! {
+        "code": code,
+        "codeName": "",
+        "errmsg": message,
+    })
+    .unwrap();
+    Error::from(ErrorKind::Command(command))
+}
+
+#[test]
+fn index_errors_are_classified() {
+    assert_eq!(classify(&command_error(68, "index already exists")), IndexFailure::AlreadyExists);
+    assert_eq!(classify(&command_error(85, "IndexOptionsConflict")), IndexFailure::Conflict);
+    assert_eq!(classify(&command_error(86, "IndexKeySpecsConflict")), IndexFailure::Conflict);
+    assert_eq!(classify(&command_error(13, "not authorized on rpf")), IndexFailure::Unauthorized);
+    assert_eq!(
+        classify(&command_error(8000, "user is not allowed to do action [createIndex] on [rpf.listings]")),
+        IndexFailure::Unauthorized
+    );
+    assert_eq!(classify(&command_error(8000, "space quota exceeded")), IndexFailure::Other);
+    assert_eq!(classify(&Error::from(std::io::Error::other("connection reset"))), IndexFailure::Other);
+}
+
+#[test]
+fn failures_continue_only_when_allowed() {
+    use IndexFailure::*;
+
+    // 이미 있는 인덱스는 설정과 관계없이 계속
+    for required in [true, false] {
+        assert_eq!(resolve(AlreadyExists, false, required), Resolution::Present);
+        assert_eq!(resolve(Unauthorized, true, required), Resolution::Present);
+        assert_eq!(resolve(Other, false, required), Resolution::Fail);
+    }
+
+    assert_eq!(resolve(Unauthorized, false, true), Resolution::Fail);
+    assert_eq!(resolve(Unauthorized, false, false), Resolution::Skipped);
+    assert_eq!(resolve(Conflict, false, true), Resolution::Fail);
+    assert_eq!(resolve(Conflict, false, false), Resolution::Skipped);
+}
+
+#[test]
+fn indexes_are_required_by_default() {
+    let config = |extra: &str| -> Config {
+        toml::from_str(&format!(
+            "[web]\nhost = \"127.0.0.1:8000\"\n\n[mongo]\nurl = \"mongodb://127.0.0.1\"\n{}",
+            extra
+        ))
+        .unwrap()
+    };
+    assert!(config("").indexes.required);
+    assert!(!config("\n[indexes]\nrequired = false\n").indexes.required);
+}
+
+#[test]
+fn drift_report_compares_keys_and_options() {
+    let mongo: Mongo = toml::from_str(r#"url = "mongodb://127.0.0.1""#).unwrap();
+    let expected = expected_indexes(&mongo, Duration::from_secs(28 * 24 * 3600));
+    let names: Vec<String> = expected.iter().map(|index| format!("{}.{}", index.collection, index.name())).collect();
+    assert_eq!(
+        names,
+        vec![
+            "listings.listing.id_1_listing.last_server_restart_1_listing.created_world_1",
+            "listings.updated_at_1",
+            "role_demand.sampled_at_1",
+            "parses.content_id_1",
+            "players.name_1_home_world_1",
+            "listings_archive.listing.id_1_listing.last_server_restart_1_listing.created_world_1",
+            "listings_archive.created_at_1",
+            "listings_archive.listing.content_id_lower_1_created_at_-1",
+        ]
+    );
+
+    let model = |keys, options| IndexModel::builder().keys(keys).options(options).build();
+    let existing = HashMap::from([
+        (
+            "listings",
+            vec![
+                model(doc! { "_id": 1 }, IndexOptions::default()),
+                expected[0].model.clone(),
+                // 보관 기간이 다른 TTL 인덱스
+                model(doc! { "updated_at": 1 }, IndexOptions::builder().expire_after(Duration::from_secs(60)).build()),
+            ],
+        ),
+        ("parses", vec![model(doc! { "_id": 1 }, IndexOptions::default())]),
+        // collation 없이 만든 이름 인덱스 (대소문자 무시 검색에 쓰이지 않음)
+        ("players", vec![model(doc! { "name": 1, "home_world": 1 }, IndexOptions::default())]),
+    ]);
+
+    let report = IndexReport::new(&expected, &existing, Utc::now());
+    let statuses: Vec<IndexStatus> = report.indexes.iter().map(|index| index.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            IndexStatus::Present,
+            IndexStatus::Different,
+            IndexStatus::Unknown,
+            IndexStatus::Missing,
+            IndexStatus::Different,
+            IndexStatus::Unknown,
+            IndexStatus::Unknown,
+            IndexStatus::Unknown,
+        ]
+    );
+    assert!(report.drift);
+    assert_eq!(
+        report.drifted(),
+        vec!["listings.updated_at_1", "parses.content_id_1", "players.name_1_home_world_1"]
+    );
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["indexes"][1], serde_json::json!({ "collection": "listings", "name": "updated_at_1", "status": "different" }));
+
+    // 목록을 읽지 못한 컬렉션만 있으면 차이로 보지 않음
+    let report = IndexReport::new(&expected[2..3], &existing, Utc::now());
+    assert!(!report.drift);
+
+    // `listIndexes`는 collation 기본값을 모두 채워서 돌려줌
+    let listed = Collation::builder()
+        .locale("en")
+        .strength(CollationStrength::Secondary)
+        .case_level(false)
+        .numeric_ordering(false)
+        .build();
+    let existing = HashMap::from([(
+        "players",
+        vec![model(doc! { "name": 1, "home_world": 1 }, IndexOptions::builder().collation(listed).build())],
+    )]);
+    let report = IndexReport::new(&expected[4..], &existing, Utc::now());
+    assert_eq!(report.indexes[0].status, IndexStatus::Present);
+}
+
+#[test]
+fn listings_ttl_follows_config() {
+    let mongo = |extra: &str| -> Mongo { toml::from_str(&format!("url = \"mongodb://127.0.0.1\"\n{}", extra)).unwrap() };
+    let hours = |hours: u64| Duration::from_secs(hours * 3600);
+    let ttl = |mongo: &Mongo| expected_indexes(mongo, hours(24))[1].model.options.as_ref().unwrap().expire_after;
+
+    let default = mongo("");
+    assert_eq!(ttl(&default), Some(hours(2)));
+    assert_eq!(default.listings_visible_window(), TimeDelta::hours(1));
+
+    let long = mongo("listings_ttl_hours = 6");
+    assert_eq!(ttl(&long), Some(hours(6)));
+    assert_eq!(long.listings_visible_window(), TimeDelta::hours(5));
+
+    // 목록 표시 기간은 TTL을 넘지 않음
+    let short = mongo("listings_ttl_hours = 1");
+    assert_eq!(ttl(&short), Some(hours(1)));
+    assert_eq!(short.listings_visible_window(), TimeDelta::hours(1));
+    assert_eq!(mongo("listings_ttl_hours = 0").listings_ttl(), hours(1));
+}
+
+#[test]
+fn changed_ttl_drops_the_existing_index_by_its_own_name() {
+    let mongo: Mongo = toml::from_str("url = \"mongodb://127.0.0.1\"\nlistings_ttl_hours = 6").unwrap();
+    let expected = expected_indexes(&mongo, Duration::from_secs(28 * 24 * 3600));
+    let ttl_index = &expected[1];
+
+    let model = |name: &str, keys, ttl| {
+        IndexModel::builder()
+            .keys(keys)
+            .options(IndexOptions::builder().name(name.to_string()).expire_after(ttl).build())
+            .build()
+    };
+    let existing = vec![
+        model("_id_", doc! { "_id": 1 }, None),
+        model("listing_expiry", doc! { "updated_at": 1 }, Some(Duration::from_secs(2 * 3600))),
+    ];
+    let stale = stale_ttl_index(ttl_index, &existing).unwrap();
+    assert_eq!(stale.options.as_ref().unwrap().name.as_deref(), Some("listing_expiry"));
+
+    // 이미 새 보관 기간이면 지울 인덱스 없음
+    let current = vec![model("updated_at_1", doc! { "updated_at": 1 }, Some(Duration::from_secs(6 * 3600)))];
+    assert!(stale_ttl_index(ttl_index, &current).is_none());
+}
@@ -0,0 +1,36 @@
+use std::sync::LazyLock;
+
+use crate::web::job_icons::{job_icon, JobIcons};
+
+static ICONS: LazyLock<JobIcons> = LazyLock::new(|| JobIcons::parse(include_str!("../../assets/icons.svg")));
+
+async fn fetch(path: &str) -> warp::http::Response<warp::hyper::body::Bytes> {
+    warp::test::request().path(path).reply(&job_icon(&ICONS)).await
+}
+
+async fn assert_icon(code: &str, fill: &str) {
+    let response = fetch(&format!("/job/{}.svg", code)).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "image/svg+xml");
+    assert!(response.headers()["cache-control"].to_str().unwrap().contains("max-age"));
+
+    let body = std::str::from_utf8(response.body()).unwrap();
+    assert!(body.starts_with("<svg"), "{}", body);
+    assert!(body.contains(&format!(r#"fill="{}""#, fill)), "{}", body);
+    assert!(body.contains("<path"), "{}", body);
+}
+
+#[tokio::test]
+async fn job_icons_carry_their_role_colour() {
+    assert_icon("PLD", "#455CCB").await;
+    assert_icon("WHM", "#487B39").await;
+    assert_icon("BLM", "#813B3C").await;
+}
+
+#[tokio::test]
+async fn unknown_codes_are_not_found() {
+    assert_eq!(fetch("/job/XYZ.svg").await.status(), 404);
+    // 스프라이트에 있어도 잡이 아닌 심볼은 제공하지 않음
+    assert_eq!(fetch("/job/clock.svg").await.status(), 404);
+    assert_eq!(fetch("/job/PLD").await.status(), 404);
+}
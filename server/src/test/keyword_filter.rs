@@ -0,0 +1,111 @@
+use chrono::Utc;
+use mongodb::bson::doc;
+use sestring::payload::{AutoTranslatePayload, TextPayload};
+use sestring::{Payload, SeString};
+
+use super::fixture_world::ListingBuilder;
+use crate::config::KeywordFilter;
+use crate::listing::keywords::BlockedKeywords;
+use crate::listing::{Blocklist, ListingFilter, PartyFinderListing};
+use crate::listing_container::{StoredUpload, UploadOutcome};
+use crate::mongo::{current_listings_pipeline, filtered_listings_pipeline};
+
+fn keywords(words: &[&str], patterns: &[&str]) -> BlockedKeywords {
+    BlockedKeywords::new(&KeywordFilter {
+        words: words.iter().map(|word| word.to_string()).collect(),
+        patterns: patterns.iter().map(|pattern| pattern.to_string()).collect(),
+    })
+    .unwrap()
+}
+
+fn listing(description: SeString) -> PartyFinderListing {
+    ListingBuilder::new(1).description(description).listing()
+}
+
+fn text(text: &str) -> Payload {
+    Payload::Text(TextPayload(text.to_string()))
+}
+
+#[test]
+fn empty_config_flags_nothing() {
+    let blocked = BlockedKeywords::new(&KeywordFilter::default()).unwrap();
+
+    assert!(blocked.is_empty());
+    assert!(!blocked.flags(&listing(SeString(vec![text("anything at all")]))));
+    // 빈 단어는 모든 설명에 맞는 패턴이 되지 않음
+    assert!(keywords(&["", "  "], &[]).is_empty());
+}
+
+#[test]
+fn invalid_patterns_fail_at_startup() {
+    let config = KeywordFilter { words: Vec::new(), patterns: vec!["(unclosed".into()] };
+    assert!(BlockedKeywords::new(&config).is_err());
+}
+
+#[test]
+fn words_are_literal_and_case_insensitive() {
+    let blocked = keywords(&["bad.word"], &[r"\bscam\w*"]);
+
+    assert!(blocked.matches("this is a BAD.WORD here"));
+    assert!(!blocked.matches("this is a badxword here"));
+    assert!(blocked.matches("Scammers welcome"));
+    assert!(!blocked.matches("no problems"));
+}
+
+#[test]
+fn unicode_descriptions_are_matched() {
+    let blocked = keywords(&["욕설", "暴言", "übel"], &[]);
+
+    assert!(blocked.flags(&listing(SeString(vec![text("초보 환영 욕설 금지 아님")]))));
+    assert!(blocked.flags(&listing(SeString(vec![text("クリア目的 暴言")]))));
+    // 라틴 문자 밖의 대소문자도 무시
+    assert!(blocked.flags(&listing(SeString(vec![text("Das ist ÜBEL")]))));
+    assert!(!blocked.flags(&listing(SeString(vec![text("초보 환영 クリア目的")]))));
+}
+
+#[test]
+fn payloads_between_text_do_not_bypass_the_filter() {
+    let blocked = keywords(&["slur"], &[]);
+
+    // 글자 사이에 아이콘 payload (`02 12 02 02 03`)를 끼운 설명
+    let mut bytes = b"a sl".to_vec();
+    bytes.extend_from_slice(&[0x02, 0x12, 0x02, 0x02, 0x03]);
+    bytes.extend_from_slice(b"ur here");
+    let description = SeString::parse(&bytes).unwrap();
+    assert_eq!(description.0.len(), 3);
+    assert!(blocked.flags(&listing(description)));
+
+    // 보이지 않는 문자와 제어 문자도 지우고 검사
+    assert!(blocked.flags(&listing(SeString(vec![text("s\u{200B}l\u{FEFF}u\u{0007}r")]))));
+    assert!(!blocked.flags(&listing(SeString(vec![text("s l u r")]))));
+}
+
+#[test]
+fn auto_translate_is_checked_in_every_language() {
+    // (1, 102): "Japanese language" / "日本語" / "Japanisch" / "Japonais"
+    let blocked = keywords(&["japonais"], &[]);
+    let description = SeString(vec![text("LF "), Payload::AutoTranslate(AutoTranslatePayload { group: 1, key: 102 })]);
+
+    assert!(blocked.flags(&listing(description)));
+}
+
+#[test]
+fn flagged_listings_are_left_out_unless_requested() {
+    let now = Utc::now();
+    let plain = current_listings_pipeline(now, now, &Blocklist::default());
+    assert_eq!(plain[0].get_document("$match").unwrap().get_document("flagged").unwrap(), &doc! { "$ne": true });
+
+    let filter = ListingFilter { flagged: true, ..Default::default() };
+    assert!(!filter.is_empty());
+    let flagged = filtered_listings_pipeline(now, now, &Blocklist::default(), &filter);
+    let first = flagged[0].get_document("$match").unwrap();
+    assert!(first.get_bool("flagged").unwrap());
+    // 모더레이터가 숨긴 모집글은 그대로 제외
+    assert_eq!(first.get_document("hidden").unwrap(), &doc! { "$ne": true });
+}
+
+#[test]
+fn flagged_uploads_are_not_published() {
+    let flagged = StoredUpload { outcome: UploadOutcome::Inserted, hidden: false, flagged: true };
+    assert!(!flagged.should_publish());
+}
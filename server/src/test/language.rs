@@ -0,0 +1,60 @@
+use crate::ffxiv::Language;
+
+fn negotiate(header: &str) -> Language {
+    Language::from_codes(Some(header))
+}
+
+#[test]
+fn quality_order_wins_over_header_order() {
+    assert_eq!(negotiate("ko-KR,ko;q=0.9,en-US;q=0.8,ja;q=0.7"), Language::English);
+    assert_eq!(negotiate("ko-KR,ko;q=0.9,ja;q=0.8,en-US;q=0.7"), Language::Japanese);
+    assert_eq!(negotiate("en;q=0.5, fr;q=0.9, de;q=0.7"), Language::French);
+    assert_eq!(negotiate("de-DE;q=0.8,ja-JP"), Language::Japanese);
+}
+
+#[test]
+fn region_subtags_fall_back_to_primary() {
+    assert_eq!(negotiate("fr-CA"), Language::French);
+    assert_eq!(negotiate("zh-TW,de-AT;q=0.5"), Language::German);
+    assert_eq!(negotiate("JA-jp"), Language::Japanese);
+    assert_eq!(Language::from_code("en-GB"), Some(Language::English));
+    assert_eq!(Language::from_code("ko-KR"), None);
+}
+
+#[test]
+fn equal_quality_keeps_header_order() {
+    assert_eq!(negotiate("de,fr"), Language::German);
+    assert_eq!(negotiate("fr;q=0.5,de;q=0.5"), Language::French);
+    assert_eq!(negotiate("ja;q=1.000,en;q=1"), Language::Japanese);
+}
+
+#[test]
+fn malformed_quality_values_are_ignored() {
+    assert_eq!(negotiate("ja;q=abc,de;q=0.1"), Language::German);
+    assert_eq!(negotiate("ja;q=1.5,fr;q=0.2"), Language::French);
+    assert_eq!(negotiate("ja;q=0.1234,fr;q=0.2"), Language::French);
+    assert_eq!(negotiate("ja;q=,fr;q=0.2"), Language::French);
+    assert_eq!(negotiate("ja;q=-1,de"), Language::German);
+    assert_eq!(negotiate("fr ; q = 0.5 , ja ; q=0.4"), Language::French);
+}
+
+#[test]
+fn zero_quality_rejects_language() {
+    assert_eq!(negotiate("ja;q=0,de;q=0.1"), Language::German);
+    assert_eq!(negotiate("en;q=0,*;q=0.5"), Language::Japanese);
+}
+
+#[test]
+fn wildcard_matches_default() {
+    assert_eq!(negotiate("*"), Language::English);
+    assert_eq!(negotiate("ko,*;q=0.1"), Language::English);
+    assert_eq!(negotiate("*;q=0.1,fr;q=0.2"), Language::French);
+}
+
+#[test]
+fn unsupported_or_empty_headers_fall_back_to_english() {
+    assert_eq!(Language::from_codes(None), Language::English);
+    assert_eq!(negotiate(""), Language::English);
+    assert_eq!(negotiate(" , ;q=0.5,"), Language::English);
+    assert_eq!(negotiate("ko-KR,zh-CN;q=0.9"), Language::English);
+}
@@ -0,0 +1,82 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use mongodb::bson::{self, doc};
+use sestring::SeString;
+
+use super::fixture_world::{ListingBuilder, SAVAGE};
+use crate::listing::archive::PendingArchive;
+use crate::listing::{DutyCategory, PartyFinderListing};
+use crate::stats::StatsQuery;
+use crate::web::archive::{ArchiveQueue, ArchiveStats};
+
+fn at(minutes: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 2, 1, 20, 0, 0).unwrap() + TimeDelta::try_minutes(minutes).unwrap()
+}
+
+fn listing(id: u32) -> PartyFinderListing {
+    ListingBuilder::new(id).duty(SAVAGE, DutyCategory::HighEndDuty).member(1001, 19).build().listing
+}
+
+#[test]
+fn repeated_uploads_are_merged_in_the_queue() {
+    let queue = ArchiveQueue::new(2);
+    let edited = ListingBuilder::new(1).duty(SAVAGE, DutyCategory::HighEndDuty).member(1001, 19).job(24).listing();
+
+    queue.push(&listing(1), at(0));
+    queue.push(&edited, at(5));
+    queue.push(&listing(2), at(5));
+    queue.push(&listing(3), at(6));
+    assert_eq!(queue.stats(), ArchiveStats { pending: 2, written: 0, dropped: 1 });
+
+    let mut entries = queue.take();
+    entries.sort_by_key(|entry| entry.listing.id);
+    assert_eq!((entries[0].first_seen, entries[0].last_seen), (at(0), at(5)));
+    assert_eq!(entries[0].listing.jobs_present, edited.jobs_present);
+    assert_eq!(queue.stats().pending, 0);
+
+    // 쓰지 못한 기록을 되돌리면 그 사이 업로드와 합침
+    queue.push(&listing(1), at(10));
+    queue.restore(entries);
+    let entries = queue.take();
+    assert_eq!(entries.len(), 2);
+    let first = entries.iter().find(|entry| entry.listing.id == 1).unwrap();
+    assert_eq!((first.first_seen, first.last_seen), (at(0), at(10)));
+    assert_eq!(first.listing.jobs_present, listing(1).jobs_present);
+}
+
+#[test]
+fn archive_records_keep_only_what_stats_need() {
+    let listing = ListingBuilder::new(7)
+        .duty(SAVAGE, DutyCategory::HighEndDuty)
+        .member(1001, 19)
+        .description(SeString::parse("Clear party, weekly reclears".as_bytes()).unwrap())
+        .listing();
+    let entry = PendingArchive { listing: listing.clone(), first_seen: at(0), last_seen: at(30), full_at: None };
+
+    assert_eq!(
+        entry.filter(),
+        doc! { "listing.id": 7, "listing.last_server_restart": listing.last_server_restart, "listing.created_world": listing.created_world as u32 }
+    );
+
+    let update = entry.update().unwrap();
+    assert_eq!(update.get_document("$setOnInsert").unwrap(), &doc! { "created_at": at(0), "first_seen": at(0) });
+    assert_eq!(update.get_document("$max").unwrap(), &doc! { "last_seen": at(30) });
+
+    // 모집글 문서와 같은 경로 / 표현
+    let set = update.get_document("$set").unwrap();
+    let stored = bson::to_document(&listing).unwrap();
+    for field in ["content_id_lower", "name", "home_world", "category", "duty", "duty_type", "search_area", "jobs_present"] {
+        assert_eq!(set.get(format!("listing.{}", field)), stored.get(field), "{}", field);
+    }
+    assert_eq!(set.get_i64("description_length"), Ok(28));
+    assert_eq!(set.get_str("description_language"), Ok("en"));
+    assert!(!set.keys().any(|key| key.contains("description.") || key == "description_text" || key == "listing.description"));
+}
+
+#[test]
+fn stats_pipeline_reads_archived_description_lengths() {
+    let pipeline = StatsQuery::default().build();
+    let facets = pipeline.last().unwrap().get_document("$facet").unwrap();
+    let project = facets.get_array("languages").unwrap()[0].as_document().unwrap().get_document("$project").unwrap();
+    let length = project.get_document("length").unwrap().get_array("$ifNull").unwrap();
+    assert_eq!(length[0], bson::Bson::String("$description_length".to_string()));
+}
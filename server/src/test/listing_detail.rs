@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use mongodb::bson::doc;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use crate::config::{Config, Logging};
+use crate::listing::Blocklist;
+use crate::mongo::{current_listings_pipeline, listing_by_id_pipeline};
+use crate::web::routes::router;
+use crate::web::State;
+
+#[test]
+fn detail_pipeline_reuses_the_list_stages() {
+    let now = Utc::now();
+    let plain = current_listings_pipeline(now, now, &Blocklist::default());
+    let pipeline = listing_by_id_pipeline(now, now, &Blocklist::default(), 123, Some(73));
+
+    assert_eq!(pipeline.len(), plain.len() + 3);
+    assert_eq!(pipeline[1], doc! { "$match": { "listing.id": 123, "listing.created_world": 73 } });
+    // time_left 계산과 만료 조건은 목록과 같음
+    assert_eq!(pipeline[2..plain.len() + 1], plain[1..]);
+    assert_eq!(pipeline[plain.len() + 1..], [doc! { "$sort": { "updated_at": -1 } }, doc! { "$limit": 1 }]);
+
+    let any_world = listing_by_id_pipeline(now, now, &Blocklist::default(), 123, None);
+    assert_eq!(any_world[1], doc! { "$match": { "listing.id": 123 } });
+}
+
+/// DB 오류는 404가 아니라 500
+#[tokio::test]
+async fn database_errors_are_not_reported_as_missing() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let state = State::new(Arc::new(config), log_handle).await.unwrap();
+
+    let response = warp::test::request()
+        .path("/api/listings/123?created_world=73")
+        .reply(&router(state))
+        .await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
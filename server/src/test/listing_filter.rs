@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use mongodb::bson::doc;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use crate::api::ListingsQuery;
+use crate::config::{Config, Logging};
+use crate::listing::{Blocklist, DutyCategory, DutyType, ListingFilter};
+use crate::mongo::{current_listings_pipeline, filtered_listings_pipeline};
+use crate::web::routes::router;
+use crate::web::State;
+
+async fn query(query: &str) -> ListingsQuery {
+    warp::test::request()
+        .path(&format!("/api/listings?{}", query))
+        .filter(&warp::query::<ListingsQuery>())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn combined_filters_are_parsed() {
+    let filter = query("duty=1075,%201069&datacentre=mana&category=HighEndDuty,fate")
+        .await
+        .listing_filter()
+        .unwrap();
+
+    assert_eq!(filter.duties, vec![1075, 1069]);
+    assert_eq!(filter.categories, vec![DutyCategory::HighEndDuty, DutyCategory::Fate]);
+    // Mana: Asura(23), Pandaemonium(28), Anima(44), ...
+    assert!(filter.worlds.starts_with(&[23, 28, 44]));
+    assert!(!filter.worlds.contains(&73));
+
+    assert!(query("").await.listing_filter().unwrap().is_empty());
+    assert!(query("duty=&category=").await.listing_filter().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn unknown_values_match_nothing() {
+    for unknown in ["duty=1075,savage", "duty=70000", "datacentre=Nowhere", "category=HighEndDuty,Raids"] {
+        assert_eq!(query(unknown).await.listing_filter(), None, "{}", unknown);
+    }
+}
+
+#[test]
+fn filters_are_matched_in_the_pipeline() {
+    let now = Utc::now();
+    let filter = ListingFilter {
+        duties: vec![1075],
+        worlds: vec![73, 79],
+        categories: vec![DutyCategory::HighEndDuty],
+        flagged: false,
+    };
+
+    let plain = current_listings_pipeline(now, now, &Blocklist::default());
+    let filtered = filtered_listings_pipeline(now, now, &Blocklist::default(), &filter);
+    assert_eq!(filtered.len(), plain.len() + 1);
+    assert_eq!(filtered[0], plain[0]);
+    assert_eq!(
+        filtered[1],
+        doc! {
+            "$match": {
+                "listing.duty_type": DutyType::Normal as i32,
+                "listing.duty": { "$in": [1075] },
+                "listing.created_world": { "$in": [73, 79] },
+                "$or": [
+                    { "canonical_category": { "$in": [64_i64] } },
+                    { "canonical_category": { "$exists": false }, "listing.category": { "$in": [64_i64] } },
+                ],
+            }
+        }
+    );
+
+    // 조건이 없으면 그대로
+    assert_eq!(filtered_listings_pipeline(now, now, &Blocklist::default(), &ListingFilter::default()), plain);
+}
+
+/// 알 수 없는 값은 DB 조회 없이 빈 배열
+#[tokio::test]
+async fn unknown_datacentre_returns_an_empty_array() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let state = State::new(Arc::new(config), log_handle).await.unwrap();
+
+    let response = warp::test::request()
+        .path("/api/listings?duty=1075&datacentre=Nowhere")
+        .reply(&router(state))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body().as_ref(), b"[]");
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+use crate::api::build_api_listings;
+use crate::config::ListingSort;
+use crate::listing::{DutyCategory, PartyFinderCategory, PartyFinderListing};
+use crate::listing_container::{bucket_minutes, sort_for_display, QueriedListing};
+use crate::web::handlers::build_renderable_listings;
+
+fn bucket(minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 6, 12, minute, 0).unwrap()
+}
+
+fn queried(id: u32, updated_minute: DateTime<Utc>, category: DutyCategory, time_left: f64) -> QueriedListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.id = id;
+    listing.category = category;
+
+    QueriedListing {
+        created_at: updated_minute,
+        updated_at: updated_minute,
+        updated_minute,
+        time_left,
+        ..super::queried(listing)
+    }
+}
+
+/// 업데이트 구간 / 카테고리 / 남은 시간이 섞인 모집글 (입력 순서는 뒤섞음)
+fn fixtures() -> Vec<QueriedListing> {
+    vec![
+        queried(1, bucket(0), DutyCategory::HighEndDuty, 1200.0),
+        queried(2, bucket(5), DutyCategory::Dungeon, 600.0),
+        queried(3, bucket(5), DutyCategory::HighEndDuty, 3000.0),
+        queried(4, bucket(0), DutyCategory::TheHunt, 100.0),
+        queried(5, bucket(5), DutyCategory::HighEndDuty, 900.0),
+        queried(6, bucket(10), DutyCategory::DutyRoulette, 3500.0),
+        queried(7, bucket(0), DutyCategory::HighEndDuty, 300.0),
+    ]
+}
+
+#[test]
+fn category_rank_follows_display_table() {
+    let ranks: Vec<u8> = PartyFinderCategory::ALL.iter().map(|c| c.display_rank()).collect();
+    assert_eq!(ranks, (0..PartyFinderCategory::ALL.len() as u8).collect::<Vec<_>>());
+}
+
+#[test]
+fn website_and_api_share_ordering() {
+    let html: Vec<u32> = build_renderable_listings(fixtures(), &HashMap::new(), &HashMap::new())
+        .iter()
+        .map(|l| l.container.listing.id)
+        .collect();
+
+    let api = build_api_listings(fixtures(), &HashMap::new(), &HashMap::new(), FixedOffset::east_opt(0).unwrap());
+    let json = serde_json::to_value(&api).unwrap();
+    let api: Vec<u32> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| l["listing"]["id"].as_u64().unwrap() as u32)
+        .collect();
+
+    // 최근 구간 먼저, 같은 구간에서는 카테고리 순위가 높은 쪽, 그다음 남은 시간이 짧은 쪽
+    assert_eq!(html, [6, 5, 3, 2, 4, 7, 1]);
+    assert_eq!(api, html);
+}
+
+#[test]
+fn api_exposes_sort_key() {
+    let api = build_api_listings(fixtures(), &HashMap::new(), &HashMap::new(), FixedOffset::east_opt(0).unwrap());
+    let json = serde_json::to_value(&api).unwrap();
+    let first = &json[0]["sort_key"];
+
+    assert_eq!(first["updated_bucket"], "2026-01-06T12:10:00Z");
+    assert_eq!(first["category_rank"], PartyFinderCategory::DutyRoulette.display_rank());
+    assert_eq!(first["time_left"], 3500.0);
+}
+
+#[test]
+fn bucket_size_follows_category_config() {
+    let sort = ListingSort::default();
+    assert_eq!(bucket_minutes(&sort, DutyCategory::HighEndDuty), 5);
+    assert_eq!(bucket_minutes(&sort, DutyCategory::FieldOperation), 15);
+    assert_eq!(bucket_minutes(&sort, DutyCategory::DeepDungeon), 15);
+
+    let sort: ListingSort = toml::from_str(
+        r#"
+        bucket_minutes = 10
+        category_bucket_minutes = { TheHunt = 30, Fate = 0 }
+        "#,
+    )
+    .unwrap();
+    assert_eq!(bucket_minutes(&sort, DutyCategory::HighEndDuty), 10);
+    assert_eq!(bucket_minutes(&sort, DutyCategory::TheHunt), 30);
+    // 표를 설정하면 기본 재정의는 사라짐, 0분은 1분으로 취급
+    assert_eq!(bucket_minutes(&sort, DutyCategory::FieldOperation), 10);
+    assert_eq!(bucket_minutes(&sort, DutyCategory::Fate), 1);
+}
+
+#[test]
+fn savage_outranks_recently_bumped_field_op() {
+    let at = |minute: u32, second: u32| Utc.with_ymd_and_hms(2026, 1, 6, 12, minute, second).unwrap();
+    let listings = |sort: &ListingSort| {
+        let mut listings = vec![
+            queried(1, at(13, 30), DutyCategory::FieldOperation, 3000.0),
+            queried(2, at(9, 10), DutyCategory::HighEndDuty, 3000.0),
+            queried(3, at(14, 50), DutyCategory::DeepDungeon, 3000.0),
+        ];
+        for listing in &mut listings {
+            listing.refresh_bucket(sort);
+        }
+        sort_for_display(&mut listings);
+        listings.iter().map(|l| (l.listing.id, l.updated_minute)).collect::<Vec<_>>()
+    };
+
+    // 필드 탐색 / 딥 던전은 15분 구간(12:00)이라 6분 전 고난이도 파티(12:05)보다 아래
+    assert_eq!(listings(&ListingSort::default()), [(2, at(5, 0)), (1, at(0, 0)), (3, at(0, 0))]);
+
+    // 모든 분류가 5분 구간이면 기존처럼 최근 갱신된 필드 탐색이 위로 올라옴
+    let flat = ListingSort {
+        bucket_minutes: 5,
+        category_bucket_minutes: HashMap::new(),
+    };
+    assert_eq!(listings(&flat), [(1, at(10, 0)), (3, at(10, 0)), (2, at(5, 0))]);
+}
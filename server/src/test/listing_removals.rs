@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use super::fixture_world::ListingBuilder;
+use crate::listing::ListingFilter;
+use crate::web::listing_events::{ListingEvent, ListingRef, RemovalTracker};
+use crate::ws::stream_listings;
+
+fn listing_ref(id: u32) -> ListingRef {
+    ListingRef {
+        id,
+        created_world: 73,
+        last_server_restart: 0,
+    }
+}
+
+#[test]
+fn listings_missing_from_the_next_cycle_are_removed() {
+    let mut tracker = RemovalTracker::default();
+
+    // 첫 주기는 비교 대상이 없음
+    tracker.observe_updated(&[ListingBuilder::new(9).build().listing]);
+    assert!(tracker.diff([listing_ref(3), listing_ref(1), listing_ref(2)]).is_empty());
+
+    assert_eq!(tracker.diff([listing_ref(2)]), [listing_ref(1), listing_ref(3)]);
+    assert!(tracker.diff([listing_ref(2), listing_ref(4)]).is_empty());
+
+    // 다른 서버의 같은 ID는 다른 모집글
+    let other_world = ListingRef { created_world: 79, ..listing_ref(2) };
+    assert_eq!(tracker.diff([other_world, listing_ref(4)]), [listing_ref(2)]);
+}
+
+/// 주기 사이에 올라와 다음 주기 전에 만료된 모집글
+#[test]
+fn updates_between_cycles_are_tracked() {
+    let mut tracker = RemovalTracker::default();
+    tracker.diff([listing_ref(1)]);
+
+    tracker.observe_updated(&[ListingBuilder::new(5).build().listing]);
+    assert_eq!(tracker.diff([listing_ref(1)]), [listing_ref(5)]);
+}
+
+#[tokio::test]
+async fn removals_are_sent_as_their_own_message() {
+    let (channel, receiver) = tokio::sync::broadcast::channel(16);
+    let (sender, mut outbound) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(stream_listings(receiver, || async { Ok(Arc::default()) }, ListingFilter::default(), sender));
+
+    let snapshot = serde_json::to_value(outbound.recv().await.unwrap()).unwrap();
+    assert_eq!(snapshot["type"], "snapshot");
+
+    channel.send(ListingEvent::Removed(vec![listing_ref(7)].into())).unwrap();
+    let removed = serde_json::to_value(outbound.recv().await.unwrap()).unwrap();
+    assert_eq!(
+        removed,
+        serde_json::json!({
+            "type": "removed",
+            "listings": [{ "id": 7, "created_world": 73, "last_server_restart": 0 }],
+        })
+    );
+}
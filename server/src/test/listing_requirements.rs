@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use chrono::FixedOffset;
+use mongodb::bson;
+
+use super::queried;
+use crate::api::build_api_listings;
+use crate::ffxiv::Language;
+use crate::listing::requirements::{ListingRequirements, LootRule, VoiceChat, MAX_DURATION_MINUTES};
+use crate::listing::PartyFinderListing;
+use crate::template::listings::ListingsTemplate;
+use crate::web::handlers::build_renderable_listings;
+
+/// 기본 모집글에 `requirements`를 붙여서 파싱
+fn listing_with(requirements: serde_json::Value) -> PartyFinderListing {
+    let mut listing: serde_json::Value = serde_json::from_str(super::LISTING).unwrap();
+    listing["requirements"] = requirements;
+    serde_json::from_value(listing).unwrap()
+}
+
+#[test]
+fn partial_objects_are_accepted() {
+    let listing = listing_with(serde_json::json!({ "voice_chat": "required" }));
+    assert_eq!(
+        listing.requirements,
+        Some(ListingRequirements { voice_chat: Some(VoiceChat::Required), ..Default::default() }),
+    );
+
+    let listing = listing_with(serde_json::json!({ "min_clears": 3, "loot": "one_per_member" }));
+    let requirements = listing.requirements.unwrap();
+    assert_eq!(requirements.min_clears, Some(3));
+    assert_eq!(requirements.loot, Some(LootRule::OnePerMember));
+    assert_eq!(requirements.voice_chat, None);
+}
+
+#[test]
+fn unknown_keys_and_values_are_ignored() {
+    // 새 플러그인이 보낸 필드와 값은 모집글을 거부하지 않음
+    let listing = listing_with(serde_json::json!({
+        "min_clears": "lots",
+        "voice_chat": "push_to_talk",
+        "loot": "free_roll",
+        "duration_minutes": -30,
+        "strats": "game8",
+    }));
+    assert_eq!(
+        listing.requirements,
+        Some(ListingRequirements { loot: Some(LootRule::FreeRoll), ..Default::default() }),
+    );
+
+    // 남는 값이 없으면 없는 것과 같음
+    let listing = listing_with(serde_json::json!({ "voice_chat": "push_to_talk", "strats": "game8" }));
+    assert_eq!(listing.requirements, None);
+    assert_eq!(listing_with(serde_json::json!({})).requirements, None);
+    assert_eq!(listing_with(serde_json::Value::Null).requirements, None);
+}
+
+#[test]
+fn durations_are_clamped() {
+    let duration = |minutes: serde_json::Value| {
+        listing_with(serde_json::json!({ "duration_minutes": minutes }))
+            .requirements
+            .and_then(|requirements| requirements.duration_minutes)
+    };
+    assert_eq!(duration(serde_json::json!(90)), Some(90));
+    assert_eq!(duration(serde_json::json!(100_000)), Some(MAX_DURATION_MINUTES));
+    assert_eq!(duration(serde_json::json!(0)), None);
+}
+
+#[test]
+fn listings_without_requirements_are_unchanged() {
+    let listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    assert_eq!(listing.requirements, None);
+    assert!(!serde_json::to_value(&listing).unwrap().as_object().unwrap().contains_key("requirements"));
+    assert!(!bson::to_document(&listing).unwrap().contains_key("requirements"));
+}
+
+#[test]
+fn requirements_round_trip_through_storage() {
+    let listing = listing_with(serde_json::json!({ "min_clears": 1, "voice_chat": "not_used", "duration_minutes": 120 }));
+    let document = bson::to_document(&listing).unwrap();
+    let stored: PartyFinderListing = bson::from_document(document).unwrap();
+    assert_eq!(stored.requirements, listing.requirements);
+}
+
+#[test]
+fn api_and_html_show_requirements() {
+    let listing = listing_with(serde_json::json!({
+        "min_clears": 3,
+        "voice_chat": "preferred",
+        "duration_minutes": 90,
+    }));
+
+    let api = serde_json::to_value(build_api_listings(
+        vec![queried(listing.clone())],
+        &HashMap::new(),
+        &HashMap::new(),
+        FixedOffset::east_opt(0).unwrap(),
+    ))
+    .unwrap();
+    assert_eq!(
+        api[0]["listing"]["requirements"],
+        serde_json::json!({ "min_clears": 3, "voice_chat": "preferred", "duration_minutes": 90 }),
+    );
+
+    let renderable = build_renderable_listings(vec![queried(listing)], &HashMap::new(), &HashMap::new());
+    let html = ListingsTemplate { containers: renderable, lang: Language::English, notice: None }.render().unwrap();
+    assert_eq!(html.matches(r#"class="requirement""#).count(), 3);
+    assert!(html.contains(r#"<title data-i18n="req_voice_preferred">Voice chat preferred</title>"#));
+    assert!(html.contains(r#"<span class="text">3+</span>"#));
+    assert!(html.contains(r#"<span class="text">1h 30m</span>"#));
+
+    // 조건이 없는 모집글은 영역 자체가 없음
+    let plain: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    let renderable = build_renderable_listings(vec![queried(plain)], &HashMap::new(), &HashMap::new());
+    let html = ListingsTemplate { containers: renderable, lang: Language::English, notice: None }.render().unwrap();
+    assert!(!html.contains(r#"class="requirements""#));
+}
@@ -0,0 +1,90 @@
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use mongodb::bson::{self, doc};
+
+use crate::listing::Blocklist;
+use crate::mongo::{current_listings_pipeline, listing_summary_pipeline};
+use crate::stats::summary::{DataCentreCount, ListingSummary, SummaryCounts, SUMMARY_TOP_DUTIES};
+use crate::web::summary::SummaryCache;
+
+fn counts() -> SummaryCounts {
+    bson::from_document(doc! {
+        "total": [{ "count": 9 }],
+        // 64 = HighEndDuty, 2 = DutyRoulette, 3 = 알 수 없는 분류
+        "categories": [{ "_id": 64_i64, "count": 5 }, { "_id": 2_i64, "count": 3 }, { "_id": 3_i64, "count": 1 }],
+        "duties": [{ "_id": 280, "count": 4 }, { "_id": 65000, "count": 1 }],
+        // Adamantoise, Cactuar (Aether), Famfrit (Primal), 알 수 없는 월드
+        "worlds": [{ "_id": 73, "count": 2 }, { "_id": 35, "count": 4 }, { "_id": 79, "count": 2 }, { "_id": 9999, "count": 1 }],
+    })
+    .unwrap()
+}
+
+#[test]
+fn summary_pipeline_counts_only_public_listings() {
+    let now = Utc::now();
+    let plain = current_listings_pipeline(now, now, &Blocklist::default());
+    let pipeline = listing_summary_pipeline(now, now, &Blocklist::default(), SUMMARY_TOP_DUTIES);
+
+    // 목록과 같은 조건 (숨김 / 금칙어 / 만료 제외) 뒤에 개수만 셈
+    assert_eq!(pipeline[..plain.len()], plain[..]);
+    assert_eq!(pipeline.len(), plain.len() + 1);
+
+    let facets = pipeline.last().unwrap().get_document("$facet").unwrap();
+    let duties = facets.get_array("duties").unwrap();
+    assert_eq!(duties.last().unwrap().as_document().unwrap(), &doc! { "$limit": SUMMARY_TOP_DUTIES as i64 });
+    let first = duties[0].as_document().unwrap().get_document("$match").unwrap();
+    assert_eq!(first.get_document("listing.duty").unwrap(), &doc! { "$ne": 0 });
+}
+
+#[test]
+fn summary_names_categories_duties_and_data_centres() {
+    let generated_at = Utc::now();
+    let summary = ListingSummary::new(counts(), generated_at);
+
+    assert_eq!(summary.listings, 9);
+    assert_eq!(summary.generated_at, generated_at);
+
+    let categories: Vec<_> = summary.categories.iter().map(|c| (c.category, c.count)).collect();
+    assert_eq!(categories, vec![("high_end_duty", 5), ("duty_roulette", 3)]);
+
+    assert_eq!(summary.duties[0].duty, 280);
+    assert_eq!(summary.duties[0].name.unwrap().en, "The Unending Coil of Bahamut (Ultimate)");
+    assert!(summary.duties[1].name.is_none());
+
+    assert_eq!(
+        summary.data_centres,
+        vec![
+            DataCentreCount { data_centre: "Aether", count: 4 },
+            DataCentreCount { data_centre: "Primal", count: 4 },
+        ]
+    );
+
+    let json = serde_json::to_value(&summary).unwrap();
+    assert!(json["generated_at"].is_string());
+    assert_eq!(json["categories"][0]["name"]["en"], "High-end Duty");
+}
+
+#[test]
+fn empty_result_is_an_empty_summary() {
+    let summary = ListingSummary::new(SummaryCounts::default(), Utc::now());
+
+    assert_eq!(summary.listings, 0);
+    assert!(summary.categories.is_empty() && summary.duties.is_empty() && summary.data_centres.is_empty());
+}
+
+#[tokio::test]
+async fn summary_is_reused_until_it_expires() {
+    let cache = SummaryCache::new(Duration::from_secs(5));
+    let now = Instant::now();
+
+    assert!(cache.get(now).is_none());
+    let stored = cache.insert(ListingSummary::new(counts(), Utc::now()), now);
+    assert_eq!(cache.get(now + Duration::from_secs(4)).unwrap().generated_at, stored.generated_at);
+    assert!(cache.get(now + Duration::from_secs(5)).is_none());
+
+    // 실패한 집계는 저장하지 않음
+    let empty = SummaryCache::new(Duration::from_secs(5));
+    assert!(empty.get_or_load(|| async { anyhow::bail!("down") }).await.is_err());
+    assert!(empty.get(Instant::now()).is_none());
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use sestring::SeString;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use super::fixture_world::{ListingBuilder, SAVAGE};
+use crate::config::{Config, Logging};
+use crate::ffxiv::unknown_ids::{IdKind, UNKNOWN_IDS};
+use crate::listing::{DutyCategory, DutyType, JobFlags, ListingProblem, PartyFinderListing, PartyFinderSlot};
+use crate::web::routes::router;
+use crate::web::State;
+
+/// 잡 240번대, 듀티 60000번대는 이 파일만 사용 (미확인 ID 레지스트리를 공유)
+fn valid() -> PartyFinderListing {
+    ListingBuilder::new(7).duty(SAVAGE, DutyCategory::HighEndDuty).member(1001, 19).build().listing
+}
+
+fn problems(change: impl FnOnce(&mut PartyFinderListing)) -> Vec<ListingProblem> {
+    let mut listing = valid();
+    change(&mut listing);
+    listing.validate().err().map(|invalid| invalid.problems).unwrap_or_default()
+}
+
+#[test]
+fn valid_listings_pass() {
+    assert_eq!(valid().validate(), Ok(()));
+    assert_eq!(serde_json::from_str::<PartyFinderListing>(super::LISTING).unwrap().validate(), Ok(()));
+
+    // 카테고리 이름으로 표시되는 모집글, 파티 수 0 (1로 취급), 연합 모집
+    assert_eq!(ListingBuilder::new(8).category(DutyCategory::Fate).build().listing.validate(), Ok(()));
+    assert!(problems(|l| l.num_parties = 0).is_empty());
+    assert!(problems(|l| {
+        l.num_parties = 3;
+        l.slots = vec![PartyFinderSlot { accepting: JobFlags::all() }; 24];
+        l.jobs_present = vec![0; 24];
+    })
+    .is_empty());
+}
+
+type Change = fn(&mut PartyFinderListing);
+
+#[test]
+fn each_rejection_reason_is_reported() {
+    let cases: [(ListingProblem, Change); 10] = [
+        (ListingProblem::SecondsRemaining, |l| l.seconds_remaining = 60 * 60 + 1),
+        (ListingProblem::UnknownDuty, |l| l.duty = 60_000),
+        (ListingProblem::UnknownDuty, |l| {
+            l.duty_type = DutyType::Roulette;
+            l.duty = 60_001;
+        }),
+        (ListingProblem::PartyCount, |l| l.num_parties = 7),
+        (ListingProblem::Slots, |l| l.slots_available = 9),
+        (ListingProblem::Slots, |l| l.slots.push(PartyFinderSlot { accepting: JobFlags::all() })),
+        (ListingProblem::JobsPresent, |l| l.jobs_present.push(0)),
+        (ListingProblem::UnknownJob, |l| l.jobs_present[1] = 240),
+        (ListingProblem::World, |l| l.home_world = 1_000),
+        (ListingProblem::DescriptionLength, |l| l.description = SeString::parse([b'a'; 3_000]).unwrap()),
+    ];
+
+    for (expected, change) in cases {
+        assert_eq!(problems(change), [expected]);
+    }
+}
+
+#[test]
+fn all_failed_checks_are_listed_and_unknown_ids_recorded() {
+    let mut listing = valid();
+    listing.duty = 60_002;
+    listing.created_world = 4_000;
+    listing.jobs_present[0] = 241;
+
+    let invalid = listing.validate().unwrap_err();
+    assert_eq!(invalid.id, 7);
+    assert_eq!(invalid.problems, [ListingProblem::UnknownDuty, ListingProblem::UnknownJob, ListingProblem::World]);
+
+    let unknown = UNKNOWN_IDS.snapshot();
+    assert!(unknown.iter().any(|id| id.kind == IdKind::Duty && id.id == 60_002 && id.sample == listing.key()));
+    assert!(unknown.iter().any(|id| id.kind == IdKind::Job && id.id == 241));
+}
+
+/// 검증은 저장 전이므로 DB 없이 응답 확인
+#[tokio::test]
+async fn invalid_uploads_get_a_structured_error() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+
+        [ratelimit]
+        uploader_secret = "test"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let state = State::new(Arc::new(config), log_handle).await.unwrap();
+
+    let mut listing = valid();
+    listing.duty = 60_000;
+    listing.num_parties = 9;
+    let response = warp::test::request()
+        .method("POST")
+        .path("/contribute")
+        .json(&listing)
+        .reply(&router(state))
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["reason"], "invalid_listing");
+    assert_eq!(body["problems"], serde_json::json!(["unknown_duty", "party_count"]));
+}
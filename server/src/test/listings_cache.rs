@@ -0,0 +1,109 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::future::join_all;
+
+use crate::listing_container::QueriedListing;
+use crate::web::listings_cache::{ListingsCache, ListingsCacheStats};
+
+fn listings(count: u32) -> Vec<QueriedListing> {
+    (1..=count)
+        .map(|id| {
+            let mut listing: crate::listing::PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+            listing.id = id;
+            super::queried(listing)
+        })
+        .collect()
+}
+
+/// `delay` 후 모집글 `count`개를 돌려주는 조회 (호출 수 기록)
+async fn rebuild(calls: &AtomicUsize, delay: Duration, count: u32) -> anyhow::Result<Vec<QueriedListing>> {
+    calls.fetch_add(1, Ordering::SeqCst);
+    tokio::time::sleep(delay).await;
+    Ok(listings(count))
+}
+
+/// 만료 직후 동시에 들어온 요청은 한 번만 다시 조회
+#[tokio::test]
+async fn concurrent_misses_rebuild_once() {
+    let cache = ListingsCache::new(Duration::from_millis(50), Duration::from_secs(5));
+    let calls = AtomicUsize::new(0);
+
+    cache.get(|| rebuild(&calls, Duration::ZERO, 1)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(60)).await;
+
+    let results = join_all((0..50).map(|_| cache.get(|| rebuild(&calls, Duration::from_millis(50), 2)))).await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(results.iter().all(|result| result.as_ref().unwrap().len() == 2));
+    assert_eq!(
+        cache.stats(),
+        ListingsCacheStats { rebuilds: 2, coalesced_waits: 49, stale_serves: 0 },
+    );
+
+    // 새 스냅샷은 다시 조회하지 않음
+    cache.get(|| rebuild(&calls, Duration::ZERO, 3)).await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+/// 업로드 후에는 만료 전이라도 다시 조회하지만, 오래 걸리면 기다리던 요청은 이전 스냅샷으로 응답
+#[tokio::test]
+async fn invalidated_cache_serves_stale_while_slow_rebuild_runs() {
+    let cache = ListingsCache::new(Duration::from_secs(60), Duration::from_millis(20));
+    let calls = AtomicUsize::new(0);
+
+    cache.get(|| rebuild(&calls, Duration::ZERO, 1)).await.unwrap();
+    cache.invalidate();
+
+    let (leader, waiter) = tokio::join!(
+        cache.get(|| rebuild(&calls, Duration::from_millis(200), 2)),
+        async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            cache.get(|| rebuild(&calls, Duration::ZERO, 3)).await
+        },
+    );
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(waiter.unwrap().len(), 1);
+    assert_eq!(leader.unwrap().len(), 2);
+    assert_eq!(cache.stats().stale_serves, 1);
+}
+
+/// 다시 조회하지 못하면 이전 스냅샷으로 응답하고, 스냅샷이 없으면 에러
+#[tokio::test]
+async fn failed_rebuild_falls_back_to_previous_snapshot() {
+    let cache = ListingsCache::new(Duration::from_secs(60), Duration::from_secs(5));
+
+    let first = cache.get(|| async { anyhow::bail!("database is down") }).await;
+    assert!(first.is_err());
+
+    cache.get(|| async { Ok(listings(1)) }).await.unwrap();
+    cache.invalidate();
+    let fallback = cache.get(|| async { anyhow::bail!("database is down") }).await.unwrap();
+    assert_eq!(fallback.len(), 1);
+    assert_eq!(cache.stats().stale_serves, 1);
+}
+
+/// 조회하던 요청이 취소돼도 다음 요청이 다시 조회
+#[tokio::test]
+async fn cancelled_rebuild_does_not_block_later_requests() {
+    let cache = Arc::new(ListingsCache::new(Duration::from_secs(60), Duration::from_secs(5)));
+
+    let stuck = {
+        let cache = Arc::clone(&cache);
+        tokio::spawn(async move {
+            cache.get(std::future::pending::<anyhow::Result<Vec<QueriedListing>>>).await
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    stuck.abort();
+    let _ = stuck.await;
+
+    let snapshot = tokio::time::timeout(Duration::from_secs(1), cache.get(|| async { Ok(listings(2)) }))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(snapshot.len(), 2);
+    assert_eq!(cache.stats().rebuilds, 2);
+}
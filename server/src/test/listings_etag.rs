@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use chrono::TimeDelta;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use super::fixture_world::ListingBuilder;
+use crate::api::conditional::{etag_matches, listings_etag};
+use crate::config::{Config, Logging};
+use crate::listing_container::QueriedListing;
+use crate::web::handlers::record_detail_stored;
+use crate::web::routes::router;
+use crate::web::State;
+
+async fn state() -> Arc<State> {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    State::new(Arc::new(config), log_handle).await.unwrap()
+}
+
+/// 현재 모집글 캐시를 DB 대신 `listings`로 채움
+async fn serve(state: &State, listings: Vec<QueriedListing>) {
+    state.listings_cache.invalidate();
+    state.listings_cache.get(|| async { Ok(listings) }).await.unwrap();
+}
+
+/// 같은 모집글을 다시 업로드 (업데이트 시각과 업로드 수가 바뀜)
+fn reuploaded(mut listing: QueriedListing) -> QueriedListing {
+    listing.updated_at += TimeDelta::try_minutes(1).unwrap();
+    listing.upload_count += 1;
+    listing
+}
+
+#[test]
+fn etag_follows_listing_keys_update_times_and_revision() {
+    let listings = vec![ListingBuilder::new(1).build(), ListingBuilder::new(2).build()];
+    let etag = listings_etag(7, &listings);
+    assert!(etag.starts_with('"') && etag.ends_with('"'));
+    assert_eq!(listings_etag(7, &listings), etag);
+
+    assert_ne!(listings_etag(8, &listings), etag);
+    assert_ne!(listings_etag(7, &listings[..1]), etag);
+    assert_ne!(listings_etag(7, &[listings[0].clone(), reuploaded(listings[1].clone())]), etag);
+
+    // 남은 시간은 ETag에 넣지 않음
+    let mut later = listings.clone();
+    later[0].time_left -= 60.0;
+    assert_eq!(listings_etag(7, &later), etag);
+}
+
+#[test]
+fn if_none_match_accepts_lists_weak_tags_and_wildcards() {
+    assert!(etag_matches(r#""abc""#, r#""abc""#));
+    assert!(etag_matches(r#""old", W/"abc""#, r#""abc""#));
+    assert!(etag_matches("*", r#""abc""#));
+    assert!(!etag_matches(r#""abd""#, r#""abc""#));
+    assert!(!etag_matches("abc", r#""abc""#));
+}
+
+#[tokio::test]
+async fn unchanged_listings_are_answered_with_304() {
+    let state = state().await;
+    let listing = ListingBuilder::new(1).build();
+    serve(&state, vec![listing.clone()]).await;
+    let routes = router(Arc::clone(&state));
+
+    let first = warp::test::request().path("/api/listings").reply(&routes).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    let etag = first.headers()["etag"].to_str().unwrap().to_string();
+    assert!(!first.body().is_empty());
+
+    let cached = warp::test::request().path("/api/listings").header("if-none-match", &etag).reply(&routes).await;
+    assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(cached.headers()["etag"], etag.as_str());
+    assert!(cached.body().is_empty());
+
+    let head = warp::test::request().method("HEAD").path("/api/listings").reply(&routes).await;
+    assert_eq!(head.status(), StatusCode::OK);
+    assert_eq!(head.headers()["etag"], etag.as_str());
+    assert_eq!(head.headers()["content-type"], "application/json");
+    assert!(head.body().is_empty());
+
+    // 새 업로드로 모집글이 바뀌면 이전 ETag로는 304가 아님
+    serve(&state, vec![reuploaded(listing)]).await;
+    let changed = warp::test::request().path("/api/listings").header("if-none-match", &etag).reply(&routes).await;
+    assert_eq!(changed.status(), StatusCode::OK);
+    let new_etag = changed.headers()["etag"].to_str().unwrap().to_string();
+    assert_ne!(new_etag, etag);
+
+    // 업데이트 시각이 그대로인 변경(플레이어 / Parse 저장)은 리비전으로 반영
+    state.bump_listings_revision();
+    let revised = warp::test::request().path("/api/listings").header("if-none-match", &new_etag).reply(&routes).await;
+    assert_eq!(revised.status(), StatusCode::OK);
+}
+
+/// 상세 정보 업로드는 업데이트 시각을 바꾸지 않으므로 캐시를 비워야 새 멤버가 보임
+#[tokio::test]
+async fn detail_upload_refreshes_cached_listings() {
+    let state = state().await;
+    let routes = router(Arc::clone(&state));
+    let builder = || ListingBuilder::new(1).job(19).job(24);
+    serve(&state, vec![builder().build()]).await;
+
+    let first = warp::test::request().path("/api/listings").reply(&routes).await;
+    let etag = first.headers()["etag"].to_str().unwrap().to_string();
+
+    // `contribute_detail_handler`가 멤버를 저장한 뒤, 다음 조회는 DB에서 새 멤버를 읽음
+    record_detail_stored(&state, 1);
+    let detailed = builder().member_ids([1001, 1002]).build();
+    state.listings_cache.get(|| async { Ok(vec![detailed]) }).await.unwrap();
+
+    let response = warp::test::request().path("/api/listings").header("if-none-match", &etag).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    let members: Vec<&serde_json::Value> = body[0]["listing"]["members"].as_array().unwrap().iter().map(|m| &m["content_id"]).collect();
+    assert_eq!(members, [1001, 1002]);
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use futures_util::StreamExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use warp::Filter;
+
+use crate::ffxiv::Language;
+use crate::listing::PartyFinderListing;
+use crate::template::listings::{render_row, unavailable_row, ListingFragmentTemplate, ListingsTemplate};
+use crate::web::handlers::{build_renderable_listings, streamed_html};
+
+fn template(count: u32) -> ListingsTemplate {
+    let containers = (1..=count)
+        .map(|id| {
+            let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+            listing.id = id;
+            super::queried(listing)
+        })
+        .collect();
+
+    ListingsTemplate {
+        containers: build_renderable_listings(containers, &HashMap::new(), &HashMap::new()),
+        lang: Language::English,
+        notice: None,
+    }
+}
+
+async fn collect(template: ListingsTemplate, per_chunk: usize) -> Vec<String> {
+    template
+        .render_chunks(per_chunk)
+        .map(|chunk| chunk.unwrap())
+        .collect()
+        .await
+}
+
+/// 응답을 보내는 서버에 HTTP/1.1 요청을 보내고 원본 응답 바이트를 읽음
+async fn raw_response(reply: impl Fn() -> warp::reply::Response + Clone + Send + Sync + 'static) -> String {
+    let (addr, server) = warp::serve(warp::any().map(reply)).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"GET /listings HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut raw = Vec::new();
+    let _ = stream.read_to_end(&mut raw).await;
+    String::from_utf8_lossy(&raw).into_owned()
+}
+
+#[tokio::test]
+async fn streamed_render_matches_full_render() {
+    for count in [0, 1, 7] {
+        let expected = template(count).render().unwrap();
+        let chunks = collect(template(count), 3).await;
+
+        assert_eq!(chunks.concat(), expected, "{} listings", count);
+        // 앞부분 + 모집글 3개씩 + 뒷부분
+        assert_eq!(chunks.len(), 2 + (count as usize).div_ceil(3));
+    }
+}
+
+#[tokio::test]
+async fn empty_page_keeps_no_listings_message() {
+    let page = collect(template(0), 50).await.concat();
+    assert!(page.contains("no-listings"));
+    assert!(!page.contains(crate::template::listings::LISTINGS_PLACEHOLDER));
+}
+
+#[tokio::test]
+async fn listings_page_uses_chunked_transfer_encoding() {
+    let raw = raw_response(|| streamed_html(template(5).render_chunks(2))).await;
+    let (headers, body) = raw.split_once("\r\n\r\n").unwrap();
+    let headers = headers.to_lowercase();
+
+    assert!(headers.starts_with("http/1.1 200"), "{}", headers);
+    assert!(headers.contains("transfer-encoding: chunked"), "{}", headers);
+    assert!(headers.contains("content-type: text/html; charset=utf-8"), "{}", headers);
+    assert!(!headers.contains("content-length"), "{}", headers);
+    // 정상 종료 시 마지막 빈 조각으로 끝남
+    assert!(body.ends_with("0\r\n\r\n"));
+    assert!(body.contains("</html>"));
+}
+
+#[tokio::test]
+async fn render_error_terminates_the_body() {
+    let raw = raw_response(|| {
+        streamed_html(async_stream::stream! {
+            yield Ok("<!doctype html>".to_string());
+            // 앞 조각이 전송될 시간
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            yield Err(askama::Error::Custom("broken".into()));
+            yield Ok("never sent".to_string());
+        })
+    })
+    .await;
+
+    assert!(raw.contains("transfer-encoding: chunked"), "{}", raw);
+    assert!(raw.contains("<!doctype html>"), "{}", raw);
+    assert!(!raw.contains("never sent"), "{}", raw);
+    // 끝 조각 없이 연결이 끊김
+    assert!(!raw.ends_with("0\r\n\r\n"), "{}", raw);
+}
+
+/// 모집글 하나의 렌더링이 패닉해도 나머지 목록은 그대로 표시
+#[tokio::test]
+async fn failing_listing_is_replaced_with_placeholder() {
+    let page: String = template(3)
+        .render_chunks_with(2, |renderable, lang, out| {
+            if renderable.container.listing.id == 2 {
+                panic!("malformed listing");
+            }
+            ListingFragmentTemplate::new(renderable, lang).render_into(out)
+        })
+        .map(|chunk| chunk.unwrap())
+        .collect::<Vec<_>>()
+        .await
+        .concat();
+
+    assert_eq!(page.matches(r#"class="listing unavailable""#).count(), 1);
+    assert!(page.contains(&unavailable_row(2)));
+    assert!(page.contains(r#"data-id="1""#));
+    assert!(page.contains(r#"data-id="3""#));
+    assert!(page.contains("</html>"));
+}
+
+/// 렌더링 에러가 난 행은 일부 출력을 남기지 않음
+#[test]
+fn render_error_discards_partial_row() {
+    let renderable = template(1).containers.remove(0);
+    let mut out = String::new();
+
+    render_row(&renderable, &mut out, |row| {
+        row.push_str("<div class=\"listing\"");
+        Err(askama::Error::Custom("broken".into()))
+    });
+
+    assert_eq!(out, unavailable_row(1));
+}
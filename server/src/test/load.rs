@@ -0,0 +1,357 @@
+//! 모집글 파이프라인 부하 테스트 / 성능 회귀 테스트
+//!
+//! 합성 데이터(모집글 N개, 플레이어 M명, Parse 문서)를 메모리에 생성한 뒤
+//! `/listings` 페이지와 `/api/listings`의 변환 경로를 DB 없이 그대로 실행하고
+//! 단계별 소요 시간과 할당 횟수를 측정합니다.
+//!
+//! 상세 리포트 (기본값: 모집글 5000개, 플레이어 20000명):
+//!
+//! ```text
+//! RPF_LOAD_LISTINGS=20000 RPF_LOAD_PLAYERS=60000 \
+//!     cargo test --release load_report -- --ignored --nocapture
+//! ```
+//!
+//! 나머지 테스트는 일반 `cargo test`에 포함되며, 넉넉한 기준으로
+//! 멤버 매칭이 이차 시간이 되거나 모집글마다 DB를 조회하는 회귀를 잡아냅니다.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use askama::Template;
+use chrono::{FixedOffset, TimeDelta, Utc};
+
+use crate::api::{build_api_listings, zone_requests};
+use crate::fflogs::mapping::DUTY_TO_FFLOGS;
+use crate::fflogs::{EncounterParse, ParseCacheDoc, ZoneCache};
+use crate::ffxiv::Language;
+use crate::listing::{DutyCategory, DutyType, JobFlags, PartyFinderListing, PartyFinderSlot};
+use crate::listing_container::QueriedListing;
+use crate::player::Player;
+use crate::template::listings::ListingsTemplate;
+use crate::web::handlers::{build_renderable_listings, collect_content_ids};
+
+// =============================================================================
+// 할당 횟수 측정
+// =============================================================================
+
+/// 스레드별 할당 횟수를 세는 할당자 (테스트 바이너리 전용)
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> u64 {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// 단계별 측정 결과
+#[derive(Debug)]
+struct Phase {
+    name: &'static str,
+    elapsed: Duration,
+    allocations: u64,
+}
+
+fn measure<T>(phases: &mut Vec<Phase>, name: &'static str, f: impl FnOnce() -> T) -> T {
+    let allocs_before = allocations();
+    let start = Instant::now();
+    let result = f();
+    phases.push(Phase {
+        name,
+        elapsed: start.elapsed(),
+        allocations: allocations() - allocs_before,
+    });
+    result
+}
+
+// =============================================================================
+// 합성 데이터 생성
+// =============================================================================
+
+/// 재현 가능한 데이터를 위한 xorshift64* 난수 생성기
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LoadConfig {
+    listings: usize,
+    players: usize,
+    seed: u64,
+}
+
+/// 메모리 저장소 (DB 조회 결과에 해당)
+struct Dataset {
+    containers: Vec<QueriedListing>,
+    players: HashMap<u64, Player>,
+    /// `/listings`, `/api/listings` 공통: ContentID → Parse 문서
+    parse_docs: HashMap<u64, ParseCacheDoc>,
+}
+
+/// 일반 던전 / 24인 레이드 / 고난도 듀티 ID 후보
+struct DutyPool {
+    dungeons: Vec<u16>,
+    alliance_raids: Vec<u16>,
+    high_end: Vec<u16>,
+    job_ids: Vec<u8>,
+}
+
+impl DutyPool {
+    fn new() -> Self {
+        let mut duties: Vec<(u32, &crate::ffxiv::duties::DutyInfo)> = crate::ffxiv::DUTIES
+            .iter()
+            .map(|(id, info)| (*id, info))
+            .collect();
+        duties.sort_by_key(|(id, _)| *id);
+
+        let by_kind = |kind: u32| -> Vec<u16> {
+            duties.iter()
+                .filter(|(_, info)| !info.high_end && info.content_kind.as_u32() == kind)
+                .map(|(id, _)| *id as u16)
+                .collect()
+        };
+
+        let mut high_end: Vec<u16> = DUTY_TO_FFLOGS.keys()
+            .copied()
+            .filter(|id| crate::ffxiv::duty(u32::from(*id)).map(|info| info.high_end).unwrap_or_default())
+            .collect();
+        high_end.sort_unstable();
+
+        let mut job_ids: Vec<u8> = crate::ffxiv::JOBS.keys().map(|id| *id as u8).collect();
+        job_ids.sort_unstable();
+
+        Self {
+            dungeons: by_kind(2),
+            alliance_raids: by_kind(5),
+            high_end,
+            job_ids,
+        }
+    }
+}
+
+/// 실제 분포와 비슷한 모집글 생성
+///
+/// 대부분 던전, 일부 24인 레이드와 듀티 없는 모브 헌트, 소수의 고난도 듀티(Parse 데이터 포함)
+fn generate(config: LoadConfig) -> Dataset {
+    let pool = DutyPool::new();
+    let mut rng = Rng(config.seed | 1);
+    let now = Utc::now();
+
+    let players: HashMap<u64, Player> = (1..=config.players as u64)
+        .map(|cid| (cid, Player {
+            content_id: cid,
+            name: format!("Player {}", cid),
+            home_world: 73,
+            last_seen: now,
+            seen_count: 1,
+        }))
+        .collect();
+
+    let mut containers = Vec::with_capacity(config.listings);
+    let mut parse_docs: HashMap<u64, ParseCacheDoc> = HashMap::new();
+
+    for i in 0..config.listings {
+        let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+        listing.id = i as u32;
+
+        let roll = rng.below(100);
+        let (duty_type, category, duty, num_parties) = match roll {
+            0..=69 if !pool.dungeons.is_empty() => (DutyType::Normal, DutyCategory::Dungeon, rng.pick(&pool.dungeons), 1),
+            70..=79 if !pool.alliance_raids.is_empty() => (DutyType::Normal, DutyCategory::Raid, rng.pick(&pool.alliance_raids), 3),
+            90..=99 if !pool.high_end.is_empty() => (DutyType::Normal, DutyCategory::HighEndDuty, rng.pick(&pool.high_end), 1),
+            _ => (DutyType::Other, DutyCategory::TheHunt, 0, 1),
+        };
+        let slots = if category == DutyCategory::Dungeon { 4 } else { 8 * num_parties as usize };
+
+        listing.duty_type = duty_type;
+        listing.category = category;
+        listing.duty = duty;
+        listing.num_parties = num_parties;
+        listing.slots_available = (slots / num_parties as usize) as u8;
+        listing.slots = (0..slots).map(|_| PartyFinderSlot { accepting: JobFlags::all() }).collect();
+
+        let filled = 1 + rng.below(slots as u64) as usize;
+        listing.jobs_present = (0..slots)
+            .map(|slot| if slot < filled { rng.pick(&pool.job_ids) } else { 0 })
+            .collect();
+        listing.member_content_ids = (0..slots)
+            .map(|slot| if slot < filled { 1 + rng.below(config.players as u64) as i64 } else { 0 })
+            .collect();
+        listing.leader_content_id = listing.member_content_ids[0] as u64;
+
+        if let Some(info) = DUTY_TO_FFLOGS.get(&duty).filter(|_| category == DutyCategory::HighEndDuty) {
+            for &cid in listing.member_content_ids.iter().filter(|&&cid| cid != 0) {
+                let zone = ZoneCache {
+                    fetched_at: now,
+                    encounters: maplit::hashmap! {
+                        info.encounter_id.to_string() => EncounterParse {
+                            percentile: rng.below(10_000) as f32 / 100.0,
+                            job_id: 0,
+                            all_stars: None,
+                            jobs: Default::default(),
+                        },
+                    },
+                };
+                parse_docs
+                    .entry(cid as u64)
+                    .or_insert_with(|| ParseCacheDoc { content_id: cid, zones: HashMap::new(), fetch: None })
+                    .zones
+                    .insert(info.zone_id.to_string(), zone);
+            }
+        }
+
+        let updated_at = now - TimeDelta::try_seconds(rng.below(3600) as i64).unwrap();
+        containers.push(QueriedListing {
+            created_at: updated_at,
+            updated_at,
+            updated_minute: updated_at,
+            time_left: rng.below(3600) as f64,
+            upload_count: 1 + rng.below(3) as u32,
+            ..super::queried(listing)
+        });
+    }
+
+    Dataset { containers, players, parse_docs }
+}
+
+// =============================================================================
+// 파이프라인 실행
+// =============================================================================
+
+/// 두 변환 경로를 실행하고 단계별 측정 결과 반환 (DB 조회 단계 제외)
+fn run_pipeline(config: LoadConfig) -> Vec<Phase> {
+    let mut phases = Vec::new();
+
+    // `/listings`
+    let dataset = generate(config);
+    let content_ids = measure(&mut phases, "html: collect ids", || collect_content_ids(&dataset.containers));
+    assert!(content_ids.len() <= config.players);
+    let renderable = measure(&mut phases, "html: match members", || {
+        build_renderable_listings(dataset.containers, &dataset.players, &dataset.parse_docs)
+    });
+    let html = measure(&mut phases, "html: render", || {
+        ListingsTemplate { containers: renderable, lang: Language::English, notice: None }.render().unwrap()
+    });
+    assert!(!html.is_empty());
+
+    // `/api/listings`
+    let dataset = generate(config);
+    measure(&mut phases, "api: zone requests", || zone_requests(&dataset.containers));
+    let api = measure(&mut phases, "api: match members", || {
+        build_api_listings(dataset.containers, &dataset.players, &dataset.parse_docs, FixedOffset::east_opt(0).unwrap())
+    });
+    let json = measure(&mut phases, "api: serialize", || serde_json::to_vec(&api).unwrap());
+    assert!(!json.is_empty());
+
+    phases
+}
+
+fn env_or(name: &str, default: usize) -> usize {
+    std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+#[test]
+#[ignore = "load report; run with --ignored --nocapture"]
+fn load_report() {
+    let config = LoadConfig {
+        listings: env_or("RPF_LOAD_LISTINGS", 5000),
+        players: env_or("RPF_LOAD_PLAYERS", 20000),
+        seed: env_or("RPF_LOAD_SEED", 1) as u64,
+    };
+
+    println!("{:?}", config);
+    println!("{:<22} {:>12} {:>14}", "phase", "time", "allocations");
+    for phase in run_pipeline(config) {
+        println!("{:<22} {:>12.2?} {:>14}", phase.name, phase.elapsed, phase.allocations);
+    }
+}
+
+#[test]
+fn generated_shape_is_realistic() {
+    let dataset = generate(LoadConfig { listings: 2000, players: 8000, seed: 7 });
+    let count = |category: DutyCategory| {
+        dataset.containers.iter().filter(|c| c.listing.category == category).count()
+    };
+
+    assert!(count(DutyCategory::Dungeon) > 1200);
+    assert!(count(DutyCategory::Raid) > 100);
+    assert!(count(DutyCategory::HighEndDuty) > 100);
+    assert!(!dataset.parse_docs.is_empty());
+}
+
+#[test]
+fn member_matching_scales_linearly() {
+    let small = run_pipeline(LoadConfig { listings: 250, players: 1000, seed: 3 });
+    let large = run_pipeline(LoadConfig { listings: 1000, players: 4000, seed: 3 });
+
+    // 4배 데이터 → 선형이면 약 4배, 이차면 약 16배
+    for (s, l) in small.iter().zip(&large) {
+        assert!(
+            l.allocations <= s.allocations.max(1) * 8,
+            "{}: {} allocations for 4x data vs {}",
+            l.name, l.allocations, s.allocations,
+        );
+    }
+}
+
+#[test]
+fn pipeline_fits_time_budget() {
+    let phases = run_pipeline(LoadConfig { listings: 3000, players: 12000, seed: 5 });
+    let total: Duration = phases.iter().map(|phase| phase.elapsed).sum();
+
+    // 디버그 빌드 기준으로도 넉넉한 예산
+    assert!(total < Duration::from_secs(10), "pipeline took {:?}: {:?}", total, phases);
+}
+
+#[test]
+fn parse_lookups_are_batched_per_zone() {
+    let small = generate(LoadConfig { listings: 200, players: 800, seed: 9 });
+    let large = generate(LoadConfig { listings: 2000, players: 8000, seed: 9 });
+
+    // DB 조회 수는 Zone 수에만 비례하고 모집글 수와 무관해야 함
+    let zones = crate::fflogs::mapping::FFLOGS_ZONES.len().max(
+        DUTY_TO_FFLOGS.values().map(|info| info.zone_id).collect::<std::collections::HashSet<_>>().len(),
+    );
+    assert!(zone_requests(&small.containers).len() <= zones);
+    assert!(zone_requests(&large.containers).len() <= zones);
+
+    let ids = collect_content_ids(&large.containers);
+    assert!(ids.windows(2).all(|w| w[0] < w[1]));
+}
@@ -0,0 +1,133 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+
+use crate::config::{Config, LogFormat, Logging};
+use crate::logging::{directives, subscriber};
+
+/// 기록된 로그를 모으는 writer
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Capture {
+    fn writer(&self) -> BoxMakeWriter {
+        let capture = self.clone();
+        BoxMakeWriter::new(move || capture.clone())
+    }
+
+    fn lines(&self) -> Vec<String> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+fn logging(format: LogFormat, level: &str) -> Logging {
+    Logging {
+        format,
+        level: level.to_string(),
+        file: false,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn logging_section_is_parsed() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://localhost"
+
+        [logging]
+        format = "json"
+        directives = "remote_party_finder::infra::fflogs=debug, warp=warn"
+        file = false
+        "#,
+    )
+    .unwrap();
+
+    let logging = config.logging;
+    assert_eq!(logging.format, LogFormat::Json);
+    assert_eq!(logging.level, "info");
+    assert!(!logging.file);
+    assert_eq!(
+        directives(&logging, Some("remote_party_finder::web=trace")),
+        "info,remote_party_finder::infra::fflogs=debug,warp=warn,remote_party_finder::web=trace"
+    );
+
+    // 섹션이 없으면 기존 동작 (사람이 읽는 형식, INFO, logs/ 파일)
+    let logging = Logging::default();
+    assert_eq!(logging.format, LogFormat::Pretty);
+    assert!(logging.file);
+    assert_eq!(directives(&logging, None), "info");
+
+    assert!(toml::from_str::<Logging>(r#"format = "xml""#).is_err());
+}
+
+#[test]
+fn reload_handle_changes_levels() {
+    let capture = Capture::default();
+    let (subscriber, handle) = subscriber(&logging(LogFormat::Pretty, "info"), capture.writer()).unwrap();
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::debug!(target: "rpf_test::fflogs", "hidden");
+
+        handle.set_directives("info,rpf_test::fflogs=debug").unwrap();
+        assert!(handle.directives().contains("rpf_test::fflogs=debug"));
+        tracing::debug!(target: "rpf_test::fflogs", "visible");
+        tracing::debug!(target: "rpf_test::web", "still hidden");
+
+        // 잘못된 지시어는 거부하고 기존 필터 유지
+        assert!(handle.set_directives("rpf_test=loud").is_err());
+        tracing::debug!(target: "rpf_test::fflogs", "kept");
+    });
+
+    let lines = capture.lines();
+    assert_eq!(lines.len(), 2, "{:?}", lines);
+    assert!(lines[0].contains("visible"));
+    assert!(lines[1].contains("kept"));
+}
+
+#[test]
+fn json_lines_are_valid_json_with_request_id() {
+    let capture = Capture::default();
+    let (subscriber, _handle) = subscriber(&logging(LogFormat::Json, "info"), capture.writer()).unwrap();
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(listings = 3, "outside a request");
+        let span = tracing::info_span!("request", request_id = "req-42");
+        let _entered = span.enter();
+        tracing::warn!("inside \"quoted\" request");
+    });
+
+    let lines: Vec<serde_json::Value> = capture
+        .lines()
+        .iter()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+
+    assert_eq!(lines[0]["level"], "INFO");
+    assert_eq!(lines[0]["fields"]["listings"], 3);
+    assert!(lines[0].get("span").is_none());
+
+    assert_eq!(lines[1]["fields"]["message"], "inside \"quoted\" request");
+    assert_eq!(lines[1]["span"]["request_id"], "req-42");
+}
@@ -0,0 +1,122 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::config::Maintenance;
+use crate::web::maintenance::{
+    AutoDetector, BackgroundTask, MaintenanceOverride, MaintenanceSchedule, MaintenanceState, PauseReason,
+    AUTO_DETECT_AFTER,
+};
+
+/// 2026-01-06 = 화요일
+fn utc(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, day, hour, minute, 0).unwrap()
+}
+
+fn config(toml: &str) -> Maintenance {
+    toml::from_str(toml).unwrap()
+}
+
+#[test]
+fn weekly_window_uses_configured_timezone() {
+    // 화요일 17:00 JST부터 3시간 = 08:00 ~ 11:00 UTC
+    let schedule = MaintenanceSchedule::new(config(
+        r#"
+        timezone = "+09:00"
+        weekly = [{ day = "Tue", start = "17:00:00", minutes = 180 }]
+        "#,
+    ));
+
+    assert_eq!(schedule.window_end(utc(6, 7, 59)), None);
+    assert_eq!(schedule.window_end(utc(6, 8, 0)), Some(utc(6, 11, 0)));
+    assert_eq!(schedule.window_end(utc(6, 10, 59)), Some(utc(6, 11, 0)));
+    assert_eq!(schedule.window_end(utc(6, 11, 0)), None);
+    // UTC로는 같은 화요일이지만 JST로는 수요일
+    assert_eq!(schedule.window_end(utc(6, 16, 0)), None);
+    // 다음 주 같은 시각
+    assert_eq!(schedule.window_end(utc(13, 9, 0)), Some(utc(13, 11, 0)));
+}
+
+#[test]
+fn weekly_window_crosses_local_midnight_and_utc_date() {
+    // 월요일 22:00 (UTC-5)부터 4시간 = 화요일 03:00 ~ 07:00 UTC
+    let schedule = MaintenanceSchedule::new(config(
+        r#"
+        timezone = "-05:00"
+        weekly = [{ day = "Mon", start = "22:00:00", minutes = 240 }]
+        "#,
+    ));
+
+    assert_eq!(schedule.window_end(utc(6, 2, 59)), None);
+    assert_eq!(schedule.window_end(utc(6, 3, 0)), Some(utc(6, 7, 0)));
+    // 현지 시각으로는 이미 화요일 01:30
+    assert_eq!(schedule.window_end(utc(6, 6, 30)), Some(utc(6, 7, 0)));
+    assert_eq!(schedule.window_end(utc(6, 7, 0)), None);
+}
+
+#[test]
+fn ad_hoc_ranges_use_configured_timezone() {
+    let schedule = MaintenanceSchedule::new(config(
+        r#"
+        timezone = "+09:00"
+        ranges = [{ start = "2026-01-08T00:00:00", end = "2026-01-08T12:00:00" }]
+        "#,
+    ));
+
+    assert_eq!(schedule.window_end(utc(7, 14, 59)), None);
+    assert_eq!(schedule.window_end(utc(7, 15, 0)), Some(utc(8, 3, 0)));
+    assert_eq!(schedule.window_end(utc(8, 3, 0)), None);
+}
+
+#[test]
+fn auto_detect_has_hysteresis() {
+    let mut detector = AutoDetector::default();
+    let start = Instant::now();
+    let at = |secs: u64| start + Duration::from_secs(secs);
+
+    assert!(!detector.observe(12, at(0)));
+    assert!(!detector.observe(0, at(60)));
+    assert!(!detector.observe(0, at(60) + AUTO_DETECT_AFTER - Duration::from_secs(1)));
+    assert!(detector.observe(0, at(60) + AUTO_DETECT_AFTER));
+    assert!(detector.observe(0, at(3600)));
+
+    // 모집글이 다시 보이면 즉시 해제, 다시 0이 되면 처음부터 계산
+    assert!(!detector.observe(1, at(3660)));
+    assert!(!detector.observe(0, at(3720)));
+    assert!(!detector.observe(0, at(3720) + AUTO_DETECT_AFTER - Duration::from_secs(1)));
+    assert!(detector.observe(0, at(3720) + AUTO_DETECT_AFTER));
+}
+
+#[test]
+fn manual_override_takes_precedence() {
+    let state = MaintenanceState::new(config(
+        r#"
+        weekly = [{ day = "Tue", start = "08:00:00", minutes = 60 }]
+        "#,
+    ));
+    let in_window = utc(6, 8, 30);
+    let outside = utc(6, 12, 0);
+
+    assert_eq!(state.pause_reason(in_window), Some(PauseReason::Scheduled));
+    assert_eq!(state.pause_reason(outside), None);
+
+    let start = Instant::now();
+    state.observe_active_listings(0, start);
+    state.observe_active_listings(0, start + AUTO_DETECT_AFTER);
+    assert_eq!(state.pause_reason(outside), Some(PauseReason::AutoDetected));
+
+    state.set_mode(MaintenanceOverride::Running);
+    assert_eq!(state.pause_reason(in_window), None);
+    assert_eq!(state.pause_reason(outside), None);
+    assert!(!state.should_skip(BackgroundTask::FFLogs));
+
+    state.set_mode(MaintenanceOverride::Paused);
+    assert_eq!(state.pause_reason(outside), Some(PauseReason::Manual));
+    assert!(state.should_skip(BackgroundTask::FFLogs));
+    assert!(state.should_skip(BackgroundTask::Stats));
+
+    let status = state.status(outside);
+    assert!(status.paused);
+    assert_eq!(status.skipped_fflogs_cycles, 1);
+    assert_eq!(status.skipped_stats_cycles, 1);
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use chrono::{FixedOffset, Utc};
+
+use crate::api::build_api_listings;
+use crate::ffxiv::Language;
+use crate::listing::PartyFinderListing;
+use crate::listing_container::QueriedListing;
+use crate::player::Player;
+use crate::template::listings::{ListingsTemplate, ParseDisplay, RenderableListing, RenderableMember};
+
+/// 모집글 생성 서버는 73 = Adamantoise (Aether)
+/// 73 = 같은 서버, 79 = Cactuar (Aether), 49 = Kujata (Elemental), 0 = 아직 모름
+const MEMBERS: [(u64, &str, u16); 4] = [
+    (1, "Same World", 73),
+    (2, "Same Dc", 79),
+    (3, "Other Dc", 49),
+    (4, "No World", 0),
+];
+
+fn player(content_id: u64, name: &str, home_world: u16) -> Player {
+    Player {
+        content_id,
+        name: name.to_string(),
+        home_world,
+        last_seen: Utc::now(),
+        seen_count: 1,
+    }
+}
+
+fn queried() -> QueriedListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.slots_available = listing.slots.len() as u8;
+    listing.member_content_ids = MEMBERS.iter().map(|&(id, _, _)| id as i64).collect();
+    // 잡이 있는 자리만 멤버로 표시 (PLD, WHM, MNK, DRG)
+    listing.jobs_present = vec![19, 24, 20, 22];
+
+    super::queried(listing)
+}
+
+#[test]
+fn member_world_flags() {
+    let listing = queried().listing;
+    let flags: Vec<_> = MEMBERS
+        .iter()
+        .map(|&(id, name, world)| {
+            let member = RenderableMember {
+                job_id: 19,
+                player: player(id, name, world),
+                parse: ParseDisplay::none(),
+            };
+            (member.home_world_name(), member.is_cross_world(&listing), member.is_cross_dc(&listing))
+        })
+        .collect();
+
+    assert_eq!(flags[0], (Some("Adamantoise"), false, false));
+    assert_eq!(flags[1], (Some("Cactuar"), true, false));
+    assert_eq!(flags[2], (Some("Kujata"), true, true));
+    assert_eq!(flags[3], (None, false, false));
+}
+
+#[test]
+fn template_renders_world_tags_and_cross_dc_icon() {
+    let template = ListingsTemplate {
+        containers: vec![RenderableListing {
+            container: queried(),
+            members: MEMBERS
+                .iter()
+                .map(|&(id, name, world)| RenderableMember {
+                    job_id: 19,
+                    player: player(id, name, world),
+                    parse: ParseDisplay::none(),
+                })
+                .collect(),
+            leader_parse: ParseDisplay::none(),
+            watched: false,
+            data_inconsistent: false,
+        }],
+        lang: Language::English,
+        notice: None,
+    };
+
+    let html = template.render().unwrap();
+
+    assert!(html.contains(r#"<small class="world">@ Adamantoise</small>"#));
+    assert!(html.contains(r#"<small class="world cross-world">@ Cactuar</small>"#));
+    assert!(html.contains(r#"<small class="world cross-world">@ Kujata</small>"#));
+    assert_eq!(html.matches("icons.svg#plane").count(), 1);
+    // 홈 서버를 모르는 멤버는 태그 없이 이름만 표시
+    assert!(!html.contains("Unknown"));
+    assert!(!html.contains("@ 0"));
+}
+
+#[test]
+fn api_members_have_world_flags() {
+    let players: HashMap<u64, Player> = MEMBERS
+        .iter()
+        .map(|&(id, name, world)| (id, player(id, name, world)))
+        .collect();
+
+    let listings = build_api_listings(vec![queried()], &players, &HashMap::new(), FixedOffset::east_opt(0).unwrap());
+    let json = serde_json::to_value(&listings).unwrap();
+    let members = json[0]["listing"]["members"].as_array().unwrap();
+
+    let flags: Vec<_> = members
+        .iter()
+        .map(|m| (m["name"].as_str().unwrap(), m["cross_world"].as_bool().unwrap(), m["cross_dc"].as_bool().unwrap()))
+        .collect();
+    assert_eq!(
+        flags,
+        [
+            ("Same World", false, false),
+            ("Same Dc", true, false),
+            ("Other Dc", true, true),
+            ("No World", false, false),
+        ]
+    );
+}
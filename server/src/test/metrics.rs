@@ -0,0 +1,125 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use crate::config::{Config, Logging};
+use crate::web::metrics::{metrics, route_label, ContributeKind, ContributeOutcome, FFLogsCycle, Metrics, OTHER_ROUTE};
+use crate::web::routes::router;
+use crate::web::State;
+use crate::ws::limits::ConnectionStats;
+
+async fn state(metrics_host: Option<&str>) -> Arc<State> {
+    let metrics_host = metrics_host.map(|host| format!("metrics_host = \"{}\"", host)).unwrap_or_default();
+    let config: Config = toml::from_str(&format!(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+        {}
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+        metrics_host
+    ))
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    State::new(Arc::new(config), log_handle).await.unwrap()
+}
+
+#[test]
+fn paths_map_to_bounded_route_labels() {
+    assert_eq!(route_label("/"), "/");
+    assert_eq!(route_label("/listings/"), "/listings");
+    assert_eq!(route_label("/api/listings"), "/api/listings");
+    assert_eq!(route_label("/api/listings/12345"), "/api/listings/{id}");
+    assert_eq!(route_label("/stats/7days/duty/1010"), "/stats/7days/duty/{id}");
+    assert_eq!(route_label("/api/bookmarks/some-token"), "/api/bookmarks/{name}");
+    assert_eq!(route_label("/assets/job/PLD.svg"), "/assets/*");
+    assert_eq!(route_label("/api/admin/listings/1/2/3/raw"), "/api/admin/*");
+
+    for path in ["/api/listings/abc", "/api/listings/1/2", "/wp-login.php", "/listingsx"] {
+        assert_eq!(route_label(path), OTHER_ROUTE, "{}", path);
+    }
+}
+
+#[test]
+fn render_uses_prometheus_text_format() {
+    let metrics = Metrics::default();
+    metrics.record_request("/api/listings", 200, Duration::from_millis(20));
+    metrics.record_request("/api/listings", 200, Duration::from_millis(300));
+    metrics.record_request("/api/listings/7", 404, Duration::from_millis(1));
+    metrics.record_contribution(ContributeKind::Listing, ContributeOutcome::Accepted, 3);
+    metrics.record_contribution(ContributeKind::Listing, ContributeOutcome::Rejected, 1);
+    metrics.record_mongo_error("insert_listing");
+    metrics.record_fflogs_cycle(FFLogsCycle { batches: 4, batch_errors: 1, parses_saved: 30, cache_hits: 12 });
+    metrics.record_fflogs_cycle(FFLogsCycle { batches: 2, batch_errors: 0, parses_saved: 10, cache_hits: 5 });
+    metrics.record_stats_run(true, Duration::from_millis(1500));
+
+    let websockets = ConnectionStats { anonymous: 5, priority: 1, timed_out: 2, ..Default::default() };
+    let text = metrics.render(&websockets);
+    let lines: Vec<&str> = text.lines().collect();
+    for expected in [
+        "# TYPE rpf_http_requests_total counter",
+        r#"rpf_http_requests_total{route="/api/listings",status="200"} 2"#,
+        r#"rpf_http_requests_total{route="/api/listings/{id}",status="404"} 1"#,
+        "# TYPE rpf_http_request_duration_seconds histogram",
+        r#"rpf_http_request_duration_seconds_bucket{route="/api/listings",le="0.01"} 0"#,
+        r#"rpf_http_request_duration_seconds_bucket{route="/api/listings",le="0.025"} 1"#,
+        r#"rpf_http_request_duration_seconds_bucket{route="/api/listings",le="0.5"} 2"#,
+        r#"rpf_http_request_duration_seconds_bucket{route="/api/listings",le="+Inf"} 2"#,
+        r#"rpf_http_request_duration_seconds_count{route="/api/listings"} 2"#,
+        r#"rpf_contributions_total{kind="listing",outcome="accepted"} 3"#,
+        r#"rpf_contributions_total{kind="listing",outcome="rejected"} 1"#,
+        r#"rpf_mongo_errors_total{operation="insert_listing"} 1"#,
+        r#"rpf_websocket_clients{tier="anonymous"} 5"#,
+        r#"rpf_websocket_clients{tier="priority"} 1"#,
+        "rpf_websocket_ping_timeouts_total 2",
+        "rpf_fflogs_cycles_total 2",
+        "rpf_fflogs_batches_total 6",
+        "rpf_fflogs_parses_saved_total 40",
+        "rpf_fflogs_last_cycle_parses_saved 10",
+        "rpf_fflogs_last_cycle_cache_hits 5",
+        r#"rpf_stats_runs_total{result="ok"} 1"#,
+        "rpf_stats_duration_seconds 1.5",
+    ] {
+        assert!(lines.contains(&expected), "missing `{}` in\n{}", expected, text);
+    }
+    // 히스토그램은 지정한 라우트만
+    assert!(!text.contains(r#"rpf_http_request_duration_seconds_count{route="/api/listings/{id}"}"#));
+}
+
+#[tokio::test]
+async fn router_counts_requests_and_serves_metrics() {
+    let routes = router(state(None).await);
+
+    let response = warp::test::request().path("/api/version").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let missing = warp::test::request().path("/no/such/page").reply(&routes).await.status();
+    assert!(missing.is_client_error());
+
+    let response = warp::test::request().path("/metrics").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/plain"));
+    let body = String::from_utf8(response.body().to_vec()).unwrap();
+    assert!(body.contains(r#"rpf_http_requests_total{route="/api/version",status="200"} 1"#), "{}", body);
+    let other = format!(r#"rpf_http_requests_total{{route="other",status="{}"}} 1"#, missing.as_u16());
+    assert!(body.contains(&other), "{}", body);
+}
+
+#[tokio::test]
+async fn separate_metrics_host_hides_public_endpoint() {
+    let state = state(Some("127.0.0.1:9100")).await;
+    let public = router(Arc::clone(&state));
+    let hidden = warp::test::request().path("/metrics").reply(&public).await.status();
+    assert!(hidden.is_client_error());
+
+    let private = metrics(state);
+    let response = warp::test::request().path("/metrics").reply(&private).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = String::from_utf8(response.body().to_vec()).unwrap();
+    let public_request = format!(r#"rpf_http_requests_total{{route="/metrics",status="{}"}} 1"#, hidden.as_u16());
+    assert!(body.contains(&public_request), "{}", body);
+}
@@ -0,0 +1,72 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+
+use crate::player::Player;
+use crate::web::missing_players::{MissingPlayerStats, MissingPlayers};
+
+fn player(content_id: u64) -> Player {
+    Player {
+        content_id,
+        name: format!("Player {}", content_id),
+        home_world: 73,
+        last_seen: Utc::now(),
+        seen_count: 1,
+    }
+}
+
+/// 1, 2번만 DB에 있는 플레이어
+async fn lookup(cache: &MissingPlayers, ids: &[u64], queries: &Mutex<Vec<Vec<u64>>>) -> Vec<u64> {
+    let players = cache
+        .lookup(ids, |ids| async move {
+            queries.lock().unwrap().push(ids.clone());
+            Ok(ids.into_iter().filter(|&id| id <= 2).map(player).collect())
+        })
+        .await
+        .unwrap();
+    players.into_iter().map(|p| p.content_id).collect()
+}
+
+#[tokio::test]
+async fn unknown_ids_are_skipped_until_uploaded() {
+    let cache = MissingPlayers::default();
+    let queries = Mutex::new(Vec::new());
+
+    assert_eq!(lookup(&cache, &[1, 2, 3, 4], &queries).await, [1, 2]);
+    assert_eq!(lookup(&cache, &[1, 2, 3, 4], &queries).await, [1, 2]);
+    // 모두 없는 플레이어면 DB 조회 자체를 건너뜀
+    assert!(lookup(&cache, &[3, 4], &queries).await.is_empty());
+    assert_eq!(*queries.lock().unwrap(), [vec![1, 2, 3, 4], vec![1, 2]]);
+
+    assert_eq!(
+        cache.stats(),
+        MissingPlayerStats {
+            entries: 2,
+            hits: 4,
+            misses: 6,
+        }
+    );
+
+    // 3번이 업로드되면 다음 렌더링에서 바로 조회
+    cache.invalidate([3]);
+    lookup(&cache, &[3, 4], &queries).await;
+    assert_eq!(queries.lock().unwrap().last().unwrap(), &[3]);
+}
+
+#[test]
+fn entries_expire_and_stay_bounded() {
+    let cache = MissingPlayers::new(Duration::from_secs(60), 3);
+    let start = Instant::now();
+
+    cache.record_missing(&[1, 2], &[], start);
+    cache.record_missing(&[3, 4], &[player(4)], start + Duration::from_secs(10));
+    assert_eq!(cache.to_query(&[1, 2, 3, 4], start + Duration::from_secs(30)), [4]);
+    // 1, 2번은 만료
+    assert_eq!(cache.to_query(&[1, 2, 3], start + Duration::from_secs(60)), [1, 2]);
+
+    // 최대 개수를 넘으면 가장 먼저 만료되는 항목(1, 2번)부터 제거
+    cache.record_missing(&[5, 6], &[], start + Duration::from_secs(20));
+    assert_eq!(cache.stats().entries, 3);
+    assert_eq!(cache.to_query(&[1, 2, 3, 5, 6], start + Duration::from_secs(30)), [1, 2]);
+}
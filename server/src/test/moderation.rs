@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use mongodb::bson::{doc, Bson};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use super::mongo_eval::Collection;
+use crate::api::moderation::authorize;
+use crate::config::{Config, Logging, Moderation, ModeratorToken};
+use crate::listing::moderation::{ModerationAction, ModerationRecord, ModerationTarget};
+use crate::listing::{Blocklist, PartyFinderListing};
+use crate::listing_container::{ListingContainer, StoredUpload, UploadOutcome};
+use crate::mongo::{current_listings_pipeline, live_hosted_listings_filter};
+use crate::web::routes::router;
+use crate::web::State;
+
+fn container() -> ListingContainer {
+    ListingContainer {
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        listing: serde_json::from_str(super::LISTING).unwrap(),
+        upload_count: 1,
+        uploader_fingerprints: Vec::new(),
+        description_hash: None,
+        description_text: None,
+        description_history: Vec::new(),
+        description_language: None,
+        has_autotranslate: false,
+        unconfirmed_at: None,
+        canonical_category: None,
+        hidden: false,
+        flagged: false,
+        slots_history: Vec::new(),
+    }
+}
+
+fn moderation() -> Moderation {
+    Moderation {
+        tokens: vec![ModeratorToken { name: "alice".into(), token: "secret".into() }],
+    }
+}
+
+#[test]
+fn only_configured_moderator_tokens_are_accepted() {
+    let moderation = moderation();
+
+    assert_eq!(authorize(Some(&moderation), Some("Bearer secret")), Some("alice"));
+    assert_eq!(authorize(Some(&moderation), Some("Bearer wrong")), None);
+    assert_eq!(authorize(Some(&moderation), Some("secret")), None);
+    assert_eq!(authorize(Some(&moderation), None), None);
+    assert_eq!(authorize(Some(&Moderation::default()), Some("Bearer ")), None);
+    assert_eq!(authorize(None, Some("Bearer secret")), None);
+}
+
+#[test]
+fn public_pipeline_excludes_hidden_listings() {
+    let pipeline = current_listings_pipeline(Utc::now(), Utc::now(), &Blocklist::default());
+    let first = pipeline[0].get_document("$match").unwrap();
+
+    assert_eq!(first.get_document("hidden").unwrap(), &doc! { "$ne": true });
+}
+
+/// 플레이어 모집글 기록도 숨기거나 금칙어로 표시된 모집글의 설명을 내보내지 않음
+#[test]
+fn hosted_listings_exclude_hidden_and_flagged_listings() {
+    let filter = live_hosted_listings_filter(1);
+
+    assert_eq!(filter.get_document("hidden").unwrap(), &doc! { "$ne": true });
+    assert_eq!(filter.get_document("flagged").unwrap(), &doc! { "$ne": true });
+}
+
+#[test]
+fn target_filter_matches_the_full_listing_key() {
+    let target = ModerationTarget { id: 123, created_world: 73, last_server_restart: 0 };
+
+    assert_eq!(
+        target.filter(),
+        doc! { "listing.id": 123_u32, "listing.created_world": 73_u32, "listing.last_server_restart": 0_u32 },
+    );
+
+    let container = container();
+    assert!(target.matches(&container));
+    assert!(!ModerationTarget { created_world: 74, ..target }.matches(&container));
+    assert!(!ModerationTarget { last_server_restart: 1, ..target }.matches(&container));
+    assert!(!ModerationTarget { id: 124, ..target }.matches(&container));
+}
+
+/// 다른 월드에서 같은 ID로 올라온 모집글은 함께 숨기지 않음
+#[test]
+fn hiding_leaves_same_id_listings_on_other_worlds_visible() {
+    let now = Utc::now();
+    let listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    let other_world = PartyFinderListing { created_world: listing.created_world + 1, ..listing.clone() };
+    let mut collection = Collection::default();
+    collection.upload(&listing, "fp", false, now);
+    collection.upload(&other_world, "fp", false, now);
+
+    let target = ModerationTarget {
+        id: listing.id,
+        created_world: listing.created_world,
+        last_server_restart: listing.last_server_restart,
+    };
+    assert_eq!(collection.update_many(&target.filter(), &doc! { "$set": { "hidden": true } }), 1);
+
+    assert!(collection.stored(&listing).hidden);
+    assert!(!collection.stored(&other_world).hidden);
+}
+
+/// 대상 키가 빠진 본문은 DB를 건드리기 전에 `400`
+#[tokio::test]
+async fn incomplete_targets_are_rejected() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+
+        [ratelimit]
+        uploader_secret = "test"
+
+        [[moderation.tokens]]
+        name = "alice"
+        token = "secret"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let state = State::new(Arc::new(config), log_handle).await.unwrap();
+
+    for body in [r#"{"id": 123}"#, r#"{"id": 123, "created_world": 73}"#, r#"{"id": 123, "last_server_restart": 0}"#] {
+        let response = warp::test::request()
+            .method("POST")
+            .path("/api/moderation/hide")
+            .header("authorization", "Bearer secret")
+            .body(body)
+            .reply(&router(Arc::clone(&state)))
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", body);
+        let error: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert!(error["detail"].as_str().unwrap().starts_with("missing field"), "{}", body);
+    }
+}
+
+#[test]
+fn hidden_flag_is_only_stored_when_set() {
+    let mut container = container();
+    let shown = mongodb::bson::to_document(&container).unwrap();
+    assert!(!shown.contains_key("hidden"));
+
+    container.hidden = true;
+    let hidden = mongodb::bson::to_document(&container).unwrap();
+    assert_eq!(hidden.get("hidden"), Some(&Bson::Boolean(true)));
+
+    // 이 필드 전에 저장된 문서는 숨기지 않은 모집글
+    let restored: ListingContainer = mongodb::bson::from_document(shown).unwrap();
+    assert!(!restored.hidden);
+}
+
+#[test]
+fn hidden_listings_are_not_published_when_reuploaded() {
+    let changed = StoredUpload { outcome: UploadOutcome::Updated, hidden: false, flagged: false };
+    let hidden = StoredUpload { outcome: UploadOutcome::Updated, hidden: true, flagged: false };
+    let unchanged = StoredUpload { outcome: UploadOutcome::Unchanged, hidden: false, flagged: false };
+
+    assert!(changed.should_publish());
+    assert!(!hidden.should_publish());
+    assert!(!unchanged.should_publish());
+}
+
+#[test]
+fn audit_record_round_trips() {
+    let record = ModerationRecord {
+        action: ModerationAction::Hide,
+        moderator: "alice".into(),
+        target: ModerationTarget { id: 123, created_world: 73, last_server_restart: 0 },
+        matched: 1,
+        reason: Some("slurs in description".into()),
+        at: Utc::now(),
+    };
+
+    let document = mongodb::bson::to_document(&record).unwrap();
+    assert_eq!(document.get_str("action").unwrap(), "hide");
+    assert_eq!(document.get_document("target").unwrap().get_i32("created_world").unwrap(), 73);
+    assert!(ModerationAction::Hide.hidden());
+    assert!(!ModerationAction::Unhide.hidden());
+
+    let restored: ModerationRecord = mongodb::bson::from_document(document).unwrap();
+    assert_eq!(restored.target, record.target);
+    assert_eq!(restored.reason, record.reason);
+}
@@ -0,0 +1,550 @@
+//! 테스트용 MongoDB 쿼리 / 업데이트 평가기
+//!
+//! 서버 코드가 만드는 조회 조건과 파이프라인 업데이트를 DB 없이 실제 문서에 적용해 보기 위한
+//! 최소 구현입니다. `insert_listing` 등에서 쓰는 연산자만 지원하고, 모르는 연산자는 패닉합니다.
+//! 없는 필드는 `Bson::Undefined`로 다룹니다 (`$ifNull` / `$type`에서 null과 구분).
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use mongodb::bson::{Bson, Document};
+
+use crate::listing::PartyFinderListing;
+use crate::listing_container::{ListingContainer, StoredUpload};
+use crate::mongo::{stored_upload, upload_filter, upload_pipeline};
+
+const MISSING: Bson = Bson::Undefined;
+
+/// 조회 조건에 맞는 문서인지
+pub fn matches(filter: &Document, doc: &Document) -> bool {
+    filter.iter().all(|(key, condition)| match key.as_str() {
+        "$or" => clauses(condition).any(|clause| matches(clause, doc)),
+        "$nor" => !clauses(condition).any(|clause| matches(clause, doc)),
+        "$and" => clauses(condition).all(|clause| matches(clause, doc)),
+        path => field_matches(&lookup(doc, path), condition),
+    })
+}
+
+fn clauses(condition: &Bson) -> impl Iterator<Item = &Document> {
+    condition.as_array().expect("logical operator takes an array").iter().map(|clause| clause.as_document().unwrap())
+}
+
+fn field_matches(value: &Bson, condition: &Bson) -> bool {
+    match condition {
+        Bson::Document(ops) if ops.keys().next().is_some_and(|key| key.starts_with('$')) => {
+            ops.iter().all(|(op, arg)| match op.as_str() {
+                "$eq" => equals(value, arg),
+                "$ne" => !equals(value, arg),
+                "$gt" => compares(value, arg, &|o| o == Ordering::Greater),
+                "$gte" => compares(value, arg, &|o| o != Ordering::Less),
+                "$lt" => compares(value, arg, &|o| o == Ordering::Less),
+                "$lte" => compares(value, arg, &|o| o != Ordering::Greater),
+                "$in" => arg.as_array().unwrap().iter().any(|item| equals(value, item)),
+                "$nin" => !arg.as_array().unwrap().iter().any(|item| equals(value, item)),
+                "$exists" => (*value != MISSING) == arg.as_bool().unwrap(),
+                "$bitsAllClear" => as_i64(value).is_some_and(|bits| bits & as_i64(arg).unwrap() == 0),
+                _ => panic!("unsupported query operator {}", op),
+            })
+        }
+        _ => equals(value, condition),
+    }
+}
+
+/// 쿼리의 같음 비교 (null은 없는 필드와도 맞고, 배열 필드는 원소 중 하나와 맞으면 됨)
+fn equals(value: &Bson, expected: &Bson) -> bool {
+    match value {
+        Bson::Array(items) if !matches!(expected, Bson::Array(_)) => items.iter().any(|item| equals(item, expected)),
+        _ if *expected == Bson::Null => matches!(value, Bson::Null | Bson::Undefined),
+        _ => compare(value, expected) == Ordering::Equal && type_order(value) == type_order(expected),
+    }
+}
+
+/// 쿼리의 크기 비교는 같은 종류의 값끼리만 맞음
+fn compares(value: &Bson, arg: &Bson, ok: &dyn Fn(Ordering) -> bool) -> bool {
+    match value {
+        Bson::Array(items) => items.iter().any(|item| compares(item, arg, ok)),
+        _ => type_order(value) == type_order(arg) && ok(compare(value, arg)),
+    }
+}
+
+/// 점 경로 값 (중간에 배열이 있으면 각 원소의 값을 모은 배열)
+fn lookup(doc: &Document, path: &str) -> Bson {
+    let mut current = Bson::Document(doc.clone());
+    for part in path.split('.') {
+        current = match current {
+            Bson::Document(doc) => doc.get(part).cloned().unwrap_or(MISSING),
+            Bson::Array(items) => Bson::Array(
+                items
+                    .iter()
+                    .filter_map(|item| item.as_document().and_then(|doc| doc.get(part)).cloned())
+                    .collect(),
+            ),
+            _ => MISSING,
+        };
+    }
+    current
+}
+
+/// 점 경로에 값 쓰기 (중간 문서가 없으면 만듦)
+fn assign(doc: &mut Document, path: &str, value: Bson) {
+    match path.split_once('.') {
+        Some((head, rest)) => {
+            if !matches!(doc.get(head), Some(Bson::Document(_))) {
+                doc.insert(head, Document::new());
+            }
+            assign(doc.get_document_mut(head).unwrap(), rest, value);
+        }
+        None if value == MISSING => {
+            doc.remove(path);
+        }
+        None => {
+            doc.insert(path, value);
+        }
+    }
+}
+
+fn unassign(doc: &mut Document, path: &str) {
+    match path.split_once('.') {
+        Some((head, rest)) => {
+            if let Ok(inner) = doc.get_document_mut(head) {
+                unassign(inner, rest);
+            }
+        }
+        None => {
+            doc.remove(path);
+        }
+    }
+}
+
+/// `updateOne` / `updateMany`의 연산자 업데이트 (`$set`, `$unset`, `$inc`, `$max`, `$min`, `$setOnInsert`, `$addToSet`)
+pub fn apply_update(doc: &mut Document, update: &Document, inserted: bool) {
+    for (op, fields) in update {
+        let fields = fields.as_document().unwrap();
+        for (path, value) in fields {
+            let current = lookup(doc, path);
+            match op.as_str() {
+                "$set" => assign(doc, path, value.clone()),
+                "$setOnInsert" if inserted => assign(doc, path, value.clone()),
+                "$setOnInsert" => {}
+                "$unset" => unassign(doc, path),
+                "$inc" => {
+                    let sum = match current {
+                        Bson::Undefined => value.clone(),
+                        _ => add(&[current, value.clone()]),
+                    };
+                    assign(doc, path, sum)
+                }
+                "$max" if current == MISSING || compare(value, &current) == Ordering::Greater => {
+                    assign(doc, path, value.clone())
+                }
+                "$min" if current == MISSING || compare(value, &current) == Ordering::Less => {
+                    assign(doc, path, value.clone())
+                }
+                "$max" | "$min" => {}
+                "$addToSet" => {
+                    let mut items = current.as_array().cloned().unwrap_or_default();
+                    if !items.contains(value) {
+                        items.push(value.clone());
+                    }
+                    assign(doc, path, Bson::Array(items))
+                }
+                _ => panic!("unsupported update operator {}", op),
+            }
+        }
+    }
+}
+
+/// 컬렉션 하나 (`find_one_and_update` / `update_one` / `update_many` 흉내)
+#[derive(Debug, Default)]
+pub struct Collection {
+    pub docs: Vec<Document>,
+}
+
+impl Collection {
+    /// upsert가 있는 파이프라인 업데이트 (갱신 전 문서 반환, 없으면 조건의 같음 비교로 새 문서를 만듦)
+    pub fn upsert_pipeline(&mut self, filter: &Document, pipeline: &[Document], now: DateTime<Utc>) -> Option<Document> {
+        let position = self.docs.iter().position(|doc| matches(filter, doc));
+        let before = position.map(|i| self.docs[i].clone());
+        let base = before.clone().unwrap_or_else(|| upsert_base(filter));
+        let after = run_pipeline(base, pipeline, now);
+        match position {
+            Some(i) => self.docs[i] = after,
+            None => self.docs.push(after),
+        }
+        before
+    }
+
+    /// `insert_listing`과 같은 조건 / 파이프라인으로 업로드 한 건 반영 (갱신 후 문서로 결과 판단)
+    pub fn upload(
+        &mut self,
+        listing: &PartyFinderListing,
+        fingerprint: &str,
+        flagged: bool,
+        now: DateTime<Utc>,
+    ) -> StoredUpload {
+        let pipeline = upload_pipeline(listing, fingerprint, flagged, now).unwrap();
+        self.upsert_pipeline(&upload_filter(listing), &pipeline, now);
+        stored_upload(self.find(&upload_filter(listing))[0], flagged).unwrap()
+    }
+
+    /// 업로드한 모집글의 저장된 컨테이너
+    pub fn stored(&self, listing: &PartyFinderListing) -> ListingContainer {
+        let doc = self.find(&upload_filter(listing)).pop().expect("listing is stored").clone();
+        mongodb::bson::from_document(doc).unwrap()
+    }
+
+    /// 조건에 맞는 모든 문서에 연산자 업데이트 (바뀐 문서 수)
+    pub fn update_many(&mut self, filter: &Document, update: &Document) -> usize {
+        let mut modified = 0;
+        for doc in self.docs.iter_mut().filter(|doc| matches(filter, doc)) {
+            let before = doc.clone();
+            apply_update(doc, update, false);
+            modified += usize::from(*doc != before);
+        }
+        modified
+    }
+
+    /// 조건에 맞는 첫 문서에 연산자 업데이트 (맞은 문서가 있었는지)
+    pub fn update_one(&mut self, filter: &Document, update: &Document, upsert: bool) -> bool {
+        match self.docs.iter_mut().find(|doc| matches(filter, doc)) {
+            Some(doc) => {
+                apply_update(doc, update, false);
+                true
+            }
+            None => {
+                if upsert {
+                    let mut doc = upsert_base(filter);
+                    apply_update(&mut doc, update, true);
+                    self.docs.push(doc);
+                }
+                false
+            }
+        }
+    }
+
+    pub fn find(&self, filter: &Document) -> Vec<&Document> {
+        self.docs.iter().filter(|doc| matches(filter, doc)).collect()
+    }
+}
+
+/// upsert로 만드는 문서의 시작 값 (조건 중 같음 비교 필드)
+fn upsert_base(filter: &Document) -> Document {
+    let mut doc = Document::new();
+    for (path, value) in filter {
+        let operator = match value {
+            Bson::Document(ops) => ops.keys().next().is_some_and(|key| key.starts_with('$')),
+            _ => false,
+        };
+        if !path.starts_with('$') && !operator {
+            assign(&mut doc, path, value.clone());
+        }
+    }
+    doc
+}
+
+/// 파이프라인 업데이트 (`$set` / `$unset` 단계)
+pub fn run_pipeline(mut doc: Document, pipeline: &[Document], now: DateTime<Utc>) -> Document {
+    let vars = HashMap::from([("NOW".to_string(), Bson::DateTime(now.into()))]);
+    for stage in pipeline {
+        let (name, spec) = stage.iter().next().unwrap();
+        match name.as_str() {
+            "$set" | "$addFields" => {
+                // 같은 단계의 필드는 모두 단계 전 문서를 기준으로 계산
+                let root = doc.clone();
+                for (path, expression) in spec.as_document().unwrap() {
+                    assign(&mut doc, path, eval(expression, &root, &vars));
+                }
+            }
+            "$unset" => match spec {
+                Bson::String(path) => unassign(&mut doc, path),
+                Bson::Array(paths) => paths.iter().for_each(|path| unassign(&mut doc, path.as_str().unwrap())),
+                _ => panic!("bad $unset"),
+            },
+            _ => panic!("unsupported pipeline stage {}", name),
+        }
+    }
+    doc
+}
+
+/// 집계 식 평가
+pub fn eval(expression: &Bson, root: &Document, vars: &HashMap<String, Bson>) -> Bson {
+    match expression {
+        Bson::String(s) if s.starts_with("$$") => {
+            let (name, path) = s[2..].split_once('.').map_or((&s[2..], None), |(name, path)| (name, Some(path)));
+            let value = vars.get(name).cloned().unwrap_or_else(|| panic!("unknown variable {}", name));
+            match (path, value) {
+                (None, value) => value,
+                (Some(path), Bson::Document(doc)) => lookup(&doc, path),
+                (Some(path), Bson::Array(items)) => {
+                    let mut wrapper = Document::new();
+                    wrapper.insert("v", items);
+                    lookup(&wrapper, &format!("v.{}", path))
+                }
+                _ => MISSING,
+            }
+        }
+        Bson::String(s) if s.starts_with('$') => lookup(root, &s[1..]),
+        Bson::Array(items) => Bson::Array(items.iter().map(|item| eval(item, root, vars)).collect()),
+        Bson::Document(doc) => match doc.keys().next() {
+            Some(op) if op.starts_with('$') => operator(op, doc.get(op).unwrap(), root, vars),
+            _ => {
+                let mut out = Document::new();
+                for (key, value) in doc {
+                    let value = eval(value, root, vars);
+                    if value != MISSING {
+                        out.insert(key, value);
+                    }
+                }
+                Bson::Document(out)
+            }
+        },
+        other => other.clone(),
+    }
+}
+
+fn operator(op: &str, arg: &Bson, root: &Document, vars: &HashMap<String, Bson>) -> Bson {
+    if op == "$literal" {
+        return arg.clone();
+    }
+    if op == "$let" {
+        let spec = arg.as_document().unwrap();
+        let mut inner = vars.clone();
+        for (name, value) in spec.get_document("vars").unwrap() {
+            inner.insert(name.clone(), eval(value, root, vars));
+        }
+        return eval(spec.get("in").unwrap(), root, &inner);
+    }
+    if op == "$cond" {
+        let (condition, then, otherwise) = match arg {
+            Bson::Array(items) => (&items[0], &items[1], &items[2]),
+            Bson::Document(spec) => (spec.get("if").unwrap(), spec.get("then").unwrap(), spec.get("else").unwrap()),
+            _ => panic!("bad $cond"),
+        };
+        return if truthy(&eval(condition, root, vars)) { eval(then, root, vars) } else { eval(otherwise, root, vars) };
+    }
+    if op == "$filter" {
+        let spec = arg.as_document().unwrap();
+        let input = eval(spec.get("input").unwrap(), root, vars);
+        let name = spec.get_str("as").unwrap_or("this").to_string();
+        let items = input.as_array().cloned().unwrap_or_default();
+        let kept = items
+            .into_iter()
+            .filter(|item| {
+                let mut inner = vars.clone();
+                inner.insert(name.clone(), item.clone());
+                truthy(&eval(spec.get("cond").unwrap(), root, &inner))
+            })
+            .collect();
+        return Bson::Array(kept);
+    }
+
+    let args: Vec<Bson> = match arg {
+        Bson::Array(items) => items.iter().map(|item| eval(item, root, vars)).collect(),
+        single => vec![eval(single, root, vars)],
+    };
+    match op {
+        "$ifNull" => args.iter().find(|value| !is_nullish(value)).cloned().unwrap_or(Bson::Null),
+        "$eq" => Bson::Boolean(compare(&args[0], &args[1]) == Ordering::Equal),
+        "$ne" => Bson::Boolean(compare(&args[0], &args[1]) != Ordering::Equal),
+        "$gt" => Bson::Boolean(compare(&args[0], &args[1]) == Ordering::Greater),
+        "$gte" => Bson::Boolean(compare(&args[0], &args[1]) != Ordering::Less),
+        "$lt" => Bson::Boolean(compare(&args[0], &args[1]) == Ordering::Less),
+        "$lte" => Bson::Boolean(compare(&args[0], &args[1]) != Ordering::Greater),
+        "$and" => Bson::Boolean(args.iter().all(truthy)),
+        "$or" => Bson::Boolean(args.iter().any(truthy)),
+        "$not" => Bson::Boolean(!truthy(&args[0])),
+        "$in" => Bson::Boolean(
+            args[1].as_array().expect("$in needs an array").iter().any(|item| compare(item, &args[0]) == Ordering::Equal),
+        ),
+        "$size" => Bson::Int32(args[0].as_array().expect("$size needs an array").len() as i32),
+        "$concatArrays" => {
+            if args.iter().any(is_nullish) {
+                return Bson::Null;
+            }
+            Bson::Array(args.iter().flat_map(|array| array.as_array().unwrap().clone()).collect())
+        }
+        "$slice" => {
+            let items = args[0].as_array().unwrap();
+            let n = as_i64(&args[1]).unwrap();
+            let kept = if n < 0 {
+                items[items.len().saturating_sub(n.unsigned_abs() as usize)..].to_vec()
+            } else {
+                items[..items.len().min(n as usize)].to_vec()
+            };
+            Bson::Array(kept)
+        }
+        "$arrayElemAt" => {
+            let Some(items) = args[0].as_array() else { return Bson::Null };
+            let i = as_i64(&args[1]).unwrap();
+            let index = if i < 0 { items.len() as i64 + i } else { i };
+            usize::try_from(index).ok().and_then(|i| items.get(i)).cloned().unwrap_or(MISSING)
+        }
+        "$add" => add(&args),
+        "$min" | "$max" => {
+            let values: Vec<&Bson> = match args.as_slice() {
+                [Bson::Array(items)] => items.iter().collect(),
+                _ => args.iter().collect(),
+            };
+            let values = values.into_iter().filter(|value| !is_nullish(value));
+            let pick = if op == "$min" { values.min_by(|a, b| compare(a, b)) } else { values.max_by(|a, b| compare(a, b)) };
+            pick.cloned().unwrap_or(Bson::Null)
+        }
+        "$type" => Bson::String(
+            match &args[0] {
+                Bson::Undefined => "missing",
+                Bson::Null => "null",
+                Bson::String(_) => "string",
+                Bson::Int32(_) => "int",
+                Bson::Int64(_) => "long",
+                Bson::Double(_) => "double",
+                Bson::Boolean(_) => "bool",
+                Bson::DateTime(_) => "date",
+                Bson::Array(_) => "array",
+                Bson::Document(_) => "object",
+                other => panic!("unsupported $type of {:?}", other),
+            }
+            .to_string(),
+        ),
+        "$toDate" => match &args[0] {
+            Bson::String(s) => Bson::DateTime(DateTime::parse_from_rfc3339(s).expect("date string").with_timezone(&Utc).into()),
+            Bson::DateTime(date) => Bson::DateTime(*date),
+            value if is_nullish(value) => Bson::Null,
+            other => panic!("unsupported $toDate of {:?}", other),
+        },
+        "$mergeObjects" => {
+            let mut merged = Document::new();
+            for value in &args {
+                if let Bson::Document(doc) = value {
+                    for (key, value) in doc {
+                        merged.insert(key, value.clone());
+                    }
+                }
+            }
+            Bson::Document(merged)
+        }
+        _ => panic!("unsupported expression operator {}", op),
+    }
+}
+
+fn is_nullish(value: &Bson) -> bool {
+    matches!(value, Bson::Null | Bson::Undefined)
+}
+
+fn truthy(value: &Bson) -> bool {
+    match value {
+        Bson::Boolean(b) => *b,
+        Bson::Null | Bson::Undefined => false,
+        Bson::Int32(n) => *n != 0,
+        Bson::Int64(n) => *n != 0,
+        Bson::Double(n) => *n != 0.0,
+        _ => true,
+    }
+}
+
+fn as_i64(value: &Bson) -> Option<i64> {
+    match value {
+        Bson::Int32(n) => Some(i64::from(*n)),
+        Bson::Int64(n) => Some(*n),
+        Bson::Double(n) if n.fract() == 0.0 => Some(*n as i64),
+        _ => None,
+    }
+}
+
+fn as_f64(value: &Bson) -> Option<f64> {
+    match value {
+        Bson::Double(n) => Some(*n),
+        other => as_i64(other).map(|n| n as f64),
+    }
+}
+
+fn add(args: &[Bson]) -> Bson {
+    if args.iter().any(is_nullish) {
+        return Bson::Null;
+    }
+    if let Some(date) = args.iter().find_map(|value| value.as_datetime()) {
+        let millis: i64 = args.iter().filter_map(as_i64).sum();
+        return Bson::DateTime(mongodb::bson::DateTime::from_millis(date.timestamp_millis() + millis));
+    }
+    if args.iter().all(|value| matches!(value, Bson::Int32(_))) {
+        return Bson::Int32(args.iter().filter_map(as_i64).sum::<i64>() as i32);
+    }
+    if args.iter().all(|value| as_i64(value).is_some() && !matches!(value, Bson::Double(_))) {
+        return Bson::Int64(args.iter().filter_map(as_i64).sum());
+    }
+    Bson::Double(args.iter().filter_map(as_f64).sum())
+}
+
+/// BSON 비교 순서의 종류 순위
+fn type_order(value: &Bson) -> u8 {
+    match value {
+        Bson::Undefined | Bson::Null => 1,
+        Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) => 2,
+        Bson::String(_) => 3,
+        Bson::Document(_) => 4,
+        Bson::Array(_) => 5,
+        Bson::Binary(_) => 6,
+        Bson::ObjectId(_) => 7,
+        Bson::Boolean(_) => 8,
+        Bson::DateTime(_) => 9,
+        _ => 10,
+    }
+}
+
+/// 집계 식의 비교 (종류가 다르면 종류 순위, 같으면 값)
+fn compare(a: &Bson, b: &Bson) -> Ordering {
+    let (ta, tb) = (type_order(a), type_order(b));
+    if ta != tb {
+        return ta.cmp(&tb);
+    }
+    match (a, b) {
+        (Bson::String(a), Bson::String(b)) => a.cmp(b),
+        (Bson::Boolean(a), Bson::Boolean(b)) => a.cmp(b),
+        (Bson::DateTime(a), Bson::DateTime(b)) => a.cmp(b),
+        (Bson::Array(a), Bson::Array(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| compare(a, b))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        (Bson::Document(a), Bson::Document(b)) => {
+            let a: Vec<_> = a.iter().collect();
+            let b: Vec<_> = b.iter().collect();
+            a.iter()
+                .zip(&b)
+                .map(|((ka, va), (kb, vb))| ka.cmp(kb).then_with(|| compare(va, vb)))
+                .find(|o| *o != Ordering::Equal)
+                .unwrap_or_else(|| a.len().cmp(&b.len()))
+        }
+        _ if ta == 2 => as_f64(a).unwrap().partial_cmp(&as_f64(b).unwrap()).unwrap_or(Ordering::Equal),
+        _ => Ordering::Equal,
+    }
+}
+
+#[test]
+fn evaluator_follows_mongo_semantics() {
+    use mongodb::bson::doc;
+
+    let now = Utc::now();
+    let doc = doc! { "a": { "b": [{ "c": 1 }, { "c": 2 }] }, "n": 3_i64, "s": "x" };
+    assert!(matches(&doc! { "a.b.c": 2 }, &doc));
+    assert!(matches(&doc! { "missing": null, "n": { "$gte": 3, "$lt": 4.5 } }, &doc));
+    assert!(!matches(&doc! { "$nor": [{ "s": "x" }] }, &doc));
+    assert!(!matches(&doc! { "s": { "$gt": 1 } }, &doc));
+
+    let pipeline = vec![doc! { "$set": {
+        "n": { "$add": ["$n", 1] },
+        "old": "$n",
+        "last": { "$arrayElemAt": ["$a.b.c", -1] },
+        "gone": "$nothing",
+        "kind": { "$type": "$nothing" },
+        "at": { "$ifNull": ["$at", "$$NOW"] },
+    } }];
+    let after = run_pipeline(doc, &pipeline, now);
+    assert_eq!(after.get_i64("n"), Ok(4));
+    assert_eq!(after.get_i64("old"), Ok(3));
+    assert_eq!(after.get_i32("last"), Ok(2));
+    assert!(!after.contains_key("gone"));
+    assert_eq!(after.get_str("kind"), Ok("missing"));
+    assert_eq!(after.get_datetime("at").unwrap().to_chrono().timestamp_millis(), now.timestamp_millis());
+}
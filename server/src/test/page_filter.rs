@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Instant;
+
+use askama::Template;
+use chrono::Utc;
+use futures_util::StreamExt;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+
+use super::fixture_world::ListingBuilder;
+use crate::api::ApiShape;
+use crate::config::{Config, Logging};
+use crate::ffxiv::{data_centre_worlds, world_data_centre, world_id_by_name, Language};
+use crate::listing::ListingFilter;
+use crate::template::listings::ListingsTemplate;
+use crate::web::handlers::{collect_content_ids, listings_page, page_filter};
+use crate::web::hints::MAX_PLAYERS_WANTED;
+use crate::web::State;
+
+#[test]
+fn worlds_resolve_by_name() {
+    assert_eq!(world_id_by_name("Tonberry"), Some(72));
+    assert_eq!(world_id_by_name("tonberry"), Some(72));
+    assert_eq!(world_id_by_name("Nowhere"), None);
+
+    let elemental = data_centre_worlds("elemental").unwrap();
+    assert!(elemental.contains(&72) && elemental.contains(&49));
+    assert!(elemental.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(world_data_centre(72), Some("Elemental"));
+    assert_eq!(data_centre_worlds("Nowhere"), None);
+}
+
+#[test]
+fn page_filters_restrict_created_worlds() {
+    let worlds = |world, dc| {
+        let (filter, notice) = page_filter(world, dc);
+        assert_eq!(notice, None);
+        filter.worlds
+    };
+
+    assert_eq!(worlds(Some("Tonberry"), None), [72]);
+    assert_eq!(worlds(Some("Tonberry"), Some("Elemental")), [72]);
+    assert_eq!(worlds(None, Some("Elemental")), data_centre_worlds("Elemental").unwrap());
+    assert!(worlds(None, None).is_empty());
+    assert!(worlds(Some(""), Some(" ")).is_empty());
+}
+
+#[test]
+fn invalid_names_show_everything_with_a_notice() {
+    let cases = [
+        (Some("Nowhere"), None, "Unknown world \"Nowhere\", showing all listings."),
+        (Some("Tonberry"), Some("Atlantis"), "Unknown data centre \"Atlantis\", showing all listings."),
+        (Some("Tonberry"), Some("Aether"), "Tonberry is not on the Aether data centre, showing all listings."),
+    ];
+    for (world, dc, expected) in cases {
+        let (filter, notice) = page_filter(world, dc);
+        assert_eq!(filter, ListingFilter::default());
+        assert_eq!(notice.as_deref(), Some(expected));
+    }
+}
+
+#[tokio::test]
+async fn notice_is_rendered_above_the_listings() {
+    let template = |notice: Option<&str>| ListingsTemplate {
+        containers: Vec::new(),
+        lang: Language::English,
+        notice: notice.map(str::to_string),
+    };
+
+    let html = template(Some("Unknown world \"<b>\", showing all listings.")).render().unwrap();
+    assert!(html.contains(r#"<p class="listings-notice" role="alert">Unknown world &quot;&lt;b&gt;&quot;"#));
+    assert!(!template(None).render().unwrap().contains("listings-notice"));
+
+    // 나눠 보내는 페이지에도 표시
+    let streamed: Vec<String> = template(Some("Unknown world")).render_chunks(50).map(|chunk| chunk.unwrap()).collect().await;
+    assert!(streamed.concat().contains(r#"<p class="listings-notice" role="alert">Unknown world</p>"#));
+}
+
+/// 조건을 건 페이지는 다른 데이터 센터의 업로드 힌트를 지우지 않음
+#[tokio::test]
+async fn filtered_page_keeps_other_data_centres_pending_players() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let state = State::new(Arc::new(config), log_handle).await.unwrap();
+
+    // 73 = Adamantoise (Aether), 49 = Kujata (Elemental)
+    let listing = |id, world, members: &[u64]| {
+        ListingBuilder::new(id).world(world).member_ids(members.iter().copied()).at(Utc::now()).time_left(3600.0).build()
+    };
+    let aether = listing(1, 73, &[101, 102]);
+    let elemental = listing(2, 49, &[201]);
+    let all = [aether.clone(), elemental.clone()];
+    state.pending_players.replace(&all, |_| false);
+    // 이름 없는 멤버로 기억해 두어 DB 조회 없이 렌더링
+    state.missing_players.record_missing(&collect_content_ids(&all), &[], Instant::now());
+
+    let wanted = |dc| state.pending_players.wanted(&HashSet::from([dc]), MAX_PLAYERS_WANTED);
+
+    let (aether_only, _) = page_filter(None, Some("Aether"));
+    assert!(!aether_only.is_empty());
+    listings_page(&state, &[listing(1, 73, &[101])], Language::English, None, ApiShape::Compact, None).await;
+    assert_eq!(wanted("Aether"), [101, 102]);
+    assert_eq!(wanted("Elemental"), [201]);
+}
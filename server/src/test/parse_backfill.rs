@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use super::fflogs_coalescing::{mock_client, spawn_mock_fflogs};
+use crate::api::admin::listing_parse_members;
+use crate::config::FFLogs as FFLogsConfig;
+use crate::fflogs::backfill::{refresh_listing_parses, RefreshTarget};
+use crate::fflogs::{EncounterParse, FFLogsClient, FFLogsEncounter, ParseCacheDoc, RequestQuota, ZoneCache};
+use crate::listing_container::ListingContainer;
+use crate::player::Player;
+
+const ENCOUNTER: FFLogsEncounter = FFLogsEncounter {
+    zone_id: 73,
+    encounter_id: 101,
+    difficulty_id: Some(101),
+    secondary_encounter_id: None,
+    name: "Test",
+};
+
+fn target(content_id: u64, leader: bool, known: bool) -> RefreshTarget {
+    RefreshTarget {
+        content_id,
+        leader,
+        player: known.then(|| Player {
+            content_id,
+            name: format!("Member {}", content_id),
+            home_world: 73,
+            last_seen: Utc::now(),
+            seen_count: 1,
+        }),
+    }
+}
+
+fn cached(content_id: u64, percentile: f32) -> ParseCacheDoc {
+    ParseCacheDoc {
+        content_id: content_id as i64,
+        zones: maplit::hashmap! {
+            "73".to_string() => ZoneCache {
+                fetched_at: Utc::now(),
+                encounters: maplit::hashmap! {
+                    "101".to_string() => EncounterParse { percentile, job_id: 0, all_stars: None, jobs: Default::default() },
+                },
+            },
+        },
+        fetch: None,
+    }
+}
+
+/// 캐시가 아직 유효해도 다시 조회하고 이전 값과 함께 보고
+#[tokio::test]
+async fn refresh_bypasses_fresh_cache() {
+    let (addr, calls) = spawn_mock_fflogs();
+    let client = mock_client(addr);
+    let previous = maplit::hashmap! { 1 => cached(1, 10.0) };
+
+    let refresh = refresh_listing_parses(
+        &client,
+        vec![target(1, true, true), target(2, false, true)],
+        &ENCOUNTER,
+        None,
+        &previous,
+        Duration::from_secs(5),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    let members = &refresh.report.members;
+    assert_eq!(members[0].fetched_percentile, Some(50.0));
+    assert_eq!(members[0].previous_percentile, Some(10.0));
+    assert!(members[0].leader);
+    assert_eq!(members[1].fetched_percentile, Some(50.0));
+    assert_eq!(members[1].previous_percentile, None);
+    assert_eq!(refresh.zone_caches.len(), 2);
+    assert_eq!(refresh.zone_caches[1].1.encounters["101"].percentile, 50.0);
+}
+
+/// 플레이어 정보가 없는 멤버는 조회하지 않고 에러로 보고
+#[tokio::test]
+async fn unknown_members_are_reported_without_lookup() {
+    let (addr, _calls) = spawn_mock_fflogs();
+    let client = mock_client(addr);
+
+    let refresh = refresh_listing_parses(
+        &client,
+        vec![target(1, false, false), target(2, false, true)],
+        &ENCOUNTER,
+        None,
+        &HashMap::new(),
+        Duration::from_secs(5),
+    )
+    .await
+    .unwrap();
+
+    let members = &refresh.report.members;
+    assert!(members[0].error.is_some());
+    assert_eq!(members[0].fetched_percentile, None);
+    assert_eq!(members[1].error, None);
+    assert_eq!(refresh.zone_caches.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![2]);
+}
+
+/// 요청 예산이 소진되면 FFLogs에 요청하지 않음
+#[tokio::test]
+async fn exhausted_quota_skips_lookup() {
+    let (addr, calls) = spawn_mock_fflogs();
+    let client = FFLogsClient::with_endpoints(
+        FFLogsConfig {
+            client_id: "id".to_string(),
+            client_secret: "secret".to_string(),
+            empty_backoff: Default::default(),
+            partitions: Default::default(),
+            requests_per_hour: Some(1),
+            cache_hours: 24,
+            zone_cache_hours: Default::default(),
+            mapping_file: None,
+            retry: Default::default(),
+        },
+        &format!("http://{}/oauth/token", addr),
+        &format!("http://{}/api/v2/client", addr),
+    );
+    client.quota().try_acquire().unwrap();
+
+    let exhausted = refresh_listing_parses(
+        &client,
+        vec![target(1, true, true)],
+        &ENCOUNTER,
+        None,
+        &HashMap::new(),
+        Duration::from_secs(5),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(exhausted.retry_after > Duration::ZERO);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+/// FFLogs 조회 실패는 멤버별 에러로 보고
+#[tokio::test]
+async fn fetch_errors_are_reported_per_member() {
+    let client = mock_client(([127, 0, 0, 1], 9).into());
+    let previous = maplit::hashmap! { 1 => cached(1, 10.0) };
+
+    let refresh = refresh_listing_parses(
+        &client,
+        vec![target(1, true, true)],
+        &ENCOUNTER,
+        None,
+        &previous,
+        Duration::from_secs(5),
+    )
+    .await
+    .unwrap();
+
+    let member = &refresh.report.members[0];
+    assert!(member.error.is_some());
+    assert_eq!(member.fetched_percentile, None);
+    assert_eq!(member.previous_percentile, Some(10.0));
+    assert!(refresh.zone_caches.is_empty());
+}
+
+#[test]
+fn quota_counts_requests_in_the_last_hour() {
+    let quota = RequestQuota::new(Some(2));
+    let start = std::time::Instant::now();
+
+    quota.try_acquire_at(start).unwrap();
+    quota.try_acquire_at(start + Duration::from_secs(60)).unwrap();
+    let exhausted = quota.try_acquire_at(start + Duration::from_secs(120)).unwrap_err();
+    assert_eq!(exhausted.retry_after, Duration::from_secs(3600 - 120));
+
+    quota.check_at(start + Duration::from_secs(3600)).unwrap();
+}
+
+/// 파티장과 멤버를 중복 없이 모음
+#[test]
+fn listing_members_include_leader_once() {
+    let mut container = ListingContainer {
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        listing: serde_json::from_str(super::LISTING).unwrap(),
+        upload_count: 0,
+        uploader_fingerprints: Vec::new(),
+        description_hash: None,
+        description_text: None,
+        description_history: Vec::new(),
+        description_language: None,
+        has_autotranslate: false,
+        unconfirmed_at: None,
+        canonical_category: None,
+        hidden: false,
+        flagged: false,
+        slots_history: Vec::new(),
+    };
+    container.listing.leader_content_id = 7;
+    container.listing.member_content_ids = vec![0, 7, 8, 8];
+
+    assert_eq!(listing_parse_members(&container), vec![(7, true), (8, false)]);
+}
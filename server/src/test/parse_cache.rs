@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use chrono::{TimeDelta, TimeZone, Utc};
+
+use crate::api::admin::{drain_lines, export_line, parse_import_line};
+use crate::fflogs::{merge_zone_caches, EncounterParse, ParseCacheDoc, ZoneCache};
+
+fn zone(hours_ago: i64, percentile: f32) -> ZoneCache {
+    let base = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+    ZoneCache {
+        fetched_at: base - TimeDelta::try_hours(hours_ago).unwrap(),
+        encounters: maplit::hashmap! {
+            "101".to_string() => EncounterParse { percentile, job_id: 0, all_stars: None, jobs: Default::default() },
+        },
+    }
+}
+
+fn doc(content_id: i64, zones: Vec<(&str, ZoneCache)>) -> ParseCacheDoc {
+    ParseCacheDoc {
+        content_id,
+        zones: zones.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+        fetch: None,
+    }
+}
+
+/// 테스트용 메모리 저장소에 가져오기 결과 적용
+fn import_into(store: &mut HashMap<i64, ParseCacheDoc>, ndjson: &[u8]) -> usize {
+    let mut pending = ndjson.to_vec();
+    pending.push(b'\n');
+
+    let mut malformed = 0;
+    for line in drain_lines(&mut pending) {
+        let incoming = match parse_import_line(&line) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => continue,
+            Err(_) => {
+                malformed += 1;
+                continue;
+            }
+        };
+
+        let outcome = merge_zone_caches(store.get(&incoming.content_id), &incoming);
+        let entry = store.entry(incoming.content_id).or_insert_with(|| ParseCacheDoc {
+            content_id: incoming.content_id,
+            zones: HashMap::new(),
+            fetch: None,
+        });
+        entry.zones.extend(outcome.zones_to_write);
+    }
+
+    malformed
+}
+
+#[test]
+fn export_import_round_trip() {
+    let docs = vec![
+        doc(1, vec![("73", zone(1, 95.5)), ("59", zone(30, 42.0))]),
+        doc(2, vec![("72", zone(5, -1.0))]),
+    ];
+
+    let mut ndjson = String::new();
+    for d in &docs {
+        ndjson.push_str(&export_line(d).unwrap());
+    }
+    ndjson.push_str("{\"summary\":{\"exported\":2,\"errors\":0,\"next_after\":null}}\n");
+
+    let mut store = HashMap::new();
+    let malformed = import_into(&mut store, ndjson.as_bytes());
+
+    assert_eq!(malformed, 0);
+    assert_eq!(store.len(), 2);
+    for d in &docs {
+        assert_eq!(store[&d.content_id].zones, d.zones);
+    }
+}
+
+#[test]
+fn merge_keeps_newer_zone() {
+    let existing = doc(1, vec![("73", zone(1, 80.0)), ("72", zone(10, 50.0))]);
+    let incoming = doc(1, vec![("73", zone(5, 99.0)), ("72", zone(2, 60.0)), ("68", zone(3, 10.0))]);
+
+    let outcome = merge_zone_caches(Some(&existing), &incoming);
+
+    assert_eq!(outcome.conflicts, 2);
+    assert_eq!(outcome.kept_existing, 1);
+    // 73: 기존이 더 최신 → 유지
+    assert!(!outcome.zones_to_write.contains_key("73"));
+    // 72: 가져온 쪽이 더 최신 → 덮어쓰기
+    assert_eq!(outcome.zones_to_write["72"], incoming.zones["72"]);
+    // 68: 기존에 없음 → 추가
+    assert_eq!(outcome.zones_to_write["68"], incoming.zones["68"]);
+}
+
+#[test]
+fn malformed_lines_are_counted() {
+    let good = export_line(&doc(3, vec![("73", zone(1, 70.0))])).unwrap();
+    let ndjson = format!("{}not json\n{{\"content_id\":\"x\"}}\n\n", good);
+
+    let mut store = HashMap::new();
+    let malformed = import_into(&mut store, ndjson.as_bytes());
+
+    assert_eq!(malformed, 2);
+    assert_eq!(store.len(), 1);
+}
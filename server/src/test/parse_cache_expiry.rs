@@ -0,0 +1,67 @@
+use chrono::{TimeDelta, TimeZone, Utc};
+
+use crate::config::Config;
+use crate::fflogs::{is_zone_cache_expired, CacheExpiry, ZoneCache};
+
+fn config(fflogs: &str) -> Config {
+    toml::from_str(&format!(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9"
+
+        {}
+        "#,
+        fflogs
+    ))
+    .unwrap()
+}
+
+#[test]
+fn expiry_defaults_to_a_day() {
+    let expiry = CacheExpiry::from_config(None);
+    assert_eq!(expiry.for_zone(73), TimeDelta::hours(24));
+
+    let config = config("[fflogs]\nclient_id = \"id\"\nclient_secret = \"secret\"");
+    assert_eq!(CacheExpiry::from_config(config.fflogs.as_ref()), CacheExpiry::default());
+}
+
+#[test]
+fn zones_can_override_the_expiry() {
+    let config = config(
+        r#"
+        [fflogs]
+        client_id = "id"
+        client_secret = "secret"
+        cache_hours = 48
+
+        [fflogs.zone_cache_hours]
+        "73" = 6
+        "59" = 168
+        "current" = 1
+        "#,
+    );
+    let expiry = CacheExpiry::from_config(config.fflogs.as_ref());
+
+    assert_eq!(expiry.for_zone(73), TimeDelta::hours(6));
+    assert_eq!(expiry.for_zone(59), TimeDelta::hours(168));
+    // 재정의가 없거나 키가 잘못된 Zone은 기본값
+    assert_eq!(expiry.for_zone(72), TimeDelta::hours(48));
+}
+
+#[test]
+fn cache_expires_after_the_zone_max_age() {
+    let now = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+    let cache = |hours_ago| ZoneCache {
+        fetched_at: now - TimeDelta::hours(hours_ago),
+        encounters: Default::default(),
+    };
+    let expiry = CacheExpiry::new(24, maplit::hashmap! { 73 => 6 });
+
+    assert!(!is_zone_cache_expired(&cache(5), expiry.for_zone(73), now));
+    assert!(is_zone_cache_expired(&cache(7), expiry.for_zone(73), now));
+    assert!(!is_zone_cache_expired(&cache(7), expiry.for_zone(72), now));
+    assert!(is_zone_cache_expired(&cache(25), expiry.for_zone(72), now));
+}
@@ -0,0 +1,86 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::time::Duration;
+
+use mongodb::bson::{doc, DateTime};
+
+use crate::fflogs::refetch::{refetch_targets, zone_order};
+use crate::fflogs::{RefetchQueue, ZonePartitions};
+use crate::listing_container::QueriedListing;
+use crate::mongo::zone_invalidation;
+
+/// duty 1006 = Futures Rewritten (Ultimate), FFLogs Zone 65
+fn queried(duty: u16, members: impl IntoIterator<Item = i64>) -> QueriedListing {
+    let mut listing: crate::listing::PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.duty = duty;
+    listing.member_content_ids = members.into_iter().collect();
+
+    QueriedListing {
+        time_left: 3600.0,
+        ..super::queried(listing)
+    }
+}
+
+#[test]
+fn invalidation_is_one_bulk_update_for_the_zone() {
+    let (filter, update) = zone_invalidation(73);
+
+    assert_eq!(filter, doc! { "zones.73": { "$exists": true } });
+    assert_eq!(
+        update,
+        doc! { "$set": { "zones.73.fetched_at": DateTime::from_millis(0) } },
+    );
+    // 다른 Zone 캐시는 건드리지 않음
+    assert!(!format!("{:?}", update).contains("zones.72"));
+}
+
+#[test]
+fn refetch_targets_are_members_of_zone_listings() {
+    let listings = vec![
+        queried(1006, [3, 1, 0]),
+        queried(1006, [1, 2]),
+        // FFLogs 매핑이 없는 듀티
+        queried(55, [99]),
+    ];
+    assert!(listings[0].listing.high_end());
+
+    assert_eq!(refetch_targets(&listings, 65), vec![1, 2, 3]);
+    assert!(refetch_targets(&listings, 73).is_empty());
+}
+
+#[tokio::test]
+async fn enqueue_dedups_and_wakes_background_task() {
+    let queue = RefetchQueue::default();
+
+    assert_eq!(queue.enqueue(65, [1, 2, 3, 0]), 3);
+    assert_eq!(queue.enqueue(65, [3, 4]), 1);
+    assert_eq!(queue.enqueue(73, [5]), 1);
+
+    // 대기 중인 작업은 이미 알림을 받은 상태로 즉시 깨어남
+    tokio::time::timeout(Duration::from_secs(1), queue.notified())
+        .await
+        .expect("background task was not woken");
+
+    let drained = queue.drain();
+    assert_eq!(drained[&65], BTreeSet::from([1, 2, 3, 4]));
+    assert_eq!(drained[&73], BTreeSet::from([5]));
+    assert!(queue.drain().is_empty());
+}
+
+#[test]
+fn priority_zones_are_processed_first() {
+    let priority = HashSet::from([68]);
+    assert_eq!(zone_order([73, 59, 68, 72], &priority), vec![68, 59, 72, 73]);
+    assert_eq!(zone_order([73, 59], &HashSet::new()), vec![59, 73]);
+}
+
+#[test]
+fn partition_overrides_replace_default_mapping() {
+    let partitions = ZonePartitions::new(HashMap::from([(72, 3)]));
+
+    assert_eq!(partitions.get(73), Some(1));
+    assert_eq!(partitions.get(72), Some(3));
+    assert_eq!(partitions.get(9999), None);
+
+    partitions.set(73, 2);
+    assert_eq!(partitions.get(73), Some(2));
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use chrono::{FixedOffset, Utc};
+use ffxiv_types::jobs::ClassJob;
+
+use super::fixture_world::parse_docs;
+use crate::api::build_api_listings;
+use crate::ffxiv::{Language, JOBS};
+use crate::fflogs::parse_response::{parse_character, parse_zone_rankings, ParsedRankings};
+use crate::fflogs::mapping::{job_id_for_spec, SPEC_TO_JOB};
+use crate::fflogs::{EncounterParse, JobParse, ZoneCache, DUTY_TO_FFLOGS};
+use crate::listing::PartyFinderListing;
+use crate::listing_container::QueriedListing;
+use crate::player::Player;
+use crate::template::listings::ListingsTemplate;
+use crate::web::handlers::build_renderable_listings;
+
+const PLD: u8 = 19;
+const WHM: u8 = 24;
+const SCH: u8 = 28;
+const BLM: u8 = 25;
+const AST: u8 = 33;
+
+fn parse(job_id: u8) -> EncounterParse {
+    EncounterParse { percentile: 99.0, job_id, all_stars: None, jobs: Default::default() }
+}
+
+fn queried(duty: u16) -> QueriedListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.duty = duty;
+    listing.member_content_ids = vec![1, 2];
+    listing.jobs_present = vec![PLD, WHM];
+
+    super::queried(listing)
+}
+
+fn players() -> HashMap<u64, Player> {
+    [1, 2]
+        .into_iter()
+        .map(|id| {
+            let player = Player {
+                content_id: id,
+                name: format!("Member {}", id),
+                home_world: 73,
+                last_seen: Utc::now(),
+                seen_count: 1,
+            };
+            (id, player)
+        })
+        .collect()
+}
+
+/// 현재 전투 잡은 모두 FFLogs spec 이름이 있음
+#[test]
+fn every_job_has_a_spec() {
+    let mapped: Vec<u8> = SPEC_TO_JOB.values().copied().collect();
+    for (&id, cj) in JOBS.iter() {
+        if matches!(cj, ClassJob::Job(_)) {
+            assert!(mapped.contains(&(id as u8)), "{} has no FFLogs spec", cj.code());
+        }
+    }
+
+    for (spec, &id) in SPEC_TO_JOB.iter() {
+        let cj = JOBS.get(&u32::from(id)).unwrap();
+        assert!(matches!(cj, ClassJob::Job(_)), "{} maps to {}", spec, cj.code());
+    }
+    assert_eq!(job_id_for_spec("Bogus"), 0);
+}
+
+#[test]
+fn best_spec_is_stored_with_the_parse() {
+    let zone_rankings = serde_json::json!({
+        "rankings": [
+            { "encounter": { "id": 101 }, "rankPercent": 99.0, "spec": "Scholar", "bestSpec": "WhiteMage" },
+            { "encounter": { "id": 102 }, "rankPercent": 80.0, "spec": "DarkKnight" },
+            { "encounter": { "id": 103 }, "rankPercent": 70.0, "bestSpec": "Astromancer" },
+        ],
+    });
+
+    let jobs: Vec<(u32, u8)> = parse_zone_rankings(&zone_rankings)
+        .into_iter()
+        .map(|(id, parse)| (id, parse.job_id))
+        .collect();
+    assert_eq!(jobs, vec![(101, WHM), (102, 32), (103, 0)]);
+}
+
+#[test]
+fn mismatch_compares_roles_not_jobs() {
+    assert!(parse(BLM).role_mismatch(WHM));
+    assert!(parse(WHM).role_mismatch(PLD));
+    // 같은 역할의 다른 잡
+    assert!(!parse(SCH).role_mismatch(WHM));
+    assert!(!parse(WHM).role_mismatch(WHM));
+    // 알 수 없는 잡은 표시하지 않음
+    assert!(!parse(0).role_mismatch(WHM));
+    assert!(!parse(BLM).role_mismatch(0));
+}
+
+/// 최고 기록은 흑마도사, 역할별 랭킹으로 백마도사 / 학자 기록이 있음
+fn parse_with_jobs() -> EncounterParse {
+    EncounterParse {
+        percentile: 99.0,
+        job_id: BLM,
+        all_stars: None,
+        jobs: maplit::hashmap! {
+            BLM.to_string() => 99.0,
+            WHM.to_string() => 60.0,
+            SCH.to_string() => 75.0,
+        },
+    }
+}
+
+#[test]
+fn role_rankings_fill_job_percentiles() {
+    let ranking = |percent: f64, spec: &str| {
+        serde_json::json!({ "rankings": [{ "encounter": { "id": 101 }, "rankPercent": percent, "bestSpec": spec }] })
+    };
+    let character = serde_json::json!({
+        "zoneRankings": ranking(99.0, "BlackMage"),
+        "tankRankings": { "rankings": [{ "encounter": { "id": 101 }, "rankPercent": null }] },
+        "healerRankings": ranking(60.0, "WhiteMage"),
+        "dpsRankings": ranking(99.0, "BlackMage"),
+    });
+
+    let ParsedRankings::Known(parses) = parse_character(Some(&character)) else {
+        panic!("expected known rankings");
+    };
+    assert_eq!(parses.len(), 1);
+    assert_eq!(parses[0].1.percentile, 99.0);
+    assert_eq!(
+        parses[0].1.jobs,
+        maplit::hashmap! { WHM.to_string() => 60.0, BLM.to_string() => 99.0 }
+    );
+
+    // 역할별 랭킹이 없으면 예전과 같음
+    let character = serde_json::json!({ "zoneRankings": ranking(99.0, "BlackMage") });
+    assert_eq!(parse_character(Some(&character)).into_result().unwrap()[0].1.jobs, HashMap::new());
+}
+
+#[test]
+fn current_job_parse_is_preferred() {
+    let parse = parse_with_jobs();
+
+    assert_eq!(parse.for_job(WHM), JobParse { percentile: 60.0, job_id: WHM });
+    // 같은 역할 중 가장 높은 기록
+    assert_eq!(parse.for_job(AST), JobParse { percentile: 75.0, job_id: SCH });
+    // 역할 기록이 없으면 최고 기록
+    assert_eq!(parse.for_job(PLD), JobParse { percentile: 99.0, job_id: BLM });
+    assert_eq!(parse.for_job(0), JobParse { percentile: 99.0, job_id: BLM });
+
+    assert!(!parse.role_mismatch(AST));
+    assert!(parse.role_mismatch(PLD));
+}
+
+#[test]
+fn documents_without_job_percentiles_still_load() {
+    let stored = mongodb::bson::doc! { "percentile": 88.5, "job_id": i32::from(SCH) };
+    let parse: EncounterParse = mongodb::bson::from_document(stored).unwrap();
+
+    assert!(parse.jobs.is_empty());
+    assert_eq!(parse.for_job(WHM), JobParse { percentile: 88.5, job_id: SCH });
+    assert!(!mongodb::bson::to_document(&parse).unwrap().contains_key("jobs"));
+}
+
+#[test]
+fn api_members_show_the_current_job_parse() {
+    let (&duty, info) = DUTY_TO_FFLOGS.iter().next().unwrap();
+    let parse_docs = parse_docs(maplit::hashmap! {
+        (info.zone_id as u16, 2) => ZoneCache {
+            fetched_at: Utc::now(),
+            encounters: maplit::hashmap! { info.encounter_id.to_string() => parse_with_jobs() },
+        },
+    });
+
+    let api = build_api_listings(vec![queried(duty)], &players(), &parse_docs, FixedOffset::east_opt(0).unwrap());
+    let members = serde_json::to_value(&api).unwrap()[0]["listing"]["members"].clone();
+
+    // 백마도사 자리: 흑마도사 99가 아닌 백마도사 60
+    assert_eq!(members[1]["parse_percentile"], 60);
+    assert_eq!(members[1]["parse_role_mismatch"], false);
+}
+
+#[test]
+fn api_members_flag_role_mismatch() {
+    let (&duty, info) = DUTY_TO_FFLOGS.iter().next().unwrap();
+    let zone_cache = |job_id| ZoneCache {
+        fetched_at: Utc::now(),
+        encounters: maplit::hashmap! { info.encounter_id.to_string() => parse(job_id) },
+    };
+    let parse_docs = parse_docs(maplit::hashmap! {
+        // 탱커 자리, 힐러로 낸 기록
+        (info.zone_id as u16, 1) => zone_cache(SCH),
+        // 힐러 자리, 힐러로 낸 기록
+        (info.zone_id as u16, 2) => zone_cache(SCH),
+    });
+
+    let api = build_api_listings(vec![queried(duty)], &players(), &parse_docs, FixedOffset::east_opt(0).unwrap());
+    let members = serde_json::to_value(&api).unwrap()[0]["listing"]["members"].clone();
+
+    assert_eq!(members[0]["parse_role_mismatch"], true);
+    assert_eq!(members[1]["parse_role_mismatch"], false);
+}
+
+#[test]
+fn template_marks_role_mismatch() {
+    // Parse 칸은 FFLogs에 매핑된 고난이도 듀티에만 표시
+    let duty = *DUTY_TO_FFLOGS.keys().next().unwrap();
+    let mut renderable = build_renderable_listings(vec![queried(duty)], &players(), &HashMap::new());
+    renderable[0].members[0].parse.role_mismatch = true;
+
+    let html = ListingsTemplate { containers: renderable, lang: Language::English, notice: None }.render().unwrap();
+    assert_eq!(html.matches(r#"class="role-mismatch""#).count(), 1);
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use chrono::FixedOffset;
+
+use super::queried;
+use crate::api::build_api_listings;
+use crate::ffxiv::Language;
+use crate::listing::{JobFlags, PartyFill, PartyFinderListing, PartyFinderSlot};
+use crate::template::listings::{ListingsTemplate, RenderableListing};
+
+/// 19 = PLD, 24 = WHM, 0 = 빈 자리
+fn listing(num_parties: u8, party_size: u8, filled_per_party: &[usize]) -> PartyFinderListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    let size = usize::from(party_size);
+    listing.num_parties = num_parties;
+    listing.slots_available = party_size;
+    listing.slots = (0..size * filled_per_party.len())
+        .map(|_| PartyFinderSlot { accepting: JobFlags::all() })
+        .collect();
+    listing.jobs_present = filled_per_party
+        .iter()
+        .flat_map(|&filled| {
+            (0..size).map(move |slot| match slot {
+                0 if filled > 0 => 19,
+                _ if slot < filled => 24,
+                _ => 0,
+            })
+        })
+        .collect();
+    listing
+}
+
+fn fill(filled: usize, capacity: usize) -> PartyFill {
+    PartyFill { filled, capacity }
+}
+
+#[test]
+fn single_party_numbers() {
+    let listing = listing(1, 8, &[3]);
+    assert_eq!(listing.party_count(), 1);
+    assert_eq!(listing.total_capacity(), 8);
+    assert_eq!(listing.filled_total(), 3);
+    assert_eq!(listing.open_slots(), 5);
+    assert_eq!(listing.parties(), [fill(3, 8)]);
+    assert_eq!(listing.slots().len(), 8);
+    assert_eq!(listing.slots().iter().filter(|slot| slot.is_ok()).count(), 3);
+}
+
+#[test]
+fn two_party_numbers() {
+    let listing = listing(2, 8, &[8, 2]);
+    assert_eq!(listing.total_capacity(), 16);
+    assert_eq!(listing.filled_total(), 10);
+    assert_eq!(listing.open_slots(), 6);
+    assert_eq!(listing.parties(), [fill(8, 8), fill(2, 8)]);
+    // 두 번째 파티의 자리도 포함
+    assert_eq!(listing.slots().len(), 16);
+    assert!(listing.slots()[8].is_ok());
+    assert!(listing.slots()[10].is_err());
+}
+
+#[test]
+fn alliance_numbers() {
+    let listing = listing(3, 8, &[8, 8, 1]);
+    assert_eq!(listing.party_count(), 3);
+    assert_eq!(listing.total_capacity(), 24);
+    assert_eq!(listing.filled_total(), 17);
+    assert_eq!(listing.open_slots(), 7);
+    assert_eq!(listing.parties(), [fill(8, 8), fill(8, 8), fill(1, 8)]);
+    assert_eq!(listing.slots().len(), 24);
+
+    // 파티 수가 0으로 올라와도 한 파티로 취급
+    let mut zero = listing;
+    zero.num_parties = 0;
+    assert_eq!(zero.total_capacity(), 8);
+    assert_eq!(zero.filled_total(), 8);
+    assert_eq!(zero.parties(), [fill(8, 8)]);
+}
+
+#[test]
+fn badge_and_api_show_totals() {
+    let html = ListingsTemplate {
+        containers: vec![RenderableListing {
+            container: queried(listing(3, 8, &[8, 8, 1])),
+            members: Vec::new(),
+            leader_parse: Default::default(),
+            watched: false,
+            data_inconsistent: false,
+        }],
+        lang: Language::English,
+        notice: None,
+    }
+    .render()
+    .unwrap();
+    assert!(html.contains(r#"<div class="total">17/24</div>"#));
+    assert_eq!(html.matches(r#"<div class="slot"#).count(), 24);
+
+    let listings = vec![queried(listing(1, 8, &[3])), queried(listing(2, 8, &[8, 2]))];
+    let api = build_api_listings(listings, &HashMap::new(), &HashMap::new(), FixedOffset::east_opt(0).unwrap());
+    let json = serde_json::to_value(&api).unwrap();
+    let totals: Vec<_> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| {
+            let l = &l["listing"];
+            (
+                l["slot_count"].as_u64().unwrap(),
+                l["total_capacity"].as_u64().unwrap(),
+                l["filled_total"].as_u64().unwrap(),
+                l["parties"].clone(),
+            )
+        })
+        .collect();
+
+    assert!(totals.contains(&(8, 8, 3, serde_json::json!([{ "filled": 3, "capacity": 8 }]))));
+    assert!(totals.contains(&(
+        8,
+        16,
+        10,
+        serde_json::json!([{ "filled": 8, "capacity": 8 }, { "filled": 2, "capacity": 8 }])
+    )));
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use mongodb::bson::doc;
+
+use super::fixture_world::ListingBuilder;
+use super::mongo_eval::Collection;
+use crate::listing::{PartyFinderListing, PartyMember};
+use crate::listing_container::UploadOutcome;
+use crate::mongo::party_detail_update;
+
+const LEADER: u64 = 18_014_398_509_481_985;
+const MEMBERS: [u64; 3] = [LEADER, 18_014_398_509_481_986, 18_014_398_509_481_987];
+
+fn at(minutes: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 2, 1, 20, 0, 0).unwrap() + TimeDelta::try_minutes(minutes).unwrap()
+}
+
+fn listing() -> PartyFinderListing {
+    ListingBuilder::new(1).listing()
+}
+
+fn member_ids() -> Vec<i64> {
+    MEMBERS.iter().map(|&id| id as i64).collect()
+}
+
+/// `contribute_detail_handler`와 같은 조건 / 업데이트로 상세 정보 기록
+fn record_detail(listings: &mut Collection, members: &[PartyMember]) {
+    let update = party_detail_update(LEADER, &MEMBERS, members).unwrap();
+    listings.update_one(&doc! { "listing.id": listing().id }, &update, false);
+}
+
+#[test]
+fn listing_upload_after_detail_keeps_member_ids() {
+    // 모집글 → 상세 정보 → 같은 모집글 다시 업로드
+    let mut listings = Collection::default();
+    listings.upload(&listing(), "aaaaaaaaaaaaaaaa", false, at(0));
+    record_detail(&mut listings, &[]);
+
+    let edited = ListingBuilder::new(1).min_item_level(10).listing();
+    assert_eq!(listings.upload(&edited, "bbbbbbbbbbbbbbbb", false, at(1)).outcome, UploadOutcome::Updated);
+
+    let stored = listings.stored(&edited).listing;
+    assert_eq!(stored.min_item_level, 10);
+    assert_eq!(stored.member_content_ids, member_ids());
+    assert_eq!(stored.leader_content_id, LEADER);
+}
+
+#[test]
+fn reupload_with_only_stored_detail_keeps_the_stored_listing() {
+    let mut listings = Collection::default();
+    listings.upload(&listing(), "aaaaaaaaaaaaaaaa", false, at(0));
+    record_detail(&mut listings, &[]);
+    let before = listings.docs[0].get_document("listing").unwrap().clone();
+
+    // 저장된 상세 정보를 유지한 모집글이 그대로이므로 바뀌지 않은 업로드
+    assert_eq!(listings.upload(&listing(), "aaaaaaaaaaaaaaaa", false, at(1)).outcome, UploadOutcome::Unchanged);
+    assert_eq!(listings.docs[0].get_document("listing").unwrap(), &before);
+    assert_eq!(listings.stored(&listing()).listing.member_content_ids, member_ids());
+}
+
+#[test]
+fn uploaded_party_detail_replaces_stored_detail() {
+    let mut listings = Collection::default();
+    listings.upload(&listing(), "aaaaaaaaaaaaaaaa", false, at(0));
+    record_detail(&mut listings, &[]);
+
+    let incoming = ListingBuilder::new(1).leader(MEMBERS[1]).member_ids([MEMBERS[1]]).listing();
+    assert_eq!(listings.upload(&incoming, "aaaaaaaaaaaaaaaa", false, at(1)).outcome, UploadOutcome::Updated);
+
+    let stored = listings.stored(&incoming).listing;
+    assert_eq!(stored.leader_content_id, MEMBERS[1]);
+    assert_eq!(stored.member_content_ids, vec![MEMBERS[1] as i64]);
+}
+
+#[test]
+fn detail_members_are_stored_in_slot_order() {
+    let mut listings = Collection::default();
+    listings.upload(&listing(), "aaaaaaaaaaaaaaaa", false, at(0));
+    let member = |slot_index, job_id| PartyMember { content_id: 0, job_id, slot_index };
+    record_detail(&mut listings, &[member(2, 24), member(0, 19), member(2, 33)]);
+
+    let stored = listings.stored(&listing()).listing;
+    let slots: Vec<(u8, u8)> = stored.members.iter().map(|m| (m.slot_index, m.job_id)).collect();
+    assert_eq!(slots, [(0, 19), (2, 24)]);
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use chrono::FixedOffset;
+use serde_json::Value;
+
+use super::fixture_world::{ListingBuilder, SAVAGE};
+use crate::api::build_api_listings;
+use crate::listing::{DutyCategory, PartyFinderListing, PartyMember};
+use crate::web::handlers::UploadablePartyDetail;
+
+const PLD: u8 = 19;
+const WHM: u8 = 24;
+const SAM: u8 = 34;
+
+fn member(content_id: u64, job_id: u8, slot_index: u8) -> PartyMember {
+    PartyMember { content_id, job_id, slot_index }
+}
+
+/// 파티장(1001, 팔라딘) / 빈 자리 / 백마도사(1002) / 사무라이(1003), 위치 목록은 빈 자리를 빼고 보낸 이전 플러그인 형태
+fn builder() -> ListingBuilder {
+    ListingBuilder::new(1)
+        .duty(SAVAGE, DutyCategory::HighEndDuty)
+        .member(1001, PLD)
+        .job(0)
+        .member(1002, WHM)
+        .member(1003, SAM)
+}
+
+fn member_slots(listing: &PartyFinderListing) -> Vec<(u64, u8)> {
+    listing.member_slots().collect()
+}
+
+#[test]
+fn slot_mapping_is_preferred_over_positional_ids() {
+    // 위치 목록이 자리와 어긋나 있어도 자리별 멤버를 따름
+    let listing = builder()
+        .members(&[member(1001, PLD, 0), member(1002, WHM, 2), member(1003, SAM, 3)])
+        .member_ids([1001, 1002, 1003])
+        .listing();
+    assert_eq!(member_slots(&listing), vec![(1001, PLD), (1002, WHM), (1003, SAM)]);
+}
+
+#[test]
+fn members_whose_slot_changed_are_dropped() {
+    let mut listing = builder()
+        .members(&[member(1001, PLD, 0), member(1002, WHM, 2), member(1003, SAM, 3), member(1004, WHM, 7)])
+        .listing();
+    // 디테일 이후 사무라이 자리가 비었음 (8번째 자리는 원래 비어 있음)
+    listing.jobs_present[3] = 0;
+    assert_eq!(member_slots(&listing), vec![(1001, PLD), (1002, WHM)]);
+}
+
+#[test]
+fn documents_without_slot_mapping_fall_back_to_positions() {
+    let listing = builder().listing();
+    assert!(listing.members.is_empty());
+    assert_eq!(member_slots(&listing), vec![(1001, PLD), (1002, WHM), (1003, SAM)]);
+
+    // 자리별 멤버 필드가 없는 기존 문서
+    let stored: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    assert!(stored.members.is_empty());
+}
+
+#[test]
+fn detail_upload_members_are_optional() {
+    let old: UploadablePartyDetail = serde_json::from_str(
+        r#"{"listing_id":1,"leader_content_id":1001,"leader_name":"A","home_world":73,"member_content_ids":[1001]}"#,
+    )
+    .unwrap();
+    assert!(old.members.is_empty());
+
+    let new: UploadablePartyDetail = serde_json::from_str(
+        r#"{"listing_id":1,"leader_content_id":1001,"leader_name":"A","home_world":73,"member_content_ids":[1001],
+            "members":[{"content_id":1001,"job_id":19,"slot_index":0}]}"#,
+    )
+    .unwrap();
+    assert_eq!(new.members, vec![member(1001, PLD, 0)]);
+}
+
+#[test]
+fn api_members_match_slots_filled() {
+    let mut listing = builder()
+        .members(&[member(1002, WHM, 2), member(1001, PLD, 0), member(1003, SAM, 3)])
+        .member_ids([1001, 1002, 1003])
+        .build();
+    listing.listing.members.sort_by_key(|member| member.slot_index);
+
+    let api = build_api_listings(vec![listing], &HashMap::new(), &HashMap::new(), FixedOffset::east_opt(0).unwrap());
+    let value = serde_json::to_value(&api).unwrap();
+    let listing = &value[0]["listing"];
+
+    let members: Vec<&Value> = listing["members"].as_array().unwrap().iter().map(|m| &m["content_id"]).collect();
+    assert_eq!(members, vec![1001, 1002, 1003]);
+    let filled: Vec<&Value> = listing["slots_filled"].as_array().unwrap().iter().filter(|job| !job.is_null()).collect();
+    assert_eq!(filled, vec!["PLD", "WHM", "SAM"]);
+    let icons: Vec<&Value> = listing["members"].as_array().unwrap().iter().map(|m| &m["icon_url"]).collect();
+    let slot_icons: Vec<&Value> = listing["slots_filled_icon_urls"].as_array().unwrap().iter().filter(|url| !url.is_null()).collect();
+    assert_eq!(icons, slot_icons);
+}
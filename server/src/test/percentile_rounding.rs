@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use chrono::{FixedOffset, Utc};
+
+use crate::api::build_api_listings;
+use crate::fflogs::mapping::{display_percentile, percentile_color_class};
+use crate::fflogs::{EncounterParse, ParseCacheDoc, ZoneCache, DUTY_TO_FFLOGS};
+use crate::listing::PartyFinderListing;
+use crate::listing_container::QueriedListing;
+use crate::player::Player;
+use crate::web::handlers::build_renderable_listings;
+
+const PLD: u8 = 19;
+
+/// (저장된 percentile, 표시 값, 색상 클래스)
+const GRID: [(f32, Option<u8>, &str); 14] = [
+    (-1.0, None, "parse-none"),
+    (f32::NAN, None, "parse-none"),
+    (0.0, Some(0), "parse-gray"),
+    (0.4, Some(0), "parse-gray"),
+    (24.5, Some(25), "parse-green"),
+    (49.49, Some(49), "parse-green"),
+    (74.5, Some(75), "parse-purple"),
+    (94.4, Some(94), "parse-purple"),
+    (94.6, Some(95), "parse-orange"),
+    (98.5, Some(99), "parse-pink"),
+    (98.9, Some(99), "parse-pink"),
+    (99.5, Some(100), "parse-gold"),
+    (100.0, Some(100), "parse-gold"),
+    (100.2, Some(100), "parse-gold"),
+];
+
+#[test]
+fn rounding_rule_and_color_agree() {
+    for (percentile, shown, class) in GRID {
+        assert_eq!(display_percentile(percentile), shown, "{}", percentile);
+        assert_eq!(percentile_color_class(percentile), class, "{}", percentile);
+    }
+}
+
+/// 모집글마다 멤버 하나 (id = 모집글 id = 파티장)와 격자 값 하나
+fn queried(id: u32, duty: u16) -> QueriedListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.id = id;
+    listing.duty = duty;
+    listing.member_content_ids = vec![i64::from(id)];
+    listing.leader_content_id = u64::from(id);
+    listing.jobs_present = vec![PLD];
+
+    super::queried(listing)
+}
+
+#[test]
+fn html_and_api_show_the_same_percentile() {
+    let (&duty, info) = DUTY_TO_FFLOGS.iter().min_by_key(|(duty, _)| **duty).unwrap();
+    let zone_cache = |percentile| ZoneCache {
+        fetched_at: Utc::now(),
+        encounters: maplit::hashmap! {
+            info.encounter_id.to_string() => EncounterParse { percentile, job_id: 0, all_stars: None, jobs: Default::default() },
+        },
+    };
+
+    let ids = 1..=GRID.len() as u32;
+    let listings = || ids.clone().map(|id| queried(id, duty)).collect::<Vec<_>>();
+    let players: HashMap<u64, Player> = ids
+        .clone()
+        .map(|id| {
+            let player = Player {
+                content_id: u64::from(id),
+                name: format!("Member {}", id),
+                home_world: 73,
+                last_seen: Utc::now(),
+                seen_count: 1,
+            };
+            (u64::from(id), player)
+        })
+        .collect();
+    let parse_docs: HashMap<u64, ParseCacheDoc> = ids
+        .clone()
+        .zip(GRID)
+        .map(|(id, (percentile, _, _))| {
+            let doc = ParseCacheDoc {
+                content_id: i64::from(id),
+                zones: maplit::hashmap! { info.zone_id.to_string() => zone_cache(percentile) },
+                fetch: None,
+            };
+            (u64::from(id), doc)
+        })
+        .collect();
+    let html: HashMap<u32, _> = build_renderable_listings(listings(), &players, &parse_docs)
+        .into_iter()
+        .map(|renderable| (renderable.container.listing.id, renderable))
+        .collect();
+    let api = serde_json::to_value(build_api_listings(
+        listings(),
+        &players,
+        &parse_docs,
+        FixedOffset::east_opt(0).unwrap(),
+    ))
+    .unwrap();
+    let api: HashMap<u64, &serde_json::Value> = api
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|container| (container["listing"]["id"].as_u64().unwrap(), &container["listing"]["members"][0]))
+        .collect();
+
+    for (id, (percentile, shown, class)) in ids.zip(GRID) {
+        let renderable = &html[&id];
+        let member = &renderable.members[0].parse;
+        let api_member = api[&u64::from(id)];
+
+        assert_eq!(member.primary_percentile, shown, "{}", percentile);
+        assert_eq!(member.primary_color_class, class, "{}", percentile);
+        assert_eq!(renderable.leader_parse.primary_percentile, shown, "{}", percentile);
+        assert_eq!(renderable.leader_parse.primary_color_class, class, "{}", percentile);
+
+        assert_eq!(api_member["parse_percentile"], serde_json::json!(shown), "{}", percentile);
+        assert_eq!(api_member["parse_color_class"], class, "{}", percentile);
+    }
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+
+use crate::fflogs::{repoint_parse_doc, FetchAccounting, ParseCacheDoc, ZoneCache};
+use mongodb::bson::{self, doc};
+
+use super::mongo_eval::Collection;
+use crate::mongo::merge_player_update;
+use crate::player::compaction::{normalized_name, plan_merges, still_duplicates, PlayerMerge, SkippedDuplicate};
+use crate::player::Player;
+
+/// 전체 ContentID (하위 32비트: `LOWER`)
+const FULL: u64 = 0x0040_0000_1234_5678;
+const LOWER: u64 = 0x1234_5678;
+
+fn at(hours: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 8, 1, 0, 0, 0).unwrap() + TimeDelta::try_hours(hours).unwrap()
+}
+
+fn player(content_id: u64, name: &str, home_world: u16, last_seen: DateTime<Utc>, seen_count: u32) -> Player {
+    Player {
+        content_id,
+        name: name.to_string(),
+        home_world,
+        last_seen,
+        seen_count,
+    }
+}
+
+fn zone(fetched_at: DateTime<Utc>) -> ZoneCache {
+    ZoneCache {
+        fetched_at,
+        encounters: Default::default(),
+    }
+}
+
+#[test]
+fn names_are_compared_without_case_or_extra_spaces() {
+    assert_eq!(normalized_name("  Alphinaud   Leveilleur "), "alphinaud leveilleur");
+    assert_eq!(normalized_name("ALPHINAUD Leveilleur"), normalized_name("Alphinaud Leveilleur"));
+}
+
+#[test]
+fn truncated_id_merges_into_full_id() {
+    let players = [
+        player(FULL, "Alphinaud Leveilleur", 73, at(0), 10),
+        player(LOWER, "alphinaud  leveilleur", 73, at(5), 3),
+        // 이름이나 서버가 다르면 같은 캐릭터로 보지 않음
+        player(0x0040_0000_0000_0001, "Alisaie Leveilleur", 73, at(0), 1),
+        player(0x0000_0001, "Alisaie Leveilleur", 74, at(0), 1),
+        player(0x0040_0000_0000_0002, "Estinien Varlineau", 73, at(0), 1),
+        player(0x0000_0002, "Estinien Wyrmblood", 73, at(0), 1),
+    ];
+
+    let plan = plan_merges(&players);
+    assert_eq!(
+        plan.merges,
+        vec![PlayerMerge {
+            target: FULL,
+            duplicate: LOWER,
+            name: "alphinaud  leveilleur".to_string(),
+            home_world: 73,
+        }],
+    );
+    assert!(plan.skipped.is_empty());
+
+    assert!(still_duplicates(&players[0], &players[1]));
+}
+
+/// `merge_player_record`와 같은 조건 / 갱신으로 중복 문서를 합침
+fn merge(collection: &mut Collection, duplicate: &Player) -> bool {
+    let filter = doc! { "content_id": FULL as i64, "merged_from": { "$ne": duplicate.content_id as i64 } };
+    collection.update_one(&filter, &merge_player_update(duplicate), false)
+}
+
+#[test]
+fn merge_adds_to_the_stored_record() {
+    let target = player(FULL, "Alphinaud Leveilleur", 73, at(5), 10);
+    let duplicate = player(LOWER, "alphinaud  leveilleur", 73, at(2), 3);
+    let mut collection = Collection { docs: vec![bson::to_document(&target).unwrap()] };
+
+    // 계획한 뒤 대상 문서에 업로드가 들어옴
+    collection.docs[0].insert("seen_count", 11_i64);
+    assert!(merge(&mut collection, &duplicate));
+    let merged: Player = bson::from_document(collection.docs[0].clone()).unwrap();
+    assert_eq!(merged.name, "Alphinaud Leveilleur");
+    assert_eq!(merged.seen_count, 14);
+    assert_eq!(merged.last_seen, at(5));
+
+    // 다시 실행해도 두 번 더하지 않음
+    assert!(!merge(&mut collection, &duplicate));
+    let merged: Player = bson::from_document(collection.docs[0].clone()).unwrap();
+    assert_eq!(merged.seen_count, 14);
+
+    // 중복 쪽이 더 최근에 관측됨
+    let later = player(LOWER + 1, "alphinaud  leveilleur", 73, at(9), 1);
+    merge(&mut collection, &later);
+    let merged: Player = bson::from_document(collection.docs[0].clone()).unwrap();
+    assert_eq!(merged.last_seen, at(9));
+}
+
+#[test]
+fn ambiguous_candidates_are_skipped() {
+    let other = 0x0041_0000_1234_5678;
+    let players = [
+        player(FULL, "Alphinaud Leveilleur", 73, at(0), 10),
+        player(other, "Alphinaud Leveilleur", 73, at(0), 2),
+        player(LOWER, "Alphinaud Leveilleur", 73, at(0), 3),
+    ];
+
+    let plan = plan_merges(&players);
+    assert!(plan.merges.is_empty());
+    assert_eq!(
+        plan.skipped,
+        vec![SkippedDuplicate {
+            content_id: LOWER,
+            name: "Alphinaud Leveilleur".to_string(),
+            home_world: 73,
+            candidates: vec![FULL, other],
+        }],
+    );
+}
+
+/// 계획 후 이름이 바뀌면 병합하지 않음
+#[test]
+fn changed_documents_are_not_merged() {
+    let target = player(FULL, "Alphinaud Leveilleur", 73, at(0), 10);
+    assert!(!still_duplicates(&target, &player(LOWER, "Alisaie Leveilleur", 73, at(0), 1)));
+    assert!(!still_duplicates(&target, &player(LOWER, "Alphinaud Leveilleur", 74, at(0), 1)));
+    assert!(!still_duplicates(&target, &player(LOWER + 1, "Alphinaud Leveilleur", 73, at(0), 1)));
+}
+
+#[test]
+fn parse_caches_move_to_the_full_id() {
+    let accounting = |attempt_count| FetchAccounting { attempt_count, last_attempt_at: at(0), ..Default::default() };
+    let duplicate = ParseCacheDoc {
+        content_id: LOWER as i64,
+        zones: maplit::hashmap! {
+            "62".to_string() => zone(at(1)),
+            "73".to_string() => zone(at(5)),
+        },
+        fetch: Some(accounting(4)),
+    };
+
+    // 대상 문서가 없으면 전부 옮김
+    let repoint = repoint_parse_doc(None, &duplicate);
+    assert_eq!(repoint.zones_to_write, duplicate.zones);
+    assert_eq!(repoint.fetch, Some(accounting(4)));
+
+    // 대상 문서의 더 최신 Zone과 조회 기록은 유지
+    let target = ParseCacheDoc {
+        content_id: FULL as i64,
+        zones: maplit::hashmap! { "73".to_string() => zone(at(9)) },
+        fetch: Some(accounting(1)),
+    };
+    let repoint = repoint_parse_doc(Some(&target), &duplicate);
+    assert_eq!(repoint.zones_to_write, maplit::hashmap! { "62".to_string() => zone(at(1)) });
+    assert_eq!(repoint.fetch, None);
+}
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use mongodb::bson::{self, doc};
+use serde_json::json;
+use sestring::SeString;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use super::fixture_world::{ListingBuilder, SAVAGE_SPLIT};
+use crate::api::players::{HistoryQuery, HostedListingRecord};
+use crate::config::{Config, Logging};
+use crate::listing::history::{hosted_listings, ArchivedListing, HostedListing, MAX_HISTORY_LIMIT};
+use crate::listing::{DutyCategory, PartyFinderListing};
+use crate::listing_container::ListingContainer;
+use crate::mongo::hosted_listings_filter;
+use crate::web::metrics::route_label;
+
+const HOST: u64 = 18_014_398_509_481_985;
+/// 하위 32비트가 `HOST`와 같은 다른 플레이어
+const OTHER_HOST: u64 = HOST + (1 << 32);
+
+fn at(minutes: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap() + TimeDelta::try_minutes(minutes).unwrap()
+}
+
+fn listing(id: u32) -> ListingBuilder {
+    ListingBuilder::new(id).duty(SAVAGE_SPLIT, DutyCategory::None).content_id_lower(HOST as u32)
+}
+
+/// `PendingArchive::update`로 쓴 보관 기록과 같은 BSON 표현
+fn archived(id: u32, created: i64) -> ArchivedListing {
+    let listing = listing(id).listing();
+    bson::from_document(doc! {
+        "created_at": at(created),
+        "first_seen": at(created),
+        "last_seen": at(created + 30),
+        "listing": {
+            "id": listing.id,
+            "last_server_restart": listing.last_server_restart,
+            "created_world": listing.created_world as u32,
+            "content_id_lower": bson::to_bson(&listing.content_id_lower).unwrap(),
+            "category": bson::to_bson(&listing.category).unwrap(),
+            "duty": bson::to_bson(&listing.duty).unwrap(),
+            "duty_type": bson::to_bson(&listing.duty_type).unwrap(),
+        },
+        "canonical_category": listing.canonical_category() as u32 as i64,
+        "description_length": 0_i64,
+    })
+    .unwrap()
+}
+
+fn live(listing: PartyFinderListing, created: i64) -> ListingContainer {
+    ListingContainer {
+        created_at: at(created),
+        updated_at: at(created + 5),
+        listing,
+        upload_count: 1,
+        uploader_fingerprints: Vec::new(),
+        description_hash: None,
+        description_text: None,
+        description_history: Vec::new(),
+        description_language: None,
+        has_autotranslate: false,
+        unconfirmed_at: None,
+        canonical_category: None,
+        hidden: false,
+        flagged: false,
+        slots_history: Vec::new(),
+    }
+}
+
+fn ids(listings: &[HostedListing]) -> Vec<u32> {
+    listings.iter().map(|listing| listing.id).collect()
+}
+
+#[test]
+fn archive_and_live_listings_merge_most_recent_first() {
+    let current = listing(3).description(SeString::parse("Weekly reclears".as_bytes()).unwrap()).leader(HOST).listing();
+    // 아직 보관 대기열에 있는 모집글
+    let queued = listing(4).listing();
+
+    let hosted = hosted_listings(
+        HOST,
+        vec![archived(1, 0), archived(3, 60), archived(2, 30)],
+        &[live(current, 61), live(queued, 90)],
+        MAX_HISTORY_LIMIT,
+    );
+    assert_eq!(ids(&hosted), vec![4, 3, 2, 1]);
+
+    // 같은 모집글은 남아 있는 쪽의 설명과 더 이른 처음 본 시각
+    assert_eq!(hosted[1].description.as_deref(), Some("Weekly reclears"));
+    assert_eq!((hosted[1].created_at, hosted[1].last_seen), (at(60), at(90)));
+    assert_eq!(hosted[2].description, None);
+
+    assert_eq!(ids(&hosted_listings(HOST, vec![archived(1, 0), archived(2, 30)], &[], 1)), vec![2]);
+}
+
+#[test]
+fn listings_of_another_leader_with_the_same_lower_id_are_excluded() {
+    let other = listing(2).leader(OTHER_HOST).listing();
+    let led = listing(3).leader(HOST).content_id_lower(0).listing();
+
+    let hosted = hosted_listings(HOST, vec![archived(1, 0), archived(2, 30)], &[live(other.clone(), 30), live(led, 40)], 10);
+    assert_eq!(ids(&hosted), vec![3, 1]);
+
+    let hosted = hosted_listings(OTHER_HOST, vec![archived(1, 0), archived(2, 30)], &[live(other, 30)], 10);
+    assert_eq!(ids(&hosted), vec![2, 1]);
+}
+
+#[test]
+fn hosted_filter_matches_leader_or_lower_id() {
+    assert_eq!(
+        hosted_listings_filter(OTHER_HOST),
+        doc! {
+            "$or": [
+                { "listing.leader_content_id": OTHER_HOST as i64 },
+                { "listing.content_id_lower": (HOST as u32) as i64 },
+            ]
+        }
+    );
+}
+
+#[test]
+fn limit_defaults_and_is_capped() {
+    assert_eq!(HistoryQuery::default().limit(), 20);
+    assert_eq!(HistoryQuery { limit: Some(500) }.limit(), MAX_HISTORY_LIMIT);
+    assert_eq!(HistoryQuery { limit: Some(0) }.limit(), 1);
+}
+
+#[test]
+fn records_reuse_the_api_duty_info() {
+    let hosted = hosted_listings(HOST, vec![archived(1, 0)], &[], 10);
+    let record = serde_json::to_value(HostedListingRecord::from(hosted[0].clone())).unwrap();
+
+    assert_eq!(record["category"], json!("None"));
+    assert_eq!(record["canonical_category"], json!("HighEndDuty"));
+    assert_eq!(record["duty_type"], json!("Normal"));
+    assert_eq!(record["duty_info"]["id"], json!(1075));
+    assert_eq!(record["duty_info"]["content_kind"], json!("Raids"));
+    assert_eq!(record["description"], json!(null));
+    assert_eq!(record["created_at"], json!("2026-03-01T12:00:00Z"));
+}
+
+#[tokio::test]
+async fn zero_content_id_is_rejected() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let state = crate::web::State::new(Arc::new(config), log_handle).await.unwrap();
+
+    let response = warp::test::request()
+        .path("/api/players/0/listings")
+        .reply(&crate::web::routes::router(state))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(route_label("/api/players/18014398509481985/listings"), "/api/players/{id}/listings");
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use crate::api::players::{
+    lookup_records, parse_lookup_request, LookupError, PlayerDetails, PlayerSearch, PlayersQuery, LOOKUP_CACHE_CONTROL,
+    MAX_LOOKUP_IDS, MAX_QUERY_IDS,
+};
+use crate::config::{Config, Logging};
+use crate::player::Player;
+use crate::web::routes::router;
+use crate::web::State;
+
+fn player(content_id: u64, name: &str) -> Player {
+    Player {
+        content_id,
+        name: name.to_string(),
+        home_world: 73,
+        last_seen: Utc::now(),
+        seen_count: 3,
+    }
+}
+
+#[test]
+fn request_ids_are_validated_and_deduplicated() {
+    let parse = |body: &str| parse_lookup_request(body.as_bytes());
+
+    assert_eq!(parse(r#"{"content_ids": [3, "18014398509481985", 3]}"#), Ok(vec![3, 18014398509481985]));
+    assert_eq!(parse(r#"{"content_ids": []}"#), Ok(Vec::new()));
+
+    for body in [
+        r#"{"content_ids": [0]}"#,
+        r#"{"content_ids": [-1]}"#,
+        r#"{"content_ids": [1.5]}"#,
+        r#"{"content_ids": ["abc"]}"#,
+        r#"{"content_ids": [null]}"#,
+        r#"{"ids": [1]}"#,
+        "not json",
+    ] {
+        assert!(matches!(parse(body), Err(LookupError::Malformed(_))), "{}", body);
+    }
+
+    let ids: Vec<usize> = (1..=MAX_LOOKUP_IDS + 1).collect();
+    let body = serde_json::json!({ "content_ids": ids }).to_string();
+    assert_eq!(parse(&body), Err(LookupError::TooMany(MAX_LOOKUP_IDS + 1)));
+}
+
+#[test]
+fn records_follow_the_request_and_omit_unlisted_players() {
+    let players = vec![player(2, "Beta Tester"), player(1, "Alpha Tester"), player(4, "")];
+    let records = serde_json::to_value(lookup_records(&[1, 3, 4, 2], players)).unwrap();
+
+    let records = records.as_array().unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["content_id"], 1);
+    assert_eq!(records[0]["name"], "Alpha Tester");
+    assert_eq!(records[0]["home_world"], serde_json::json!({ "id": 73, "name": "Adamantoise" }));
+    assert!(records[0]["last_seen"].is_string());
+    assert!(records[0].get("seen_count").is_none());
+    assert_eq!(records[1]["content_id"], 2);
+}
+
+#[tokio::test]
+async fn route_limits_and_caching() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let routes = router(State::new(Arc::new(config), log_handle).await.unwrap());
+
+    let lookup = |body: String| warp::test::request().method("POST").path("/api/players/lookup").body(body);
+
+    let ids: Vec<usize> = (1..=MAX_LOOKUP_IDS + 1).collect();
+    let response = lookup(serde_json::json!({ "content_ids": ids }).to_string()).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    // 본문 크기 제한도 같은 응답
+    let response = lookup("[".repeat(64 * 1024)).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let response = lookup(r#"{"content_ids": ["abc"]}"#.into()).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 빈 요청은 DB를 조회하지 않음
+    let response = lookup(r#"{"content_ids": []}"#.into()).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["cache-control"], LOOKUP_CACHE_CONTROL);
+    assert_eq!(response.body().as_ref(), br#"{"players":[]}"#);
+}
+
+#[test]
+fn search_queries_pick_one_lookup() {
+    let query = |content_ids: Option<&str>, name: Option<&str>, world: Option<&str>| PlayersQuery {
+        content_ids: content_ids.map(str::to_string),
+        name: name.map(str::to_string),
+        world: world.map(str::to_string),
+    };
+
+    assert_eq!(query(Some("3, 1,3"), None, None).search(), Ok(PlayerSearch::ContentIds(vec![3, 1])));
+    assert_eq!(
+        query(None, Some(" Alpha Tester "), Some("tonberry")).search(),
+        Ok(PlayerSearch::Name { name: "Alpha Tester".to_string(), home_world: Some(72) })
+    );
+    assert_eq!(
+        query(None, Some("Alpha Tester"), None).search(),
+        Ok(PlayerSearch::Name { name: "Alpha Tester".to_string(), home_world: None })
+    );
+
+    let ids: Vec<String> = (1..=MAX_QUERY_IDS + 1).map(|id| id.to_string()).collect();
+    for invalid in [
+        query(Some(&ids.join(",")), None, None),
+        query(Some("1,abc"), None, None),
+        query(Some("0"), None, None),
+        query(Some("1"), Some("Alpha Tester"), None),
+        query(Some("1"), None, Some("Tonberry")),
+        query(None, Some("Alpha Tester"), Some("Nowhere")),
+        query(None, None, Some("Tonberry")),
+        query(Some(""), Some(" "), None),
+    ] {
+        assert!(matches!(invalid.search(), Err(LookupError::Malformed(_))), "{:?}", invalid);
+    }
+}
+
+#[test]
+fn search_results_include_the_seen_count() {
+    let records: Vec<PlayerDetails> = vec![player(1, "Alpha Tester")].into_iter().map(PlayerDetails::from).collect();
+    let records = serde_json::to_value(records).unwrap();
+    assert_eq!(records[0]["content_id"], 1);
+    assert_eq!(records[0]["home_world"]["name"], "Adamantoise");
+    assert_eq!(records[0]["seen_count"], 3);
+}
+
+#[tokio::test]
+async fn search_route_rejects_bad_queries() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let routes = router(State::new(Arc::new(config), log_handle).await.unwrap());
+
+    let ids: Vec<String> = (1..=MAX_QUERY_IDS + 1).map(|id| id.to_string()).collect();
+    for path in [
+        format!("/api/players?content_ids={}", ids.join(",")),
+        "/api/players?name=Alpha%20Tester&world=Nowhere".to_string(),
+        "/api/players".to_string(),
+    ] {
+        let response = warp::test::request().path(&path).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", path);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert!(body["error"].is_string());
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::{TimeZone, Utc};
+
+use crate::api::parses::PlayerParses;
+use crate::fflogs::mapping::encounter_by_id;
+use crate::fflogs::{AllStars, EncounterParse, ParseCacheDoc, ZoneCache};
+
+fn parse(percentile: f32, job_id: u8) -> EncounterParse {
+    EncounterParse { percentile, job_id, all_stars: None, jobs: Default::default() }
+}
+
+fn doc() -> ParseCacheDoc {
+    let fetched_at = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+    ParseCacheDoc {
+        content_id: 4_000_000_001,
+        zones: maplit::hashmap! {
+            "73".to_string() => ZoneCache {
+                fetched_at,
+                encounters: maplit::hashmap! {
+                    "105".to_string() => parse(-1.0, 0),
+                    "101".to_string() => EncounterParse {
+                        all_stars: Some(AllStars { points: 120.5, rank: 42 }),
+                        ..parse(94.6, 19)
+                    },
+                },
+            },
+            "72".to_string() => ZoneCache { fetched_at, encounters: maplit::hashmap! {} },
+            "9999".to_string() => ZoneCache {
+                fetched_at,
+                encounters: maplit::hashmap! { "1".to_string() => parse(50.0, 0) },
+            },
+        },
+        fetch: None,
+    }
+}
+
+#[test]
+fn encounters_are_found_by_zone_and_encounter_id() {
+    let (info, secondary) = encounter_by_id(73, 101).unwrap();
+    assert_eq!((info.name, secondary), ("AAC Heavyweight M1 (Savage)", false));
+
+    let (info, secondary) = encounter_by_id(73, 105).unwrap();
+    assert_eq!((info.encounter_id, secondary), (104, true));
+
+    assert!(encounter_by_id(72, 101).is_none());
+}
+
+#[test]
+fn cached_parses_are_listed_by_zone_with_names() {
+    let body = serde_json::to_value(PlayerParses::new(&doc(), None)).unwrap();
+
+    assert_eq!(body["content_id"], 4_000_000_001u64);
+    let zones = body["zones"].as_array().unwrap();
+    let ids: Vec<_> = zones.iter().map(|zone| zone["zone_id"].as_u64().unwrap()).collect();
+    assert_eq!(ids, [72, 73, 9999]);
+
+    assert_eq!(zones[1]["name"], "AAC Heavyweight (Savage)");
+    assert_eq!(zones[1]["fetched_at"], "2026-01-01T12:00:00Z");
+    assert_eq!(
+        zones[1]["encounters"],
+        serde_json::json!([
+            {
+                "encounter_id": 101,
+                "name": "AAC Heavyweight M1 (Savage)",
+                "secondary": false,
+                "percentile": 95,
+                "job": "PLD",
+                "all_stars": { "points": 120.5, "rank": 42 },
+            },
+            {
+                "encounter_id": 105,
+                "name": "AAC Heavyweight M4 (Savage)",
+                "secondary": true,
+                "percentile": null,
+                "job": null,
+            },
+        ])
+    );
+
+    // 매핑에 없는 Zone / Encounter도 기록은 그대로 보여줌
+    assert_eq!(zones[2]["name"], serde_json::Value::Null);
+    assert_eq!(zones[2]["encounters"][0]["name"], serde_json::Value::Null);
+}
+
+#[test]
+fn zone_filter_returns_a_single_zone() {
+    let parses = PlayerParses::new(&doc(), Some(73));
+    assert_eq!(parses.zones.len(), 1);
+    assert_eq!(parses.zones[0].zone_id, 73);
+
+    assert!(PlayerParses::new(&doc(), Some(68)).zones.is_empty());
+}
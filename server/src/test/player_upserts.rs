@@ -0,0 +1,68 @@
+use chrono::{TimeZone, Utc};
+use mongodb::bson::doc;
+
+use crate::mongo::{player_upserts, successful_upserts, upsert_players};
+use crate::player::{Player, UploadablePlayer};
+
+fn player(content_id: u64, name: &str, home_world: u16) -> UploadablePlayer {
+    UploadablePlayer {
+        content_id,
+        name: name.to_string(),
+        home_world,
+    }
+}
+
+#[test]
+fn invalid_and_repeated_players_are_dropped() {
+    let now = Utc.with_ymd_and_hms(2026, 1, 5, 12, 0, 0).unwrap();
+    let players = [
+        player(1, "Alpha Tank", 73),
+        player(0, "No Id", 73),
+        player(2, "", 73),
+        player(3, "Cloud World", 1_000),
+        player(4, "Delta Scholar", 49),
+        player(1, "Alpha Renamed", 79),
+    ];
+
+    let updates = player_upserts(&players, now);
+    assert_eq!(updates.len(), 2);
+    assert_eq!(
+        updates[0],
+        doc! {
+            "q": { "content_id": 1_i64 },
+            "u": {
+                "$set": { "name": "Alpha Renamed", "home_world": 79_u32, "last_seen": now },
+                "$inc": { "seen_count": 1 },
+                "$setOnInsert": { "content_id": 1_i64 },
+            },
+            "upsert": true,
+        }
+    );
+    assert_eq!(updates[1].get_document("q").unwrap(), &doc! { "content_id": 4_i64 });
+}
+
+#[test]
+fn failed_statements_are_not_counted() {
+    let all_ok = doc! { "n": 3, "nModified": 1, "upserted": [{ "index": 0, "_id": 1 }, { "index": 2, "_id": 2 }], "ok": 1.0 };
+    assert_eq!(successful_upserts(3, &all_ok), 3);
+
+    // 순서 없는 일괄 처리는 실패한 문만 빠짐
+    let partial = doc! {
+        "n": 2,
+        "nModified": 0,
+        "writeErrors": [{ "index": 1, "code": 11000, "errmsg": "E11000 duplicate key error" }],
+        "ok": 1.0,
+    };
+    assert_eq!(successful_upserts(3, &partial), 2);
+}
+
+/// 명령 자체가 실패하면 0이 아니라 에러
+#[tokio::test]
+async fn unreachable_database_is_an_error() {
+    let client = mongodb::Client::with_uri_str("mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100").await.unwrap();
+    let collection = client.database("rpf_test").collection::<Player>("players");
+
+    assert!(upsert_players(collection.clone(), &[player(1, "Alpha Tank", 73)]).await.is_err());
+    // 보낼 것이 없으면 DB에 묻지 않음
+    assert_eq!(upsert_players(collection, &[player(0, "No Id", 73)]).await.unwrap(), 0);
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+
+use super::fixture_world::{ListingBuilder, SAVAGE};
+use crate::config::EmptyBackoff;
+use crate::ffxiv::Language;
+use crate::fflogs::{EncounterParse, FetchAccounting, ParseCacheDoc, ProfileMiss, ZoneCache, DUTY_TO_FFLOGS};
+use crate::listing::DutyCategory;
+use crate::template::listings::ListingsTemplate;
+use crate::web::handlers::build_renderable_listings;
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 6, 12, 0, 0).unwrap()
+}
+
+fn hours(n: i64) -> TimeDelta {
+    TimeDelta::try_hours(n).unwrap()
+}
+
+#[test]
+fn misses_use_the_long_backoff_until_the_character_is_found() {
+    let policy = EmptyBackoff::default();
+    let mut fetch = FetchAccounting::default();
+
+    // 첫 조회부터 일주일 간격
+    fetch.record_miss(ProfileMiss::Hidden, start());
+    assert_eq!(fetch.attempt_count, 1);
+    assert_eq!(fetch.profile, Some(ProfileMiss::Hidden));
+    // 캐릭터 단위이므로 모든 Zone에 해당
+    assert_eq!(fetch.empty_backoff_until(73, &policy), Some(start() + hours(24 * 7)));
+    assert!(fetch.in_empty_backoff(62, &policy, start() + hours(24 * 6)));
+
+    let policy: EmptyBackoff = toml::from_str("missing_hours = 48").unwrap();
+    assert_eq!(fetch.empty_backoff_until(73, &policy), Some(start() + hours(48)));
+
+    // 기록이 보이면 표시와 긴 간격 모두 해제
+    let now = start() + hours(48);
+    fetch.record(73, false, now);
+    assert_eq!(fetch.profile, None);
+    assert_eq!(fetch.empty_backoff_until(73, &policy), None);
+    assert_eq!(fetch.empty_backoff_until(62, &policy), None);
+}
+
+#[test]
+fn profile_miss_round_trips_through_bson() {
+    let fetch = FetchAccounting { profile: Some(ProfileMiss::Missing), ..Default::default() };
+    let doc = mongodb::bson::to_document(&fetch).unwrap();
+    assert_eq!(doc.get_str("profile").unwrap(), "missing");
+    assert_eq!(mongodb::bson::from_document::<FetchAccounting>(doc).unwrap(), fetch);
+
+    // 예전 문서에는 필드가 없음
+    let doc = mongodb::bson::to_document(&FetchAccounting::default()).unwrap();
+    assert!(!doc.contains_key("profile"));
+
+    // Zone별로 세기 전의 문서 (플레이어 단위 `consecutive_empty`는 무시)
+    let legacy = mongodb::bson::doc! {
+        "attempt_count": 5,
+        "last_attempt_at": mongodb::bson::DateTime::from_chrono(start()),
+        "consecutive_empty": 5,
+    };
+    let fetch: FetchAccounting = mongodb::bson::from_document(legacy).unwrap();
+    assert_eq!(fetch.attempt_count, 5);
+    assert!(fetch.zones.is_empty());
+}
+
+#[test]
+fn hidden_players_get_a_hidden_badge() {
+    let info = DUTY_TO_FFLOGS[&SAVAGE];
+    let doc = |content_id: u64, profile, percentile: Option<f32>| ParseCacheDoc {
+        content_id: content_id as i64,
+        zones: percentile
+            .map(|percentile| {
+                let encounters = maplit::hashmap! {
+                    info.encounter_id.to_string() => EncounterParse { percentile, job_id: 19, all_stars: None, jobs: Default::default() },
+                };
+                maplit::hashmap! { info.zone_id.to_string() => ZoneCache { fetched_at: start(), encounters } }
+            })
+            .unwrap_or_default(),
+        fetch: Some(FetchAccounting { profile: Some(profile), ..Default::default() }),
+    };
+    // 파티장은 비공개, 1002는 FFLogs에 없음, 1003은 비공개로 바꾸기 전 기록이 남음
+    let docs: HashMap<u64, ParseCacheDoc> = [
+        (1001, doc(1001, ProfileMiss::Hidden, None)),
+        (1002, doc(1002, ProfileMiss::Missing, None)),
+        (1003, doc(1003, ProfileMiss::Hidden, Some(80.0))),
+    ]
+    .into_iter()
+    .collect();
+    let listing = ListingBuilder::new(1)
+        .duty(SAVAGE, DutyCategory::HighEndDuty)
+        .leader(1001)
+        .member(1001, 21)
+        .member(1002, 19)
+        .member(1003, 24)
+        .build();
+
+    let containers = build_renderable_listings(vec![listing], &HashMap::new(), &docs);
+    let renderable = &containers[0];
+    assert!(renderable.leader_parse.hidden);
+    assert_eq!(renderable.leader_parse.primary_color_class, "parse-hidden");
+    let members: Vec<_> = renderable.members.iter().map(|member| (member.parse.hidden, member.parse.primary_percentile)).collect();
+    assert_eq!(members, [(true, None), (false, None), (false, Some(80))]);
+
+    let html = ListingsTemplate { containers, lang: Language::English, notice: None }.render().unwrap();
+    assert_eq!(html.matches(r#"class="parse parse-hidden""#).count(), 2);
+    assert_eq!(html.matches(r#"class="parse parse-none""#).count(), 1);
+}
@@ -0,0 +1,154 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use crate::config::{Config, Logging, RateLimit};
+use crate::web::rate_limit::RateLimiter;
+use crate::web::routes::router;
+use crate::web::State;
+
+const CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+const PROXY: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 40000);
+
+fn new_limiter(per_minute: u32, burst: Option<u32>) -> RateLimiter {
+    RateLimiter::new(&RateLimit {
+        contribute_per_minute: per_minute,
+        contribute_burst: burst,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn bursts_are_allowed_then_limited() {
+    let limiter = new_limiter(60, Some(3));
+    let start = Instant::now();
+
+    for _ in 0..3 {
+        assert_eq!(limiter.check(CLIENT, start), Ok(()));
+    }
+    assert_eq!(limiter.check(CLIENT, start), Err(Duration::from_secs(1)));
+    // 다른 주소는 따로 계산
+    assert_eq!(limiter.check(IpAddr::V4(Ipv4Addr::LOCALHOST), start), Ok(()));
+
+    // 초당 한 개씩 다시 채워짐
+    let later = start + Duration::from_millis(1500);
+    assert_eq!(limiter.check(CLIENT, later), Ok(()));
+    let retry = limiter.check(CLIENT, later).unwrap_err();
+    assert!(retry > Duration::from_millis(400) && retry < Duration::from_millis(600), "{:?}", retry);
+
+    // 0이면 제한 없음
+    let unlimited = new_limiter(0, None);
+    assert!((0..1000).all(|_| unlimited.check(CLIENT, start).is_ok()));
+    assert!(unlimited.is_empty());
+}
+
+#[test]
+fn full_buckets_are_pruned() {
+    let limiter = new_limiter(60, Some(5));
+    let start = Instant::now();
+    limiter.check(CLIENT, start).unwrap();
+    for _ in 0..5 {
+        let _ = limiter.check(IpAddr::V4(Ipv4Addr::LOCALHOST), start);
+    }
+
+    assert_eq!(limiter.prune(start + Duration::from_secs(2)), 1);
+    assert_eq!(limiter.len(), 1);
+    assert_eq!(limiter.prune(start + Duration::from_secs(5)), 1);
+    assert!(limiter.is_empty());
+}
+
+async fn state(trust_forwarded_for: bool) -> Arc<State> {
+    let config: Config = toml::from_str(&format!(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+
+        [ratelimit]
+        contribute_per_minute = 2
+        trust_forwarded_for = {}
+        uploader_secret = "test"
+        "#,
+        trust_forwarded_for
+    ))
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    State::new(Arc::new(config), log_handle).await.unwrap()
+}
+
+/// 잘못된 본문은 제한을 거친 뒤 `400`이므로 DB 없이 횟수만 확인
+async fn contribute(state: &Arc<State>, path: &str, forwarded_for: &str) -> warp::http::Response<warp::hyper::body::Bytes> {
+    warp::test::request()
+        .method("POST")
+        .path(path)
+        .remote_addr(PROXY)
+        .header("x-forwarded-for", forwarded_for)
+        .body("not json")
+        .reply(&router(Arc::clone(state)))
+        .await
+}
+
+#[tokio::test]
+async fn limited_uploads_get_retry_after() {
+    let state = state(true).await;
+
+    // 업로드 경로끼리 같은 버킷을 씀
+    assert_eq!(contribute(&state, "/contribute", "192.0.2.1").await.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(contribute(&state, "/contribute/players", "192.0.2.1").await.status(), StatusCode::BAD_REQUEST);
+
+    let limited = contribute(&state, "/contribute/multiple", "198.51.100.7, 192.0.2.1").await;
+    assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(limited.headers()["retry-after"], "30");
+    assert_eq!(limited.body().as_ref(), br#"{"error":"too many requests"}"#);
+
+    // 프록시 뒤의 다른 클라이언트는 따로 계산
+    assert_eq!(contribute(&state, "/contribute/detail", "192.0.2.2").await.status(), StatusCode::BAD_REQUEST);
+}
+
+/// 클라이언트가 보낸 `X-Forwarded-For`에 주소를 바꿔 넣어도 프록시가 붙인 주소 기준
+#[tokio::test]
+async fn spoofed_forwarded_for_does_not_evade_the_limit() {
+    let state = state(true).await;
+
+    for spoofed in ["198.51.100.1", "198.51.100.2"] {
+        let forwarded_for = format!("{}, 192.0.2.1", spoofed);
+        assert_eq!(contribute(&state, "/contribute", &forwarded_for).await.status(), StatusCode::BAD_REQUEST);
+    }
+    let spoofed = contribute(&state, "/contribute", "198.51.100.3, 192.0.2.1").await;
+    assert_eq!(spoofed.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(state.contribute_limiter.len(), 1);
+}
+
+/// 헤더를 믿지 않으면 헤더를 바꿔도 연결 주소 기준
+#[tokio::test]
+async fn forwarded_for_is_ignored_unless_trusted() {
+    let state = state(false).await;
+
+    assert_eq!(contribute(&state, "/contribute", "192.0.2.1").await.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(contribute(&state, "/contribute", "192.0.2.2").await.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(contribute(&state, "/contribute", "192.0.2.3").await.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(state.contribute_limiter.len(), 1);
+}
+
+/// 업로더 지문 / 웹소켓 연결 제한도 같은 설정으로 주소를 정함
+#[tokio::test]
+async fn client_address_follows_the_forwarded_for_setting() {
+    async fn address(trust_forwarded_for: bool) -> Option<IpAddr> {
+        let ratelimit = RateLimit { trust_forwarded_for, ..Default::default() };
+        warp::test::request()
+            .remote_addr(PROXY)
+            .header("x-forwarded-for", "192.0.2.1")
+            .filter(&crate::web::fingerprint::client_address(&ratelimit))
+            .await
+            .unwrap()
+    }
+
+    assert_eq!(address(true).await, Some(CLIENT));
+    assert_eq!(address(false).await, Some(PROXY.ip()));
+}
@@ -0,0 +1,76 @@
+use chrono::{TimeDelta, TimeZone, Utc};
+
+use crate::api::admin::ListingDiagnostics;
+use crate::config::ListingSort;
+use crate::fflogs::mapping::DUTY_TO_FFLOGS;
+use crate::listing::{DutyCategory, PartyFinderListing};
+use crate::listing_container::ListingContainer;
+
+fn fixture() -> ListingContainer {
+    let updated_at = Utc.with_ymd_and_hms(2026, 3, 1, 12, 7, 42).unwrap();
+    ListingContainer {
+        created_at: updated_at,
+        updated_at,
+        listing: serde_json::from_str(super::LISTING).unwrap(),
+        upload_count: 1,
+        uploader_fingerprints: Vec::new(),
+        description_hash: None,
+        description_text: None,
+        description_history: Vec::new(),
+        description_language: None,
+        has_autotranslate: false,
+        unconfirmed_at: None,
+        canonical_category: None,
+        hidden: false,
+        flagged: false,
+        slots_history: Vec::new(),
+    }
+}
+
+#[test]
+fn time_left_matches_listing_query() {
+    let container = fixture();
+    let now = container.updated_at + TimeDelta::try_milliseconds(300_500).unwrap();
+
+    let diagnostics = ListingDiagnostics::new(&container, now, &ListingSort::default());
+
+    // seconds_remaining(3300) - 경과 시간(300.5초)
+    assert_eq!(diagnostics.time_left, 2999.5);
+    assert_eq!(diagnostics.updated_minute, Utc.with_ymd_and_hms(2026, 3, 1, 12, 5, 0).unwrap());
+    assert!(!diagnostics.private);
+
+    let later = now + TimeDelta::try_hours(1).unwrap();
+    let expired = ListingDiagnostics::new(&container, later, &ListingSort::default());
+    assert!(expired.time_left < 0.0);
+}
+
+#[test]
+fn derived_values_match_listing_methods() {
+    let container = fixture();
+    let listing: &PartyFinderListing = &container.listing;
+    let diagnostics = ListingDiagnostics::new(&container, Utc::now(), &ListingSort::default());
+
+    assert_eq!(diagnostics.pf_category, listing.html_pf_category());
+    assert_eq!(diagnostics.high_end, listing.high_end());
+    assert_eq!(diagnostics.content_kind, listing.content_kind());
+    assert_eq!(diagnostics.slots_filled, 1);
+    assert_eq!(diagnostics.jobs.len(), listing.jobs_present.len());
+    assert!(diagnostics.jobs[0].is_some());
+    assert_eq!(diagnostics.jobs[1], None);
+}
+
+#[test]
+fn fflogs_mapping_hit_matches_pipeline() {
+    let (&duty, info) = DUTY_TO_FFLOGS.iter().min_by_key(|(duty, _)| **duty).unwrap();
+
+    let mut container = fixture();
+    container.listing.category = DutyCategory::HighEndDuty;
+    container.listing.duty = duty;
+
+    let fflogs = ListingDiagnostics::new(&container, Utc::now(), &ListingSort::default()).fflogs.unwrap();
+    assert_eq!(fflogs.zone_id, info.zone_id);
+    assert_eq!(fflogs.encounter_id, info.encounter_id);
+
+    container.listing.duty = 0;
+    assert!(ListingDiagnostics::new(&container, Utc::now(), &ListingSort::default()).fflogs.is_none());
+}
@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use mongodb::options::{ReadPreference as Mode, SelectionCriteria};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+
+use crate::config::{Config, Logging, ReadPreference};
+use crate::web::listings_cache::ListingsCache;
+use crate::web::State;
+
+fn config(mongo: &str) -> Config {
+    toml::from_str(&format!(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        {}
+        "#,
+        mongo
+    ))
+    .unwrap()
+}
+
+async fn build_state(mongo: &str) -> Arc<State> {
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    State::new(Arc::new(config(mongo)), log_handle).await.unwrap()
+}
+
+fn secondary_preferred(max_staleness: Option<Duration>) -> Option<SelectionCriteria> {
+    let options = mongodb::options::ReadPreferenceOptions::builder().max_staleness(max_staleness).build();
+    Some(SelectionCriteria::ReadPreference(Mode::SecondaryPreferred { options }))
+}
+
+#[test]
+fn read_preference_settings() {
+    let primary = config("");
+    assert_eq!(primary.mongo.read_preference, ReadPreference::Primary);
+    assert_eq!(primary.mongo.read_criteria().unwrap(), None);
+    assert!(!primary.mongo.reads_may_lag());
+
+    let secondary = config(r#"read_preference = "secondaryPreferred"
+        max_staleness_secs = 120"#);
+    assert_eq!(secondary.mongo.read_criteria().unwrap(), secondary_preferred(Some(Duration::from_secs(120))));
+    assert!(secondary.mongo.reads_may_lag());
+
+    let preferred = config(r#"read_preference = "primaryPreferred""#);
+    assert!(matches!(
+        preferred.mongo.read_criteria().unwrap(),
+        Some(SelectionCriteria::ReadPreference(Mode::PrimaryPreferred { .. }))
+    ));
+
+    // MongoDB가 거부하는 값은 시작할 때 알림
+    let too_fresh = config(r#"read_preference = "secondaryPreferred"
+        max_staleness_secs = 30"#);
+    assert!(too_fresh.mongo.read_criteria().is_err());
+    assert!(toml::from_str::<Config>("[web]\nhost = \"127.0.0.1:8000\"\n[mongo]\nurl = \"x\"\nread_preference = \"nearest\"").is_err());
+}
+
+/// 읽기 전용 조회 컬렉션에만 읽기 설정을 붙이고, 쓰기 / 읽고 고치는 컬렉션은 primary 노드
+#[tokio::test]
+async fn only_read_collections_carry_the_read_preference() {
+    let state = build_state(r#"read_preference = "secondaryPreferred""#).await;
+    let expected = secondary_preferred(None);
+
+    assert_eq!(state.read_collection().primary().selection_criteria(), expected.as_ref());
+    assert_eq!(state.players_read_collection().primary().selection_criteria(), expected.as_ref());
+    assert_eq!(state.parse_read_collection().primary().selection_criteria(), expected.as_ref());
+
+    assert_eq!(state.collection().primary().selection_criteria(), None);
+    assert_eq!(state.players_collection().primary().selection_criteria(), None);
+    assert_eq!(state.parse_collection().primary().selection_criteria(), None);
+    assert_eq!(state.role_demand_collection().selection_criteria(), None);
+
+    let primary = build_state("").await;
+    assert_eq!(primary.read_collection().primary().selection_criteria(), None);
+}
+
+/// secondary 노드에서 읽으면 업로드 직후가 아니라 TTL로만 다시 조회
+#[tokio::test]
+async fn lagging_reads_refresh_on_ttl_only() {
+    for (follow, expected) in [(true, 2), (false, 1)] {
+        let cache = ListingsCache::new(Duration::from_secs(60), Duration::from_secs(5)).follow_invalidations(follow);
+        let calls = AtomicUsize::new(0);
+        let rebuild = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Vec::new())
+        };
+
+        cache.get(rebuild).await.unwrap();
+        cache.invalidate();
+        cache.get(rebuild).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), expected, "follow_invalidations = {}", follow);
+    }
+}
@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use warp::{Filter, Reply};
+
+use crate::web::readiness::{bind_after_ready, initialise, readyz, Readiness, StartupStore};
+
+/// 느리게 응답하고 처음 몇 번은 연결에 실패하는 저장소
+struct SlowStore {
+    delay: Duration,
+    failed_pings: usize,
+    pings: AtomicUsize,
+    index_builds: AtomicUsize,
+}
+
+impl SlowStore {
+    fn new(delay: Duration, failed_pings: usize) -> Self {
+        Self {
+            delay,
+            failed_pings,
+            pings: AtomicUsize::new(0),
+            index_builds: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl StartupStore for SlowStore {
+    async fn ping(&self) -> anyhow::Result<()> {
+        tokio::time::sleep(self.delay).await;
+        if self.pings.fetch_add(1, Ordering::SeqCst) < self.failed_pings {
+            anyhow::bail!("connection refused");
+        }
+        Ok(())
+    }
+
+    async fn load_secrets(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn ensure_indexes(&self) -> anyhow::Result<()> {
+        tokio::time::sleep(self.delay).await;
+        self.index_builds.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[test]
+fn gate_requires_indexes_mongo_and_stats() {
+    let readiness = Readiness::new(Duration::from_secs(3600));
+    assert!(!readiness.is_ready());
+
+    readiness.mark_mongo_ready();
+    readiness.mark_indexes_ready();
+    let report = readiness.report(Instant::now());
+    assert!(!report.ready);
+    assert!(report.mongo && report.indexes && !report.stats);
+
+    readiness.mark_stats_ready();
+    assert!(readiness.is_ready());
+}
+
+#[test]
+fn grace_period_replaces_stats() {
+    let readiness = Readiness::new(Duration::from_secs(60));
+    readiness.mark_mongo_ready();
+    readiness.mark_indexes_ready();
+
+    assert!(!readiness.report(Instant::now()).ready);
+    let later = readiness.report(Instant::now() + Duration::from_secs(61));
+    assert!(later.ready && later.grace_elapsed && !later.stats);
+
+    // 통계만 있고 인덱스가 없으면 준비되지 않음
+    let readiness = Readiness::new(Duration::ZERO);
+    readiness.mark_stats_ready();
+    assert!(!readiness.is_ready());
+}
+
+#[tokio::test]
+async fn slow_store_is_retried_until_ready() {
+    let readiness = Arc::new(Readiness::new(Duration::ZERO));
+    let store = Arc::new(SlowStore::new(Duration::from_millis(20), 2));
+
+    let task = {
+        let readiness = Arc::clone(&readiness);
+        let store = Arc::clone(&store);
+        tokio::spawn(async move { initialise(&readiness, &*store, Duration::from_millis(10)).await })
+    };
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    let response = warp::test::request().path("/readyz").reply(&readyz(Arc::clone(&readiness))).await;
+    assert_eq!(response.status(), 503);
+
+    tokio::time::timeout(Duration::from_secs(5), readiness.wait_ready()).await.unwrap();
+    task.await.unwrap();
+    assert_eq!(store.pings.load(Ordering::SeqCst), 3);
+    assert_eq!(store.index_builds.load(Ordering::SeqCst), 1);
+
+    let response = warp::test::request().path("/readyz").reply(&readyz(readiness)).await;
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn delayed_bind_serves_router_only_after_ready() {
+    let readiness = Arc::new(Readiness::new(Duration::ZERO));
+    let router = readyz(Arc::clone(&readiness))
+        .or(warp::path("listings").map(|| "listings".into_response()))
+        .unify()
+        .boxed();
+
+    let (addr, server) = bind_after_ready(Arc::clone(&readiness), router, ([127, 0, 0, 1], 0).into()).unwrap();
+    tokio::spawn(server);
+
+    let client = reqwest::Client::builder().pool_max_idle_per_host(0).build().unwrap();
+    let get = |path: &'static str| {
+        let client = client.clone();
+        async move { client.get(format!("http://{}{}", addr, path)).send().await }
+    };
+
+    assert_eq!(get("/readyz").await.unwrap().status(), 503);
+    assert_eq!(get("/listings").await.unwrap().status(), 503);
+
+    let store = SlowStore::new(Duration::from_millis(20), 0);
+    initialise(&readiness, &store, Duration::from_millis(10)).await;
+
+    // 전체 라우터로 교체될 때까지 잠깐 연결이 거부될 수 있음
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        if let Ok(response) = get("/listings").await {
+            if response.status() == 200 {
+                assert_eq!(response.text().await.unwrap(), "listings");
+                break;
+            }
+        }
+        assert!(Instant::now() < deadline, "router was not served after ready");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(get("/readyz").await.unwrap().status(), 200);
+}
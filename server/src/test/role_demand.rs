@@ -0,0 +1,101 @@
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+use crate::listing::{DutyCategory, JobFlags, PartyFinderListing, PartyFinderSlot, RoleSlots};
+use crate::stats::role_demand::{matrix, sample, CategoryDemand, RoleDemandSample};
+
+/// 탱커 / 힐러 / 아무나 / 흑마도사 자리 중 탱커 자리만 참
+fn listing(category: DutyCategory) -> PartyFinderListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.category = category;
+    listing.slots_available = 4;
+    listing.slots = [JobFlags::PALADIN, JobFlags::WHITE_MAGE, JobFlags::all(), JobFlags::BLACK_MAGE]
+        .into_iter()
+        .map(|accepting| PartyFinderSlot { accepting })
+        .collect();
+    // 19 = PLD
+    listing.jobs_present = vec![19, 0, 0, 0];
+    listing
+}
+
+fn slots(tank: u32, healer: u32, dps: u32) -> RoleSlots {
+    RoleSlots { tank, healer, dps }
+}
+
+#[test]
+fn open_slots_count_once_per_accepted_role() {
+    assert_eq!(listing(DutyCategory::Raid).open_role_slots(), slots(1, 2, 2));
+}
+
+#[test]
+fn samples_cover_every_data_centre() {
+    let data_centres: BTreeSet<&'static str> = ["Aether", "Mana"].into_iter().collect();
+    let listings = [listing(DutyCategory::Raid), listing(DutyCategory::Raid), listing(DutyCategory::Dungeon)];
+    let now = Utc::now();
+
+    let samples = sample(&listings, &data_centres, now);
+
+    // 73 = Adamantoise (Aether)
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data_centre, "Aether");
+    assert_eq!(
+        samples[0].categories,
+        vec![
+            CategoryDemand { category: "Dungeon".into(), open: slots(1, 2, 2) },
+            CategoryDemand { category: "Raid".into(), open: slots(2, 4, 4) },
+        ],
+    );
+    // 모집글이 없던 데이터 센터도 평균에 들어가도록 기록
+    assert_eq!(samples[1].data_centre, "Mana");
+    assert!(samples[1].categories.is_empty());
+}
+
+fn at(day: u32, hour: u32, categories: &[(&str, RoleSlots)]) -> RoleDemandSample {
+    // 2026-01-05 = 월요일
+    let sampled_at: DateTime<Utc> = Utc.with_ymd_and_hms(2026, 1, 5 + day, hour, 0, 0).unwrap();
+    RoleDemandSample {
+        sampled_at,
+        data_centre: "Mana".into(),
+        categories: categories
+            .iter()
+            .map(|&(category, open)| CategoryDemand { category: category.into(), open })
+            .collect(),
+    }
+}
+
+#[test]
+fn matrix_averages_samples_per_hour_of_week() {
+    let samples = [
+        at(0, 10, &[("Raid", slots(3, 1, 0)), ("Dungeon", slots(1, 1, 2))]),
+        at(0, 10, &[("Raid", slots(1, 0, 0))]),
+        // 빈 표본도 평균을 낮춤
+        at(0, 10, &[]),
+        at(6, 23, &[("Raid", slots(0, 0, 5))]),
+    ];
+    let utc = FixedOffset::east_opt(0).unwrap();
+
+    let all = matrix(&samples, utc, None);
+    assert_eq!(all.tank.len(), 7);
+    assert!(all.tank.iter().all(|hours| hours.len() == 24));
+    assert_eq!(all.samples[0][10], 3);
+    assert_eq!(all.tank[0][10], Some(5.0 / 3.0));
+    assert_eq!(all.dps[0][10], Some(2.0 / 3.0));
+    assert_eq!(all.dps[6][23], Some(5.0));
+    assert_eq!(all.tank[3][12], None);
+
+    let raids = matrix(&samples, utc, Some(DutyCategory::Raid));
+    assert_eq!(raids.tank[0][10], Some(4.0 / 3.0));
+    assert_eq!(raids.dps[0][10], Some(0.0));
+}
+
+#[test]
+fn matrix_uses_display_timezone() {
+    let samples = [at(6, 23, &[("Raid", slots(2, 0, 0))])];
+    let kst = FixedOffset::east_opt(9 * 3600).unwrap();
+
+    // 일요일 23시 UTC = 월요일 8시 KST
+    let local = matrix(&samples, kst, None);
+    assert_eq!(local.tank[0][8], Some(2.0));
+    assert_eq!(local.tank[6][23], None);
+}
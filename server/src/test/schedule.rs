@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset, TimeDelta, TimeZone, Utc};
+use sestring::SeString;
+
+use crate::feeds::ics::{render_calendar, ScheduleEvent};
+use crate::feeds::schedule_events;
+use crate::listing::schedule::extract_schedule;
+use crate::listing::{DutyCategory, DutyType};
+use crate::listing_container::QueriedListing;
+
+/// 2026-01-02 (금) 21:00 JST
+fn reference() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 2, 12, 0, 0).unwrap()
+}
+
+fn jst() -> FixedOffset {
+    FixedOffset::east_opt(9 * 3600).unwrap()
+}
+
+fn utc(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, day, hour, minute, 0).unwrap()
+}
+
+#[test]
+fn extracts_multilingual_samples() {
+    let samples = [
+        ("Tonight 22:00 start, chill clear party", utc(2, 13, 0)),
+        ("Sunday 9pm, farm until done", utc(4, 12, 0)),
+        ("tomorrow 12am sharp", utc(2, 15, 0)),
+        ("土曜22時〜 固定募集", utc(3, 13, 0)),
+        ("1/3(土)22:00~ 消化", utc(3, 13, 0)),
+        ("明日21時半から", utc(3, 12, 30)),
+        ("２３：３０開始", utc(2, 14, 30)),
+        ("25時から練習", utc(2, 16, 0)),
+        ("morgen 20 Uhr, Lernparty", utc(3, 11, 0)),
+        ("Samstag 20.30 Uhr", utc(3, 11, 30)),
+        ("demain 21h, ambiance détendue", utc(3, 12, 0)),
+        ("après-demain 21h30", utc(4, 12, 30)),
+    ];
+
+    for (description, expected) in samples {
+        assert_eq!(
+            extract_schedule(description, reference(), jst()),
+            Some(expected),
+            "{}",
+            description,
+        );
+    }
+}
+
+#[test]
+fn skips_ambiguous_and_past_times() {
+    let samples = [
+        // 이미 지난 시각
+        "today 20:00",
+        "金曜20時",
+        // 날짜 충돌
+        "today or tomorrow 22:00",
+        "明日か明後日の22時",
+        // 시각 없음
+        "LF tank, any time, ilvl 710+",
+        "mon ami, 8/8 clear",
+    ];
+
+    for description in samples {
+        assert_eq!(extract_schedule(description, reference(), jst()), None, "{}", description);
+    }
+}
+
+#[test]
+fn relative_dates_follow_display_timezone() {
+    // 12:00 UTC 기준 "tomorrow": UTC에서는 3일, UTC-10에서는 아직 2일 02:00 → 3일
+    let utc_offset = FixedOffset::east_opt(0).unwrap();
+    let hst = FixedOffset::west_opt(10 * 3600).unwrap();
+
+    assert_eq!(extract_schedule("tomorrow 20:00", reference(), utc_offset), Some(utc(3, 20, 0)));
+    assert_eq!(extract_schedule("tomorrow 20:00", reference(), hst), Some(utc(4, 6, 0)));
+}
+
+fn queried(id: u32, world: u16, description: &str) -> QueriedListing {
+    let mut listing: crate::listing::PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.id = id;
+    listing.created_world = world;
+    listing.description = SeString::parse(description.as_bytes()).unwrap();
+
+    QueriedListing {
+        created_at: reference(),
+        updated_at: reference(),
+        updated_minute: reference(),
+        time_left: 3600.0,
+        ..super::queried(listing)
+    }
+}
+
+/// 테스트용 최소 iCalendar 파서: 줄 접기를 풀고 VEVENT별 속성 맵을 반환
+fn parse_ics(ics: &str) -> Vec<HashMap<String, String>> {
+    assert!(ics.ends_with("\r\n"));
+    let raw_lines: Vec<&str> = ics.trim_end_matches("\r\n").split("\r\n").collect();
+    for line in &raw_lines {
+        assert!(!line.contains('\n'), "bare LF in {:?}", line);
+        assert!(line.len() <= 75, "line longer than 75 octets: {:?}", line);
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for line in raw_lines {
+        match line.strip_prefix(' ') {
+            Some(continuation) => lines.last_mut().unwrap().push_str(continuation),
+            None => lines.push(line.to_string()),
+        }
+    }
+
+    assert_eq!(lines.first().map(String::as_str), Some("BEGIN:VCALENDAR"));
+    assert_eq!(lines.last().map(String::as_str), Some("END:VCALENDAR"));
+    assert!(lines.iter().any(|l| l == "VERSION:2.0"));
+    assert!(lines.iter().any(|l| l.starts_with("PRODID:")));
+
+    let mut events = Vec::new();
+    let mut current: Option<HashMap<String, String>> = None;
+    for line in lines {
+        let (name, value) = line.split_once(':').expect("property without value");
+        match (name, value) {
+            ("BEGIN", "VEVENT") => current = Some(HashMap::new()),
+            ("END", "VEVENT") => events.push(current.take().expect("END without BEGIN")),
+            _ => {
+                if let Some(event) = current.as_mut() {
+                    let value = value
+                        .replace("\\n", "\n")
+                        .replace("\\,", ",")
+                        .replace("\\;", ";")
+                        .replace("\\\\", "\\");
+                    event.insert(name.to_string(), value);
+                }
+            }
+        }
+    }
+    assert!(current.is_none(), "unterminated VEVENT");
+
+    events
+}
+
+#[test]
+fn ics_feed_is_valid_and_filtered() {
+    // 73 = Adamantoise (Aether), 49 = Kujata (Elemental)
+    let listings = vec![
+        queried(1, 73, "Sunday 9pm, farm; bring food, pots"),
+        queried(2, 73, "tonight 22:00"),
+        queried(3, 73, "today 20:00"),
+        queried(4, 49, "tonight 22:00"),
+        queried(5, 73, "no schedule here"),
+    ];
+
+    let events = schedule_events(&listings, "Aether", None, jst(), reference());
+    let ics = render_calendar("Party Finder: Aether", &events, reference());
+    let parsed = parse_ics(&ics);
+
+    assert_eq!(parsed.len(), 2);
+    // 시작 시각 순
+    assert_eq!(parsed[0]["UID"], "listing-2-73-0@remote-party-finder");
+    assert_eq!(parsed[0]["DTSTART"], "20260102T130000Z");
+    assert_eq!(parsed[1]["UID"], "listing-1-73-0@remote-party-finder");
+    assert_eq!(parsed[1]["DTSTART"], "20260104T120000Z");
+    assert_eq!(parsed[1]["DESCRIPTION"], "Sunday 9pm, farm; bring food, pots");
+    assert!(parsed.iter().all(|e| e["DTSTAMP"] == "20260102T120000Z"));
+    assert!(parsed.iter().all(|e| e["SUMMARY"].ends_with(")")));
+
+    // 듀티 필터
+    let other_duty = schedule_events(&listings, "Aether", Some(1), jst(), reference());
+    assert!(other_duty.is_empty());
+
+    // 듀티 필터는 일반 듀티 ID만 (같은 번호의 무작위 임무는 제외)
+    let mut roulette = queried(6, 73, "tonight 21:30");
+    roulette.listing.duty = 55;
+    roulette.listing.duty_type = DutyType::Roulette;
+    roulette.listing.category = DutyCategory::DutyRoulette;
+    let mut listings = listings;
+    listings.push(roulette);
+    let duty = schedule_events(&listings, "Aether", Some(55), jst(), reference());
+    let ids: Vec<_> = duty.iter().map(|event| event.uid.as_str()).collect();
+    assert_eq!(ids, ["listing-2-73-0@remote-party-finder", "listing-1-73-0@remote-party-finder"]);
+}
+
+#[test]
+fn ics_folds_long_multibyte_lines() {
+    let description = "零式固定募集、".repeat(20);
+    let event = ScheduleEvent {
+        uid: "listing-1-73-0@remote-party-finder".to_string(),
+        start: reference() + TimeDelta::try_hours(1).unwrap(),
+        summary: "万魔殿 (テスト)".to_string(),
+        description: description.clone(),
+    };
+
+    let parsed = parse_ics(&render_calendar("Party Finder: Mana", &[event], reference()));
+
+    assert_eq!(parsed[0]["DESCRIPTION"], description);
+    assert_eq!(parsed[0]["SUMMARY"], "万魔殿 (テスト)");
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+
+use super::mongo_eval::Collection;
+use crate::listing::{Blocklist, PartyFinderListing};
+use crate::listing_container::UploadOutcome;
+use crate::web::handlers::publish_listings;
+
+fn at(minutes: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 2, 1, 20, 0, 0).unwrap() + TimeDelta::try_minutes(minutes).unwrap()
+}
+
+/// 19 = PLD, 24 = WHM
+fn snapshot(minutes: Option<i64>, jobs: [u8; 2]) -> PartyFinderListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.jobs_present = jobs.to_vec();
+    listing.snapshot_at = minutes.map(at);
+    listing
+}
+
+/// `insert_listing`의 업데이트 파이프라인으로 저장하고, 저장된 모집글이 바뀐 경우만 전송
+fn upload_all(uploads: Vec<PartyFinderListing>) -> (PartyFinderListing, Vec<UploadOutcome>, usize) {
+    let (tx, mut rx) = tokio::sync::broadcast::channel(16);
+    let mut listings = Collection::default();
+    let mut outcomes = Vec::new();
+
+    for listing in uploads {
+        let stored = listings.upload(&listing, "aaaaaaaaaaaaaaaa", false, at(60));
+        outcomes.push(stored.outcome);
+        if stored.should_publish() {
+            publish_listings(&tx, &Blocklist::default(), vec![listing]);
+        }
+    }
+
+    let mut broadcasts = 0;
+    while rx.try_recv().is_ok() {
+        broadcasts += 1;
+    }
+    (listings.stored(&snapshot(None, [0, 0])).listing, outcomes, broadcasts)
+}
+
+#[test]
+fn late_stale_snapshots_do_not_overwrite_fresh_ones() {
+    let (stored, outcomes, broadcasts) = upload_all(vec![
+        snapshot(Some(10), [19, 0]),
+        snapshot(Some(0), [0, 0]),
+        snapshot(Some(20), [19, 24]),
+        snapshot(Some(15), [19, 0]),
+    ]);
+
+    assert_eq!(
+        outcomes,
+        [UploadOutcome::Inserted, UploadOutcome::Stale, UploadOutcome::Updated, UploadOutcome::Stale],
+    );
+    assert_eq!(stored.jobs_present, [19, 24]);
+    assert_eq!(stored.snapshot_at, Some(at(20)));
+    assert_eq!(broadcasts, 2);
+}
+
+#[test]
+fn snapshot_taken_at_the_same_time_is_not_newer() {
+    let (stored, outcomes, broadcasts) = upload_all(vec![snapshot(Some(5), [19, 0]), snapshot(Some(5), [0, 0])]);
+
+    assert_eq!(outcomes, [UploadOutcome::Inserted, UploadOutcome::Stale]);
+    assert_eq!(stored.jobs_present, [19, 0]);
+    assert_eq!(broadcasts, 1);
+}
+
+#[test]
+fn uploads_without_snapshot_keep_last_writer_wins() {
+    let (stored, outcomes, broadcasts) = upload_all(vec![
+        snapshot(Some(10), [19, 24]),
+        snapshot(None, [19, 0]),
+        // 저장된 스냅샷 시각이 없어졌으므로 다음 스냅샷은 시각과 관계없이 적용
+        snapshot(Some(0), [0, 0]),
+    ]);
+
+    assert_eq!(
+        outcomes,
+        [UploadOutcome::Inserted, UploadOutcome::Updated, UploadOutcome::Updated],
+    );
+    assert_eq!(stored.jobs_present, [0, 0]);
+    assert_eq!(broadcasts, 3);
+}
+
+#[test]
+fn identical_reuploads_are_not_broadcast() {
+    let (_, outcomes, broadcasts) = upload_all(vec![snapshot(None, [19, 0]), snapshot(None, [19, 0])]);
+
+    assert_eq!(outcomes, [UploadOutcome::Inserted, UploadOutcome::Unchanged]);
+    assert_eq!(broadcasts, 1);
+}
+
+#[test]
+fn newer_snapshots_of_the_same_listing_are_not_broadcast() {
+    let (stored, outcomes, broadcasts) = upload_all(vec![snapshot(Some(5), [19, 0]), snapshot(Some(10), [19, 0])]);
+
+    assert_eq!(outcomes, [UploadOutcome::Inserted, UploadOutcome::Unchanged]);
+    // 바뀐 내용이 없어도 스냅샷 시각은 갱신
+    assert_eq!(stored.snapshot_at, Some(at(10)));
+    assert_eq!(broadcasts, 1);
+}
+
+#[test]
+fn snapshots_within_the_same_millisecond_are_not_newer() {
+    // 저장된 시각은 밀리초 단위로 비교되므로 결과도 파이프라인 기준
+    let mut first = snapshot(Some(5), [19, 0]);
+    first.snapshot_at = first.snapshot_at.map(|at| at + TimeDelta::microseconds(300));
+    let mut second = snapshot(Some(5), [0, 0]);
+    second.snapshot_at = second.snapshot_at.map(|at| at + TimeDelta::microseconds(700));
+
+    let (stored, outcomes, broadcasts) = upload_all(vec![first, second]);
+    assert_eq!(outcomes, [UploadOutcome::Inserted, UploadOutcome::Stale]);
+    assert_eq!(stored.jobs_present, [19, 0]);
+    assert_eq!(broadcasts, 1);
+}
+
+#[test]
+fn future_snapshots_are_clamped_to_the_upload_time() {
+    let mut listings = Collection::default();
+    let future = snapshot(Some(24 * 60), [19, 0]);
+    assert_eq!(listings.upload(&future, "aaaaaaaaaaaaaaaa", false, at(0)).outcome, UploadOutcome::Inserted);
+    assert_eq!(listings.stored(&future).listing.snapshot_at, Some(at(0)));
+
+    // 미래 시각이 저장돼 이후 스냅샷이 모두 밀리지 않음
+    let later = snapshot(Some(5), [19, 24]);
+    assert_eq!(listings.upload(&later, "aaaaaaaaaaaaaaaa", false, at(5)).outcome, UploadOutcome::Updated);
+    assert_eq!(listings.stored(&later).listing.jobs_present, [19, 24]);
+}
+
+#[test]
+fn snapshot_at_is_optional_in_uploads() {
+    let plain: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    assert_eq!(plain.snapshot_at, None);
+    assert!(!serde_json::to_string(&plain).unwrap().contains("snapshot_at"));
+
+    let stamped = snapshot(Some(3), [19, 0]);
+    let json = serde_json::to_string(&stamped).unwrap();
+    let parsed: PartyFinderListing = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.snapshot_at, Some(at(3)));
+}
@@ -0,0 +1,168 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use mongodb::bson::{self, Bson, Document};
+
+use super::mongo_eval::{matches, Collection};
+use crate::config::Snapshot;
+use crate::listing::snapshot::SnapshotScope;
+use crate::listing::{Blocklist, PartyFinderListing};
+use crate::listing_container::ListingContainer;
+use crate::mongo::{current_listings_pipeline, unconfirmed_update};
+use crate::web::handlers::MultipleUpload;
+
+const UPLOADER: &str = "aaaaaaaaaaaaaaaa";
+const OTHER_UPLOADER: &str = "bbbbbbbbbbbbbbbb";
+
+fn at(minutes: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 2, 1, 20, 0, 0).unwrap() + TimeDelta::try_minutes(minutes).unwrap()
+}
+
+fn listing(id: u32, created_world: u16) -> PartyFinderListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.id = id;
+    listing.created_world = created_world;
+    listing
+}
+
+fn container(id: u32, created_world: u16, uploaders: &[&str]) -> ListingContainer {
+    ListingContainer {
+        created_at: at(0),
+        updated_at: at(0),
+        listing: listing(id, created_world),
+        upload_count: 0,
+        uploader_fingerprints: uploaders.iter().map(|fp| fp.to_string()).collect(),
+        description_hash: None,
+        description_text: None,
+        description_history: Vec::new(),
+        description_language: None,
+        has_autotranslate: false,
+        unconfirmed_at: None,
+        canonical_category: None,
+        hidden: false,
+        flagged: false,
+        slots_history: Vec::new(),
+    }
+}
+
+/// 저장된 컨테이너 문서
+fn document(container: &ListingContainer) -> Document {
+    bson::to_document(container).unwrap()
+}
+
+/// `mark_unconfirmed`와 같은 조건 / 업데이트로 스냅샷에서 빠진 모집글 기록
+fn mark_unconfirmed(listings: &mut Collection, scope: &SnapshotScope, now: DateTime<Utc>) -> usize {
+    listings.update_many(&scope.absent_filter(now - TimeDelta::try_minutes(60).unwrap()), &unconfirmed_update(now))
+}
+
+/// `current_listings_pipeline`의 첫 `$match`(업데이트 / 숨김 / 확인 조건)를 통과하는지
+fn listed(listings: &Collection, id: u32, now: DateTime<Utc>, window: TimeDelta) -> bool {
+    let pipeline = current_listings_pipeline(at(-60), now - window, &Blocklist::default());
+    let stage = pipeline[0].get_document("$match").unwrap();
+    listings.docs.iter().any(|doc| doc.get_document("listing").unwrap().get_i64("id") == Ok(i64::from(id)) && matches(stage, doc))
+}
+
+#[test]
+fn listings_missing_from_the_snapshot_are_detected() {
+    // 73, 79 = Adamantoise, Cactuar
+    let scope = SnapshotScope::new(UPLOADER, &[73, 79], &[listing(1, 73)]);
+
+    let mut stale = container(6, 73, &[UPLOADER]);
+    stale.updated_at = at(-61);
+    // 같은 id라도 서버 재시작이 다르면 다른 모집글
+    let mut restarted = container(1, 73, &[UPLOADER]);
+    restarted.listing.last_server_restart = 1;
+    // 이미 빠진 것으로 기록된 모집글은 처음 시각을 유지
+    let mut marked = container(7, 73, &[UPLOADER]);
+    marked.unconfirmed_at = Some(bson::DateTime::from_chrono(at(-5)));
+
+    let docs = vec![
+        // 스냅샷에 있음
+        document(&container(1, 73, &[UPLOADER])),
+        // 이 업로더가 올렸는데 스냅샷에서 빠짐
+        document(&container(2, 73, &[UPLOADER, OTHER_UPLOADER])),
+        document(&container(3, 79, &[UPLOADER])),
+        // 범위 밖 월드, 다른 업로더만 올린 모집글, 오래된 모집글은 건드리지 않음
+        document(&container(4, 80, &[UPLOADER])),
+        document(&container(5, 73, &[OTHER_UPLOADER])),
+        document(&stale),
+        document(&restarted),
+        document(&marked),
+    ];
+    let mut listings = Collection { docs };
+    assert_eq!(mark_unconfirmed(&mut listings, &scope, at(0)), 3);
+
+    let unconfirmed_at = |i: usize| listings.docs[i].get_datetime("unconfirmed_at").ok().map(|at| at.to_chrono());
+    let marked_now: Vec<usize> = (0..listings.docs.len()).filter(|&i| unconfirmed_at(i) == Some(at(0))).collect();
+    assert_eq!(marked_now, [1, 2, 6]);
+    assert_eq!(unconfirmed_at(7), Some(at(-5)));
+}
+
+#[test]
+fn absent_filter_matches_scope() {
+    let scope = SnapshotScope::new(UPLOADER, &[73], &[listing(1, 73), listing(2, 73)]);
+    let filter = scope.absent_filter(at(0));
+    assert_eq!(filter.get_str("uploader_fingerprints"), Ok(UPLOADER));
+    assert_eq!(filter.get("unconfirmed_at"), Some(&Bson::Null));
+    assert_eq!(filter.get_array("$nor").unwrap().len(), 2);
+
+    // 빈 스냅샷이면 범위 안의 모든 모집글이 대상 (빈 `$nor`는 쓰지 않음)
+    let empty = SnapshotScope::new(UPLOADER, &[73], &[]).absent_filter(at(0));
+    assert!(!empty.contains_key("$nor"));
+}
+
+#[test]
+fn another_uploader_keeps_the_listing_alive() {
+    let window = Snapshot::default().unconfirmed_window();
+    let scope = SnapshotScope::new(UPLOADER, &[73], &[]);
+    let mut listings = Collection::default();
+    listings.docs.push(document(&container(2, 73, &[UPLOADER, OTHER_UPLOADER])));
+
+    mark_unconfirmed(&mut listings, &scope, at(0));
+    assert!(!listed(&listings, 2, at(11), window));
+
+    // 다른 업로더가 아직 보고 있음 (`insert_listing`의 파이프라인이 기록을 지움)
+    listings.upload(&listing(2, 73), OTHER_UPLOADER, false, at(11));
+    assert_eq!(listings.stored(&listing(2, 73)).unconfirmed_at, None);
+    assert!(listed(&listings, 2, at(11), window));
+}
+
+#[test]
+fn unconfirmed_listings_decay_after_the_window() {
+    let window = Snapshot::default().unconfirmed_window();
+    assert_eq!(window, TimeDelta::try_minutes(10).unwrap());
+
+    let scope = SnapshotScope::new(UPLOADER, &[73], &[]);
+    let mut listings = Collection::default();
+    listings.docs.push(document(&container(2, 73, &[UPLOADER])));
+    assert!(listed(&listings, 2, at(59), window));
+
+    assert_eq!(mark_unconfirmed(&mut listings, &scope, at(0)), 1);
+    // 다시 빠져도 처음 빠진 시각 기준
+    assert_eq!(mark_unconfirmed(&mut listings, &scope, at(5)), 0);
+    assert!(listed(&listings, 2, at(9), window));
+    assert!(listed(&listings, 2, at(10), window));
+    assert!(!listed(&listings, 2, at(11), window));
+}
+
+#[test]
+fn plain_arrays_keep_upsert_only_semantics() {
+    let listings = serde_json::to_value(vec![listing(1, 73)]).unwrap();
+
+    let (parsed, scope) = serde_json::from_value::<MultipleUpload>(listings.clone()).unwrap().into_parts(UPLOADER, true);
+    assert_eq!(parsed.len(), 1);
+    assert!(scope.is_none());
+
+    let snapshot = serde_json::json!({ "snapshot": true, "worlds": [73], "listings": listings });
+    let (parsed, scope) = serde_json::from_value::<MultipleUpload>(snapshot.clone()).unwrap().into_parts(UPLOADER, true);
+    assert_eq!(parsed.len(), 1);
+    assert!(matches(&scope.unwrap().absent_filter(at(-60)), &document(&container(2, 73, &[UPLOADER]))));
+
+    // 업로드 토큰 없이 보낸 스냅샷은 저장만 함 (다른 업로더의 모집글을 내리지 못함)
+    let (parsed, scope) = serde_json::from_value::<MultipleUpload>(snapshot).unwrap().into_parts(UPLOADER, false);
+    assert_eq!(parsed.len(), 1);
+    assert!(scope.is_none());
+
+    // 범위가 없으면 스냅샷으로 다루지 않음
+    let unscoped = serde_json::json!({ "snapshot": true, "listings": [] });
+    let (_, scope) = serde_json::from_value::<MultipleUpload>(unscoped).unwrap().into_parts(UPLOADER, true);
+    assert!(scope.is_none());
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use chrono::{TimeDelta, Utc};
+use sestring::SeString;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use super::fixture_world::SAVAGE;
+use crate::config::{Config, Logging};
+use crate::listing::{DutyCategory, DutyType};
+use crate::stats::{Alias, CachedStatistics, Statistics};
+use crate::web::routes::router;
+use crate::web::State;
+
+/// 영식 듀티 하나, 월드 하나(상위 호스트 1은 이름 있음, 2는 없음)의 통계
+fn statistics(listings: usize) -> Statistics {
+    let mut stats: Statistics = serde_json::from_value(serde_json::json!({
+        "count": [{ "count": listings }],
+        "duties": [{ "_id": [DutyType::Normal.as_u8(), DutyCategory::HighEndDuty as u32, SAVAGE], "count": listings }],
+        "hosts": [{
+            "_id": 73,
+            "count": listings,
+            "content_ids": [{ "content_id": 1, "count": 2 }, { "content_id": 2, "count": 1 }],
+        }],
+        "hours": [{ "_id": 20, "count": listings }],
+        "days": [{ "_id": 1, "count": listings }],
+    }))
+    .unwrap();
+    stats.aliases.insert(1, Alias { name: SeString::parse(b"Host One").unwrap(), home_world: 73 });
+    stats
+}
+
+async fn state() -> Arc<State> {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    State::new(Arc::new(config), log_handle).await.unwrap()
+}
+
+#[test]
+fn statistics_serialize_with_resolved_names() {
+    let json = serde_json::to_value(statistics(5)).unwrap();
+    let info = crate::ffxiv::duty(u32::from(SAVAGE)).unwrap();
+
+    assert_eq!(json["listings"], 5);
+    let duty = &json["duties"][0];
+    assert_eq!(duty["duty"], SAVAGE);
+    assert_eq!(duty["drill_down_id"], SAVAGE);
+    assert_eq!(duty["name"]["en"], info.name.en);
+    assert_eq!(duty["name"]["ja"], info.name.ja);
+    assert_eq!(duty["count"], 5);
+
+    let host = &json["hosts"][0];
+    assert_eq!(host["world"], 73);
+    assert_eq!(host["other"], 2);
+    assert_eq!(host["players"][0]["name"], format!("Host One @ {}", host["world_name"].as_str().unwrap()));
+    assert_eq!(host["players"][0]["alias"]["name"], "Host One");
+    assert_eq!(host["players"][1]["name"], "<unknown>");
+    assert!(host["players"][1]["alias"].is_null());
+
+    assert_eq!(json["hours"], serde_json::json!([{ "hour": 20, "count": 5 }]));
+    assert_eq!(json["days"], serde_json::json!([{ "day": 1, "name": "Sunday", "count": 5 }]));
+    assert!(json["fill"].is_null());
+}
+
+#[tokio::test]
+async fn stats_api_serves_each_window() {
+    let state = state().await;
+    let routes = router(Arc::clone(&state));
+
+    let response = warp::test::request().path("/api/stats").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(body["error"], "stats haven't been calculated yet");
+
+    let updated_at = Utc::now() - TimeDelta::try_minutes(12).unwrap();
+    *state.stats.write().await = Some(CachedStatistics { all_time: statistics(40), seven_days: statistics(6), updated_at });
+    for (path, listings, seven_days) in [("/api/stats", 40, false), ("/api/stats/7days", 6, true)] {
+        let response = warp::test::request().path(path).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::OK, "{}", path);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["listings"], listings, "{}", path);
+        assert_eq!(body["seven_days"], seven_days, "{}", path);
+        assert_eq!(body["duties"][0]["count"], listings, "{}", path);
+        assert!(body["generated_at"].is_string());
+        assert_eq!(body["updated_at"], serde_json::json!(updated_at), "{}", path);
+    }
+}
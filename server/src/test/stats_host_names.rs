@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use chrono::{TimeDelta, Utc};
+use mongodb::bson::doc;
+use sestring::SeString;
+
+use crate::player::Player;
+use crate::stats::{resolve_host_names, Alias, Statistics, StatsQuery};
+
+/// 호스트 1, 2, 3을 상위 호스트로 가진 통계
+fn statistics() -> Statistics {
+    serde_json::from_value(serde_json::json!({
+        "count": [{ "count": 9 }],
+        "duties": [],
+        "hosts": [{
+            "_id": 73,
+            "count": 9,
+            "content_ids": [
+                { "content_id": 1, "count": 4 },
+                { "content_id": 2, "count": 3 },
+                { "content_id": 3, "count": 2 },
+            ],
+        }],
+        "hours": [],
+        "days": [],
+    }))
+    .unwrap()
+}
+
+fn alias(name: &str) -> Alias {
+    Alias {
+        name: SeString::parse(name.as_bytes()).unwrap(),
+        home_world: 73,
+    }
+}
+
+fn player(content_id: u64, name: &str, hours_ago: i64) -> Player {
+    Player {
+        content_id,
+        name: name.to_string(),
+        home_world: 79,
+        last_seen: Utc::now() - TimeDelta::try_hours(hours_ago).unwrap(),
+        seen_count: 1,
+    }
+}
+
+/// 두 집계가 같은 시각을 상한으로 씀
+#[test]
+fn both_passes_share_the_snapshot_bound() {
+    let since = Utc::now() - TimeDelta::try_days(7).unwrap();
+    let until = Utc::now();
+    let query = StatsQuery::default().since(since).until(until);
+
+    assert_eq!(query.build()[0], doc! { "$match": { "created_at": { "$gte": since, "$lte": until } } });
+    assert_eq!(
+        query.aliases(&[1, 2])[0],
+        doc! { "$match": { "listing.content_id_lower": { "$in": [1, 2] }, "created_at": { "$lte": until } } }
+    );
+
+    // 상한 없이 만든 파이프라인은 그대로
+    assert_eq!(StatsQuery::default().build().len() + 1, StatsQuery::default().until(until).build().len());
+}
+
+/// 첫 집계 뒤 호스트의 모집글이 지워져도 플레이어 기록으로 이름을 찾음
+#[tokio::test]
+async fn hosts_missing_from_the_alias_pass_fall_back_to_players() {
+    let mut stats = statistics();
+    let requested = std::sync::Mutex::new(Vec::new());
+
+    resolve_host_names(
+        &mut stats,
+        |ids| async move {
+            assert_eq!(ids, vec![1, 2, 3]);
+            // 두 집계 사이에 호스트 2, 3의 모집글이 사라진 저장소
+            Ok(HashMap::from([(1, alias("Alpha Tester"))]))
+        },
+        |ids| {
+            *requested.lock().unwrap() = ids;
+            async {
+                Ok(vec![
+                    player((7 << 32) | 2, "Beta Tester", 1),
+                    player(2, "Beta Old", 48),
+                    player(3, "", 1),
+                    player(4, "Delta Tester", 1),
+                ])
+            }
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(*requested.lock().unwrap(), vec![2, 3]);
+    assert_eq!(stats.player_name(&1), "Alpha Tester @ Adamantoise");
+    assert_eq!(stats.player_name(&2), "Beta Tester @ Cactuar");
+    // 어디에도 이름이 없는 호스트만 알 수 없음
+    assert_eq!(stats.player_name(&3), "<unknown>");
+    assert!(!stats.aliases.contains_key(&4));
+}
+
+#[tokio::test]
+async fn players_are_only_queried_for_unnamed_hosts() {
+    let calls = AtomicUsize::new(0);
+    let mut stats = statistics();
+    resolve_host_names(
+        &mut stats,
+        |ids| async move { Ok(ids.into_iter().map(|id| (id, alias("Known Host"))).collect()) },
+        |_| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Ok(Vec::new()) }
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    assert!(stats.unnamed_hosts().is_empty());
+
+    // 플레이어 조회가 실패해도 통계는 만들어짐
+    let mut stats = statistics();
+    resolve_host_names(
+        &mut stats,
+        |_| async { Ok(HashMap::new()) },
+        |_| async { anyhow::bail!("database unavailable") },
+    )
+    .await
+    .unwrap();
+    assert_eq!(stats.unnamed_hosts(), vec![1, 2, 3]);
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use askama::Template;
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::ffxiv::Language;
+use crate::stats::{CachedStatistics, Statistics};
+use crate::template::stats::StatsTemplate;
+use crate::web::stats_refresh::{retry_delay, StatsRefresh, STATS_RETRY_MAX, STATS_RETRY_MIN};
+
+const MAX_AGE: Duration = Duration::from_secs(18 * 60 * 60);
+
+fn statistics(generated_at: DateTime<Utc>) -> Statistics {
+    Statistics {
+        count: vec![],
+        aliases: HashMap::new(),
+        duties: vec![],
+        hosts: vec![],
+        hours: vec![],
+        days: vec![],
+        languages: vec![],
+        timeline: vec![],
+        fill: vec![],
+        fill_times: vec![],
+        daily: vec![],
+        generated_at,
+    }
+}
+
+fn cached(age_hours: i64) -> CachedStatistics {
+    let generated_at = Utc::now() - TimeDelta::try_hours(age_hours).unwrap();
+    CachedStatistics {
+        all_time: statistics(Utc::now()),
+        seven_days: statistics(generated_at),
+        updated_at: generated_at,
+    }
+}
+
+#[test]
+fn only_stale_stats_request_a_refresh() {
+    let refresh = StatsRefresh::new(MAX_AGE);
+
+    assert!(!refresh.request_if_stale(&cached(12), Utc::now()));
+    assert!(!refresh.in_flight());
+
+    // 더 오래된 기간 기준
+    assert!(refresh.request_if_stale(&cached(19), Utc::now()));
+    assert!(refresh.in_flight());
+}
+
+#[test]
+fn refresh_is_requested_once_until_finished() {
+    let refresh = StatsRefresh::new(MAX_AGE);
+    let stale = cached(24);
+
+    assert!(refresh.request_if_stale(&stale, Utc::now()));
+    assert!(!refresh.request_if_stale(&stale, Utc::now()));
+
+    refresh.begin();
+    assert!(!refresh.request_if_stale(&stale, Utc::now()));
+    refresh.finish();
+
+    assert!(refresh.request_if_stale(&stale, Utc::now()));
+}
+
+#[test]
+fn failed_runs_back_off_up_to_an_hour() {
+    let minutes = |failures| retry_delay(failures).as_secs() / 60;
+    assert_eq!(retry_delay(1), STATS_RETRY_MIN);
+    assert_eq!([1, 2, 3, 4, 5].map(minutes), [5, 10, 20, 40, 60]);
+    assert_eq!(retry_delay(6), STATS_RETRY_MAX);
+    assert_eq!(retry_delay(u32::MAX), STATS_RETRY_MAX);
+}
+
+#[test]
+fn stats_interval_is_configurable() {
+    let config: crate::config::Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+        stats_interval_hours = 3
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.web.stats_interval_hours, 3);
+
+    let web: crate::config::Web = toml::from_str(r#"host = "127.0.0.1:8000""#).unwrap();
+    assert_eq!(web.stats_interval_hours, 12);
+}
+
+#[tokio::test]
+async fn request_wakes_the_stats_task() {
+    let refresh = StatsRefresh::new(MAX_AGE);
+    assert!(refresh.request());
+
+    tokio::time::timeout(Duration::from_secs(1), refresh.wait(Duration::from_secs(60 * 60)))
+        .await
+        .expect("request should end the wait early");
+}
+
+#[test]
+fn stats_page_shows_generation_time() {
+    let html = StatsTemplate {
+        stats: statistics(Utc::now() - TimeDelta::try_hours(3).unwrap()),
+        lang: Language::English,
+        seven_days: false,
+        unknown_ids: 0,
+        hidden_from_listings: false,
+    }
+    .render()
+    .unwrap();
+
+    assert!(html.contains("(as of 3 hours ago)"), "{}", html);
+}
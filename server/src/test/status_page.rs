@@ -0,0 +1,165 @@
+use std::collections::BTreeSet;
+
+use askama::Template;
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+
+use crate::ffxiv::Language;
+use crate::template::status::StatusTemplate;
+use crate::web::maintenance::{MaintenanceOverride, MaintenanceStatus, PauseReason};
+use crate::web::status::{
+    Banner, ContributionState, CycleSnapshot, CycleTracker, PipelineState, StatusInputs, StatusSummary,
+};
+use crate::web::volume::VolumeAlert;
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 8, 1, 12, 0, 0).unwrap()
+}
+
+fn minutes_ago(minutes: i64) -> DateTime<Utc> {
+    now() - TimeDelta::try_minutes(minutes).unwrap()
+}
+
+fn data_centres() -> BTreeSet<&'static str> {
+    ["Aether", "Mana"].into_iter().collect()
+}
+
+fn render(summary: StatusSummary) -> String {
+    StatusTemplate { summary, lang: Language::English }.render().unwrap()
+}
+
+/// `<tr id="...">` 행 (또는 `data-dc`) 하나의 HTML
+fn row<'a>(html: &'a str, marker: &str) -> &'a str {
+    let start = html.find(marker).unwrap();
+    let end = start + html[start..].find("</tr>").unwrap();
+    &html[start..end]
+}
+
+fn cycle(finished_minutes_ago: i64, ok: bool) -> CycleSnapshot {
+    CycleSnapshot {
+        running_since: None,
+        last_finished_at: Some(minutes_ago(finished_minutes_ago)),
+        last_duration_secs: Some(12.0),
+        last_ok: ok,
+    }
+}
+
+#[test]
+fn fresh_server_without_optional_subsystems() {
+    let summary = StatusSummary::build(StatusInputs::default(), &data_centres(), now());
+
+    assert!(summary.banners.is_empty());
+    assert!(summary
+        .data_centres
+        .iter()
+        .all(|dc| dc.contribution == ContributionState::Unknown && dc.listings.is_none()));
+    assert_eq!(summary.fflogs.state, PipelineState::Disabled);
+    assert_eq!(summary.stats.generated_at, None);
+
+    let html = render(summary);
+    assert!(!html.contains("status-banner"));
+    assert!(row(&html, r#"data-dc="Mana""#).contains("never"));
+    assert!(row(&html, r#"id="fflogs""#).contains("disabled"));
+    assert!(!row(&html, r#"id="fflogs""#).contains("last cycle"));
+    assert!(row(&html, r#"id="stats""#).contains("calculating"));
+}
+
+#[test]
+fn contributions_and_pipelines_are_reported() {
+    let inputs = StatusInputs {
+        contributions: maplit::hashmap! { "Mana" => minutes_ago(2), "Aether" => minutes_ago(40) },
+        listing_counts: maplit::hashmap! { "Mana" => 120, "Aether" => 3 },
+        fflogs: Some(cycle(1, true)),
+        stats_generated_at: Some(minutes_ago(90)),
+        ..Default::default()
+    };
+    let summary = StatusSummary::build(inputs, &data_centres(), now());
+
+    let states: Vec<_> = summary.data_centres.iter().map(|dc| (dc.name, dc.contribution, dc.listings)).collect();
+    assert_eq!(
+        states,
+        vec![("Aether", ContributionState::Stale, Some(3)), ("Mana", ContributionState::Ok, Some(120))],
+    );
+    assert_eq!(summary.fflogs.state, PipelineState::Running);
+    assert_eq!(summary.stats.age_secs, Some(90 * 60));
+
+    let json = serde_json::to_value(&summary).unwrap();
+    assert_eq!(json["fflogs"]["state"], "running");
+    assert_eq!(json["data_centres"][0]["contribution"], "stale");
+    assert_eq!(json["data_centres"][1]["last_contribution_secs"], 120);
+
+    let html = render(summary);
+    assert!(row(&html, r#"data-dc="Mana""#).contains(r#"class="status-ok">2m ago"#));
+    assert!(row(&html, r#"data-dc="Aether""#).contains(r#"class="status-stale">40m ago"#));
+    assert!(row(&html, r#"id="fflogs""#).contains("last cycle 1m ago (took 12s)"));
+    assert!(row(&html, r#"id="stats""#).contains("1h 30m ago"));
+}
+
+#[test]
+fn stalled_and_starting_pipelines() {
+    let state = |fflogs| {
+        let inputs = StatusInputs { fflogs: Some(fflogs), ..Default::default() };
+        StatusSummary::build(inputs, &data_centres(), now()).fflogs.state
+    };
+
+    assert_eq!(state(cycle(30, true)), PipelineState::Stalled);
+    assert_eq!(state(CycleSnapshot::default()), PipelineState::Starting);
+    // 오래 걸리는 주기라도 최근에 시작했으면 동작 중
+    assert_eq!(state(CycleSnapshot { running_since: Some(minutes_ago(3)), ..cycle(30, false) }), PipelineState::Running);
+
+    let html = render(StatusSummary::build(
+        StatusInputs { fflogs: Some(cycle(30, false)), ..Default::default() },
+        &data_centres(),
+        now(),
+    ));
+    assert!(row(&html, r#"id="fflogs""#).contains("stalled"));
+    assert!(row(&html, r#"id="fflogs""#).contains("30m ago (took 12s, failed)"));
+}
+
+#[test]
+fn maintenance_and_low_volume_banners() {
+    let until = now() + TimeDelta::try_hours(2).unwrap();
+    let inputs = StatusInputs {
+        fflogs: Some(cycle(30, true)),
+        volume_alerts: vec![VolumeAlert { data_centre: "Mana", count: 2, baseline: 80.0, since: minutes_ago(10) }],
+        maintenance: Some(MaintenanceStatus {
+            paused: true,
+            reason: Some(PauseReason::Scheduled),
+            mode: MaintenanceOverride::Auto,
+            scheduled_until: Some(until),
+            skipped_fflogs_cycles: 0,
+            skipped_stats_cycles: 0,
+        }),
+        ..Default::default()
+    };
+    let summary = StatusSummary::build(inputs, &data_centres(), now());
+
+    // 점검 중에는 수집이 멈춘 게 아니라 쉬는 중
+    assert_eq!(summary.fflogs.state, PipelineState::Paused);
+    assert_eq!(
+        summary.banners,
+        vec![
+            Banner::Maintenance { reason: PauseReason::Scheduled, until: Some(until) },
+            Banner::LowVolume { data_centre: "Mana", since: minutes_ago(10) },
+        ],
+    );
+    assert!(summary.data_centres.iter().find(|dc| dc.name == "Mana").unwrap().volume_alert);
+
+    let html = render(summary);
+    assert_eq!(html.matches(r#"class="status-banner""#).count(), 2);
+    assert!(html.contains("Game maintenance in progress until 2024-08-01 14:00 UTC"));
+    assert!(html.contains("Fewer listings than usual on Mana"));
+}
+
+#[test]
+fn cycle_tracker_records_duration_and_result() {
+    let tracker = CycleTracker::default();
+    tracker.start(minutes_ago(1));
+    assert_eq!(tracker.snapshot().running_since, Some(minutes_ago(1)));
+
+    tracker.finish(false, now());
+    let snapshot = tracker.snapshot();
+    assert_eq!(snapshot.running_since, None);
+    assert_eq!(snapshot.last_finished_at, Some(now()));
+    assert_eq!(snapshot.last_duration_secs, Some(60.0));
+    assert!(!snapshot.last_ok);
+}
@@ -0,0 +1,90 @@
+use crate::api::ApiReadableListing;
+use crate::ffxiv::unknown_ids::{IdKind, UnknownIds, UNKNOWN_IDS};
+use crate::listing::{DutyType, PartyFinderListing};
+
+/// 테이블에 없는 ID를 가진 모집글 (전역 레지스트리를 공유하므로 테스트마다 다른 ID 사용)
+fn listing_with(id: u32, duty: u16, job: u8, world: u16) -> PartyFinderListing {
+    let mut listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.id = id;
+    listing.duty_type = DutyType::Normal;
+    listing.duty = duty;
+    listing.jobs_present[0] = job;
+    listing.slots_available = 1;
+    listing.home_world = world;
+    listing
+}
+
+fn recorded(kind: IdKind, id: u32) -> Option<(u64, String)> {
+    UNKNOWN_IDS
+        .snapshot()
+        .into_iter()
+        .find(|entry| entry.kind == kind && entry.id == id)
+        .map(|entry| (entry.count, entry.sample))
+}
+
+#[test]
+fn api_conversion_records_unknown_ids() {
+    let listing = listing_with(9001, 65001, 251, 998);
+    let key = listing.key();
+
+    let json = serde_json::to_value(ApiReadableListing::from(listing)).unwrap();
+
+    assert!(json["duty_info"].is_null());
+    assert_eq!(json["home_world"]["name"], "Unknown");
+    assert_eq!(recorded(IdKind::Duty, 65001), Some((1, key.clone())));
+    assert_eq!(recorded(IdKind::Job, 251), Some((1, key.clone())));
+    assert_eq!(recorded(IdKind::World, 998), Some((1, key)));
+}
+
+#[test]
+fn template_helpers_record_unknown_ids() {
+    let listing = listing_with(9002, 65002, 252, 997);
+
+    assert!(!listing.high_end());
+    assert!(listing.slots()[0].is_err());
+    assert!(listing.home_world().is_none());
+
+    assert_eq!(recorded(IdKind::Duty, 65002), Some((1, "9002/73/0".to_string())));
+    assert!(recorded(IdKind::Job, 252).is_some());
+    assert!(recorded(IdKind::World, 997).is_some());
+}
+
+#[test]
+fn known_ids_and_empty_slots_are_not_recorded() {
+    let listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    let _ = ApiReadableListing::from(listing);
+
+    assert!(recorded(IdKind::Duty, 55).is_none());
+    assert!(recorded(IdKind::Job, 0).is_none());
+    assert!(recorded(IdKind::World, 73).is_none());
+}
+
+/// 같은 모집글을 여러 번 표시해도 한 번만 셈
+#[test]
+fn repeated_renders_are_counted_once() {
+    let listing = listing_with(9003, 65003, 253, 996);
+    for _ in 0..3 {
+        assert!(!listing.high_end());
+        let _ = ApiReadableListing::from(listing.clone());
+    }
+    assert_eq!(recorded(IdKind::Duty, 65003).map(|(count, _)| count), Some(1));
+
+    let mut other = listing_with(9004, 65003, 253, 996);
+    other.created_world = 74;
+    let _ = ApiReadableListing::from(other);
+    assert_eq!(recorded(IdKind::Duty, 65003), Some((2, "9003/73/0".to_string())));
+}
+
+#[test]
+fn registry_counts_sources_and_keeps_first_sample() {
+    let registry = UnknownIds::default();
+    registry.record(IdKind::Duty, 1, || "a".to_string());
+    registry.record(IdKind::Duty, 1, || "b".to_string());
+    registry.record(IdKind::Duty, 1, || "a".to_string());
+    registry.record(IdKind::World, 1, || "c".to_string());
+
+    let snapshot = registry.snapshot();
+    assert_eq!(registry.len(), 2);
+    assert_eq!((snapshot[0].kind, snapshot[0].count, snapshot[0].sample.as_str()), (IdKind::Duty, 2, "a"));
+    assert_eq!((snapshot[1].kind, snapshot[1].count), (IdKind::World, 1));
+}
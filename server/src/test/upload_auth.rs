@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use crate::config::{Auth, Config, Logging, UploadToken};
+use crate::web::routes::router;
+use crate::web::token_compare::tokens_match;
+use crate::web::upload_auth::authorize;
+use crate::web::State;
+
+fn auth() -> Auth {
+    Auth {
+        tokens: vec![
+            UploadToken { name: "plugin".to_string(), token: "first-secret".to_string() },
+            UploadToken { name: "mirror".to_string(), token: "second-secret".to_string() },
+        ],
+    }
+}
+
+#[test]
+fn tokens_are_required_only_when_configured() {
+    assert_eq!(authorize(None, None).unwrap(), None);
+    assert_eq!(authorize(Some(&Auth::default()), Some("Bearer anything")).unwrap(), None);
+
+    let auth = auth();
+    assert_eq!(authorize(Some(&auth), Some("Bearer first-secret")).unwrap(), Some("plugin"));
+    assert_eq!(authorize(Some(&auth), Some("Bearer second-secret")).unwrap(), Some("mirror"));
+    for header in [None, Some(""), Some("Bearer "), Some("first-secret"), Some("Bearer wrong"), Some("Basic first-secret")] {
+        assert!(authorize(Some(&auth), header).is_err(), "{:?}", header);
+    }
+}
+
+/// 길이가 다르거나 앞부분만 같은 토큰은 받지 않음
+#[test]
+fn tokens_match_only_exact_values() {
+    assert!(tokens_match("first-secret", "first-secret"));
+    for provided in ["", "first", "first-secret2", "First-secret", "second-secret"] {
+        assert!(!tokens_match("first-secret", provided), "{:?}", provided);
+    }
+}
+
+async fn state(auth: &str) -> Arc<State> {
+    let config: Config = toml::from_str(&format!(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+
+        [ratelimit]
+        uploader_secret = "test"
+
+        {}
+        "#,
+        auth
+    ))
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    State::new(Arc::new(config), log_handle).await.unwrap()
+}
+
+/// 잘못된 본문은 인증을 거친 뒤 `400`이므로 DB 없이 확인
+async fn contribute(state: &Arc<State>, path: &str, authorization: Option<&str>) -> StatusCode {
+    let mut request = warp::test::request().method("POST").path(path).body("not json");
+    if let Some(authorization) = authorization {
+        request = request.header("authorization", authorization);
+    }
+    request.reply(&router(Arc::clone(state))).await.status()
+}
+
+#[tokio::test]
+async fn contribute_routes_reject_missing_tokens() {
+    let state = state(
+        r#"
+        [[auth.tokens]]
+        name = "plugin"
+        token = "first-secret"
+        "#,
+    )
+    .await;
+
+    for path in ["/contribute", "/contribute/multiple", "/contribute/players", "/contribute/detail"] {
+        assert_eq!(contribute(&state, path, None).await, StatusCode::UNAUTHORIZED, "{}", path);
+        assert_eq!(contribute(&state, path, Some("Bearer wrong")).await, StatusCode::UNAUTHORIZED, "{}", path);
+        assert_eq!(contribute(&state, path, Some("Bearer first-secret")).await, StatusCode::BAD_REQUEST, "{}", path);
+    }
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/contribute")
+        .body("[]")
+        .reply(&router(Arc::clone(&state)))
+        .await;
+    assert_eq!(response.body().as_ref(), br#"{"error":"invalid upload token"}"#);
+}
+
+#[tokio::test]
+async fn no_tokens_keep_uploads_open() {
+    let state = state("").await;
+    assert_eq!(contribute(&state, "/contribute", None).await, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use crate::config::{Config, Logging};
+use crate::web::routes::router;
+use crate::web::State;
+
+async fn state(limits: &str) -> Arc<State> {
+    let config: Config = toml::from_str(&format!(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+
+        [ratelimit]
+        uploader_secret = "test"
+
+        {}
+        "#,
+        limits
+    ))
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    State::new(Arc::new(config), log_handle).await.unwrap()
+}
+
+async fn post(state: &Arc<State>, path: &str, body: impl AsRef<[u8]>) -> (StatusCode, serde_json::Value) {
+    let response = warp::test::request()
+        .method("POST")
+        .path(path)
+        .header("content-type", "application/json")
+        .body(body)
+        .reply(&router(Arc::clone(state)))
+        .await;
+    (response.status(), serde_json::from_slice(response.body()).unwrap())
+}
+
+#[tokio::test]
+async fn oversized_bodies_are_refused_per_route() {
+    let state = state("[body_limits]\ncontribute_kb = 1\nmultiple_kb = 2").await;
+    let padding = " ".repeat(1500);
+
+    let (status, body) = post(&state, "/contribute", format!("{{}}{}", padding)).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body, serde_json::json!({ "error": "body too large" }));
+
+    // 같은 크기라도 일괄 업로드 제한 안이면 본문을 해석함
+    let (status, body) = post(&state, "/contribute/multiple", format!("{{}}{}", padding)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid JSON body");
+}
+
+#[tokio::test]
+async fn invalid_json_reports_the_parser_error() {
+    let state = state("").await;
+
+    let (status, body) = post(&state, "/contribute/players", "[{\"content_id\": 1,").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid JSON body");
+    assert!(body["detail"].as_str().unwrap().contains("EOF"), "{}", body);
+    assert_eq!(body["line"], 1);
+    assert_eq!(body["column"], 18);
+}
+
+#[tokio::test]
+async fn schema_mismatches_name_the_offending_field() {
+    let state = state("").await;
+
+    let (status, body) = post(&state, "/contribute", "{\"id\": 1}").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid JSON body");
+    assert!(body["detail"].as_str().unwrap().starts_with("missing field"), "{}", body);
+
+    let (status, body) = post(&state, "/contribute/detail", "{\"listing_id\": \"not a number\"}").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["detail"].as_str().unwrap().contains("invalid type"), "{}", body);
+}
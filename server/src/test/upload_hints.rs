@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+
+use crate::config::{Config, Logging};
+use crate::listing_container::QueriedListing;
+use crate::web::handlers::collect_content_ids;
+use crate::web::hints::{
+    next_upload_interval, upload_hints, CoverageTracker, PendingPlayers, UploadLoad,
+    MAX_PLAYERS_WANTED, MAX_UPLOAD_INTERVAL, SOURCE_UPLOADS_PER_WINDOW, SURGE_UPLOADS_PER_WINDOW,
+};
+use crate::web::State;
+
+/// 73 = Adamantoise (Aether), 49 = Kujata (Elemental)
+fn queried(id: u32, world: u16, members: impl IntoIterator<Item = i64>) -> QueriedListing {
+    let mut listing: crate::listing::PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    listing.id = id;
+    listing.created_world = world;
+    listing.member_content_ids = members.into_iter().collect();
+
+    QueriedListing {
+        time_left: 3600.0,
+        ..super::queried(listing)
+    }
+}
+
+fn aether() -> HashSet<&'static str> {
+    HashSet::from(["Aether"])
+}
+
+#[test]
+fn quiet_server_uses_cache_ttl() {
+    let load = UploadLoad::default();
+    let hints = upload_hints(
+        &load,
+        &PendingPlayers::default(),
+        &CoverageTracker::default(),
+        "source-a",
+        &aether(),
+        [],
+        Instant::now(),
+    );
+
+    assert_eq!(hints.next_upload_seconds, 60);
+    assert!(!hints.detail_wanted);
+    assert!(hints.players_wanted.is_empty());
+}
+
+#[test]
+fn surge_doubles_interval() {
+    let load = UploadLoad::default();
+    let pending = PendingPlayers::default();
+    let coverage = CoverageTracker::default();
+    let now = Instant::now();
+
+    for i in 0..SURGE_UPLOADS_PER_WINDOW {
+        load.record(&format!("other-{}", i), now);
+    }
+    let hints = upload_hints(&load, &pending, &coverage, "source-a", &aether(), [], now);
+    assert_eq!(hints.next_upload_seconds, 120);
+
+    // 집계 구간이 지나면 과부하 해제
+    let later = now + Duration::from_secs(61);
+    let hints = upload_hints(&load, &pending, &coverage, "source-b", &aether(), [], later);
+    assert_eq!(hints.next_upload_seconds, 60);
+}
+
+#[test]
+fn busy_source_gets_longer_interval() {
+    assert_eq!(next_upload_interval(false, 0), Duration::from_secs(60));
+    assert_eq!(next_upload_interval(false, SOURCE_UPLOADS_PER_WINDOW / 2), Duration::from_secs(90));
+    assert_eq!(next_upload_interval(false, SOURCE_UPLOADS_PER_WINDOW * 10), Duration::from_secs(120));
+    assert_eq!(next_upload_interval(true, SOURCE_UPLOADS_PER_WINDOW), Duration::from_secs(240));
+    assert!(next_upload_interval(true, usize::MAX) <= MAX_UPLOAD_INTERVAL);
+
+    let load = UploadLoad::default();
+    let now = Instant::now();
+    for _ in 0..SOURCE_UPLOADS_PER_WINDOW / 2 {
+        load.record("source-a", now);
+    }
+    let hints = upload_hints(
+        &load,
+        &PendingPlayers::default(),
+        &CoverageTracker::default(),
+        "source-a",
+        &aether(),
+        [],
+        now,
+    );
+    assert_eq!(hints.next_upload_seconds, 90);
+}
+
+#[test]
+fn players_wanted_follow_data_centre_and_cap() {
+    let pending = PendingPlayers::default();
+    let listings = vec![
+        queried(1, 73, 1..=30),
+        queried(2, 73, [5, 31]),
+        queried(3, 49, [1000, 1001]),
+    ];
+    // 짝수 ID는 이미 알고 있는 플레이어
+    pending.replace(&listings, |id| id % 2 == 0);
+
+    let wanted = pending.wanted(&aether(), MAX_PLAYERS_WANTED);
+    assert_eq!(wanted.len(), 16);
+    assert!(wanted.iter().all(|id| id % 2 == 1 && *id <= 31));
+
+    let elemental = pending.wanted(&HashSet::from(["Elemental"]), MAX_PLAYERS_WANTED);
+    assert_eq!(elemental, vec![1001]);
+
+    pending.resolve([1, 3, 5]);
+    assert!(!pending.wanted(&aether(), MAX_PLAYERS_WANTED).contains(&1));
+
+    // 상한
+    pending.replace(&[queried(4, 73, 1..=100)], |_| false);
+    assert_eq!(pending.wanted(&aether(), MAX_PLAYERS_WANTED).len(), MAX_PLAYERS_WANTED);
+}
+
+#[test]
+fn detail_wanted_until_members_known() {
+    let coverage = CoverageTracker::default();
+    let now = Instant::now();
+    coverage.observe(&[queried(1, 73, [11, 12]), queried(2, 73, [])], now);
+
+    assert!(!coverage.lacks_detail([1]));
+    assert!(coverage.lacks_detail([1, 2]));
+
+    coverage.mark_detailed(2, now);
+    let hints = upload_hints(
+        &UploadLoad::default(),
+        &PendingPlayers::default(),
+        &coverage,
+        "source-a",
+        &aether(),
+        [1, 2],
+        now,
+    );
+    assert!(!hints.detail_wanted);
+}
+
+/// 목록 페이지를 렌더링하지 않아도 (API만 쓰는 배포) 현재 모집글 스냅샷으로 힌트가 채워짐
+#[tokio::test]
+async fn snapshot_refresh_fills_hints_without_page_renders() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let state = State::new(Arc::new(config), log_handle).await.unwrap();
+
+    let listings = vec![queried(1, 73, [11, 12]), queried(2, 73, [])];
+    // 이름이 업로드된 적 없는 멤버로 기억해 두어 DB 조회 없이 갱신
+    state.missing_players.record_missing(&collect_content_ids(&listings), &[], Instant::now());
+    assert!(state.upload_hints("source-a", &aether(), [1]).detail_wanted);
+
+    state.refresh_upload_hints(&listings).await;
+
+    let hints = state.upload_hints("source-a", &aether(), [1]);
+    assert!(!hints.detail_wanted);
+    assert_eq!(hints.players_wanted, [11, 12]);
+    assert!(state.upload_hints("source-a", &aether(), [2]).detail_wanted);
+}
@@ -0,0 +1,124 @@
+use serde_json::json;
+use warp::http::StatusCode;
+
+use crate::listing::{InvalidListing, ListingProblem};
+use crate::listing_container::UploadOutcome;
+use crate::web::hints::UploadHints;
+use crate::web::upload_response::{
+    ContributeResponse, DetailUploaded, LeaderUpsert, ListingResult, ListingUploaded, ListingsUploaded, PlayersUploaded, UploadError,
+    UploadStatus,
+};
+
+fn hints() -> UploadHints {
+    UploadHints {
+        next_upload_seconds: 30,
+        detail_wanted: false,
+        players_wanted: vec![],
+    }
+}
+
+async fn body<T: serde::Serialize + Send>(result: UploadStatus<T>) -> (StatusCode, serde_json::Value) {
+    let response = ContributeResponse { result, upload_hints: hints() }.into_reply();
+    let status = response.status();
+    let bytes = warp::hyper::body::to_bytes(response.into_body()).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+fn hints_json() -> serde_json::Value {
+    json!({ "next_upload_seconds": 30, "detail_wanted": false, "players_wanted": [] })
+}
+
+#[tokio::test]
+async fn single_listing_responses() {
+    assert_eq!(
+        body(UploadStatus::Ok(ListingUploaded { outcome: UploadOutcome::Inserted })).await,
+        (StatusCode::OK, json!({ "status": "ok", "outcome": "inserted", "upload_hints": hints_json() })),
+    );
+
+    let invalid = InvalidListing { id: 3, problems: vec![ListingProblem::SecondsRemaining] };
+    assert_eq!(
+        body(UploadStatus::<ListingUploaded>::Error(invalid.into())).await,
+        (
+            StatusCode::BAD_REQUEST,
+            json!({
+                "status": "error",
+                "reason": "invalid_listing",
+                "problems": ["seconds_remaining"],
+                "upload_hints": hints_json(),
+            })
+        ),
+    );
+
+    let failed: UploadStatus<ListingUploaded> = Err(anyhow::anyhow!("connection refused")).into();
+    assert_eq!(
+        body(failed).await,
+        (StatusCode::INTERNAL_SERVER_ERROR, json!({ "status": "error", "reason": "database_error", "upload_hints": hints_json() })),
+    );
+}
+
+/// `insert_listing`의 검증 실패는 저장 실패가 아님
+#[test]
+fn validation_errors_keep_their_reason() {
+    let error = anyhow::Error::new(InvalidListing { id: 1, problems: vec![ListingProblem::World] });
+    assert_eq!(
+        UploadStatus::<ListingUploaded>::from(Err(error)),
+        UploadStatus::Error(UploadError::InvalidListing { problems: vec![ListingProblem::World] }),
+    );
+}
+
+#[tokio::test]
+async fn batch_uploads_report_each_listing() {
+    let result = UploadStatus::Ok(ListingsUploaded {
+        updated: 1,
+        total: 3,
+        unconfirmed: Some(2),
+        results: vec![
+            ListingResult { id: 1, result: UploadStatus::Ok(ListingUploaded { outcome: UploadOutcome::Stale }) },
+            ListingResult {
+                id: 2,
+                result: UploadStatus::Error(UploadError::InvalidListing { problems: vec![ListingProblem::UnknownJob] }),
+            },
+            ListingResult { id: 3, result: UploadStatus::Error(UploadError::DatabaseError) },
+        ],
+    });
+
+    assert_eq!(
+        body(result).await,
+        (
+            StatusCode::OK,
+            json!({
+                "status": "ok",
+                "updated": 1,
+                "total": 3,
+                "unconfirmed": 2,
+                "results": [
+                    { "id": 1, "status": "ok", "outcome": "stale" },
+                    { "id": 2, "status": "error", "reason": "invalid_listing", "problems": ["unknown_job"] },
+                    { "id": 3, "status": "error", "reason": "database_error" },
+                ],
+                "upload_hints": hints_json(),
+            })
+        ),
+    );
+
+    // 스냅샷이 아니면 `unconfirmed` 없음
+    let plain = UploadStatus::Ok(ListingsUploaded { updated: 0, total: 0, unconfirmed: None, results: vec![] });
+    assert_eq!(body(plain).await.1, json!({ "status": "ok", "updated": 0, "total": 0, "results": [], "upload_hints": hints_json() }));
+}
+
+#[tokio::test]
+async fn player_and_detail_responses() {
+    assert_eq!(
+        body(UploadStatus::Ok(PlayersUploaded { updated: 4, total: 5 })).await.1,
+        json!({ "status": "ok", "updated": 4, "total": 5, "upload_hints": hints_json() }),
+    );
+    assert_eq!(
+        body(UploadStatus::Ok(DetailUploaded { matched: 1, modified: 0, leader: LeaderUpsert::Saved })).await.1,
+        json!({ "status": "ok", "matched": 1, "modified": 0, "leader": "saved", "upload_hints": hints_json() }),
+    );
+    // 모집글이 아직 없어도 `ok`, 파티장 저장 실패는 따로 알림
+    assert_eq!(
+        body(UploadStatus::Ok(DetailUploaded { matched: 0, modified: 0, leader: LeaderUpsert::Failed })).await.1,
+        json!({ "status": "ok", "matched": 0, "modified": 0, "leader": "failed", "upload_hints": hints_json() }),
+    );
+}
@@ -0,0 +1,147 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use chrono::Utc;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::http::StatusCode;
+
+use super::mongo_eval::Collection;
+use crate::api::admin::IngestionReport;
+use crate::config::{Config, Logging, RateLimit};
+use crate::listing::PartyFinderListing;
+use crate::listing_container::{QueriedListing, MAX_UPLOADER_FINGERPRINTS};
+use crate::web::fingerprint::{client_ip, fingerprint, salt_from_secret, UploaderSalt};
+use crate::web::routes::router;
+use crate::web::State;
+
+fn ip(s: &str) -> Option<IpAddr> {
+    Some(s.parse().unwrap())
+}
+
+#[test]
+fn three_uploaders_and_one_repeat() {
+    let salt = 42;
+    let uploads = ["203.0.113.1", "203.0.113.2", "2001:db8::7", "203.0.113.1"];
+
+    let listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    let mut listings = Collection::default();
+    for addr in uploads {
+        listings.upload(&listing, &fingerprint(None, ip(addr), salt), false, Utc::now());
+    }
+
+    let stored = listings.stored(&listing);
+    assert_eq!(listings.docs.len(), 1);
+    assert_eq!(stored.upload_count, 4);
+    assert_eq!(stored.uploader_count(), 3);
+}
+
+#[test]
+fn fingerprints_are_capped() {
+    let listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+    let mut listings = Collection::default();
+    for i in 0..40 {
+        listings.upload(&listing, &fingerprint(None, ip(&format!("198.51.100.{}", i)), 1), false, Utc::now());
+    }
+
+    let stored = listings.stored(&listing);
+    assert_eq!(stored.upload_count, 40);
+    assert_eq!(stored.uploader_count(), MAX_UPLOADER_FINGERPRINTS);
+    assert_eq!(stored.uploader_fingerprints[0], fingerprint(None, ip("198.51.100.0"), 1));
+}
+
+#[test]
+fn fingerprint_is_stable_and_salted() {
+    let a = fingerprint(None, ip("203.0.113.1"), 7);
+
+    assert_eq!(a.len(), 16);
+    assert_eq!(a, fingerprint(None, ip("203.0.113.1"), 7));
+    assert_ne!(a, fingerprint(None, ip("203.0.113.1"), 8));
+    assert_ne!(a, fingerprint(None, ip("203.0.113.2"), 7));
+    assert!(!a.contains("203"));
+}
+
+#[test]
+fn token_uploads_share_a_fingerprint_across_addresses() {
+    let home = fingerprint(Some("plugin"), ip("203.0.113.1"), 7);
+
+    assert_eq!(home, fingerprint(Some("plugin"), ip("198.51.100.4"), 7));
+    assert_eq!(home, fingerprint(Some("plugin"), None, 7));
+    assert_ne!(home, fingerprint(Some("bot"), ip("203.0.113.1"), 7));
+    assert_ne!(home, fingerprint(None, ip("203.0.113.1"), 7));
+}
+
+#[test]
+fn configured_secret_sets_the_salt() {
+    let ratelimit = RateLimit { uploader_secret: Some("secret".to_string()), ..Default::default() };
+    let salt = UploaderSalt::from_config(&ratelimit);
+    assert_eq!(salt.get(), Some(salt_from_secret("secret")));
+    assert_ne!(salt_from_secret("secret"), salt_from_secret("other"));
+
+    // 설정이 없으면 시작할 때 저장한 비밀값을 읽을 때까지 비어 있음
+    let salt = UploaderSalt::from_config(&RateLimit::default());
+    assert_eq!(salt.get(), None);
+    salt.set("stored");
+    salt.set("ignored");
+    assert_eq!(salt.get(), Some(salt_from_secret("stored")));
+}
+
+#[tokio::test]
+async fn uploads_wait_for_the_stored_secret() {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    let state = State::new(Arc::new(config), log_handle).await.unwrap();
+
+    let contribute = || warp::test::request().method("POST").path("/contribute").body("not json");
+    let response = contribute().reply(&router(Arc::clone(&state))).await;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.headers()["retry-after"], "5");
+
+    // 잘못된 본문은 지문을 만든 뒤 `400`
+    state.uploader_salt.set("stored");
+    let response = contribute().reply(&router(Arc::clone(&state))).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn forwarded_header_is_read_from_the_right() {
+    // 프록시 하나: 프록시가 붙인 마지막 주소
+    assert_eq!(client_ip("203.0.113.9, 10.0.0.1", 1), ip("10.0.0.1"));
+    assert_eq!(client_ip("203.0.113.9, 10.0.0.1", 2), ip("203.0.113.9"));
+    assert_eq!(client_ip(" 2001:db8::1 ", 1), ip("2001:db8::1"));
+    // 프록시 수보다 주소가 적거나 프록시가 없다고 설정하면 연결 주소 사용
+    assert_eq!(client_ip("203.0.113.9", 2), None);
+    assert_eq!(client_ip("203.0.113.9", 0), None);
+    assert_eq!(client_ip("unknown", 1), None);
+}
+
+#[test]
+fn ingestion_report_counts_multi_sourced() {
+    let queried = |upload_count, uploader_count| {
+        let listing: PartyFinderListing = serde_json::from_str(super::LISTING).unwrap();
+        QueriedListing {
+            time_left: 100.0,
+            upload_count,
+            uploader_count,
+            ..super::queried(listing)
+        }
+    };
+
+    let listings = vec![queried(1, 1), queried(4, 3), queried(2, 1)];
+    let report = IngestionReport::from_listings(&listings);
+
+    assert_eq!(report.listings, 3);
+    assert_eq!(report.uploads, 7);
+    assert_eq!(report.multi_sourced, 2);
+    assert_eq!(report.multi_uploader, 1);
+    assert_eq!(report.entries[0].upload_count, 4);
+}
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+use warp::{Filter, Reply};
+
+use crate::version::{header, version, TableDigest, VERSION, VERSION_HEADER};
+
+#[tokio::test]
+async fn version_endpoint_fields_are_filled() {
+    let response = warp::test::request().path("/version").reply(&version()).await;
+    assert_eq!(response.status(), 200);
+
+    let json: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    for field in ["version", "git_hash", "built_at", "data_tables", "fflogs_mapping"] {
+        let value = json[field].as_str().unwrap_or_default();
+        assert!(!value.is_empty(), "`{}` is empty", field);
+    }
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+}
+
+#[tokio::test]
+async fn every_route_gets_version_header() {
+    let routes = warp::path("listings")
+        .map(|| "listings".into_response())
+        .or(warp::any().map(|| warp::http::StatusCode::NOT_FOUND.into_response()))
+        .unify()
+        .with(header());
+
+    for path in ["/listings", "/missing"] {
+        let response = warp::test::request().path(path).reply(&routes).await;
+        assert_eq!(response.headers()[VERSION_HEADER], VERSION.summary().as_str());
+    }
+    assert!(VERSION.summary().contains(&VERSION.data_tables));
+}
+
+#[test]
+fn table_digest_follows_content_not_order() {
+    let a: HashMap<u32, &str> = (0..50).map(|i| (i, "duty")).collect();
+    let mut b: HashMap<u32, &str> = (0..50).rev().map(|i| (i, "duty")).collect();
+    let digest = |table: &HashMap<u32, &str>| TableDigest::default().table("duties", table).finish();
+
+    assert_eq!(digest(&a), digest(&b));
+    b.insert(7, "renamed duty");
+    assert_ne!(digest(&a), digest(&b));
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeDelta, TimeZone, Timelike, Utc};
+
+use crate::config::VolumeAlerts;
+use crate::web::volume::{VolumeDetector, VolumeEvent};
+
+fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, day, hour, minute, 0).unwrap()
+}
+
+/// 낮에는 100개, 밤(UTC 0~7시)에는 20개
+fn usual(now: DateTime<Utc>) -> u64 {
+    if now.hour() < 8 { 20 } else { 100 }
+}
+
+fn counts(light: u64) -> HashMap<&'static str, u64> {
+    HashMap::from([("Light", light), ("Chaos", 50)])
+}
+
+/// `from`부터 `until` 전까지 5분마다 평소 값을 기록하고 발생한 이벤트 반환
+fn run_usual(detector: &mut VolumeDetector, from: DateTime<Utc>, until: DateTime<Utc>) -> Vec<VolumeEvent> {
+    let mut events = Vec::new();
+    let mut now = from;
+    while now < until {
+        events.extend(detector.observe(&counts(usual(now)), now));
+        now += TimeDelta::try_minutes(5).unwrap();
+    }
+    events
+}
+
+#[test]
+fn night_lows_do_not_trigger() {
+    let mut detector = VolumeDetector::new(VolumeAlerts::default());
+
+    // 사흘 동안 낮 / 밤 변화만 있으면 경고 없음 (하루 평균과 비교하면 밤마다 경고됨)
+    assert!(run_usual(&mut detector, at(1, 0, 0), at(4, 0, 0)).is_empty());
+    assert!(detector.alerts().is_empty());
+}
+
+#[test]
+fn sustained_drop_is_flagged_and_recovers_with_hysteresis() {
+    let mut detector = VolumeDetector::new(VolumeAlerts::default());
+    run_usual(&mut detector, at(1, 0, 0), at(3, 12, 0));
+
+    // 한 번만 적으면 경고하지 않음
+    assert!(detector.observe(&counts(10), at(3, 12, 0)).is_empty());
+    assert!(detector.observe(&counts(100), at(3, 12, 5)).is_empty());
+
+    assert!(detector.observe(&counts(10), at(3, 12, 10)).is_empty());
+    let events = detector.observe(&counts(10), at(3, 12, 15));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].data_centre, "Light");
+    assert!(events[0].dropped);
+    assert!((events[0].baseline - 100.0).abs() < 1e-9);
+
+    let alerts = detector.alerts();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].since, at(3, 12, 15));
+
+    // 해제 기준(70%) 전까지는 경고 유지
+    assert!(detector.observe(&counts(60), at(3, 12, 20)).is_empty());
+    assert_eq!(detector.alerts().len(), 1);
+
+    let events = detector.observe(&counts(80), at(3, 12, 25));
+    assert_eq!(events.len(), 1);
+    assert!(!events[0].dropped);
+    assert!(detector.alerts().is_empty());
+}
+
+#[test]
+fn vanished_data_centre_counts_as_zero() {
+    let mut detector = VolumeDetector::new(VolumeAlerts::default());
+    run_usual(&mut detector, at(1, 0, 0), at(2, 15, 0));
+
+    let only_chaos = HashMap::from([("Chaos", 50)]);
+    assert!(detector.observe(&only_chaos, at(2, 15, 0)).is_empty());
+    let events = detector.observe(&only_chaos, at(2, 15, 5));
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].data_centre, events[0].count), ("Light", 0));
+}
+
+#[test]
+fn no_alert_without_same_hour_history() {
+    let mut detector = VolumeDetector::new(VolumeAlerts::default());
+    // 첫날 오전만 기록된 상태에서 오후 시간대는 평균이 없음
+    run_usual(&mut detector, at(1, 8, 0), at(1, 12, 0));
+
+    assert!(detector.observe(&counts(0), at(1, 13, 0)).is_empty());
+    assert!(detector.observe(&counts(0), at(1, 13, 5)).is_empty());
+    assert!(detector.observe(&counts(0), at(1, 13, 10)).is_empty());
+}
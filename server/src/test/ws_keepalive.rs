@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use warp::ws::Message;
+
+use crate::config::{Config, Logging};
+use crate::web::State;
+use crate::ws::keepalive::Keepalive;
+use crate::ws::limits::{close_code, Tier};
+use crate::ws::WsApiClient;
+
+const INTERVAL: Duration = Duration::from_millis(20);
+
+async fn state() -> Arc<State> {
+    let config: Config = toml::from_str(
+        r#"
+        [web]
+        host = "127.0.0.1:8000"
+
+        [mongo]
+        url = "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100"
+        "#,
+    )
+    .unwrap();
+    let logging = Logging { file: false, ..Default::default() };
+    let (_subscriber, log_handle) = crate::logging::subscriber(&logging, BoxMakeWriter::new(std::io::sink)).unwrap();
+    State::new(Arc::new(config), log_handle).await.unwrap()
+}
+
+/// 채널로 만든 웹소켓
+struct MockSocket<S> {
+    /// 서버 쪽 전송 / 수신
+    sink: S,
+    stream: UnboundedReceiverStream<Result<Message, warp::Error>>,
+    /// 서버가 보낸 프레임
+    server_frames: UnboundedReceiver<Message>,
+    /// 클라이언트가 보낼 프레임
+    client: UnboundedSender<Result<Message, warp::Error>>,
+}
+
+fn mock_socket() -> MockSocket<impl futures_util::Sink<Message, Error = ()> + Unpin> {
+    let (sent, server_frames) = unbounded_channel();
+    let sink = Box::pin(futures_util::sink::unfold(sent, |sent: UnboundedSender<Message>, message| async move {
+        sent.send(message).map_err(|_| ())?;
+        Ok(sent)
+    }));
+    let (client, received) = unbounded_channel();
+    MockSocket { sink, stream: UnboundedReceiverStream::new(received), server_frames, client }
+}
+
+#[test]
+fn websocket_defaults_ping_every_thirty_seconds_and_buffer_512_changes() {
+    let config: Config = toml::from_str("[web]\nhost = \"127.0.0.1:8000\"\n[mongo]\nurl = \"mongodb://x\"").unwrap();
+    assert_eq!(config.websocket.ping_interval_secs, 30);
+    assert_eq!(config.websocket.max_missed_pongs, 2);
+    assert_eq!(config.websocket.broadcast_capacity, 512);
+}
+
+/// pong에 답하는 동안은 유지하고, 응답 없는 ping이 2개 쌓이면 close(1001)로 끊음
+#[tokio::test]
+async fn connections_that_stop_answering_pings_are_closed() {
+    let state = state().await;
+    let permit = state.websockets.admit(Tier::Anonymous, None, Instant::now()).unwrap();
+    let MockSocket { sink, stream, mut server_frames, client } = mock_socket();
+
+    let server = tokio::spawn(WsApiClient::serve(Arc::clone(&state), sink, stream, permit, Keepalive::new(INTERVAL, 2)));
+
+    let mut pings = 0;
+    let close = loop {
+        let frame = tokio::time::timeout(Duration::from_secs(5), server_frames.recv()).await.unwrap().unwrap();
+        if frame.is_close() {
+            break frame;
+        }
+        assert!(frame.is_ping());
+        pings += 1;
+        // 처음 4개에만 응답
+        if pings <= 4 {
+            client.send(Ok(Message::pong(Vec::new()))).unwrap();
+        }
+    };
+
+    assert_eq!(pings, 6);
+    assert_eq!(close.close_frame().map(|(code, _)| code), Some(close_code::PING_TIMEOUT));
+    server.await.unwrap();
+
+    let stats = state.websockets.stats();
+    assert_eq!((stats.anonymous, stats.timed_out), (0, 1));
+}
+
+/// 클라이언트가 끊으면 ping 없이 바로 끝남
+#[tokio::test]
+async fn closed_clients_end_the_connection() {
+    let state = state().await;
+    let permit = state.websockets.admit(Tier::Anonymous, None, Instant::now()).unwrap();
+    let MockSocket { sink, stream, mut server_frames, client } = mock_socket();
+    drop(client);
+
+    WsApiClient::serve(Arc::clone(&state), sink, stream, permit, Keepalive::new(Duration::from_secs(60), 2)).await;
+    assert!(server_frames.try_recv().is_err());
+    assert_eq!(state.websockets.stats().timed_out, 0);
+}
@@ -0,0 +1,137 @@
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::config::Websocket;
+use crate::ws::limits::{close_code, ConnectionLimits, ConnectionStats, Refusal, Tier, SUSTAINED_PRESSURE};
+
+const TOKEN: &str = "bot-token";
+
+fn limits(max_connections: usize, max_per_address: usize) -> Arc<ConnectionLimits> {
+    let config = Websocket { max_connections, max_per_address, ..Default::default() };
+    Arc::new(ConnectionLimits::new(&config, vec![TOKEN.to_string(), String::new()]))
+}
+
+fn address(n: u8) -> Option<IpAddr> {
+    Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, n)))
+}
+
+fn secs(start: Instant, secs: u64) -> Instant {
+    start + Duration::from_secs(secs)
+}
+
+#[test]
+fn tokens_select_the_tier() {
+    let limits = limits(10, 2);
+    assert_eq!(limits.tier(Some(TOKEN)), Tier::Priority);
+    assert_eq!(limits.tier(Some("guess")), Tier::Anonymous);
+    // 빈 토큰은 설정에 없는 것과 같음
+    assert_eq!(limits.tier(Some("")), Tier::Anonymous);
+    assert_eq!(limits.tier(None), Tier::Anonymous);
+}
+
+#[test]
+fn caps_apply_to_anonymous_connections_only() {
+    let limits = limits(3, 2);
+    let now = Instant::now();
+
+    let first = limits.admit(Tier::Anonymous, address(1), now).unwrap();
+    let _second = limits.admit(Tier::Anonymous, address(1), now).unwrap();
+    assert_eq!(limits.admit(Tier::Anonymous, address(1), now).err(), Some(Refusal::TooManyFromAddress));
+    let _third = limits.admit(Tier::Anonymous, address(2), now).unwrap();
+    assert_eq!(limits.admit(Tier::Anonymous, address(3), now).err(), Some(Refusal::Busy));
+
+    // 우선 연결은 같은 주소 / 전체 제한과 관계없이 받음
+    let priority: Vec<_> = (0..5).map(|_| limits.admit(Tier::Priority, address(1), now).unwrap()).collect();
+    assert!(priority.iter().all(|permit| permit.tier() == Tier::Priority));
+
+    assert_eq!(
+        limits.stats(),
+        ConnectionStats { anonymous: 3, priority: 5, refused_busy: 1, refused_per_address: 1, shed: 0, timed_out: 0 },
+    );
+
+    // 연결이 끝나면 자리가 생김
+    drop(first);
+    assert!(limits.admit(Tier::Anonymous, address(1), now).is_ok());
+    assert_eq!(Refusal::Busy.close_code(), close_code::SERVER_BUSY);
+}
+
+#[test]
+fn sustained_pressure_sheds_the_longest_idle_anonymous_connections() {
+    let limits = limits(4, 10);
+    let start = Instant::now();
+
+    let priority = limits.admit(Tier::Priority, address(9), start).unwrap();
+    let permits: Vec<_> = (0..4)
+        .map(|n| limits.admit(Tier::Anonymous, address(n), secs(start, u64::from(n))).unwrap())
+        .collect();
+    // 가장 먼저 연결했지만 최근에 메시지를 보낸 연결은 나중에 끊김
+    permits[0].touch(secs(start, 10));
+
+    // 과부하가 막 시작됐을 때는 거절만 함
+    assert_eq!(limits.admit(Tier::Anonymous, address(20), secs(start, 11)).err(), Some(Refusal::Busy));
+    assert_eq!(limits.admit(Tier::Anonymous, address(21), secs(start, 20)).err(), Some(Refusal::Busy));
+    assert!(permits.iter().all(|permit| !permit.is_shed()));
+
+    // 과부하가 이어지면 가장 오래 조용했던 익명 연결을 끊고 새 연결을 받음
+    let later = secs(start, 11) + SUSTAINED_PRESSURE;
+    let newcomer = limits.admit(Tier::Anonymous, address(22), later).unwrap();
+    let shed: Vec<bool> = permits.iter().map(|permit| permit.is_shed()).collect();
+    assert_eq!(shed, vec![false, true, false, false]);
+    assert!(!priority.is_shed());
+    assert!(!newcomer.is_shed());
+
+    let stats = limits.stats();
+    assert_eq!((stats.anonymous, stats.priority, stats.shed, stats.refused_busy), (4, 1, 1, 2));
+
+    // 끊긴 연결이 정리돼도 두 번 세지 않음
+    drop(permits);
+    assert_eq!(limits.stats().anonymous, 1);
+}
+
+#[test]
+fn priority_connections_are_never_shed() {
+    let limits = limits(1, 10);
+    let start = Instant::now();
+
+    let priority: Vec<_> = (0..3).map(|_| limits.admit(Tier::Priority, None, start).unwrap()).collect();
+    let anonymous = limits.admit(Tier::Anonymous, None, start).unwrap();
+
+    assert_eq!(limits.admit(Tier::Anonymous, None, start).err(), Some(Refusal::Busy));
+    let _replacement = limits.admit(Tier::Anonymous, None, start + SUSTAINED_PRESSURE).unwrap();
+
+    assert!(anonymous.is_shed());
+    assert!(priority.iter().all(|permit| !permit.is_shed()));
+    assert_eq!(limits.stats().priority, 3);
+}
+
+#[tokio::test]
+async fn shed_signal_wakes_the_connection() {
+    let limits = limits(1, 10);
+    let start = Instant::now();
+    let victim = limits.admit(Tier::Anonymous, None, start).unwrap();
+
+    let waiter = tokio::spawn(async move {
+        victim.shed().await;
+        victim.is_shed()
+    });
+
+    assert!(limits.admit(Tier::Anonymous, None, start).is_err());
+    let _replacement = limits.admit(Tier::Anonymous, None, start + SUSTAINED_PRESSURE).unwrap();
+    assert!(tokio::time::timeout(Duration::from_secs(1), waiter).await.unwrap().unwrap());
+}
+
+/// 관리자 토큰은 쿼리 문자열로 받지 않음 (접근 로그에 남음)
+#[test]
+fn admin_token_is_not_a_priority_token() {
+    let admin: crate::config::Admin = toml::from_str(
+        r#"
+        token = "admin-token"
+        websocket_tokens = ["bot-token"]
+        "#,
+    )
+    .unwrap();
+    let limits = ConnectionLimits::new(&Websocket::default(), admin.priority_tokens());
+    assert_eq!(limits.tier(Some("bot-token")), Tier::Priority);
+    assert_eq!(limits.tier(Some("admin-token")), Tier::Anonymous);
+}
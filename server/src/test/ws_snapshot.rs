@@ -0,0 +1,150 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use super::fixture_world::{ListingBuilder, DUNGEON, SAVAGE};
+use crate::listing::{DutyCategory, ListingFilter};
+use crate::web::listing_events::{ListingEvent, ListingRef};
+use crate::ws::{stream_listings, InboundApiMessage, OutboundApiMessage};
+
+async fn next(outbound: &mut UnboundedReceiver<OutboundApiMessage>) -> Value {
+    serde_json::to_value(outbound.recv().await.unwrap()).unwrap()
+}
+
+fn ids(message: &Value) -> Vec<u64> {
+    message["listings"].as_array().unwrap().iter().map(|listing| listing["id"].as_u64().unwrap()).collect()
+}
+
+/// 스냅샷을 읽는 동안 올라온 모집글은 스냅샷 다음 `update`로 전송
+#[tokio::test]
+async fn uploads_during_the_snapshot_follow_as_updates() {
+    let (channel, receiver) = tokio::sync::broadcast::channel(16);
+    let (sender, mut outbound) = tokio::sync::mpsc::unbounded_channel();
+
+    let uploaded = ListingBuilder::new(2).build().listing;
+    let snapshot = move || {
+        // 스냅샷 조회가 끝나기 전에 업로드됨
+        channel.send(ListingEvent::Updated(vec![uploaded.clone()].into())).unwrap();
+        async { Ok(Arc::new(vec![ListingBuilder::new(1).one_player_per_job().member(1001, 19).member(1002, 19).build()])) }
+    };
+    tokio::spawn(stream_listings(receiver, snapshot, ListingFilter::default(), sender));
+
+    let first = next(&mut outbound).await;
+    assert_eq!(first["type"], "snapshot");
+    assert_eq!(ids(&first), [1]);
+    // 업데이트와 같은 자리 규칙
+    assert_eq!(first["listings"][0]["jobs_present"][1], 0);
+
+    let second = next(&mut outbound).await;
+    assert_eq!(second["type"], "update");
+    assert_eq!(ids(&second), [2]);
+}
+
+#[tokio::test]
+async fn snapshot_errors_are_reported_and_updates_continue() {
+    let (channel, receiver) = tokio::sync::broadcast::channel(16);
+    let (sender, mut outbound) = tokio::sync::mpsc::unbounded_channel();
+
+    tokio::spawn(stream_listings(receiver, || async { anyhow::bail!("database unavailable") }, ListingFilter::default(), sender));
+
+    let first = next(&mut outbound).await;
+    assert_eq!(first["type"], "err");
+    assert_eq!(first["message"], "could not load current listings");
+
+    channel.send(ListingEvent::Updated(vec![ListingBuilder::new(3).build().listing].into())).unwrap();
+    let second = next(&mut outbound).await;
+    assert_eq!(second["type"], "update");
+    assert_eq!(ids(&second), [3]);
+}
+
+fn subscription(json: &str) -> Result<ListingFilter, String> {
+    match serde_json::from_str(json).unwrap() {
+        InboundApiMessage::Subscribe { filter, .. } => filter.into_filter(),
+        other => panic!("not a subscription: {:?}", other),
+    }
+}
+
+#[test]
+fn subscribe_messages_carry_an_optional_filter() {
+    assert_eq!(subscription(r#"{"type":"subscribe","channel":"listings"}"#), Ok(ListingFilter::default()));
+    assert_eq!(
+        subscription(
+            r#"{"type":"subscribe","channel":"listings","duties":[1075],"created_worlds":[45,49],"categories":["HighEndDuty"]}"#
+        ),
+        Ok(ListingFilter { duties: vec![1075], worlds: vec![45, 49], categories: vec![DutyCategory::HighEndDuty], flagged: false })
+    );
+    assert_eq!(
+        subscription(r#"{"type":"subscribe","channel":"listings","categories":["Nope"]}"#),
+        Err("unknown category `Nope`".to_string())
+    );
+}
+
+/// 구독 조건에 맞는 모집글만 스냅샷 / 업데이트로 보내고, 사라진 모집글은 생성 서버로만 거름
+#[tokio::test]
+async fn filtered_subscriptions_only_receive_matching_listings() {
+    let (channel, receiver) = tokio::sync::broadcast::channel(16);
+    let (sender, mut outbound) = tokio::sync::mpsc::unbounded_channel();
+
+    let snapshot = Arc::new(vec![
+        ListingBuilder::new(1).duty(SAVAGE, DutyCategory::HighEndDuty).world(45).build(),
+        ListingBuilder::new(2).duty(SAVAGE, DutyCategory::HighEndDuty).world(73).build(),
+        ListingBuilder::new(3).duty(DUNGEON, DutyCategory::Dungeon).world(45).build(),
+    ]);
+    let filter = ListingFilter { duties: vec![SAVAGE], worlds: vec![45, 49], categories: vec![], flagged: false };
+    tokio::spawn(stream_listings(receiver, move || std::future::ready(Ok(Arc::clone(&snapshot))), filter, sender));
+
+    let first = next(&mut outbound).await;
+    assert_eq!(first["type"], "snapshot");
+    assert_eq!(ids(&first), [1]);
+
+    // 맞는 모집글이 없는 업로드는 보내지 않음
+    let dungeon = ListingBuilder::new(4).duty(DUNGEON, DutyCategory::Dungeon).world(49).listing();
+    channel.send(ListingEvent::Updated(vec![dungeon.clone()].into())).unwrap();
+    let savage = ListingBuilder::new(5).duty(SAVAGE, DutyCategory::HighEndDuty).world(49).listing();
+    channel.send(ListingEvent::Updated(vec![dungeon, savage].into())).unwrap();
+    let removed = |id, created_world| ListingRef { id, created_world, last_server_restart: 0 };
+    channel.send(ListingEvent::Removed(vec![removed(2, 73), removed(3, 45)].into())).unwrap();
+
+    let update = next(&mut outbound).await;
+    assert_eq!(update["type"], "update");
+    assert_eq!(ids(&update), [5]);
+
+    let removal = next(&mut outbound).await;
+    assert_eq!(removal["type"], "removed");
+    assert_eq!(ids(&removal), [3]);
+}
+
+/// 방송 채널을 따라가지 못한 구독자는 놓친 변경 대신 새 스냅샷을 받고 이후 업데이트를 계속 받음
+#[tokio::test]
+async fn lagging_subscribers_are_resynchronized_with_a_new_snapshot() {
+    let (channel, receiver) = tokio::sync::broadcast::channel(2);
+    let (sender, mut outbound) = tokio::sync::mpsc::unbounded_channel();
+
+    let snapshots = Arc::new(AtomicU32::new(0));
+    let counter = Arc::clone(&snapshots);
+    let snapshot = move || {
+        let taken = counter.fetch_add(1, Ordering::Relaxed) + 1;
+        std::future::ready(Ok(Arc::new(vec![ListingBuilder::new(100 + taken).build()])))
+    };
+    tokio::spawn(stream_listings(receiver, snapshot, ListingFilter::default(), sender));
+    assert_eq!(ids(&next(&mut outbound).await), [101]);
+
+    // 구독자가 읽기 전에 채널 용량보다 많이 보냄
+    for id in 1..=4 {
+        channel.send(ListingEvent::Updated(vec![ListingBuilder::new(id).build().listing].into())).unwrap();
+    }
+
+    let lagged = next(&mut outbound).await;
+    assert_eq!(lagged, serde_json::json!({ "type": "lagged", "missed": 2 }));
+    let resync = next(&mut outbound).await;
+    assert_eq!(resync["type"], "snapshot");
+    assert_eq!(ids(&resync), [102]);
+    for id in [3, 4] {
+        let update = next(&mut outbound).await;
+        assert_eq!(update["type"], "update");
+        assert_eq!(ids(&update), [id]);
+    }
+    assert_eq!(snapshots.load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,97 @@
+//! 빌드 / 데이터 테이블 버전 정보
+//!
+//! 사용자 제보를 조사할 때 어떤 바이너리와 듀티 테이블이 응답했는지 알 수 있도록
+//! `GET /api/version`과 모든 응답의 `X-RPF-Version` 헤더로 노출합니다.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use warp::filters::BoxedFilter;
+use warp::reply::with::WithHeader;
+use warp::{Filter, Reply};
+
+/// 모든 응답에 붙이는 버전 헤더
+pub const VERSION_HEADER: &str = "x-rpf-version";
+
+pub static VERSION: LazyLock<VersionInfo> = LazyLock::new(VersionInfo::current);
+
+/// 실행 중인 서버의 버전 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionInfo {
+    /// `Cargo.toml`의 패키지 버전
+    pub version: &'static str,
+    /// 빌드한 커밋 (알 수 없으면 `"unknown"`)
+    pub git_hash: &'static str,
+    pub built_at: DateTime<Utc>,
+    /// 컴파일된 FFXIV 데이터 테이블 (듀티, 잡, 월드 등) 해시
+    pub data_tables: String,
+    /// `DUTY_TO_FFLOGS` / `FFLOGS_ZONES` 매핑 해시
+    pub fflogs_mapping: String,
+}
+
+impl VersionInfo {
+    fn current() -> Self {
+        let built_at = env!("RPF_BUILD_TIMESTAMP")
+            .parse()
+            .ok()
+            .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+            .unwrap_or_default();
+
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_hash: env!("RPF_GIT_HASH"),
+            built_at,
+            data_tables: crate::ffxiv::data_tables_hash(),
+            fflogs_mapping: crate::fflogs::mapping::mapping_hash(),
+        }
+    }
+
+    /// 헤더 / 시작 로그용 한 줄 표기 (예: `0.1.0+1a2b3c4d5e6f data=… fflogs=…`)
+    pub fn summary(&self) -> String {
+        format!(
+            "{}+{} data={} fflogs={}",
+            self.version, self.git_hash, self.data_tables, self.fflogs_mapping
+        )
+    }
+}
+
+/// 테이블 내용 해시 (키 순서로 정렬한 `Debug` 표현 기준이라 HashMap 순서와 무관)
+#[derive(Default)]
+pub struct TableDigest {
+    hasher: Sha256,
+}
+
+impl TableDigest {
+    pub fn table<K: Ord + Debug, V: Debug>(mut self, name: &str, table: &HashMap<K, V>) -> Self {
+        let mut entries: Vec<_> = table.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+        self.hasher.update(name.as_bytes());
+        for (key, value) in entries {
+            self.hasher.update(format!("{:?}={:?};", key, value).as_bytes());
+        }
+        self
+    }
+
+    pub fn finish(self) -> String {
+        self.hasher.finalize()[..6].iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// 모든 응답에 `X-RPF-Version` 헤더 추가
+pub fn header() -> WithHeader {
+    warp::reply::with::header(VERSION_HEADER, VERSION.summary())
+}
+
+/// GET /api/version
+pub fn version() -> BoxedFilter<(impl Reply,)> {
+    warp::get()
+        .and(warp::path("version"))
+        .and(warp::path::end())
+        .map(|| warp::reply::json(&*VERSION))
+        .boxed()
+}
@@ -0,0 +1,257 @@
+//! 관리자 페이지 (`/admin`)
+//!
+//! 관리자 API를 curl 없이 쓰기 위한 서버 렌더링 페이지입니다. 관리자 토큰으로 로그인하면 서명한
+//! 세션 쿠키를 발급하고, 페이지의 폼은 기존 `/api/admin/...` 엔드포인트로 바로 전송합니다.
+//! API는 `Authorization` 헤더 대신 세션 쿠키도 받으며, 이때 POST 요청은 폼 주소에 붙은
+//! `csrf` 토큰을 요구합니다. 쿠키 형식: `만료 시각(유닉스 초).base64url(HMAC-SHA256)`
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use warp::filters::BoxedFilter;
+use warp::http::{header, Response, StatusCode};
+use warp::{Filter, Reply};
+
+use crate::api::admin::ListingDiagnostics;
+use crate::bookmarks::parse_listing_key;
+use crate::ffxiv::Language;
+use crate::listing_container::ListingContainer;
+use crate::mongo::get_raw_listing;
+use crate::template::admin::{AdminLoginTemplate, AdminTemplate, ListingLookup};
+use crate::web::token_compare::tokens_match;
+use crate::web::State;
+
+/// 세션 쿠키 이름
+pub const SESSION_COOKIE: &str = "rpf_admin";
+
+/// 세션 유지 시간
+pub const SESSION_HOURS: i64 = 12;
+
+const BASE64: base64::Config = base64::URL_SAFE_NO_PAD;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 관리자 세션 쿠키와 CSRF 토큰 서명 / 검증
+///
+/// 관리자 토큰을 키로 쓰므로 토큰을 바꾸면 기존 세션이 모두 무효가 됩니다.
+pub struct AdminSessions {
+    token: String,
+}
+
+impl AdminSessions {
+    pub fn new(token: &str) -> Self {
+        Self { token: token.to_string() }
+    }
+
+    /// 설정의 관리자 토큰 (`[admin]`이 없거나 토큰이 비었으면 `None`)
+    pub fn from_state(state: &State) -> Option<Self> {
+        state
+            .config
+            .admin
+            .as_ref()
+            .filter(|admin| !admin.token.is_empty())
+            .map(|admin| Self::new(&admin.token))
+    }
+
+    fn mac(&self, purpose: &str, payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(self.token.as_bytes()).expect("HMAC accepts keys of any length");
+        mac.update(purpose.as_bytes());
+        mac.update(b":");
+        mac.update(payload.as_bytes());
+        mac
+    }
+
+    fn verify_mac(&self, purpose: &str, payload: &str, signature: &str) -> bool {
+        base64::decode_config(signature, BASE64)
+            .is_ok_and(|signature| self.mac(purpose, payload).verify_slice(&signature).is_ok())
+    }
+
+    /// 관리자 토큰 확인 (로그인 폼, `Authorization` 헤더)
+    pub fn check_token(&self, provided: &str) -> bool {
+        !provided.is_empty() && tokens_match(&self.token, provided)
+    }
+
+    /// 새 세션 쿠키 값
+    pub fn issue(&self, now: DateTime<Utc>) -> String {
+        let expires = (now + TimeDelta::try_hours(SESSION_HOURS).unwrap()).timestamp().to_string();
+        let signature = self.mac("session", &expires).finalize().into_bytes();
+        format!("{}.{}", expires, base64::encode_config(signature, BASE64))
+    }
+
+    /// 서명이 맞고 만료되지 않은 세션인지
+    pub fn verify(&self, session: &str, now: DateTime<Utc>) -> bool {
+        let Some((expires, signature)) = session.split_once('.') else {
+            return false;
+        };
+        expires.parse::<i64>().is_ok_and(|expires| now.timestamp() < expires)
+            && self.verify_mac("session", expires, signature)
+    }
+
+    /// 세션에 묶인 CSRF 토큰 (페이지의 폼 주소에 붙임)
+    pub fn csrf_token(&self, session: &str) -> String {
+        base64::encode_config(self.mac("csrf", session).finalize().into_bytes(), BASE64)
+    }
+
+    pub fn verify_csrf(&self, session: &str, csrf: &str) -> bool {
+        self.verify_mac("csrf", session, csrf)
+    }
+}
+
+/// 세션 쿠키 `Set-Cookie` 값 (HTTPS 뒤에 있으면 `Secure`)
+pub fn session_cookie(session: &str, secure: bool) -> String {
+    format!(
+        "{}={}; Path=/; Max-Age={}; HttpOnly; SameSite=Strict{}",
+        SESSION_COOKIE,
+        session,
+        SESSION_HOURS * 60 * 60,
+        if secure { "; Secure" } else { "" },
+    )
+}
+
+pub fn admin_page(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    warp::path("admin")
+        .and(page(Arc::clone(&state)).or(login(Arc::clone(&state))).or(logout()))
+        .boxed()
+}
+
+#[derive(Debug, Deserialize)]
+struct PageQuery {
+    /// 조회할 모집글 키 (`id/created_world/last_server_restart`)
+    listing: Option<String>,
+}
+
+/// GET /admin: 로그인했으면 관리자 페이지, 아니면 로그인 폼
+fn page(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(
+        state: Arc<State>,
+        lang: Language,
+        session: Option<String>,
+        query: PageQuery,
+    ) -> Result<warp::reply::Response, Infallible> {
+        let sessions = AdminSessions::from_state(&state);
+        let session = session.filter(|session| sessions.as_ref().is_some_and(|s| s.verify(session, Utc::now())));
+        let (Some(sessions), Some(session)) = (sessions, session) else {
+            return Ok(no_store(AdminLoginTemplate { lang, error: None }));
+        };
+
+        let lookup = match query.listing.as_deref().map(str::trim).filter(|key| !key.is_empty()) {
+            Some(key) => Some(lookup_listing(&state, key).await),
+            None => None,
+        };
+
+        let compaction = &state.player_compaction;
+        let template = AdminTemplate {
+            lang,
+            csrf: sessions.csrf_token(&session),
+            maintenance: state.maintenance.status(Utc::now()),
+            compaction_running: compaction.running(),
+            compaction_last: compaction.last_report(),
+            unknown_ids: state.unknown_ids.snapshot(),
+            blocklist: state.blocklist(),
+            fflogs_enabled: state.fflogs_client.is_some(),
+            log_directives: state.log_handle.directives(),
+            lookup,
+        };
+        Ok(no_store(template))
+    }
+
+    warp::get()
+        .and(warp::path::end())
+        .and(super::routes::language())
+        .and(warp::cookie::optional::<String>(SESSION_COOKIE))
+        .and(warp::query::<PageQuery>())
+        .and_then(move |lang, session, query| logic(Arc::clone(&state), lang, session, query))
+        .boxed()
+}
+
+/// 모집글 원본 조회 (DB에 연결할 수 없어도 페이지는 표시)
+async fn lookup_listing(state: &State, key: &str) -> ListingLookup {
+    let Some((id, created_world, last_server_restart)) = parse_listing_key(key) else {
+        return ListingLookup::error(key, "expected id/created_world/last_server_restart");
+    };
+
+    let document = state
+        .collection()
+        .read_one(|collection| get_raw_listing(collection, id, created_world, last_server_restart))
+        .await;
+    match document {
+        Ok(Some(document)) => match mongodb::bson::from_document::<ListingContainer>(document) {
+            // 검증한 키만 링크와 폼 주소에 씀
+            Ok(container) => ListingLookup {
+                key: format!("{}/{}/{}", id, created_world, last_server_restart),
+                diagnostics: Some(ListingDiagnostics::new(&container, Utc::now(), &state.config.sort)),
+                error: None,
+            },
+            Err(_) => ListingLookup::error(key, "stored document does not deserialize"),
+        },
+        Ok(None) => ListingLookup::error(key, "not found"),
+        Err(e) => {
+            tracing::warn!("[Admin] Listing lookup failed: {:#?}", e);
+            ListingLookup::error(key, "database unavailable")
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LoginForm {
+    token: String,
+}
+
+/// POST /admin/login
+fn login(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    async fn logic(
+        state: Arc<State>,
+        lang: Language,
+        forwarded_proto: Option<String>,
+        form: LoginForm,
+    ) -> Result<warp::reply::Response, Infallible> {
+        let Some(sessions) = AdminSessions::from_state(&state).filter(|sessions| sessions.check_token(&form.token)) else {
+            tracing::warn!("[Admin] Rejected admin page login");
+            let template = AdminLoginTemplate { lang, error: Some("Invalid admin token.") };
+            return Ok(warp::reply::with_status(no_store(template), StatusCode::UNAUTHORIZED).into_response());
+        };
+
+        let secure = forwarded_proto.as_deref() == Some("https");
+        Ok(see_other(Some(session_cookie(&sessions.issue(Utc::now()), secure))))
+    }
+
+    warp::post()
+        .and(warp::path("login"))
+        .and(warp::path::end())
+        .and(super::routes::language())
+        .and(warp::header::optional::<String>("x-forwarded-proto"))
+        .and(warp::body::form())
+        .and_then(move |lang, proto, form| logic(Arc::clone(&state), lang, proto, form))
+        .boxed()
+}
+
+/// POST /admin/logout
+fn logout() -> BoxedFilter<(impl Reply,)> {
+    warp::post()
+        .and(warp::path("logout"))
+        .and(warp::path::end())
+        .map(|| see_other(Some(format!("{}=; Path=/; Max-Age=0; HttpOnly; SameSite=Strict", SESSION_COOKIE))))
+        .boxed()
+}
+
+/// `/admin`으로 돌아가는 응답 (쿠키 설정 포함)
+fn see_other(cookie: Option<String>) -> warp::reply::Response {
+    let mut response = Response::builder()
+        .status(StatusCode::SEE_OTHER)
+        .header(header::LOCATION, "/admin")
+        .header(header::CACHE_CONTROL, "no-store");
+    if let Some(cookie) = cookie {
+        response = response.header(header::SET_COOKIE, cookie);
+    }
+    response
+        .body(warp::hyper::Body::empty())
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+fn no_store(reply: impl Reply) -> warp::reply::Response {
+    warp::reply::with_header(reply, header::CACHE_CONTROL, "no-store").into_response()
+}
@@ -0,0 +1,95 @@
+//! 모집글 보관 대기열
+//!
+//! 업로드 요청은 보관 기록을 직접 쓰지 않고 대기열에 넣기만 합니다. 보관 태스크가 주기적으로 대기열을 비워
+//! `listings_archive`에 씁니다. 같은 모집글은 대기열에서 합쳐지므로 자주 올라오는 모집글도 주기마다 한 번만 씁니다.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::listing::archive::PendingArchive;
+use crate::listing::PartyFinderListing;
+
+/// 보관 태스크가 대기열을 비우는 간격
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 대기열 최대 모집글 수 (넘으면 새 모집글은 버리고 `dropped`에 셈)
+pub const MAX_PENDING: usize = 20_000;
+
+/// `/api/health`에 표시하는 대기열 상태
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct ArchiveStats {
+    pub pending: usize,
+    /// 보관한 모집글 기록 수 (같은 모집글의 재기록 포함)
+    pub written: u64,
+    /// 대기열이 가득 차 버린 모집글 수
+    pub dropped: u64,
+}
+
+pub struct ArchiveQueue {
+    pending: Mutex<HashMap<(u32, u32, u16), PendingArchive>>,
+    capacity: usize,
+    written: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl Default for ArchiveQueue {
+    fn default() -> Self {
+        Self::new(MAX_PENDING)
+    }
+}
+
+impl ArchiveQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            pending: Default::default(),
+            capacity,
+            written: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// 저장된 업로드를 대기열에 추가
+    pub fn push(&self, listing: &PartyFinderListing, seen_at: DateTime<Utc>) {
+        self.add(PendingArchive::new(listing.clone(), seen_at));
+    }
+
+    fn add(&self, entry: PendingArchive) {
+        let mut pending = self.pending.lock().unwrap();
+        if let Some(existing) = pending.get_mut(&entry.key()) {
+            existing.merge(entry);
+        } else if pending.len() < self.capacity {
+            pending.insert(entry.key(), entry);
+        } else {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// 대기 중인 모집글을 모두 꺼냄
+    pub fn take(&self) -> Vec<PendingArchive> {
+        std::mem::take(&mut *self.pending.lock().unwrap()).into_values().collect()
+    }
+
+    /// 쓰지 못한 모집글을 다음 주기로 되돌림 (그 사이 들어온 업로드와 합침)
+    pub fn restore(&self, entries: Vec<PendingArchive>) {
+        for entry in entries {
+            self.add(entry);
+        }
+    }
+
+    pub fn record_written(&self, count: usize) {
+        self.written.fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> ArchiveStats {
+        ArchiveStats {
+            pending: self.pending.lock().unwrap().len(),
+            written: self.written.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -1,36 +1,377 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{collections::{HashMap, HashSet}, path::PathBuf, sync::Arc, time::Duration};
 use anyhow::Result;
+use tokio::sync::broadcast::error::RecvError;
 
-use crate::mongo::{get_current_listings, get_players_by_content_ids};
+use crate::infra::player_compaction::compact_players;
+use crate::listing::archive::PendingArchive;
+use crate::listing::Blocklist;
+use crate::mongo::count_active_listings;
+use super::listing_events::{ListingEvent, ListingRef, RemovalTracker};
+use super::maintenance::{BackgroundTask, ACTIVE_LISTING_WINDOW};
+use super::stats_refresh::retry_delay;
+use super::volume::SAMPLE_INTERVAL;
+use crate::stats::daily;
+use crate::stats::role_demand;
 use crate::stats::CachedStatistics;
 use super::State;
 
+/// 시작하자마자 통계를 계산하고, 이후 정기 간격(`stats_interval_hours`) 또는 오래된 통계 갱신 요청마다 다시 계산하는 태스크
+///
+/// 실패하면 가진 통계를 그대로 두고 `retry_delay`만큼 기다린 뒤 다시 계산합니다.
 pub fn spawn_stats_task(state: Arc<State>) {
     let stats_state = Arc::clone(&state);
+    let interval = Duration::from_secs(state.config.web.stats_interval_hours.max(1) * 60 * 60);
     tokio::task::spawn(async move {
+        let mut failures = 0;
         loop {
-            let all_time = match crate::stats::get_stats(&*stats_state).await {
-                Ok(stats) => stats,
+            if stats_state.maintenance.should_skip(BackgroundTask::Stats) {
+                tokio::time::sleep(MAINTENANCE_RECHECK).await;
+                continue;
+            }
+
+            stats_state.stats_refresh.begin();
+            let started = std::time::Instant::now();
+            let stats = generate_stats(&stats_state).await;
+            stats_state.metrics.record_stats_run(stats.is_ok(), started.elapsed());
+            if stats.is_err() {
+                stats_state.metrics.record_mongo_error("stats");
+            }
+            stats_state.stats_refresh.finish();
+
+            match stats {
+                Ok(stats) => {
+                    if failures > 0 {
+                        tracing::info!("Stats generated after {} failed attempts", failures);
+                    }
+                    failures = 0;
+                    *stats_state.stats.write().await = Some(stats);
+                    stats_state.readiness.mark_stats_ready();
+                    stats_state.stats_refresh.wait(interval).await;
+                }
                 Err(e) => {
-                    tracing::error!("error generating stats: {:#?}", e);
-                    continue;
+                    failures += 1;
+                    let delay = retry_delay(failures);
+                    tracing::error!("error generating stats (attempt {}, retrying in {:?}): {:#?}", failures, delay, e);
+                    tokio::time::sleep(delay).await;
                 }
-            };
+            }
+        }
+    });
+}
+
+async fn generate_stats(state: &State) -> Result<CachedStatistics> {
+    let all_time = crate::stats::get_stats(state).await?;
+    let seven_days = crate::stats::get_stats_seven_days(state).await?;
+    Ok(CachedStatistics { all_time, seven_days, updated_at: chrono::Utc::now() })
+}
+
+/// 점검 중 통계 태스크가 다시 확인하기까지 대기 시간
+const MAINTENANCE_RECHECK: Duration = Duration::from_secs(60);
 
-            let seven_days = match crate::stats::get_stats_seven_days(&*stats_state).await {
-                Ok(stats) => stats,
+/// 활성 모집글 수로 점검을 추정하는 태스크
+pub fn spawn_maintenance_task(state: Arc<State>) {
+    tokio::task::spawn(async move {
+        loop {
+            let since = chrono::Utc::now() - chrono::TimeDelta::from_std(ACTIVE_LISTING_WINDOW).unwrap();
+            match count_active_listings(state.collection().primary(), since).await {
+                Ok(count) => state.maintenance.observe_active_listings(count, std::time::Instant::now()),
+                Err(e) => tracing::warn!("could not count active listings: {:#?}", e),
+            }
+
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+}
+
+/// 현재 모집글을 주기적으로 표본 추출하는 태스크 (점검 중에는 건너뜀)
+///
+/// 한 번 조회한 목록으로 데이터 센터별 모집글 수 급감 감지와 역할별 빈 자리 기록을 함께 처리합니다.
+pub fn spawn_sampling_task(state: Arc<State>) {
+    tokio::task::spawn(async move {
+        let data_centres = role_demand::data_centres();
+        let mut last_role_demand: Option<chrono::DateTime<chrono::Utc>> = None;
+
+        loop {
+            tokio::time::sleep(SAMPLE_INTERVAL).await;
+
+            let now = chrono::Utc::now();
+            if state.maintenance.pause_reason(now).is_some() {
+                continue;
+            }
+
+            let listings = match state.current_listings().await {
+                Ok(listings) => listings,
                 Err(e) => {
-                    tracing::error!("error generating stats: {:#?}", e);
+                    tracing::warn!("could not sample current listings: {:#?}", e);
                     continue;
                 }
             };
 
-            *stats_state.stats.write().await = Some(CachedStatistics {
-                all_time,
-                seven_days,
-            });
+            let mut counts: HashMap<&'static str, u64> = HashMap::new();
+            for data_centre in listings.iter().filter_map(|l| l.listing.data_centre_name()) {
+                *counts.entry(data_centre).or_default() += 1;
+            }
+            state.volume.observe(&counts, now).await;
+
+            let role_demand_due = last_role_demand
+                .is_none_or(|last| (now - last).to_std().unwrap_or_default() >= role_demand::SAMPLE_INTERVAL);
+            if role_demand_due {
+                let samples = role_demand::sample(listings.iter().map(|l| &l.listing), &data_centres, now);
+                match crate::mongo::insert_role_demand(state.role_demand_collection(), &samples).await {
+                    Ok(()) => last_role_demand = Some(now),
+                    Err(e) => tracing::warn!("could not record role demand: {:#?}", e),
+                }
+            }
+        }
+    });
+}
+
+/// 모집글이 TTL로 지워지기 전에 날짜별 모집글 수를 `daily_listings`에 더하는 태스크
+pub fn spawn_daily_rollup_task(state: Arc<State>) {
+    tokio::task::spawn(async move {
+        loop {
+            if let Err(e) = rollup_daily_listings(&state).await {
+                tracing::warn!("could not roll up daily listing counts: {:#?}", e);
+            }
+
+            tokio::time::sleep(daily::ROLLUP_INTERVAL).await;
+        }
+    });
+}
+
+async fn rollup_daily_listings(state: &State) -> Result<()> {
+    let collection = state.daily_listings_collection();
+    let watermark = crate::mongo::daily_listings_watermark(collection.clone()).await?;
+    let Some((after, until)) = daily::rollup_window(watermark, chrono::Utc::now()) else {
+        return Ok(());
+    };
+
+    let counts = crate::mongo::count_listings_by_day(state.collection().primary(), after, until).await?;
+
+    crate::mongo::add_daily_listings(collection, &counts, until).await?;
+    tracing::debug!("rolled up {} day(s) of listings up to {}", counts.len(), until);
+    Ok(())
+}
+
+/// 업로드 대기열을 `listings_archive`에 쓰는 태스크
+///
+/// 시작할 때 아직 남아 있는 모집글을 먼저 대기열에 넣어, 배포 전에 올라온 모집글도 보관합니다.
+pub fn spawn_archive_task(state: Arc<State>) {
+    tokio::task::spawn(async move {
+        match crate::mongo::all_listings(state.collection().primary()).await {
+            Ok(listings) => {
+                let count = listings.len();
+                state.archive.restore(listings.into_iter().map(PendingArchive::stored).collect());
+                tracing::info!("queued {} stored listings for the archive", count);
+            }
+            Err(e) => tracing::warn!("could not queue current listings for the archive: {:#?}", e),
+        }
+
+        loop {
+            tokio::time::sleep(super::archive::FLUSH_INTERVAL).await;
+
+            let entries = state.archive.take();
+            if entries.is_empty() {
+                continue;
+            }
+            match crate::mongo::archive_listings(state.archive_collection(), entries).await {
+                Ok(written) => state.archive.record_written(written),
+                Err((e, unwritten)) => {
+                    tracing::warn!("could not archive {} listings: {:#?}", unwritten.len(), e);
+                    state.archive.restore(unwritten);
+                }
+            }
+        }
+    });
+}
+
+/// 다 채워진 업로드 요청 제한 버킷을 지우는 태스크
+pub fn spawn_rate_limit_prune_task(state: Arc<State>) {
+    tokio::task::spawn(async move {
+        loop {
+            tokio::time::sleep(super::rate_limit::PRUNE_INTERVAL).await;
+
+            let pruned = state.contribute_limiter.prune(std::time::Instant::now());
+            tracing::debug!("pruned {} rate limit buckets", pruned);
+        }
+    });
+}
+
+/// 현재 목록에서 사라진 모집글을 찾는 간격
+pub const REMOVAL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 주기마다 현재 모집글을 이전 주기와 비교해 사라진 모집글을 웹소켓으로 보내는 태스크
+///
+/// 주기 사이에 업로드로 전송된 모집글도 기록해, 다음 주기 전에 만료된 모집글도 사라졌다고 보냅니다.
+pub fn spawn_removal_task(state: Arc<State>) {
+    tokio::task::spawn(async move {
+        let mut tracker = RemovalTracker::default();
+        let mut events = state.listings_channel.subscribe();
+        let mut interval = tokio::time::interval(REMOVAL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Ok(ListingEvent::Updated(listings)) => tracker.observe_updated(&listings),
+                    Ok(ListingEvent::Removed(_)) => {}
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                },
+                _ = interval.tick() => {
+                    let listings = match state.current_listings().await {
+                        Ok(listings) => listings,
+                        Err(e) => {
+                            tracing::warn!("could not check for removed listings: {:#?}", e);
+                            continue;
+                        }
+                    };
+
+                    let removed = tracker.diff(listings.iter().map(|l| ListingRef::from(&l.listing)));
+                    if !removed.is_empty() {
+                        let _ = state.listings_channel.send(ListingEvent::Removed(removed.into()));
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// SIGHUP을 받으면 설정 파일에서 목록 숨김 설정을 다시 읽는 태스크 (다른 설정은 재시작 필요)
+#[cfg(unix)]
+pub fn spawn_reload_task(state: Arc<State>, config_path: PathBuf) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            tracing::warn!("could not listen for SIGHUP, config reload disabled: {:#?}", e);
+            return;
+        }
+    };
+
+    tokio::task::spawn(async move {
+        while hangup.recv().await.is_some() {
+            match crate::get_config(&config_path).await {
+                Ok(config) => {
+                    let blocklist = Blocklist::new(&config.display);
+                    tracing::info!(
+                        "Reloaded display blocklist: {} duties, {} categories",
+                        blocklist.duties.len(),
+                        blocklist.categories.len(),
+                    );
+                    *state.blocklist.write().unwrap() = blocklist;
+                    state.listings_cache.invalidate();
+                    state.bump_listings_revision();
+                }
+                Err(e) => tracing::warn!("could not reload config from {}: {:#}", config_path.display(), e),
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+pub fn spawn_reload_task(_state: Arc<State>, _config_path: PathBuf) {}
+
+/// 이전 데이터베이스 문서를 새 데이터베이스로 옮기는 태스크 (`mongo.migrate_from`이 있을 때만)
+pub fn spawn_migration_task(state: Arc<State>) {
+    let Some(legacy) = state.legacy_database() else {
+        return;
+    };
+
+    tokio::task::spawn(async move {
+        loop {
+            match crate::infra::migration::copy_all(&legacy, &state.database(), &state.config.mongo, &state.migration).await {
+                Ok(()) => {
+                    tracing::info!(
+                        "Migration from database `{}` complete; remove `mongo.migrate_from` from the config and restart",
+                        legacy.name(),
+                    );
+                    return;
+                }
+                Err(e) => {
+                    tracing::error!("error migrating legacy database: {:#?}", e);
+                    tokio::time::sleep(MIGRATION_RETRY).await;
+                }
+            }
+        }
+    });
+}
+
+/// 복사 실패 후 재시도까지 대기 시간
+const MIGRATION_RETRY: Duration = Duration::from_secs(60);
+
+/// 중복 플레이어 병합을 백그라운드에서 시작 (이미 실행 중이면 `false`)
+pub fn start_player_compaction(state: Arc<State>, dry_run: bool, trigger: &'static str) -> bool {
+    if !state.player_compaction.begin() {
+        return false;
+    }
+
+    tokio::task::spawn(async move {
+        let report = compact_players(state.players_collection(), state.parse_collection(), dry_run, trigger).await;
+        state.player_compaction.finish(report);
+    });
+    true
+}
+
+/// 매일 `player_compaction.hour` (UTC)에 중복 플레이어를 병합하는 태스크 (`player_compaction.scheduled`일 때만)
+pub fn spawn_player_compaction_task(state: Arc<State>) {
+    let config = state.config.player_compaction.clone();
+    if !config.scheduled {
+        return;
+    }
+
+    tokio::task::spawn(async move {
+        loop {
+            let now = chrono::Utc::now();
+            let today = now.date_naive().and_hms_opt(config.hour.min(23), 0, 0).unwrap().and_utc();
+            let next_run = if today > now { today } else { today + chrono::TimeDelta::try_days(1).unwrap() };
+            let wait = (next_run - now).to_std().unwrap_or(Duration::from_secs(60));
+            tokio::time::sleep(wait).await;
+
+            if !start_player_compaction(Arc::clone(&state), config.dry_run, "schedule") {
+                tracing::info!("player compaction already running, skipping scheduled run");
+            }
+        }
+    });
+}
+
+/// 매일 전날(과 복사본이 남아 있는 이전 날짜) 데이터셋을 생성하는 태스크 (이미 있는 날짜는 건너뜀)
+///
+/// 내보낼 모집글은 `STAGING_INTERVAL`마다 `listings_export`에 복사해 둡니다.
+pub fn spawn_export_task(state: Arc<State>) {
+    let Some(config) = state.config.export.clone() else {
+        tracing::info!("Dataset export not configured, skipping export task.");
+        return;
+    };
+
+    let staging_state = Arc::clone(&state);
+    tokio::task::spawn(async move {
+        // 처음에는 남아 있는 모집글 전부, 이후에는 지난 복사 시작 시각부터
+        let mut since = chrono::Utc::now() - chrono::TimeDelta::try_days(2).unwrap();
+        loop {
+            let started_at = chrono::Utc::now();
+            match crate::mongo::stage_listings_for_export(staging_state.collection().primary(), since).await {
+                Ok(()) => since = started_at - chrono::TimeDelta::try_minutes(1).unwrap(),
+                Err(e) => tracing::warn!("could not stage listings for export: {:#?}", e),
+            }
+            tokio::time::sleep(crate::export::STAGING_INTERVAL).await;
+        }
+    });
+
+    tokio::task::spawn(async move {
+        loop {
+            let now = chrono::Utc::now();
+            let yesterday = now.date_naive() - chrono::TimeDelta::try_days(1).unwrap();
+            crate::export::export_pending_days(&state, &config, yesterday).await;
 
-            tokio::time::sleep(Duration::from_secs(60 * 60 * 12)).await;
+            // 다음 날 00:10 (UTC)까지 대기
+            let next_run = (now.date_naive() + chrono::TimeDelta::try_days(1).unwrap())
+                .and_hms_opt(0, 10, 0)
+                .unwrap()
+                .and_utc();
+            let wait = (next_run - chrono::Utc::now()).to_std().unwrap_or(Duration::from_secs(60));
+            tokio::time::sleep(wait).await;
         }
     });
 }
@@ -41,10 +382,19 @@ pub fn spawn_fflogs_task(state: Arc<State>) {
         tokio::task::spawn(async move {
             tracing::info!("Starting FFLogs background service...");
             loop {
-               if let Err(e) = fetch_parses_task(&parse_state).await {
-                   tracing::error!("Error in FFLogs background task: {:?}", e);
+               if !parse_state.maintenance.should_skip(BackgroundTask::FFLogs) {
+                   parse_state.fflogs_cycles.start(chrono::Utc::now());
+                   let result = fetch_parses_task(&parse_state).await;
+                   parse_state.fflogs_cycles.finish(result.is_ok(), chrono::Utc::now());
+                   if let Err(e) = result {
+                       tracing::error!("Error in FFLogs background task: {:?}", e);
+                   }
+               }
+               // 우선 재조회 요청이 들어오면 대기 없이 바로 다음 주기 실행
+               tokio::select! {
+                   _ = tokio::time::sleep(Duration::from_secs(60)) => {}
+                   _ = parse_state.parse_refetch.notified() => {}
                }
-               tokio::time::sleep(Duration::from_secs(60)).await;
             }
         });
     } else {
@@ -56,26 +406,24 @@ pub fn spawn_fflogs_task(state: Arc<State>) {
 /// 
 /// 1시간 이내 활성 파티의 멤버만 대상으로 파싱을 수집합니다.
 /// Zone 단위로 조회하여 모든 encounter 데이터를 한 번에 저장합니다.
-/// 배치 크기: 20명, Rate Limit: 1초/배치
+/// 배치 크기: 20명, Rate Limit: 1초/배치 (API 포인트를 80% 넘게 쓰면 5초/배치)
+/// API 포인트가 거의 소진되면 남은 배치는 다음 주기로 미룹니다.
 async fn fetch_parses_task(state: &State) -> Result<()> {
     let client = state.fflogs_client.as_ref().unwrap();
+
+    // 관리자가 무효화 후 재조회를 요청한 Zone / 플레이어 (캐시와 무관하게 먼저 조회)
+    let priority = state.parse_refetch.drain();
     
     // 1. 현재 활성 파티 목록 가져오기 (1시간 이내)
-    let listings = get_current_listings(state.collection()).await?;
+    let listings = state.current_listings().await?;
     
     // 2. 고난이도 파티만 필터링하고, Zone별로 플레이어 그룹화
     // Key: zone_id, Value: (difficulty_id, Vec<(content_id, name, server, region)>)
     let mut zone_players: HashMap<u32, (Option<u32>, Vec<(u64, String, String, &'static str)>)> = HashMap::new();
     
-    for container in &listings {
-        let duty_id = container.listing.duty as u16;
-        
-        // High-end + FFLogs 매핑 확인
-        if !container.listing.high_end() {
-            continue;
-        }
-        
-        let fflogs_info = match crate::fflogs::mapping::get_fflogs_encounter(duty_id) {
+    for container in listings.iter() {
+        // High-end + FFLogs 매핑 확인 (목록 / API 표시와 같은 기준)
+        let fflogs_info = match container.listing.fflogs_encounter() {
             Some(info) => info,
             None => continue,
         };
@@ -87,7 +435,7 @@ async fn fetch_parses_task(state: &State) -> Result<()> {
             .filter(|&id| id != 0)
             .collect();
         
-        let players = get_players_by_content_ids(state.players_collection(), &member_ids).await?;
+        let players = state.players_by_content_ids(&member_ids).await?;
         
         let entry = zone_players.entry(fflogs_info.zone_id)
             .or_insert_with(|| (fflogs_info.difficulty_id, Vec::new()));
@@ -110,33 +458,60 @@ async fn fetch_parses_task(state: &State) -> Result<()> {
     
     let mut fetch_count = 0;
     let mut skip_count = 0;
+    let mut backoff_skip_count = 0;
     let mut saved_count = 0;
+    let mut response_count = 0;
+    let mut unrecognized_count = 0;
+    let mut miss_count = 0;
+    let mut batch_errors = 0;
     let batch_size = 20;
+    let empty_backoff = state.config.fflogs.as_ref().map(|c| c.empty_backoff.clone()).unwrap_or_default();
     
-    // Zone별로 처리
-    for (zone_id, (difficulty_id, players)) in &zone_players {
+    // Zone별로 처리 (우선 재조회 Zone 먼저)
+    let priority_zones: HashSet<u32> = priority.keys().copied().collect();
+    'zones: for zone_id in &crate::fflogs::refetch::zone_order(zone_players.keys().copied(), &priority_zones) {
+        let (difficulty_id, players) = &zone_players[zone_id];
+        let forced = priority.get(zone_id);
         let zone_name = crate::fflogs::mapping::FFLOGS_ZONES
             .get(zone_id)
             .map(|z| z.name)
             .unwrap_or("Unknown Zone");
         
-        // 배치로 Zone 캐시 일괄 조회 (N+1 쿼리 방지)
+        // 배치로 Parse 문서 일괄 조회 (N+1 쿼리 방지)
         let content_ids: Vec<u64> = players.iter().map(|p| p.0).collect();
-        let cached_zones = crate::mongo::get_zone_caches(
-            state.parse_collection(),
-            &content_ids,
-            *zone_id
-        ).await.unwrap_or_default();
+        let mut parse_docs = state
+            .parse_collection()
+            .read_by_ids(&content_ids, |collection, ids| async move {
+                crate::mongo::get_parse_docs(collection, &ids).await
+            })
+            .await
+            .unwrap_or_default();
+        let zone_key = zone_id.to_string();
+        let now = chrono::Utc::now();
+        let max_age = state.parse_cache_expiry.for_zone(*zone_id);
         
-        // 캐시 확인 후 필터링: 해당 Zone의 캐시가 만료되지 않았는지 확인
+        // 캐시 확인 후 필터링: 해당 Zone의 캐시가 만료되지 않았는지, 빈 결과 백오프 중인지 확인
         let mut players_to_fetch: Vec<&(u64, String, String, &'static str)> = Vec::new();
         
         for player in players {
-            match cached_zones.get(&player.0) {
-                Some(cache) if !crate::mongo::is_zone_cache_expired(cache) => {
+            if forced.is_some_and(|ids| ids.contains(&player.0)) {
+                players_to_fetch.push(player);
+                continue;
+            }
+
+            let doc = parse_docs.get(&player.0);
+            match doc.and_then(|doc| doc.zones.get(&zone_key)) {
+                Some(cache) if !crate::mongo::is_zone_cache_expired(cache, max_age, now) => {
                     // 캐시가 유효함
                     skip_count += 1;
                 }
+                _ if doc
+                    .and_then(|doc| doc.fetch.as_ref())
+                    .is_some_and(|fetch| fetch.in_empty_backoff(*zone_id, &empty_backoff, now)) =>
+                {
+                    // 랭킹이 없는 플레이어는 정책에 따라 드물게 재조회
+                    backoff_skip_count += 1;
+                }
                 _ => {
                     // 캐시 없거나 만료됨
                     players_to_fetch.push(player);
@@ -150,9 +525,7 @@ async fn fetch_parses_task(state: &State) -> Result<()> {
         
         tracing::info!("[FFLogs] {} - {} players to fetch", zone_name, players_to_fetch.len());
         
-        let partition = crate::fflogs::mapping::FFLOGS_ZONES
-            .get(zone_id)
-            .map(|z| z.partition);
+        let partition = state.zone_partitions.get(*zone_id);
         
         // 배치 단위로 처리
         for chunk in players_to_fetch.chunks(batch_size) {
@@ -160,11 +533,22 @@ async fn fetch_parses_task(state: &State) -> Result<()> {
                 .map(|p| (p.1.clone(), p.2.clone(), p.3))
                 .collect();
             
-            // Rate Limit: 배치당 1초 대기
-            tokio::time::sleep(Duration::from_secs(1)).await;
+            // API 포인트가 거의 남지 않았으면 초기화될 때까지 조회하지 않음
+            if client.points().should_pause(chrono::Utc::now()) {
+                let usage = client.points().snapshot();
+                tracing::warn!(
+                    "[FFLogs] API points nearly exhausted ({:.0} left, resets in {}s); pausing this cycle",
+                    usage.map_or(0.0, |usage| usage.remaining(chrono::Utc::now())),
+                    usage.map_or(0, |usage| usage.reset_in_secs)
+                );
+                break 'zones;
+            }
+
+            // Rate Limit: 배치 간격 (포인트 사용량이 많으면 늘어남)
+            tokio::time::sleep(client.points().batch_delay(chrono::Utc::now())).await;
             
             // Zone 내 모든 encounter를 조회
-            let results = client.get_batch_zone_all_parses(
+            let results = client.get_batch_zone_rankings(
                 batch,
                 *zone_id,
                 *difficulty_id,
@@ -177,18 +561,47 @@ async fn fetch_parses_task(state: &State) -> Result<()> {
                 Ok(batch_results) => {
                     for (idx, encounters) in &batch_results {
                         let player = chunk[*idx];
+                        response_count += 1;
+
+                        let encounters = match encounters {
+                            Ok(encounters) => encounters,
+                            // 형태를 알 수 없는 응답은 "기록 없음"으로 저장하지 않음 (다음 주기에 다시 조회)
+                            Err(crate::fflogs::RankingsUnavailable::Unrecognized(_)) => {
+                                unrecognized_count += 1;
+                                continue;
+                            }
+                            // 없거나 비공개인 캐릭터는 Zone 캐시 없이 조회 기록만 남김 (`missing_hours` 뒤에 재조회)
+                            Err(crate::fflogs::RankingsUnavailable::Profile(miss)) => {
+                                let fetch = parse_docs
+                                    .entry(player.0)
+                                    .or_insert_with(|| crate::mongo::ParseCacheDoc {
+                                        content_id: player.0 as i64,
+                                        zones: HashMap::new(),
+                                        fetch: None,
+                                    })
+                                    .fetch
+                                    .get_or_insert_with(Default::default);
+                                fetch.record_miss(*miss, chrono::Utc::now());
+                                if let Err(e) = state
+                                    .parse_collection()
+                                    .write(|collection| crate::mongo::set_fetch_accounting(collection, player.0, fetch))
+                                    .await {
+                                    tracing::warn!("[FFLogs] Failed to record fetch for {}: {:?}", player.0, e);
+                                }
+                                miss_count += 1;
+                                continue;
+                            }
+                        };
                         
                         // ZoneCache 생성
-                        let mut encounter_map = HashMap::new();
-                        for (enc_id, percentile) in encounters {
-                            encounter_map.insert(
-                                enc_id.to_string(),
-                                crate::mongo::EncounterParse {
-                                    percentile: *percentile,
-                                    job_id: 0,
-                                }
-                            );
-                        }
+                        let encounter_map: HashMap<String, crate::mongo::EncounterParse> = encounters
+                            .iter()
+                            .map(|(enc_id, parse)| (enc_id.to_string(), parse.clone()))
+                            .collect();
+                        let percentiles: Vec<(u32, f32)> = encounters
+                            .iter()
+                            .map(|(enc_id, parse)| (*enc_id, parse.percentile))
+                            .collect();
                         
                         let zone_cache = crate::mongo::ZoneCache {
                             fetched_at: chrono::Utc::now(),
@@ -196,24 +609,62 @@ async fn fetch_parses_task(state: &State) -> Result<()> {
                         };
                         
                         // Zone 전체 upsert
-                        let _ = crate::mongo::upsert_zone_cache(
-                            state.parse_collection(),
-                            player.0,
-                            *zone_id,
-                            &zone_cache
-                        ).await;
+                        let _ = state
+                            .parse_collection()
+                            .write(|collection| crate::mongo::upsert_zone_cache(collection, player.0, *zone_id, &zone_cache))
+                            .await;
+
+                        // 조회 기록 갱신 (캐시 저장 실패와 무관하게 기록)
+                        let fetch = parse_docs
+                            .entry(player.0)
+                            .or_insert_with(|| crate::mongo::ParseCacheDoc {
+                                content_id: player.0 as i64,
+                                zones: HashMap::new(),
+                                fetch: None,
+                            })
+                            .fetch
+                            .get_or_insert_with(Default::default);
+                        fetch.record(*zone_id, crate::fflogs::is_empty_result(&percentiles), chrono::Utc::now());
+                        if let Err(e) = state
+                            .parse_collection()
+                            .write(|collection| crate::mongo::set_fetch_accounting(collection, player.0, fetch))
+                            .await {
+                            tracing::warn!("[FFLogs] Failed to record fetch for {}: {:?}", player.0, e);
+                        }
                         
                         saved_count += encounters.len();
                     }
+                    // 저장한 Parse가 API 응답에 보이도록 ETag 갱신
+                    if !batch_results.is_empty() {
+                        state.bump_listings_revision();
+                    }
                 },
                 Err(e) => {
+                    batch_errors += 1;
                     tracing::warn!("[FFLogs] Batch error for {}: {:?}", zone_name, e);
                 }
             }
         }
     }
     
-    tracing::info!("[FFLogs] Cycle complete: {} batches, {} parses saved, {} skipped (cached)", 
-        fetch_count, saved_count, skip_count);
+    if state.fflogs_schema.record_cycle(response_count, unrecognized_count, chrono::Utc::now()) {
+        tracing::error!(
+            "[FFLogs] {} of {} zoneRankings responses had an unrecognized shape; FFLogs may have changed its response format",
+            unrecognized_count, response_count
+        );
+    } else if unrecognized_count > 0 {
+        tracing::warn!("[FFLogs] {} of {} zoneRankings responses had an unrecognized shape", unrecognized_count, response_count);
+    }
+
+    state.metrics.record_fflogs_cycle(crate::web::metrics::FFLogsCycle {
+        batches: fetch_count,
+        batch_errors,
+        parses_saved: saved_count as u64,
+        cache_hits: skip_count,
+    });
+
+    let (memo_hits, coalesced_waits) = client.coalescing_stats();
+    tracing::info!("[FFLogs] Cycle complete: {} batches, {} parses saved, {} missing/hidden, {} skipped (cached), {} skipped (empty-backoff), {} memo hits, {} coalesced",
+        fetch_count, saved_count, miss_count, skip_count, backoff_skip_count, memo_hits, coalesced_waits);
     Ok(())
 }
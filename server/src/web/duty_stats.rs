@@ -0,0 +1,142 @@
+//! 듀티별 통계 짧은 캐시 (`/stats/duty/{duty_id}`, `/api/stats/duty/{duty_id}`)
+//!
+//! 듀티별 통계는 요청할 때 집계하므로 (듀티, 기간)마다 결과를 잠시 재사용합니다.
+//! 없는 듀티 ID는 집계 전에 거르므로 키는 게임 데이터의 듀티 수로 제한되고,
+//! 그래도 `capacity`를 넘으면 만료된 항목, 가장 오래된 항목 순으로 지웁니다.
+//! 같은 키를 집계하는 중에 들어온 요청은 새로 집계하지 않고 그 결과를 기다립니다 (`listings_cache`와 같은 방식).
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+
+use crate::stats::Statistics;
+
+/// 듀티별 통계를 다시 집계하지 않고 쓰는 시간
+pub const DUTY_STATS_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// 캐시에 두는 최대 (듀티, 기간) 수
+pub const DUTY_STATS_CAPACITY: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DutyStatsKey {
+    pub duty: u16,
+    /// 최근 7일 (아니면 전체 기간)
+    pub seven_days: bool,
+}
+
+/// 진행 중인 집계 결과 (에러는 공유를 위해 문자열로 전달)
+type Load = Option<Result<Arc<Statistics>, String>>;
+
+pub struct DutyStatsCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<DutyStatsKey, (Instant, Arc<Statistics>)>>,
+    /// 키별로 진행 중인 집계
+    loading: Mutex<HashMap<DutyStatsKey, watch::Receiver<Load>>>,
+}
+
+enum Role {
+    Fresh(Arc<Statistics>),
+    Wait(watch::Receiver<Load>),
+    Lead(watch::Sender<Load>),
+}
+
+/// 집계하던 요청이 취소돼도 다음 요청이 다시 집계할 수 있게 진행 표시를 지움
+struct LoadGuard<'a> {
+    loading: &'a Mutex<HashMap<DutyStatsKey, watch::Receiver<Load>>>,
+    key: DutyStatsKey,
+}
+
+impl Drop for LoadGuard<'_> {
+    fn drop(&mut self) {
+        self.loading.lock().unwrap().remove(&self.key);
+    }
+}
+
+impl DutyStatsCache {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Default::default(),
+            loading: Default::default(),
+        }
+    }
+
+    /// 만료되지 않은 통계
+    pub fn get(&self, key: DutyStatsKey, now: Instant) -> Option<Arc<Statistics>> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(&key)
+            .filter(|(stored_at, _)| now.saturating_duration_since(*stored_at) < self.ttl)
+            .map(|(_, stats)| Arc::clone(stats))
+    }
+
+    pub fn insert(&self, key: DutyStatsKey, stats: Statistics, now: Instant) -> Arc<Statistics> {
+        let stats = Arc::new(stats);
+        let mut entries = self.entries.lock().unwrap();
+        if !entries.contains_key(&key) && entries.len() >= self.capacity {
+            entries.retain(|_, (stored_at, _)| now.saturating_duration_since(*stored_at) < self.ttl);
+            if entries.len() >= self.capacity {
+                let oldest = entries.iter().min_by_key(|(_, (stored_at, _))| *stored_at).map(|(key, _)| *key);
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(key, (now, Arc::clone(&stats)));
+        stats
+    }
+
+    /// 캐시된 통계를 반환하고, 없으면 `load`로 집계해 저장 (키마다 동시에 한 번만, 실패는 저장하지 않음)
+    pub async fn get_or_load<F, Fut>(&self, key: DutyStatsKey, load: F) -> anyhow::Result<Arc<Statistics>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<Statistics>>,
+    {
+        let role = {
+            let mut loading = self.loading.lock().unwrap();
+            match (self.get(key, Instant::now()), loading.get(&key)) {
+                (Some(stats), _) => Role::Fresh(stats),
+                (None, Some(rx)) => Role::Wait(rx.clone()),
+                (None, None) => {
+                    let (tx, rx) = watch::channel(None);
+                    loading.insert(key, rx);
+                    Role::Lead(tx)
+                }
+            }
+        };
+
+        match role {
+            Role::Fresh(stats) => Ok(stats),
+            Role::Wait(mut rx) => {
+                let shared = rx.wait_for(Option::is_some).await.ok().and_then(|result| result.clone());
+                match shared {
+                    Some(result) => result.map_err(|e| anyhow::anyhow!(e)),
+                    // 집계하던 요청이 취소됨
+                    None => {
+                        let stats = load().await?;
+                        Ok(self.insert(key, stats, Instant::now()))
+                    }
+                }
+            }
+            Role::Lead(tx) => {
+                let guard = LoadGuard { loading: &self.loading, key };
+                let result = load().await.map(|stats| self.insert(key, stats, Instant::now()));
+                drop(guard);
+                tx.send_replace(Some(result.as_ref().map(Arc::clone).map_err(|e| format!("{:#}", e))));
+                result
+            }
+        }
+    }
+}
+
+impl Default for DutyStatsCache {
+    fn default() -> Self {
+        Self::new(DUTY_STATS_TTL, DUTY_STATS_CAPACITY)
+    }
+}
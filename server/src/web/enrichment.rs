@@ -0,0 +1,140 @@
+//! 모집글 멤버 / 파티장 Parse 구성 (목록 페이지, `/api/listings` 공통)
+//!
+//! 미리 조회한 플레이어 / Parse 정보로 표시용 모집글을 만드는 곳은 여기 한 곳입니다.
+//! 목록 페이지는 결과를 그대로 렌더링하고 API는 JSON 형태로 옮기므로,
+//! 정렬, 멤버로 표시하는 자리, percentile 반올림, 중복 잡 정리가 두 경로에서 같습니다.
+
+use std::collections::HashMap;
+
+use crate::fflogs::{EncounterParse, FFLogsEncounter, ParseCacheDoc, ProfileMiss, ZoneCache};
+use crate::fflogs::mapping::percentile_display;
+use crate::listing_container::{sort_for_display, QueriedListing};
+use crate::player::Player;
+use crate::template::listings::{ParseDisplay, RenderableListing, RenderableMember};
+
+/// 미리 조회한 Parse 캐시
+///
+/// 목록 페이지와 API 모두 플레이어별 전체 문서(`parse_content_ids`)를 미리 조회합니다.
+pub trait ParseSource {
+    fn zone_cache(&self, content_id: u64, zone_id: u32) -> Option<&ZoneCache>;
+
+    /// 마지막 조회에서 FFLogs 캐릭터를 볼 수 없었던 이유
+    fn profile_miss(&self, content_id: u64) -> Option<ProfileMiss>;
+}
+
+impl ParseSource for HashMap<u64, ParseCacheDoc> {
+    fn zone_cache(&self, content_id: u64, zone_id: u32) -> Option<&ZoneCache> {
+        self.get(&content_id)?.zones.get(&zone_id.to_string())
+    }
+
+    fn profile_miss(&self, content_id: u64) -> Option<ProfileMiss> {
+        self.get(&content_id)?.fetch.as_ref()?.profile
+    }
+}
+
+/// Parse 문서를 미리 조회할 Content ID (FFLogs 조회 대상 모집글의 멤버 / 파티장, 정렬, 중복 제거)
+///
+/// 목록 페이지와 API가 같은 문서를 조회하도록 두 경로 모두 이 목록으로 `get_parse_docs`를 호출합니다.
+pub fn parse_content_ids(listings: &[QueriedListing]) -> Vec<u64> {
+    let mut ids: Vec<u64> = crate::api::zone_requests(listings).into_values().flatten().collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+/// 플레이어 정보가 없는 멤버 (관측 기록 없음)
+pub fn unknown_player(content_id: u64) -> Player {
+    Player {
+        content_id,
+        name: "Unknown Member".to_string(),
+        home_world: 0,
+        last_seen: chrono::Utc::now(),
+        seen_count: 0,
+    }
+}
+
+fn encounter_parse(
+    parses: &impl ParseSource,
+    content_id: u64,
+    zone_id: u32,
+    encounter_id: u32,
+) -> Option<&EncounterParse> {
+    parses.zone_cache(content_id, zone_id)?.encounters.get(&encounter_id.to_string())
+}
+
+/// 멤버 / 파티장 한 명의 Parse 표시 (`job_id`가 있으면 역할 불일치도 확인)
+///
+/// 기록이 없고 FFLogs에서 기록을 비공개로 한 플레이어는 "hidden"으로 표시합니다.
+pub fn parse_display(
+    parses: &impl ParseSource,
+    content_id: u64,
+    encounter: Option<&FFLogsEncounter>,
+    job_id: Option<u8>,
+) -> ParseDisplay {
+    let Some(encounter) = encounter else {
+        return ParseDisplay::none();
+    };
+
+    let primary = encounter_parse(parses, content_id, encounter.zone_id, encounter.encounter_id);
+    let secondary = encounter
+        .secondary_encounter_id
+        .and_then(|secondary| encounter_parse(parses, content_id, encounter.zone_id, secondary));
+    // 멤버는 현재 잡에 맞는 기록, 파티장은 최고 기록
+    let display = |parse: Option<&EncounterParse>| {
+        let (percentile, class) = parse.map_or((None, "parse-none"), |parse| {
+            percentile_display(job_id.map_or(parse.percentile, |job_id| parse.for_job(job_id).percentile))
+        });
+        (percentile, class.to_string())
+    };
+    let ((p1, p1_class), (p2, p2_class)) = (display(primary), display(secondary));
+
+    ParseDisplay::new(p1, p1_class, p2, p2_class, encounter.secondary_encounter_id.is_some())
+        .with_all_stars(primary.and_then(|parse| parse.all_stars))
+        .with_role_mismatch(job_id.is_some_and(|job_id| {
+            primary.is_some_and(|parse| parse.for_job(job_id).percentile >= 0.0 && parse.role_mismatch(job_id))
+        }))
+        .with_hidden(
+            primary.is_none() && secondary.is_none() && parses.profile_miss(content_id) == Some(ProfileMiss::Hidden),
+        )
+}
+
+/// 표시 순서로 정렬한 모집글에 멤버 / 파티장 Parse를 붙임 (DB 조회 없음)
+///
+/// 한 잡당 한 명인 모집글의 중복 잡은 비우고(`data_inconsistent`), 멤버는
+/// `PartyFinderListing::member_slots`의 자리만 표시합니다. 플레이어 정보가 없는 멤버는
+/// `unknown_player`로 표시합니다.
+pub fn enrich_listings(
+    mut containers: Vec<QueriedListing>,
+    players: &HashMap<u64, Player>,
+    parses: &impl ParseSource,
+) -> Vec<RenderableListing> {
+    sort_for_display(&mut containers);
+
+    containers
+        .into_iter()
+        .map(|mut container| {
+            let data_inconsistent = container.listing.clear_duplicate_jobs();
+            // 백그라운드 조회와 같은 기준
+            let encounter = container.listing.fflogs_encounter();
+
+            let members = container
+                .listing
+                .member_slots()
+                .map(|(content_id, job_id)| RenderableMember {
+                    job_id,
+                    player: players.get(&content_id).cloned().unwrap_or_else(|| unknown_player(content_id)),
+                    parse: parse_display(parses, content_id, encounter, Some(job_id)),
+                })
+                .collect();
+            let leader_parse = parse_display(parses, container.listing.leader_content_id, encounter, None);
+
+            RenderableListing {
+                container,
+                members,
+                leader_parse,
+                watched: false,
+                data_inconsistent,
+            }
+        })
+        .collect()
+}
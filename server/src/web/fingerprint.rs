@@ -0,0 +1,160 @@
+//! 업로더 지문
+//!
+//! 같은 모집글을 여러 클라이언트가 올렸는지 구분하기 위한 값입니다.
+//! 업로드 토큰으로 인증한 요청은 토큰 이름을, 아니면 IP 주소를 솔트와 함께 해시합니다.
+//! 솔트는 `ratelimit.uploader_secret`에서 만들고, 설정이 없으면 Mongo에 저장한 비밀값을 사용하므로
+//! 재시작하거나 인스턴스가 여러 개여도 같은 업로더의 지문은 같습니다.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, Result};
+use mongodb::bson::{doc, Document};
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::Database;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use warp::http::StatusCode;
+use warp::{Filter, Rejection, Reply};
+
+use crate::config::RateLimit;
+use super::{upload_auth, State};
+
+/// 저장한 비밀값 컬렉션
+pub const SECRETS_COLLECTION: &str = "secrets";
+/// 업로더 지문 비밀값 문서의 `_id`
+const UPLOADER_SECRET_ID: &str = "uploader_fingerprint";
+
+/// 업로더 지문 솔트 (설정에 없으면 시작할 때 Mongo에서 읽어 옴)
+#[derive(Default)]
+pub struct UploaderSalt(OnceLock<u64>);
+
+impl UploaderSalt {
+    /// `ratelimit.uploader_secret`이 있으면 바로 정해진 솔트
+    pub fn from_config(ratelimit: &RateLimit) -> Self {
+        let salt = Self::default();
+        if let Some(secret) = &ratelimit.uploader_secret {
+            salt.set(secret);
+        }
+        salt
+    }
+
+    pub fn get(&self) -> Option<u64> {
+        self.0.get().copied()
+    }
+
+    /// 비밀값으로 솔트 설정 (이미 정해져 있으면 그대로 둠)
+    pub fn set(&self, secret: &str) {
+        let _ = self.0.set(salt_from_secret(secret));
+    }
+}
+
+/// 비밀값에서 만든 솔트
+pub fn salt_from_secret(secret: &str) -> u64 {
+    let digest = Sha256::digest(secret.as_bytes());
+    u64::from_le_bytes(digest[..8].try_into().unwrap())
+}
+
+/// 저장한 업로더 지문 비밀값 (없으면 새로 만들어 저장, 여러 인스턴스가 동시에 시작해도 같은 값을 읽음)
+pub async fn load_or_create_secret(database: &Database) -> Result<String> {
+    let candidate: String = rand::thread_rng()
+        .gen::<[u8; 32]>()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    let options = FindOneAndUpdateOptions::builder()
+        .upsert(true)
+        .return_document(ReturnDocument::After)
+        .build();
+    let saved = database
+        .collection::<Document>(SECRETS_COLLECTION)
+        .find_one_and_update(
+            doc! { "_id": UPLOADER_SECRET_ID },
+            doc! { "$setOnInsert": { "secret": candidate } },
+            options,
+        )
+        .await
+        .context("could not load the uploader fingerprint secret")?
+        .context("uploader fingerprint secret was not saved")?;
+
+    Ok(saved.get_str("secret").context("uploader fingerprint secret is not a string")?.to_string())
+}
+
+/// 솔트를 아직 읽지 못함 (Mongo 연결 전)
+#[derive(Debug)]
+pub struct SaltNotReady;
+
+impl warp::reject::Reject for SaltNotReady {}
+
+/// 업로드 토큰 확인 후 요청의 업로더 지문 추출 (토큰 이름, 지문)
+///
+/// 주소는 `client_address`와 같은 기준입니다.
+pub fn authenticated_uploader(
+    state: Arc<State>,
+) -> impl Filter<Extract = (Option<String>, String), Error = Rejection> + Clone {
+    upload_auth::token(Arc::clone(&state))
+        .and(client_address(&state.config.ratelimit))
+        .and_then(move |token: Option<String>, ip: Option<IpAddr>| {
+            let result = match state.uploader_salt.get() {
+                Some(salt) => {
+                    let uploader = fingerprint(token.as_deref(), ip, salt);
+                    Ok((token, uploader))
+                }
+                None => Err(warp::reject::custom(SaltNotReady)),
+            };
+            async move { result }
+        })
+        .untuple_one()
+}
+
+/// 업로드 토큰 확인 후 요청의 업로더 지문 추출
+pub fn uploader(state: Arc<State>) -> impl Filter<Extract = (String,), Error = Rejection> + Clone {
+    authenticated_uploader(state).map(|_: Option<String>, uploader: String| uploader)
+}
+
+/// `SaltNotReady`를 `503` 응답으로 변환
+pub async fn not_ready(rejection: Rejection) -> Result<warp::reply::Response, Rejection> {
+    if rejection.find::<SaltNotReady>().is_none() {
+        return Err(rejection);
+    }
+
+    let reply = warp::reply::json(&serde_json::json!({ "error": "starting up" }));
+    let reply = warp::reply::with_status(reply, StatusCode::SERVICE_UNAVAILABLE);
+    Ok(warp::reply::with_header(reply, "retry-after", "5").into_response())
+}
+
+/// 요청한 클라이언트 주소 (`trust_forwarded_for`이면 `X-Forwarded-For`에서 프록시가 붙인 주소 우선)
+pub fn client_address(ratelimit: &RateLimit) -> impl Filter<Extract = (Option<IpAddr>,), Error = Rejection> + Clone {
+    let trust_forwarded_for = ratelimit.trust_forwarded_for;
+    let trusted_proxies = ratelimit.trusted_proxies;
+    warp::header::optional::<String>("x-forwarded-for")
+        .and(warp::addr::remote())
+        .map(move |forwarded: Option<String>, remote: Option<SocketAddr>| {
+            forwarded
+                .as_deref()
+                .filter(|_| trust_forwarded_for)
+                .and_then(|forwarded| client_ip(forwarded, trusted_proxies))
+                .or(remote.map(|addr| addr.ip()))
+        })
+}
+
+/// `X-Forwarded-For` 헤더에서 오른쪽부터 `trusted_proxies`번째 주소
+///
+/// 프록시는 받은 연결의 주소를 오른쪽에 붙이므로 그보다 왼쪽은 클라이언트가 마음대로 넣을 수 있습니다.
+/// 주소가 모자라면 `None` (연결 주소를 사용)
+pub fn client_ip(forwarded: &str, trusted_proxies: usize) -> Option<IpAddr> {
+    forwarded.rsplit(',').nth(trusted_proxies.checked_sub(1)?)?.trim().parse().ok()
+}
+
+/// 업로드 토큰 이름(없으면 주소)을 솔트와 함께 해시한 16자리 16진수 지문
+pub fn fingerprint(token: Option<&str>, ip: Option<IpAddr>, salt: u64) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.to_le_bytes());
+    match (token, ip) {
+        (Some(token), _) => hasher.update(format!("token:{}", token)),
+        (None, Some(ip)) => hasher.update(ip.to_string()),
+        (None, None) => hasher.update("unknown"),
+    }
+
+    hasher.finalize()[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -1,195 +1,188 @@
-use std::{cmp::Ordering, collections::HashMap, convert::Infallible, sync::Arc};
+use std::{collections::{HashMap, HashSet}, convert::Infallible, sync::Arc, time::Instant};
 use warp::Reply;
 use mongodb::bson::doc;
 
-use crate::listing::PartyFinderListing;
+use chrono::Utc;
+use futures_util::{Stream, StreamExt};
+use tokio::sync::broadcast::Sender;
 
-use crate::mongo::{get_current_listings, insert_listing, upsert_players, get_players_by_content_ids, get_parse_docs, ParseCacheDoc};
-use crate::player::UploadablePlayer;
+use crate::listing::snapshot::SnapshotScope;
+use crate::listing::{Blocklist, ListingFilter, PartyFinderListing, PartyMember};
+use crate::listing_container::QueriedListing;
+
+use crate::api::ApiShape;
+use crate::mongo::{insert_listing, mark_unconfirmed, party_detail_update, upsert_players, get_parse_docs, ParseCacheDoc};
+use crate::player::{Player, UploadablePlayer};
+use crate::bookmarks::{pin_watched, watched_keys};
 use crate::{
-    ffxiv::Language,
-    template::listings::ListingsTemplate,
-    template::stats::StatsTemplate,
+    ffxiv::{world_data_centre, Language},
+    template::listings::{ListingsTemplate, RenderableListing},
+    template::stats::{DutyStatsTemplate, StatsTemplate},
+    template::status::StatusTemplate,
+};
+use super::enrichment::{enrich_listings, parse_content_ids};
+use super::listing_events::ListingEvent;
+use super::metrics::{ContributeKind, ContributeOutcome};
+use super::upload_response::{
+    ContributeResponse, DetailUploaded, LeaderUpsert, ListingResult, ListingUploaded, ListingsUploaded, PlayersUploaded, UploadStatus,
 };
 use super::State;
 
-/// Parse percentile 조회 헬퍼 함수
-/// 
-/// Returns: (p1_percentile, p1_color_class, p2_percentile, p2_color_class)
-fn lookup_parse_percentiles(
-    parse_docs: &HashMap<u64, ParseCacheDoc>,
-    content_id: u64,
-    zone_key: &str,
-    encounter_id: u32,
-    secondary_encounter_id: Option<u32>,
-) -> (Option<u8>, String, Option<u8>, String) {
-    let mut p1_percentile = None;
-    let mut p1_class = "parse-none".to_string();
-    let mut p2_percentile = None;
-    let mut p2_class = "parse-none".to_string();
-    
-    if let Some(doc) = parse_docs.get(&content_id) {
-        if let Some(zone_cache) = doc.zones.get(zone_key) {
-            // Primary (P1)
-            if let Some(enc_parse) = zone_cache.encounters.get(&encounter_id.to_string()) {
-                if enc_parse.percentile >= 0.0 {
-                    p1_percentile = Some(enc_parse.percentile as u8);
-                    p1_class = crate::fflogs::mapping::percentile_color_class(enc_parse.percentile).to_string();
-                }
-            }
-            
-            // Secondary (P2)
-            if let Some(sec_id) = secondary_encounter_id {
-                if let Some(enc_parse) = zone_cache.encounters.get(&sec_id.to_string()) {
-                    if enc_parse.percentile >= 0.0 {
-                        p2_percentile = Some(enc_parse.percentile as u8);
-                        p2_class = crate::fflogs::mapping::percentile_color_class(enc_parse.percentile).to_string();
-                    }
-                }
-            }
-        }
+/// 목록 페이지의 서버 / 데이터 센터 조건 (`?world=Tonberry`, `?dc=Elemental`)
+///
+/// 알 수 없는 이름이나 데이터 센터에 없는 서버가 있으면 조건 없이 모든 모집글을 보여주고,
+/// 페이지에 표시할 경고를 함께 반환합니다.
+pub(crate) fn page_filter(world: Option<&str>, dc: Option<&str>) -> (ListingFilter, Option<String>) {
+    fn given(value: Option<&str>) -> Option<&str> {
+        value.map(str::trim).filter(|value| !value.is_empty())
     }
-    
-    (p1_percentile, p1_class, p2_percentile, p2_class)
+    let ignored = |notice: String| (ListingFilter::default(), Some(format!("{}, showing all listings.", notice)));
+
+    let world = match given(world) {
+        Some(name) => match crate::ffxiv::world_id_by_name(name) {
+            Some(id) => Some((name, id)),
+            None => return ignored(format!("Unknown world \"{}\"", name)),
+        },
+        None => None,
+    };
+    let dc = match given(dc) {
+        Some(name) => match crate::ffxiv::data_centre_worlds(name) {
+            Some(worlds) => Some((name, worlds)),
+            None => return ignored(format!("Unknown data centre \"{}\"", name)),
+        },
+        None => None,
+    };
+
+    let worlds = match (world, dc) {
+        (Some((world, id)), Some((dc, worlds))) if !worlds.contains(&id) => {
+            return ignored(format!("{} is not on the {} data centre", world, dc));
+        }
+        (Some((_, id)), _) => vec![id],
+        (None, Some((_, worlds))) => worlds,
+        (None, None) => Vec::new(),
+    };
+    (ListingFilter { worlds, ..Default::default() }, None)
 }
 
 pub async fn listings_handler(
     state: Arc<State>,
-    codes: Option<String>,
+    lang: Language,
+    watch: Option<String>,
+    shape: ApiShape,
+    world: Option<String>,
+    dc: Option<String>,
 ) -> std::result::Result<impl Reply, Infallible> {
-    let lang = Language::from_codes(codes.as_deref());
-
-    let res = get_current_listings(state.collection()).await;
-    Ok(match res {
-        Ok(mut containers) => {
-            // 단일 정렬로 통합: updated_minute DESC → pf_category DESC → time_left ASC
-            containers.sort_by(|a, b| {
-                b.updated_minute.cmp(&a.updated_minute)
-                    .then_with(|| b.listing.pf_category().cmp(&a.listing.pf_category()))
-                    .then_with(|| a.time_left.partial_cmp(&b.time_left).unwrap_or(Ordering::Equal))
-            });
-
-            // Collect all member IDs + leader IDs
-            let mut all_content_ids: Vec<u64> = containers.iter()
-                .flat_map(|l| {
-                    let member_ids = l.listing.member_content_ids.iter().map(|&id| id as u64);
-                    let leader_id = std::iter::once(l.listing.leader_content_id);
-                    member_ids.chain(leader_id)
-                })
-                .filter(|&id| id != 0)
-                .collect();
-            all_content_ids.sort_unstable();
-            all_content_ids.dedup();
-            
-            // Fetch players
-            let players_list = get_players_by_content_ids(state.players_collection(), &all_content_ids).await.unwrap_or_default();
-            let players: HashMap<u64, crate::player::Player> = players_list.into_iter().map(|p| (p.content_id, p)).collect();
-
-            // Optimisation: Pre-fetch all parse docs for all visible players
-            let all_parse_docs = get_parse_docs(state.parse_collection(), &all_content_ids).await.unwrap_or_default();
-
-            // Match players to listings with job info
-            let mut renderable_containers = Vec::new();
-
-            for container in containers {
-                // Determine FFLogs Zone ID/Encounter ID
-                let duty_id = container.listing.duty as u16;
-                let high_end = container.listing.high_end();
-                let fflogs_info = if high_end {
-                    crate::fflogs::mapping::get_fflogs_encounter(duty_id)
-                } else {
-                    None
-                };
-                
-                let (zone_id, encounter_id, secondary_encounter_id) = if let Some(info) = fflogs_info {
-                    (info.zone_id, info.encounter_id, info.secondary_encounter_id)
-                } else {
-                    (0, 0, None)
-                };
-
-                let jobs = &container.listing.jobs_present;
-                let content_ids = &container.listing.member_content_ids;
-                
-                let zone_key = zone_id.to_string();
-
-                let members: Vec<crate::template::listings::RenderableMember> = content_ids.iter()
-                    .enumerate()
-                    .filter(|(_, id)| **id != 0) // 빈 슬롯 제외
-                    .filter_map(|(i, id)| {
-                        let uid = *id as u64;
-                        let job_id = jobs.get(i).copied().unwrap_or(0);
-                        let player = players.get(&uid).cloned().unwrap_or(crate::player::Player {
-                            content_id: uid,
-                            name: "Unknown Member".to_string(),
-                            home_world: 0,
-                            last_seen: chrono::Utc::now(),
-                            seen_count: 0,
-                        });
-                        
-                        // 잡 정보가 없는 멤버는 표시하지 않음 (Ghost Member 방지)
-                        // 리스팅 정보(jobs)와 세부 정보(content_ids) 간의 불일치 시, 리스팅 정보를 신뢰함
-                        if job_id == 0 {
-                            return None;
-                        }
-
-                        // Parse Data (P1 & P2) - 헬퍼 함수 사용
-                        let (p1_percentile, p1_class, p2_percentile, p2_class) = if zone_id > 0 {
-                            lookup_parse_percentiles(&all_parse_docs, uid, &zone_key, encounter_id, secondary_encounter_id)
-                        } else {
-                            (None, "parse-none".to_string(), None, "parse-none".to_string())
-                        };
-
-                        Some(crate::template::listings::RenderableMember { 
-                            job_id, 
-                            player,
-                            parse: crate::template::listings::ParseDisplay::new(
-                                p1_percentile, p1_class,
-                                p2_percentile, p2_class,
-                                secondary_encounter_id.is_some(),
-                            ),
-                        })
-                    })
-                    .collect();
-                
-                // 파티장 로그 계산 (leader_content_id 사용) - 헬퍼 함수 사용
-                let leader_content_id = container.listing.leader_content_id;
-                let (leader_p1_percentile, leader_p1_class, leader_p2_percentile, leader_p2_class) = 
-                    if zone_id > 0 && leader_content_id != 0 {
-                        lookup_parse_percentiles(&all_parse_docs, leader_content_id, &zone_key, encounter_id, secondary_encounter_id)
-                    } else {
-                        (None, "parse-none".to_string(), None, "parse-none".to_string())
-                    };
-
-                renderable_containers.push(crate::template::listings::RenderableListing {
-                    container,
-                    members,
-                    leader_parse: crate::template::listings::ParseDisplay::new(
-                        leader_p1_percentile, leader_p1_class,
-                        leader_p2_percentile, leader_p2_class,
-                        secondary_encounter_id.is_some(),
-                    ),
-                });
-            }
+    let (filter, notice) = page_filter(world.as_deref(), dc.as_deref());
 
-            ListingsTemplate { containers: renderable_containers, lang }
-        }
+    // 조건은 DB 조회에 적용되므로 플레이어 / Parse 조회도 보이는 모집글로 한정됨
+    let res = state.filtered_listings(&filter).await;
+    let template = match res {
+        Ok(containers) => listings_page(&state, &containers, lang, watch.as_deref(), shape, notice).await,
         Err(e) => {
             tracing::error!("Failed to get listings: {:#?}", e);
             ListingsTemplate {
                 containers: Default::default(),
                 lang,
+                notice,
             }
         }
-    })
+    };
+
+    Ok(streamed_html(template.render_chunks(LISTINGS_PER_CHUNK)))
+}
+
+/// 조회한 모집글로 목록 페이지 구성
+///
+/// 업로드 힌트는 현재 모집글 스냅샷을 다시 조회할 때 갱신되므로 (`State::refresh_upload_hints`)
+/// 조건을 건 페이지를 렌더링해도 다른 데이터 센터의 힌트는 바뀌지 않습니다.
+pub(crate) async fn listings_page(
+    state: &State,
+    containers: &[QueriedListing],
+    lang: Language,
+    watch: Option<&str>,
+    shape: ApiShape,
+    notice: Option<String>,
+) -> ListingsTemplate {
+    // Collect all member IDs + leader IDs
+    let all_content_ids = collect_content_ids(containers);
+
+    // Fetch players
+    let players_list = state.players_by_content_ids(&all_content_ids).await.unwrap_or_default();
+    let players: HashMap<u64, Player> = players_list.into_iter().map(|p| (p.content_id, p)).collect();
+
+    // Optimisation: Pre-fetch parse docs of players in FFLogs listings (same as `/api/listings`)
+    let all_parse_docs = state
+        .parse_read_collection()
+        .read_by_ids(&parse_content_ids(containers), |collection, ids| async move { get_parse_docs(collection, &ids).await })
+        .await
+        .unwrap_or_default();
+
+    let mut renderable_containers = build_renderable_listings(containers.to_vec(), &players, &all_parse_docs);
+    if let Some(keys) = watch.and_then(|token| watched_keys(&state.config, token)) {
+        pin_watched(&mut renderable_containers, &keys);
+    }
+    if shape == ApiShape::Compact {
+        renderable_containers.iter_mut().for_each(RenderableListing::hide_all_stars);
+    }
+
+    ListingsTemplate { containers: renderable_containers, lang, notice }
+}
+
+/// 목록 페이지를 나눠 보낼 때 한 조각에 담는 모집글 수
+const LISTINGS_PER_CHUNK: usize = 50;
+
+/// 조각으로 렌더링한 HTML을 chunked 응답으로 전송
+///
+/// 페이지 전체를 메모리에 만들지 않으므로 모집글이 많아도 요청당 메모리 사용량이 일정합니다.
+/// 페이지 틀을 렌더링하지 못하면 응답 본문을 중단합니다 (모집글 한 행의 실패는 `render_row`가 대체).
+pub(crate) fn streamed_html(
+    chunks: impl Stream<Item = askama::Result<String>> + Send + 'static,
+) -> warp::reply::Response {
+    let body = chunks.map(|chunk| {
+        chunk.map_err(|e| {
+            tracing::error!("Failed to render listings: {:#?}", e);
+            std::io::Error::other(e.to_string())
+        })
+    });
+
+    warp::http::Response::builder()
+        .header("content-type", "text/html; charset=utf-8")
+        .body(warp::hyper::Body::wrap_stream(body))
+        .unwrap_or_else(|_| warp::http::StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+/// 모든 모집글의 멤버 + 파티장 Content ID (정렬, 중복 제거)
+pub(crate) fn collect_content_ids(containers: &[QueriedListing]) -> Vec<u64> {
+    let mut all_content_ids: Vec<u64> = containers.iter()
+        .flat_map(|l| {
+            let member_ids = l.listing.member_content_ids.iter().map(|&id| id as u64);
+            let leader_id = std::iter::once(l.listing.leader_content_id);
+            member_ids.chain(leader_id)
+        })
+        .filter(|&id| id != 0)
+        .collect();
+    all_content_ids.sort_unstable();
+    all_content_ids.dedup();
+    all_content_ids
+}
+
+/// 미리 조회한 플레이어 / Parse 정보로 렌더링용 모집글 목록 구성
+///
+/// DB 조회 없이 메모리에서만 동작합니다 (`/api/listings`와 같은 `enrichment::enrich_listings`).
+pub(crate) fn build_renderable_listings(
+    containers: Vec<QueriedListing>,
+    players: &HashMap<u64, Player>,
+    all_parse_docs: &HashMap<u64, ParseCacheDoc>,
+) -> Vec<RenderableListing> {
+    enrich_listings(containers, players, all_parse_docs)
 }
 
 pub async fn stats_handler(
     state: Arc<State>,
-    codes: Option<String>,
+    lang: Language,
     seven_days: bool,
 ) -> std::result::Result<impl Reply, Infallible> {
-    let lang = Language::from_codes(codes.as_deref());
-    let stats = state.stats.read().await.clone();
+    let stats = state.cached_stats().await;
     Ok(match stats {
         Some(stats) => StatsTemplate {
             stats: if seven_days {
@@ -198,64 +191,250 @@ pub async fn stats_handler(
                 stats.all_time
             },
             lang,
+            seven_days,
+            unknown_ids: state.unknown_ids.len(),
+            hidden_from_listings: !state.blocklist().is_empty(),
         }.into_response(),
         None => "Stats haven't been calculated yet. Please wait :(".into_response(),
     })
 }
 
+/// GET /stats/duty/{duty_id}, /stats/7days/duty/{duty_id}: 듀티 하나의 통계 (없는 듀티는 404)
+pub async fn duty_stats_handler(
+    state: Arc<State>,
+    lang: Language,
+    duty: u16,
+    seven_days: bool,
+) -> std::result::Result<warp::reply::Response, Infallible> {
+    let Some(info) = crate::ffxiv::duty(u32::from(duty)) else {
+        return Ok(warp::reply::with_status("Unknown duty.", warp::http::StatusCode::NOT_FOUND).into_response());
+    };
+
+    match state.duty_stats(duty, seven_days).await {
+        Ok(stats) => Ok(DutyStatsTemplate {
+            duty,
+            duty_name: info.name.text(&lang).to_string(),
+            stats,
+            seven_days,
+            lang,
+        }
+        .into_response()),
+        Err(e) => {
+            tracing::error!("could not calculate stats for duty {}: {:#?}", duty, e);
+            Ok(warp::http::StatusCode::INTERNAL_SERVER_ERROR.into_response())
+        }
+    }
+}
+
+/// GET /status: 사용자용 상태 요약
+pub async fn status_handler(state: Arc<State>, lang: Language) -> std::result::Result<impl Reply, Infallible> {
+    let summary = state.status_summary().await;
+    Ok(warp::reply::with_header(StatusTemplate { summary, lang }, "cache-control", "no-store"))
+}
+
+/// 모집글 업로드에서 업로더의 데이터 센터 추정
+fn listing_data_centres<'a>(listings: impl IntoIterator<Item = &'a PartyFinderListing>) -> HashSet<&'static str> {
+    listings.into_iter().filter_map(|listing| listing.data_centre_name()).collect()
+}
+
+/// 업로드된 모집글을 웹소켓으로 전송 (숨긴 듀티 / 카테고리 제외, 남는 것이 없으면 보내지 않음)
+///
+/// 자리 계산이 목록 / API와 같도록 중복 잡은 같은 규칙으로 비웁니다.
+pub(crate) fn publish_listings(
+    channel: &Sender<ListingEvent>,
+    blocklist: &Blocklist,
+    mut listings: Vec<PartyFinderListing>,
+) {
+    blocklist.retain_visible(&mut listings);
+    for listing in &mut listings {
+        listing.clear_duplicate_jobs();
+    }
+    if !listings.is_empty() {
+        let _ = channel.send(ListingEvent::Updated(listings.into()));
+    }
+}
+
+/// 모집글 저장 결과를 지표에 기록
+fn record_listing_result<T>(state: &State, result: &anyhow::Result<T>) {
+    if result.is_ok() {
+        state.metrics.record_contribution(ContributeKind::Listing, ContributeOutcome::Accepted, 1);
+    } else {
+        state.metrics.record_contribution(ContributeKind::Listing, ContributeOutcome::Failed, 1);
+        state.metrics.record_mongo_error("insert_listing");
+    }
+}
+
+/// 상세 정보가 저장된 모집글 반영 (캐시된 목록과 ETag가 이전 멤버로 남지 않도록 캐시도 비움)
+pub(crate) fn record_detail_stored(state: &State, listing_id: u32) {
+    state.listings_cache.invalidate();
+    state.bump_listings_revision();
+    state.coverage.mark_detailed(listing_id, Instant::now());
+}
+
 pub async fn contribute_handler(
     state: Arc<State>,
     listing: PartyFinderListing,
+    uploader: String,
 ) -> std::result::Result<impl Reply, Infallible> {
-    if listing.seconds_remaining > 60 * 60 {
-        return Ok("invalid listing".to_string());
+    let upload_hints = state.upload_hints(&uploader, &listing_data_centres([&listing]), [listing.id]);
+
+    if let Err(invalid) = listing.validate() {
+        state.metrics.record_contribution(ContributeKind::Listing, ContributeOutcome::Rejected, 1);
+        let result = UploadStatus::<ListingUploaded>::Error(invalid.into());
+        return Ok(ContributeResponse { result, upload_hints }.into_reply());
+    }
+
+    let flagged = state.blocked_keywords.flags(&listing);
+    let result = state.collection().write(|collection| insert_listing(collection, &listing, &uploader, flagged)).await;
+    record_listing_result(&state, &result);
+    match &result {
+        Ok(_) => state.archive.push(&listing, chrono::Utc::now()),
+        Err(e) => tracing::warn!("Failed to insert listing: {:#?}", e),
+    }
+
+    // publish listings to websockets (only if the stored listing changed and is not hidden or flagged)
+    if result.as_ref().is_ok_and(|stored| stored.should_publish()) {
+        state.listings_cache.invalidate();
+        state.bump_listings_revision();
+        publish_listings(&state.listings_channel, &state.blocklist(), vec![listing]);
     }
+    let result = result.map(|stored| ListingUploaded { outcome: stored.outcome }).into();
+    Ok(ContributeResponse { result, upload_hints }.into_reply())
+}
+
+/// `/contribute/multiple` 본문 (모집글 배열, 또는 `snapshot`과 범위를 함께 보내는 객체)
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+pub enum MultipleUpload {
+    Listings(Vec<PartyFinderListing>),
+    Snapshot(SnapshotUpload),
+}
 
-    let result = insert_listing(state.collection(), &listing).await;
+#[derive(Debug, serde::Deserialize)]
+pub struct SnapshotUpload {
+    pub listings: Vec<PartyFinderListing>,
+    /// 업로더가 지금 보는 모집글 전체인지 (아니면 배열 업로드와 같음)
+    #[serde(default)]
+    pub snapshot: bool,
+    /// 스냅샷이 다루는 모집글 생성 월드
+    #[serde(default)]
+    pub worlds: Vec<u16>,
+}
 
-    // publish listings to websockets
-    let _ = state.listings_channel.send(vec![listing].into()); 
-    Ok(format!("{:#?}", result))
+impl MultipleUpload {
+    /// 모집글과 스냅샷 범위 (스냅샷이 아니거나 범위가 비었으면 `None`)
+    ///
+    /// 업로드 토큰으로 인증하지 않은 업로드(`authenticated == false`)는 배열 업로드와 같이 다룹니다.
+    pub fn into_parts(self, uploader: &str, authenticated: bool) -> (Vec<PartyFinderListing>, Option<SnapshotScope>) {
+        match self {
+            Self::Listings(listings) => (listings, None),
+            Self::Snapshot(upload) => {
+                let scope = (upload.snapshot && authenticated && !upload.worlds.is_empty())
+                    .then(|| SnapshotScope::new(uploader, &upload.worlds, &upload.listings));
+                (upload.listings, scope)
+            }
+        }
+    }
 }
 
+/// 모집글 일괄 업로드
+///
+/// 일부 모집글이 실패해도 `200`이며 모집글별 결과는 `results`에 보낸 순서대로 담습니다.
+/// 스냅샷이면 저장 후 이 업로더가 전에 올렸는데 범위 안에서 빠진 모집글을 확인되지 않은 것으로 기록합니다.
+/// 스냅샷은 업로드 토큰으로 인증한 업로드(`authenticated`)만 받습니다.
 pub async fn contribute_multiple_handler(
     state: Arc<State>,
-    listings: Vec<PartyFinderListing>,
+    upload: MultipleUpload,
+    uploader: String,
+    authenticated: bool,
 ) -> std::result::Result<impl Reply, Infallible> {
+    let (listings, snapshot) = upload.into_parts(&uploader, authenticated);
     let total = listings.len();
     let mut successful = 0;
+    let mut changed = Vec::new();
 
-    for listing in &listings {
-        if listing.seconds_remaining > 60 * 60 {
+    let upload_hints = state.upload_hints(
+        &uploader,
+        &listing_data_centres(&listings),
+        listings.iter().map(|listing| listing.id),
+    );
+
+    let mut results = Vec::with_capacity(total);
+    for listing in listings {
+        let id = listing.id;
+        if let Err(invalid) = listing.validate() {
+            state.metrics.record_contribution(ContributeKind::Listing, ContributeOutcome::Rejected, 1);
+            results.push(ListingResult { id, result: UploadStatus::Error(invalid.into()) });
             continue;
         }
 
-        let result = insert_listing(state.collection(), listing).await;
-        if result.is_ok() {
-            successful += 1;
-        } else {
-            tracing::warn!("Failed to insert listing: {:#?}", result);
+        let flagged = state.blocked_keywords.flags(&listing);
+        let result = state.collection().write(|collection| insert_listing(collection, &listing, &uploader, flagged)).await;
+        record_listing_result(&state, &result);
+        match &result {
+            Ok(stored) => {
+                successful += 1;
+                state.archive.push(&listing, chrono::Utc::now());
+                if stored.should_publish() {
+                    changed.push(listing);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to insert listing: {:#?}", e),
+        }
+        results.push(ListingResult { id, result: result.map(|stored| ListingUploaded { outcome: stored.outcome }).into() });
+    }
+
+    let mut unconfirmed = None;
+    if let Some(scope) = snapshot {
+        let filter = scope.absent_filter(Utc::now() - state.config.mongo.listings_visible_window());
+        match state.collection().write(|collection| mark_unconfirmed(collection, filter.clone())).await {
+            Ok(marked) => unconfirmed = Some(marked),
+            Err(e) => tracing::warn!("Failed to mark unconfirmed listings: {:#?}", e),
         }
     }
 
-    let _ = state.listings_channel.send(listings.into());
-    Ok(format!("{}/{} updated", successful, total))
+    if !changed.is_empty() || unconfirmed.is_some_and(|marked| marked > 0) {
+        state.listings_cache.invalidate();
+        state.bump_listings_revision();
+    }
+    publish_listings(&state.listings_channel, &state.blocklist(), changed);
+    let result = UploadStatus::Ok(ListingsUploaded {
+        updated: successful,
+        total,
+        unconfirmed,
+        results,
+    });
+    Ok(ContributeResponse { result, upload_hints }.into_reply())
 }
 
 pub async fn contribute_players_handler(
     state: Arc<State>,
     players: Vec<UploadablePlayer>,
+    uploader: String,
 ) -> std::result::Result<impl Reply, Infallible> {
     let total = players.len();
-    let result = upsert_players(state.players_collection(), &players).await;
+    let result = state.players_collection().write(|collection| upsert_players(collection, &players)).await;
 
-    match result {
-        Ok(successful) => Ok(format!("{}/{} players updated", successful, total)),
+    match &result {
+        Ok(_) => {
+            state.metrics.record_contribution(ContributeKind::Players, ContributeOutcome::Accepted, total);
+            state.bump_listings_revision();
+            state.pending_players.resolve(players.iter().map(|p| p.content_id));
+            state.missing_players.invalidate(players.iter().map(|p| p.content_id));
+        }
         Err(e) => {
+            state.metrics.record_contribution(ContributeKind::Players, ContributeOutcome::Failed, total);
+            state.metrics.record_mongo_error("upsert_players");
             tracing::error!("error upserting players: {:#?}", e);
-            Ok(format!("0/{} players updated (error)", total))
         }
     }
+
+    let data_centres = players.iter().filter_map(|p| world_data_centre(p.home_world)).collect();
+    Ok(ContributeResponse {
+        result: result.map(|updated| PlayersUploaded { updated, total }).into(),
+        upload_hints: state.upload_hints(&uploader, &data_centres, []),
+    }
+    .into_reply())
 }
 
 /// 파티 상세 정보 (멤버 ContentId 목록)
@@ -266,42 +445,84 @@ pub struct UploadablePartyDetail {
     pub leader_name: String,
     pub home_world: u16,
     pub member_content_ids: Vec<u64>,
+    /// 자리별 멤버 (이전 플러그인은 보내지 않음)
+    #[serde(default)]
+    pub members: Vec<PartyMember>,
 }
 
 pub async fn contribute_detail_handler(
     state: Arc<State>,
     detail: UploadablePartyDetail,
+    uploader: String,
 ) -> std::result::Result<impl Reply, Infallible> {
     // 리더 정보를 플레이어로 저장
-    if detail.leader_content_id != 0 && !detail.leader_name.is_empty() && detail.home_world < 1000 {
+    let leader_upsert = if detail.leader_content_id != 0 && !detail.leader_name.is_empty() && detail.home_world < 1000 {
         let leader = crate::player::UploadablePlayer {
             content_id: detail.leader_content_id,
             name: detail.leader_name.clone(),
             home_world: detail.home_world,
         };
-        let upsert_res = upsert_players(state.players_collection(), &[leader]).await;
+        let upsert_res = state.players_collection().write(|collection| upsert_players(collection, std::slice::from_ref(&leader))).await;
         tracing::debug!("Upserted leader {}: {:?}", detail.leader_content_id, upsert_res);
+        match upsert_res {
+            Ok(_) => {
+                state.missing_players.invalidate([detail.leader_content_id]);
+                LeaderUpsert::Saved
+            }
+            Err(e) => {
+                state.metrics.record_mongo_error("upsert_players");
+                tracing::warn!("Failed to upsert leader {}: {:#?}", detail.leader_content_id, e);
+                LeaderUpsert::Failed
+            }
+        }
     } else {
         tracing::debug!("Skipping leader upsert: ID={} Name='{}' World={}", detail.leader_content_id, detail.leader_name, detail.home_world);
-    }
+        LeaderUpsert::Skipped
+    };
 
-    // listing에 member_content_ids 및 leader_content_id 저장
-    let member_ids_i64: Vec<i64> = detail.member_content_ids.iter().map(|&id| id as i64).collect();
-
-    let update_result = state.collection()
-        .update_one(
-            doc! { "listing.id": detail.listing_id },
-            doc! {
-                "$set": {
-                    "listing.member_content_ids": member_ids_i64,
-                    "listing.leader_content_id": detail.leader_content_id as i64,
-                }
-            },
-            None,
-        )
+    // listing에 member_content_ids, members 및 leader_content_id 저장
+    let update_result = state
+        .collection()
+        .write(|collection| {
+            let update = party_detail_update(detail.leader_content_id, &detail.member_content_ids, &detail.members);
+            async move {
+                let result = collection
+                    .update_one(doc! { "listing.id": detail.listing_id }, update?, None)
+                    .await?;
+                Ok(result)
+            }
+        })
         .await;
 
     tracing::debug!("Updated listing {} members: {:?}", detail.listing_id, update_result);
 
-    Ok(warp::reply::json(&"ok"))
+    if let Err(e) = &update_result {
+        state.metrics.record_contribution(ContributeKind::Detail, ContributeOutcome::Failed, 1);
+        state.metrics.record_mongo_error("update_listing_members");
+        tracing::warn!("Failed to update listing {} members: {:#?}", detail.listing_id, e);
+    }
+    if let Ok(result) = &update_result {
+        state.metrics.record_contribution(ContributeKind::Detail, ContributeOutcome::Accepted, 1);
+        // 저장된 모집글이 없으면 (만료됐거나 아직 업로드 전) 바뀐 것이 없음
+        if result.matched_count > 0 {
+            record_detail_stored(&state, detail.listing_id);
+        } else {
+            tracing::debug!("Detail for unknown listing {}", detail.listing_id);
+        }
+        if detail.leader_content_id != 0 && leader_upsert != LeaderUpsert::Failed {
+            state.pending_players.resolve([detail.leader_content_id]);
+        }
+    }
+
+    let data_centres = world_data_centre(detail.home_world).into_iter().collect();
+    let result = update_result.map(|result| DetailUploaded {
+        matched: result.matched_count,
+        modified: result.modified_count,
+        leader: leader_upsert,
+    });
+    Ok(ContributeResponse {
+        result: result.into(),
+        upload_hints: state.upload_hints(&uploader, &data_centres, []),
+    }
+    .into_reply())
 }
@@ -0,0 +1,227 @@
+//! 업로드 응답 힌트
+//!
+//! 업로더(플러그인)에게 다음 업로드 시점과 서버에 부족한 데이터를 알려줍니다.
+//! 힌트는 참고용이며, 요청마다 DB 조회 없이 메모리 상태만으로 계산합니다.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::listing_container::QueriedListing;
+
+/// 부하 / 업로더별 사용량 집계 구간
+pub const LOAD_WINDOW: Duration = Duration::from_secs(60);
+/// 집계 구간 동안 전체 업로드가 이 값을 넘으면 과부하(surge) 모드
+pub const SURGE_UPLOADS_PER_WINDOW: usize = 600;
+/// 업로더 하나가 집계 구간 동안 보내도 되는 업로드 수 (권장치, 강제하지 않음)
+pub const SOURCE_UPLOADS_PER_WINDOW: usize = 30;
+/// 평소 권장 업로드 간격
+pub const BASE_UPLOAD_INTERVAL: Duration = Duration::from_secs(60);
+/// 권장 업로드 간격 상한
+pub const MAX_UPLOAD_INTERVAL: Duration = Duration::from_secs(600);
+/// `players_wanted` 최대 개수
+pub const MAX_PLAYERS_WANTED: usize = 20;
+/// 상세 정보 확인 기록 보관 시간 (모집글 TTL과 동일)
+const COVERAGE_TTL: Duration = Duration::from_secs(3600 * 2);
+
+/// 업로드 응답에 포함되는 힌트
+#[derive(Debug, Serialize, PartialEq)]
+pub struct UploadHints {
+    /// 다음 업로드까지 권장 대기 시간 (초)
+    pub next_upload_seconds: u64,
+    /// 멤버 Content ID가 없는 모집글이 있어 상세 정보 업로드가 필요한지
+    pub detail_wanted: bool,
+    /// 업로더 데이터 센터에서 이름을 모르는 플레이어 Content ID
+    pub players_wanted: Vec<u64>,
+}
+
+/// 업로드 시점 기록 (전체 과부하 판단 + 업로더별 여유분)
+#[derive(Default)]
+pub struct UploadLoad {
+    inner: Mutex<LoadWindow>,
+}
+
+#[derive(Default)]
+struct LoadWindow {
+    uploads: VecDeque<(Instant, String)>,
+    per_source: HashMap<String, usize>,
+}
+
+impl LoadWindow {
+    fn expire(&mut self, now: Instant) {
+        while let Some((at, _)) = self.uploads.front() {
+            if now.saturating_duration_since(*at) < LOAD_WINDOW {
+                break;
+            }
+
+            let (_, source) = self.uploads.pop_front().unwrap();
+            if let Some(count) = self.per_source.get_mut(&source) {
+                *count -= 1;
+                if *count == 0 {
+                    self.per_source.remove(&source);
+                }
+            }
+        }
+    }
+}
+
+impl UploadLoad {
+    /// 업로드 한 건 기록 후 (전체 업로드 수, 해당 업로더 업로드 수) 반환
+    pub fn record(&self, uploader: &str, now: Instant) -> (usize, usize) {
+        let mut window = self.inner.lock().unwrap();
+        window.expire(now);
+        window.uploads.push_back((now, uploader.to_string()));
+        let source = window.per_source.entry(uploader.to_string()).or_default();
+        *source += 1;
+        let source = *source;
+
+        (window.uploads.len(), source)
+    }
+}
+
+/// 목록에 이름 없이 표시되는 플레이어 (데이터 센터별)
+///
+/// 현재 모집글 스냅샷을 다시 조회할 때마다 새로 채워지고, 플레이어가 업로드되면 제거됩니다.
+#[derive(Default)]
+pub struct PendingPlayers {
+    by_data_centre: Mutex<HashMap<&'static str, Vec<u64>>>,
+}
+
+impl PendingPlayers {
+    /// 모집글 중 플레이어 정보가 없는 멤버로 교체
+    pub fn replace(&self, containers: &[QueriedListing], known: impl Fn(u64) -> bool) {
+        let mut by_data_centre: HashMap<&'static str, Vec<u64>> = HashMap::new();
+        for container in containers {
+            let Some(data_centre) = container.listing.data_centre_name() else {
+                continue;
+            };
+
+            let unknown = container.listing.member_content_ids
+                .iter()
+                .map(|&id| id as u64)
+                .filter(|&id| id != 0 && !known(id));
+            by_data_centre.entry(data_centre).or_default().extend(unknown);
+        }
+
+        for ids in by_data_centre.values_mut() {
+            ids.sort_unstable();
+            ids.dedup();
+        }
+        by_data_centre.retain(|_, ids| !ids.is_empty());
+
+        *self.by_data_centre.lock().unwrap() = by_data_centre;
+    }
+
+    /// 업로드된 플레이어 제거
+    pub fn resolve(&self, content_ids: impl IntoIterator<Item = u64>) {
+        let resolved: HashSet<u64> = content_ids.into_iter().collect();
+        let mut by_data_centre = self.by_data_centre.lock().unwrap();
+        for ids in by_data_centre.values_mut() {
+            ids.retain(|id| !resolved.contains(id));
+        }
+        by_data_centre.retain(|_, ids| !ids.is_empty());
+    }
+
+    /// 해당 데이터 센터들의 미확인 플레이어 (최대 `limit`개)
+    pub fn wanted(&self, data_centres: &HashSet<&'static str>, limit: usize) -> Vec<u64> {
+        let by_data_centre = self.by_data_centre.lock().unwrap();
+        let mut wanted: Vec<u64> = data_centres
+            .iter()
+            .filter_map(|dc| by_data_centre.get(dc))
+            .flatten()
+            .copied()
+            .collect();
+        wanted.sort_unstable();
+        wanted.dedup();
+        wanted.truncate(limit);
+        wanted
+    }
+}
+
+/// 멤버 Content ID(상세 정보)가 확인된 모집글 ID
+#[derive(Default)]
+pub struct CoverageTracker {
+    detailed: Mutex<HashMap<u32, Instant>>,
+    /// 데이터 센터별 마지막 업로드 시각 (`/status`)
+    contributions: Mutex<HashMap<&'static str, DateTime<Utc>>>,
+}
+
+impl CoverageTracker {
+    /// 업로드가 들어온 데이터 센터 기록
+    pub fn record_contribution(&self, data_centres: &HashSet<&'static str>, now: DateTime<Utc>) {
+        let mut contributions = self.contributions.lock().unwrap();
+        for &data_centre in data_centres {
+            contributions.insert(data_centre, now);
+        }
+    }
+
+    /// 데이터 센터별 마지막 업로드 시각
+    pub fn last_contributions(&self) -> HashMap<&'static str, DateTime<Utc>> {
+        self.contributions.lock().unwrap().clone()
+    }
+
+    pub fn mark_detailed(&self, listing_id: u32, now: Instant) {
+        let mut detailed = self.detailed.lock().unwrap();
+        detailed.retain(|_, at| now.saturating_duration_since(*at) < COVERAGE_TTL);
+        detailed.insert(listing_id, now);
+    }
+
+    /// 모집글 중 멤버 정보가 있는 것을 기록
+    pub fn observe(&self, containers: &[QueriedListing], now: Instant) {
+        let mut detailed = self.detailed.lock().unwrap();
+        detailed.retain(|_, at| now.saturating_duration_since(*at) < COVERAGE_TTL);
+        for container in containers {
+            if !container.listing.member_content_ids.is_empty() {
+                detailed.insert(container.listing.id, now);
+            }
+        }
+    }
+
+    /// 상세 정보가 확인되지 않은 모집글이 하나라도 있는지
+    pub fn lacks_detail(&self, listing_ids: impl IntoIterator<Item = u32>) -> bool {
+        let detailed = self.detailed.lock().unwrap();
+        listing_ids.into_iter().any(|id| !detailed.contains_key(&id))
+    }
+}
+
+/// 권장 업로드 간격 계산
+///
+/// `BASE_UPLOAD_INTERVAL`을 기본값으로 하고, 과부하 모드에서는 두 배, 업로더가 집계 구간 동안 이미 보낸 업로드가
+/// 권장 사용량에 가까울수록 최대 두 배까지 추가로 늘립니다.
+pub fn next_upload_interval(surge: bool, previous_source_uploads: usize) -> Duration {
+    let mut interval = BASE_UPLOAD_INTERVAL;
+    if surge {
+        interval *= 2;
+    }
+
+    let used = previous_source_uploads.min(SOURCE_UPLOADS_PER_WINDOW) as u32;
+    interval += interval * used / SOURCE_UPLOADS_PER_WINDOW as u32;
+
+    interval.min(MAX_UPLOAD_INTERVAL)
+}
+
+/// 업로드 한 건을 기록하고 힌트 생성
+///
+/// `data_centres`는 업로드 내용으로 추정한 업로더의 데이터 센터,
+/// `listing_ids`는 상세 정보 필요 여부를 확인할 모집글입니다.
+pub fn upload_hints(
+    load: &UploadLoad,
+    pending_players: &PendingPlayers,
+    coverage: &CoverageTracker,
+    uploader: &str,
+    data_centres: &HashSet<&'static str>,
+    listing_ids: impl IntoIterator<Item = u32>,
+    now: Instant,
+) -> UploadHints {
+    let (total_uploads, source_uploads) = load.record(uploader, now);
+    let surge = total_uploads > SURGE_UPLOADS_PER_WINDOW;
+
+    UploadHints {
+        next_upload_seconds: next_upload_interval(surge, source_uploads - 1).as_secs(),
+        detail_wanted: coverage.lacks_detail(listing_ids),
+        players_wanted: pending_players.wanted(data_centres, MAX_PLAYERS_WANTED),
+    }
+}
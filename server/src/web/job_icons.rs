@@ -0,0 +1,122 @@
+//! 잡별 아이콘 (`/assets/job/{code}.svg`)
+//!
+//! 시작할 때 `icons.svg` 스프라이트를 한 번 읽어 잡 코드별 심볼을 역할 색을 입힌 단독 SVG로 만들어 둡니다.
+//! 요청마다 스프라이트를 다시 읽지 않습니다.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use warp::filters::BoxedFilter;
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
+
+/// 스프라이트 경로 (`/assets/icons.svg`와 같은 파일)
+const SPRITE_PATH: &str = "./assets/icons.svg";
+
+/// 역할 색 (`common.css`의 `--tank-blue` 등과 같은 값)
+const TANK_COLOUR: &str = "#455CCB";
+const HEALER_COLOUR: &str = "#487B39";
+const DPS_COLOUR: &str = "#813B3C";
+
+/// 아이콘은 자산 갱신 때만 바뀌므로 오래 캐시
+const CACHE_CONTROL: &str = "public, max-age=604800";
+
+pub static JOB_ICONS: LazyLock<JobIcons> = LazyLock::new(|| match std::fs::read_to_string(SPRITE_PATH) {
+    Ok(sprite) => JobIcons::parse(&sprite),
+    Err(e) => {
+        tracing::warn!("could not read {}, job icons disabled: {:#?}", SPRITE_PATH, e);
+        JobIcons::default()
+    }
+});
+
+/// 잡 아이콘 주소
+pub fn icon_url(code: &str) -> String {
+    format!("/assets/job/{}.svg", code)
+}
+
+/// 잡 코드별 단독 SVG
+#[derive(Debug, Default)]
+pub struct JobIcons {
+    icons: HashMap<&'static str, String>,
+}
+
+impl JobIcons {
+    /// 스프라이트의 `<symbol>`을 id로 모아 잡 코드에 해당하는 것만 아이콘으로 만듦
+    pub fn parse(sprite: &str) -> Self {
+        let symbols = symbols(sprite);
+
+        let icons = crate::ffxiv::JOBS
+            .values()
+            .filter_map(|&cj| {
+                let (view_box, body) = symbols.get(cj.code())?;
+                let fill = match crate::ffxiv::jobs::role_class(cj) {
+                    "tank" => TANK_COLOUR,
+                    "healer" => HEALER_COLOUR,
+                    "dps" => DPS_COLOUR,
+                    _ => "currentColor",
+                };
+                let svg = format!(
+                    r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{}" fill="{}">{}</svg>"#,
+                    view_box, fill, body,
+                );
+                Some((cj.code(), svg))
+            })
+            .collect();
+
+        Self { icons }
+    }
+
+    pub fn get(&self, code: &str) -> Option<&str> {
+        self.icons.get(code).map(String::as_str)
+    }
+
+    pub fn count(&self) -> usize {
+        self.icons.len()
+    }
+}
+
+/// `<symbol id=".." viewBox="..">본문</symbol>` 목록 (id → (viewBox, 본문))
+fn symbols(sprite: &str) -> HashMap<&str, (&str, &str)> {
+    let mut symbols = HashMap::new();
+    let mut rest = sprite;
+
+    while let Some(start) = rest.find("<symbol") {
+        rest = &rest[start..];
+        let Some(open_end) = rest.find('>') else { break };
+        let Some(close) = rest.find("</symbol>") else { break };
+        let open = &rest[..open_end];
+
+        if let Some(id) = attribute(open, "id") {
+            let view_box = attribute(open, "viewBox").unwrap_or("0 0 32 32");
+            symbols.insert(id, (view_box, rest[open_end + 1..close].trim()));
+        }
+
+        rest = &rest[close + "</symbol>".len()..];
+    }
+
+    symbols
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!(" {}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+/// GET /assets/job/{code}.svg (없는 잡 코드는 404)
+pub fn job_icon(icons: &'static JobIcons) -> BoxedFilter<(impl Reply,)> {
+    warp::path("job")
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .map(move |file: String| {
+            match file.strip_suffix(".svg").and_then(|code| icons.get(code)) {
+                Some(svg) => {
+                    let reply = warp::reply::with_header(svg.to_owned(), "content-type", "image/svg+xml");
+                    warp::reply::with_header(reply, "cache-control", CACHE_CONTROL).into_response()
+                }
+                None => StatusCode::NOT_FOUND.into_response(),
+            }
+        })
+        .boxed()
+}
@@ -0,0 +1,66 @@
+//! 웹소켓으로 보내는 모집글 변경 (업로드 / 목록에서 사라짐)
+//!
+//! 업로드는 `handlers::publish_listings`가 바로 보내고, 사라진 모집글은
+//! `background::spawn_removal_task`가 주기적으로 현재 목록을 이전 주기와 비교해 보냅니다.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::listing::PartyFinderListing;
+
+/// `State::listings_channel`로 전송하는 변경
+#[derive(Debug, Clone)]
+pub enum ListingEvent {
+    /// 새로 올라왔거나 바뀐 모집글
+    Updated(Arc<[PartyFinderListing]>),
+    /// 만료, 스냅샷에서 빠짐, 숨김 등으로 현재 목록에서 사라진 모집글
+    Removed(Arc<[ListingRef]>),
+}
+
+/// 모집글 식별자 (`PartyFinderListing::key`와 같은 필드)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ListingRef {
+    pub id: u32,
+    pub created_world: u16,
+    pub last_server_restart: u32,
+}
+
+impl From<&PartyFinderListing> for ListingRef {
+    fn from(listing: &PartyFinderListing) -> Self {
+        Self {
+            id: listing.id,
+            created_world: listing.created_world,
+            last_server_restart: listing.last_server_restart,
+        }
+    }
+}
+
+/// 이전 주기에 보였던 모집글
+#[derive(Debug, Default)]
+pub struct RemovalTracker {
+    /// 첫 주기 전에는 `None` (비교 대상이 없으므로 아무것도 보내지 않음)
+    known: Option<HashSet<ListingRef>>,
+}
+
+impl RemovalTracker {
+    /// 주기 사이에 업로드로 전송된 모집글 (다음 주기 전에 만료되어도 사라졌다고 보냄)
+    pub fn observe_updated(&mut self, listings: &[PartyFinderListing]) {
+        if let Some(known) = &mut self.known {
+            known.extend(listings.iter().map(ListingRef::from));
+        }
+    }
+
+    /// 현재 목록을 기록하고, 이전에 보였지만 지금은 없는 모집글을 반환 (정렬됨)
+    pub fn diff(&mut self, current: impl IntoIterator<Item = ListingRef>) -> Vec<ListingRef> {
+        let current: HashSet<ListingRef> = current.into_iter().collect();
+        let mut removed: Vec<ListingRef> = match &self.known {
+            Some(known) => known.difference(&current).copied().collect(),
+            None => Vec::new(),
+        };
+        removed.sort_unstable();
+        self.known = Some(current);
+        removed
+    }
+}
@@ -0,0 +1,183 @@
+//! 현재 모집글 짧은 캐시
+//!
+//! 집계 쿼리 결과를 잠시 재사용합니다. 캐시가 만료되면 처음 요청한 쪽만 다시 조회하고,
+//! 그동안 들어온 요청은 새로 조회하지 않고 그 결과를 기다립니다.
+//! 기다리는 시간이 `wait`을 넘거나 다시 조회하지 못하면 이전 스냅샷이 있을 때 그것으로 응답합니다.
+//! 업로드는 캐시를 지우지 않고 다시 조회가 필요하다고만 표시하므로 읽는 쪽은 항상 응답할 수 있습니다.
+//! secondary 노드에서 읽을 때는 업로드 표시를 따르지 않고 TTL로만 다시 조회합니다
+//! (복제가 늦은 노드에서 바로 다시 조회해도 방금 받은 업로드가 없을 수 있음).
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::watch;
+
+use crate::listing_container::QueriedListing;
+
+/// 스냅샷을 다시 조회하지 않고 쓰는 시간
+pub const LISTINGS_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// 진행 중인 조회를 기다리는 최대 시간 (넘으면 이전 스냅샷으로 응답)
+pub const REBUILD_WAIT: Duration = Duration::from_secs(10);
+
+type Snapshot = Arc<Vec<QueriedListing>>;
+
+/// 진행 중인 조회 결과 (에러는 공유를 위해 문자열로 전달)
+type Rebuild = Option<Result<Snapshot, String>>;
+
+pub struct ListingsCache {
+    ttl: Duration,
+    wait: Duration,
+    /// 업로드(`invalidate`) 후 TTL 전에도 다시 조회
+    follow_invalidations: bool,
+    inner: Mutex<Inner>,
+    rebuilds: AtomicU64,
+    coalesced_waits: AtomicU64,
+    stale_serves: AtomicU64,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// (조회 시각, 조회를 시작할 때의 `version`, 모집글)
+    snapshot: Option<(Instant, u64, Snapshot)>,
+    /// 모집글이 바뀔 때마다 증가 (스냅샷과 다르면 다시 조회)
+    version: u64,
+    /// 진행 중인 조회
+    rebuilding: Option<watch::Receiver<Rebuild>>,
+}
+
+/// 캐시 사용 기록 (서버 시작 이후)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ListingsCacheStats {
+    /// 다시 조회한 횟수
+    pub rebuilds: u64,
+    /// 진행 중인 조회를 기다린 요청 수
+    pub coalesced_waits: u64,
+    /// 이전 스냅샷으로 응답한 요청 수
+    pub stale_serves: u64,
+}
+
+enum Role {
+    Fresh(Snapshot),
+    Wait(watch::Receiver<Rebuild>, Option<Snapshot>),
+    Lead(watch::Sender<Rebuild>, u64, Option<Snapshot>),
+}
+
+/// 조회하던 요청이 취소돼도 다음 요청이 다시 조회할 수 있게 진행 표시를 지움
+struct RebuildGuard<'a> {
+    inner: &'a Mutex<Inner>,
+}
+
+impl Drop for RebuildGuard<'_> {
+    fn drop(&mut self) {
+        self.inner.lock().unwrap().rebuilding = None;
+    }
+}
+
+impl ListingsCache {
+    pub fn new(ttl: Duration, wait: Duration) -> Self {
+        Self {
+            ttl,
+            wait,
+            follow_invalidations: true,
+            inner: Default::default(),
+            rebuilds: AtomicU64::new(0),
+            coalesced_waits: AtomicU64::new(0),
+            stale_serves: AtomicU64::new(0),
+        }
+    }
+
+    /// 업로드 후 TTL 전에도 다시 조회할지 (기본값 `true`)
+    pub fn follow_invalidations(mut self, follow: bool) -> Self {
+        self.follow_invalidations = follow;
+        self
+    }
+
+    /// 유효한 스냅샷을 반환하고, 없으면 `rebuild`로 다시 조회 (동시에 한 번만)
+    pub async fn get<F, Fut>(&self, rebuild: F) -> anyhow::Result<Snapshot>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<Vec<QueriedListing>>>,
+    {
+        let role = {
+            let mut inner = self.inner.lock().unwrap();
+            let stale = inner.snapshot.as_ref().map(|(_, _, snapshot)| Arc::clone(snapshot));
+            match &inner.snapshot {
+                Some((built_at, version, snapshot))
+                    if (*version == inner.version || !self.follow_invalidations) && built_at.elapsed() < self.ttl =>
+                {
+                    Role::Fresh(Arc::clone(snapshot))
+                }
+                _ => match &inner.rebuilding {
+                    Some(rebuilding) => Role::Wait(rebuilding.clone(), stale),
+                    None => {
+                        let (tx, rx) = watch::channel(None);
+                        inner.rebuilding = Some(rx);
+                        Role::Lead(tx, inner.version, stale)
+                    }
+                },
+            }
+        };
+
+        match role {
+            Role::Fresh(snapshot) => Ok(snapshot),
+            Role::Wait(rx, stale) => self.wait_for_rebuild(rx, stale).await,
+            Role::Lead(tx, version, stale) => {
+                let guard = RebuildGuard { inner: &self.inner };
+                self.rebuilds.fetch_add(1, Ordering::Relaxed);
+                let result = rebuild().await.map(Arc::new);
+
+                if let Ok(snapshot) = &result {
+                    // 조회 중에 들어온 업로드가 있으면 다음 요청이 다시 조회
+                    self.inner.lock().unwrap().snapshot = Some((Instant::now(), version, Arc::clone(snapshot)));
+                }
+                drop(guard);
+                tx.send_replace(Some(result.as_ref().map(Arc::clone).map_err(|e| format!("{:#}", e))));
+
+                match (result, stale) {
+                    (Ok(snapshot), _) => Ok(snapshot),
+                    (Err(e), Some(stale)) => Ok(self.serve_stale(stale, &format!("{:#}", e))),
+                    (Err(e), None) => Err(e),
+                }
+            }
+        }
+    }
+
+    async fn wait_for_rebuild(&self, mut rx: watch::Receiver<Rebuild>, stale: Option<Snapshot>) -> anyhow::Result<Snapshot> {
+        self.coalesced_waits.fetch_add(1, Ordering::Relaxed);
+
+        let result = match tokio::time::timeout(self.wait, rx.wait_for(Option::is_some)).await {
+            Ok(Ok(result)) => result.clone().unwrap_or_else(|| Err("rebuild finished without a result".to_string())),
+            Ok(Err(_)) => Err("rebuild was cancelled".to_string()),
+            Err(_) => Err(format!("rebuild took longer than {}s", self.wait.as_secs())),
+        };
+
+        match (result, stale) {
+            (Ok(snapshot), _) => Ok(snapshot),
+            (Err(e), Some(stale)) => Ok(self.serve_stale(stale, &e)),
+            (Err(e), None) => Err(anyhow::anyhow!(e)),
+        }
+    }
+
+    fn serve_stale(&self, stale: Snapshot, reason: &str) -> Snapshot {
+        self.stale_serves.fetch_add(1, Ordering::Relaxed);
+        tracing::warn!("Serving stale listings: {}", reason);
+        stale
+    }
+
+    /// 모집글이 바뀜 (스냅샷은 다음 요청이 다시 조회할 때까지 그대로 씀)
+    pub fn invalidate(&self) {
+        self.inner.lock().unwrap().version += 1;
+    }
+
+    pub fn stats(&self) -> ListingsCacheStats {
+        ListingsCacheStats {
+            rebuilds: self.rebuilds.load(Ordering::Relaxed),
+            coalesced_waits: self.coalesced_waits.load(Ordering::Relaxed),
+            stale_serves: self.stale_serves.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! 게임 점검 중 백그라운드 작업 일시 정지
+//!
+//! 점검 중에는 모집글이 없고 FFLogs도 느려지므로 FFLogs 수집과 통계 계산을 건너뜁니다.
+//! 점검 여부는 다음 순서로 판단합니다.
+//!
+//! 1. 관리자 수동 설정 (`POST /api/admin/maintenance`)
+//! 2. 설정의 점검 일정 (`[maintenance]`)
+//! 3. 모든 데이터 센터의 활성 모집글이 `AUTO_DETECT_AFTER` 이상 0개인 경우 (모집글이 다시 보이면 해제)
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Datelike, NaiveDateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Maintenance as MaintenanceConfig;
+
+/// 활성 모집글이 이 시간 이상 없으면 점검으로 판단
+pub const AUTO_DETECT_AFTER: Duration = Duration::from_secs(15 * 60);
+
+/// 이 시간 안에 갱신된 모집글을 활성 모집글로 봄
+pub const ACTIVE_LISTING_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// 점검 일정 (설정의 시간대 기준)
+pub struct MaintenanceSchedule {
+    config: MaintenanceConfig,
+}
+
+impl MaintenanceSchedule {
+    pub fn new(config: MaintenanceConfig) -> Self {
+        Self { config }
+    }
+
+    /// 해당 시각이 속한 점검의 종료 시각 (점검이 아니면 `None`)
+    pub fn window_end(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let tz = self.config.timezone;
+        let local = now.with_timezone(&tz).naive_local();
+        let to_utc = |naive: NaiveDateTime| (naive - TimeDelta::try_seconds(i64::from(tz.local_minus_utc())).unwrap()).and_utc();
+
+        let ranges = self
+            .config
+            .ranges
+            .iter()
+            .filter(|range| range.start <= local && local < range.end)
+            .map(|range| range.end);
+
+        // 자정이나 주 경계를 넘는 점검도 있으므로 최근 일주일 안에 시작한 회차를 확인
+        let weekly = self.config.weekly.iter().flat_map(|window| {
+            (0..=7).filter_map(move |days_ago| {
+                let date = local.date() - TimeDelta::try_days(days_ago).unwrap();
+                if date.weekday() != window.day {
+                    return None;
+                }
+                let start = date.and_time(window.start);
+                let end = start + TimeDelta::try_minutes(i64::from(window.minutes)).unwrap();
+                (start <= local && local < end).then_some(end)
+            })
+        });
+
+        ranges.chain(weekly).max().map(to_utc)
+    }
+}
+
+/// 활성 모집글 수로 점검을 추정 (히스테리시스: 진입은 `AUTO_DETECT_AFTER` 후, 해제는 즉시)
+#[derive(Debug, Default)]
+pub struct AutoDetector {
+    zero_since: Option<Instant>,
+    detected: bool,
+}
+
+impl AutoDetector {
+    /// 활성 모집글 수를 반영하고 점검 추정 여부 반환
+    pub fn observe(&mut self, active_listings: u64, now: Instant) -> bool {
+        if active_listings > 0 {
+            if self.detected {
+                tracing::info!("listings reappeared, resuming background work");
+            }
+            self.zero_since = None;
+            self.detected = false;
+            return false;
+        }
+
+        let zero_since = *self.zero_since.get_or_insert(now);
+        if !self.detected && now.duration_since(zero_since) >= AUTO_DETECT_AFTER {
+            tracing::warn!("no active listings for {:?}, assuming game maintenance", AUTO_DETECT_AFTER);
+            self.detected = true;
+        }
+        self.detected
+    }
+
+    pub fn detected(&self) -> bool {
+        self.detected
+    }
+}
+
+/// 관리자 수동 설정
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceOverride {
+    /// 일정 / 자동 감지를 따름
+    #[default]
+    Auto,
+    /// 항상 일시 정지
+    Paused,
+    /// 항상 실행
+    Running,
+}
+
+/// 일시 정지 사유
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseReason {
+    Manual,
+    Scheduled,
+    AutoDetected,
+}
+
+/// 점검 중 건너뛰는 백그라운드 작업
+#[derive(Debug, Clone, Copy)]
+pub enum BackgroundTask {
+    FFLogs,
+    Stats,
+}
+
+/// `/api/health`에 표시하는 점검 상태
+#[derive(Debug, Serialize)]
+pub struct MaintenanceStatus {
+    pub paused: bool,
+    pub reason: Option<PauseReason>,
+    pub mode: MaintenanceOverride,
+    /// 일정상 점검 종료 시각
+    pub scheduled_until: Option<DateTime<Utc>>,
+    pub skipped_fflogs_cycles: u64,
+    pub skipped_stats_cycles: u64,
+}
+
+pub struct MaintenanceState {
+    schedule: MaintenanceSchedule,
+    detector: Mutex<AutoDetector>,
+    mode: Mutex<MaintenanceOverride>,
+    skipped_fflogs: AtomicU64,
+    skipped_stats: AtomicU64,
+}
+
+impl MaintenanceState {
+    pub fn new(config: MaintenanceConfig) -> Self {
+        Self {
+            schedule: MaintenanceSchedule::new(config),
+            detector: Default::default(),
+            mode: Default::default(),
+            skipped_fflogs: AtomicU64::new(0),
+            skipped_stats: AtomicU64::new(0),
+        }
+    }
+
+    pub fn set_mode(&self, mode: MaintenanceOverride) {
+        *self.mode.lock().unwrap() = mode;
+    }
+
+    /// 활성 모집글 수 반영 (모니터 태스크에서 주기적으로 호출)
+    pub fn observe_active_listings(&self, count: u64, now: Instant) {
+        self.detector.lock().unwrap().observe(count, now);
+    }
+
+    /// 현재 일시 정지 사유 (실행 중이면 `None`)
+    pub fn pause_reason(&self, now: DateTime<Utc>) -> Option<PauseReason> {
+        match *self.mode.lock().unwrap() {
+            MaintenanceOverride::Paused => return Some(PauseReason::Manual),
+            MaintenanceOverride::Running => return None,
+            MaintenanceOverride::Auto => {}
+        }
+
+        if self.schedule.window_end(now).is_some() {
+            Some(PauseReason::Scheduled)
+        } else if self.detector.lock().unwrap().detected() {
+            Some(PauseReason::AutoDetected)
+        } else {
+            None
+        }
+    }
+
+    /// 이번 주기를 건너뛰어야 하면 건너뛴 횟수를 기록하고 `true` 반환
+    pub fn should_skip(&self, task: BackgroundTask) -> bool {
+        let Some(reason) = self.pause_reason(Utc::now()) else {
+            return false;
+        };
+
+        let counter = match task {
+            BackgroundTask::FFLogs => &self.skipped_fflogs,
+            BackgroundTask::Stats => &self.skipped_stats,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!("skipping {:?} cycle during maintenance ({:?})", task, reason);
+        true
+    }
+
+    pub fn status(&self, now: DateTime<Utc>) -> MaintenanceStatus {
+        let reason = self.pause_reason(now);
+        MaintenanceStatus {
+            paused: reason.is_some(),
+            reason,
+            mode: *self.mode.lock().unwrap(),
+            scheduled_until: self.schedule.window_end(now),
+            skipped_fflogs_cycles: self.skipped_fflogs.load(Ordering::Relaxed),
+            skipped_stats_cycles: self.skipped_stats.load(Ordering::Relaxed),
+        }
+    }
+}
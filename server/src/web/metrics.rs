@@ -0,0 +1,343 @@
+//! Prometheus 지표 (`/metrics`)
+//!
+//! 요청 / 업로드 / FFLogs 수집 / 통계 태스크 카운터를 메모리에 모아 텍스트 형식으로 내보냅니다.
+//! 라벨 수가 늘지 않도록 요청 경로는 등록된 라우트 형태로 바꾸고, 모르는 경로는 `other`로 셉니다.
+//! `web.metrics_host`를 설정하면 공개 라우터 대신 그 주소에서만 제공합니다.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use warp::{filters::BoxedFilter, Filter, Reply};
+
+use crate::ws::limits::ConnectionStats;
+use super::State;
+
+/// 지연 시간 히스토그램 구간 (초)
+pub const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// 지연 시간 히스토그램을 기록하는 라우트
+pub const LATENCY_ROUTES: [&str; 2] = ["/listings", "/api/listings"];
+
+/// 요청 라벨로 쓰는 라우트 형태
+///
+/// `{id}`는 숫자 구간, `{name}`은 아무 구간, `*`는 나머지 경로 전체와 맞습니다.
+const ROUTES: &[&str] = &[
+    "/",
+    "/listings",
+    "/contribute",
+    "/contribute/multiple",
+    "/contribute/players",
+    "/contribute/detail",
+    "/stats",
+    "/stats/7days",
+    "/stats/duty/{id}",
+    "/stats/7days/duty/{id}",
+    "/status",
+    "/readyz",
+    "/metrics",
+    "/admin",
+    "/admin/login",
+    "/admin/logout",
+    "/assets/*",
+    "/feeds/schedule/{name}",
+    "/feeds/listings.atom",
+    "/api/ws",
+    "/api/health",
+    "/api/status",
+    "/api/stats",
+    "/api/stats/7days",
+    "/api/stats/duty/{id}",
+    "/api/role_demand",
+    "/api/summary",
+    "/api/version",
+    "/api/listings",
+    "/api/listings/{id}",
+    "/api/players",
+    "/api/players/lookup",
+    "/api/players/{id}/listings",
+    "/api/parses/{id}",
+    "/api/bookmarks",
+    "/api/bookmarks/{name}",
+    "/api/datasets/*",
+    "/api/admin/*",
+    "/api/moderation/hide",
+    "/api/moderation/unhide",
+];
+
+/// 모르는 경로의 요청 라벨
+pub const OTHER_ROUTE: &str = "other";
+
+/// 요청 경로의 지표 라벨
+pub fn route_label(path: &str) -> &'static str {
+    ROUTES.iter().copied().find(|route| route_matches(route, path)).unwrap_or(OTHER_ROUTE)
+}
+
+fn route_matches(route: &str, path: &str) -> bool {
+    let mut path = path.trim_end_matches('/').split('/').skip(1);
+    for expected in route.trim_end_matches('/').split('/').skip(1) {
+        if expected == "*" {
+            return true;
+        }
+        let Some(segment) = path.next() else {
+            return false;
+        };
+        let matched = match expected {
+            "{id}" => !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()),
+            "{name}" => !segment.is_empty(),
+            _ => segment == expected,
+        };
+        if !matched {
+            return false;
+        }
+    }
+    path.next().is_none()
+}
+
+/// 업로드 종류 (`/contribute` 경로별)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ContributeKind {
+    Listing,
+    Players,
+    Detail,
+}
+
+impl ContributeKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Listing => "listing",
+            Self::Players => "players",
+            Self::Detail => "detail",
+        }
+    }
+}
+
+/// 업로드 한 건의 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ContributeOutcome {
+    /// 저장함
+    Accepted,
+    /// 검증에 실패해 버림
+    Rejected,
+    /// 저장 중 데이터베이스 오류
+    Failed,
+}
+
+impl ContributeOutcome {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Accepted => "accepted",
+            Self::Rejected => "rejected",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// FFLogs 수집 한 주기의 결과
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FFLogsCycle {
+    /// 보낸 배치 요청 수
+    pub batches: u64,
+    /// 실패한 배치 요청 수
+    pub batch_errors: u64,
+    /// 저장한 Parse 수
+    pub parses_saved: u64,
+    /// 캐시가 유효해 건너뛴 플레이어 수
+    pub cache_hits: u64,
+}
+
+#[derive(Default)]
+struct Histogram {
+    /// 구간별 개수 (누적 아님)
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|&bound| seconds <= bound) {
+            self.buckets[bucket] += 1;
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    requests: BTreeMap<(&'static str, u16), u64>,
+    latency: BTreeMap<&'static str, Histogram>,
+    contributions: BTreeMap<(ContributeKind, ContributeOutcome), u64>,
+    mongo_errors: BTreeMap<&'static str, u64>,
+    fflogs_cycles: u64,
+    fflogs_total: FFLogsCycle,
+    fflogs_last: Option<FFLogsCycle>,
+    stats_runs: BTreeMap<&'static str, u64>,
+    stats_duration: Option<Duration>,
+}
+
+#[derive(Default)]
+pub struct Metrics {
+    counters: Mutex<Counters>,
+}
+
+impl Metrics {
+    /// 응답 한 건 (라우트 라벨, 상태 코드, 처리 시간)
+    pub fn record_request(&self, path: &str, status: u16, elapsed: Duration) {
+        let route = route_label(path);
+        let mut counters = self.counters.lock().unwrap();
+        *counters.requests.entry((route, status)).or_default() += 1;
+        if LATENCY_ROUTES.contains(&route) {
+            counters.latency.entry(route).or_default().observe(elapsed.as_secs_f64());
+        }
+    }
+
+    /// 업로드 결과 (`count`는 모집글 / 플레이어 / 상세 정보 수)
+    pub fn record_contribution(&self, kind: ContributeKind, outcome: ContributeOutcome, count: usize) {
+        *self.counters.lock().unwrap().contributions.entry((kind, outcome)).or_default() += count as u64;
+    }
+
+    /// 데이터베이스 오류 (`operation`은 정해진 작업 이름)
+    pub fn record_mongo_error(&self, operation: &'static str) {
+        *self.counters.lock().unwrap().mongo_errors.entry(operation).or_default() += 1;
+    }
+
+    pub fn record_fflogs_cycle(&self, cycle: FFLogsCycle) {
+        let mut counters = self.counters.lock().unwrap();
+        counters.fflogs_cycles += 1;
+        let total = &mut counters.fflogs_total;
+        total.batches += cycle.batches;
+        total.batch_errors += cycle.batch_errors;
+        total.parses_saved += cycle.parses_saved;
+        total.cache_hits += cycle.cache_hits;
+        counters.fflogs_last = Some(cycle);
+    }
+
+    /// 통계 계산 한 번 (성공 여부, 걸린 시간)
+    pub fn record_stats_run(&self, ok: bool, duration: Duration) {
+        let mut counters = self.counters.lock().unwrap();
+        *counters.stats_runs.entry(if ok { "ok" } else { "error" }).or_default() += 1;
+        counters.stats_duration = Some(duration);
+    }
+
+    /// Prometheus 텍스트 형식
+    pub fn render(&self, websockets: &ConnectionStats) -> String {
+        let counters = self.counters.lock().unwrap();
+        let mut out = String::new();
+
+        header(&mut out, "rpf_http_requests_total", "counter", "HTTP responses by route and status.");
+        for ((route, status), count) in &counters.requests {
+            let _ = writeln!(out, "rpf_http_requests_total{{route=\"{}\",status=\"{}\"}} {}", route, status, count);
+        }
+
+        header(&mut out, "rpf_http_request_duration_seconds", "histogram", "Response time of the listing pages.");
+        for (route, histogram) in &counters.latency {
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "rpf_http_request_duration_seconds_bucket{{route=\"{}\",le=\"{}\"}} {}",
+                    route, bound, cumulative
+                );
+            }
+            let _ = writeln!(out, "rpf_http_request_duration_seconds_bucket{{route=\"{}\",le=\"+Inf\"}} {}", route, histogram.count);
+            let _ = writeln!(out, "rpf_http_request_duration_seconds_sum{{route=\"{}\"}} {}", route, histogram.sum);
+            let _ = writeln!(out, "rpf_http_request_duration_seconds_count{{route=\"{}\"}} {}", route, histogram.count);
+        }
+
+        header(&mut out, "rpf_contributions_total", "counter", "Uploaded items by kind and outcome.");
+        for ((kind, outcome), count) in &counters.contributions {
+            let _ = writeln!(
+                out,
+                "rpf_contributions_total{{kind=\"{}\",outcome=\"{}\"}} {}",
+                kind.label(),
+                outcome.label(),
+                count
+            );
+        }
+
+        header(&mut out, "rpf_mongo_errors_total", "counter", "Failed database operations.");
+        for (operation, count) in &counters.mongo_errors {
+            let _ = writeln!(out, "rpf_mongo_errors_total{{operation=\"{}\"}} {}", operation, count);
+        }
+
+        header(&mut out, "rpf_websocket_clients", "gauge", "Connected websocket clients by tier.");
+        let _ = writeln!(out, "rpf_websocket_clients{{tier=\"anonymous\"}} {}", websockets.anonymous);
+        let _ = writeln!(out, "rpf_websocket_clients{{tier=\"priority\"}} {}", websockets.priority);
+        header(&mut out, "rpf_websocket_ping_timeouts_total", "counter", "Websocket connections closed for not answering pings.");
+        let _ = writeln!(out, "rpf_websocket_ping_timeouts_total {}", websockets.timed_out);
+
+        let total = counters.fflogs_total;
+        let last = counters.fflogs_last.unwrap_or_default();
+        let fflogs = [
+            ("cycles", "Completed FFLogs collection cycles.", counters.fflogs_cycles, None),
+            ("batches", "FFLogs batch requests sent.", total.batches, Some(last.batches)),
+            ("batch_errors", "FFLogs batch requests that failed.", total.batch_errors, Some(last.batch_errors)),
+            ("parses_saved", "Parses saved from FFLogs.", total.parses_saved, Some(last.parses_saved)),
+            ("cache_hits", "Players skipped because their parse cache was fresh.", total.cache_hits, Some(last.cache_hits)),
+        ];
+        for (name, help, value, _) in fflogs {
+            let metric = format!("rpf_fflogs_{}_total", name);
+            header(&mut out, &metric, "counter", help);
+            let _ = writeln!(out, "{} {}", metric, value);
+        }
+        for (name, help, _, last) in fflogs {
+            let Some(last) = last else { continue };
+            let metric = format!("rpf_fflogs_last_cycle_{}", name);
+            header(&mut out, &metric, "gauge", &format!("{} (last cycle)", help.trim_end_matches('.')));
+            let _ = writeln!(out, "{} {}", metric, last);
+        }
+
+        header(&mut out, "rpf_stats_runs_total", "counter", "Statistics calculations by result.");
+        for (result, count) in &counters.stats_runs {
+            let _ = writeln!(out, "rpf_stats_runs_total{{result=\"{}\"}} {}", result, count);
+        }
+        if let Some(duration) = counters.stats_duration {
+            header(&mut out, "rpf_stats_duration_seconds", "gauge", "Duration of the last statistics calculation.");
+            let _ = writeln!(out, "rpf_stats_duration_seconds {}", duration.as_secs_f64());
+        }
+
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// 요청 지표를 기록하는 래퍼 (`router`에 씌움)
+pub fn record(state: Arc<State>) -> warp::log::Log<impl Fn(warp::log::Info<'_>) + Clone + Send> {
+    warp::log::custom(move |info| state.metrics.record_request(info.path(), info.status().as_u16(), info.elapsed()))
+}
+
+/// GET /metrics
+pub fn metrics(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    warp::get()
+        .and(warp::path("metrics"))
+        .and(warp::path::end())
+        .map(move || {
+            let body = state.metrics.render(&state.websockets.stats());
+            warp::reply::with_header(body, "content-type", "text/plain; version=0.0.4")
+        })
+        .boxed()
+}
+
+/// 공개 라우터의 `/metrics` (`web.metrics_host`를 설정하면 없는 경로)
+pub fn public_metrics(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    let separate = state.config.web.metrics_host.is_some();
+    warp::any()
+        .and_then(move || async move {
+            if separate {
+                Err(warp::reject::not_found())
+            } else {
+                Ok(())
+            }
+        })
+        .untuple_one()
+        .and(metrics(state))
+        .boxed()
+}
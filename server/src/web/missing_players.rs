@@ -0,0 +1,125 @@
+//! 없는 플레이어 조회 결과 캐시
+//!
+//! 이름이 업로드된 적 없는 멤버는 목록을 렌더링할 때마다 `players` 컬렉션에서 다시 조회되지만
+//! 결과는 항상 비어 있습니다. 조회 결과가 없던 Content ID를 잠시 기억해 `$in` 목록에서 빼고,
+//! 해당 플레이어가 업로드되면 즉시 지웁니다. HTML / API / 백그라운드 조회가 같은 캐시를 씁니다.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::player::Player;
+
+/// 없는 플레이어를 다시 조회하기까지의 시간
+pub const MISSING_PLAYER_TTL: Duration = Duration::from_secs(5 * 60);
+/// 기억하는 Content ID 최대 개수 (넘으면 가장 먼저 만료되는 항목부터 제거)
+pub const MAX_MISSING_PLAYERS: usize = 20_000;
+
+/// `/api/health`에 표시하는 캐시 상태
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct MissingPlayerStats {
+    pub entries: usize,
+    /// 조회를 건너뛴 Content ID 수
+    pub hits: u64,
+    /// DB에서 조회한 Content ID 수
+    pub misses: u64,
+}
+
+pub struct MissingPlayers {
+    /// Content ID → 만료 시각
+    expiry: Mutex<HashMap<u64, Instant>>,
+    ttl: Duration,
+    capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Default for MissingPlayers {
+    fn default() -> Self {
+        Self::new(MISSING_PLAYER_TTL, MAX_MISSING_PLAYERS)
+    }
+}
+
+impl MissingPlayers {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            expiry: Default::default(),
+            ttl,
+            capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// 없다고 기억하는 ID를 뺀 조회 대상
+    pub fn to_query(&self, content_ids: &[u64], now: Instant) -> Vec<u64> {
+        let expiry = self.expiry.lock().unwrap();
+        let query: Vec<u64> = content_ids
+            .iter()
+            .copied()
+            .filter(|id| !expiry.get(id).is_some_and(|&until| now < until))
+            .collect();
+
+        self.hits.fetch_add((content_ids.len() - query.len()) as u64, Ordering::Relaxed);
+        self.misses.fetch_add(query.len() as u64, Ordering::Relaxed);
+        query
+    }
+
+    /// 조회했지만 결과가 없던 ID 기록
+    pub fn record_missing(&self, queried: &[u64], found: &[Player], now: Instant) {
+        let found: HashSet<u64> = found.iter().map(|player| player.content_id).collect();
+        let mut expiry = self.expiry.lock().unwrap();
+        for &id in queried.iter().filter(|id| !found.contains(id)) {
+            expiry.insert(id, now + self.ttl);
+        }
+
+        if expiry.len() > self.capacity {
+            expiry.retain(|_, until| now < *until);
+        }
+        if expiry.len() > self.capacity {
+            let mut by_expiry: Vec<(Instant, u64)> = expiry.iter().map(|(&id, &until)| (until, id)).collect();
+            by_expiry.sort_unstable();
+            let excess = expiry.len() - self.capacity;
+            for (_, id) in &by_expiry[..excess] {
+                expiry.remove(id);
+            }
+        }
+    }
+
+    /// 업로드된 플레이어는 바로 다시 조회되도록 제거
+    pub fn invalidate(&self, content_ids: impl IntoIterator<Item = u64>) {
+        let mut expiry = self.expiry.lock().unwrap();
+        for id in content_ids {
+            expiry.remove(&id);
+        }
+    }
+
+    /// 캐시를 거쳐 플레이어 조회 (조회할 ID가 없으면 `fetch`를 호출하지 않음)
+    pub async fn lookup<F, Fut>(&self, content_ids: &[u64], fetch: F) -> Result<Vec<Player>>
+    where
+        F: FnOnce(Vec<u64>) -> Fut,
+        Fut: Future<Output = Result<Vec<Player>>>,
+    {
+        let query = self.to_query(content_ids, Instant::now());
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let players = fetch(query.clone()).await?;
+        self.record_missing(&query, &players, Instant::now());
+        Ok(players)
+    }
+
+    pub fn stats(&self) -> MissingPlayerStats {
+        MissingPlayerStats {
+            entries: self.expiry.lock().unwrap().len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -1,134 +1,564 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashSet, path::PathBuf, sync::{atomic::{AtomicU64, Ordering}, Arc}, time::{Duration, Instant}};
 use anyhow::{Context, Result};
-use mongodb::{
-    options::IndexOptions,
-    Client as MongoClient, Collection, IndexModel,
-};
+use mongodb::{Client as MongoClient, Collection, Database};
 use tokio::sync::broadcast::Sender;
 use tokio::sync::RwLock;
 
 use crate::config::Config;
-use crate::listing::PartyFinderListing;
-use crate::listing_container::ListingContainer;
+use crate::feeds::{FeedCache, FEED_CACHE_TTL};
+use crate::infra::indexes::{check_indexes, ensure_index, expected_indexes, IndexHealth};
+use crate::infra::migration::MigrationStatus;
+use crate::infra::mirror::Mirrored;
+use crate::logging::LogHandle;
+use self::hints::{CoverageTracker, PendingPlayers, UploadLoad};
+use self::missing_players::MissingPlayers;
+use self::stats_refresh::StatsRefresh;
+use crate::ffxiv::unknown_ids::{UnknownIds, UNKNOWN_IDS};
+use crate::listing::{Blocklist, ListingFilter};
+use crate::listing::history::{hosted_listings, HostedListing};
+use crate::listing::keywords::BlockedKeywords;
+use crate::listing::moderation::ModerationRecord;
+use crate::listing_container::{ListingContainer, QueriedListing};
+use crate::mongo::{get_current_listings, get_filtered_listings, get_listing_by_id, get_players_by_content_ids, ParseCacheDoc};
 use crate::player::Player;
+use crate::stats::daily::DailyCount;
+use crate::stats::role_demand::RoleDemandSample;
 use crate::stats::CachedStatistics;
 
 pub mod routes;
 pub mod handlers;
+pub mod admin_page;
+pub mod archive;
 pub mod background;
+pub mod duty_stats;
+pub mod enrichment;
+pub mod fingerprint;
+pub mod hints;
+pub mod job_icons;
+pub mod listing_events;
+pub mod listings_cache;
+pub mod maintenance;
+pub mod metrics;
+pub mod missing_players;
+pub mod rate_limit;
+pub mod readiness;
+pub mod stats_refresh;
+pub mod status;
+pub mod summary;
+pub mod token_compare;
+pub mod upload_auth;
+pub mod upload_body;
+pub mod upload_response;
+pub mod volume;
 
-pub async fn start(config: Arc<Config>) -> Result<()> {
-    let state = State::new(Arc::clone(&config)).await?;
+pub async fn start(config: Arc<Config>, config_path: PathBuf, log_handle: LogHandle) -> Result<()> {
+    let state = State::new(Arc::clone(&config), log_handle).await?;
+
+    // Mongo 연결 확인 + 비밀값 읽기 + 인덱스 생성 (완료되면 준비 상태에 반영)
+    let startup_state = Arc::clone(&state);
+    tokio::task::spawn(async move {
+        readiness::initialise(&startup_state.readiness, &*startup_state, readiness::STARTUP_RETRY).await;
+    });
+
+    // 잡 아이콘은 요청 전에 만들어 둠
+    tracing::info!("prepared {} job icons", job_icons::JOB_ICONS.count());
 
     // Background tasks
     background::spawn_stats_task(Arc::clone(&state));
     background::spawn_fflogs_task(Arc::clone(&state));
+    background::spawn_export_task(Arc::clone(&state));
+    background::spawn_maintenance_task(Arc::clone(&state));
+    background::spawn_sampling_task(Arc::clone(&state));
+    background::spawn_daily_rollup_task(Arc::clone(&state));
+    background::spawn_archive_task(Arc::clone(&state));
+    background::spawn_removal_task(Arc::clone(&state));
+    background::spawn_rate_limit_prune_task(Arc::clone(&state));
+    background::spawn_migration_task(Arc::clone(&state));
+    background::spawn_player_compaction_task(Arc::clone(&state));
+    background::spawn_reload_task(Arc::clone(&state), config_path);
+
+    if let Some(metrics_host) = config.web.metrics_host {
+        tracing::info!("serving metrics at {}", metrics_host);
+        let metrics = metrics::metrics(Arc::clone(&state));
+        tokio::task::spawn(warp::serve(metrics).run(metrics_host));
+    }
 
     tracing::info!("listening at {}", config.web.host);
-    warp::serve(routes::router(state)).run(config.web.host).await;
+    if config.web.wait_for_ready {
+        let readiness = Arc::clone(&state.readiness);
+        let (_, server) = readiness::bind_after_ready(readiness, routes::router(state), config.web.host)?;
+        server.await?;
+    } else {
+        warp::serve(routes::router(state)).run(config.web.host).await;
+    }
     Ok(())
 }
 
 pub struct State {
+    pub config: Arc<Config>,
     pub mongo: MongoClient,
+    /// 모집글 / 플레이어 / Parse 컬렉션 (데이터베이스 이름 변경 중에는 양쪽에 기록)
+    listings: Mirrored<Collection<ListingContainer>>,
+    players: Mirrored<Collection<Player>>,
+    parses: Mirrored<Collection<ParseCacheDoc>>,
+    /// 읽기 전용 조회용 같은 컬렉션 (`mongo.read_preference` 적용)
+    listing_reads: Mirrored<Collection<ListingContainer>>,
+    player_reads: Mirrored<Collection<Player>>,
+    parse_reads: Mirrored<Collection<ParseCacheDoc>>,
+    /// 이전 데이터베이스 복사 진행 상황
+    pub migration: MigrationStatus,
+    /// 시작할 때 비교한 인덱스 상태
+    pub indexes: IndexHealth,
+    /// 중복 플레이어 병합 실행 상태와 마지막 보고서
+    pub player_compaction: crate::infra::player_compaction::CompactionStatus,
     pub stats: RwLock<Option<CachedStatistics>>,
-    pub listings_channel: Sender<Arc<[PartyFinderListing]>>,
+    /// 오래된 통계 갱신 요청
+    pub stats_refresh: StatsRefresh,
+    /// 듀티별 통계 짧은 캐시
+    pub duty_stats: duty_stats::DutyStatsCache,
+    /// 현재 모집글 요약 짧은 캐시
+    pub summary: summary::SummaryCache,
+    /// 업로드 / 사라진 모집글 (웹소켓)
+    pub listings_channel: Sender<listing_events::ListingEvent>,
+    /// 웹소켓 연결 제한
+    pub websockets: Arc<crate::ws::limits::ConnectionLimits>,
+    /// 업로드 주소별 요청 제한
+    pub contribute_limiter: rate_limit::RateLimiter,
     pub fflogs_client: Option<crate::fflogs::FFLogsClient>,
+    /// 게임 데이터에 없는 ID 기록 (조회 헬퍼가 전역으로 기록하므로 같은 레지스트리를 가리킴)
+    pub unknown_ids: &'static UnknownIds,
+    /// 렌더링한 구독 피드 캐시
+    pub feed_cache: FeedCache,
+    /// 현재 모집글 짧은 캐시 (업로드되면 다시 조회 표시)
+    pub listings_cache: listings_cache::ListingsCache,
+    /// `/api/listings` ETag 리비전 (모집글 업데이트 시각에 드러나지 않는 변경마다 증가)
+    listings_revision: AtomicU64,
+    /// 업로드 부하 (힌트의 권장 업로드 간격 계산용)
+    pub upload_load: UploadLoad,
+    /// 목록에 이름 없이 표시된 플레이어
+    pub pending_players: PendingPlayers,
+    /// 조회 결과가 없던 플레이어 (반복 조회 방지)
+    pub missing_players: MissingPlayers,
+    /// `listings_archive`에 쓸 업로드 대기열
+    pub archive: archive::ArchiveQueue,
+    /// Prometheus 지표 (`/metrics`)
+    pub metrics: metrics::Metrics,
+    /// 멤버 정보가 확인된 모집글
+    pub coverage: CoverageTracker,
+    /// FFLogs Zone별 조회 파티션 (설정 재정의 + 관리자 변경)
+    pub zone_partitions: crate::fflogs::ZonePartitions,
+    /// Zone별 Parse 캐시 갱신 주기
+    pub parse_cache_expiry: crate::fflogs::CacheExpiry,
+    /// 관리자가 요청한 Parse 우선 재조회 대기열
+    pub parse_refetch: crate::fflogs::RefetchQueue,
+    /// FFLogs 수집 주기 기록 (`/status`)
+    pub fflogs_cycles: status::CycleTracker,
+    /// FFLogs 응답 형태 변경 감지 (`/api/health`)
+    pub fflogs_schema: crate::fflogs::SchemaHealth,
+    /// 시작 준비 상태 (`/readyz`)
+    pub readiness: Arc<readiness::Readiness>,
+    /// 점검 중 백그라운드 작업 일시 정지 상태
+    pub maintenance: maintenance::MaintenanceState,
+    /// 로그 레벨 변경 핸들 (관리자 API)
+    pub log_handle: LogHandle,
+    /// 데이터 센터별 모집글 수 급감 감지
+    pub volume: volume::VolumeMonitor,
+    /// 공개 목록에서 숨길 듀티 / 카테고리 (SIGHUP으로 다시 읽음)
+    pub blocklist: std::sync::RwLock<Blocklist>,
+    /// 설명 금칙어 (시작할 때 한 번 컴파일)
+    pub blocked_keywords: BlockedKeywords,
+    /// 업로더 지문 솔트
+    pub uploader_salt: fingerprint::UploaderSalt,
 }
 
 impl State {
-    pub async fn new(config: Arc<Config>) -> Result<Arc<Self>> {
+    pub async fn new(config: Arc<Config>, log_handle: LogHandle) -> Result<Arc<Self>> {
         let mongo = MongoClient::with_uri_str(&config.mongo.url)
             .await
             .context("could not create mongodb client")?;
             
+        let primary = mongo.database(&config.mongo.database);
+        let legacy = config.mongo.legacy_database().map(|name| mongo.database(name));
+        if let Some(legacy) = &legacy {
+            tracing::info!(
+                "Migrating from database `{}` to `{}`: writing to both, reading `{}` first",
+                legacy.name(),
+                primary.name(),
+                primary.name(),
+            );
+        }
+        let names = &config.mongo;
+        let listings = Mirrored::collection(&primary, legacy.as_ref(), &names.listings_collection);
+        let players = Mirrored::collection(&primary, legacy.as_ref(), &names.players_collection);
+        let parses = Mirrored::collection(&primary, legacy.as_ref(), &names.parses_collection);
+
+        let read_criteria = config.mongo.read_criteria()?;
+        if let Some(criteria) = &read_criteria {
+            tracing::info!("Read-only queries use read preference {:?}", criteria);
+        }
+        let listing_reads =
+            Mirrored::collection_for_reads(&primary, legacy.as_ref(), &names.listings_collection, read_criteria.clone());
+        let player_reads =
+            Mirrored::collection_for_reads(&primary, legacy.as_ref(), &names.players_collection, read_criteria.clone());
+        let parse_reads =
+            Mirrored::collection_for_reads(&primary, legacy.as_ref(), &names.parses_collection, read_criteria);
+
         let fflogs_client = config.fflogs.clone().map(crate::fflogs::FFLogsClient::new);
+        let partition_overrides = config.fflogs.as_ref().map(|f| f.partition_overrides()).unwrap_or_default();
+        let parse_cache_expiry = crate::fflogs::CacheExpiry::from_config(config.fflogs.as_ref());
+
+        let stats_grace = Duration::from_secs(config.web.stats_grace_secs);
+        let stats_refresh = StatsRefresh::new(Duration::from_secs(config.web.stats_max_age_hours * 60 * 60));
+        let maintenance = maintenance::MaintenanceState::new(config.maintenance.clone());
+        let volume = volume::VolumeMonitor::new(config.volume_alerts.clone());
+        let blocklist = std::sync::RwLock::new(Blocklist::new(&config.display));
+        let blocked_keywords = BlockedKeywords::new(&config.keyword_filter)?;
+
+        let priority_tokens = config.admin.as_ref().map(|admin| admin.priority_tokens()).unwrap_or_default();
+        let websockets = Arc::new(crate::ws::limits::ConnectionLimits::new(&config.websocket, priority_tokens));
+        let contribute_limiter = rate_limit::RateLimiter::new(&config.ratelimit);
+        let uploader_salt = fingerprint::UploaderSalt::from_config(&config.ratelimit);
+
+        // secondary 노드는 방금 받은 업로드를 아직 모를 수 있으므로 업로드마다 다시 조회하지 않고 TTL로만 갱신
+        let listings_cache =
+            listings_cache::ListingsCache::new(listings_cache::LISTINGS_CACHE_TTL, listings_cache::REBUILD_WAIT)
+                .follow_invalidations(!config.mongo.reads_may_lag());
 
-        let (tx, _) = tokio::sync::broadcast::channel(16);
+        let (tx, _) = tokio::sync::broadcast::channel(config.websocket.broadcast_capacity.max(1));
         let state = Arc::new(Self {
+            config,
             mongo,
+            listings,
+            players,
+            parses,
+            listing_reads,
+            player_reads,
+            parse_reads,
+            migration: Default::default(),
+            indexes: Default::default(),
+            player_compaction: Default::default(),
             stats: Default::default(),
+            stats_refresh,
+            duty_stats: Default::default(),
+            summary: Default::default(),
             listings_channel: tx,
+            websockets,
+            contribute_limiter,
             fflogs_client,
+            unknown_ids: &UNKNOWN_IDS,
+            feed_cache: FeedCache::new(FEED_CACHE_TTL),
+            listings_cache,
+            // 재시작 전 ETag와 겹치지 않도록 시작 시각에서 시작
+            listings_revision: AtomicU64::new(chrono::Utc::now().timestamp_millis() as u64),
+            upload_load: Default::default(),
+            pending_players: Default::default(),
+            missing_players: Default::default(),
+            archive: Default::default(),
+            metrics: Default::default(),
+            coverage: Default::default(),
+            zone_partitions: crate::fflogs::ZonePartitions::new(partition_overrides),
+            parse_cache_expiry,
+            parse_refetch: Default::default(),
+            fflogs_cycles: Default::default(),
+            fflogs_schema: Default::default(),
+            readiness: Arc::new(readiness::Readiness::new(stats_grace)),
+            maintenance,
+            log_handle,
+            volume,
+            blocklist,
+            blocked_keywords,
+            uploader_salt,
         });
 
-        // Initialize Indexes
-        state.ensure_indexes().await?;
-
         Ok(state)
     }
 
-    async fn ensure_indexes(&self) -> Result<()> {
-        // Listings Unique Index
-        self.collection()
-            .create_index(
-                IndexModel::builder()
-                    .keys(mongodb::bson::doc! {
-                        "listing.id": 1,
-                        "listing.last_server_restart": 1,
-                        "listing.created_world": 1,
-                    })
-                    .options(IndexOptions::builder().unique(true).build())
-                    .build(),
-                None,
-            )
-            .await
-            .context("could not create unique index")?;
+    /// 업로드 한 건을 기록하고 응답에 포함할 힌트 생성
+    pub fn upload_hints(
+        &self,
+        uploader: &str,
+        data_centres: &HashSet<&'static str>,
+        listing_ids: impl IntoIterator<Item = u32>,
+    ) -> hints::UploadHints {
+        self.coverage.record_contribution(data_centres, chrono::Utc::now());
+        hints::upload_hints(
+            &self.upload_load,
+            &self.pending_players,
+            &self.coverage,
+            uploader,
+            data_centres,
+            listing_ids,
+            Instant::now(),
+        )
+    }
+
+    /// `/status`, `/api/status` 요약
+    pub async fn status_summary(&self) -> status::StatusSummary {
+        let data_centres = crate::stats::role_demand::data_centres();
+        status::StatusSummary::build(self.status_inputs().await, &data_centres, chrono::Utc::now())
+    }
+
+    /// `/status` 요약에 필요한 메모리 상태 (DB 조회 없음)
+    pub async fn status_inputs(&self) -> status::StatusInputs {
+        let now = chrono::Utc::now();
+        status::StatusInputs {
+            contributions: self.coverage.last_contributions(),
+            listing_counts: self.volume.counts(),
+            volume_alerts: self.volume.alerts(),
+            fflogs: self.fflogs_client.as_ref().map(|_| self.fflogs_cycles.snapshot()),
+            stats_generated_at: self.stats.read().await.as_ref().map(|stats| stats.generated_at()),
+            stats_refreshing: self.stats_refresh.in_flight(),
+            maintenance: Some(self.maintenance.status(now)),
+        }
+    }
 
-        // Listings TTL Index
-        let listings_index_model = IndexModel::builder()
-            .keys(mongodb::bson::doc! {
-                "updated_at": 1,
+    /// 공개 목록에 표시할 활성 모집글 (숨긴 듀티 / 카테고리 제외, `listings_cache`의 스냅샷을 그대로 공유)
+    pub async fn current_listings(&self) -> Result<Arc<Vec<QueriedListing>>> {
+        self.listings_cache
+            .get(|| async {
+                let blocklist = self.blocklist();
+                let listings = get_current_listings(
+                    self.read_collection().primary(),
+                    &self.config.sort,
+                    &blocklist,
+                    self.config.mongo.listings_visible_window(),
+                    self.config.snapshot.unconfirmed_window(),
+                )
+                .await?;
+                self.refresh_upload_hints(&listings).await;
+                Ok(listings)
             })
-            .options(IndexOptions::builder().expire_after(Duration::from_secs(3600 * 2)).build())
-            .build();
-
-        if let Err(e) = self.collection().create_index(listings_index_model.clone(), None).await {
-            // Check for IndexOptionsConflict (Error code 85)
-            let is_conflict = match &*e.kind {
-                mongodb::error::ErrorKind::Command(cmd_err) => cmd_err.code == 85,
-                _ => false,
-            };
-
-            if is_conflict {
-                tracing::warn!("Index option conflict detected for 'updated_at'. Dropping old index and recreating...");
-                self.collection().drop_index("updated_at_1", None).await
-                    .context("could not drop conflicting updated_at index")?;
-                
-                self.collection().create_index(listings_index_model, None).await
-                    .context("could not create updated_at index after restart")?;
-                tracing::info!("Index 'updated_at' recreated with new options.");
-            } else {
-                return Err(e).context("could not create updated_at index");
+            .await
+    }
+
+    /// 다시 조회한 현재 모집글로 업로드 힌트 갱신 (미확인 플레이어 / 상세 정보가 확인된 모집글)
+    ///
+    /// HTML / API / 웹소켓 모두 같은 스냅샷을 쓰므로 어느 쪽으로 조회하든 힌트가 채워집니다.
+    pub(crate) async fn refresh_upload_hints(&self, listings: &[QueriedListing]) {
+        self.coverage.observe(listings, Instant::now());
+
+        match self.players_by_content_ids(&handlers::collect_content_ids(listings)).await {
+            Ok(players) => {
+                let known: HashSet<u64> = players.iter().map(|player| player.content_id).collect();
+                self.pending_players.replace(listings, |id| known.contains(&id));
             }
+            Err(e) => tracing::warn!("Could not look up players for upload hints: {:#}", e),
+        }
+    }
+
+    /// `filter` 조건에 맞는 활성 모집글 (조건이 없으면 `current_listings`, 있으면 캐시 없이 조회)
+    pub async fn filtered_listings(&self, filter: &ListingFilter) -> Result<Arc<Vec<QueriedListing>>> {
+        if filter.is_empty() {
+            return self.current_listings().await;
         }
 
-        // Parse collection indexes
-        self.parse_collection()
-            .create_index(
-                IndexModel::builder()
-                    .keys(mongodb::bson::doc! {
-                        "content_id": 1,
+        get_filtered_listings(
+            self.read_collection().primary(),
+            &self.config.sort,
+            &self.blocklist(),
+            self.config.mongo.listings_visible_window(),
+            self.config.snapshot.unconfirmed_window(),
+            filter,
+        )
+        .await
+        .map(Arc::new)
+    }
+
+    /// ID로 활성 모집글 하나 조회 (`/api/listings/{id}`, 캐시 없이 조회)
+    pub async fn listing_by_id(&self, id: u32, created_world: Option<u16>) -> Result<Option<QueriedListing>> {
+        get_listing_by_id(
+            self.read_collection().primary(),
+            &self.config.sort,
+            &self.blocklist(),
+            self.config.mongo.listings_visible_window(),
+            self.config.snapshot.unconfirmed_window(),
+            id,
+            created_world,
+        )
+        .await
+    }
+
+    pub fn listings_revision(&self) -> u64 {
+        self.listings_revision.load(Ordering::Relaxed)
+    }
+
+    /// 모집글 응답 내용이 바뀜 (`/api/listings` ETag 갱신)
+    pub fn bump_listings_revision(&self) {
+        self.listings_revision.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 현재 적용 중인 숨김 목록
+    pub fn blocklist(&self) -> Blocklist {
+        self.blocklist.read().unwrap().clone()
+    }
+
+    /// 이름으로 플레이어 검색 (새 데이터베이스만)
+    pub async fn players_by_name(&self, name: &str, home_world: Option<u16>, limit: i64) -> Result<Vec<Player>> {
+        crate::mongo::get_players_by_name(self.players_read_collection().primary(), name, home_world, limit).await
+    }
+
+    /// Content ID로 플레이어 조회 (최근에 없던 플레이어는 조회하지 않음)
+    pub async fn players_by_content_ids(&self, content_ids: &[u64]) -> Result<Vec<Player>> {
+        self.missing_players
+            .lookup(content_ids, |ids| async move {
+                let found = self
+                    .players_read_collection()
+                    .read_by_ids(&ids, |collection, ids| async move {
+                        let players = get_players_by_content_ids(collection, &ids).await?;
+                        Ok(players.into_iter().map(|player| (player.content_id, player)).collect())
                     })
-                    .options(IndexOptions::builder().unique(true).build())
-                    .build(),
-                None,
-            )
+                    .await?;
+                Ok(found.into_values().collect())
+            })
             .await
-            .context("could not create parse index")?;
+    }
 
-        Ok(())
+    /// 플레이어가 올린 모집글 (보관 기록 + 아직 남아 있는 모집글, 최근에 만든 순)
+    pub async fn hosted_listings(&self, content_id: u64, limit: usize) -> Result<Vec<HostedListing>> {
+        let archived = crate::mongo::archived_listings_by_host(self.archive_collection(), content_id, limit).await?;
+        let live = crate::mongo::live_listings_by_host(self.read_collection().primary(), content_id).await?;
+        Ok(hosted_listings(content_id, archived, &live, limit))
     }
 
-    pub fn collection(&self) -> Collection<ListingContainer> {
-        self.mongo.database("rpf").collection("listings")
+    /// 캐시된 통계 (오래됐으면 그대로 돌려주고 백그라운드 갱신을 요청)
+    pub async fn cached_stats(&self) -> Option<CachedStatistics> {
+        let stats = self.stats.read().await.clone()?;
+        if self.stats_refresh.request_if_stale(&stats, chrono::Utc::now()) {
+            tracing::info!("Stats from {} are stale, refreshing in the background", stats.generated_at());
+        }
+        Some(stats)
     }
 
-    pub fn players_collection(&self) -> Collection<Player> {
-        self.mongo.database("rpf").collection("players")
+    /// 듀티 하나의 통계 (`duty_stats`를 거침, 듀티 ID는 호출하는 쪽에서 확인)
+    pub async fn duty_stats(&self, duty: u16, seven_days: bool) -> Result<Arc<crate::stats::Statistics>> {
+        self.duty_stats
+            .get_or_load(duty_stats::DutyStatsKey { duty, seven_days }, || {
+                crate::stats::get_duty_stats(self, duty, seven_days)
+            })
+            .await
     }
 
-    pub fn parse_collection(&self) -> Collection<crate::mongo::ParseCacheDoc> {
-        self.mongo.database("rpf").collection("parses")
+    /// 현재 모집글 요약 (`summary`를 거침, 숨긴 듀티 / 카테고리 제외)
+    pub async fn listing_summary(&self) -> Result<Arc<crate::stats::summary::ListingSummary>> {
+        self.summary
+            .get_or_load(|| async {
+                let counts = crate::mongo::listing_summary(
+                    self.read_collection().primary(),
+                    &self.blocklist(),
+                    self.config.mongo.listings_visible_window(),
+                    self.config.snapshot.unconfirmed_window(),
+                    crate::stats::summary::SUMMARY_TOP_DUTIES,
+                )
+                .await?;
+                Ok(crate::stats::summary::ListingSummary::new(counts, chrono::Utc::now()))
+            })
+            .await
+    }
+
+    /// 역할별 빈 자리 기록 (이름 변경 전 데이터베이스에는 기록하지 않음)
+    pub fn role_demand_collection(&self) -> Collection<RoleDemandSample> {
+        self.database().collection("role_demand")
+    }
+
+    /// 날짜별 모집글 수 롤업 (이름 변경 전 데이터베이스에는 기록하지 않음)
+    pub fn daily_listings_collection(&self) -> Collection<DailyCount> {
+        self.database().collection("daily_listings")
+    }
+
+    /// TTL 없이 남기는 모집글 기록 (통계 집계 대상, 이름 변경 전 데이터베이스에는 기록하지 않음)
+    pub fn archive_collection(&self) -> Collection<mongodb::bson::Document> {
+        self.database().collection("listings_archive")
+    }
+
+    /// 데이터셋으로 내보낼 모집글 복사본 (모집글 컬렉션과 같은 데이터베이스, `$merge` 대상)
+    pub fn export_staging_collection(&self) -> Collection<ListingContainer> {
+        self.database().collection(crate::mongo::EXPORT_STAGING_COLLECTION)
+    }
+
+    /// 모더레이터 숨김 / 해제 기록 (이름 변경 전 데이터베이스에는 기록하지 않음)
+    pub fn moderation_log_collection(&self) -> Collection<ModerationRecord> {
+        self.database().collection("moderation_log")
+    }
+
+    /// 사용 중인 데이터베이스
+    pub fn database(&self) -> Database {
+        self.mongo.database(&self.config.mongo.database)
+    }
+
+    /// 복사 중인 이전 데이터베이스
+    pub fn legacy_database(&self) -> Option<Database> {
+        self.config.mongo.legacy_database().map(|name| self.mongo.database(name))
+    }
+
+    pub fn collection(&self) -> &Mirrored<Collection<ListingContainer>> {
+        &self.listings
+    }
+
+    pub fn players_collection(&self) -> &Mirrored<Collection<Player>> {
+        &self.players
+    }
+
+    pub fn parse_collection(&self) -> &Mirrored<Collection<ParseCacheDoc>> {
+        &self.parses
+    }
+
+    /// 읽기 전용 모집글 조회 (목록, 내보내기)
+    pub fn read_collection(&self) -> &Mirrored<Collection<ListingContainer>> {
+        &self.listing_reads
+    }
+
+    /// 읽기 전용 플레이어 조회 (화면 / API 표시)
+    pub fn players_read_collection(&self) -> &Mirrored<Collection<Player>> {
+        &self.player_reads
+    }
+
+    /// 읽기 전용 Parse 조회 (화면 / API 표시, 조회 주기 판단은 `parse_collection`)
+    pub fn parse_read_collection(&self) -> &Mirrored<Collection<ParseCacheDoc>> {
+        &self.parse_reads
+    }
+}
+
+impl readiness::StartupStore for State {
+    async fn ping(&self) -> Result<()> {
+        self.database()
+            .run_command(mongodb::bson::doc! { "ping": 1 }, None)
+            .await
+            .context("could not ping mongodb")?;
+        Ok(())
+    }
+
+    async fn load_secrets(&self) -> Result<()> {
+        if self.uploader_salt.get().is_none() {
+            let secret = fingerprint::load_or_create_secret(&self.database()).await?;
+            self.uploader_salt.set(&secret);
+        }
+        Ok(())
+    }
+
+    async fn ensure_indexes(&self) -> Result<()> {
+        let horizon = Duration::from_secs(u64::from(self.config.role_demand.horizon_days) * 24 * 3600);
+        let expected = expected_indexes(&self.config.mongo, horizon);
+        let database = self.database();
+
+        let mut skipped = Vec::new();
+        for index in &expected {
+            if !ensure_index(&database, index, self.config.indexes.required).await? {
+                skipped.push(format!("{}.{}", index.collection, index.name()));
+            }
+        }
+        if !skipped.is_empty() {
+            tracing::warn!(
+                "Starting without indexes that could not be created (indexes.required = false): {}",
+                skipped.join(", ")
+            );
+        }
+
+        let report = check_indexes(&database, &expected).await;
+        if report.drift {
+            tracing::warn!("Indexes differ from the expected set: {}", report.drifted().join(", "));
+        }
+        self.indexes.record(report);
+
+        Ok(())
     }
 }
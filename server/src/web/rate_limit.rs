@@ -0,0 +1,121 @@
+//! 업로드 주소별 요청 제한
+//!
+//! 클라이언트 주소(IP)마다 토큰 버킷을 두고 업로드 요청마다 토큰 하나를 씁니다.
+//! 버킷은 `contribute_burst`개에서 시작해 분당 `contribute_per_minute`개씩 다시 채워지고,
+//! 비어 있으면 다음 토큰이 생길 때까지 `429 Too Many Requests`와 `Retry-After`로 응답합니다.
+//! 가득 찬 버킷은 처음 만든 버킷과 같으므로 `prune`으로 주기적으로 지웁니다.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use warp::http::{header, StatusCode};
+use warp::{Filter, Rejection, Reply};
+
+use crate::config::RateLimit as RateLimitConfig;
+use super::fingerprint;
+use super::State;
+
+/// 다 채워진 버킷을 지우는 간격
+pub const PRUNE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+pub struct RateLimiter {
+    /// 초당 채워지는 토큰 수 (0이면 제한하지 않음)
+    per_second: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        let per_minute = config.contribute_per_minute;
+        Self {
+            per_second: f64::from(per_minute) / 60.0,
+            burst: f64::from(config.contribute_burst.unwrap_or(per_minute).max(1)),
+            buckets: Default::default(),
+        }
+    }
+
+    /// 토큰 하나 사용 (없으면 다음 토큰까지 남은 시간)
+    pub fn check(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        if self.per_second <= 0.0 {
+            return Ok(());
+        }
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        bucket.tokens = self.refilled(bucket, now);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.per_second))
+        }
+    }
+
+    /// 다 채워진 버킷 삭제 (지운 개수)
+    pub fn prune(&self, now: Instant) -> usize {
+        let mut buckets = self.buckets.lock().unwrap();
+        let before = buckets.len();
+        buckets.retain(|_, bucket| self.refilled(bucket, now) < self.burst);
+        before - buckets.len()
+    }
+
+    /// 추적 중인 주소 수
+    pub fn len(&self) -> usize {
+        self.buckets.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn refilled(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * self.per_second).min(self.burst)
+    }
+}
+
+/// 요청 제한 초과
+#[derive(Debug)]
+pub struct RateLimited {
+    pub retry_after: Duration,
+}
+
+impl warp::reject::Reject for RateLimited {}
+
+/// 업로드 요청 제한 필터 (주소를 알 수 없는 요청은 제한하지 않음)
+pub fn contribute(state: Arc<State>) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    fingerprint::client_address(&state.config.ratelimit)
+        .and_then(move |ip: Option<IpAddr>| {
+            let result = match ip {
+                Some(ip) => state.contribute_limiter.check(ip, Instant::now()),
+                None => Ok(()),
+            };
+            async move { result.map_err(|retry_after| warp::reject::custom(RateLimited { retry_after })) }
+        })
+        .untuple_one()
+}
+
+/// `RateLimited`를 `429` 응답으로 변환 (`Retry-After`는 올림한 초, 최소 1초)
+pub async fn too_many_requests(rejection: Rejection) -> Result<warp::reply::Response, Rejection> {
+    let Some(limited) = rejection.find::<RateLimited>() else {
+        return Err(rejection);
+    };
+
+    let retry_after = limited.retry_after.as_secs_f64().ceil().max(1.0) as u64;
+    let reply = warp::reply::json(&serde_json::json!({ "error": "too many requests" }));
+    let reply = warp::reply::with_status(reply, StatusCode::TOO_MANY_REQUESTS);
+    Ok(warp::reply::with_header(reply, header::RETRY_AFTER, retry_after.to_string()).into_response())
+}
@@ -0,0 +1,197 @@
+//! 시작 준비 상태
+//!
+//! 인덱스 생성, Mongo 연결 확인, 첫 통계 계산이 끝나기 전에는 요청이 느리거나
+//! 통계 페이지가 비어 있으므로, 로드 밸런서가 `/readyz`로 준비 여부를 확인합니다.
+//!
+//! `web.wait_for_ready`를 켜면 준비될 때까지 같은 주소에서 `/readyz`만 제공하고
+//! (나머지 경로는 503), 준비되면 전체 라우터로 교체합니다.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio::sync::Notify;
+use warp::filters::BoxedFilter;
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
+
+/// 초기화 실패 시 다시 시도하기까지 대기 시간
+pub const STARTUP_RETRY: Duration = Duration::from_secs(5);
+
+pub struct Readiness {
+    mongo: AtomicBool,
+    indexes: AtomicBool,
+    stats: AtomicBool,
+    /// 이 시각이 지나면 통계 캐시 없이도 준비 완료
+    grace_deadline: Instant,
+    changed: Notify,
+}
+
+/// `/readyz` 응답
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub mongo: bool,
+    pub indexes: bool,
+    pub stats: bool,
+    pub grace_elapsed: bool,
+}
+
+impl Readiness {
+    pub fn new(stats_grace: Duration) -> Self {
+        Self {
+            mongo: AtomicBool::new(false),
+            indexes: AtomicBool::new(false),
+            stats: AtomicBool::new(false),
+            grace_deadline: Instant::now() + stats_grace,
+            changed: Notify::new(),
+        }
+    }
+
+    pub fn mark_mongo_ready(&self) {
+        self.mark(&self.mongo);
+    }
+
+    pub fn mark_indexes_ready(&self) {
+        self.mark(&self.indexes);
+    }
+
+    pub fn mark_stats_ready(&self) {
+        self.mark(&self.stats);
+    }
+
+    fn mark(&self, flag: &AtomicBool) {
+        flag.store(true, Ordering::SeqCst);
+        self.changed.notify_waiters();
+    }
+
+    pub fn report(&self, now: Instant) -> ReadinessReport {
+        let mongo = self.mongo.load(Ordering::SeqCst);
+        let indexes = self.indexes.load(Ordering::SeqCst);
+        let stats = self.stats.load(Ordering::SeqCst);
+        let grace_elapsed = now >= self.grace_deadline;
+
+        ReadinessReport {
+            ready: mongo && indexes && (stats || grace_elapsed),
+            mongo,
+            indexes,
+            stats,
+            grace_elapsed,
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.report(Instant::now()).ready
+    }
+
+    /// 준비될 때까지 대기
+    pub async fn wait_ready(&self) {
+        loop {
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            let report = self.report(Instant::now());
+            if report.ready {
+                return;
+            }
+
+            // 유예 시간이 이미 지났으면 다른 항목이 바뀔 때까지만 대기
+            if report.grace_elapsed {
+                changed.await;
+            } else {
+                tokio::select! {
+                    _ = changed => {}
+                    _ = tokio::time::sleep_until(self.grace_deadline.into()) => {}
+                }
+            }
+        }
+    }
+}
+
+/// 준비 상태 확인에 필요한 저장소 작업
+pub trait StartupStore {
+    async fn ping(&self) -> Result<()>;
+    /// 설정에 없는 비밀값을 저장소에서 읽음 (없으면 만들어 저장)
+    async fn load_secrets(&self) -> Result<()>;
+    async fn ensure_indexes(&self) -> Result<()>;
+}
+
+/// Mongo 연결 확인, 비밀값 읽기와 인덱스 생성 (성공할 때까지 재시도)
+pub async fn initialise(readiness: &Readiness, store: &impl StartupStore, retry: Duration) {
+    while let Err(e) = store.ping().await {
+        tracing::warn!("MongoDB is not reachable yet: {:#}", e);
+        tokio::time::sleep(retry).await;
+    }
+    readiness.mark_mongo_ready();
+
+    while let Err(e) = store.load_secrets().await {
+        tracing::error!("could not load secrets: {:#}", e);
+        tokio::time::sleep(retry).await;
+    }
+
+    while let Err(e) = store.ensure_indexes().await {
+        tracing::error!("could not ensure indexes: {:#}", e);
+        tokio::time::sleep(retry).await;
+    }
+    readiness.mark_indexes_ready();
+    tracing::info!("MongoDB connection verified and indexes ready");
+}
+
+/// `GET /readyz`: 준비되면 200, 아니면 503
+pub fn readyz(readiness: Arc<Readiness>) -> BoxedFilter<(warp::reply::Response,)> {
+    warp::get()
+        .and(warp::path("readyz"))
+        .and(warp::path::end())
+        .map(move || {
+            let report = readiness.report(Instant::now());
+            let status = if report.ready {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            let reply = warp::reply::with_status(warp::reply::json(&report), status);
+            warp::reply::with_header(reply, "cache-control", "no-store").into_response()
+        })
+        .boxed()
+}
+
+/// 준비 전에 제공하는 가벼운 경로 (`/readyz` 외에는 503)
+fn startup_routes(readiness: Arc<Readiness>) -> BoxedFilter<(warp::reply::Response,)> {
+    let starting = warp::any().map(|| {
+        let reply = warp::reply::with_status("starting up", StatusCode::SERVICE_UNAVAILABLE);
+        warp::reply::with_header(reply, "retry-after", "5").into_response()
+    });
+
+    readyz(readiness).or(starting).unify().boxed()
+}
+
+/// 준비될 때까지 가벼운 경로만 제공한 뒤, 같은 주소에서 전체 라우터를 제공
+///
+/// 실제로 바인딩한 주소와 서버 future를 반환합니다 (준비 후 다시 바인딩하지 못하면 future가 에러로 끝남).
+/// 교체 순간 잠깐 연결이 거부될 수 있지만, 로드 밸런서는 그 전에 이미 `/readyz`로 준비를 확인합니다.
+pub fn bind_after_ready<R: Reply + 'static>(
+    readiness: Arc<Readiness>,
+    router: BoxedFilter<(R,)>,
+    host: SocketAddr,
+) -> Result<(SocketAddr, impl Future<Output = Result<()>>)> {
+    let ready = Arc::clone(&readiness);
+    let (addr, startup) = warp::serve(startup_routes(readiness))
+        .try_bind_with_graceful_shutdown(host, async move { ready.wait_ready().await })?;
+
+    let server = async move {
+        startup.await;
+        tracing::info!("ready, serving all routes at {}", addr);
+        let (_, server) = warp::serve(router)
+            .try_bind_ephemeral(addr)
+            .with_context(|| format!("could not bind {} after startup", addr))?;
+        server.await;
+        Ok(())
+    };
+
+    Ok((addr, server))
+}
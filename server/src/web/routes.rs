@@ -1,23 +1,37 @@
 
+use serde::Deserialize;
 use std::sync::Arc;
 use warp::{filters::BoxedFilter, http::Uri, Filter, Reply};
 
+use crate::api::ApiShape;
+use crate::ffxiv::Language;
 use crate::listing::PartyFinderListing;
 use crate::player::UploadablePlayer;
+use super::fingerprint;
 use super::handlers;
+use super::job_icons;
+use super::rate_limit;
+use super::upload_auth;
+use super::upload_body;
 use super::State;
 
 pub fn router(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
     index()
+        .or(super::readiness::readyz(Arc::clone(&state.readiness)))
         .or(listings(Arc::clone(&state)))
-        .or(contribute(Arc::clone(&state)))
-        .or(contribute_multiple(Arc::clone(&state)))
-        .or(contribute_players(Arc::clone(&state)))
-        .or(contribute_detail(Arc::clone(&state)))
+        .or(contribute_routes(Arc::clone(&state)))
         .or(stats(Arc::clone(&state)))
         .or(stats_seven_days(Arc::clone(&state)))
+        .or(stats_duty(Arc::clone(&state)))
+        .or(stats_seven_days_duty(Arc::clone(&state)))
+        .or(status(Arc::clone(&state)))
+        .or(super::admin_page::admin_page(Arc::clone(&state)))
         .or(assets())
         .or(crate::api::api(Arc::clone(&state)))
+        .or(crate::feeds::feeds(Arc::clone(&state)))
+        .or(super::metrics::public_metrics(Arc::clone(&state)))
+        .with(crate::version::header())
+        .with(super::metrics::record(state))
         .boxed()
 }
 
@@ -26,18 +40,42 @@ fn index() -> BoxedFilter<(impl Reply,)> {
     warp::get().and(route).boxed()
 }
 
+/// 표시 언어 결정
+///
+/// `lang` 쿠키가 지원 언어이면 그대로 사용하고, 아니면 `Accept-Language` 헤더로 협상합니다.
+pub(super) fn language() -> BoxedFilter<(Language,)> {
+    warp::cookie::optional::<String>("lang")
+        .and(warp::header::optional::<String>("accept-language"))
+        .map(|cookie: Option<String>, accept: Option<String>| {
+            cookie
+                .as_deref()
+                .and_then(Language::from_code)
+                .unwrap_or_else(|| Language::from_codes(accept.as_deref()))
+        })
+        .boxed()
+}
+
+#[derive(Deserialize)]
+struct ListingsQuery {
+    /// 익명 북마크 토큰
+    watch: Option<String>,
+    /// `extended`면 All Stars 점수 / 순위 표시
+    #[serde(default)]
+    shape: ApiShape,
+    /// 이 서버에서 만든 모집글만 표시 (예: `Tonberry`)
+    world: Option<String>,
+    /// 이 데이터 센터에서 만든 모집글만 표시 (예: `Elemental`)
+    dc: Option<String>,
+}
+
 fn listings(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
     let route = warp::path("listings")
         .and(warp::path::end())
-        .and(
-            warp::cookie::<String>("lang")
-                .or(warp::header::<String>("accept-language"))
-                .unify()
-                .map(Some)
-                .or(warp::any().map(|| None))
-                .unify(),
-        )
-        .and_then(move |codes: Option<String>| handlers::listings_handler(Arc::clone(&state), codes));
+        .and(language())
+        .and(warp::query::<ListingsQuery>())
+        .and_then(move |lang: Language, query: ListingsQuery| {
+            handlers::listings_handler(Arc::clone(&state), lang, query.watch, query.shape, query.world, query.dc)
+        });
 
     warp::get().and(route).boxed()
 }
@@ -45,15 +83,8 @@ fn listings(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
 fn stats(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
     let route = warp::path("stats")
         .and(warp::path::end())
-        .and(
-            warp::cookie::<String>("lang")
-                .or(warp::header::<String>("accept-language"))
-                .unify()
-                .map(Some)
-                .or(warp::any().map(|| None))
-                .unify(),
-        )
-        .and_then(move |codes: Option<String>| handlers::stats_handler(Arc::clone(&state), codes, false));
+        .and(language())
+        .and_then(move |lang: Language| handlers::stats_handler(Arc::clone(&state), lang, false));
 
     warp::get().and(route).boxed()
 }
@@ -62,24 +93,57 @@ fn stats_seven_days(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
     let route = warp::path("stats")
         .and(warp::path("7days"))
         .and(warp::path::end())
-        .and(
-            warp::cookie::<String>("lang")
-                .or(warp::header::<String>("accept-language"))
-                .unify()
-                .map(Some)
-                .or(warp::any().map(|| None))
-                .unify(),
-        )
-        .and_then(move |codes: Option<String>| handlers::stats_handler(Arc::clone(&state), codes, true));
+        .and(language())
+        .and_then(move |lang: Language| handlers::stats_handler(Arc::clone(&state), lang, true));
+
+    warp::get().and(route).boxed()
+}
+
+fn stats_duty(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    let route = warp::path!("stats" / "duty" / u16)
+        .and(language())
+        .and_then(move |duty: u16, lang: Language| handlers::duty_stats_handler(Arc::clone(&state), lang, duty, false));
 
     warp::get().and(route).boxed()
 }
 
+fn stats_seven_days_duty(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    let route = warp::path!("stats" / "7days" / "duty" / u16)
+        .and(language())
+        .and_then(move |duty: u16, lang: Language| handlers::duty_stats_handler(Arc::clone(&state), lang, duty, true));
+
+    warp::get().and(route).boxed()
+}
+
+fn status(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    let route = warp::path("status")
+        .and(warp::path::end())
+        .and(language())
+        .and_then(move |lang: Language| handlers::status_handler(Arc::clone(&state), lang));
+
+    warp::get().and(route).boxed()
+}
+
+/// 업로드 경로 (업로드 토큰이 틀리면 `401`, 시작 중이면 `503`, 주소별 요청 제한을 넘으면 `429`, 본문이 크거나 틀리면 JSON `413` / `400`)
+fn contribute_routes(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
+    contribute(Arc::clone(&state))
+        .or(contribute_multiple(Arc::clone(&state)))
+        .or(contribute_players(Arc::clone(&state)))
+        .or(contribute_detail(state))
+        .recover(upload_auth::unauthorized)
+        .recover(fingerprint::not_ready)
+        .recover(rate_limit::too_many_requests)
+        .recover(upload_body::invalid_body)
+        .boxed()
+}
+
 fn contribute(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
     let route = warp::path("contribute")
         .and(warp::path::end())
-        .and(warp::body::json())
-        .and_then(move |listing: PartyFinderListing| handlers::contribute_handler(Arc::clone(&state), listing));
+        .and(fingerprint::uploader(Arc::clone(&state)))
+        .and(rate_limit::contribute(Arc::clone(&state)))
+        .and(upload_body::json(state.config.body_limits.contribute_kb))
+        .and_then(move |uploader: String, listing: PartyFinderListing| handlers::contribute_handler(Arc::clone(&state), listing, uploader));
     warp::post().and(route).boxed()
 }
 
@@ -87,8 +151,12 @@ fn contribute_multiple(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
     let route = warp::path("contribute")
         .and(warp::path("multiple"))
         .and(warp::path::end())
-        .and(warp::body::json())
-        .and_then(move |listings: Vec<PartyFinderListing>| handlers::contribute_multiple_handler(Arc::clone(&state), listings));
+        .and(fingerprint::authenticated_uploader(Arc::clone(&state)))
+        .and(rate_limit::contribute(Arc::clone(&state)))
+        .and(upload_body::json(state.config.body_limits.multiple_kb))
+        .and_then(move |token: Option<String>, uploader: String, upload: handlers::MultipleUpload| {
+            handlers::contribute_multiple_handler(Arc::clone(&state), upload, uploader, token.is_some())
+        });
     warp::post().and(route).boxed()
 }
 
@@ -96,8 +164,10 @@ fn contribute_players(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
     let route = warp::path("contribute")
         .and(warp::path("players"))
         .and(warp::path::end())
-        .and(warp::body::json())
-        .and_then(move |players: Vec<UploadablePlayer>| handlers::contribute_players_handler(Arc::clone(&state), players));
+        .and(fingerprint::uploader(Arc::clone(&state)))
+        .and(rate_limit::contribute(Arc::clone(&state)))
+        .and(upload_body::json(state.config.body_limits.players_kb))
+        .and_then(move |uploader: String, players: Vec<UploadablePlayer>| handlers::contribute_players_handler(Arc::clone(&state), players, uploader));
     warp::post().and(route).boxed()
 }
 
@@ -105,8 +175,10 @@ fn contribute_detail(state: Arc<State>) -> BoxedFilter<(impl Reply,)> {
     let route = warp::path("contribute")
         .and(warp::path("detail"))
         .and(warp::path::end())
-        .and(warp::body::json())
-        .and_then(move |detail: handlers::UploadablePartyDetail| handlers::contribute_detail_handler(Arc::clone(&state), detail));
+        .and(fingerprint::uploader(Arc::clone(&state)))
+        .and(rate_limit::contribute(Arc::clone(&state)))
+        .and(upload_body::json(state.config.body_limits.detail_kb))
+        .and_then(move |uploader: String, detail: handlers::UploadablePartyDetail| handlers::contribute_detail_handler(Arc::clone(&state), detail, uploader));
     warp::post().and(route).boxed()
 }
 
@@ -115,12 +187,15 @@ fn assets() -> BoxedFilter<(impl Reply,)> {
         .and(warp::path("assets"))
         .and(
             icons()
+                .or(job_icons::job_icon(&job_icons::JOB_ICONS))
                 .or(minireset())
                 .or(common_css())
                 .or(listings_css())
                 .or(listings_js())
                 .or(stats_css())
                 .or(stats_js())
+                .or(status_css())
+                .or(admin_css())
                 .or(d3())
                 .or(pico())
                 .or(common_js())
@@ -172,6 +247,20 @@ fn stats_css() -> BoxedFilter<(impl Reply,)> {
         .boxed()
 }
 
+fn status_css() -> BoxedFilter<(impl Reply,)> {
+    warp::path("status.css")
+        .and(warp::path::end())
+        .and(warp::fs::file("./assets/status.css"))
+        .boxed()
+}
+
+fn admin_css() -> BoxedFilter<(impl Reply,)> {
+    warp::path("admin.css")
+        .and(warp::path::end())
+        .and(warp::fs::file("./assets/admin.css"))
+        .boxed()
+}
+
 fn stats_js() -> BoxedFilter<(impl Reply,)> {
     warp::path("stats.js")
         .and(warp::path::end())
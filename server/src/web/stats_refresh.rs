@@ -0,0 +1,82 @@
+//! 통계 캐시 갱신 요청
+//!
+//! 오래된 통계를 받은 요청은 기다리지 않고 가진 통계로 바로 응답하며,
+//! 통계 태스크에 갱신을 한 번만 요청합니다 (계산 중에 들어온 요청은 무시).
+//! 계산이 실패하면 정기 간격 대신 짧은 간격부터 두 배씩 늘려 가며 다시 시도합니다.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use tokio::sync::Notify;
+
+use crate::stats::CachedStatistics;
+
+/// 통계 계산 실패 후 첫 재시도까지 대기 시간 (연속 실패마다 두 배)
+pub const STATS_RETRY_MIN: Duration = Duration::from_secs(5 * 60);
+
+/// 재시도 대기 시간 상한
+pub const STATS_RETRY_MAX: Duration = Duration::from_secs(60 * 60);
+
+/// 연속 `failures`번 실패한 뒤 재시도까지 대기 시간
+pub fn retry_delay(failures: u32) -> Duration {
+    let doublings = failures.saturating_sub(1).min(16);
+    STATS_RETRY_MIN.saturating_mul(1 << doublings).min(STATS_RETRY_MAX)
+}
+
+pub struct StatsRefresh {
+    max_age: TimeDelta,
+    /// 계산 중이거나 갱신이 요청됨
+    in_flight: AtomicBool,
+    notify: Notify,
+}
+
+impl StatsRefresh {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            max_age: TimeDelta::from_std(max_age).unwrap_or(TimeDelta::MAX),
+            in_flight: AtomicBool::new(false),
+            notify: Notify::new(),
+        }
+    }
+
+    pub fn is_stale(&self, generated_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        now - generated_at > self.max_age
+    }
+
+    /// 통계가 오래됐으면 갱신 요청 (새로 요청했으면 true)
+    pub fn request_if_stale(&self, stats: &CachedStatistics, now: DateTime<Utc>) -> bool {
+        self.is_stale(stats.generated_at(), now) && self.request()
+    }
+
+    /// 갱신 요청 (이미 계산 중이거나 요청된 상태면 false)
+    pub fn request(&self) -> bool {
+        if self.in_flight.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        self.notify.notify_one();
+        true
+    }
+
+    pub fn in_flight(&self) -> bool {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// 계산 시작 (끝날 때까지 들어온 요청은 이번 계산으로 처리)
+    pub fn begin(&self) {
+        self.in_flight.store(true, Ordering::SeqCst);
+    }
+
+    /// 계산 종료 (이후 요청은 다시 갱신을 시작)
+    pub fn finish(&self) {
+        self.in_flight.store(false, Ordering::SeqCst);
+    }
+
+    /// 다음 정기 계산 또는 갱신 요청까지 대기
+    pub async fn wait(&self, interval: Duration) {
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = self.notify.notified() => {}
+        }
+    }
+}
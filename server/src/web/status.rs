@@ -0,0 +1,234 @@
+//! 사용자용 상태 요약 (`/status`, `/api/status`)
+//!
+//! "사이트가 고장 났는지, 모집글이 없는 건지"를 확인할 수 있게 데이터 센터별 마지막 업로드,
+//! FFLogs 수집 상태, 통계 계산 시각, 점검 여부를 보여줍니다.
+//! 요청마다 DB를 조회하지 않고 다른 기능이 유지하는 메모리 상태만 사용합니다.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::maintenance::{MaintenanceStatus, PauseReason};
+use super::volume::VolumeAlert;
+
+/// 마지막 업로드가 이 시간보다 오래되면 업로드가 끊긴 것으로 표시
+pub const CONTRIBUTION_STALE_AFTER: Duration = Duration::from_secs(15 * 60);
+
+/// 마지막 FFLogs 수집 주기가 이 시간보다 오래되면 멈춘 것으로 표시 (주기 간격 1분)
+pub const FFLOGS_STALL_AFTER: Duration = Duration::from_secs(15 * 60);
+
+/// 반복 작업 한 주기의 시작 / 종료 기록
+#[derive(Debug, Default)]
+pub struct CycleTracker {
+    inner: Mutex<CycleSnapshot>,
+}
+
+/// `CycleTracker`의 현재 상태
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct CycleSnapshot {
+    /// 진행 중인 주기의 시작 시각
+    pub running_since: Option<DateTime<Utc>>,
+    /// 마지막으로 끝난 주기의 종료 시각
+    pub last_finished_at: Option<DateTime<Utc>>,
+    /// 마지막으로 끝난 주기의 소요 시간 (초)
+    pub last_duration_secs: Option<f64>,
+    pub last_ok: bool,
+}
+
+impl CycleTracker {
+    pub fn start(&self, now: DateTime<Utc>) {
+        self.inner.lock().unwrap().running_since = Some(now);
+    }
+
+    pub fn finish(&self, ok: bool, now: DateTime<Utc>) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(started) = inner.running_since.take() {
+            inner.last_duration_secs = Some((now - started).num_milliseconds() as f64 / 1000.0);
+        }
+        inner.last_finished_at = Some(now);
+        inner.last_ok = ok;
+    }
+
+    pub fn snapshot(&self) -> CycleSnapshot {
+        *self.inner.lock().unwrap()
+    }
+}
+
+/// 요약에 필요한 상태 (`State::status_inputs`가 모음)
+#[derive(Debug, Default)]
+pub struct StatusInputs {
+    /// 데이터 센터별 마지막 업로드 시각 (`CoverageTracker`)
+    pub contributions: HashMap<&'static str, DateTime<Utc>>,
+    /// 마지막 기록의 데이터 센터별 활성 모집글 수 (`VolumeMonitor`)
+    pub listing_counts: HashMap<&'static str, u64>,
+    pub volume_alerts: Vec<VolumeAlert>,
+    /// FFLogs 수집 주기 (클라이언트가 설정되지 않았으면 `None`)
+    pub fflogs: Option<CycleSnapshot>,
+    /// 통계 계산 시각 (아직 없으면 `None`)
+    pub stats_generated_at: Option<DateTime<Utc>>,
+    pub stats_refreshing: bool,
+    pub maintenance: Option<MaintenanceStatus>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContributionState {
+    /// 최근 업로드가 있음
+    Ok,
+    /// 마지막 업로드가 `CONTRIBUTION_STALE_AFTER`보다 오래됨
+    Stale,
+    /// 서버 시작 후 업로드가 없음
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataCentreStatus {
+    pub name: &'static str,
+    pub contribution: ContributionState,
+    pub last_contribution_at: Option<DateTime<Utc>>,
+    /// 마지막 업로드 후 지난 시간 (초)
+    pub last_contribution_secs: Option<i64>,
+    /// 활성 모집글 수 (아직 기록이 없으면 `None`)
+    pub listings: Option<u64>,
+    /// 모집글 수가 평소보다 크게 적음
+    pub volume_alert: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineState {
+    /// FFLogs가 설정되지 않음
+    Disabled,
+    /// 점검 중이라 쉬는 중
+    Paused,
+    /// 첫 주기가 아직 끝나지 않음
+    Starting,
+    Running,
+    /// 마지막 주기가 `FFLOGS_STALL_AFTER`보다 오래됨
+    Stalled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FFLogsStatus {
+    pub state: PipelineState,
+    #[serde(flatten)]
+    pub cycle: CycleSnapshot,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsStatus {
+    pub generated_at: Option<DateTime<Utc>>,
+    /// 통계 계산 후 지난 시간 (초)
+    pub age_secs: Option<i64>,
+    pub refreshing: bool,
+}
+
+/// 페이지 상단에 표시하는 안내
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Banner {
+    Maintenance {
+        reason: PauseReason,
+        until: Option<DateTime<Utc>>,
+    },
+    LowVolume {
+        data_centre: &'static str,
+        since: DateTime<Utc>,
+    },
+}
+
+/// `/status` 페이지와 `/api/status` 응답
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusSummary {
+    pub generated_at: DateTime<Utc>,
+    pub banners: Vec<Banner>,
+    pub data_centres: Vec<DataCentreStatus>,
+    pub fflogs: FFLogsStatus,
+    pub stats: StatsStatus,
+}
+
+impl StatusSummary {
+    pub fn build(inputs: StatusInputs, data_centres: &BTreeSet<&'static str>, now: DateTime<Utc>) -> Self {
+        let age = |at: DateTime<Utc>| (now - at).num_seconds().max(0);
+        let paused = inputs.maintenance.as_ref().and_then(|maintenance| maintenance.reason);
+
+        let mut banners = Vec::new();
+        if let Some(reason) = paused {
+            banners.push(Banner::Maintenance {
+                reason,
+                until: inputs.maintenance.as_ref().and_then(|maintenance| maintenance.scheduled_until),
+            });
+        }
+        banners.extend(inputs.volume_alerts.iter().map(|alert| Banner::LowVolume {
+            data_centre: alert.data_centre,
+            since: alert.since,
+        }));
+
+        let data_centres = data_centres
+            .iter()
+            .map(|&name| {
+                let last = inputs.contributions.get(name).copied();
+                let contribution = match last {
+                    None => ContributionState::Unknown,
+                    Some(at) if age(at) as u64 > CONTRIBUTION_STALE_AFTER.as_secs() => ContributionState::Stale,
+                    Some(_) => ContributionState::Ok,
+                };
+                DataCentreStatus {
+                    name,
+                    contribution,
+                    last_contribution_at: last,
+                    last_contribution_secs: last.map(age),
+                    listings: inputs.listing_counts.get(name).copied(),
+                    volume_alert: inputs.volume_alerts.iter().any(|alert| alert.data_centre == name),
+                }
+            })
+            .collect();
+
+        let fflogs = match inputs.fflogs {
+            None => FFLogsStatus {
+                state: PipelineState::Disabled,
+                cycle: CycleSnapshot::default(),
+            },
+            Some(cycle) => {
+                let latest = cycle.running_since.max(cycle.last_finished_at);
+                let state = if paused.is_some() {
+                    PipelineState::Paused
+                } else if cycle.last_finished_at.is_none() {
+                    PipelineState::Starting
+                } else if latest.is_some_and(|at| age(at) as u64 <= FFLOGS_STALL_AFTER.as_secs()) {
+                    PipelineState::Running
+                } else {
+                    PipelineState::Stalled
+                };
+                FFLogsStatus { state, cycle }
+            }
+        };
+
+        let stats = StatsStatus {
+            generated_at: inputs.stats_generated_at,
+            age_secs: inputs.stats_generated_at.map(age),
+            refreshing: inputs.stats_refreshing,
+        };
+
+        Self {
+            generated_at: now,
+            banners,
+            data_centres,
+            fflogs,
+            stats,
+        }
+    }
+}
+
+/// 지난 시간을 "3m ago" 형식으로 (페이지 표시용)
+pub fn human_age(secs: i64) -> String {
+    match secs {
+        0..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86_399 => format!("{}h {}m ago", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
@@ -0,0 +1,63 @@
+//! 현재 모집글 요약 짧은 캐시 (`/api/summary`)
+//!
+//! 봇이 몇 초마다 조회해도 집계는 `SUMMARY_TTL`에 한 번만 하도록 마지막 결과를 재사용합니다.
+//! 응답의 `generated_at`은 집계한 시각이므로 캐시된 결과도 얼마나 오래됐는지 알 수 있습니다.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::stats::summary::ListingSummary;
+
+/// 요약을 다시 집계하지 않고 쓰는 시간
+pub const SUMMARY_TTL: Duration = Duration::from_secs(5);
+
+pub struct SummaryCache {
+    ttl: Duration,
+    entry: Mutex<Option<(Instant, Arc<ListingSummary>)>>,
+}
+
+impl SummaryCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Default::default(),
+        }
+    }
+
+    /// 만료되지 않은 요약
+    pub fn get(&self, now: Instant) -> Option<Arc<ListingSummary>> {
+        self.entry
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|(stored_at, _)| now.saturating_duration_since(*stored_at) < self.ttl)
+            .map(|(_, summary)| Arc::clone(summary))
+    }
+
+    pub fn insert(&self, summary: ListingSummary, now: Instant) -> Arc<ListingSummary> {
+        let summary = Arc::new(summary);
+        *self.entry.lock().unwrap() = Some((now, Arc::clone(&summary)));
+        summary
+    }
+
+    /// 캐시된 요약을 반환하고, 없으면 `load`로 집계해 저장 (실패는 저장하지 않음)
+    pub async fn get_or_load<F, Fut>(&self, load: F) -> anyhow::Result<Arc<ListingSummary>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<ListingSummary>>,
+    {
+        if let Some(summary) = self.get(Instant::now()) {
+            return Ok(summary);
+        }
+
+        let summary = load().await?;
+        Ok(self.insert(summary, Instant::now()))
+    }
+}
+
+impl Default for SummaryCache {
+    fn default() -> Self {
+        Self::new(SUMMARY_TTL)
+    }
+}
@@ -0,0 +1,19 @@
+//! 비밀 토큰 비교
+//!
+//! `==`는 처음 다른 바이트에서 멈추므로 응답 시간으로 토큰을 한 글자씩 맞춰 볼 수 있습니다.
+//! 두 값을 HMAC으로 바꾼 뒤 `verify_slice`(상수 시간 비교)로 확인합니다.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 설정된 토큰 `expected`와 요청의 `provided`가 같은지 (상수 시간 비교)
+pub fn tokens_match(expected: &str, provided: &str) -> bool {
+    let mac = |value: &str| {
+        let mut mac = HmacSha256::new_from_slice(expected.as_bytes()).expect("HMAC accepts keys of any length");
+        mac.update(value.as_bytes());
+        mac
+    };
+    mac(provided).verify_slice(&mac(expected).finalize().into_bytes()).is_ok()
+}
@@ -0,0 +1,68 @@
+//! 업로드 토큰 인증
+//!
+//! `[auth]`에 토큰이 있으면 업로드(`/contribute` 경로)에 `Authorization: Bearer <token>`이 필요합니다.
+//! 토큰이 없으면 인증하지 않으므로 기존 배포는 그대로 동작합니다.
+//! 업로드마다 토큰 이름을 로그에 남겨 이상한 업로드를 보낸 쪽을 찾을 수 있게 합니다.
+
+use std::sync::Arc;
+
+use warp::http::StatusCode;
+use warp::path::FullPath;
+use warp::{Filter, Rejection, Reply};
+
+use crate::config::Auth;
+use crate::web::token_compare::tokens_match;
+use super::State;
+
+/// 업로드 토큰이 없거나 틀림
+#[derive(Debug)]
+pub struct InvalidUploadToken;
+
+impl warp::reject::Reject for InvalidUploadToken {}
+
+/// `Authorization` 헤더 확인
+///
+/// 인증하지 않으면 `Ok(None)`, 맞는 토큰이면 그 토큰의 이름입니다.
+pub fn authorize<'a>(auth: Option<&'a Auth>, authorization: Option<&str>) -> Result<Option<&'a str>, InvalidUploadToken> {
+    let tokens = match auth {
+        Some(auth) if !auth.tokens.is_empty() => &auth.tokens,
+        _ => return Ok(None),
+    };
+
+    let provided = authorization
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .filter(|token| !token.is_empty())
+        .ok_or(InvalidUploadToken)?;
+    tokens
+        .iter()
+        .find(|token| tokens_match(&token.token, provided))
+        .map(|token| Some(token.name.as_str()))
+        .ok_or(InvalidUploadToken)
+}
+
+/// 업로드 토큰 확인 필터 (맞는 토큰의 이름, 인증하지 않으면 `None`)
+pub fn token(state: Arc<State>) -> impl Filter<Extract = (Option<String>,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("authorization")
+        .and(warp::path::full())
+        .and_then(move |authorization: Option<String>, path: FullPath| {
+            let result = match authorize(state.config.auth.as_ref(), authorization.as_deref()) {
+                Ok(Some(name)) => {
+                    tracing::info!("contribution to {} with upload token {}", path.as_str(), name);
+                    Ok(Some(name.to_string()))
+                }
+                Ok(None) => Ok(None),
+                Err(e) => Err(warp::reject::custom(e)),
+            };
+            async move { result }
+        })
+}
+
+/// `InvalidUploadToken`을 `401` 응답으로 변환
+pub async fn unauthorized(rejection: Rejection) -> Result<warp::reply::Response, Rejection> {
+    if rejection.find::<InvalidUploadToken>().is_none() {
+        return Err(rejection);
+    }
+
+    let reply = warp::reply::json(&serde_json::json!({ "error": "invalid upload token" }));
+    Ok(warp::reply::with_status(reply, StatusCode::UNAUTHORIZED).into_response())
+}
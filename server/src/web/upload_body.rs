@@ -0,0 +1,58 @@
+//! 업로드 본문 크기 제한과 JSON 오류 응답
+//!
+//! 업로드 경로마다 `[body_limits]`의 크기까지만 본문을 받고, 넘으면 읽기 전에 `413`으로 응답합니다.
+//! 본문을 해석하지 못하면 warp 기본 거절(텍스트 `400`) 대신 serde 오류 메시지와 위치를 담은
+//! JSON `400`으로 응답해 플러그인 개발자가 어떤 필드가 틀렸는지 알 수 있게 합니다.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use warp::http::StatusCode;
+use warp::hyper::body::Bytes;
+use warp::{Filter, Rejection, Reply};
+
+/// 본문을 해석하지 못함
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InvalidBody {
+    /// serde 오류 메시지 (예: ``missing field `id` at line 1 column 2``)
+    pub detail: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl warp::reject::Reject for InvalidBody {}
+
+impl From<serde_json::Error> for InvalidBody {
+    fn from(e: serde_json::Error) -> Self {
+        Self { detail: e.to_string(), line: e.line(), column: e.column() }
+    }
+}
+
+/// `limit_kb` 이하의 JSON 본문 (`Content-Length`가 없으면 `411`)
+pub fn json<T: DeserializeOwned + Send>(limit_kb: u64) -> impl Filter<Extract = (T,), Error = Rejection> + Clone {
+    warp::body::content_length_limit(limit_kb.saturating_mul(1024))
+        .and(warp::body::bytes())
+        .and_then(|body: Bytes| async move {
+            serde_json::from_slice(&body).map_err(|e| warp::reject::custom(InvalidBody::from(e)))
+        })
+}
+
+/// 본문 거절을 JSON 응답으로 변환 (`400` 해석 실패, `411` 길이 없음, `413` 크기 초과)
+pub async fn invalid_body(rejection: Rejection) -> Result<warp::reply::Response, Rejection> {
+    let (status, body) = if let Some(invalid) = rejection.find::<InvalidBody>() {
+        let body = serde_json::json!({
+            "error": "invalid JSON body",
+            "detail": invalid.detail,
+            "line": invalid.line,
+            "column": invalid.column,
+        });
+        (StatusCode::BAD_REQUEST, body)
+    } else if rejection.find::<warp::reject::PayloadTooLarge>().is_some() {
+        (StatusCode::PAYLOAD_TOO_LARGE, serde_json::json!({ "error": "body too large" }))
+    } else if rejection.find::<warp::reject::LengthRequired>().is_some() {
+        (StatusCode::LENGTH_REQUIRED, serde_json::json!({ "error": "content-length required" }))
+    } else {
+        return Err(rejection);
+    };
+
+    Ok(warp::reply::with_status(warp::reply::json(&body), status).into_response())
+}
@@ -0,0 +1,137 @@
+//! 업로드(`/contribute` 경로) 응답 본문
+//!
+//! 모든 응답은 `status`(`"ok"` / `"error"`)와 다음 업로드 힌트(`upload_hints`)를 가집니다.
+//! 실패하면 `reason`(`"invalid_listing"` / `"database_error"`)을 붙이고
+//! 검증 실패는 `problems`로 실패한 항목을 알려줍니다.
+//!
+//! ```json
+//! {"status":"ok","outcome":"inserted","upload_hints":{...}}
+//! {"status":"error","reason":"invalid_listing","problems":["unknown_duty"],"upload_hints":{...}}
+//! ```
+//!
+//! 일괄 업로드는 `results`에 모집글마다 같은 형태의 결과를 `id`와 함께 담습니다.
+
+use serde::Serialize;
+use warp::http::StatusCode;
+use warp::Reply;
+
+use crate::listing::{InvalidListing, ListingProblem};
+use crate::listing_container::UploadOutcome;
+use super::hints::UploadHints;
+
+/// 업로드 응답
+#[derive(Debug, Serialize)]
+pub struct ContributeResponse<T> {
+    #[serde(flatten)]
+    pub result: UploadStatus<T>,
+    pub upload_hints: UploadHints,
+}
+
+impl<T: Serialize + Send> ContributeResponse<T> {
+    /// 결과에 맞는 상태 코드 (`200` / `400` / `500`)와 함께 응답
+    pub fn into_reply(self) -> warp::reply::Response {
+        let status = match &self.result {
+            UploadStatus::Ok(_) => StatusCode::OK,
+            UploadStatus::Error(error) => error.status_code(),
+        };
+        warp::reply::with_status(warp::reply::json(&self), status).into_response()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum UploadStatus<T> {
+    Ok(T),
+    Error(UploadError),
+}
+
+impl<T> From<anyhow::Result<T>> for UploadStatus<T> {
+    fn from(result: anyhow::Result<T>) -> Self {
+        match result {
+            Ok(value) => Self::Ok(value),
+            Err(e) => match e.downcast::<InvalidListing>() {
+                Ok(invalid) => Self::Error(invalid.into()),
+                Err(_) => Self::Error(UploadError::DatabaseError),
+            },
+        }
+    }
+}
+
+/// 업로드 실패 이유
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum UploadError {
+    /// 검증 실패 (`400`)
+    InvalidListing { problems: Vec<ListingProblem> },
+    /// 저장 실패 (`500`)
+    DatabaseError,
+}
+
+impl UploadError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidListing { .. } => StatusCode::BAD_REQUEST,
+            Self::DatabaseError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl From<InvalidListing> for UploadError {
+    fn from(invalid: InvalidListing) -> Self {
+        Self::InvalidListing { problems: invalid.problems }
+    }
+}
+
+/// `/contribute` 결과
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ListingUploaded {
+    pub outcome: UploadOutcome,
+}
+
+/// `/contribute/multiple` 결과 (모집글 일부가 실패해도 `ok`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ListingsUploaded {
+    /// 저장한 모집글 수
+    pub updated: usize,
+    pub total: usize,
+    /// 스냅샷에서 빠져 확인되지 않은 것으로 기록한 모집글 수 (스냅샷 업로드만)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unconfirmed: Option<u64>,
+    /// 보낸 순서대로 모집글별 결과
+    pub results: Vec<ListingResult>,
+}
+
+/// 일괄 업로드의 모집글 하나 결과
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ListingResult {
+    pub id: u32,
+    #[serde(flatten)]
+    pub result: UploadStatus<ListingUploaded>,
+}
+
+/// `/contribute/players` 결과
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayersUploaded {
+    pub updated: usize,
+    pub total: usize,
+}
+
+/// `/contribute/detail` 결과 (`matched == 0`이면 아직 저장되지 않았거나 이미 만료된 모집글)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DetailUploaded {
+    pub matched: u64,
+    pub modified: u64,
+    /// 파티장 플레이어 저장 결과 (모집글 갱신과 따로 실패할 수 있음)
+    pub leader: LeaderUpsert,
+}
+
+/// `/contribute/detail`의 파티장 플레이어 저장 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderUpsert {
+    Saved,
+    /// 파티장 정보가 비었거나 월드가 잘못됨
+    Skipped,
+    /// 데이터베이스 오류
+    Failed,
+}
@@ -0,0 +1,246 @@
+//! 데이터 센터별 모집글 수 급감 감지
+//!
+//! 업로더 플러그인이 조용히 고장 나면 한 데이터 센터의 모집글이 한 시간에 걸쳐 사라지는데,
+//! 사용자 제보 전까지는 알기 어렵습니다. 5분마다 데이터 센터별 활성 모집글 수를 기록하고,
+//! 같은 시간대(UTC 시)의 이전 날짜 평균(EWMA)보다 크게 적은 상태가 두 번 연속이면 경고합니다.
+//! 밤 시간대처럼 원래 적은 시간대는 그 시간대의 평균과 비교하므로 경고하지 않습니다.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Timelike, Utc};
+use serde::Serialize;
+
+use crate::config::VolumeAlerts;
+
+/// 모집글 수 기록 간격
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// 경고까지 필요한 연속 저조 횟수
+pub const LOW_SAMPLES_TO_FLAG: u32 = 2;
+
+/// 상태 변화 (경고 / 해제)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VolumeEvent {
+    pub data_centre: &'static str,
+    pub dropped: bool,
+    pub count: u64,
+    /// 같은 시간대 평균
+    pub baseline: f64,
+}
+
+/// `/api/health`에 표시하는 경고 중인 데이터 센터
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VolumeAlert {
+    pub data_centre: &'static str,
+    pub count: u64,
+    pub baseline: f64,
+    pub since: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct DataCentreVolume {
+    /// UTC 시간대별 평균 모집글 수 (이전 날짜 기준)
+    hourly: [Option<f64>; 24],
+    /// 진행 중인 시간의 (시작 시각, 합계, 기록 수)
+    current_hour: Option<(DateTime<Utc>, u64, u32)>,
+    low_samples: u32,
+    flagged_since: Option<DateTime<Utc>>,
+    last_count: u64,
+}
+
+impl DataCentreVolume {
+    /// 끝난 시간의 평균을 해당 시간대 EWMA에 반영
+    fn roll_hour(&mut self, hour_start: DateTime<Utc>, alpha: f64) {
+        match self.current_hour {
+            Some((start, _, _)) if start == hour_start => return,
+            Some((start, sum, samples)) if samples > 0 => {
+                let mean = sum as f64 / f64::from(samples);
+                let slot = &mut self.hourly[start.hour() as usize];
+                *slot = Some(match *slot {
+                    Some(previous) => alpha * mean + (1.0 - alpha) * previous,
+                    None => mean,
+                });
+            }
+            _ => {}
+        }
+        self.current_hour = Some((hour_start, 0, 0));
+    }
+}
+
+/// 데이터 센터별 시간대 평균과 경고 상태
+#[derive(Debug)]
+pub struct VolumeDetector {
+    config: VolumeAlerts,
+    data_centres: HashMap<&'static str, DataCentreVolume>,
+}
+
+impl VolumeDetector {
+    pub fn new(config: VolumeAlerts) -> Self {
+        Self {
+            config,
+            data_centres: HashMap::new(),
+        }
+    }
+
+    /// 한 번의 기록 반영 (한 번이라도 본 데이터 센터가 목록에 없으면 0개로 기록)
+    pub fn observe(&mut self, counts: &HashMap<&'static str, u64>, now: DateTime<Utc>) -> Vec<VolumeEvent> {
+        for &data_centre in counts.keys() {
+            self.data_centres.entry(data_centre).or_default();
+        }
+
+        let hour_start = now
+            .with_minute(0)
+            .and_then(|t| t.with_second(0))
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(now);
+        let config = &self.config;
+        let mut events = Vec::new();
+
+        for (&data_centre, volume) in &mut self.data_centres {
+            let count = counts.get(data_centre).copied().unwrap_or_default();
+            volume.roll_hour(hour_start, config.alpha);
+            volume.last_count = count;
+
+            let baseline = volume.hourly[now.hour() as usize].filter(|&baseline| baseline >= config.min_baseline);
+            let Some(baseline) = baseline else {
+                // 이 시간대의 평균이 아직 없거나 원래 적은 시간대
+                volume.low_samples = 0;
+                if volume.flagged_since.take().is_some() {
+                    events.push(VolumeEvent { data_centre, dropped: false, count, baseline: 0.0 });
+                }
+                record(volume, count);
+                continue;
+            };
+
+            let ratio = count as f64 / baseline;
+            if volume.flagged_since.is_some() {
+                if ratio >= config.recover_ratio {
+                    volume.flagged_since = None;
+                    volume.low_samples = 0;
+                    events.push(VolumeEvent { data_centre, dropped: false, count, baseline });
+                    record(volume, count);
+                }
+                // 경고 중인 값은 평균에 반영하지 않음 (장애를 정상으로 학습하지 않도록)
+                continue;
+            }
+
+            if ratio < config.drop_ratio {
+                volume.low_samples += 1;
+                if volume.low_samples >= LOW_SAMPLES_TO_FLAG {
+                    volume.flagged_since = Some(now);
+                    events.push(VolumeEvent { data_centre, dropped: true, count, baseline });
+                }
+                continue;
+            }
+
+            volume.low_samples = 0;
+            record(volume, count);
+        }
+
+        events.sort_by_key(|event| event.data_centre);
+        events
+    }
+
+    /// 마지막 기록의 데이터 센터별 모집글 수
+    pub fn counts(&self) -> HashMap<&'static str, u64> {
+        self.data_centres
+            .iter()
+            .map(|(&data_centre, volume)| (data_centre, volume.last_count))
+            .collect()
+    }
+
+    /// 경고 중인 데이터 센터 (이름순)
+    pub fn alerts(&self) -> Vec<VolumeAlert> {
+        let mut alerts: Vec<VolumeAlert> = self
+            .data_centres
+            .iter()
+            .filter_map(|(&data_centre, volume)| {
+                let since = volume.flagged_since?;
+                Some(VolumeAlert {
+                    data_centre,
+                    count: volume.last_count,
+                    baseline: volume.hourly[since.hour() as usize].unwrap_or_default(),
+                    since,
+                })
+            })
+            .collect();
+        alerts.sort_by_key(|alert| alert.data_centre);
+        alerts
+    }
+}
+
+fn record(volume: &mut DataCentreVolume, count: u64) {
+    if let Some((_, sum, samples)) = &mut volume.current_hour {
+        *sum += count;
+        *samples += 1;
+    }
+}
+
+/// `State`에서 공유하는 감지기
+pub struct VolumeMonitor {
+    detector: Mutex<VolumeDetector>,
+    webhook: Option<String>,
+}
+
+impl VolumeMonitor {
+    pub fn new(config: VolumeAlerts) -> Self {
+        Self {
+            webhook: config.webhook.clone(),
+            detector: Mutex::new(VolumeDetector::new(config)),
+        }
+    }
+
+    /// 기록을 반영하고 경고 / 해제를 로그와 웹훅으로 알림
+    pub async fn observe(&self, counts: &HashMap<&'static str, u64>, now: DateTime<Utc>) {
+        let events = self.detector.lock().unwrap().observe(counts, now);
+
+        for event in &events {
+            if event.dropped {
+                tracing::warn!(
+                    "listing volume for {} dropped to {} (usual {:.1} at this hour), uploaders may be broken",
+                    event.data_centre,
+                    event.count,
+                    event.baseline
+                );
+            } else {
+                tracing::info!("listing volume for {} recovered ({})", event.data_centre, event.count);
+            }
+
+            if let Some(url) = &self.webhook {
+                if let Err(e) = send_webhook(url, event).await {
+                    tracing::warn!("could not send volume alert webhook: {:#}", e);
+                }
+            }
+        }
+    }
+
+    pub fn alerts(&self) -> Vec<VolumeAlert> {
+        self.detector.lock().unwrap().alerts()
+    }
+
+    pub fn counts(&self) -> HashMap<&'static str, u64> {
+        self.detector.lock().unwrap().counts()
+    }
+}
+
+async fn send_webhook(url: &str, event: &VolumeEvent) -> anyhow::Result<()> {
+    let text = if event.dropped {
+        format!(
+            "Party Finder listings on {} dropped to {} (usually {:.0} at this hour)",
+            event.data_centre, event.count, event.baseline
+        )
+    } else {
+        format!("Party Finder listings on {} recovered ({})", event.data_centre, event.count)
+    };
+
+    reqwest::Client::new()
+        .post(url)
+        .timeout(Duration::from_secs(10))
+        .json(&serde_json::json!({ "text": text, "event": event }))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
@@ -1,13 +1,25 @@
-use crate::listing::PartyFinderListing;
+use crate::listing::filter::category_by_name;
+use crate::listing::{ListingFilter, PartyFinderListing};
+use crate::listing_container::QueriedListing;
+use crate::web::listing_events::{ListingEvent, ListingRef};
 use crate::web::State;
-use futures_util::stream::{SplitSink, SplitStream};
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::task::{AbortHandle, JoinHandle};
 use warp::ws::{Message, WebSocket};
 
+use self::keepalive::{Keepalive, CLOSE_TIMEOUT};
+use self::limits::{close_code, ConnectionPermit, Refusal};
+
+pub mod keepalive;
+pub mod limits;
+
 pub struct WsApiClient {
     state: Arc<State>,
     outbound: UnboundedSender<OutboundApiMessage>,
@@ -17,47 +29,100 @@ pub struct WsApiClient {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
-enum InboundApiMessage {
-    Subscribe { channel: MessageChannel },
+pub(crate) enum InboundApiMessage {
+    /// Subscribing again replaces the filter and starts over with a new snapshot
+    Subscribe {
+        channel: MessageChannel,
+        #[serde(flatten)]
+        filter: SubscriptionFilter,
+    },
     Unsubscribe { channel: MessageChannel },
 }
 
+/// Which listings a subscription receives (every list empty means every listing)
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub(crate) struct SubscriptionFilter {
+    /// Normal duty IDs
+    duties: Vec<u16>,
+    /// World IDs the listings were created on
+    created_worlds: Vec<u16>,
+    /// Category names such as `HighEndDuty`
+    categories: Vec<String>,
+}
+
+impl SubscriptionFilter {
+    pub(crate) fn into_filter(self) -> Result<ListingFilter, String> {
+        let categories = self
+            .categories
+            .iter()
+            .map(|name| category_by_name(name).ok_or_else(|| format!("unknown category `{}`", name)))
+            .collect::<Result<_, _>>()?;
+        Ok(ListingFilter {
+            duties: self.duties,
+            worlds: self.created_worlds,
+            categories,
+            flagged: false,
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
-enum OutboundApiMessage {
+pub(crate) enum OutboundApiMessage {
     Subscribed { channel: MessageChannel },
     Unsubscribed { channel: MessageChannel },
-    Listings { listings: Arc<[PartyFinderListing]> },
+    /// Every current listing, sent once right after subscribing to the listings channel
+    Snapshot { listings: Arc<[PartyFinderListing]> },
+    /// Listings uploaded after the snapshot was taken
+    Update { listings: Arc<[PartyFinderListing]> },
+    /// Listings that left the current list (expired, dropped from a snapshot upload or hidden)
+    Removed { listings: Arc<[ListingRef]> },
+    /// The subscription fell behind and lost `missed` changes; a fresh snapshot follows
+    Lagged { missed: u64 },
     Err { message: String },
 }
 
 #[derive(Serialize, Deserialize, Copy, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
-enum MessageChannel {
+pub(crate) enum MessageChannel {
     Listings,
 }
 
 impl WsApiClient {
     async fn handle(&mut self, msg: InboundApiMessage) {
         match msg {
-            InboundApiMessage::Subscribe { channel } => {
+            InboundApiMessage::Subscribe { channel, filter } => {
+                // a bad filter leaves the current subscription as it is
+                let filter = match filter.into_filter() {
+                    Ok(filter) => filter,
+                    Err(message) => {
+                        let _ = self.outbound.send(OutboundApiMessage::Err { message });
+                        return;
+                    }
+                };
+
                 match channel {
                     MessageChannel::Listings => {
+                        // subscribe before taking the snapshot so nothing uploaded in between is lost
+                        let receiver = self.state.listings_channel.subscribe();
+
+                        // send a message letting the client know they've been subscribed
+                        self.outbound
+                            .send(OutboundApiMessage::Subscribed { channel })
+                            .unwrap();
+
+                        let state = self.state.clone();
+                        let snapshot = move || {
+                            let state = state.clone();
+                            async move { state.current_listings().await }
+                        };
                         self.listings = Some(
-                            tokio::spawn(Self::listings_task(
-                                self.state.clone(),
-                                self.outbound.clone(),
-                            ))
-                            .into(),
-                        )
+                            tokio::spawn(stream_listings(receiver, snapshot, filter, self.outbound.clone())).into(),
+                        );
                     }
                 };
-
-                // send a message letting the client know they've been subscribed
-                self.outbound
-                    .send(OutboundApiMessage::Subscribed { channel })
-                    .unwrap()
             }
             InboundApiMessage::Unsubscribe { channel } => {
                 match channel {
@@ -74,9 +139,24 @@ impl WsApiClient {
         }
     }
 
-    pub async fn run(state: Arc<State>, web_socket: WebSocket) {
+    pub async fn run(state: Arc<State>, web_socket: WebSocket, permit: ConnectionPermit) {
+        let keepalive = Keepalive::from_config(&state.config.websocket);
+        let (ws_sender, ws_receiver) = web_socket.split();
+        Self::serve(state, ws_sender, ws_receiver, permit, keepalive).await;
+    }
+
+    /// Runs one connection until it closes, fails, is shed or stops answering pings.
+    pub(crate) async fn serve<S, R>(
+        state: Arc<State>,
+        mut ws_sender: S,
+        mut ws_receiver: R,
+        permit: ConnectionPermit,
+        keepalive: Keepalive,
+    ) where
+        S: Sink<Message> + Unpin,
+        R: Stream<Item = Result<Message, warp::Error>> + Unpin,
+    {
         let (outbound_sender, mut outbound_receiver) = tokio::sync::mpsc::unbounded_channel();
-        let (mut ws_sender, mut ws_receiver) = web_socket.split();
 
         let mut client = Self {
             state,
@@ -84,35 +164,77 @@ impl WsApiClient {
             listings: None,
         };
 
-        let send_task = Self::send_task(&mut outbound_receiver, &mut ws_sender);
-        let recv_task = Self::recv_task(&mut ws_receiver, &mut client);
+        let mut timed_out = false;
+        {
+            let send_task = Self::send_task(&mut outbound_receiver, &mut ws_sender, &keepalive);
+            let recv_task = Self::recv_task(&mut ws_receiver, &mut client, &permit, &keepalive);
+
+            // run either send or recv to completion;
+            // either exiting is fatal to the ws client.
+            tokio::select! {
+                _ = send_task => (),
+                _ = recv_task => (),
+                _ = permit.shed() => (),
+                _ = keepalive.timed_out() => timed_out = true,
+            }
+        }
+
+        // shed to make room for new connections under sustained load
+        if permit.is_shed() {
+            let _ = ws_sender.send(Message::close_with(close_code::SERVER_BUSY, Refusal::Busy.reason())).await;
+        }
 
-        // run either send or recv to completion;
-        // either exiting is fatal to the ws client.
-        tokio::select! {
-            _ = send_task => (),
-            _ = recv_task => (),
+        // the peer is most likely gone, so don't wait long for the close frame to go out
+        if timed_out {
+            permit.timed_out();
+            tracing::debug!("closing a websocket connection that stopped answering pings");
+            let close = Message::close_with(close_code::PING_TIMEOUT, "ping timeout");
+            let _ = tokio::time::timeout(CLOSE_TIMEOUT, ws_sender.send(close)).await;
         }
     }
 
-    async fn send_task(
+    /// Sends a close frame to a connection that was refused by the connection limits.
+    pub async fn refuse(mut web_socket: WebSocket, refusal: Refusal) {
+        let _ = web_socket.send(Message::close_with(refusal.close_code(), refusal.reason())).await;
+        let _ = web_socket.close().await;
+    }
+
+    async fn send_task<S: Sink<Message> + Unpin>(
         outbound_receiver: &mut UnboundedReceiver<OutboundApiMessage>,
-        ws_sender: &mut SplitSink<WebSocket, Message>,
+        ws_sender: &mut S,
+        keepalive: &Keepalive,
     ) {
-        while let Some(msg) = outbound_receiver.recv().await {
-            let Ok(json) = serde_json::to_string(&msg) else {
-                tracing::warn!("failed to serialize outbound message: {:#?}", msg);
-                continue;
+        loop {
+            let message = tokio::select! {
+                msg = outbound_receiver.recv() => {
+                    let Some(msg) = msg else {
+                        break;
+                    };
+                    let Ok(json) = serde_json::to_string(&msg) else {
+                        tracing::warn!("failed to serialize outbound message: {:#?}", msg);
+                        continue;
+                    };
+                    Message::text(json)
+                }
+                _ = keepalive.ping_due() => Message::ping(Vec::new()),
             };
 
-            if ws_sender.send(Message::text(json)).await.is_err() {
+            if ws_sender.send(message).await.is_err() {
                 break; // can't send. fatal. die
             }
         }
     }
 
-    async fn recv_task(ws_receiver: &mut SplitStream<WebSocket>, client: &mut WsApiClient) {
+    async fn recv_task<R: Stream<Item = Result<Message, warp::Error>> + Unpin>(
+        ws_receiver: &mut R,
+        client: &mut WsApiClient,
+        permit: &ConnectionPermit,
+        keepalive: &Keepalive,
+    ) {
         while let Some(Ok(msg)) = ws_receiver.next().await {
+            permit.touch(Instant::now());
+            // any frame (pongs included) shows the connection is alive
+            keepalive.received();
             // give up if there's an error (as far as I can tell they're fatal anyway)
             if let Ok(msg) = msg.to_str() {
                 // only a close message has no to_str
@@ -129,14 +251,99 @@ impl WsApiClient {
             }
         }
     }
+}
 
-    async fn listings_task(state: Arc<State>, sender: UnboundedSender<OutboundApiMessage>) {
-        let mut receiver = state.listings_channel.subscribe();
+/// Sends the snapshot, then every update buffered in `receiver` since it subscribed.
+///
+/// Listings uploaded while the snapshot was loading may appear in both; clients replace
+/// listings by key, so a repeat is harmless where a gap would not be.
+///
+/// Only listings matching `filter` are sent. Removals only carry the listing key, so they are
+/// filtered by created world alone.
+///
+/// A subscriber that falls behind the broadcast channel has missed updates and removals it
+/// can't get back, so it is told how many it lost and sent a fresh snapshot from `snapshot`.
+pub(crate) async fn stream_listings<F, Fut>(
+    mut receiver: Receiver<ListingEvent>,
+    mut snapshot: F,
+    filter: ListingFilter,
+    sender: UnboundedSender<OutboundApiMessage>,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<Arc<Vec<QueriedListing>>>>,
+{
+    if sender.send(snapshot_message(snapshot().await, &filter)).is_err() {
+        return;
+    }
+
+    let mut missed_total = 0;
+    loop {
+        let message = match receiver.recv().await {
+            Ok(ListingEvent::Updated(listings)) => {
+                let listings = retain(listings, |listing| filter.matches(listing));
+                if listings.is_empty() {
+                    continue;
+                }
+                OutboundApiMessage::Update { listings }
+            }
+            Ok(ListingEvent::Removed(listings)) => {
+                let listings = retain(listings, |listing| filter.matches_world(listing.created_world));
+                if listings.is_empty() {
+                    continue;
+                }
+                OutboundApiMessage::Removed { listings }
+            }
+            Err(RecvError::Lagged(missed)) => {
+                missed_total += missed;
+                tracing::warn!(
+                    "websocket subscriber lagged behind by {} events ({} in total), resending the snapshot",
+                    missed,
+                    missed_total
+                );
+                if sender.send(OutboundApiMessage::Lagged { missed }).is_err() {
+                    break;
+                }
+                snapshot_message(snapshot().await, &filter)
+            }
+            Err(RecvError::Closed) => break,
+        };
+        if sender.send(message).is_err() {
+            break;
+        }
+    }
+}
 
-        while let Ok(listings) = receiver.recv().await {
-            let _ = sender.send(OutboundApiMessage::Listings { listings });
+/// The `snapshot` message for the current listings matching `filter`
+fn snapshot_message(current: anyhow::Result<Arc<Vec<QueriedListing>>>, filter: &ListingFilter) -> OutboundApiMessage {
+    match current {
+        Ok(current) => {
+            // same slot rule as the updates (`publish_listings`)
+            let listings: Vec<PartyFinderListing> = current
+                .iter()
+                .filter(|container| filter.matches(&container.listing))
+                .map(|container| {
+                    let mut listing = container.listing.clone();
+                    listing.clear_duplicate_jobs();
+                    listing
+                })
+                .collect();
+            OutboundApiMessage::Snapshot { listings: listings.into() }
         }
+        Err(e) => {
+            tracing::warn!("could not load the websocket snapshot: {:#}", e);
+            OutboundApiMessage::Err {
+                message: "could not load current listings".to_string(),
+            }
+        }
+    }
+}
+
+/// The items of a broadcast matching `keep` (the shared slice itself when every item does)
+fn retain<T: Clone>(items: Arc<[T]>, keep: impl Fn(&T) -> bool) -> Arc<[T]> {
+    if items.iter().all(&keep) {
+        return items;
     }
+    items.iter().filter(|item| keep(item)).cloned().collect()
 }
 
 /// A handle to a tokio task that aborts the task when dropped.
@@ -0,0 +1,64 @@
+//! 웹소켓 연결 유지 확인
+//!
+//! 일부 프록시를 거친 연결은 끊겨도 알리지 않아 전송 대상에 계속 남으므로
+//! 서버가 주기적으로 ping을 보내고, 응답 없는 ping이 `max_missed_pongs`개 쌓이면 연결을 끊습니다.
+//! pong이 아니어도 클라이언트에게서 받은 프레임이 있으면 살아 있는 연결로 봅니다.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use tokio::sync::Notify;
+use tokio::time::MissedTickBehavior;
+
+use crate::config::Websocket as WebsocketConfig;
+
+/// 끊기 전에 close 프레임 전송을 기다리는 시간 (죽은 연결은 보내지 못함)
+pub const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 연결 하나의 ping 주기와 응답 없는 ping 수
+pub struct Keepalive {
+    interval: Duration,
+    max_missed: u32,
+    missed: AtomicU32,
+    ping: Notify,
+}
+
+impl Keepalive {
+    pub fn new(interval: Duration, max_missed: u32) -> Self {
+        Self {
+            interval,
+            max_missed: max_missed.max(1),
+            missed: AtomicU32::new(0),
+            ping: Notify::new(),
+        }
+    }
+
+    pub fn from_config(config: &WebsocketConfig) -> Self {
+        Self::new(Duration::from_secs(config.ping_interval_secs.max(1)), config.max_missed_pongs)
+    }
+
+    /// 클라이언트에게서 프레임을 받음
+    pub fn received(&self) {
+        self.missed.store(0, Ordering::Relaxed);
+    }
+
+    /// ping을 보낼 차례까지 대기 (전송 작업용)
+    pub async fn ping_due(&self) {
+        self.ping.notified().await;
+    }
+
+    /// 주기마다 ping을 요청하고, 응답 없는 ping이 `max_missed`개 쌓이면 끝남
+    ///
+    /// 전송이 막힌 연결도 ping을 보내지 못한 채 주기가 지나므로 같이 끊깁니다.
+    pub async fn timed_out(&self) {
+        let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + self.interval, self.interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if self.missed.fetch_add(1, Ordering::Relaxed) >= self.max_missed {
+                return;
+            }
+            self.ping.notify_one();
+        }
+    }
+}
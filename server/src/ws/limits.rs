@@ -0,0 +1,283 @@
+//! 웹소켓 연결 수 제한
+//!
+//! 패치 당일 저녁처럼 익명 연결이 몰리면 전송 작업이 사이트 응답을 밀어내므로
+//! 익명 연결은 전체 / IP별 최대 개수를 넘으면 받지 않습니다.
+//! 알려진 토큰을 제시한 우선 연결은 제한에서 빠지고 끊기지도 않습니다.
+//!
+//! 전체 제한에 걸린 상태가 `SUSTAINED_PRESSURE` 이상 이어지면
+//! 가장 오래 메시지가 없던 익명 연결부터 `SHED_FRACTION`만큼 끊어 새 연결에 자리를 내줍니다.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::watch;
+
+use crate::config::Websocket as WebsocketConfig;
+use crate::web::token_compare::tokens_match;
+
+/// 전체 제한에 걸린 상태가 이 시간 이상 이어지면 익명 연결을 끊기 시작
+pub const SUSTAINED_PRESSURE: Duration = Duration::from_secs(30);
+
+/// 한 번에 끊는 익명 연결 비율 (전체 제한 기준, 최소 1개)
+pub const SHED_FRACTION: f64 = 0.05;
+
+/// 익명 연결이 전체 제한의 이 비율 아래로 내려가면 과부하 상태 해제
+const PRESSURE_RELEASE_RATIO: f64 = 0.9;
+
+/// 클라이언트에게 보내는 close 코드
+pub mod close_code {
+    /// 서버가 바쁨 (RFC 6455 `Try Again Later`)
+    pub const SERVER_BUSY: u16 = 1013;
+    /// IP별 연결 수 초과 (`Policy Violation`)
+    pub const TOO_MANY_FROM_ADDRESS: u16 = 1008;
+    /// ping에 응답 없음 (`Going Away`)
+    pub const PING_TIMEOUT: u16 = 1001;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tier {
+    Anonymous,
+    /// 알려진 토큰을 제시한 연결 (제한 / 끊기 제외)
+    Priority,
+}
+
+/// 연결을 받지 않은 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Refusal {
+    /// 전체 익명 연결 수 초과
+    Busy,
+    /// 같은 주소의 익명 연결 수 초과
+    TooManyFromAddress,
+}
+
+impl Refusal {
+    pub fn close_code(self) -> u16 {
+        match self {
+            Self::Busy => close_code::SERVER_BUSY,
+            Self::TooManyFromAddress => close_code::TOO_MANY_FROM_ADDRESS,
+        }
+    }
+
+    pub fn reason(self) -> &'static str {
+        match self {
+            Self::Busy => "server busy",
+            Self::TooManyFromAddress => "too many connections from this address",
+        }
+    }
+}
+
+/// `/api/health`에 표시하는 연결 수
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ConnectionStats {
+    pub anonymous: usize,
+    pub priority: usize,
+    pub refused_busy: u64,
+    pub refused_per_address: u64,
+    pub shed: u64,
+    /// ping에 응답이 없어 끊은 연결 수
+    pub timed_out: u64,
+}
+
+struct Connection {
+    tier: Tier,
+    address: Option<IpAddr>,
+    /// 연결 또는 마지막 수신 메시지 시각
+    last_active: Instant,
+    shed: watch::Sender<bool>,
+}
+
+#[derive(Default)]
+struct Registry {
+    next_id: u64,
+    connections: HashMap<u64, Connection>,
+    per_address: HashMap<IpAddr, usize>,
+    /// 전체 제한에 처음 걸린 시각 (해제되면 `None`)
+    busy_since: Option<Instant>,
+    stats: ConnectionStats,
+}
+
+impl Registry {
+    fn remove(&mut self, id: u64) -> Option<Connection> {
+        let connection = self.connections.remove(&id)?;
+        match connection.tier {
+            Tier::Anonymous => self.stats.anonymous -= 1,
+            Tier::Priority => self.stats.priority -= 1,
+        }
+        if let Some(address) = connection.address.filter(|_| connection.tier == Tier::Anonymous) {
+            if let Some(count) = self.per_address.get_mut(&address) {
+                *count -= 1;
+                if *count == 0 {
+                    self.per_address.remove(&address);
+                }
+            }
+        }
+        Some(connection)
+    }
+}
+
+/// `State`에서 공유하는 연결 관리자
+pub struct ConnectionLimits {
+    max_connections: usize,
+    max_per_address: usize,
+    priority_tokens: Vec<String>,
+    registry: Mutex<Registry>,
+}
+
+impl ConnectionLimits {
+    pub fn new(config: &WebsocketConfig, priority_tokens: Vec<String>) -> Self {
+        Self {
+            max_connections: config.max_connections,
+            max_per_address: config.max_per_address,
+            priority_tokens: priority_tokens.into_iter().filter(|token| !token.is_empty()).collect(),
+            registry: Default::default(),
+        }
+    }
+
+    /// 제시한 토큰으로 등급 결정
+    pub fn tier(&self, token: Option<&str>) -> Tier {
+        match token {
+            Some(token) if self.priority_tokens.iter().any(|known| tokens_match(known, token)) => Tier::Priority,
+            _ => Tier::Anonymous,
+        }
+    }
+
+    /// 새 연결 등록 (연결이 끝나면 `ConnectionPermit`을 버려 해제)
+    ///
+    /// 과부하가 `SUSTAINED_PRESSURE` 이상 이어졌으면 익명 연결 일부를 끊고 그 자리에 받습니다.
+    pub fn admit(self: &Arc<Self>, tier: Tier, address: Option<IpAddr>, now: Instant) -> Result<ConnectionPermit, Refusal> {
+        let mut registry = self.registry.lock().unwrap();
+
+        if tier == Tier::Anonymous {
+            let from_address = address.and_then(|address| registry.per_address.get(&address).copied()).unwrap_or(0);
+            if from_address >= self.max_per_address {
+                registry.stats.refused_per_address += 1;
+                return Err(Refusal::TooManyFromAddress);
+            }
+
+            if registry.stats.anonymous >= self.max_connections {
+                let busy_since = *registry.busy_since.get_or_insert(now);
+                if now.saturating_duration_since(busy_since) < SUSTAINED_PRESSURE || self.shed(&mut registry, now) == 0 {
+                    registry.stats.refused_busy += 1;
+                    return Err(Refusal::Busy);
+                }
+            }
+        }
+
+        let id = registry.next_id;
+        registry.next_id += 1;
+        let (shed, shed_signal) = watch::channel(false);
+        registry.connections.insert(id, Connection { tier, address, last_active: now, shed });
+        match tier {
+            Tier::Anonymous => {
+                registry.stats.anonymous += 1;
+                if let Some(address) = address {
+                    *registry.per_address.entry(address).or_default() += 1;
+                }
+            }
+            Tier::Priority => registry.stats.priority += 1,
+        }
+
+        Ok(ConnectionPermit {
+            id,
+            tier,
+            limits: Arc::clone(self),
+            shed_signal,
+        })
+    }
+
+    /// 가장 오래 메시지가 없던 익명 연결부터 끊고 끊은 수 반환
+    fn shed(&self, registry: &mut Registry, now: Instant) -> usize {
+        let count = ((self.max_connections as f64 * SHED_FRACTION).ceil() as usize).max(1);
+
+        let mut idle: Vec<(Instant, u64)> = registry
+            .connections
+            .iter()
+            .filter(|(_, connection)| connection.tier == Tier::Anonymous)
+            .map(|(&id, connection)| (connection.last_active, id))
+            .collect();
+        idle.sort_unstable();
+
+        let mut shed = 0;
+        for (_, id) in idle.into_iter().take(count) {
+            if let Some(connection) = registry.remove(id) {
+                let _ = connection.shed.send(true);
+                shed += 1;
+            }
+        }
+
+        // 다시 끊기까지 `SUSTAINED_PRESSURE`만큼 기다림
+        registry.busy_since = Some(now);
+        if shed > 0 {
+            registry.stats.shed += shed as u64;
+            tracing::warn!("websocket connections at capacity for {:?}, shed {} idle anonymous connections", SUSTAINED_PRESSURE, shed);
+        }
+        shed
+    }
+
+    fn release(&self, id: u64) {
+        let mut registry = self.registry.lock().unwrap();
+        registry.remove(id);
+        if (registry.stats.anonymous as f64) < self.max_connections as f64 * PRESSURE_RELEASE_RATIO {
+            registry.busy_since = None;
+        }
+    }
+
+    fn touch(&self, id: u64, now: Instant) {
+        if let Some(connection) = self.registry.lock().unwrap().connections.get_mut(&id) {
+            connection.last_active = now;
+        }
+    }
+
+    fn record_timeout(&self) {
+        self.registry.lock().unwrap().stats.timed_out += 1;
+    }
+
+    pub fn stats(&self) -> ConnectionStats {
+        self.registry.lock().unwrap().stats
+    }
+}
+
+/// 등록된 연결 (버리면 해제)
+pub struct ConnectionPermit {
+    id: u64,
+    tier: Tier,
+    limits: Arc<ConnectionLimits>,
+    shed_signal: watch::Receiver<bool>,
+}
+
+impl ConnectionPermit {
+    pub fn tier(&self) -> Tier {
+        self.tier
+    }
+
+    /// 메시지를 받은 시각 기록 (끊을 연결을 고를 때 사용)
+    pub fn touch(&self, now: Instant) {
+        self.limits.touch(self.id, now);
+    }
+
+    /// ping에 응답이 없어 끊음
+    pub fn timed_out(&self) {
+        self.limits.record_timeout();
+    }
+
+    /// 과부하로 끊기로 했는지
+    pub fn is_shed(&self) -> bool {
+        *self.shed_signal.borrow()
+    }
+
+    /// 끊기로 할 때까지 대기
+    pub async fn shed(&self) {
+        let mut signal = self.shed_signal.clone();
+        let _ = signal.wait_for(|shed| *shed).await;
+    }
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        self.limits.release(self.id);
+    }
+}